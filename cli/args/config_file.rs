@@ -312,6 +312,7 @@ impl SerializedFilesConfig {
         .into_iter()
         .map(|p| config_dir.join(p))
         .collect::<Vec<_>>(),
+      no_ignore: false,
     })
   }
 
@@ -324,6 +325,8 @@ impl SerializedFilesConfig {
 pub struct FilesConfig {
   pub include: Vec<PathBuf>,
   pub exclude: Vec<PathBuf>,
+  /// Don't use `.denoignore` files when collecting the files.
+  pub no_ignore: bool,
 }
 
 impl FilesConfig {
@@ -348,6 +351,7 @@ impl FilesConfig {
     Self {
       include: [self.include, rhs.include].concat(),
       exclude: [self.exclude, rhs.exclude].concat(),
+      no_ignore: self.no_ignore || rhs.no_ignore,
     }
   }
 }
@@ -591,6 +595,15 @@ impl SerializedTestConfig {
   }
 }
 
+/// `watch` config representation, listing the tasks or commands to run
+/// around restarts triggered by the file watcher.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct WatchConfig {
+  pub pre_restart: Vec<String>,
+  pub post_restart: Vec<String>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct TestConfig {
   pub files: FilesConfig,
@@ -642,6 +655,62 @@ impl BenchConfig {
   }
 }
 
+/// `licenses` config representation, used by `deno info --licenses` to
+/// flag dependencies with unknown or unwanted licenses.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LicensesConfig {
+  /// SPDX identifiers of the licenses that are allowed. An empty list means
+  /// that every license not explicitly denied is allowed.
+  pub allow: Vec<String>,
+  /// SPDX identifiers of the licenses that are denied.
+  pub deny: Vec<String>,
+}
+
+/// `check` config representation for serde
+///
+/// diagnostic codes are given like `"TS2345"`, or just `"2345"`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+struct SerializedCheckConfig {
+  pub error_limit: Option<usize>,
+  pub warn_only: Vec<String>,
+}
+
+impl SerializedCheckConfig {
+  pub fn into_resolved(self) -> Result<CheckConfig, AnyError> {
+    if self.error_limit == Some(0) {
+      bail!("\"errorLimit\" must be at least 1");
+    }
+    let warn_only = self
+      .warn_only
+      .iter()
+      .map(|code| {
+        code
+          .strip_prefix("TS")
+          .unwrap_or(code)
+          .parse::<u64>()
+          .map_err(|_| anyhow!("Invalid diagnostic code \"{}\"", code))
+      })
+      .collect::<Result<_, _>>()?;
+    Ok(CheckConfig {
+      error_limit: self.error_limit,
+      warn_only,
+    })
+  }
+}
+
+/// `check` config, which makes type checking less strict so that it can be
+/// adopted step by step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CheckConfig {
+  /// How many errors are reported at most.
+  pub error_limit: Option<usize>,
+  /// The codes of the diagnostics that are reported as warnings, which don't
+  /// fail the type check.
+  pub warn_only: HashSet<u64>,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(untagged)]
 pub enum LockConfig {
@@ -660,10 +729,15 @@ pub struct ConfigFileJson {
   pub fmt: Option<Value>,
   pub tasks: Option<Value>,
   pub test: Option<Value>,
+  pub watch: Option<Value>,
   pub bench: Option<Value>,
   pub lock: Option<Value>,
+  pub licenses: Option<Value>,
+  pub check: Option<Value>,
   pub exclude: Option<Value>,
   pub node_modules_dir: Option<bool>,
+  pub node_conditions: Option<Vec<String>>,
+  pub deno: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -859,6 +933,16 @@ impl ConfigFile {
     self.json.node_modules_dir
   }
 
+  pub fn node_conditions(&self) -> Option<Vec<String>> {
+    self.json.node_conditions.clone()
+  }
+
+  /// The requirement on the version of Deno running the project, e.g.
+  /// `">=1.34"`.
+  pub fn deno_version_req(&self) -> Option<&str> {
+    self.json.deno.as_deref()
+  }
+
   pub fn to_import_map_value(&self) -> Value {
     let mut value = serde_json::Map::with_capacity(2);
     if let Some(imports) = &self.json.imports {
@@ -1007,6 +1091,16 @@ impl ConfigFile {
     }
   }
 
+  pub fn to_watch_config(&self) -> Result<Option<WatchConfig>, AnyError> {
+    if let Some(config) = self.json.watch.clone() {
+      let watch_config: WatchConfig = serde_json::from_value(config)
+        .context("Failed to parse \"watch\" configuration")?;
+      Ok(Some(watch_config))
+    } else {
+      Ok(None)
+    }
+  }
+
   /// If the configuration file contains "extra" modules (like TypeScript
   /// `"types"`) options, return them as imports to be added to a module graph.
   pub fn to_maybe_imports(&self) -> MaybeImportsResult {
@@ -1079,6 +1173,26 @@ impl ConfigFile {
     }
   }
 
+  pub fn to_check_config(&self) -> Result<Option<CheckConfig>, AnyError> {
+    if let Some(config) = self.json.check.clone() {
+      let check_config: SerializedCheckConfig = serde_json::from_value(config)
+        .context("Failed to parse \"check\" configuration")?;
+      Ok(Some(check_config.into_resolved()?))
+    } else {
+      Ok(None)
+    }
+  }
+
+  pub fn to_licenses_config(&self) -> Result<Option<LicensesConfig>, AnyError> {
+    if let Some(config) = self.json.licenses.clone() {
+      let licenses_config: LicensesConfig = serde_json::from_value(config)
+        .context("Failed to parse \"licenses\" configuration")?;
+      Ok(Some(licenses_config))
+    } else {
+      Ok(None)
+    }
+  }
+
   pub fn resolve_lockfile_path(&self) -> Result<Option<PathBuf>, AnyError> {
     match self.to_lock_config()? {
       Some(LockConfig::Bool(lock)) if !lock => Ok(None),
@@ -1354,6 +1468,7 @@ mod tests {
         files: FilesConfig {
           include: vec![PathBuf::from("/deno/src/")],
           exclude: vec![PathBuf::from("/deno/src/testdata/")],
+          no_ignore: false,
         },
         rules: LintRulesConfig {
           include: Some(vec!["ban-untagged-todo".to_string()]),
@@ -1369,6 +1484,7 @@ mod tests {
         files: FilesConfig {
           include: vec![PathBuf::from("/deno/src/")],
           exclude: vec![PathBuf::from("/deno/src/testdata/")],
+          no_ignore: false,
         },
         options: FmtOptionsConfig {
           use_tabs: Some(true),
@@ -1423,6 +1539,7 @@ mod tests {
       FilesConfig {
         include: vec![PathBuf::from("/deno/src/")],
         exclude: vec![],
+        no_ignore: false,
       }
     );
 
@@ -1432,6 +1549,7 @@ mod tests {
       FilesConfig {
         exclude: vec![PathBuf::from("/deno/dist/")],
         include: vec![],
+        no_ignore: false,
       }
     );
 
@@ -1514,6 +1632,37 @@ mod tests {
     assert_eq!(fmt_options_deprecated.semi_colons, Some(true));
   }
 
+  #[test]
+  fn test_parse_check_config() {
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_text = r#"{
+      "check": {
+        "errorLimit": 10,
+        "warnOnly": ["TS2345", "2322"]
+      }
+    }"#;
+    let config_file =
+      ConfigFile::new(config_text, config_specifier.clone()).unwrap();
+    assert_eq!(
+      config_file.to_check_config().unwrap().unwrap(),
+      CheckConfig {
+        error_limit: Some(10),
+        warn_only: HashSet::from([2345, 2322]),
+      }
+    );
+
+    for config_text in [
+      r#"{ "check": { "errorLimit": 0 } }"#,
+      r#"{ "check": { "warnOnly": ["TSfoo"] } }"#,
+      r#"{ "check": { "unknown": true } }"#,
+    ] {
+      let config_file =
+        ConfigFile::new(config_text, config_specifier.clone()).unwrap();
+      assert!(config_file.to_check_config().is_err());
+    }
+  }
+
   #[test]
   fn test_parse_config_with_empty_file() {
     let config_text = "";
@@ -1609,6 +1758,33 @@ mod tests {
     assert!(ConfigFile::new(config_text, config_specifier).is_err());
   }
 
+  #[test]
+  fn test_parse_watch_config() {
+    let config_text = r#"{
+      "watch": {
+        "preRestart": ["codegen"],
+        "postRestart": ["echo restarted"]
+      }
+    }"#;
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+    let watch_config = config_file.to_watch_config().unwrap().unwrap();
+    assert_eq!(
+      watch_config,
+      WatchConfig {
+        pre_restart: vec!["codegen".to_string()],
+        post_restart: vec!["echo restarted".to_string()],
+      }
+    );
+
+    let config_text = r#"{ "watch": { "restart": [] } }"#;
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+    assert!(config_file.to_watch_config().is_err());
+  }
+
   #[test]
   fn test_tsconfig_as_bytes() {
     let mut tsconfig1 = TsConfig::new(json!({
@@ -0,0 +1,134 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_semver::Version;
+use deno_semver::VersionReq;
+
+use super::ConfigFile;
+use crate::colors;
+use crate::version;
+
+/// The name of the file that pins the version of Deno a project uses.
+pub const DENO_VERSION_FILE_NAME: &str = ".deno-version";
+
+/// A version of Deno pinned by a `.deno-version` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinnedDenoVersion {
+  pub path: PathBuf,
+  pub version: Version,
+}
+
+/// Looks for a `.deno-version` file in `start_dir` and its ancestors. The
+/// file only contains a version, optionally prefixed with `v`.
+///
+/// The pin is only advisory, so a file that can't be read or doesn't contain
+/// a version is warned about and ignored.
+pub fn discover_pinned_version(start_dir: &Path) -> Option<PinnedDenoVersion> {
+  for dir in start_dir.ancestors() {
+    let path = dir.join(DENO_VERSION_FILE_NAME);
+    match read_pinned_version(&path) {
+      Ok(Some(version)) => return Some(PinnedDenoVersion { path, version }),
+      Ok(None) => continue,
+      Err(err) => {
+        log::warn!("{} {:#}", colors::yellow("Warning"), err);
+        return None;
+      }
+    }
+  }
+  None
+}
+
+fn read_pinned_version(path: &Path) -> Result<Option<Version>, AnyError> {
+  let text = match std::fs::read_to_string(path) {
+    Ok(text) => text,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+    Err(err) => {
+      return Err(err)
+        .with_context(|| format!("Failed reading {}", path.display()))
+    }
+  };
+  let text = text.trim();
+  let version = text.strip_prefix('v').unwrap_or(text);
+  match Version::parse_standard(version) {
+    Ok(version) => Ok(Some(version)),
+    Err(_) => bail!("Invalid version \"{}\" in {}", text, path.display()),
+  }
+}
+
+/// Checks the running executable against the `"deno"` version requirement
+/// of the config file, which fails on mismatch, and against the version
+/// pinned by a `.deno-version` file, which only warns.
+pub fn check_deno_version(
+  maybe_config_file: Option<&ConfigFile>,
+  cwd: &Path,
+) -> Result<(), AnyError> {
+  // canary builds are checked as the release they precede
+  let current = Version::parse_standard(env!("CARGO_PKG_VERSION")).unwrap();
+  if let Some(config_file) = maybe_config_file {
+    if let Some(version_req) = config_file.deno_version_req() {
+      let parsed_req =
+        VersionReq::parse_from_npm(version_req).with_context(|| {
+          format!(
+            "Invalid \"deno\" version requirement \"{}\" in {}",
+            version_req, config_file.specifier
+          )
+        })?;
+      if !parsed_req.matches(&current) {
+        bail!(
+          concat!(
+            "Deno {} does not satisfy the version requirement \"{}\" of {}.\n",
+            "Run `deno upgrade --version <version>` to install a matching version."
+          ),
+          version::deno(),
+          version_req,
+          config_file.specifier
+        );
+      }
+    }
+  }
+  if !version::is_canary() {
+    if let Some(pinned) = discover_pinned_version(cwd) {
+      if pinned.version != current {
+        log::warn!(
+          "{} {} pins Deno {}, but this is Deno {}. Run `deno upgrade --pinned` to install it.",
+          colors::yellow("Warning"),
+          pinned.path.display(),
+          pinned.version,
+          current
+        );
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use test_util::TempDir;
+
+  #[test]
+  fn discover_pinned_version_in_ancestors() {
+    let temp_dir = TempDir::new();
+    temp_dir.create_dir_all("a/b");
+    assert_eq!(discover_pinned_version(&temp_dir.path().join("a/b")), None);
+
+    temp_dir.write("a/.deno-version", "v1.33.4\n");
+    assert_eq!(
+      discover_pinned_version(&temp_dir.path().join("a/b")),
+      Some(PinnedDenoVersion {
+        path: temp_dir.path().join("a/.deno-version"),
+        version: Version::parse_standard("1.33.4").unwrap(),
+      })
+    );
+
+    // an invalid pin is ignored
+    temp_dir.write("a/b/.deno-version", "latest");
+    assert_eq!(discover_pinned_version(&temp_dir.path().join("a/b")), None);
+  }
+}
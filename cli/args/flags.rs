@@ -11,6 +11,7 @@ use deno_core::resolve_url_or_path;
 use deno_core::url::Url;
 use deno_graph::GraphKind;
 use deno_runtime::permissions::parse_sys_kind;
+use deno_runtime::resource_limits::resource_limits;
 use log::debug;
 use log::Level;
 use std::env;
@@ -30,6 +31,8 @@ use super::flags_allow_net;
 pub struct FileFlags {
   pub ignore: Vec<PathBuf>,
   pub include: Vec<PathBuf>,
+  /// Don't use `.denoignore` files.
+  pub no_ignore: bool,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -63,6 +66,8 @@ pub struct CompileFlags {
   pub args: Vec<String>,
   pub target: Option<String>,
   pub include: Vec<String>,
+  pub show: bool,
+  pub code_cache: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -77,6 +82,10 @@ pub struct CoverageFlags {
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub lcov: bool,
+  /// The git ref to report the coverage of the changed lines since.
+  pub changed_since: Option<String>,
+  /// The percentage of the changed lines that must be covered.
+  pub changed_threshold: u8,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -105,6 +114,18 @@ pub struct EvalFlags {
   pub code: String,
 }
 
+/// How `deno fmt --check` reports the changes for files that aren't formatted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FmtDiffFormat {
+  /// Colored diff with line numbers, meant to be read by humans.
+  #[default]
+  Pretty,
+  /// Unified diff that can be applied with `git apply` or `patch`.
+  Unified,
+  /// JSON with the diff hunks of each file.
+  Json,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FmtFlags {
   pub check: bool,
@@ -115,6 +136,9 @@ pub struct FmtFlags {
   pub single_quote: Option<bool>,
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
+  pub diff_format: FmtDiffFormat,
+  pub diff_context: usize,
+  pub stdin_batch: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -126,6 +150,17 @@ pub struct InitFlags {
 pub struct InfoFlags {
   pub json: bool,
   pub file: Option<String>,
+  pub licenses: bool,
+  pub graph_format: Option<InfoGraphFormat>,
+}
+
+/// A graph description language `deno info` can output the module graph in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoGraphFormat {
+  /// The DOT language of Graphviz.
+  Dot,
+  /// A Mermaid flowchart.
+  Mermaid,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -152,6 +187,7 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub sarif: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -189,6 +225,22 @@ pub struct TestFlags {
   pub shuffle: Option<u64>,
   pub concurrent_jobs: Option<NonZeroUsize>,
   pub trace_ops: bool,
+  pub debug_failures: Option<SocketAddr>,
+  pub split_output: bool,
+  pub reporter: TestReporterConfig,
+  pub reporter_output: Option<PathBuf>,
+}
+
+/// How `deno test` reports the results of the tests.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TestReporterConfig {
+  /// Human readable output on the terminal.
+  #[default]
+  Pretty,
+  /// JUnit XML, meant to be ingested by CI systems.
+  Junit,
+  /// TAP version 13, for the tools and harnesses that consume TAP.
+  Tap,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -197,6 +249,8 @@ pub struct UpgradeFlags {
   pub force: bool,
   pub canary: bool,
   pub version: Option<String>,
+  /// Install the version pinned by a `.deno-version` file.
+  pub pinned: bool,
   pub output: Option<PathBuf>,
 }
 
@@ -204,6 +258,7 @@ pub struct UpgradeFlags {
 pub struct VendorFlags {
   pub specifiers: Vec<String>,
   pub output_path: Option<PathBuf>,
+  pub eszip_path: Option<PathBuf>,
   pub force: bool,
 }
 
@@ -315,10 +370,19 @@ pub struct Flags {
   pub allow_hrtime: bool,
   pub allow_net: Option<Vec<String>>,
   pub allow_ffi: Option<Vec<PathBuf>>,
+  pub allow_ffi_symbols: Option<PathBuf>,
   pub allow_read: Option<Vec<PathBuf>>,
   pub allow_run: Option<Vec<String>>,
   pub allow_sys: Option<Vec<String>>,
   pub allow_write: Option<Vec<PathBuf>>,
+  pub deny_env: Option<Vec<String>>,
+  pub deny_net: Option<Vec<String>>,
+  pub deny_ffi: Option<Vec<PathBuf>>,
+  pub deny_read: Option<Vec<PathBuf>>,
+  pub deny_run: Option<Vec<String>>,
+  pub deny_sys: Option<Vec<String>>,
+  pub deny_write: Option<Vec<PathBuf>>,
+  pub broadcast_channel_ipc: bool,
   pub ca_stores: Option<Vec<String>>,
   pub ca_data: Option<CaData>,
   pub cache_blocklist: Vec<String>,
@@ -329,11 +393,13 @@ pub struct Flags {
   pub type_check_mode: TypeCheckMode,
   pub config_flag: ConfigFlag,
   pub node_modules_dir: Option<bool>,
+  pub node_conditions: Vec<String>,
   pub coverage_dir: Option<String>,
   pub enable_testing_features: bool,
   pub ext: Option<String>,
   pub ignore: Vec<PathBuf>,
   pub import_map_path: Option<String>,
+  pub import_source: Option<PathBuf>,
   pub inspect_brk: Option<SocketAddr>,
   pub inspect_wait: Option<SocketAddr>,
   pub inspect: Option<SocketAddr>,
@@ -346,13 +412,21 @@ pub struct Flags {
   pub no_npm: bool,
   pub no_prompt: bool,
   pub reload: bool,
+  /// `--reload=stale`, use cached remote modules right away and revalidate
+  /// them in the background.
+  pub reload_stale: bool,
   pub seed: Option<u64>,
+  /// `--split-output` of `deno run` and `deno task`.
+  pub split_output: bool,
+  pub startup_report: bool,
   pub unstable: bool,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub v8_flags: Vec<String>,
   pub version: bool,
   pub watch: Option<Vec<PathBuf>>,
   pub no_clear_screen: bool,
+  pub watch_pre_restart: Vec<String>,
+  pub watch_post_restart: Vec<String>,
 }
 
 fn join_paths(allowlist: &[PathBuf], d: &str) -> String {
@@ -371,6 +445,7 @@ impl Flags {
 
     if self.allow_all {
       args.push("--allow-all".to_string());
+      args.extend(self.to_deny_args());
       return args;
     }
 
@@ -465,10 +540,47 @@ impl Flags {
       _ => {}
     }
 
+    if let Some(allow_ffi_symbols) = &self.allow_ffi_symbols {
+      args.push(format!(
+        "--allow-ffi-symbols={}",
+        allow_ffi_symbols.to_string_lossy()
+      ));
+    }
+
     if self.allow_hrtime {
       args.push("--allow-hrtime".to_string());
     }
 
+    args.extend(self.to_deny_args());
+    args
+  }
+
+  fn to_deny_args(&self) -> Vec<String> {
+    fn push(args: &mut Vec<String>, name: &str, list: &Option<Vec<String>>) {
+      match list {
+        Some(list) if list.is_empty() => args.push(format!("--deny-{name}")),
+        Some(list) => args.push(format!("--deny-{}={}", name, list.join(","))),
+        None => {}
+      }
+    }
+
+    fn paths(list: &Option<Vec<PathBuf>>) -> Option<Vec<String>> {
+      list.as_ref().map(|list| {
+        list
+          .iter()
+          .map(|path| path.to_string_lossy().to_string())
+          .collect()
+      })
+    }
+
+    let mut args = vec![];
+    push(&mut args, "read", &paths(&self.deny_read));
+    push(&mut args, "write", &paths(&self.deny_write));
+    push(&mut args, "net", &self.deny_net);
+    push(&mut args, "env", &self.deny_env);
+    push(&mut args, "run", &self.deny_run);
+    push(&mut args, "sys", &self.deny_sys);
+    push(&mut args, "ffi", &paths(&self.deny_ffi));
     args
   }
 
@@ -610,6 +722,8 @@ static ENV_VARIABLES_HELP: &str = r#"ENVIRONMENT VARIABLES:
                          (module downloads, fetch)
     HTTPS_PROXY          Proxy address for HTTPS requests
                          (module downloads, fetch)
+    NODE_EXTRA_CA_CERTS  Load additional certificate authorities from PEM
+                         encoded file
     NPM_CONFIG_REGISTRY  URL to use for the npm registry.
     NO_COLOR             Set to disable color
     NO_PROXY             Comma-separated list of hosts which do not use a proxy
@@ -804,6 +918,7 @@ fn bench_subcommand() -> Command {
         .help("Ignore files")
         .value_parser(value_parser!(PathBuf)),
     )
+    .arg(no_ignore_arg())
     .arg(
       Arg::new("filter")
         .long("filter")
@@ -825,6 +940,8 @@ fn bench_subcommand() -> Command {
     )
     .arg(watch_arg(false))
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
     .arg(script_arg().last(true))
     .about("Run benchmarks")
     .long_about(
@@ -858,6 +975,8 @@ fn bundle_subcommand() -> Command {
     )
     .arg(watch_arg(false))
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
     .arg(executable_ext_arg())
     .about("Bundle module and dependencies into single file")
     .long_about(
@@ -964,6 +1083,28 @@ fn compile_subcommand() -> Command {
           "aarch64-apple-darwin",
         ]),
     )
+    .arg(
+      Arg::new("show")
+        .long("show")
+        .help("Show the contents of an existing compiled executable")
+        .long_help(
+          "Prints the metadata, embedded modules, npm packages and permissions
+    of an existing executable produced by 'deno compile', instead of compiling
+    a script. The positional argument is the path to the executable.",
+        )
+        .action(ArgAction::SetTrue)
+        .conflicts_with_all(["include", "output", "target", "code-cache"]),
+    )
+    .arg(
+      Arg::new("code-cache")
+        .long("code-cache")
+        .help("Keep the V8 code cache of the modules between runs")
+        .long_help(
+          "Makes the executable keep the V8 code cache of its modules in a
+    temporary directory on the first run, which speeds up the next runs.",
+        )
+        .action(ArgAction::SetTrue),
+    )
     .arg(executable_ext_arg())
     .about("UNSTABLE: Compile the script into a self contained executable")
     .long_about(
@@ -986,6 +1127,10 @@ Cross-compiling to different target architectures is supported using the
 `--target` flag. On the first invocation with deno will download proper
 binary and cache it in $DENO_DIR. The aarch64-apple-darwin target is not
 supported in canary.
+
+To audit an existing executable before deploying it, use the `--show` flag:
+
+  deno compile --show ./file_server
 ",
     )
 }
@@ -1042,6 +1187,11 @@ Write a report using the lcov format:
 Generate html reports from lcov:
 
   genhtml -o html_cov cov.lcov
+
+Only report the lines that were changed since a git ref, and fail if less than
+90% of them are covered:
+
+  deno coverage --changed-since=main --changed-threshold=90 cov_profile
 ",
     )
     .arg(
@@ -1093,6 +1243,28 @@ Generate html reports from lcov:
         .require_equals(true)
         .value_hint(ValueHint::FilePath),
     )
+    .arg(
+      Arg::new("changed-since")
+        .long("changed-since")
+        .value_name("git-ref")
+        .require_equals(true)
+        .help("Only report the lines that changed since a git ref")
+        .long_help(
+          "Only report the lines that were added or modified since the given \
+git ref, including the ones that aren't committed yet, and fail if less than \
+--changed-threshold percent of them are covered.",
+        ),
+    )
+    .arg(
+      Arg::new("changed-threshold")
+        .long("changed-threshold")
+        .value_name("percent")
+        .require_equals(true)
+        .requires("changed-since")
+        .value_parser(value_parser!(u8).range(0..=100))
+        .default_value("80")
+        .help("The percentage of the changed lines that must be covered"),
+    )
     .arg(
       Arg::new("files")
         .num_args(1..)
@@ -1219,6 +1391,11 @@ Format stdin and write to stdout:
 
   cat file.ts | deno fmt -
 
+Format several files read from stdin in a single process, one JSON object per
+line in and out, for editors and pre-commit hooks:
+
+  echo '{\"path\":\"a.ts\",\"contents\":\"let a=1\"}' | deno fmt --stdin-batch
+
 Ignore formatting code by preceding it with an ignore comment:
 
   // deno-fmt-ignore
@@ -1235,6 +1412,44 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
         .help("Check if the source files are formatted")
         .num_args(0),
     )
+    .arg(
+      Arg::new("diff-format")
+        .long("diff-format")
+        .help("Format of the diffs printed by --check")
+        .long_help(
+          "Format of the diffs printed for files that are not formatted when
+using --check. 'unified' prints a patch that can be applied with
+`git apply`, 'json' prints the diff hunks of each file as JSON.",
+        )
+        .value_parser(["pretty", "unified", "json"])
+        .default_value("pretty")
+        .requires("check"),
+    )
+    .arg(
+      Arg::new("diff-context")
+        .long("diff-context")
+        .help(
+          "Number of context lines around changes in unified and JSON diffs",
+        )
+        .value_parser(value_parser!(usize))
+        .default_value("3")
+        .requires("check"),
+    )
+    .arg(
+      Arg::new("stdin-batch")
+        .long("stdin-batch")
+        .help("Format files read as JSON lines from stdin")
+        .long_help(
+          "Format files read from stdin, one JSON object per line with the
+'path' of the file and its 'contents'. The path picks the formatter and
+isn't read from disk. For every file, a JSON object is written to stdout
+with the 'path', whether it 'changed' and its formatted 'contents', or an
+'error' if it couldn't be formatted. With --check the contents are
+omitted and the exit code is non-zero if any file is not formatted.",
+        )
+        .action(ArgAction::SetTrue)
+        .conflicts_with_all(["files", "watch", "diff-format"]),
+    )
     .arg(
       Arg::new("ext")
         .long("ext")
@@ -1253,6 +1468,7 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
         .help("Ignore formatting particular source files")
         .value_hint(ValueHint::AnyPath),
     )
+    .arg(no_ignore_arg())
     .arg(
       Arg::new("files")
         .value_parser(value_parser!(PathBuf))
@@ -1263,6 +1479,8 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
     )
     .arg(watch_arg(false))
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
     .arg(
       Arg::new("use-tabs")
         .long("use-tabs")
@@ -1370,6 +1588,40 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
         .help("UNSTABLE: Outputs the information in JSON format")
         .action(ArgAction::SetTrue),
     )
+    .arg(
+      Arg::new("licenses")
+        .long("licenses")
+        .help("UNSTABLE: Show the licenses of the dependencies of the module")
+        .long_help("UNSTABLE: Show the licenses of the dependencies of the module.
+
+Licenses of npm packages are read from their package.json and licenses of
+remote modules from the 'x-license' response header. Packages are checked
+against the \"licenses\" configuration in the config file:
+
+  {
+    \"licenses\": {
+      \"allow\": [\"MIT\", \"Apache-2.0\"],
+      \"deny\": [\"GPL-3.0\"]
+    }
+  }")
+        .requires("file")
+        .action(ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("graph-format")
+        .long("graph-format")
+        .help("UNSTABLE: Outputs the module graph in a graph description language")
+        .long_help("UNSTABLE: Outputs the module graph in a graph description language.
+
+'dot' outputs the DOT language of Graphviz and 'mermaid' a Mermaid flowchart.
+Modules are sized by their size in bytes and colored by where they come from:
+local files, remote modules, npm packages or Node built-ins.
+
+  deno info --graph-format=dot main.ts | dot -Tsvg > graph.svg")
+        .value_parser(["dot", "mermaid"])
+        .requires("file")
+        .conflicts_with_all(["json", "licenses"]),
+    )
 }
 
 fn install_subcommand() -> Command {
@@ -1481,6 +1733,10 @@ Print result as JSON:
 
   deno lint --json
 
+Print result as SARIF, for code scanning tools:
+
+  deno lint --output=sarif > results.sarif
+
 Read from stdin:
 
   cat file.ts | deno lint -
@@ -1549,6 +1805,7 @@ Ignore linting a file by adding an ignore comment at the top of the file:
         .help("Ignore linting particular source files")
         .value_hint(ValueHint::AnyPath),
     )
+    .arg(no_ignore_arg())
     .arg(
       Arg::new("json")
         .long("json")
@@ -1562,6 +1819,14 @@ Ignore linting a file by adding an ignore comment at the top of the file:
         .action(ArgAction::SetTrue)
         .conflicts_with("json"),
     )
+    .arg(
+      Arg::new("output")
+        .long("output")
+        .require_equals(true)
+        .value_parser(["sarif"])
+        .help("Output lint result in the given format")
+        .conflicts_with_all(["json", "compact"]),
+    )
     .arg(
       Arg::new("files")
         .value_parser(value_parser!(PathBuf))
@@ -1572,6 +1837,8 @@ Ignore linting a file by adding an ignore comment at the top of the file:
     )
     .arg(watch_arg(false))
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
 }
 
 fn repl_subcommand() -> Command {
@@ -1605,7 +1872,23 @@ fn run_subcommand() -> Command {
         .conflicts_with("inspect-brk"),
     )
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
     .arg(executable_ext_arg())
+    .arg(startup_report_arg())
+    .arg(import_source_arg())
+    .arg(broadcast_channel_ipc_arg())
+    .arg(
+      Arg::new("split-output")
+        .long("split-output")
+        .help("Print the output of each web worker in one piece")
+        .long_help(
+          "Print what each web worker prints in one piece once the worker is \
+done, framed with the worker it came from, instead of interleaving it with the \
+output of the other workers.",
+        )
+        .action(ArgAction::SetTrue),
+    )
     .arg(
       script_arg()
         .required_unless_present("v8-flags")
@@ -1650,6 +1933,18 @@ fn task_subcommand() -> Command {
         .help("Specify the directory to run the task in")
         .value_hint(ValueHint::DirPath),
     )
+    .arg(
+      Arg::new("split-output")
+        .long("split-output")
+        .help("Show the output of parallel commands in a pane per command")
+        .long_help(
+          "Show what the commands of a task that run at the same time, like \
+`deno task dev` with a `server & watcher` script, print in a pane per command \
+instead of interleaving their output. The output of each command is printed \
+in one piece once the command is done.",
+        )
+        .action(ArgAction::SetTrue),
+    )
     .about("Run a task defined in the configuration file")
     .long_about(
       "Run a task defined in the configuration file
@@ -1671,6 +1966,7 @@ fn test_subcommand() -> Command {
         .help("Ignore files")
         .value_hint(ValueHint::AnyPath),
     )
+    .arg(no_ignore_arg())
     .arg(
       Arg::new("no-run")
         .long("no-run")
@@ -1759,7 +2055,60 @@ fn test_subcommand() -> Command {
         .conflicts_with("no-run")
         .conflicts_with("coverage"),
     )
+    .arg(
+      Arg::new("debug-failures")
+        .long("debug-failures")
+        .value_name("HOST:PORT")
+        .help("Re-run a test that fails in watch mode with the inspector paused on its first statement")
+        .num_args(0..=1)
+        .require_equals(true)
+        .value_parser(value_parser!(SocketAddr))
+        .requires("watch")
+        .conflicts_with("inspect")
+        .conflicts_with("inspect-wait")
+        .conflicts_with("inspect-brk"),
+    )
+    .arg(
+      Arg::new("split-output")
+        .long("split-output")
+        .help("Show the output of test modules in a pane per module")
+        .long_help(
+          "Show what the test modules that run at the same time print in a
+pane per module below the results, instead of interleaving their output.
+The output of each test is printed in one piece once the test is done.",
+        )
+        .action(ArgAction::SetTrue),
+    )
+    .arg(
+      Arg::new("reporter")
+        .long("reporter")
+        .help("Select the reporter to use for the test results")
+        .long_help(
+          "Select the reporter to use for the test results. Defaults to 'pretty',
+'junit' prints a JUnit XML report that CI systems can ingest and 'tap'
+prints the results in the TAP version 13 format.",
+        )
+        .require_equals(true)
+        .value_parser(["pretty", "junit", "tap"]),
+    )
+    .arg(
+      Arg::new("reporter-output")
+        .long("reporter-output")
+        .value_name("PATH")
+        .help("Write the report of --reporter to the given file")
+        .long_help(
+          "Write the report of --reporter to the given file instead of stdout,
+and show the results on the terminal as usual. Not used by the 'pretty'
+reporter.",
+        )
+        .require_equals(true)
+        .requires("reporter")
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::FilePath),
+    )
     .arg(no_clear_screen_arg())
+    .arg(watch_pre_restart_arg())
+    .arg(watch_post_restart_arg())
     .arg(script_arg().last(true))
     .about("Run tests")
     .long_about(
@@ -1803,13 +2152,25 @@ and is used to replace the current executable.
 If you want to not replace the current Deno executable but instead download an
 update to a different location, use the --output flag
 
-  deno upgrade --output $HOME/my_deno",
+  deno upgrade --output $HOME/my_deno
+
+To install the version pinned by a .deno-version file in the current
+directory or one of its parents, use the --pinned flag
+
+  deno upgrade --pinned",
     )
     .arg(
       Arg::new("version")
         .long("version")
         .help("The version to upgrade to"),
     )
+    .arg(
+      Arg::new("pinned")
+        .long("pinned")
+        .help("Install the version pinned by a .deno-version file")
+        .conflicts_with_all(["version", "canary"])
+        .action(ArgAction::SetTrue),
+    )
     .arg(
       Arg::new("output")
         .long("output")
@@ -1820,7 +2181,7 @@ update to a different location, use the --output flag
     .arg(
       Arg::new("dry-run")
         .long("dry-run")
-        .help("Perform all checks without replacing old exe")
+        .help("Perform all checks and print what would change without replacing old exe")
         .action(ArgAction::SetTrue),
     )
     .arg(
@@ -1854,7 +2215,13 @@ maps remote specifiers to the downloaded files.
 
 Remote modules and multiple modules may also be specified:
 
-  deno vendor main.ts test.deps.ts https://deno.land/std/path/mod.ts",
+  deno vendor main.ts test.deps.ts https://deno.land/std/path/mod.ts
+
+The modules may instead be packed into a single eszip archive, which is
+used in place of fetching them by specifying it as the import source:
+
+  deno vendor --eszip out.eszip main.ts
+  deno run --import-source=out.eszip main.ts",
     )
     .arg(
       Arg::new("specifiers")
@@ -1869,6 +2236,20 @@ Remote modules and multiple modules may also be specified:
         .value_parser(value_parser!(PathBuf))
         .value_hint(ValueHint::DirPath),
     )
+    .arg(
+      Arg::new("eszip")
+        .long("eszip")
+        .help("Pack the modules into an eszip archive instead of a directory")
+        .long_help(
+          "Pack the modules into an eszip archive at the given path instead \
+of writing them to a directory. Local and remote modules are included. npm \
+packages are not, so they are still resolved from the npm cache or registry \
+when the archive is used.",
+        )
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::FilePath)
+        .conflicts_with("output"),
+    )
     .arg(
       Arg::new("force")
         .long("force")
@@ -1897,6 +2278,7 @@ fn compile_args_without_check_args(app: Command) -> Command {
     .arg(no_remote_arg())
     .arg(no_npm_arg())
     .arg(node_modules_dir_arg())
+    .arg(conditions_arg())
     .arg(config_arg())
     .arg(no_config_arg())
     .arg(reload_arg())
@@ -1976,6 +2358,14 @@ static ALLOW_FFI_HELP: &str = concat!(
   "  --allow-ffi=\"./libfoo.so\""
 );
 
+static ALLOW_FFI_SYMBOLS_HELP: &str = concat!(
+  "(Unstable) Only allow binding the listed symbols of the listed dynamic libraries.\n",
+  "The file maps library paths, or directories of libraries, relative to it, to arrays\n",
+  "of symbol names. Libraries that aren't listed may not be loaded.\n",
+  "Examples:\n",
+  "  --allow-ffi --allow-ffi-symbols=\"./ffi_symbols.json\""
+);
+
 static ALLOW_HRTIME_HELP: &str = concat!(
   "Allow high-resolution time measurement. Note: this can enable timing attacks and fingerprinting.\n",
   "Docs: https://deno.land/manual@v",
@@ -1983,6 +2373,69 @@ static ALLOW_HRTIME_HELP: &str = concat!(
   "/basics/permissions\n"
 );
 
+static DENY_READ_HELP: &str = concat!(
+  "Deny file system read access, even where other flags allow it. Optionally specify denied paths.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-read --deny-read=\"/etc\""
+);
+
+static DENY_WRITE_HELP: &str = concat!(
+  "Deny file system write access, even where other flags allow it. Optionally specify denied paths.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-write --deny-write=\".git\""
+);
+
+static DENY_NET_HELP: &str = concat!(
+  "Deny network access, even where other flags allow it. Optionally specify denied IP addresses and host names, with ports as necessary.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-net --deny-net=\"169.254.169.254,metadata.internal\""
+);
+
+static DENY_ENV_HELP: &str = concat!(
+  "Deny access to environment variables, even where other flags allow it. Optionally specify denied variables.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-env --deny-env=\"AWS_SECRET_ACCESS_KEY\""
+);
+
+static DENY_SYS_HELP: &str = concat!(
+  "Deny access to OS information, even where other flags allow it. Optionally deny specific APIs by function name.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-sys --deny-sys=\"networkInterfaces\""
+);
+
+static DENY_RUN_HELP: &str = concat!(
+  "Deny running subprocesses, even where other flags allow it. Optionally specify denied executables.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-run --deny-run=\"curl\""
+);
+
+static DENY_FFI_HELP: &str = concat!(
+  "Deny loading dynamic libraries, even where other flags allow it. Optionally specify denied directories or files.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n",
+  "Examples:\n",
+  "  --allow-ffi --deny-ffi=\"./libfoo.so\""
+);
+
 static ALLOW_ALL_HELP: &str = concat!(
   "Allow all permissions. Learn more about permissions in Deno:\n",
   "https://deno.land/manual@v",
@@ -2033,17 +2486,7 @@ fn permission_args(app: Command) -> Command {
         .require_equals(true)
         .value_name("VARIABLE_NAME")
         .help(ALLOW_ENV_HELP)
-        .value_parser(|key: &str| {
-          if key.is_empty() || key.contains(&['=', '\0'] as &[char]) {
-            return Err(format!("invalid key \"{key}\""));
-          }
-
-          Ok(if cfg!(windows) {
-            key.to_uppercase()
-          } else {
-            key.to_string()
-          })
-        }),
+        .value_parser(env_var_name_parser),
     )
     .arg(
       Arg::new("allow-sys")
@@ -2075,12 +2518,93 @@ fn permission_args(app: Command) -> Command {
         .value_parser(value_parser!(PathBuf))
         .value_hint(ValueHint::AnyPath),
     )
+    .arg(
+      Arg::new("allow-ffi-symbols")
+        .long("allow-ffi-symbols")
+        .require_equals(true)
+        .value_name("FILE")
+        .help(ALLOW_FFI_SYMBOLS_HELP)
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::FilePath),
+    )
     .arg(
       Arg::new("allow-hrtime")
         .long("allow-hrtime")
         .action(ArgAction::SetTrue)
         .help(ALLOW_HRTIME_HELP),
     )
+    .arg(
+      Arg::new("deny-read")
+        .long("deny-read")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("PATH")
+        .help(DENY_READ_HELP)
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::AnyPath),
+    )
+    .arg(
+      Arg::new("deny-write")
+        .long("deny-write")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("PATH")
+        .help(DENY_WRITE_HELP)
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::AnyPath),
+    )
+    .arg(
+      Arg::new("deny-net")
+        .long("deny-net")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("IP_OR_HOSTNAME")
+        .help(DENY_NET_HELP)
+        .value_parser(flags_allow_net::validator),
+    )
+    .arg(
+      Arg::new("deny-env")
+        .long("deny-env")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("VARIABLE_NAME")
+        .help(DENY_ENV_HELP)
+        .value_parser(env_var_name_parser),
+    )
+    .arg(
+      Arg::new("deny-sys")
+        .long("deny-sys")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("API_NAME")
+        .help(DENY_SYS_HELP)
+        .value_parser(|key: &str| parse_sys_kind(key).map(ToString::to_string)),
+    )
+    .arg(
+      Arg::new("deny-run")
+        .long("deny-run")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("PROGRAM_NAME")
+        .help(DENY_RUN_HELP),
+    )
+    .arg(
+      Arg::new("deny-ffi")
+        .long("deny-ffi")
+        .num_args(0..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_name("PATH")
+        .help(DENY_FFI_HELP)
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::AnyPath),
+    )
     .arg(
       Arg::new("allow-all")
         .short('A')
@@ -2105,6 +2629,18 @@ fn permission_args(app: Command) -> Command {
     )
 }
 
+fn env_var_name_parser(key: &str) -> Result<String, String> {
+  if key.is_empty() || key.contains(&['=', '\0'] as &[char]) {
+    return Err(format!("invalid key \"{key}\""));
+  }
+
+  Ok(if cfg!(windows) {
+    key.to_uppercase()
+  } else {
+    key.to_string()
+  })
+}
+
 fn runtime_args(
   app: Command,
   include_perms: bool,
@@ -2203,7 +2739,12 @@ fn reload_arg() -> Arg {
 --reload=npm:
   Reload all npm modules
 --reload=npm:chalk
-  Reload specific npm module",
+  Reload specific npm module
+--reload=stale
+  Use cached remote modules right away and update them in the background
+  for the next run
+--reload=stale,https://deno.land/std
+  Reload only standard modules, and update the others in the background",
     )
     .value_hint(ValueHint::FilePath)
     .value_parser(reload_arg_validate)
@@ -2309,6 +2850,61 @@ Additional paths might be watched by passing them as arguments to this flag.",
   }
 }
 
+fn import_source_arg() -> Arg {
+  Arg::new("import-source")
+    .long("import-source")
+    .value_name("FILE")
+    .help("Load modules from an eszip archive made by `deno vendor --eszip`")
+    .long_help(
+      "Load modules from an eszip archive made by `deno vendor --eszip` \
+instead of fetching them. Modules that are not in the archive are loaded as \
+usual. The modules are still subject to the permissions, --no-remote and the \
+lockfile.",
+    )
+    .value_parser(value_parser!(PathBuf))
+    .value_hint(ValueHint::FilePath)
+}
+
+fn broadcast_channel_ipc_arg() -> Arg {
+  Arg::new("broadcast-channel-ipc")
+    .long("broadcast-channel-ipc")
+    .action(ArgAction::SetTrue)
+    .help("(Unstable) Exchange BroadcastChannel messages with other processes")
+    .long_help(
+      "(Unstable) Exchange BroadcastChannel messages with the other processes \
+that use the same DENO_DIR and this flag, instead of only with the workers of \
+this process. The processes meet on a socket, or a named pipe on Windows, \
+that only the current user can connect to.",
+    )
+}
+
+fn startup_report_arg() -> Arg {
+  Arg::new("startup-report")
+    .long("startup-report")
+    .action(ArgAction::SetTrue)
+    .conflicts_with("watch")
+    .help("Print how long each phase of the startup took")
+    .long_help(
+      "Print how long each phase of the startup took to stderr right before \
+the program starts executing: parsing the flags, discovering the \
+configuration file, building the module graph, resolving npm packages, \
+type checking, emitting, booting the runtime from the snapshot and the \
+total time until the first user code runs.",
+    )
+}
+
+fn no_ignore_arg() -> Arg {
+  Arg::new("no-ignore")
+    .long("no-ignore")
+    .action(ArgAction::SetTrue)
+    .help("Do not use .denoignore files to ignore files")
+    .long_help(
+      "Do not use .denoignore files to ignore files. By default, the
+    .denoignore files in the current directory and in the collected
+    directories are honored. They use the same syntax as .gitignore files.",
+    )
+}
+
 fn no_clear_screen_arg() -> Arg {
   Arg::new("no-clear-screen")
     .requires("watch")
@@ -2317,14 +2913,44 @@ fn no_clear_screen_arg() -> Arg {
     .help("Do not clear terminal screen when under watch mode")
 }
 
-fn no_check_arg() -> Arg {
-  Arg::new("no-check")
-    .num_args(0..=1)
-    .require_equals(true)
-    .value_name("NO_CHECK_TYPE")
-    .long("no-check")
-    .help("Skip type-checking modules")
-    .long_help(
+fn watch_pre_restart_arg() -> Arg {
+  Arg::new("watch-pre-restart")
+    .requires("watch")
+    .long("watch-pre-restart")
+    .value_name("TASK")
+    .action(ArgAction::Append)
+    .help("Run a task or command before each restart under watch mode")
+    .long_help(
+      "Run a task or command after a file change is detected, before the \
+restart under watch mode. The value is the name of a task from the \
+configuration file or a command. Can be repeated. Overrides \"preRestart\" in \
+the \"watch\" configuration.",
+    )
+}
+
+fn watch_post_restart_arg() -> Arg {
+  Arg::new("watch-post-restart")
+    .requires("watch")
+    .long("watch-post-restart")
+    .value_name("TASK")
+    .action(ArgAction::Append)
+    .help("Run a task or command after each restart under watch mode")
+    .long_help(
+      "Run a task or command once the process has been restarted under watch \
+mode. The value is the name of a task from the configuration file or a \
+command. Can be repeated. Overrides \"postRestart\" in the \"watch\" \
+configuration.",
+    )
+}
+
+fn no_check_arg() -> Arg {
+  Arg::new("no-check")
+    .num_args(0..=1)
+    .require_equals(true)
+    .value_name("NO_CHECK_TYPE")
+    .long("no-check")
+    .help("Skip type-checking modules")
+    .long_help(
       "Skip type-checking. If the value of '--no-check=remote' is supplied,
 diagnostic errors from remote modules will be ignored.",
     )
@@ -2455,6 +3081,21 @@ fn node_modules_dir_arg() -> Arg {
     .help("Enables or disables the use of a local node_modules folder for npm packages")
 }
 
+fn conditions_arg() -> Arg {
+  Arg::new("conditions")
+    .long("conditions")
+    .num_args(1..)
+    .use_value_delimiter(true)
+    .require_equals(true)
+    .action(ArgAction::Append)
+    .value_name("CONDITIONS")
+    .help("Additional conditions to use when resolving the \"exports\" and \"imports\" of npm packages")
+    .long_help("Additional conditions to use when resolving the \"exports\" and \"imports\" of npm packages.
+
+The \"browser\" condition also makes the \"browser\" field of package.json
+files replace the modules it maps, where `false` means an empty module.")
+}
+
 fn unsafely_ignore_certificate_errors_arg() -> Arg {
   Arg::new("unsafely-ignore-certificate-errors")
     .long("unsafely-ignore-certificate-errors")
@@ -2500,7 +3141,11 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   watch_arg_parse(flags, matches, false);
   flags.subcommand = DenoSubcommand::Bench(BenchFlags {
-    files: FileFlags { include, ignore },
+    files: FileFlags {
+      include,
+      ignore,
+      no_ignore: matches.get_flag("no-ignore"),
+    },
     filter,
     json,
     no_run,
@@ -2560,6 +3205,8 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
+  let show = matches.get_flag("show");
+  let code_cache = matches.get_flag("code-cache");
   ext_arg_parse(flags, matches);
 
   flags.subcommand = DenoSubcommand::Compile(CompileFlags {
@@ -2568,6 +3215,8 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     args,
     target,
     include,
+    show,
+    code_cache,
   });
 }
 
@@ -2619,15 +3268,21 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
   let lcov = matches.get_flag("lcov");
   let output = matches.remove_one::<PathBuf>("output");
+  let changed_since = matches.remove_one::<String>("changed-since");
+  let changed_threshold =
+    matches.remove_one::<u8>("changed-threshold").unwrap();
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
       ignore,
+      no_ignore: false,
     },
     output,
     include,
     exclude,
     lcov,
+    changed_since,
+    changed_threshold,
   });
 }
 
@@ -2715,16 +3370,30 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let single_quote = matches.remove_one::<bool>("single-quote");
   let prose_wrap = matches.remove_one::<String>("prose-wrap");
   let no_semicolons = matches.remove_one::<bool>("no-semicolons");
+  let diff_format = match matches.remove_one::<String>("diff-format").as_deref()
+  {
+    Some("unified") => FmtDiffFormat::Unified,
+    Some("json") => FmtDiffFormat::Json,
+    _ => FmtDiffFormat::Pretty,
+  };
+  let diff_context = matches.remove_one::<usize>("diff-context").unwrap();
 
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
-    files: FileFlags { include, ignore },
+    files: FileFlags {
+      include,
+      ignore,
+      no_ignore: matches.get_flag("no-ignore"),
+    },
     use_tabs,
     line_width,
     indent_width,
     single_quote,
     prose_wrap,
     no_semicolons,
+    diff_format,
+    diff_context,
+    stdin_batch: matches.get_flag("stdin-batch"),
   });
 }
 
@@ -2746,9 +3415,18 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   let json = matches.get_flag("json");
+  let licenses = matches.get_flag("licenses");
+  let graph_format =
+    match matches.remove_one::<String>("graph-format").as_deref() {
+      Some("dot") => Some(InfoGraphFormat::Dot),
+      Some("mermaid") => Some(InfoGraphFormat::Mermaid),
+      _ => None,
+    };
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    licenses,
+    graph_format,
   });
 }
 
@@ -2810,10 +3488,13 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let sarif =
+    matches.remove_one::<String>("output").as_deref() == Some("sarif");
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
       include: files,
       ignore,
+      no_ignore: matches.get_flag("no-ignore"),
     },
     rules,
     maybe_rules_tags,
@@ -2822,6 +3503,7 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
     json,
     compact,
+    sarif,
   });
 }
 
@@ -2854,6 +3536,10 @@ fn run_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   ext_arg_parse(flags, matches);
 
   watch_arg_parse(flags, matches, true);
+  flags.startup_report = matches.get_flag("startup-report");
+  flags.import_source = matches.remove_one::<PathBuf>("import-source");
+  flags.broadcast_channel_ipc = matches.get_flag("broadcast-channel-ipc");
+  flags.split_output = matches.get_flag("split-output");
   flags.subcommand = DenoSubcommand::Run(RunFlags { script });
 }
 
@@ -2862,6 +3548,7 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     .remove_one::<String>("config")
     .map(ConfigFlag::Path)
     .unwrap_or(ConfigFlag::Discover);
+  flags.split_output = matches.get_flag("split-output");
 
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
@@ -2929,7 +3616,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     if let Ok(value) = env::var("DENO_JOBS") {
       value.parse::<NonZeroUsize>().ok()
     } else {
-      std::thread::available_parallelism().ok()
+      NonZeroUsize::new(resource_limits().cpu_count)
     }
   } else if matches.contains_id("jobs") {
     // We can't change this to use the log crate because its not configured
@@ -2943,7 +3630,7 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     if let Some(value) = matches.remove_one::<NonZeroUsize>("jobs") {
       Some(value)
     } else {
-      std::thread::available_parallelism().ok()
+      NonZeroUsize::new(resource_limits().cpu_count)
     }
   } else {
     None
@@ -2955,18 +3642,43 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Vec::new()
   };
 
+  let debug_failures = if matches.contains_id("debug-failures") {
+    Some(
+      matches
+        .remove_one::<SocketAddr>("debug-failures")
+        .unwrap_or_else(|| "127.0.0.1:9229".parse().unwrap()),
+    )
+  } else {
+    None
+  };
+
+  let reporter = match matches.remove_one::<String>("reporter").as_deref() {
+    Some("junit") => TestReporterConfig::Junit,
+    Some("tap") => TestReporterConfig::Tap,
+    _ => TestReporterConfig::Pretty,
+  };
+  let reporter_output = matches.remove_one::<PathBuf>("reporter-output");
+
   flags.coverage_dir = matches.remove_one::<String>("coverage");
   watch_arg_parse(flags, matches, false);
   flags.subcommand = DenoSubcommand::Test(TestFlags {
     no_run,
     doc,
     fail_fast,
-    files: FileFlags { include, ignore },
+    files: FileFlags {
+      include,
+      ignore,
+      no_ignore: matches.get_flag("no-ignore"),
+    },
     filter,
     shuffle,
     allow_none,
     concurrent_jobs,
     trace_ops,
+    debug_failures,
+    split_output: matches.get_flag("split-output"),
+    reporter,
+    reporter_output,
   });
 }
 
@@ -2981,12 +3693,14 @@ fn upgrade_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let force = matches.get_flag("force");
   let canary = matches.get_flag("canary");
   let version = matches.remove_one::<String>("version");
+  let pinned = matches.get_flag("pinned");
   let output = matches.remove_one::<PathBuf>("output");
   flags.subcommand = DenoSubcommand::Upgrade(UpgradeFlags {
     dry_run,
     force,
     canary,
     version,
+    pinned,
     output,
   });
 }
@@ -3005,6 +3719,7 @@ fn vendor_parse(flags: &mut Flags, matches: &mut ArgMatches) {
       .map(|p| p.collect())
       .unwrap_or_default(),
     output_path: matches.remove_one::<PathBuf>("output"),
+    eszip_path: matches.remove_one::<PathBuf>("eszip"),
     force: matches.get_flag("force"),
   });
 }
@@ -3023,6 +3738,7 @@ fn compile_args_without_check_parse(
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   node_modules_dir_arg_parse(flags, matches);
+  conditions_arg_parse(flags, matches);
   config_args_parse(flags, matches);
   reload_arg_parse(flags, matches);
   lock_args_parse(flags, matches);
@@ -3064,9 +3780,45 @@ fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     debug!("ffi allowlist: {:#?}", &flags.allow_ffi);
   }
 
+  flags.allow_ffi_symbols = matches.remove_one::<PathBuf>("allow-ffi-symbols");
+
   if matches.get_flag("allow-hrtime") {
     flags.allow_hrtime = true;
   }
+
+  if let Some(read_dl) = matches.remove_many::<PathBuf>("deny-read") {
+    flags.deny_read = Some(read_dl.collect());
+  }
+
+  if let Some(write_dl) = matches.remove_many::<PathBuf>("deny-write") {
+    flags.deny_write = Some(write_dl.collect());
+  }
+
+  if let Some(net_dl) = matches.remove_many::<String>("deny-net") {
+    let net_denylist = flags_allow_net::parse(net_dl.collect()).unwrap();
+    flags.deny_net = Some(net_denylist);
+  }
+
+  if let Some(env_dl) = matches.remove_many::<String>("deny-env") {
+    flags.deny_env = Some(env_dl.collect());
+    debug!("env denylist: {:#?}", &flags.deny_env);
+  }
+
+  if let Some(run_dl) = matches.remove_many::<String>("deny-run") {
+    flags.deny_run = Some(run_dl.collect());
+    debug!("run denylist: {:#?}", &flags.deny_run);
+  }
+
+  if let Some(sys_dl) = matches.remove_many::<String>("deny-sys") {
+    flags.deny_sys = Some(sys_dl.collect());
+    debug!("sys info denylist: {:#?}", &flags.deny_sys);
+  }
+
+  if let Some(ffi_dl) = matches.remove_many::<PathBuf>("deny-ffi") {
+    flags.deny_ffi = Some(ffi_dl.collect());
+    debug!("ffi denylist: {:#?}", &flags.deny_ffi);
+  }
+
   if matches.get_flag("allow-all") {
     flags.allow_all = true;
     flags.allow_read = Some(vec![]);
@@ -3151,9 +3903,11 @@ fn import_map_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
 fn reload_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if let Some(cache_bl) = matches.remove_many::<String>("reload") {
-    let raw_cache_blocklist: Vec<String> = cache_bl.collect();
+    let (stale, raw_cache_blocklist): (Vec<String>, Vec<String>) =
+      cache_bl.partition(|value| value == "stale");
+    flags.reload_stale = !stale.is_empty();
     if raw_cache_blocklist.is_empty() {
-      flags.reload = true;
+      flags.reload = !flags.reload_stale;
     } else {
       flags.cache_blocklist = resolve_urls(raw_cache_blocklist);
       debug!("cache blocklist: {:#?}", &flags.cache_blocklist);
@@ -3280,10 +4034,19 @@ fn node_modules_dir_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.node_modules_dir = matches.remove_one::<bool>("node-modules-dir");
 }
 
+fn conditions_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(conditions) = matches.remove_many::<String>("conditions") {
+    flags.node_conditions = conditions.collect();
+  }
+}
+
 fn reload_arg_validate(urlstr: &str) -> Result<String, String> {
   if urlstr.is_empty() {
     return Err(String::from("Missing url. Check for extra commas."));
   }
+  if urlstr == "stale" {
+    return Ok(urlstr.to_string());
+  }
   match Url::from_str(urlstr) {
     Ok(_) => Ok(urlstr.to_string()),
     Err(e) => Err(e.to_string()),
@@ -3306,6 +4069,13 @@ fn watch_arg_parse(
   if matches.get_flag("no-clear-screen") {
     flags.no_clear_screen = true;
   }
+
+  if let Some(tasks) = matches.remove_many::<String>("watch-pre-restart") {
+    flags.watch_pre_restart = tasks.collect();
+  }
+  if let Some(tasks) = matches.remove_many::<String>("watch-post-restart") {
+    flags.watch_post_restart = tasks.collect();
+  }
 }
 
 // TODO(ry) move this to utility module and add test.
@@ -3372,11 +4142,40 @@ mod tests {
           dry_run: true,
           canary: false,
           version: None,
+          pinned: false,
+          output: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn upgrade_pinned() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--pinned"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          version: None,
+          pinned: true,
           output: None,
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "upgrade",
+      "--pinned",
+      "--version",
+      "1.0.0"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -3409,6 +4208,42 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_reload_stale() {
+    let r = flags_from_vec(svec!["deno", "run", "--reload=stale", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        reload_stale: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_reload_stale_with_urls() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--reload=stale,https://deno.land/std",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        reload_stale: true,
+        cache_blocklist: svec!["https://deno.land/std"],
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn run_watch() {
     let r = flags_from_vec(svec!["deno", "run", "--watch", "script.ts"]);
@@ -3425,6 +4260,88 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_import_source() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--import-source=out.eszip",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        import_source: Some(PathBuf::from("out.eszip")),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_broadcast_channel_ipc() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--unstable",
+      "--broadcast-channel-ipc",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        unstable: true,
+        broadcast_channel_ipc: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_split_output() {
+    let r = flags_from_vec(svec!["deno", "run", "--split-output", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        split_output: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_startup_report() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--startup-report", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        startup_report: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--startup-report",
+      "--watch",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_watch_with_external() {
     let r =
@@ -3466,6 +4383,44 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_watch_with_restart_hooks() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--watch-pre-restart",
+      "codegen",
+      "--watch-pre-restart",
+      "echo pre",
+      "--watch-post-restart=echo post",
+      "script.ts"
+    ]);
+
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        watch: Some(vec![]),
+        watch_pre_restart: svec!["codegen", "echo pre"],
+        watch_post_restart: svec!["echo post"],
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch-pre-restart",
+      "codegen",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_reload_allow_write() {
     let r =
@@ -3650,6 +4605,7 @@ mod tests {
               PathBuf::from("script_2.ts")
             ],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3657,6 +4613,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -3672,6 +4631,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3679,6 +4639,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -3694,6 +4657,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3701,6 +4665,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -3716,6 +4683,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3723,6 +4691,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         watch: Some(vec![]),
@@ -3740,6 +4711,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3747,6 +4719,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         watch: Some(vec![]),
@@ -3771,6 +4746,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("foo.ts")],
             ignore: vec![PathBuf::from("bar.js")],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3778,6 +4754,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         watch: Some(vec![]),
@@ -3794,6 +4773,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3801,6 +4781,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -3824,6 +4807,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("foo.ts")],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: None,
           line_width: None,
@@ -3831,6 +4815,9 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ext: Some("ts".to_string()),
@@ -3860,6 +4847,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: Some(true),
           line_width: Some(NonZeroU32::new(60).unwrap()),
@@ -3867,6 +4855,9 @@ mod tests {
           single_quote: Some(true),
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -3889,6 +4880,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           use_tabs: Some(false),
           line_width: None,
@@ -3896,6 +4888,9 @@ mod tests {
           single_quote: Some(false),
           prose_wrap: None,
           no_semicolons: Some(false),
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -3903,6 +4898,106 @@ mod tests {
     );
   }
 
+  #[test]
+  fn fmt_no_ignore() {
+    let r = flags_from_vec(svec!["deno", "fmt", "--no-ignore", "src"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![PathBuf::from("src")],
+            ignore: vec![],
+            no_ignore: true,
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: false,
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+  }
+  #[test]
+  fn fmt_diff_format() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--check",
+      "--diff-format",
+      "json",
+      "--diff-context",
+      "1"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+            no_ignore: false,
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          diff_format: FmtDiffFormat::Json,
+          diff_context: 1,
+          stdin_batch: false,
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--diff-format", "unified"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn fmt_stdin_batch() {
+    let r = flags_from_vec(svec!["deno", "fmt", "--stdin-batch", "--check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+            no_ignore: false,
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          diff_format: FmtDiffFormat::Pretty,
+          diff_context: 3,
+          stdin_batch: true,
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--stdin-batch", "a.ts"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn lint() {
     let r = flags_from_vec(svec!["deno", "lint", "script_1.ts", "script_2.ts"]);
@@ -3916,6 +5011,7 @@ mod tests {
               PathBuf::from("script_2.ts")
             ],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -3923,6 +5019,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
         }),
         ..Flags::default()
       }
@@ -3945,6 +5042,7 @@ mod tests {
               PathBuf::from("script_2.ts")
             ],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -3952,6 +5050,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
         }),
         watch: Some(vec![]),
         ..Flags::default()
@@ -3976,6 +5075,7 @@ mod tests {
               PathBuf::from("script_2.ts")
             ],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -3983,6 +5083,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
         }),
         watch: Some(vec![]),
         no_clear_screen: true,
@@ -4002,6 +5103,7 @@ mod tests {
               PathBuf::from("script_1.ts"),
               PathBuf::from("script_2.ts")
             ],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -4009,6 +5111,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
         }),
         ..Flags::default()
       }
@@ -4022,6 +5125,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: true,
           maybe_rules_tags: None,
@@ -4029,6 +5133,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          sarif: false,
         }),
         ..Flags::default()
       }
@@ -4048,6 +5153,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: Some(svec![""]),
@@ -4055,6 +5161,7 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          sarif: false,
         }),
         ..Flags::default()
       }
@@ -4068,6 +5175,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("script_1.ts")],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -4075,6 +5183,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
         }),
         ..Flags::default()
       }
@@ -4095,6 +5204,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("script_1.ts")],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -4102,6 +5212,7 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          sarif: false,
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
@@ -4123,6 +5234,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("script_1.ts")],
             ignore: vec![],
+            no_ignore: false,
           },
           rules: false,
           maybe_rules_tags: None,
@@ -4130,11 +5242,44 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          sarif: false,
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "lint", "--output=sarif", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![PathBuf::from("script_1.ts")],
+            ignore: vec![],
+            no_ignore: false,
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          sarif: true,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--output=sarif",
+      "--json",
+      "script_1.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -4213,6 +5358,8 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          licenses: false,
+          graph_format: None,
         }),
         ..Flags::default()
       }
@@ -4225,6 +5372,8 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("script.ts".to_string()),
+          licenses: false,
+          graph_format: None,
         }),
         reload: true,
         ..Flags::default()
@@ -4238,6 +5387,8 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
           file: Some("script.ts".to_string()),
+          licenses: false,
+          graph_format: None,
         }),
         ..Flags::default()
       }
@@ -4249,7 +5400,9 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: None,
+          licenses: false,
+          graph_format: None,
         }),
         ..Flags::default()
       }
@@ -4261,33 +5414,84 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
-          file: None
+          file: None,
+          licenses: false,
+          graph_format: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--no-npm",
+      "--no-remote",
+      "--config",
+      "tsconfig.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: None,
+          licenses: false,
+          graph_format: None,
+        }),
+        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
+        no_npm: true,
+        no_remote: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--licenses", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          file: Some("script.ts".to_string()),
+          licenses: true,
+          graph_format: None,
         }),
         ..Flags::default()
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "info", "--licenses"]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec![
       "deno",
       "info",
-      "--no-npm",
-      "--no-remote",
-      "--config",
-      "tsconfig.json"
+      "--graph-format=mermaid",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
-          file: None
+          file: Some("script.ts".to_string()),
+          licenses: false,
+          graph_format: Some(InfoGraphFormat::Mermaid),
         }),
-        config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
-        no_npm: true,
-        no_remote: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "info", "--graph-format=dot"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "info",
+      "--graph-format=dot",
+      "--json",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -4730,6 +5934,9 @@ mod tests {
       "script.ts"
     ]);
     assert!(r.is_ok());
+    let r =
+      flags_from_vec(svec!["deno", "run", "--allow-sys=cpus", "script.ts"]);
+    assert!(r.is_ok());
     let r =
       flags_from_vec(svec!["deno", "run", "--allow-sys=foo", "script.ts"]);
     assert!(r.is_err());
@@ -4742,6 +5949,81 @@ mod tests {
     assert!(r.is_err());
   }
 
+  #[test]
+  fn allow_ffi_symbols() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-ffi=./libfoo.so",
+      "--allow-ffi-symbols=./ffi_symbols.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        allow_ffi: Some(vec![PathBuf::from("./libfoo.so")]),
+        allow_ffi_symbols: Some(PathBuf::from("./ffi_symbols.json")),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_flags() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "-A",
+      "--deny-net=metadata.internal,10.0.0.1:8080",
+      "--deny-read=/etc",
+      "--deny-env=SECRET",
+      "--deny-sys=networkInterfaces",
+      "--deny-run",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        allow_all: true,
+        allow_env: Some(vec![]),
+        allow_hrtime: true,
+        allow_net: Some(vec![]),
+        allow_ffi: Some(vec![]),
+        allow_read: Some(vec![]),
+        allow_run: Some(vec![]),
+        allow_sys: Some(vec![]),
+        allow_write: Some(vec![]),
+        deny_env: Some(svec!["SECRET"]),
+        deny_net: Some(svec!["metadata.internal", "10.0.0.1:8080"]),
+        deny_read: Some(vec![PathBuf::from("/etc")]),
+        deny_run: Some(vec![]),
+        deny_sys: Some(svec!["networkInterfaces"]),
+        ..Flags::default()
+      }
+    );
+    assert_eq!(
+      flags.to_permission_args(),
+      svec![
+        "--allow-all",
+        "--deny-read=/etc",
+        "--deny-net=metadata.internal,10.0.0.1:8080",
+        "--deny-env=SECRET",
+        "--deny-run",
+        "--deny-sys=networkInterfaces",
+      ]
+    );
+
+    let r = flags_from_vec(svec!["deno", "run", "--deny-sys=foo", "script.ts"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn reload_validator() {
     let r = flags_from_vec(svec![
@@ -5506,6 +6788,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn conditions() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--conditions=development,react-server",
+      "--conditions=custom",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+        }),
+        node_conditions: svec!["development", "react-server", "custom"],
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn cached_only() {
     let r = flags_from_vec(svec!["deno", "run", "--cached-only", "script.ts"]);
@@ -5711,10 +7014,15 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("dir1/"), PathBuf::from("dir2/")],
             ignore: vec![],
+            no_ignore: false,
           },
           shuffle: None,
           concurrent_jobs: None,
           trace_ops: true,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         unstable: true,
         no_prompt: true,
@@ -5787,9 +7095,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: Some(NonZeroUsize::new(4).unwrap()),
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         no_prompt: true,
@@ -5817,9 +7130,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         no_prompt: true,
@@ -5851,9 +7169,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         no_prompt: true,
         type_check_mode: TypeCheckMode::Local,
@@ -5879,9 +7202,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         no_prompt: true,
         watch: None,
@@ -5907,9 +7235,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         no_prompt: true,
         type_check_mode: TypeCheckMode::Local,
@@ -5934,9 +7267,14 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("./")],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         no_prompt: true,
         type_check_mode: TypeCheckMode::Local,
@@ -5963,9 +7301,14 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
           concurrent_jobs: None,
           trace_ops: false,
+          debug_failures: None,
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
         }),
         watch: Some(vec![]),
         type_check_mode: TypeCheckMode::Local,
@@ -5976,6 +7319,111 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_debug_failures() {
+    let r =
+      flags_from_vec(svec!["deno", "test", "--watch", "--debug-failures"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          shuffle: None,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+            no_ignore: false,
+          },
+          concurrent_jobs: None,
+          trace_ops: false,
+          debug_failures: Some("127.0.0.1:9229".parse().unwrap()),
+          split_output: false,
+          reporter: TestReporterConfig::Pretty,
+          reporter_output: None,
+        }),
+        watch: Some(vec![]),
+        type_check_mode: TypeCheckMode::Local,
+        no_prompt: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--watch",
+      "--debug-failures=127.0.0.1:9230"
+    ]);
+    assert!(matches!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        debug_failures: Some(addr),
+        ..
+      }) if addr == "127.0.0.1:9230".parse().unwrap()
+    ));
+
+    let r = flags_from_vec(svec!["deno", "test", "--debug-failures"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--watch",
+      "--debug-failures",
+      "--inspect-brk"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_split_output() {
+    let r =
+      flags_from_vec(svec!["deno", "test", "--parallel", "--split-output"]);
+    assert!(matches!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        split_output: true,
+        ..
+      })
+    ));
+  }
+
+  #[test]
+  fn test_reporter() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "test",
+      "--reporter=junit",
+      "--reporter-output=report.xml"
+    ]);
+    assert!(matches!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        reporter: TestReporterConfig::Junit,
+        reporter_output: Some(path),
+        ..
+      }) if path == PathBuf::from("report.xml")
+    ));
+
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=tap"]);
+    assert!(matches!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        reporter: TestReporterConfig::Tap,
+        reporter_output: None,
+        ..
+      })
+    ));
+
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=xml"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "test", "--reporter-output=a.xml"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn bundle_with_cafile() {
     let r = flags_from_vec(svec![
@@ -6010,6 +7458,7 @@ mod tests {
           dry_run: false,
           canary: false,
           version: None,
+          pinned: false,
           output: None,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
@@ -6055,6 +7504,8 @@ mod tests {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
           file: Some("https://example.com".to_string()),
+          licenses: false,
+          graph_format: None,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -6211,7 +7662,9 @@ mod tests {
           output: None,
           args: vec![],
           target: None,
-          include: vec![]
+          include: vec![],
+          show: false,
+          code_cache: false,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -6231,7 +7684,9 @@ mod tests {
           output: Some(PathBuf::from("colors")),
           args: svec!["foo", "bar"],
           target: None,
-          include: vec![]
+          include: vec![],
+          show: false,
+          code_cache: false,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -6253,6 +7708,58 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compile_show() {
+    let r = flags_from_vec(svec!["deno", "compile", "--show", "./colors"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "./colors".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          include: vec![],
+          show: true,
+          code_cache: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno", "compile", "--show", "--output", "foo", "./colors"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn compile_code_cache() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--code-cache",
+      "https://deno.land/std/examples/colors.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "https://deno.land/std/examples/colors.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          include: vec![],
+          show: false,
+          code_cache: true,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn coverage() {
     let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
@@ -6263,11 +7770,14 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("foo.json")],
             ignore: vec![],
+            no_ignore: false,
           },
           output: None,
           include: vec![r"^file:".to_string()],
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           lcov: false,
+          changed_since: None,
+          changed_threshold: 80,
         }),
         ..Flags::default()
       }
@@ -6290,15 +7800,64 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("foo.json")],
             ignore: vec![],
+            no_ignore: false,
           },
           include: vec![r"^file:".to_string()],
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           lcov: true,
           output: Some(PathBuf::from("foo.lcov")),
+          changed_since: None,
+          changed_threshold: 80,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_changed_since() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--changed-since=main",
+      "--changed-threshold=90",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec![PathBuf::from("foo.json")],
+            ignore: vec![],
+            no_ignore: false,
+          },
+          output: None,
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          lcov: false,
+          changed_since: Some("main".to_string()),
+          changed_threshold: 90,
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--changed-threshold=90",
+      "foo.json"
+    ]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--changed-since=main",
+      "--changed-threshold=101",
+      "foo.json"
+    ]);
+    assert!(r.is_err());
   }
   #[test]
   fn location_with_bad_scheme() {
@@ -6360,6 +7919,7 @@ mod tests {
           specifiers: svec!["mod.ts"],
           force: false,
           output_path: None,
+          eszip_path: None,
         }),
         ..Flags::default()
       }
@@ -6391,6 +7951,7 @@ mod tests {
           specifiers: svec!["mod.ts", "deps.test.ts"],
           force: true,
           output_path: Some(PathBuf::from("out_dir")),
+          eszip_path: None,
         }),
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
         import_map_path: Some("import_map.json".to_string()),
@@ -6401,6 +7962,37 @@ mod tests {
     );
   }
 
+  #[test]
+  fn vendor_eszip() {
+    let r =
+      flags_from_vec(
+        svec!["deno", "vendor", "--eszip", "out.eszip", "mod.ts",],
+      );
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Vendor(VendorFlags {
+          specifiers: svec!["mod.ts"],
+          force: false,
+          output_path: None,
+          eszip_path: Some(PathBuf::from("out.eszip")),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "vendor",
+      "--eszip",
+      "out.eszip",
+      "--output",
+      "out_dir",
+      "mod.ts",
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn task_subcommand() {
     let r = flags_from_vec(svec!["deno", "task", "build", "hello", "world",]);
@@ -6441,6 +8033,22 @@ mod tests {
     );
   }
 
+  #[test]
+  fn task_split_output() {
+    let r = flags_from_vec(svec!["deno", "task", "--split-output", "dev"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("dev".to_string()),
+        }),
+        split_output: true,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn task_subcommand_double_hyphen() {
     let r = flags_from_vec(svec![
@@ -6637,6 +8245,7 @@ mod tests {
           files: FileFlags {
             include: vec![PathBuf::from("dir1/"), PathBuf::from("dir2/")],
             ignore: vec![],
+            no_ignore: false,
           },
         }),
         unstable: true,
@@ -6665,6 +8274,7 @@ mod tests {
           files: FileFlags {
             include: vec![],
             ignore: vec![],
+            no_ignore: false,
           },
         }),
         no_prompt: true,
@@ -1,12 +1,15 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 mod config_file;
+mod deno_version;
 mod flags;
 mod flags_allow_net;
 mod import_map;
 mod lockfile;
 pub mod package_json;
 
+pub use self::deno_version::discover_pinned_version;
+pub use self::deno_version::DENO_VERSION_FILE_NAME;
 pub use self::import_map::resolve_import_map_from_specifier;
 pub use self::lockfile::snapshot_from_lockfile;
 use self::package_json::PackageJsonDeps;
@@ -19,12 +22,14 @@ use deno_semver::npm::NpmPackageReqReference;
 use indexmap::IndexMap;
 
 pub use config_file::BenchConfig;
+pub use config_file::CheckConfig;
 pub use config_file::CompilerOptions;
 pub use config_file::ConfigFile;
 pub use config_file::EmitConfigOptions;
 pub use config_file::FilesConfig;
 pub use config_file::FmtOptionsConfig;
 pub use config_file::JsxImportSourceConfig;
+pub use config_file::LicensesConfig;
 pub use config_file::LintRulesConfig;
 pub use config_file::ProseWrap;
 pub use config_file::TsConfig;
@@ -47,11 +52,8 @@ use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_runtime::colors;
 use deno_runtime::deno_node::PackageJson;
-use deno_runtime::deno_tls::rustls;
 use deno_runtime::deno_tls::rustls::RootCertStore;
-use deno_runtime::deno_tls::rustls_native_certs::load_native_certs;
-use deno_runtime::deno_tls::rustls_pemfile;
-use deno_runtime::deno_tls::webpki_roots;
+use deno_runtime::deno_tls::CaStore;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::permissions::PermissionsOptions;
 use once_cell::sync::Lazy;
@@ -70,6 +72,8 @@ use thiserror::Error;
 use crate::file_fetcher::FileFetcher;
 use crate::npm::CliNpmRegistryApi;
 use crate::npm::NpmProcessState;
+use crate::util::file_watcher::RestartHook;
+use crate::util::file_watcher::RestartHooks;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::version;
 
@@ -95,6 +99,12 @@ pub enum CacheSetting {
   /// headers and other metadata associated with a cached response, reloading
   /// any cached "non-fresh" cached responses.
   RespectHeaders,
+  /// Cached remote modules should be used right away, while they are
+  /// revalidated in the background so that changes are picked up on the next
+  /// run, except for the listed ones which are reloaded. This is the
+  /// equivalent of `--reload=stale` or
+  /// `--reload=stale,https://deno.land/std` in the CLI.
+  Stale(Vec<String>),
   /// The cached source files should be used for local modules.  This is the
   /// default behavior of the CLI.
   Use,
@@ -104,7 +114,7 @@ impl CacheSetting {
   pub fn should_use_for_npm_package(&self, package_name: &str) -> bool {
     match self {
       CacheSetting::ReloadAll => false,
-      CacheSetting::ReloadSome(list) => {
+      CacheSetting::ReloadSome(list) | CacheSetting::Stale(list) => {
         if list.iter().any(|i| i == "npm:") {
           return false;
         }
@@ -148,7 +158,12 @@ impl BenchOptions {
 #[derive(Clone, Debug, Default)]
 pub struct FmtOptions {
   pub is_stdin: bool,
+  /// Format the files read as JSON lines from stdin, see `--stdin-batch`.
+  pub is_stdin_batch: bool,
   pub check: bool,
+  pub diff_format: FmtDiffFormat,
+  /// Number of unchanged lines shown around changes in unified and JSON diffs.
+  pub diff_context: usize,
   pub options: FmtOptionsConfig,
   pub files: FilesConfig,
 }
@@ -174,7 +189,19 @@ impl FmtOptions {
 
     Ok(Self {
       is_stdin,
+      is_stdin_batch: maybe_fmt_flags
+        .as_ref()
+        .map(|f| f.stdin_batch)
+        .unwrap_or(false),
       check: maybe_fmt_flags.as_ref().map(|f| f.check).unwrap_or(false),
+      diff_format: maybe_fmt_flags
+        .as_ref()
+        .map(|f| f.diff_format)
+        .unwrap_or_default(),
+      diff_context: maybe_fmt_flags
+        .as_ref()
+        .map(|f| f.diff_context)
+        .unwrap_or(3),
       options: resolve_fmt_options(
         maybe_fmt_flags.as_ref(),
         maybe_config_options,
@@ -239,6 +266,10 @@ pub struct TestOptions {
   pub shuffle: Option<u64>,
   pub concurrent_jobs: NonZeroUsize,
   pub trace_ops: bool,
+  pub debug_failures: Option<SocketAddr>,
+  pub split_output: bool,
+  pub reporter: TestReporterConfig,
+  pub reporter_output: Option<PathBuf>,
 }
 
 impl TestOptions {
@@ -263,6 +294,10 @@ impl TestOptions {
       no_run: test_flags.no_run,
       shuffle: test_flags.shuffle,
       trace_ops: test_flags.trace_ops,
+      debug_failures: test_flags.debug_failures,
+      split_output: test_flags.split_output,
+      reporter: test_flags.reporter,
+      reporter_output: test_flags.reporter_output,
     })
   }
 }
@@ -273,6 +308,7 @@ pub enum LintReporterKind {
   Pretty,
   Json,
   Compact,
+  Sarif,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -306,6 +342,8 @@ impl LintOptions {
           Some(LintReporterKind::Json)
         } else if lint_flags.compact {
           Some(LintReporterKind::Compact)
+        } else if lint_flags.sarif {
+          Some(LintReporterKind::Sarif)
         } else {
           None
         }
@@ -318,6 +356,7 @@ impl LintOptions {
           Some("json") => Some(LintReporterKind::Json),
           Some("compact") => Some(LintReporterKind::Compact),
           Some("pretty") => Some(LintReporterKind::Pretty),
+          Some("sarif") => Some(LintReporterKind::Sarif),
           Some(_) => {
             bail!("Invalid lint report type in config file")
           }
@@ -406,7 +445,7 @@ fn discover_package_json(
 }
 
 struct CliRootCertStoreProvider {
-  cell: OnceCell<RootCertStore>,
+  cell: OnceCell<CaStore>,
   maybe_root_path: Option<PathBuf>,
   maybe_ca_stores: Option<Vec<String>>,
   maybe_ca_data: Option<CaData>,
@@ -429,15 +468,20 @@ impl CliRootCertStoreProvider {
 
 impl RootCertStoreProvider for CliRootCertStoreProvider {
   fn get_or_try_init(&self) -> Result<&RootCertStore, AnyError> {
+    Ok(self.get_ca_store()?.unwrap().root_cert_store())
+  }
+
+  fn get_ca_store(&self) -> Result<Option<&CaStore>, AnyError> {
     self
       .cell
       .get_or_try_init(|| {
-        get_root_cert_store(
+        get_ca_store(
           self.maybe_root_path.clone(),
           self.maybe_ca_stores.clone(),
           self.maybe_ca_data.clone(),
         )
       })
+      .map(Some)
       .map_err(|e| e.into())
   }
 }
@@ -461,7 +505,22 @@ pub fn get_root_cert_store(
   maybe_ca_stores: Option<Vec<String>>,
   maybe_ca_data: Option<CaData>,
 ) -> Result<RootCertStore, RootCertStoreLoadError> {
-  let mut root_cert_store = RootCertStore::empty();
+  get_ca_store(maybe_root_path, maybe_ca_stores, maybe_ca_data)
+    .map(CaStore::into_root_cert_store)
+}
+
+/// Create and populate the CA store shared by `fetch` and `node:tls` based on
+/// the passed options and environment.
+///
+/// Certificates from `NODE_EXTRA_CA_CERTS` are added on top of the ones from
+/// `--cert` or `DENO_CERT`. Like in Node, a missing or unreadable file only
+/// results in a warning.
+pub fn get_ca_store(
+  maybe_root_path: Option<PathBuf>,
+  maybe_ca_stores: Option<Vec<String>>,
+  maybe_ca_data: Option<CaData>,
+) -> Result<CaStore, RootCertStoreLoadError> {
+  let mut ca_store = CaStore::empty();
   let ca_stores: Vec<String> = maybe_ca_stores
     .or_else(|| {
       let env_ca_store = env::var("DENO_TLS_CA_STORE").ok()?;
@@ -478,23 +537,12 @@ pub fn get_root_cert_store(
   for store in ca_stores.iter() {
     match store.as_str() {
       "mozilla" => {
-        root_cert_store.add_server_trust_anchors(
-          webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
-            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
-              ta.subject,
-              ta.spki,
-              ta.name_constraints,
-            )
-          }),
-        );
+        ca_store.add_bundled();
       }
       "system" => {
-        let roots = load_native_certs().expect("could not load platform certs");
-        for root in roots {
-          root_cert_store
-            .add(&rustls::Certificate(root.0))
-            .expect("Failed to add platform cert to root cert store");
-        }
+        ca_store
+          .add_system()
+          .expect("could not load platform certs");
       }
       _ => {
         return Err(RootCertStoreLoadError::UnknownStore(store.clone()));
@@ -516,25 +564,33 @@ pub fn get_root_cert_store(
           RootCertStoreLoadError::CaFileOpenError(err.to_string())
         })?;
         let mut reader = BufReader::new(certfile);
-        rustls_pemfile::certs(&mut reader)
+        ca_store.add_extra_pem(&mut reader)
       }
       CaData::Bytes(data) => {
         let mut reader = BufReader::new(Cursor::new(data));
-        rustls_pemfile::certs(&mut reader)
+        ca_store.add_extra_pem(&mut reader)
       }
     };
 
-    match result {
-      Ok(certs) => {
-        root_cert_store.add_parsable_certificates(&certs);
-      }
-      Err(e) => {
-        return Err(RootCertStoreLoadError::FailedAddPemFile(e.to_string()));
-      }
+    if let Err(e) = result {
+      return Err(RootCertStoreLoadError::FailedAddPemFile(e.to_string()));
     }
   }
 
-  Ok(root_cert_store)
+  if let Some(extra_ca_file) = env::var_os("NODE_EXTRA_CA_CERTS") {
+    let result = std::fs::File::open(&extra_ca_file)
+      .and_then(|file| ca_store.add_extra_pem(&mut BufReader::new(file)));
+    if let Err(err) = result {
+      log::warn!(
+        "{} Ignoring extra certs from `{}`, load failed: {}",
+        colors::yellow("Warning"),
+        PathBuf::from(extra_ca_file).display(),
+        err,
+      );
+    }
+  }
+
+  Ok(ca_store)
 }
 
 const RESOLUTION_STATE_ENV_VAR_NAME: &str =
@@ -593,6 +649,16 @@ impl CliOptions {
       eprintln!("{}", colors::yellow(msg));
     }
 
+    if matches!(
+      flags.subcommand,
+      DenoSubcommand::Run(_) | DenoSubcommand::Test(_)
+    ) {
+      deno_version::check_deno_version(
+        maybe_config_file.as_ref(),
+        &initial_cwd,
+      )?;
+    }
+
     let maybe_node_modules_folder = resolve_local_node_modules_folder(
       &initial_cwd,
       &flags,
@@ -678,6 +744,8 @@ impl CliOptions {
   pub fn cache_setting(&self) -> CacheSetting {
     if self.flags.cached_only {
       CacheSetting::Only
+    } else if self.flags.reload_stale {
+      CacheSetting::Stale(self.flags.cache_blocklist.clone())
     } else if !self.flags.cache_blocklist.is_empty() {
       CacheSetting::ReloadSome(self.flags.cache_blocklist.clone())
     } else if self.flags.reload {
@@ -884,6 +952,19 @@ impl CliOptions {
     })
   }
 
+  /// Custom conditions used when resolving the "exports" and "imports" of
+  /// npm packages, on top of the default ones.
+  pub fn node_conditions(&self) -> Vec<String> {
+    if !self.flags.node_conditions.is_empty() {
+      return self.flags.node_conditions.clone();
+    }
+    self
+      .maybe_config_file
+      .as_ref()
+      .and_then(|c| c.node_conditions())
+      .unwrap_or_default()
+  }
+
   pub fn node_modules_dir_specifier(&self) -> Option<ModuleSpecifier> {
     self
       .maybe_node_modules_folder
@@ -957,6 +1038,24 @@ impl CliOptions {
     }
   }
 
+  pub fn resolve_check_config(&self) -> Result<CheckConfig, AnyError> {
+    match &self.maybe_config_file {
+      Some(config_file) => {
+        Ok(config_file.to_check_config()?.unwrap_or_default())
+      }
+      None => Ok(Default::default()),
+    }
+  }
+
+  pub fn resolve_licenses_config(&self) -> Result<LicensesConfig, AnyError> {
+    match &self.maybe_config_file {
+      Some(config_file) => {
+        Ok(config_file.to_licenses_config()?.unwrap_or_default())
+      }
+      None => Ok(Default::default()),
+    }
+  }
+
   pub fn maybe_config_file(&self) -> &Option<ConfigFile> {
     &self.maybe_config_file
   }
@@ -1090,6 +1189,22 @@ impl CliOptions {
     self.flags.inspect_brk
   }
 
+  /// Creates the options used by `deno test --debug-failures` to re-run a
+  /// failed test, which pause on the first statement until a debugger
+  /// connects to `host`.
+  pub fn with_inspect_brk(&self, host: SocketAddr) -> Result<Self, AnyError> {
+    let mut flags = self.flags.clone();
+    flags.inspect_brk = Some(host);
+    flags.watch = None;
+    Self::new(
+      flags,
+      self.initial_cwd.clone(),
+      self.maybe_config_file.clone(),
+      self.maybe_lockfile.clone(),
+      self.maybe_package_json.clone(),
+    )
+  }
+
   pub fn inspect_wait(&self) -> Option<SocketAddr> {
     self.flags.inspect_wait
   }
@@ -1105,6 +1220,27 @@ impl CliOptions {
       .unwrap_or(false)
   }
 
+  /// The eszip archive to load modules from, from `--import-source`.
+  pub fn import_source(&self) -> Option<PathBuf> {
+    self
+      .flags
+      .import_source
+      .as_ref()
+      .map(|path| self.initial_cwd.join(path))
+  }
+
+  /// Whether `BroadcastChannel` also reaches other processes, from
+  /// `--broadcast-channel-ipc`.
+  pub fn broadcast_channel_ipc(&self) -> bool {
+    self.flags.broadcast_channel_ipc
+  }
+
+  /// Whether the output of the things that run at the same time is kept
+  /// apart, from `--split-output` of `deno run` and `deno task`.
+  pub fn split_output(&self) -> bool {
+    self.flags.split_output
+  }
+
   pub fn location_flag(&self) -> &Option<Url> {
     &self.flags.location
   }
@@ -1135,10 +1271,18 @@ impl CliOptions {
       allow_hrtime: self.flags.allow_hrtime,
       allow_net: self.flags.allow_net.clone(),
       allow_ffi: self.flags.allow_ffi.clone(),
+      allow_ffi_symbols: self.flags.allow_ffi_symbols.clone(),
       allow_read: self.flags.allow_read.clone(),
       allow_run: self.flags.allow_run.clone(),
       allow_sys: self.flags.allow_sys.clone(),
       allow_write: self.flags.allow_write.clone(),
+      deny_env: self.flags.deny_env.clone(),
+      deny_net: self.flags.deny_net.clone(),
+      deny_ffi: self.flags.deny_ffi.clone(),
+      deny_read: self.flags.deny_read.clone(),
+      deny_run: self.flags.deny_run.clone(),
+      deny_sys: self.flags.deny_sys.clone(),
+      deny_write: self.flags.deny_write.clone(),
       prompt: !self.no_prompt(),
     }
   }
@@ -1174,6 +1318,67 @@ impl CliOptions {
   pub fn watch_paths(&self) -> &Option<Vec<PathBuf>> {
     &self.flags.watch
   }
+
+  /// Resolves the tasks or commands to run around restarts in watch mode.
+  /// Flags take precedence over the "watch" configuration.
+  pub fn resolve_watch_restart_hooks(&self) -> Result<RestartHooks, AnyError> {
+    let (watch_config, tasks_config) = match &self.maybe_config_file {
+      Some(config_file) => (
+        config_file.to_watch_config()?.unwrap_or_default(),
+        config_file.to_tasks_config()?.unwrap_or_default(),
+      ),
+      None => Default::default(),
+    };
+    let resolve_hooks = |flag_entries: &Vec<String>,
+                         config_entries: &Vec<String>| {
+      let entries = if flag_entries.is_empty() {
+        config_entries
+      } else {
+        flag_entries
+      };
+      entries
+        .iter()
+        .map(|entry| match tasks_config.get(entry) {
+          Some(script) => RestartHook {
+            name: entry.clone(),
+            command: script.clone(),
+          },
+          None => RestartHook {
+            name: entry
+              .split_whitespace()
+              .next()
+              .unwrap_or(entry.as_str())
+              .to_string(),
+            command: entry.clone(),
+          },
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let cwd = match self.maybe_config_file_specifier() {
+      Some(specifier) if specifier.scheme() == "file" => specifier
+        .to_file_path()
+        .ok()
+        .and_then(|path| path.parent().map(|p| p.to_path_buf())),
+      _ => None,
+    };
+    let cwd = match cwd {
+      Some(cwd) => cwd,
+      None => std::env::current_dir()?,
+    };
+
+    Ok(RestartHooks {
+      pre_restart: resolve_hooks(
+        &self.flags.watch_pre_restart,
+        &watch_config.pre_restart,
+      ),
+      post_restart: resolve_hooks(
+        &self.flags.watch_post_restart,
+        &watch_config.post_restart,
+      ),
+      cwd,
+    })
+  }
 }
 
 /// Resolves the path to use for a local node_modules folder.
@@ -1363,6 +1568,7 @@ fn resolve_files(
     if !file_flags.ignore.is_empty() {
       result.exclude = file_flags.ignore;
     }
+    result.no_ignore = file_flags.no_ignore;
   }
   // Now expand globs if there are any
   if !result.include.is_empty() {
@@ -1588,6 +1794,7 @@ mod test {
       Some(FilesConfig {
         include: vec![temp_dir.path().join("data/**********.ts")],
         exclude: vec![],
+        no_ignore: false,
       }),
       None,
     )
@@ -1603,6 +1810,7 @@ mod test {
           temp_dir.path().join("pages/[id].ts"),
         ],
         exclude: vec![temp_dir.path().join("nested/**/*bazz.ts")],
+        no_ignore: false,
       }),
       None,
     )
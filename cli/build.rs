@@ -341,6 +341,7 @@ fn create_cli_snapshot(snapshot_path: PathBuf) -> CreateSnapshotOutput {
     deno_web::deno_web::init_ops::<PermissionsContainer>(
       deno_web::BlobStore::default(),
       Default::default(),
+      false, // No --unstable.
     ),
     deno_fetch::deno_fetch::init_ops::<PermissionsContainer>(Default::default()),
     deno_cache::deno_cache::init_ops::<SqliteBackedCache>(None),
@@ -352,7 +353,7 @@ fn create_cli_snapshot(snapshot_path: PathBuf) -> CreateSnapshotOutput {
     deno_webstorage::deno_webstorage::init_ops(None),
     deno_crypto::deno_crypto::init_ops(None),
     deno_broadcast_channel::deno_broadcast_channel::init_ops(
-      deno_broadcast_channel::InMemoryBroadcastChannel::default(),
+      deno_broadcast_channel::RuntimeBroadcastChannel::default(),
       false, // No --unstable.
     ),
     deno_ffi::deno_ffi::init_ops::<PermissionsContainer>(false),
@@ -369,7 +370,11 @@ fn create_cli_snapshot(snapshot_path: PathBuf) -> CreateSnapshotOutput {
     deno_http::deno_http::init_ops::<DefaultHttpPropertyExtractor>(),
     deno_io::deno_io::init_ops(Default::default()),
     deno_fs::deno_fs::init_ops::<PermissionsContainer>(false, fs.clone()),
-    deno_node::deno_node::init_ops::<PermissionsContainer>(None, fs),
+    deno_node::deno_node::init_ops::<PermissionsContainer>(
+      None,
+      fs,
+      Vec::new(),
+    ),
     cli::init_ops_and_esm(), // NOTE: This needs to be init_ops_and_esm!
   ];
 
@@ -3,6 +3,7 @@
 use super::cache_db::CacheDB;
 use super::cache_db::CacheDBConfiguration;
 use super::cache_db::CacheFailure;
+use crate::args::TypeCheckMode;
 use deno_ast::ModuleSpecifier;
 use deno_core::error::AnyError;
 use deno_runtime::deno_webstorage::rusqlite::params;
@@ -16,16 +17,37 @@ pub static TYPE_CHECK_CACHE_DB: CacheDBConfiguration = CacheDBConfiguration {
       specifier TEXT PRIMARY KEY,
       text TEXT NOT NULL
     );",
+    "CREATE TABLE IF NOT EXISTS checkmodules (
+      specifier TEXT PRIMARY KEY,
+      type_check_mode TEXT NOT NULL,
+      source_hash TEXT NOT NULL,
+      config_hash TEXT NOT NULL,
+      dependencies_hash TEXT NOT NULL
+    );",
   ),
   on_version_change: concat!(
     "DELETE FROM checkcache;",
-    "DELETE FROM tsbuildinfo;"
+    "DELETE FROM tsbuildinfo;",
+    "DELETE FROM checkmodules;"
   ),
   preheat_queries: &[],
   // If the cache fails, just ignore all caching attempts
   on_failure: CacheFailure::Blackhole,
 };
 
+/// The inputs a module was type checked with, which tell why it has to be
+/// type checked again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleCheckHashes {
+  /// The hash of the module's specifier and source.
+  pub source: u64,
+  /// The hash of the compiler options.
+  pub config: u64,
+  /// The hash of the sources of the type checked modules the module
+  /// depends on, directly or not.
+  pub dependencies: u64,
+}
+
 /// The cache used to tell whether type checking should occur again.
 ///
 /// This simply stores a hash of the inputs of each successful type check
@@ -101,6 +123,103 @@ impl TypeCheckCache {
     }
   }
 
+  /// Gets the inputs of the last successful type check that included the
+  /// module, along with the mode it was type checked with.
+  pub fn get_module_check_hashes(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<(TypeCheckMode, ModuleCheckHashes)> {
+    let sql = "
+    SELECT
+      type_check_mode, source_hash, config_hash, dependencies_hash
+    FROM
+      checkmodules
+    WHERE
+      specifier=?1
+    LIMIT 1";
+    let parse = |text: String| text.parse::<u64>().ok();
+    self
+      .0
+      .query_row(sql, params![specifier.to_string()], |row| {
+        let type_check_mode = match row.get::<_, String>(0)?.as_str() {
+          "all" => TypeCheckMode::All,
+          "local" => TypeCheckMode::Local,
+          _ => return Ok(None),
+        };
+        Ok(
+          parse(row.get(1)?)
+            .zip(parse(row.get(2)?))
+            .zip(parse(row.get(3)?))
+            .map(|((source, config), dependencies)| {
+              let hashes = ModuleCheckHashes {
+                source,
+                config,
+                dependencies,
+              };
+              (type_check_mode, hashes)
+            }),
+        )
+      })
+      .ok()??
+  }
+
+  /// Stores the inputs of the modules of a successful type check, at once.
+  pub fn set_module_check_hashes<'a>(
+    &self,
+    type_check_mode: TypeCheckMode,
+    modules: impl IntoIterator<Item = (&'a ModuleSpecifier, &'a ModuleCheckHashes)>,
+  ) {
+    let type_check_mode = match type_check_mode {
+      TypeCheckMode::All => "all",
+      TypeCheckMode::Local => "local",
+      // nothing was type checked
+      TypeCheckMode::None => return,
+    };
+    let result = self.set_module_check_hashes_result(type_check_mode, modules);
+    if let Err(err) = result {
+      if cfg!(debug_assertions) {
+        panic!("Error saving module check hashes: {err}");
+      } else {
+        log::debug!("Error saving module check hashes: {}", err);
+      }
+    }
+  }
+
+  fn set_module_check_hashes_result<'a>(
+    &self,
+    type_check_mode: &str,
+    modules: impl IntoIterator<Item = (&'a ModuleSpecifier, &'a ModuleCheckHashes)>,
+  ) -> Result<(), AnyError> {
+    let sql = "
+    INSERT OR REPLACE INTO
+      checkmodules (
+        specifier,
+        type_check_mode,
+        source_hash,
+        config_hash,
+        dependencies_hash
+      )
+    VALUES
+      (?1, ?2, ?3, ?4, ?5)";
+    self.0.with_connection(|conn| {
+      let transaction = conn.unchecked_transaction()?;
+      {
+        let mut stmt = transaction.prepare_cached(sql)?;
+        for (specifier, hashes) in modules {
+          stmt.execute(params![
+            specifier.to_string(),
+            type_check_mode,
+            hashes.source.to_string(),
+            hashes.config.to_string(),
+            hashes.dependencies.to_string(),
+          ])?;
+        }
+      }
+      transaction.commit()?;
+      Ok(())
+    })
+  }
+
   fn set_tsbuildinfo_result(
     &self,
     specifier: &ModuleSpecifier,
@@ -133,6 +252,32 @@ mod test {
     cache.set_tsbuildinfo(&specifier1, "test");
     assert_eq!(cache.get_tsbuildinfo(&specifier1), Some("test".to_string()));
 
+    let hashes = ModuleCheckHashes {
+      source: 1,
+      config: 2,
+      dependencies: 3,
+    };
+    let specifier2 = ModuleSpecifier::parse("file:///test.ts").unwrap();
+    assert_eq!(cache.get_module_check_hashes(&specifier1), None);
+    cache.set_module_check_hashes(
+      TypeCheckMode::Local,
+      [(&specifier1, &hashes), (&specifier2, &hashes)],
+    );
+    assert_eq!(
+      cache.get_module_check_hashes(&specifier1),
+      Some((TypeCheckMode::Local, hashes))
+    );
+    // the mode is stored per module
+    cache.set_module_check_hashes(TypeCheckMode::All, [(&specifier2, &hashes)]);
+    assert_eq!(
+      cache.get_module_check_hashes(&specifier1),
+      Some((TypeCheckMode::Local, hashes))
+    );
+    assert_eq!(
+      cache.get_module_check_hashes(&specifier2),
+      Some((TypeCheckMode::All, hashes))
+    );
+
     // try changing the cli version (should clear)
     let conn = cache.0.recreate_with_version("2.0.0");
     let cache = TypeCheckCache::new(conn);
@@ -141,6 +286,7 @@ mod test {
     cache.add_check_hash(1);
     assert!(cache.has_check_hash(1));
     assert_eq!(cache.get_tsbuildinfo(&specifier1), None);
+    assert_eq!(cache.get_module_check_hashes(&specifier1), None);
     cache.set_tsbuildinfo(&specifier1, "test");
     assert_eq!(cache.get_tsbuildinfo(&specifier1), Some("test".to_string()));
 
@@ -2,6 +2,8 @@
 
 use std::hash::Hasher;
 
+use serde::Serialize;
+
 /// A very fast insecure hasher that uses the xxHash algorithm.
 #[derive(Default)]
 pub struct FastInsecureHasher(twox_hash::XxHash64);
@@ -43,3 +45,27 @@ impl FastInsecureHasher {
     self.0.finish()
   }
 }
+
+/// Whether the artifacts a cache keeps for a module can be used as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "camelCase")]
+pub enum CacheStatus {
+  Cached,
+  /// Nothing was ever cached for the module.
+  Missing,
+  Stale(StaleReason),
+}
+
+/// Why the artifacts a cache keeps for a module are out of date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StaleReason {
+  SourceChanged,
+  /// The options the artifacts were created with changed, like the
+  /// compiler options of the config file.
+  ConfigChanged,
+  /// One of the modules the module depends on changed.
+  DependencyChanged,
+  /// The artifacts were created by another version of Deno, or modified.
+  DenoVersionChanged,
+}
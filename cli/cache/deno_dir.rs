@@ -81,6 +81,12 @@ impl DenoDir {
     self.root.display()
   }
 
+  /// Path of the socket, or name of the named pipe on Windows, that the
+  /// processes using this DENO_DIR exchange `BroadcastChannel` messages on.
+  pub fn broadcast_channel_socket_path(&self) -> PathBuf {
+    deno_runtime::deno_broadcast_channel::socket_path_for_deno_dir(&self.root)
+  }
+
   /// Path for the incremental cache used for formatting.
   pub fn fmt_incremental_cache_db_file_path(&self) -> PathBuf {
     // bump this version name to invalidate the entire cache
@@ -111,6 +117,13 @@ impl DenoDir {
     self.root.join("check_cache_v1")
   }
 
+  /// Folder for the V8 code cache of executables created by
+  /// `deno compile --code-cache`.
+  pub fn compile_code_cache_folder_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("compile_code_cache_v1")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
@@ -9,13 +9,19 @@ use deno_core::serde_json;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::CacheStatus;
 use super::DiskCache;
 use super::FastInsecureHasher;
+use super::StaleReason;
 
 #[derive(Debug, Deserialize, Serialize)]
 struct EmitMetadata {
   pub source_hash: String,
   pub emit_hash: String,
+  /// The hash of the emit options, which is part of the source hash. Missing
+  /// from the metadata written by older CLI releases.
+  #[serde(default)]
+  pub options_hash: Option<String>,
 }
 
 /// The cache that stores previously emitted files.
@@ -67,6 +73,42 @@ impl EmitCache {
     Some(emit_text)
   }
 
+  /// Tells whether `get_emit_code` would return the emit, and if not, why.
+  /// `options_hash` is the hash of the emit options that is part of the
+  /// expected source hash.
+  pub fn get_emit_status(
+    &self,
+    specifier: &ModuleSpecifier,
+    expected_source_hash: u64,
+    options_hash: u64,
+  ) -> CacheStatus {
+    let meta = self
+      .get_meta_filename(specifier)
+      .and_then(|filename| self.disk_cache.get(&filename).ok())
+      .and_then(|bytes| serde_json::from_slice::<EmitMetadata>(&bytes).ok());
+    let emit_bytes = self
+      .get_emit_filename(specifier)
+      .and_then(|filename| self.disk_cache.get(&filename).ok());
+    let (Some(meta), Some(emit_bytes)) = (meta, emit_bytes) else {
+      return CacheStatus::Missing;
+    };
+    if meta.source_hash != expected_source_hash.to_string() {
+      let options_changed = meta
+        .options_hash
+        .map(|hash| hash != options_hash.to_string())
+        .unwrap_or(false);
+      return CacheStatus::Stale(if options_changed {
+        StaleReason::ConfigChanged
+      } else {
+        StaleReason::SourceChanged
+      });
+    }
+    if meta.emit_hash != compute_emit_hash(&emit_bytes, self.cli_version) {
+      return CacheStatus::Stale(StaleReason::DenoVersionChanged);
+    }
+    CacheStatus::Cached
+  }
+
   /// Gets the filepath which stores the emit.
   pub fn get_emit_filepath(
     &self,
@@ -85,9 +127,12 @@ impl EmitCache {
     &self,
     specifier: &ModuleSpecifier,
     source_hash: u64,
+    options_hash: u64,
     code: &str,
   ) {
-    if let Err(err) = self.set_emit_code_result(specifier, source_hash, code) {
+    if let Err(err) =
+      self.set_emit_code_result(specifier, source_hash, options_hash, code)
+    {
       // should never error here, but if it ever does don't fail
       if cfg!(debug_assertions) {
         panic!("Error saving emit data ({specifier}): {err}");
@@ -101,6 +146,7 @@ impl EmitCache {
     &self,
     specifier: &ModuleSpecifier,
     source_hash: u64,
+    options_hash: u64,
     code: &str,
   ) -> Result<(), AnyError> {
     let meta_filename = self
@@ -114,6 +160,7 @@ impl EmitCache {
     let metadata = EmitMetadata {
       source_hash: source_hash.to_string(),
       emit_hash: compute_emit_hash(code.as_bytes(), self.cli_version),
+      options_hash: Some(options_hash.to_string()),
     };
     self
       .disk_cache
@@ -174,8 +221,8 @@ mod test {
     assert_eq!(cache.get_emit_code(&specifier1, 1), None);
     let emit_code1 = "text1".to_string();
     let emit_code2 = "text2".to_string();
-    cache.set_emit_code(&specifier1, 10, &emit_code1);
-    cache.set_emit_code(&specifier2, 2, &emit_code2);
+    cache.set_emit_code(&specifier1, 10, 1, &emit_code1);
+    cache.set_emit_code(&specifier2, 2, 1, &emit_code2);
     // providing the incorrect source hash
     assert_eq!(cache.get_emit_code(&specifier1, 5), None);
     // providing the correct source hash
@@ -191,7 +238,7 @@ mod test {
       cli_version: "2.0.0",
     };
     assert_eq!(cache.get_emit_code(&specifier1, 10), None);
-    cache.set_emit_code(&specifier1, 5, &emit_code1);
+    cache.set_emit_code(&specifier1, 5, 1, &emit_code1);
 
     // recreating the cache should still load the data because the CLI version is the same
     let cache = EmitCache {
@@ -202,8 +249,48 @@ mod test {
 
     // adding when already exists should not cause issue
     let emit_code3 = "asdf".to_string();
-    cache.set_emit_code(&specifier1, 20, &emit_code3);
+    cache.set_emit_code(&specifier1, 20, 1, &emit_code3);
     assert_eq!(cache.get_emit_code(&specifier1, 5), None);
     assert_eq!(cache.get_emit_code(&specifier1, 20), Some(emit_code3));
   }
+
+  #[test]
+  pub fn emit_cache_status() {
+    let temp_dir = TempDir::new();
+    let disk_cache = DiskCache::new(temp_dir.path());
+    let cache = EmitCache {
+      disk_cache: disk_cache.clone(),
+      cli_version: "1.0.0",
+    };
+
+    let specifier =
+      ModuleSpecifier::from_file_path(temp_dir.path().join("file1.ts"))
+        .unwrap();
+    assert_eq!(
+      cache.get_emit_status(&specifier, 10, 1),
+      CacheStatus::Missing
+    );
+    cache.set_emit_code(&specifier, 10, 1, "text1");
+    assert_eq!(
+      cache.get_emit_status(&specifier, 10, 1),
+      CacheStatus::Cached
+    );
+    assert_eq!(
+      cache.get_emit_status(&specifier, 11, 1),
+      CacheStatus::Stale(StaleReason::SourceChanged)
+    );
+    assert_eq!(
+      cache.get_emit_status(&specifier, 11, 2),
+      CacheStatus::Stale(StaleReason::ConfigChanged)
+    );
+
+    let cache = EmitCache {
+      disk_cache,
+      cli_version: "2.0.0",
+    };
+    assert_eq!(
+      cache.get_emit_status(&specifier, 10, 1),
+      CacheStatus::Stale(StaleReason::DenoVersionChanged)
+    );
+  }
 }
@@ -0,0 +1,135 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use deno_ast::MediaType;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::futures::executor::block_on;
+use deno_core::futures::io::BufReader;
+use deno_core::serde::Deserialize;
+use deno_core::serde::Serialize;
+use deno_core::serde_json;
+use deno_core::ModuleSpecifier;
+
+/// The specifier the sources of the modules are stored under in the archive.
+const SOURCES_SPECIFIER: &str = "deno:import-source/sources.json";
+
+/// The original sources of the modules of an archive and the redirects
+/// between them, as the modules themselves are stored emitted.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSourceSources {
+  pub modules: BTreeMap<ModuleSpecifier, ImportSourceModule>,
+  pub redirects: BTreeMap<ModuleSpecifier, ModuleSpecifier>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSourceModule {
+  pub content_type: String,
+  pub source: Arc<str>,
+}
+
+impl ImportSourceModule {
+  pub fn new(media_type: MediaType, source: Arc<str>) -> Self {
+    let content_type = match media_type {
+      MediaType::JavaScript | MediaType::Mjs | MediaType::Cjs => {
+        "application/javascript"
+      }
+      MediaType::Jsx => "text/jsx",
+      MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Cts
+      | MediaType::Dts
+      | MediaType::Dmts
+      | MediaType::Dcts => "application/typescript",
+      MediaType::Tsx => "text/tsx",
+      MediaType::Json => "application/json",
+      MediaType::Wasm => "application/wasm",
+      MediaType::TsBuildInfo | MediaType::SourceMap | MediaType::Unknown => {
+        "application/octet-stream"
+      }
+    };
+    Self {
+      content_type: content_type.to_string(),
+      source,
+    }
+  }
+}
+
+impl ImportSourceSources {
+  /// Adds the sources to the archive.
+  pub fn add_to_eszip(&self, eszip: &mut eszip::EszipV2) {
+    let sources = serde_json::to_vec(self).unwrap();
+    eszip.add_import_map(
+      eszip::ModuleKind::Json,
+      SOURCES_SPECIFIER.to_string(),
+      Arc::from(sources),
+    );
+  }
+}
+
+/// An eszip archive made by `deno vendor --eszip`, which is provided with
+/// `--import-source` and that the file fetcher loads modules from instead of
+/// fetching them.
+///
+/// The original sources of the modules are loaded, rather than the emitted
+/// ones, so that they are checked against the lockfile and type checked like
+/// modules that were fetched.
+pub struct ImportSource {
+  sources: ImportSourceSources,
+}
+
+impl ImportSource {
+  pub fn load(path: &Path) -> Result<Self, AnyError> {
+    let bytes = std::fs::read(path)
+      .with_context(|| format!("Opening import source {}", path.display()))?;
+    // the archive is read from memory, so parsing it never has to wait
+    let sources = block_on(async {
+      let (eszip, loader) =
+        eszip::EszipV2::parse(BufReader::new(bytes.as_slice())).await?;
+      loader.await?;
+      let module =
+        eszip.get_import_map(SOURCES_SPECIFIER).ok_or_else(|| {
+          anyhow!("It was not made by `deno vendor --eszip` of this version.")
+        })?;
+      let sources = module
+        .source()
+        .await
+        .ok_or_else(|| anyhow!("The sources of its modules are missing."))?;
+      Ok::<_, AnyError>(serde_json::from_slice(&sources)?)
+    })
+    .with_context(|| {
+      format!("Failed to parse import source {}", path.display())
+    })?;
+    Ok(Self { sources })
+  }
+
+  /// Gets the module for the specifier, following the redirects stored in
+  /// the archive. Returns the final specifier along with the module.
+  pub fn get(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<(&ModuleSpecifier, &ImportSourceModule)> {
+    let mut specifier = specifier;
+    // the redirects were resolved when the archive was made, but a broken
+    // archive shouldn't hang
+    for _ in 0..10 {
+      match self.sources.redirects.get(specifier) {
+        Some(redirect) => specifier = redirect,
+        None => break,
+      }
+    }
+    self.sources.modules.get_key_value(specifier)
+  }
+}
+
+impl std::fmt::Debug for ImportSource {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ImportSource").finish_non_exhaustive()
+  }
+}
@@ -2,6 +2,7 @@
 
 use crate::errors::get_error_class_name;
 use crate::file_fetcher::FileFetcher;
+use crate::graph_util::AssetSpecifiers;
 
 use deno_core::futures;
 use deno_core::futures::FutureExt;
@@ -22,19 +23,26 @@ mod deno_dir;
 mod disk_cache;
 mod emit;
 mod http_cache;
+mod import_source;
 mod incremental;
 mod node;
 mod parsed_source;
 
 pub use caches::Caches;
+pub use check::ModuleCheckHashes;
 pub use check::TypeCheckCache;
+pub use common::CacheStatus;
 pub use common::FastInsecureHasher;
+pub use common::StaleReason;
 pub use deno_dir::DenoDir;
 pub use deno_dir::DenoDirProvider;
 pub use disk_cache::DiskCache;
 pub use emit::EmitCache;
 pub use http_cache::CachedUrlMetadata;
 pub use http_cache::HttpCache;
+pub use import_source::ImportSource;
+pub use import_source::ImportSourceModule;
+pub use import_source::ImportSourceSources;
 pub use incremental::IncrementalCache;
 pub use node::NodeAnalysisCache;
 pub use parsed_source::ParsedSourceCache;
@@ -51,6 +59,7 @@ pub struct FetchCacher {
   permissions: PermissionsContainer,
   cache_info_enabled: bool,
   maybe_local_node_modules_url: Option<ModuleSpecifier>,
+  asset_specifiers: AssetSpecifiers,
 }
 
 impl FetchCacher {
@@ -60,6 +69,7 @@ impl FetchCacher {
     file_header_overrides: HashMap<ModuleSpecifier, HashMap<String, String>>,
     permissions: PermissionsContainer,
     maybe_local_node_modules_url: Option<ModuleSpecifier>,
+    asset_specifiers: AssetSpecifiers,
   ) -> Self {
     Self {
       emit_cache,
@@ -68,6 +78,7 @@ impl FetchCacher {
       permissions,
       cache_info_enabled: false,
       maybe_local_node_modules_url,
+      asset_specifiers,
     }
   }
 
@@ -105,6 +116,14 @@ impl Loader for FetchCacher {
     specifier: &ModuleSpecifier,
     _is_dynamic: bool,
   ) -> LoadFuture {
+    if self.asset_specifiers.contains(specifier) {
+      return Box::pin(futures::future::ready(Ok(Some(
+        LoadResponse::External {
+          specifier: specifier.clone(),
+        },
+      ))));
+    }
+
     if let Some(node_modules_url) = self.maybe_local_node_modules_url.as_ref() {
       // The specifier might be in a completely different symlinked tree than
       // what the resolved node_modules_url is in (ex. `/my-project-1/node_modules`
@@ -1,8 +1,8 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-use deno_ast::CjsAnalysis;
 use deno_core::error::AnyError;
 use deno_core::serde_json;
+use deno_runtime::deno_node::analyze::CjsAnalysis;
 use deno_runtime::deno_webstorage::rusqlite::params;
 
 use super::cache_db::CacheDB;
@@ -238,6 +238,7 @@ mod test {
     let cjs_analysis = CjsAnalysis {
       exports: vec!["export1".to_string()],
       reexports: vec!["re-export1".to_string()],
+      ..Default::default()
     };
     cache
       .set_cjs_analysis("file.js", "2", &cjs_analysis)
@@ -1,8 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::cache::CacheStatus;
 use crate::cache::EmitCache;
 use crate::cache::FastInsecureHasher;
 use crate::cache::ParsedSourceCache;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 
 use deno_core::error::AnyError;
 use deno_core::ModuleCode;
@@ -18,6 +21,7 @@ pub struct Emitter {
   emit_options: deno_ast::EmitOptions,
   // cached hash of the emit options
   emit_options_hash: u64,
+  startup_timer: Arc<PhaseTimer>,
 }
 
 impl Emitter {
@@ -25,6 +29,7 @@ impl Emitter {
     emit_cache: EmitCache,
     parsed_source_cache: Arc<ParsedSourceCache>,
     emit_options: deno_ast::EmitOptions,
+    startup_timer: Arc<PhaseTimer>,
   ) -> Self {
     let emit_options_hash = FastInsecureHasher::new()
       .write_hashable(&emit_options)
@@ -34,6 +39,7 @@ impl Emitter {
       parsed_source_cache,
       emit_options,
       emit_options_hash,
+      startup_timer,
     }
   }
 
@@ -43,15 +49,7 @@ impl Emitter {
   ) -> Result<(), AnyError> {
     for module in graph.modules() {
       if let Module::Esm(module) = module {
-        let is_emittable = matches!(
-          module.media_type,
-          MediaType::TypeScript
-            | MediaType::Mts
-            | MediaType::Cts
-            | MediaType::Jsx
-            | MediaType::Tsx
-        );
-        if is_emittable {
+        if is_emittable(module.media_type) {
           self.emit_parsed_source(
             &module.specifier,
             module.media_type,
@@ -63,6 +61,20 @@ impl Emitter {
     Ok(())
   }
 
+  /// Tells whether the emit of the source is cached, and if not, why.
+  pub fn emit_status(
+    &self,
+    specifier: &ModuleSpecifier,
+    source: &str,
+  ) -> CacheStatus {
+    let source_hash = self.get_source_hash(source);
+    self.emit_cache.get_emit_status(
+      specifier,
+      source_hash,
+      self.emit_options_hash,
+    )
+  }
+
   /// Gets a cached emit if the source matches the hash found in the cache.
   pub fn maybed_cached_emit(
     &self,
@@ -79,6 +91,7 @@ impl Emitter {
     media_type: MediaType,
     source: &Arc<str>,
   ) -> Result<ModuleCode, AnyError> {
+    let _timer = self.startup_timer.measure(StartupPhase::Emit);
     let source_hash = self.get_source_hash(source);
 
     if let Some(emit_code) =
@@ -97,6 +110,7 @@ impl Emitter {
       self.emit_cache.set_emit_code(
         specifier,
         source_hash,
+        self.emit_options_hash,
         &transpiled_source.text,
       );
       Ok(transpiled_source.text.into())
@@ -113,3 +127,15 @@ impl Emitter {
       .finish()
   }
 }
+
+/// Whether modules of the media type are transpiled before being run.
+pub fn is_emittable(media_type: MediaType) -> bool {
+  matches!(
+    media_type,
+    MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Cts
+      | MediaType::Jsx
+      | MediaType::Tsx
+  )
+}
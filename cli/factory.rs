@@ -13,6 +13,7 @@ use crate::cache::DenoDir;
 use crate::cache::DenoDirProvider;
 use crate::cache::EmitCache;
 use crate::cache::HttpCache;
+use crate::cache::ImportSource;
 use crate::cache::NodeAnalysisCache;
 use crate::cache::ParsedSourceCache;
 use crate::emit::Emitter;
@@ -36,6 +37,8 @@ use crate::npm::PackageJsonDepsInstaller;
 use crate::resolver::CliGraphResolver;
 use crate::standalone::DenoCompileBinaryWriter;
 use crate::tools::check::TypeChecker;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::watcher::FileWatcher;
@@ -50,6 +53,7 @@ use deno_core::parking_lot::Mutex;
 use deno_graph::GraphKind;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node::analyze::NodeCodeTranslator;
+use deno_runtime::deno_node::NodeOptions;
 use deno_runtime::deno_node::NodeResolver;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_runtime::deno_web::BlobStore;
@@ -64,11 +68,15 @@ use std::sync::Arc;
 
 pub struct CliFactoryBuilder {
   maybe_sender: Option<tokio::sync::mpsc::UnboundedSender<Vec<PathBuf>>>,
+  maybe_startup_timer: Option<Arc<PhaseTimer>>,
 }
 
 impl CliFactoryBuilder {
   pub fn new() -> Self {
-    Self { maybe_sender: None }
+    Self {
+      maybe_sender: None,
+      maybe_startup_timer: None,
+    }
   }
 
   pub fn with_watcher(
@@ -79,17 +87,33 @@ impl CliFactoryBuilder {
     self
   }
 
+  /// Measures the startup phases for `--startup-report`.
+  pub fn with_startup_timer(mut self, startup_timer: Arc<PhaseTimer>) -> Self {
+    self.maybe_startup_timer = Some(startup_timer);
+    self
+  }
+
   pub async fn build_from_flags(
     self,
     flags: Flags,
   ) -> Result<CliFactory, AnyError> {
-    Ok(self.build_from_cli_options(Arc::new(CliOptions::from_flags(flags)?)))
+    let options = {
+      let _timer = self
+        .maybe_startup_timer
+        .as_ref()
+        .map(|timer| timer.measure(StartupPhase::ConfigDiscovery));
+      CliOptions::from_flags(flags)?
+    };
+    Ok(self.build_from_cli_options(Arc::new(options)))
   }
 
   pub fn build_from_cli_options(self, options: Arc<CliOptions>) -> CliFactory {
     CliFactory {
       maybe_sender: RefCell::new(self.maybe_sender),
       options,
+      startup_timer: self
+        .maybe_startup_timer
+        .unwrap_or_else(|| Arc::new(PhaseTimer::disabled())),
       services: Default::default(),
     }
   }
@@ -168,6 +192,7 @@ pub struct CliFactory {
   maybe_sender:
     RefCell<Option<tokio::sync::mpsc::UnboundedSender<Vec<PathBuf>>>>,
   options: Arc<CliOptions>,
+  startup_timer: Arc<PhaseTimer>,
   services: CliFactoryServices,
 }
 
@@ -184,6 +209,10 @@ impl CliFactory {
     &self.options
   }
 
+  pub fn startup_timer(&self) -> &Arc<PhaseTimer> {
+    &self.startup_timer
+  }
+
   pub fn deno_dir_provider(&self) -> &Arc<DenoDirProvider> {
     self.services.deno_dir_provider.get_or_init(|| {
       Arc::new(DenoDirProvider::new(
@@ -245,14 +274,18 @@ impl CliFactory {
 
   pub fn file_fetcher(&self) -> Result<&Arc<FileFetcher>, AnyError> {
     self.services.file_fetcher.get_or_try_init(|| {
-      Ok(Arc::new(FileFetcher::new(
+      let mut file_fetcher = FileFetcher::new(
         HttpCache::new(&self.deno_dir()?.deps_folder_path()),
         self.options.cache_setting(),
         !self.options.no_remote(),
         self.http_client().clone(),
         self.blob_store().clone(),
         Some(self.text_only_progress_bar().clone()),
-      )))
+      );
+      if let Some(path) = self.options.import_source() {
+        file_fetcher.set_import_source(ImportSource::load(&path)?);
+      }
+      Ok(Arc::new(file_fetcher))
     })
   }
 
@@ -462,6 +495,7 @@ impl CliFactory {
         self.emit_cache()?.clone(),
         self.parsed_source_cache()?.clone(),
         emit_options,
+        self.startup_timer.clone(),
       )))
     })
   }
@@ -474,6 +508,7 @@ impl CliFactory {
         Ok(Arc::new(NodeResolver::new(
           self.fs().clone(),
           self.npm_resolver().await?.clone(),
+          self.options.node_conditions(),
         )))
       })
       .await
@@ -532,6 +567,7 @@ impl CliFactory {
           self.emit_cache()?.clone(),
           self.file_fetcher()?.clone(),
           self.type_checker().await?.clone(),
+          self.startup_timer.clone(),
         )))
       })
       .await
@@ -573,6 +609,7 @@ impl CliFactory {
           self.text_only_progress_bar().clone(),
           self.resolver().await?.clone(),
           self.type_checker().await?.clone(),
+          self.startup_timer.clone(),
         )))
       })
       .await
@@ -595,6 +632,7 @@ impl CliFactory {
       self.npm_resolver().await?,
       self.options.npm_system_info(),
       self.package_json_deps_provider(),
+      self.maybe_lockfile().clone(),
     ))
   }
 
@@ -619,6 +657,8 @@ impl CliFactory {
     let npm_resolver = self.npm_resolver().await?.clone();
     let maybe_inspector_server = self.maybe_inspector_server().clone();
     let maybe_lockfile = self.maybe_lockfile().clone();
+    let startup_timer = self.startup_timer.clone();
+    let file_fetcher = self.file_fetcher()?.clone();
     Ok(Arc::new(move || {
       CliMainWorkerFactory::new(
         StorageKeyResolver::from_options(&options),
@@ -639,11 +679,13 @@ impl CliFactory {
             fs.clone(),
             node_resolver.clone(),
           ),
+          file_fetcher.clone(),
         )),
         root_cert_store_provider.clone(),
         fs.clone(),
         maybe_inspector_server.clone(),
         maybe_lockfile.clone(),
+        startup_timer.clone(),
         main_worker_options.clone(),
       )
     }))
@@ -673,11 +715,13 @@ impl CliFactory {
           fs.clone(),
           node_resolver.clone(),
         ),
+        self.file_fetcher()?.clone(),
       )),
       self.root_cert_store_provider().clone(),
       self.fs().clone(),
       self.maybe_inspector_server().clone(),
       self.maybe_lockfile().clone(),
+      self.startup_timer.clone(),
       self.create_cli_main_worker_options()?,
     ))
   }
@@ -687,6 +731,11 @@ impl CliFactory {
   ) -> Result<CliMainWorkerOptions, AnyError> {
     Ok(CliMainWorkerOptions {
       argv: self.options.argv().clone(),
+      broadcast_channel_socket: if self.options.broadcast_channel_ipc() {
+        Some(self.deno_dir()?.broadcast_channel_socket_path())
+      } else {
+        None
+      },
       log_level: self.options.log_level().unwrap_or(log::Level::Info).into(),
       coverage_dir: self.options.coverage_dir(),
       enable_testing_features: self.options.enable_testing_features(),
@@ -708,8 +757,11 @@ impl CliFactory {
         }
         maybe_binary_command_name
       },
+      node_conditions: self.options.node_conditions(),
+      node_options: NodeOptions::from_env(),
       origin_data_folder_path: Some(self.deno_dir()?.origin_data_folder_path()),
       seed: self.options.seed(),
+      split_output: self.options.split_output(),
       unsafely_ignore_certificate_errors: self
         .options
         .unsafely_ignore_certificate_errors()
@@ -4,6 +4,7 @@ use crate::args::CacheSetting;
 use crate::auth_tokens::AuthToken;
 use crate::auth_tokens::AuthTokens;
 use crate::cache::HttpCache;
+use crate::cache::ImportSource;
 use crate::colors;
 use crate::http_util;
 use crate::http_util::resolve_redirect_from_response;
@@ -33,6 +34,7 @@ use deno_runtime::deno_fetch::reqwest::StatusCode;
 use deno_runtime::deno_web::BlobStore;
 use deno_runtime::permissions::PermissionsContainer;
 use log::debug;
+use once_cell::sync::Lazy;
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::env;
@@ -42,6 +44,7 @@ use std::io::Read;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use std::time::SystemTime;
 
 pub const SUPPORTED_SCHEMES: [&str; 5] =
@@ -136,6 +139,13 @@ pub fn get_source_from_bytes(
 }
 
 /// Return a validated scheme for a given module specifier.
+fn no_remote_error(specifier: &ModuleSpecifier) -> AnyError {
+  custom_error(
+    "NoRemote",
+    format!("A remote specifier was requested: \"{specifier}\", but --no-remote is specified."),
+  )
+}
+
 fn get_validated_scheme(
   specifier: &ModuleSpecifier,
 ) -> Result<String, AnyError> {
@@ -170,6 +180,32 @@ pub fn map_content_type(
   }
 }
 
+/// How long the process waits for the remote modules that are revalidated in
+/// the background to be updated before it exits.
+const REVALIDATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The revalidations of all the file fetchers of the process, so that they
+/// are waited for once the subcommand is done whichever one it is.
+static REVALIDATIONS: Lazy<Mutex<Vec<tokio::task::JoinHandle<()>>>> =
+  Lazy::new(Default::default);
+
+/// Waits for the remote modules that are being revalidated in the background
+/// with `--reload=stale` to be updated in the cache. It gives up after a
+/// timeout, so that a slow server doesn't keep the process alive.
+pub async fn wait_for_revalidations() {
+  let handles = std::mem::take(&mut *REVALIDATIONS.lock());
+  if handles.is_empty() {
+    return;
+  }
+  let revalidations = futures::future::join_all(handles);
+  if tokio::time::timeout(REVALIDATION_TIMEOUT, revalidations)
+    .await
+    .is_err()
+  {
+    debug!("Timed out waiting for cached modules to be revalidated");
+  }
+}
+
 /// A structure for resolving, fetching and caching source files.
 #[derive(Debug, Clone)]
 pub struct FileFetcher {
@@ -182,6 +218,7 @@ pub struct FileFetcher {
   blob_store: BlobStore,
   download_log_level: log::Level,
   progress_bar: Option<ProgressBar>,
+  maybe_import_source: Option<ImportSource>,
 }
 
 impl FileFetcher {
@@ -203,9 +240,16 @@ impl FileFetcher {
       blob_store,
       download_log_level: log::Level::Info,
       progress_bar,
+      maybe_import_source: None,
     }
   }
 
+  /// Sets the archive to load modules from instead of the disk or the
+  /// network, from `--import-source`.
+  pub fn set_import_source(&mut self, import_source: ImportSource) {
+    self.maybe_import_source = Some(import_source);
+  }
+
   /// Sets the log level to use when outputting the download message.
   pub fn set_download_log_level(&mut self, level: log::Level) {
     self.download_log_level = level;
@@ -256,6 +300,23 @@ impl FileFetcher {
     redirect_limit: i64,
   ) -> Result<Option<File>, AnyError> {
     debug!("FileFetcher::fetch_cached - specifier: {}", specifier);
+    match self.fetch_cached_bytes(specifier, redirect_limit)? {
+      Some((specifier, bytes, headers)) => {
+        Ok(Some(self.build_remote_file(&specifier, bytes, &headers)?))
+      }
+      None => Ok(None),
+    }
+  }
+
+  /// Retrieve the raw, undecoded contents of a cached remote file, along with
+  /// its final specifier and headers.
+  ///
+  /// This is a recursive operation if source file has redirections.
+  fn fetch_cached_bytes(
+    &self,
+    specifier: &ModuleSpecifier,
+    redirect_limit: i64,
+  ) -> Result<Option<(ModuleSpecifier, Vec<u8>, HeadersMap)>, AnyError> {
     if redirect_limit < 0 {
       return Err(custom_error("Http", "Too many redirects."));
     }
@@ -274,13 +335,12 @@ impl FileFetcher {
     if let Some(redirect_to) = headers.get("location") {
       let redirect =
         deno_core::resolve_import(redirect_to, specifier.as_str())?;
-      return self.fetch_cached(&redirect, redirect_limit - 1);
+      return self.fetch_cached_bytes(&redirect, redirect_limit - 1);
     }
     let mut bytes = Vec::new();
     source_file.read_to_end(&mut bytes)?;
-    let file = self.build_remote_file(specifier, bytes, &headers)?;
 
-    Ok(Some(file))
+    Ok(Some((specifier.clone(), bytes, headers)))
   }
 
   /// Convert a data URL into a file, resulting in an error if the URL is
@@ -396,17 +456,46 @@ impl FileFetcher {
 
   /// Asynchronously fetch remote source file specified by the URL following
   /// redirects.
+  fn fetch_remote(
+    &self,
+    specifier: &ModuleSpecifier,
+    permissions: PermissionsContainer,
+    redirect_limit: i64,
+    maybe_accept: Option<String>,
+  ) -> Pin<Box<dyn Future<Output = Result<File, AnyError>> + Send>> {
+    let file_fetcher = self.clone();
+    self
+      .fetch_remote_bytes(specifier, permissions, redirect_limit, maybe_accept)
+      .map(move |result| {
+        let (specifier, bytes, headers) = result?;
+        file_fetcher.build_remote_file(&specifier, bytes, &headers)
+      })
+      .boxed()
+  }
+
+  /// Asynchronously fetch the raw contents of a remote file specified by the
+  /// URL following redirects, returning its final specifier and headers.
   ///
   /// **Note** this is a recursive method so it can't be "async", but needs to
   /// return a `Pin<Box<..>>`.
-  fn fetch_remote(
+  #[allow(clippy::type_complexity)]
+  fn fetch_remote_bytes(
     &self,
     specifier: &ModuleSpecifier,
     permissions: PermissionsContainer,
     redirect_limit: i64,
     maybe_accept: Option<String>,
-  ) -> Pin<Box<dyn Future<Output = Result<File, AnyError>> + Send>> {
-    debug!("FileFetcher::fetch_remote() - specifier: {}", specifier);
+  ) -> Pin<
+    Box<
+      dyn Future<
+          Output = Result<(ModuleSpecifier, Vec<u8>, HeadersMap), AnyError>,
+        > + Send,
+    >,
+  > {
+    debug!(
+      "FileFetcher::fetch_remote_bytes() - specifier: {}",
+      specifier
+    );
     if redirect_limit < 0 {
       return futures::future::err(custom_error("Http", "Too many redirects."))
         .boxed();
@@ -417,9 +506,12 @@ impl FileFetcher {
     }
 
     if self.should_use_cache(specifier) {
-      match self.fetch_cached(specifier, redirect_limit) {
-        Ok(Some(file)) => {
-          return futures::future::ok(file).boxed();
+      match self.fetch_cached_bytes(specifier, redirect_limit) {
+        Ok(Some(cached)) => {
+          if matches!(self.cache_setting, CacheSetting::Stale(_)) {
+            self.revalidate_in_background(specifier, maybe_accept);
+          }
+          return futures::future::ok(cached).boxed();
         }
         Ok(None) => {}
         Err(err) => {
@@ -473,13 +565,12 @@ impl FileFetcher {
       .await?
       {
         FetchOnceResult::NotModified => {
-          let file = file_fetcher.fetch_cached(&specifier, 10)?.unwrap();
-          Ok(file)
+          Ok(file_fetcher.fetch_cached_bytes(&specifier, 10)?.unwrap())
         }
         FetchOnceResult::Redirect(redirect_url, headers) => {
           file_fetcher.http_cache.set(&specifier, headers, &[])?;
           file_fetcher
-            .fetch_remote(
+            .fetch_remote_bytes(
               &redirect_url,
               permissions,
               redirect_limit - 1,
@@ -491,9 +582,7 @@ impl FileFetcher {
           file_fetcher
             .http_cache
             .set(&specifier, headers.clone(), &bytes)?;
-          let file =
-            file_fetcher.build_remote_file(&specifier, bytes, &headers)?;
-          Ok(file)
+          Ok((specifier, bytes, headers))
         }
       };
       drop(maybe_progress_guard);
@@ -502,6 +591,78 @@ impl FileFetcher {
     .boxed()
   }
 
+  /// Checks whether a cached remote module is still up to date without
+  /// waiting for it, updating the cache when it changed so that the new
+  /// version is used on the next run.
+  fn revalidate_in_background(
+    &self,
+    specifier: &ModuleSpecifier,
+    maybe_accept: Option<String>,
+  ) {
+    let Ok((mut cached_file, cached_headers, _)) =
+      self.http_cache.get(specifier)
+    else {
+      return;
+    };
+    let maybe_auth_token = self.auth_tokens.get(specifier);
+    let specifier = specifier.clone();
+    let file_fetcher = self.clone();
+    let handle = tokio::spawn(async move {
+      let result = fetch_once(
+        &file_fetcher.http_client,
+        FetchOnceArgs {
+          url: specifier.clone(),
+          maybe_accept,
+          maybe_etag: cached_headers.get("etag").cloned(),
+          maybe_auth_token,
+          maybe_progress_guard: None,
+        },
+      )
+      .await;
+      let changed = match result {
+        Ok(FetchOnceResult::NotModified) => Ok(false),
+        Ok(FetchOnceResult::Redirect(redirect_url, headers)) => {
+          let cached_redirect =
+            cached_headers.get("location").and_then(|location| {
+              deno_core::resolve_import(location, specifier.as_str()).ok()
+            });
+          if cached_redirect.as_ref() == Some(&redirect_url) {
+            Ok(false)
+          } else {
+            file_fetcher
+              .http_cache
+              .set(&specifier, headers, &[])
+              .map(|_| true)
+          }
+        }
+        Ok(FetchOnceResult::Code(bytes, headers)) => {
+          let mut cached_bytes = Vec::new();
+          let unchanged = !cached_headers.contains_key("location")
+            && cached_file.read_to_end(&mut cached_bytes).is_ok()
+            && cached_bytes == bytes;
+          // store the response anyway so the cache metadata is fresh
+          file_fetcher
+            .http_cache
+            .set(&specifier, headers, &bytes)
+            .map(|_| !unchanged)
+        }
+        Err(err) => Err(err),
+      };
+      match changed {
+        Ok(true) => log::info!(
+          "{} {} (the new version will be used on the next run)",
+          colors::green("Updated"),
+          specifier
+        ),
+        Ok(false) => {}
+        Err(err) => {
+          debug!("Failed to revalidate \"{}\": {:#}", specifier, err)
+        }
+      }
+    });
+    REVALIDATIONS.lock().push(handle);
+  }
+
   /// Returns if the cache should be used for a given specifier.
   fn should_use_cache(&self, specifier: &ModuleSpecifier) -> bool {
     match &self.cache_setting {
@@ -516,7 +677,7 @@ impl FileFetcher {
           false
         }
       }
-      CacheSetting::ReloadSome(list) => {
+      CacheSetting::ReloadSome(list) | CacheSetting::Stale(list) => {
         let mut url = specifier.clone();
         url.set_fragment(None);
         if list.iter().any(|x| x == url.as_str()) {
@@ -557,6 +718,11 @@ impl FileFetcher {
     permissions.check_specifier(specifier)?;
     if let Some(file) = self.cache.get(specifier) {
       Ok(file)
+    } else if let Some(file) =
+      self.fetch_import_source(specifier, &scheme, &permissions)?
+    {
+      self.cache.insert(specifier.clone(), file.clone());
+      Ok(file)
     } else if scheme == "file" {
       // we do not in memory cache files, as this would prevent files on the
       // disk changing effecting things like workers and dynamic imports.
@@ -574,10 +740,7 @@ impl FileFetcher {
       }
       result
     } else if !self.allow_remote {
-      Err(custom_error(
-        "NoRemote",
-        format!("A remote specifier was requested: \"{specifier}\", but --no-remote is specified."),
-      ))
+      Err(no_remote_error(specifier))
     } else {
       let result = self
         .fetch_remote(
@@ -594,6 +757,98 @@ impl FileFetcher {
     }
   }
 
+  /// Gets a module from the archive of `--import-source`, which is still
+  /// subject to `--no-remote` when the module is remote.
+  fn fetch_import_source(
+    &self,
+    specifier: &ModuleSpecifier,
+    scheme: &str,
+    permissions: &PermissionsContainer,
+  ) -> Result<Option<File>, AnyError> {
+    let Some(import_source) = &self.maybe_import_source else {
+      return Ok(None);
+    };
+    if scheme != "file" && scheme != "http" && scheme != "https" {
+      return Ok(None);
+    }
+    let Some((redirect, module)) = import_source.get(specifier) else {
+      return Ok(None);
+    };
+    if redirect != specifier {
+      permissions.check_specifier(redirect)?;
+    }
+    let specifier = redirect;
+    if specifier.scheme() != "file" && !self.allow_remote {
+      return Err(no_remote_error(specifier));
+    }
+    let (media_type, _) =
+      map_content_type(specifier, Some(&module.content_type));
+    Ok(Some(File {
+      local: self.get_local_path(specifier).unwrap_or_default(),
+      maybe_types: None,
+      media_type,
+      source: module.source.clone(),
+      specifier: specifier.clone(),
+      maybe_headers: Some(HashMap::from([(
+        "content-type".to_string(),
+        module.content_type.clone(),
+      )])),
+    }))
+  }
+
+  /// Fetch the raw contents of a file without decoding them, as used for
+  /// modules imported with a `bytes` or `text` type assertion. Returns the
+  /// final specifier of the file along with its contents.
+  ///
+  /// This goes through the same checks and cache settings as fetching a
+  /// module, except that the contents aren't kept in the in memory cache.
+  pub async fn fetch_bytes(
+    &self,
+    specifier: &ModuleSpecifier,
+    permissions: PermissionsContainer,
+  ) -> Result<(ModuleSpecifier, Vec<u8>), AnyError> {
+    debug!("FileFetcher::fetch_bytes() - specifier: {}", specifier);
+    let scheme = get_validated_scheme(specifier)?;
+    permissions.check_specifier(specifier)?;
+    if let Some(file) =
+      self.fetch_import_source(specifier, &scheme, &permissions)?
+    {
+      Ok((file.specifier, file.source.as_bytes().to_vec()))
+    } else if scheme == "file" {
+      let local = specifier.to_file_path().map_err(|_| {
+        uri_error(format!("Invalid file path.\n  Specifier: {specifier}"))
+      })?;
+      Ok((specifier.clone(), fs::read(local)?))
+    } else if scheme == "data" {
+      let data_url = DataUrl::process(specifier.as_str())
+        .map_err(|e| uri_error(format!("{e:?}")))?;
+      let (bytes, _) = data_url
+        .decode_to_vec()
+        .map_err(|e| uri_error(format!("{e:?}")))?;
+      Ok((specifier.clone(), bytes))
+    } else if scheme == "blob" {
+      let blob = {
+        let blob_store = self.blob_store.borrow();
+        blob_store
+          .get_object_url(specifier.clone())
+          .ok_or_else(|| {
+            custom_error(
+              "NotFound",
+              format!("Blob URL not found: \"{specifier}\"."),
+            )
+          })?
+      };
+      Ok((specifier.clone(), blob.read_all().await?))
+    } else if !self.allow_remote {
+      Err(no_remote_error(specifier))
+    } else {
+      let (specifier, bytes, _) = self
+        .fetch_remote_bytes(specifier, permissions, 10, None)
+        .await?;
+      Ok((specifier, bytes))
+    }
+  }
+
   pub fn get_local_path(&self, specifier: &ModuleSpecifier) -> Option<PathBuf> {
     // TODO(@kitsonk) fix when deno_graph does not query cache for synthetic
     // modules
@@ -1275,6 +1530,40 @@ mod tests {
     assert_eq!(metadata_file_modified_01, metadata_file_modified_02);
   }
 
+  #[tokio::test]
+  async fn test_fetch_stale_revalidates() {
+    let _http_server_guard = test_util::http_server();
+    let (file_fetcher, _) = setup(CacheSetting::Stale(vec![]), None);
+    let specifier =
+      resolve_url("http://localhost:4545/subdir/mod2.ts").unwrap();
+    let mut headers = HashMap::new();
+    headers.insert(
+      "content-type".to_string(),
+      "application/typescript".to_string(),
+    );
+    file_fetcher
+      .http_cache
+      .set(&specifier, headers, b"export const stale = true;")
+      .unwrap();
+
+    let file = file_fetcher
+      .fetch(&specifier, PermissionsContainer::allow_all())
+      .await
+      .unwrap();
+    assert_eq!(&*file.source, "export const stale = true;");
+
+    wait_for_revalidations().await;
+    let (mut cached_file, _, _) =
+      file_fetcher.http_cache.get(&specifier).unwrap();
+    let mut cached_source = String::new();
+    cached_file.read_to_string(&mut cached_source).unwrap();
+    assert_eq!(
+      cached_source,
+      fs::read_to_string(test_util::testdata_path().join("subdir/mod2.ts"))
+        .unwrap()
+    );
+  }
+
   #[tokio::test]
   async fn test_fetch_redirected() {
     let _http_server_guard = test_util::http_server();
@@ -1585,6 +1874,71 @@ mod tests {
     assert!(result.is_ok());
   }
 
+  #[tokio::test]
+  async fn test_fetch_bytes_no_remote() {
+    let _http_server_guard = test_util::http_server();
+    let temp_dir = TempDir::new();
+    let location = temp_dir.path().join("deps");
+    let file_fetcher = FileFetcher::new(
+      HttpCache::new(&location),
+      CacheSetting::Use,
+      false,
+      Arc::new(HttpClient::new(None, None)),
+      BlobStore::default(),
+      None,
+    );
+    let specifier =
+      resolve_url("http://localhost:4545/run/002_hello.ts").unwrap();
+
+    let result = file_fetcher
+      .fetch_bytes(&specifier, PermissionsContainer::allow_all())
+      .await;
+    let err = result.unwrap_err();
+    assert_eq!(get_custom_error_class(&err), Some("NoRemote"));
+  }
+
+  #[tokio::test]
+  async fn test_fetch_bytes_cache_only() {
+    let _http_server_guard = test_util::http_server();
+    let temp_dir = TempDir::new();
+    let location = temp_dir.path().join("deps");
+    let file_fetcher_01 = FileFetcher::new(
+      HttpCache::new(&location),
+      CacheSetting::Only,
+      true,
+      Arc::new(HttpClient::new(None, None)),
+      BlobStore::default(),
+      None,
+    );
+    let file_fetcher_02 = FileFetcher::new(
+      HttpCache::new(&location),
+      CacheSetting::Use,
+      true,
+      Arc::new(HttpClient::new(None, None)),
+      BlobStore::default(),
+      None,
+    );
+    let specifier =
+      resolve_url("http://localhost:4545/run/002_hello.ts").unwrap();
+
+    let result = file_fetcher_01
+      .fetch_bytes(&specifier, PermissionsContainer::allow_all())
+      .await;
+    let err = result.unwrap_err();
+    assert_eq!(get_custom_error_class(&err), Some("NotCached"));
+
+    let (_, expected) = file_fetcher_02
+      .fetch_bytes(&specifier, PermissionsContainer::allow_all())
+      .await
+      .unwrap();
+    let (found_url, bytes) = file_fetcher_01
+      .fetch_bytes(&specifier, PermissionsContainer::allow_all())
+      .await
+      .unwrap();
+    assert_eq!(found_url, specifier);
+    assert_eq!(bytes, expected);
+  }
+
   #[tokio::test]
   async fn test_fetch_local_bypasses_file_cache() {
     let (file_fetcher, temp_dir) = setup(CacheSetting::Use, None);
@@ -12,7 +12,11 @@ use crate::npm::CliNpmResolver;
 use crate::resolver::CliGraphResolver;
 use crate::tools::check;
 use crate::tools::check::TypeChecker;
+use crate::util::checksum;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 
+use deno_ast::MediaType;
 use deno_core::anyhow::bail;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
@@ -22,11 +26,14 @@ use deno_core::ModuleSpecifier;
 use deno_core::TaskQueue;
 use deno_core::TaskQueuePermit;
 use deno_graph::source::Loader;
+use deno_graph::source::Resolver;
 use deno_graph::GraphKind;
 use deno_graph::Module;
+use deno_graph::ModuleAnalyzer;
 use deno_graph::ModuleError;
 use deno_graph::ModuleGraph;
 use deno_graph::ModuleGraphError;
+use deno_graph::ModuleInfo;
 use deno_graph::ResolutionError;
 use deno_graph::SpecifierError;
 use deno_runtime::deno_node;
@@ -139,6 +146,98 @@ pub fn graph_valid(
   }
 }
 
+/// Import assertion types of modules that are loaded as raw assets instead
+/// of being parsed as part of the module graph.
+pub const ASSET_ASSERT_TYPES: &[&str] = &["bytes", "text"];
+
+/// Gets the specifiers that are imported with a `bytes` or `text` type
+/// assertion somewhere in the graph.
+pub fn graph_asset_specifiers(graph: &ModuleGraph) -> HashSet<ModuleSpecifier> {
+  graph
+    .modules()
+    .filter_map(|module| module.esm())
+    .flat_map(|module| module.dependencies.values())
+    .filter(|dep| {
+      dep
+        .maybe_assert_type
+        .as_deref()
+        .map(|assert_type| ASSET_ASSERT_TYPES.contains(&assert_type))
+        .unwrap_or(false)
+    })
+    .filter_map(|dep| dep.maybe_code.maybe_specifier())
+    .cloned()
+    .collect()
+}
+
+/// The specifiers that are imported with a `bytes` or `text` type assertion.
+///
+/// deno_graph doesn't understand these imports, so they are recorded while a
+/// graph is built and loaders mark them as external instead of loading them
+/// as modules. The runtime loads them on its own.
+#[derive(Clone, Default)]
+pub struct AssetSpecifiers(Arc<Mutex<HashSet<ModuleSpecifier>>>);
+
+impl AssetSpecifiers {
+  pub fn contains(&self, specifier: &ModuleSpecifier) -> bool {
+    self.0.lock().contains(specifier)
+  }
+
+  /// Wraps a module analyzer so that the assets the analyzed modules import
+  /// are recorded.
+  pub fn as_analyzer<'a>(
+    &'a self,
+    analyzer: &'a dyn ModuleAnalyzer,
+    maybe_resolver: Option<&'a dyn Resolver>,
+  ) -> AssetModuleAnalyzer<'a> {
+    AssetModuleAnalyzer {
+      analyzer,
+      maybe_resolver,
+      asset_specifiers: self,
+    }
+  }
+}
+
+pub struct AssetModuleAnalyzer<'a> {
+  analyzer: &'a dyn ModuleAnalyzer,
+  maybe_resolver: Option<&'a dyn Resolver>,
+  asset_specifiers: &'a AssetSpecifiers,
+}
+
+impl ModuleAnalyzer for AssetModuleAnalyzer<'_> {
+  fn analyze(
+    &self,
+    specifier: &ModuleSpecifier,
+    source: Arc<str>,
+    media_type: MediaType,
+  ) -> Result<ModuleInfo, deno_ast::Diagnostic> {
+    let module_info = self.analyzer.analyze(specifier, source, media_type)?;
+    for dependency in &module_info.dependencies {
+      let is_asset = dependency
+        .import_assertions
+        .get("type")
+        .map(|assert_type| ASSET_ASSERT_TYPES.contains(&assert_type.as_str()))
+        .unwrap_or(false);
+      if !is_asset {
+        continue;
+      }
+      // resolution errors are surfaced by the graph itself
+      let maybe_resolved = match self.maybe_resolver {
+        Some(resolver) => {
+          resolver.resolve(&dependency.specifier, specifier).ok()
+        }
+        None => {
+          deno_core::resolve_import(&dependency.specifier, specifier.as_str())
+            .ok()
+        }
+      };
+      if let Some(resolved) = maybe_resolved {
+        self.asset_specifiers.0.lock().insert(resolved);
+      }
+    }
+    Ok(module_info)
+  }
+}
+
 /// Checks the lockfile against the graph and and exits on errors.
 pub fn graph_lock_or_exit(graph: &ModuleGraph, lockfile: &mut Lockfile) {
   for module in graph.modules() {
@@ -147,19 +246,47 @@ pub fn graph_lock_or_exit(graph: &ModuleGraph, lockfile: &mut Lockfile) {
       Module::Json(module) => &module.source,
       Module::Node(_) | Module::Npm(_) | Module::External(_) => continue,
     };
-    if !lockfile.check_or_insert_remote(module.specifier().as_str(), source) {
-      let err = format!(
-        concat!(
-          "The source code is invalid, as it does not match the expected hash in the lock file.\n",
-          "  Specifier: {}\n",
-          "  Lock file: {}",
-        ),
-        module.specifier(),
-        lockfile.filename.display(),
-      );
-      log::error!("{} {}", colors::red("error:"), err);
-      std::process::exit(10);
+    lock_or_exit(lockfile, module.specifier(), source);
+  }
+}
+
+/// Checks the lockfile against the contents of a module that was imported
+/// with a `bytes` or `text` type assertion and exits on errors.
+pub fn asset_lock_or_exit(
+  lockfile: &mut Lockfile,
+  specifier: &ModuleSpecifier,
+  bytes: &[u8],
+) {
+  // text is checked like the source of a module, so that the entry is the
+  // same however the file is imported
+  let hash;
+  let source = match std::str::from_utf8(bytes) {
+    Ok(source) => source,
+    Err(_) => {
+      hash = checksum::gen(&[bytes]);
+      &hash
     }
+  };
+  lock_or_exit(lockfile, specifier, source);
+}
+
+fn lock_or_exit(
+  lockfile: &mut Lockfile,
+  specifier: &ModuleSpecifier,
+  source: &str,
+) {
+  if !lockfile.check_or_insert_remote(specifier.as_str(), source) {
+    let err = format!(
+      concat!(
+        "The source code is invalid, as it does not match the expected hash in the lock file.\n",
+        "  Specifier: {}\n",
+        "  Lock file: {}",
+      ),
+      specifier,
+      lockfile.filename.display(),
+    );
+    log::error!("{} {}", colors::red("error:"), err);
+    std::process::exit(10);
   }
 }
 
@@ -172,6 +299,8 @@ pub struct ModuleGraphBuilder {
   emit_cache: cache::EmitCache,
   file_fetcher: Arc<FileFetcher>,
   type_checker: Arc<TypeChecker>,
+  startup_timer: Arc<PhaseTimer>,
+  asset_specifiers: AssetSpecifiers,
 }
 
 impl ModuleGraphBuilder {
@@ -185,6 +314,7 @@ impl ModuleGraphBuilder {
     emit_cache: cache::EmitCache,
     file_fetcher: Arc<FileFetcher>,
     type_checker: Arc<TypeChecker>,
+    startup_timer: Arc<PhaseTimer>,
   ) -> Self {
     Self {
       options,
@@ -195,6 +325,8 @@ impl ModuleGraphBuilder {
       emit_cache,
       file_fetcher,
       type_checker,
+      startup_timer,
+      asset_specifiers: Default::default(),
     }
   }
 
@@ -302,8 +434,25 @@ impl ModuleGraphBuilder {
       self.resolver.force_top_level_package_json_install().await?;
     }
 
-    graph.build(roots, loader, options).await;
+    {
+      let _timer = self.startup_timer.measure(StartupPhase::GraphBuild);
+      let asset_analyzer;
+      let options = match options.module_analyzer {
+        Some(analyzer) => {
+          asset_analyzer = self
+            .asset_specifiers
+            .as_analyzer(analyzer, options.resolver);
+          deno_graph::BuildOptions {
+            module_analyzer: Some(&asset_analyzer),
+            ..options
+          }
+        }
+        None => options,
+      };
+      graph.build(roots, loader, options).await;
+    }
 
+    let _timer = self.startup_timer.measure(StartupPhase::NpmResolution);
     // ensure that the top level package.json is installed if a
     // specifier was matched in the package.json
     self
@@ -333,6 +482,7 @@ impl ModuleGraphBuilder {
       self.options.resolve_file_header_overrides(),
       permissions,
       self.options.node_modules_dir_specifier(),
+      self.asset_specifiers.clone(),
     )
   }
 
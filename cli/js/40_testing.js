@@ -6,12 +6,15 @@ import { setExitHandler } from "ext:runtime/30_os.js";
 import { Console } from "ext:deno_console/01_console.js";
 import { serializePermissions } from "ext:runtime/10_permissions.js";
 import { assert } from "ext:deno_web/00_infra.js";
+import { makeTempDir, remove } from "ext:deno_fs/30_fs.js";
+import { errors } from "ext:runtime/01_errors.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayPrototypeFilter,
   ArrayPrototypeJoin,
   ArrayPrototypePush,
   ArrayPrototypeShift,
+  ArrayPrototypeUnshift,
   DateNow,
   Error,
   FunctionPrototype,
@@ -20,10 +23,12 @@ const {
   MapPrototypeHas,
   MapPrototypeSet,
   MathCeil,
+  MathSqrt,
   ObjectKeys,
   ObjectHasOwn,
   ObjectPrototypeIsPrototypeOf,
   Promise,
+  PromisePrototype,
   SafeArrayIterator,
   Set,
   SymbolToStringTag,
@@ -116,6 +121,7 @@ const OP_DETAILS = {
   "op_seek_async": ["seek in a file", "awaiting the result of a `Deno.File#seek` call"],
   "op_signal_poll": ["get the next signal", "un-registering a OS signal handler"],
   "op_sleep": ["sleep for a duration", "cancelling a `setTimeout` or `setInterval` call"],
+  "op_sleep_interval": ["sleep until the next period of an interval", "cancelling a `setInterval` call"],
   "op_stat_async": ["get file metadata", "awaiting the result of a `Deno.stat` call"],
   "op_symlink_async": ["create a symlink", "awaiting the result of a `Deno.symlink` call"],
   "op_net_accept_tls": ["accept a TLS stream", "closing a `Deno.TlsListener`"],
@@ -418,6 +424,31 @@ function assertExit(fn, isTest) {
   };
 }
 
+// Wrap test function so that the temporary directories created through
+// `TestContext.tempDir()` are removed once it finishes, whether it passed or
+// not. This runs inside the sanitizers so the removal ops are accounted for.
+function cleanupTempDirs(fn, desc) {
+  return async function tempDirCleanup(...params) {
+    try {
+      return await fn(...new SafeArrayIterator(params));
+    } finally {
+      const state = MapPrototypeGet(testStates, desc.id);
+      const tempDirs = state.tempDirs;
+      state.tempDirs = [];
+      for (const path of new SafeArrayIterator(tempDirs)) {
+        try {
+          await remove(path, { recursive: true });
+        } catch (error) {
+          // The test may have removed the directory itself.
+          if (!ObjectPrototypeIsPrototypeOf(errors.NotFound.prototype, error)) {
+            throw error;
+          }
+        }
+      }
+    }
+  };
+}
+
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
     try {
@@ -529,9 +560,19 @@ function withPermissions(fn, permissions) {
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
  *   permissions: PermissionOptions,
+ *   suites?: string[],
  * }} TestDescription
  *
  * @typedef {{
+ *   name: string,
+ *   ignore: boolean,
+ *   only: boolean,
+ *   beforeEach: ((t: TestContext) => void | Promise<void>)[],
+ *   afterEach: ((t: TestContext) => void | Promise<void>)[],
+ *   parent: TestSuite | null,
+ * }} TestSuite
+ *
+ * @typedef {{
  *   id: number,
  *   name: string,
  *   fn: TestFunction
@@ -575,16 +616,16 @@ function withPermissions(fn, permissions) {
 /** @type {Map<number, TestState | TestStepState>} */
 const testStates = new Map();
 
-// Main test function provided by Deno.
-function test(
+/**
+ * Builds the description of a test from the arguments of `Deno.test()`,
+ * which `it()` takes too.
+ * @returns {TestDescription}
+ */
+function createTestDescription(
   nameOrFnOrOptions,
   optionsOrFn,
   maybeFn,
 ) {
-  if (typeof ops.op_register_test != "function") {
-    return;
-  }
-
   let testDesc;
   const defaults = {
     ignore: false,
@@ -665,14 +706,44 @@ function test(
     testDesc = { ...defaults, ...nameOrFnOrOptions, fn, name };
   }
 
-  // Delete this prop in case the user passed it. It's used to detect steps.
+  // Delete these props in case the user passed them. `parent` is used to
+  // detect steps, and `suites` is set by `it()`.
   delete testDesc.parent;
+  delete testDesc.suites;
+  return testDesc;
+}
+
+/** The location of the code that called the function that calls this. */
+function getCallerLocation() {
   const jsError = core.destructureError(new Error());
-  testDesc.location = {
-    fileName: jsError.frames[1].fileName,
-    lineNumber: jsError.frames[1].lineNumber,
-    columnNumber: jsError.frames[1].columnNumber,
+  return {
+    fileName: jsError.frames[2].fileName,
+    lineNumber: jsError.frames[2].lineNumber,
+    columnNumber: jsError.frames[2].columnNumber,
   };
+}
+
+// Main test function provided by Deno.
+function test(
+  nameOrFnOrOptions,
+  optionsOrFn,
+  maybeFn,
+) {
+  if (typeof ops.op_register_test != "function") {
+    return;
+  }
+
+  const testDesc = createTestDescription(
+    nameOrFnOrOptions,
+    optionsOrFn,
+    maybeFn,
+  );
+  testDesc.location = getCallerLocation();
+  registerTest(testDesc);
+}
+
+/** @param testDesc {TestDescription} */
+function registerTest(testDesc) {
   testDesc.fn = wrapTest(testDesc);
 
   const { id, origin } = ops.op_register_test(testDesc);
@@ -681,10 +752,159 @@ function test(
   MapPrototypeSet(testStates, testDesc.id, {
     context: createTestContext(testDesc),
     children: [],
+    tempDirs: [],
     completed: false,
   });
 }
 
+/**
+ * The suite that `describe()` is collecting the cases of, if any.
+ * @type {TestSuite | null}
+ */
+let currentSuite = null;
+
+/** @param suite {TestSuite} */
+function getSuiteNames(suite) {
+  const names = [];
+  for (let current = suite; current !== null; current = current.parent) {
+    ArrayPrototypeUnshift(names, current.name);
+  }
+  return names;
+}
+
+// Groups the cases registered by `fn` with `it()`, and the hooks registered
+// with `beforeEach()` and `afterEach()`. `fn` is called right away, and may
+// call `describe()` itself to nest suites.
+function describe(nameOrOptions, maybeFn) {
+  if (typeof ops.op_register_test != "function") {
+    return;
+  }
+
+  let options;
+  if (typeof nameOrOptions === "string") {
+    options = { name: nameOrOptions, fn: maybeFn };
+  } else if (typeof nameOrOptions === "object" && nameOrOptions !== null) {
+    if (maybeFn != undefined && nameOrOptions.fn != undefined) {
+      throw new TypeError(
+        "Unexpected 'fn' field in options, suite function is already provided as the second argument.",
+      );
+    }
+    options = { ...nameOrOptions, fn: maybeFn ?? nameOrOptions.fn };
+  } else {
+    throw new TypeError("Expected a suite definition or name and function.");
+  }
+  if (!options.name) {
+    throw new TypeError("The suite name can't be empty");
+  }
+  if (typeof options.fn !== "function") {
+    throw new TypeError("Missing suite function");
+  }
+
+  /** @type {TestSuite} */
+  const suite = {
+    name: options.name,
+    ignore: !!options.ignore || !!currentSuite?.ignore,
+    only: !!options.only || !!currentSuite?.only,
+    beforeEach: [],
+    afterEach: [],
+    parent: currentSuite,
+  };
+  currentSuite = suite;
+  try {
+    const result = options.fn();
+    if (ObjectPrototypeIsPrototypeOf(PromisePrototype, result)) {
+      throw new TypeError(
+        "The suite function can't be async, cases must be registered synchronously.",
+      );
+    }
+  } finally {
+    currentSuite = suite.parent;
+  }
+}
+
+// Registers a test case of the current suite, which is named after the
+// suites it is in, like "suite > case", so that filters can select it. Out of
+// a suite, it is the same as `Deno.test()`.
+function it(
+  nameOrFnOrOptions,
+  optionsOrFn,
+  maybeFn,
+) {
+  if (typeof ops.op_register_test != "function") {
+    return;
+  }
+
+  const testDesc = createTestDescription(
+    nameOrFnOrOptions,
+    optionsOrFn,
+    maybeFn,
+  );
+  testDesc.location = getCallerLocation();
+  const suite = currentSuite;
+  if (suite !== null) {
+    testDesc.suites = getSuiteNames(suite);
+    testDesc.name = ArrayPrototypeJoin(
+      [...new SafeArrayIterator(testDesc.suites), testDesc.name],
+      " > ",
+    );
+    testDesc.ignore ||= suite.ignore;
+    testDesc.only ||= suite.only;
+    testDesc.fn = withHooks(testDesc.fn, suite);
+  }
+  registerTest(testDesc);
+}
+
+/**
+ * Runs the `beforeEach()` hooks of the suites, from the outermost one, before
+ * `fn`, and their `afterEach()` hooks after it, from the innermost one. The
+ * hooks are looked up when the case runs, so the ones registered after the
+ * case in the suite apply too.
+ * @param suite {TestSuite}
+ */
+function withHooks(fn, suite) {
+  return async function caseWithHooks(t) {
+    const suites = [];
+    for (let current = suite; current !== null; current = current.parent) {
+      ArrayPrototypeUnshift(suites, current);
+    }
+    for (const { beforeEach } of new SafeArrayIterator(suites)) {
+      for (const hook of new SafeArrayIterator(beforeEach)) {
+        await hook(t);
+      }
+    }
+    try {
+      await fn(t);
+    } finally {
+      for (let i = suites.length - 1; i >= 0; i--) {
+        for (const hook of new SafeArrayIterator(suites[i].afterEach)) {
+          await hook(t);
+        }
+      }
+    }
+  };
+}
+
+function registerHook(kind, fn) {
+  if (typeof ops.op_register_test != "function") {
+    return;
+  }
+  if (currentSuite === null) {
+    throw new TypeError(`${kind}() must be called inside describe()`);
+  }
+  if (typeof fn !== "function") {
+    throw new TypeError(`Expected function for the ${kind}() hook.`);
+  }
+  ArrayPrototypePush(currentSuite[kind], fn);
+}
+
+function beforeEach(fn) {
+  registerHook("beforeEach", fn);
+}
+
+function afterEach(fn) {
+  registerHook("afterEach", fn);
+}
+
 // Main bench function provided by Deno.
 function bench(
   nameOrFnOrOptions,
@@ -791,6 +1011,14 @@ function compareMeasurements(a, b) {
 }
 
 function benchStats(n, highPrecision, avg, min, max, all) {
+  // the sample standard deviation, used for the confidence intervals of the
+  // group summaries
+  const mean = avg / n;
+  let variance = 0;
+  for (let i = 0; i < n; i++) {
+    variance += (all[i] - mean) ** 2;
+  }
+  variance /= n > 1 ? n - 1 : 1;
   return {
     n,
     min,
@@ -800,6 +1028,7 @@ function benchStats(n, highPrecision, avg, min, max, all) {
     p995: all[MathCeil(n * (99.5 / 100)) - 1],
     p999: all[MathCeil(n * (99.9 / 100)) - 1],
     avg: !highPrecision ? (avg / n) : MathCeil(avg / n),
+    sd: MathSqrt(variance),
   };
 }
 
@@ -1071,6 +1300,7 @@ function createTestContext(desc) {
       const state = {
         context: createTestContext(stepDesc),
         children: [],
+        tempDirs: [],
         failed: false,
         completed: false,
       };
@@ -1088,6 +1318,22 @@ function createTestContext(desc) {
       stepReportResult(stepDesc, result, elapsed);
       return result == "ok";
     },
+
+    /**
+     * @param options {{ prefix?: string, suffix?: string } | undefined}
+     * @returns {Promise<string>}
+     */
+    async tempDir(options = {}) {
+      const state = MapPrototypeGet(testStates, desc.id);
+      if (state.completed) {
+        throw new Error(
+          "Cannot create a temporary directory after the test has finished execution.",
+        );
+      }
+      const path = await makeTempDir(options);
+      ArrayPrototypePush(state.tempDirs, path);
+      return path;
+    },
   };
 }
 
@@ -1099,7 +1345,7 @@ function createTestContext(desc) {
  * @returns {T}
  */
 function wrapTest(desc) {
-  let testFn = wrapInner(desc.fn);
+  let testFn = cleanupTempDirs(wrapInner(desc.fn), desc);
   if (desc.sanitizeOps) {
     testFn = assertOps(testFn);
   }
@@ -1117,4 +1363,8 @@ function wrapTest(desc) {
 
 import { denoNs } from "ext:runtime/90_deno_ns.js";
 denoNs.bench = bench;
+test.describe = describe;
+test.it = it;
+test.beforeEach = beforeEach;
+test.afterEach = afterEach;
 denoNs.test = test;
@@ -8,6 +8,7 @@
 use deno_core::serde_json::json;
 use tower_lsp::lsp_types::*;
 
+use super::lsp_custom::UPDATE_DEPENDENCY_COMMAND;
 use super::refactor::ALL_KNOWN_REFACTOR_ACTION_KINDS;
 use super::semantic_tokens::get_legend;
 
@@ -117,7 +118,10 @@ pub fn server_capabilities(
     rename_provider: Some(OneOf::Left(true)),
     document_link_provider: None,
     color_provider: None,
-    execute_command_provider: None,
+    execute_command_provider: Some(ExecuteCommandOptions {
+      commands: vec![UPDATE_DEPENDENCY_COMMAND.to_string()],
+      work_done_progress_options: Default::default(),
+    }),
     call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
     semantic_tokens_provider: Some(
       SemanticTokensServerCapabilities::SemanticTokensOptions(
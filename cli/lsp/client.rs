@@ -112,6 +112,13 @@ impl OutsideLockClient {
     self.0.register_capability(registrations).await
   }
 
+  pub async fn apply_edit(
+    &self,
+    edit: lsp::WorkspaceEdit,
+  ) -> Result<bool, AnyError> {
+    self.0.apply_edit(edit).await
+  }
+
   pub async fn specifier_configurations(
     &self,
     specifiers: Vec<LspClientUrl>,
@@ -189,6 +196,10 @@ trait ClientTrait: Send + Sync {
     &self,
     registrations: Vec<lsp::Registration>,
   ) -> Result<(), AnyError>;
+  async fn apply_edit(
+    &self,
+    edit: lsp::WorkspaceEdit,
+  ) -> Result<bool, AnyError>;
 }
 
 #[derive(Clone)]
@@ -318,6 +329,18 @@ impl ClientTrait for TowerClient {
       .await
       .map_err(|err| anyhow!("{}", err))
   }
+
+  async fn apply_edit(
+    &self,
+    edit: lsp::WorkspaceEdit,
+  ) -> Result<bool, AnyError> {
+    self
+      .0
+      .apply_edit(edit)
+      .await
+      .map(|response| response.applied)
+      .map_err(|err| anyhow!("{}", err))
+  }
 }
 
 #[derive(Clone)]
@@ -381,4 +404,11 @@ impl ClientTrait for ReplClient {
   ) -> Result<(), AnyError> {
     Ok(())
   }
+
+  async fn apply_edit(
+    &self,
+    _edit: lsp::WorkspaceEdit,
+  ) -> Result<bool, AnyError> {
+    Ok(false)
+  }
 }
@@ -3,7 +3,11 @@
 use super::analysis::source_range_to_lsp_range;
 use super::config::Config;
 use super::config::WorkspaceSettings;
+use super::documents::to_lsp_range;
+use super::documents::Document;
 use super::language_server;
+use super::lsp_custom::UPDATE_DEPENDENCY_COMMAND;
+use super::registry_metadata::RegistryDependency;
 use super::text::LineIndex;
 use super::tsc;
 use super::tsc::NavigationTree;
@@ -21,10 +25,12 @@ use deno_core::serde::Serialize;
 use deno_core::serde_json;
 use deno_core::serde_json::json;
 use deno_core::ModuleSpecifier;
+use deno_graph::Resolution;
 use lazy_regex::lazy_regex;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -40,6 +46,8 @@ pub enum CodeLensSource {
   Implementations,
   #[serde(rename = "references")]
   References,
+  #[serde(rename = "dependencies")]
+  Dependencies,
 }
 
 #[derive(Debug, Deserialize)]
@@ -365,6 +373,75 @@ async fn resolve_references_code_lens(
   })
 }
 
+/// Resolves the code lens of a dependency to one that updates it to the
+/// latest version of its package.
+async fn resolve_dependency_code_lens(
+  code_lens: lsp::CodeLens,
+  data: CodeLensData,
+  language_server: &language_server::Inner,
+) -> Result<lsp::CodeLens, AnyError> {
+  let asset_or_doc = language_server.get_asset_or_document(&data.specifier)?;
+  let maybe_dependency = asset_or_doc
+    .get_maybe_dependency(&code_lens.range.start)
+    .and_then(|(_, dep, _)| dep.get_code().cloned())
+    .and_then(|specifier| RegistryDependency::from_specifier(&specifier));
+  let maybe_latest_version = match &maybe_dependency {
+    Some(dependency) => language_server
+      .registry_metadata
+      .get(dependency)
+      .and_then(|metadata| metadata.latest_version.clone()),
+    None => None,
+  };
+  let command = match (maybe_dependency, maybe_latest_version) {
+    (Some(dependency), Some(latest_version))
+      if dependency.is_outdated(&latest_version) =>
+    {
+      // the range of the dependency includes the quotes around it
+      let mut range = code_lens.range;
+      let line_index = asset_or_doc.line_index();
+      let text = asset_or_doc.text();
+      let start = usize::from(line_index.offset(range.start)?);
+      if matches!(text.as_bytes().get(start), Some(b'"' | b'\'' | b'`')) {
+        range.start.character += 1;
+        range.end.character -= 1;
+      }
+      let edit = lsp::WorkspaceEdit {
+        changes: Some(HashMap::from([(
+          language_server
+            .url_map
+            .normalize_specifier(&data.specifier)?
+            .into_url(),
+          vec![lsp::TextEdit {
+            range,
+            new_text: dependency.with_version(&latest_version),
+          }],
+        )])),
+        ..Default::default()
+      };
+      lsp::Command {
+        title: format!("Update to {latest_version}"),
+        command: UPDATE_DEPENDENCY_COMMAND.to_string(),
+        arguments: Some(vec![json!(edit)]),
+      }
+    }
+    (_, Some(_)) => lsp::Command {
+      title: "Latest version".to_string(),
+      command: String::new(),
+      arguments: None,
+    },
+    (_, None) => lsp::Command {
+      title: "Unknown latest version".to_string(),
+      command: String::new(),
+      arguments: None,
+    },
+  };
+  Ok(lsp::CodeLens {
+    range: code_lens.range,
+    command: Some(command),
+    data: None,
+  })
+}
+
 pub async fn resolve_code_lens(
   code_lens: lsp::CodeLens,
   language_server: &language_server::Inner,
@@ -378,17 +455,26 @@ pub async fn resolve_code_lens(
     CodeLensSource::References => {
       resolve_references_code_lens(code_lens, data, language_server).await
     }
+    CodeLensSource::Dependencies => {
+      resolve_dependency_code_lens(code_lens, data, language_server).await
+    }
   }
 }
 
 pub async fn collect(
   specifier: &ModuleSpecifier,
   parsed_source: Option<ParsedSource>,
+  maybe_document: Option<&Document>,
   config: &Config,
   line_index: Arc<LineIndex>,
   navigation_tree: &NavigationTree,
 ) -> Result<Vec<lsp::CodeLens>, AnyError> {
   let mut code_lenses = collect_test(specifier, parsed_source, config)?;
+  if config.workspace_settings().code_lens.dependencies {
+    if let Some(document) = maybe_document {
+      code_lenses.extend(collect_dependencies(specifier, document));
+    }
+  }
   code_lenses.extend(
     collect_tsc(
       specifier,
@@ -418,6 +504,34 @@ fn collect_test(
   Ok(Vec::new())
 }
 
+/// Return a code lens for each dependency that comes from a registry, which
+/// is resolved to one that updates the dependency when it is outdated.
+fn collect_dependencies(
+  specifier: &ModuleSpecifier,
+  document: &Document,
+) -> Vec<lsp::CodeLens> {
+  document
+    .dependencies()
+    .values()
+    .filter_map(|dep| match &dep.maybe_code {
+      Resolution::Ok(resolved)
+        if RegistryDependency::from_specifier(&resolved.specifier)
+          .is_some() =>
+      {
+        Some(lsp::CodeLens {
+          range: to_lsp_range(&resolved.range),
+          command: None,
+          data: Some(json!({
+            "specifier": specifier,
+            "source": CodeLensSource::Dependencies,
+          })),
+        })
+      }
+      _ => None,
+    })
+    .collect()
+}
+
 /// Return tsc navigation tree code lenses.
 async fn collect_tsc(
   specifier: &ModuleSpecifier,
@@ -51,6 +51,10 @@ pub struct CodeLensSettings {
   /// also the `test_args` setting, but this is not used by the server.
   #[serde(default = "is_true")]
   pub test: bool,
+  /// Flag for providing code lenses that update the `npm:` and remote
+  /// dependencies which have a newer version in their registry.
+  #[serde(default)]
+  pub dependencies: bool,
 }
 
 impl Default for CodeLensSettings {
@@ -60,6 +64,7 @@ impl Default for CodeLensSettings {
       references: false,
       references_all_functions: false,
       test: true,
+      dependencies: false,
     }
   }
 }
@@ -326,6 +331,12 @@ pub struct WorkspaceSettings {
   #[serde(default = "default_document_preload_limit")]
   pub document_preload_limit: usize,
 
+  /// A flag that indicates if the metadata of `npm:` and `https://deno.land/`
+  /// dependencies, like their description and latest version, is fetched
+  /// from their registry and shown when hovering them.
+  #[serde(default)]
+  pub registry_metadata: bool,
+
   /// A flag that indicates if Dene should validate code against the unstable
   /// APIs for the workspace.
   #[serde(default)]
@@ -363,6 +374,7 @@ impl Default for WorkspaceSettings {
       internal_debug: false,
       lint: true,
       document_preload_limit: default_document_preload_limit(),
+      registry_metadata: false,
       suggest: Default::default(),
       testing: Default::default(),
       tls_certificate: None,
@@ -376,7 +388,9 @@ impl WorkspaceSettings {
   /// Determine if any code lenses are enabled at all.  This allows short
   /// circuiting when there are no code lenses enabled.
   pub fn enabled_code_lens(&self) -> bool {
-    self.code_lens.implementations || self.code_lens.references
+    self.code_lens.implementations
+      || self.code_lens.references
+      || self.code_lens.dependencies
   }
 
   /// Determine if any inlay hints are enabled. This allows short circuiting
@@ -736,6 +750,7 @@ mod tests {
           references: false,
           references_all_functions: false,
           test: true,
+          dependencies: false,
         },
         inlay_hints: InlayHintsSettings {
           parameter_names: InlayHintsParamNamesOptions {
@@ -760,6 +775,7 @@ mod tests {
         internal_debug: false,
         lint: true,
         document_preload_limit: 1_000,
+        registry_metadata: false,
         suggest: CompletionSettings {
           complete_function_calls: false,
           names: true,
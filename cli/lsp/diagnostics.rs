@@ -1000,6 +1000,8 @@ fn diagnose_resolution(
           match maybe_assert_type {
             // The module has the correct assertion type, no diagnostic
             Some("json") => (),
+            // Any module can be imported as raw bytes or text
+            Some("bytes" | "text") => (),
             // The dynamic import statement is missing an assertion type, which
             // we might not be able to statically detect, therefore we will
             // not provide a potentially incorrect diagnostic.
@@ -65,6 +65,8 @@ use super::performance::Performance;
 use super::performance::PerformanceMark;
 use super::refactor;
 use super::registries::ModuleRegistry;
+use super::registry_metadata::RegistryDependency;
+use super::registry_metadata::RegistryMetadataClient;
 use super::testing;
 use super::text;
 use super::tsc;
@@ -215,6 +217,8 @@ pub struct Inner {
   module_registries: ModuleRegistry,
   /// The path to the module registries cache
   module_registries_location: PathBuf,
+  /// Fetches what registries know about the packages of dependencies.
+  pub registry_metadata: RegistryMetadataClient,
   /// An optional path to the DENO_DIR which has been specified in the client
   /// options.
   maybe_cache_path: Option<PathBuf>,
@@ -619,6 +623,8 @@ impl Inner {
     );
     let assets = Assets::new(ts_server.clone());
     let registry_url = CliNpmRegistryApi::default_url();
+    let registry_metadata =
+      RegistryMetadataClient::new(http_client.clone(), registry_url.clone());
     let progress_bar = ProgressBar::new(ProgressBarStyle::TextOnly);
 
     let (npm_api, npm_cache) = create_npm_api_and_cache(
@@ -655,6 +661,7 @@ impl Inner {
       maybe_testing_server: None,
       module_registries,
       module_registries_location,
+      registry_metadata,
       npm: LspNpmServices {
         config_hash: LspNpmConfigHash(0), // this will be updated in initialize
         api: npm_api,
@@ -866,8 +873,15 @@ impl Inner {
       ),
       self.maybe_lockfile().cloned(),
     ));
-    let node_resolver =
-      Arc::new(NodeResolver::new(node_fs, npm_resolver.clone()));
+    let node_conditions = self
+      .maybe_config_file()
+      .and_then(|c| c.node_conditions())
+      .unwrap_or_default();
+    let node_resolver = Arc::new(NodeResolver::new(
+      node_fs,
+      npm_resolver.clone(),
+      node_conditions,
+    ));
     Arc::new(StateSnapshot {
       assets: self.assets.snapshot(),
       cache_metadata: self.cache_metadata.clone(),
@@ -946,6 +960,10 @@ impl Inner {
       self.http_client.clone(),
     );
     self.module_registries_location = module_registries_location;
+    self.registry_metadata = RegistryMetadataClient::new(
+      self.http_client.clone(),
+      CliNpmRegistryApi::default_url().clone(),
+    );
     // update the cache path
     let location = dir.deps_folder_path();
     self.documents.set_location(&location);
@@ -1817,6 +1835,22 @@ impl Inner {
         } else {
           value
         };
+      let maybe_dependency =
+        if self.config.workspace_settings().registry_metadata {
+          dep.get_code().and_then(RegistryDependency::from_specifier)
+        } else {
+          None
+        };
+      let value = if let Some(dependency) = maybe_dependency {
+        match self.registry_metadata.get(&dependency) {
+          Some(metadata) => {
+            format!("{value}\n\n---\n\n{}", metadata.to_hover_text(&dependency))
+          }
+          None => value,
+        }
+      } else {
+        value
+      };
       Some(Hover {
         contents: HoverContents::Markup(MarkupContent {
           kind: MarkupKind::Markdown,
@@ -2098,6 +2132,7 @@ impl Inner {
     let code_lenses = code_lens::collect(
       &specifier,
       parsed_source,
+      asset_or_doc.document(),
       &self.config,
       line_index,
       &navigation_tree,
@@ -3243,6 +3278,33 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.0.read().await.code_lens_resolve(params).await
   }
 
+  async fn execute_command(
+    &self,
+    params: ExecuteCommandParams,
+  ) -> LspResult<Option<Value>> {
+    if params.command != lsp_custom::UPDATE_DEPENDENCY_COMMAND {
+      return Err(LspError::invalid_params(format!(
+        "Unknown command \"{}\".",
+        params.command
+      )));
+    }
+    let edit: WorkspaceEdit = params
+      .arguments
+      .into_iter()
+      .next()
+      .and_then(|value| serde_json::from_value(value).ok())
+      .ok_or_else(|| {
+        LspError::invalid_params("Expected a workspace edit argument.")
+      })?;
+    // apply the edit outside of the lock, as the client will likely call
+    // back into the server with the changes
+    let client = self.0.read().await.client.clone();
+    if let Err(err) = client.when_outside_lsp_lock().apply_edit(edit).await {
+      error!("Error applying the dependency update: {}", err);
+    }
+    Ok(None)
+  }
+
   async fn document_highlight(
     &self,
     params: DocumentHighlightParams,
@@ -13,6 +13,10 @@ pub const VIRTUAL_TEXT_DOCUMENT: &str = "deno/virtualTextDocument";
 pub const LATEST_DIAGNOSTIC_BATCH_INDEX: &str =
   "deno/internalLatestDiagnosticBatchIndex";
 
+/// The command of the code lenses that update a dependency, which applies
+/// the workspace edit it is given.
+pub const UPDATE_DEPENDENCY_COMMAND: &str = "deno.updateDependency";
+
 // While lsp_types supports inlay hints currently, tower_lsp does not.
 pub const INLAY_HINT: &str = "textDocument/inlayHint";
 
@@ -27,6 +27,7 @@ mod path_to_regex;
 mod performance;
 mod refactor;
 mod registries;
+mod registry_metadata;
 mod repl;
 mod semantic_tokens;
 mod testing;
@@ -0,0 +1,408 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The metadata that registries keep about the packages which are imported
+//! with `npm:` and `https://deno.land/` specifiers, like their description
+//! and latest version. It is shown when hovering a dependency and used to
+//! offer updating it.
+//!
+//! The metadata is fetched in the background, so that the language server is
+//! never kept waiting on a registry. It is there once it is asked for again.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde::Deserialize;
+use deno_core::serde_json;
+use deno_core::task::spawn;
+use deno_core::url::Url;
+use deno_core::ModuleSpecifier;
+use deno_semver::npm::NpmPackageReqReference;
+use deno_semver::Version;
+use lazy_regex::lazy_regex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::logging::lsp_warn;
+use crate::http_util::HttpClient;
+
+/// How long the metadata of a package is kept before it is fetched again.
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+/// How long fetching the metadata of a package may take.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+static DENO_LAND_RE: Lazy<Regex> =
+  lazy_regex!(r"^https://deno\.land/(?:x/([^/@]+)|(std))@([^/]+)(/.*)?$");
+
+/// A dependency that comes from a registry which can be asked about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryDependency {
+  Npm(NpmPackageReqReference),
+  DenoLand {
+    name: String,
+    version: String,
+    path: String,
+  },
+}
+
+impl RegistryDependency {
+  pub fn from_specifier(specifier: &ModuleSpecifier) -> Option<Self> {
+    if specifier.scheme() == "npm" {
+      return NpmPackageReqReference::from_specifier(specifier)
+        .ok()
+        .map(Self::Npm);
+    }
+    let captures = DENO_LAND_RE.captures(specifier.as_str())?;
+    let name = captures.get(1).or_else(|| captures.get(2))?.as_str();
+    Some(Self::DenoLand {
+      name: name.to_string(),
+      version: captures[3].to_string(),
+      path: captures
+        .get(4)
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_default(),
+    })
+  }
+
+  pub fn name(&self) -> &str {
+    match self {
+      Self::Npm(reference) => &reference.req.name,
+      Self::DenoLand { name, .. } => name,
+    }
+  }
+
+  /// Whether the version that is asked for can't be `version`. Dependencies
+  /// without a version are always up to date.
+  pub fn is_outdated(&self, version: &str) -> bool {
+    match self {
+      Self::Npm(reference) => {
+        match (&reference.req.version_req, Version::parse_from_npm(version)) {
+          (Some(version_req), Ok(version)) => !version_req.matches(&version),
+          _ => false,
+        }
+      }
+      Self::DenoLand {
+        version: current, ..
+      } => current != version,
+    }
+  }
+
+  /// The specifier of the same module in another version of the package.
+  pub fn with_version(&self, version: &str) -> String {
+    match self {
+      Self::Npm(reference) => {
+        let sub_path = reference
+          .sub_path
+          .as_ref()
+          .map(|sub_path| format!("/{sub_path}"))
+          .unwrap_or_default();
+        format!("npm:{}@{}{}", reference.req.name, version, sub_path)
+      }
+      Self::DenoLand { name, path, .. } if name == "std" => {
+        format!("https://deno.land/std@{version}{path}")
+      }
+      Self::DenoLand { name, path, .. } => {
+        format!("https://deno.land/x/{name}@{version}{path}")
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageMetadata {
+  pub description: Option<String>,
+  pub latest_version: Option<String>,
+  /// The deprecation messages of the deprecated versions.
+  pub deprecated_versions: HashMap<String, String>,
+}
+
+impl PackageMetadata {
+  fn from_npm_packument(text: &str) -> Result<Self, AnyError> {
+    #[derive(Deserialize)]
+    struct VersionInfo {
+      deprecated: Option<String>,
+    }
+    #[derive(Deserialize)]
+    struct Packument {
+      description: Option<String>,
+      #[serde(rename = "dist-tags", default)]
+      dist_tags: HashMap<String, String>,
+      #[serde(default)]
+      versions: HashMap<String, VersionInfo>,
+    }
+
+    let mut packument: Packument = serde_json::from_str(text)?;
+    Ok(Self {
+      description: packument.description,
+      latest_version: packument.dist_tags.remove("latest"),
+      deprecated_versions: packument
+        .versions
+        .into_iter()
+        .filter_map(|(version, info)| Some((version, info.deprecated?)))
+        .collect(),
+    })
+  }
+
+  fn from_deno_land_module(text: &str) -> Result<Self, AnyError> {
+    #[derive(Deserialize)]
+    struct Module {
+      description: Option<String>,
+      latest_version: Option<String>,
+    }
+
+    let module: Module = serde_json::from_str(text)?;
+    Ok(Self {
+      description: module.description,
+      latest_version: module.latest_version,
+      deprecated_versions: HashMap::new(),
+    })
+  }
+
+  /// The markdown that is appended to the hover of a dependency.
+  pub fn to_hover_text(&self, dependency: &RegistryDependency) -> String {
+    let mut parts = vec![format!("**{}**", dependency.name())];
+    if let Some(description) = &self.description {
+      parts.push(description.clone());
+    }
+    if let Some(latest_version) = &self.latest_version {
+      parts.push(format!("**Latest Version**: {latest_version}"));
+    }
+    let deprecation = match dependency {
+      RegistryDependency::Npm(reference) => {
+        reference.req.version_req.as_ref().and_then(|version_req| {
+          self
+            .deprecated_versions
+            .iter()
+            .find_map(|(version, message)| {
+              let version = Version::parse_from_npm(version).ok()?;
+              version_req.matches(&version).then_some(message)
+            })
+        })
+      }
+      RegistryDependency::DenoLand { version, .. } => {
+        self.deprecated_versions.get(version)
+      }
+    };
+    if let Some(message) = deprecation {
+      parts.push(format!("**Deprecated**: {message}"));
+    }
+    parts.join("\n\n")
+  }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+  fetched_at: Instant,
+  metadata: Option<Arc<PackageMetadata>>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+  entries: HashMap<Url, CacheEntry>,
+  /// The URLs whose metadata is being fetched.
+  fetching: HashSet<Url>,
+}
+
+/// Fetches the metadata of packages and keeps it in memory for a while,
+/// including the failures to get it.
+#[derive(Debug, Clone)]
+pub struct RegistryMetadataClient {
+  http_client: Arc<HttpClient>,
+  npm_registry_url: Url,
+  cache: Arc<Mutex<Cache>>,
+}
+
+impl RegistryMetadataClient {
+  pub fn new(http_client: Arc<HttpClient>, npm_registry_url: Url) -> Self {
+    Self {
+      http_client,
+      npm_registry_url,
+      cache: Default::default(),
+    }
+  }
+
+  fn metadata_url(&self, dependency: &RegistryDependency) -> Option<Url> {
+    match dependency {
+      RegistryDependency::Npm(reference) => {
+        self.npm_registry_url.join(&reference.req.name).ok()
+      }
+      RegistryDependency::DenoLand { name, .. } => {
+        Url::parse(&format!("https://apiland.deno.dev/v2/modules/{name}")).ok()
+      }
+    }
+  }
+
+  /// Gets the metadata of a package that was fetched before. If there is
+  /// none yet, or it is outdated, it is fetched in the background, and the
+  /// outdated metadata is returned in the meantime.
+  pub fn get(
+    &self,
+    dependency: &RegistryDependency,
+  ) -> Option<Arc<PackageMetadata>> {
+    let url = self.metadata_url(dependency)?;
+    let mut cache = self.cache.lock();
+    let maybe_outdated = match cache.entries.get(&url) {
+      Some(entry) if entry.fetched_at.elapsed() < CACHE_TTL => {
+        return entry.metadata.clone();
+      }
+      Some(entry) => entry.metadata.clone(),
+      None => None,
+    };
+    if cache.fetching.insert(url.clone()) {
+      let client = self.clone();
+      let dependency = dependency.clone();
+      spawn(async move {
+        let metadata = match tokio::time::timeout(
+          FETCH_TIMEOUT,
+          client.fetch(&dependency, &url),
+        )
+        .await
+        {
+          Ok(Ok(metadata)) => Some(Arc::new(metadata)),
+          Ok(Err(err)) => {
+            lsp_warn!("Unable to get the metadata of \"{}\": {}", url, err);
+            None
+          }
+          Err(_) => {
+            lsp_warn!("Timed out getting the metadata of \"{}\"", url);
+            None
+          }
+        };
+        let mut cache = client.cache.lock();
+        cache.fetching.remove(&url);
+        cache.entries.insert(
+          url,
+          CacheEntry {
+            fetched_at: Instant::now(),
+            metadata,
+          },
+        );
+      });
+    }
+    maybe_outdated
+  }
+
+  async fn fetch(
+    &self,
+    dependency: &RegistryDependency,
+    url: &Url,
+  ) -> Result<PackageMetadata, AnyError> {
+    let text = self.http_client.download_text(url.clone()).await?;
+    match dependency {
+      RegistryDependency::Npm(_) => PackageMetadata::from_npm_packument(&text),
+      RegistryDependency::DenoLand { .. } => {
+        PackageMetadata::from_deno_land_module(&text)
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn dependency(specifier: &str) -> RegistryDependency {
+    RegistryDependency::from_specifier(&Url::parse(specifier).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn registry_dependency() {
+    let chalk = dependency("npm:chalk@4.1.0");
+    assert_eq!(chalk.name(), "chalk");
+    assert!(chalk.is_outdated("5.3.0"));
+    assert!(!chalk.is_outdated("4.1.0"));
+    assert_eq!(chalk.with_version("5.3.0"), "npm:chalk@5.3.0");
+
+    let preact = dependency("npm:@preact/signals@^1.0.0/react");
+    assert_eq!(preact.name(), "@preact/signals");
+    assert!(!preact.is_outdated("1.2.0"));
+    assert_eq!(
+      preact.with_version("2.0.0"),
+      "npm:@preact/signals@2.0.0/react"
+    );
+    assert!(!dependency("npm:chalk").is_outdated("5.3.0"));
+
+    let oak = dependency("https://deno.land/x/oak@v12.0.0/mod.ts");
+    assert_eq!(oak.name(), "oak");
+    assert!(oak.is_outdated("v12.6.0"));
+    assert_eq!(
+      oak.with_version("v12.6.0"),
+      "https://deno.land/x/oak@v12.6.0/mod.ts"
+    );
+    let std = dependency("https://deno.land/std@0.190.0/path/mod.ts");
+    assert_eq!(std.name(), "std");
+    assert_eq!(
+      std.with_version("0.200.0"),
+      "https://deno.land/std@0.200.0/path/mod.ts"
+    );
+
+    for specifier in [
+      "https://deno.land/x/oak/mod.ts",
+      "https://example.com/x/oak@v1.0.0/mod.ts",
+      "file:///a/mod.ts",
+    ] {
+      let specifier = Url::parse(specifier).unwrap();
+      assert_eq!(RegistryDependency::from_specifier(&specifier), None);
+    }
+  }
+
+  #[test]
+  fn npm_packument_metadata() {
+    let metadata = PackageMetadata::from_npm_packument(
+      r#"{
+        "name": "chalk",
+        "description": "Terminal string styling done right",
+        "dist-tags": { "latest": "5.3.0", "next": "6.0.0-rc.1" },
+        "versions": {
+          "4.1.0": { "deprecated": "Upgrade to 4.1.1" },
+          "4.1.1": {},
+          "5.3.0": {}
+        }
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(
+      metadata,
+      PackageMetadata {
+        description: Some("Terminal string styling done right".to_string()),
+        latest_version: Some("5.3.0".to_string()),
+        deprecated_versions: HashMap::from([(
+          "4.1.0".to_string(),
+          "Upgrade to 4.1.1".to_string()
+        )]),
+      }
+    );
+    assert_eq!(
+      metadata.to_hover_text(&dependency("npm:chalk@4.1.0")),
+      "**chalk**\n\nTerminal string styling done right\n\n**Latest Version**: 5.3.0\n\n**Deprecated**: Upgrade to 4.1.1"
+    );
+    assert_eq!(
+      metadata.to_hover_text(&dependency("npm:chalk@5")),
+      "**chalk**\n\nTerminal string styling done right\n\n**Latest Version**: 5.3.0"
+    );
+  }
+
+  #[test]
+  fn deno_land_module_metadata() {
+    let metadata = PackageMetadata::from_deno_land_module(
+      r#"{
+        "name": "oak",
+        "description": "A middleware framework for handling HTTP with Deno",
+        "versions": ["v12.6.0", "v12.5.0"],
+        "latest_version": "v12.6.0"
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(metadata.latest_version.as_deref(), Some("v12.6.0"));
+    assert_eq!(
+      metadata.to_hover_text(&dependency(
+        "https://deno.land/x/oak@v12.5.0/mod.ts"
+      )),
+      "**oak**\n\nA middleware framework for handling HTTP with Deno\n\n**Latest Version**: v12.6.0"
+    );
+  }
+}
@@ -360,6 +360,9 @@ impl TestRun {
             test::TestEvent::Output(output) => {
               reporter.report_output(&output);
             }
+            test::TestEvent::ModuleOutput(_, output) => {
+              reporter.report_output(&output);
+            }
             test::TestEvent::Result(id, result, elapsed) => {
               let description = tests.read().get(&id).unwrap().clone();
               match &result {
@@ -30,6 +30,8 @@ use crate::args::flags_from_vec;
 use crate::args::DenoSubcommand;
 use crate::args::Flags;
 use crate::util::display;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 use crate::util::v8::get_v8_flags_from_env;
 use crate::util::v8::init_v8_flags;
 
@@ -47,6 +49,8 @@ use std::env;
 use std::env::current_exe;
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Ensures that all subcommands return an i32 exit code and an [`AnyError`] error type.
 trait SubcommandOutput {
@@ -81,7 +85,10 @@ fn spawn_subcommand<F: Future<Output = T> + 'static, T: SubcommandOutput>(
   deno_core::task::spawn(f.map(|r| r.output()))
 }
 
-async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
+async fn run_subcommand(
+  flags: Flags,
+  startup_timer: Arc<PhaseTimer>,
+) -> Result<i32, AnyError> {
   let handle = match flags.subcommand.clone() {
     DenoSubcommand::Bench(bench_flags) => spawn_subcommand(async {
       let cli_options = CliOptions::from_flags(flags)?;
@@ -158,9 +165,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     }
     DenoSubcommand::Run(run_flags) => spawn_subcommand(async move {
       if run_flags.is_stdin() {
-        tools::run::run_from_stdin(flags).await
+        tools::run::run_from_stdin(flags, startup_timer).await
       } else {
-        tools::run::run_script(flags).await
+        tools::run::run_script(flags, startup_timer).await
       }
     }),
     DenoSubcommand::Task(task_flags) => spawn_subcommand(async {
@@ -205,7 +212,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     }),
   };
 
-  handle.await?
+  let result = handle.await?;
+  file_fetcher::wait_for_revalidations().await;
+  result
 }
 
 fn setup_panic_hook() {
@@ -257,6 +266,7 @@ fn unwrap_or_exit<T>(result: Result<T, AnyError>) -> T {
 }
 
 pub fn main() {
+  let start = Instant::now();
   setup_panic_hook();
 
   util::unix::raise_fd_limit();
@@ -273,16 +283,17 @@ pub fn main() {
   let future = async move {
     let current_exe_path = current_exe()?;
     let standalone_res =
-      match standalone::extract_standalone(&current_exe_path, args.clone())
-        .await
-      {
-        Ok(Some((metadata, eszip))) => standalone::run(eszip, metadata).await,
+      match standalone::extract_standalone(&current_exe_path, args.clone()) {
+        Ok(Some((metadata, modules))) => {
+          standalone::run(modules, metadata).await
+        }
         Ok(None) => Ok(()),
         Err(err) => Err(err),
       };
     // TODO(bartlomieju): doesn't handle exit code set by the runtime properly
     unwrap_or_exit(standalone_res);
 
+    let flag_parse_start = Instant::now();
     let flags = match flags_from_vec(args) {
       Ok(flags) => flags,
       Err(err @ clap::Error { .. })
@@ -294,6 +305,8 @@ pub fn main() {
       }
       Err(err) => unwrap_or_exit(Err(AnyError::from(err))),
     };
+    let startup_timer = Arc::new(PhaseTimer::new(start, flags.startup_report));
+    startup_timer.record(StartupPhase::FlagParse, flag_parse_start.elapsed());
 
     let default_v8_flags = match flags.subcommand {
       // Using same default as VSCode:
@@ -305,7 +318,7 @@ pub fn main() {
 
     util::logger::init(flags.log_level);
 
-    run_subcommand(flags).await
+    run_subcommand(flags, startup_timer).await
   };
 
   let exit_code = unwrap_or_exit(create_and_run_current_thread(future));
@@ -5,6 +5,8 @@ use crate::args::DenoSubcommand;
 use crate::args::TsTypeLib;
 use crate::cache::ParsedSourceCache;
 use crate::emit::Emitter;
+use crate::file_fetcher::FileFetcher;
+use crate::graph_util::asset_lock_or_exit;
 use crate::graph_util::graph_lock_or_exit;
 use crate::graph_util::graph_valid_with_cli_options;
 use crate::graph_util::ModuleGraphBuilder;
@@ -14,6 +16,8 @@ use crate::node::CliNodeCodeTranslator;
 use crate::resolver::CliGraphResolver;
 use crate::tools::check;
 use crate::tools::check::TypeChecker;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::text_encoding::code_without_source_map;
 use crate::util::text_encoding::source_map_from_code;
@@ -48,6 +52,7 @@ use deno_runtime::deno_fs;
 use deno_runtime::deno_node::NodeResolution;
 use deno_runtime::deno_node::NodeResolutionMode;
 use deno_runtime::deno_node::NodeResolver;
+use deno_runtime::deno_node::EMPTY_MODULE_SPECIFIER;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_semver::npm::NpmPackageNvReference;
 use deno_semver::npm::NpmPackageReqReference;
@@ -68,6 +73,7 @@ pub struct ModuleLoadPreparer {
   progress_bar: ProgressBar,
   resolver: Arc<CliGraphResolver>,
   type_checker: Arc<TypeChecker>,
+  startup_timer: Arc<PhaseTimer>,
 }
 
 impl ModuleLoadPreparer {
@@ -82,6 +88,7 @@ impl ModuleLoadPreparer {
     progress_bar: ProgressBar,
     resolver: Arc<CliGraphResolver>,
     type_checker: Arc<TypeChecker>,
+    startup_timer: Arc<PhaseTimer>,
   ) -> Self {
     Self {
       options,
@@ -93,6 +100,7 @@ impl ModuleLoadPreparer {
       progress_bar,
       resolver,
       type_checker,
+      startup_timer,
     }
   }
 
@@ -171,6 +179,7 @@ impl ModuleLoadPreparer {
     if self.options.type_check_mode().is_true()
       && !self.graph_container.is_type_checked(&roots, lib)
     {
+      let _timer = self.startup_timer.measure(StartupPhase::TypeCheck);
       let graph = Arc::new(graph.segment(&roots));
       self
         .type_checker
@@ -213,6 +222,21 @@ impl ModuleLoadPreparer {
       )
       .await
   }
+
+  /// Checks the contents of a module imported with a `bytes` or `text` type
+  /// assertion against the lockfile, as they aren't part of the graph.
+  pub fn lock_asset(
+    &self,
+    specifier: &ModuleSpecifier,
+    bytes: &[u8],
+  ) -> Result<(), AnyError> {
+    if let Some(lockfile) = &self.lockfile {
+      let mut lockfile = lockfile.lock();
+      asset_lock_or_exit(&mut lockfile, specifier, bytes);
+      lockfile.write().context("Failed writing lockfile.")?;
+    }
+    Ok(())
+  }
 }
 
 pub struct ModuleCodeSource {
@@ -309,6 +333,7 @@ struct SharedCliModuleLoaderState {
   prepared_module_loader: PreparedModuleLoader,
   resolver: Arc<CliGraphResolver>,
   npm_module_loader: NpmModuleLoader,
+  file_fetcher: Arc<FileFetcher>,
 }
 
 pub struct CliModuleLoaderFactory {
@@ -324,6 +349,7 @@ impl CliModuleLoaderFactory {
     parsed_source_cache: Arc<ParsedSourceCache>,
     resolver: Arc<CliGraphResolver>,
     npm_module_loader: NpmModuleLoader,
+    file_fetcher: Arc<FileFetcher>,
   ) -> Self {
     Self {
       shared: Arc::new(SharedCliModuleLoaderState {
@@ -340,6 +366,7 @@ impl CliModuleLoaderFactory {
         module_load_preparer,
         resolver,
         npm_module_loader,
+        file_fetcher,
       }),
     }
   }
@@ -561,6 +588,29 @@ impl ModuleLoader for CliModuleLoader {
     )))
   }
 
+  fn load_bytes(
+    &self,
+    specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    is_dynamic: bool,
+  ) -> Pin<Box<deno_core::ModuleSourceFuture>> {
+    let permissions = if is_dynamic {
+      self.dynamic_permissions.clone()
+    } else {
+      self.root_permissions.clone()
+    };
+    let file_fetcher = self.shared.file_fetcher.clone();
+    let module_load_preparer = self.shared.module_load_preparer.clone();
+    let specifier = specifier.clone();
+    async move {
+      let (found_url, bytes) =
+        file_fetcher.fetch_bytes(&specifier, permissions).await?;
+      module_load_preparer.lock_asset(&found_url, &bytes)?;
+      Ok(ModuleSource::new_bytes(bytes, &specifier, &found_url))
+    }
+    .boxed_local()
+  }
+
   fn prepare_load(
     &self,
     specifier: &ModuleSpecifier,
@@ -717,7 +767,9 @@ impl NpmModuleLoader {
     &self,
     specifier: &ModuleSpecifier,
   ) -> Option<Result<(), AnyError>> {
-    if self.node_resolver.in_npm_package(specifier) {
+    if self.node_resolver.in_npm_package(specifier)
+      || specifier.as_str() == EMPTY_MODULE_SPECIFIER
+    {
       // nothing to prepare
       Some(Ok(()))
     } else {
@@ -731,7 +783,14 @@ impl NpmModuleLoader {
     maybe_referrer: Option<&ModuleSpecifier>,
     permissions: &PermissionsContainer,
   ) -> Option<Result<ModuleCodeSource, AnyError>> {
-    if self.node_resolver.in_npm_package(specifier) {
+    if specifier.as_str() == EMPTY_MODULE_SPECIFIER {
+      // a module mapped to `false` in the "browser" field of a package
+      Some(Ok(ModuleCodeSource {
+        code: ModuleCode::from_static("export default {};"),
+        found_url: specifier.clone(),
+        media_type: MediaType::JavaScript,
+      }))
+    } else if self.node_resolver.in_npm_package(specifier) {
       Some(self.load_sync(specifier, maybe_referrer, permissions))
     } else {
       None
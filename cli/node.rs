@@ -2,16 +2,19 @@
 
 use std::collections::HashSet;
 
+use deno_ast::swc::ast;
 use deno_ast::swc::common::SyntaxContext;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
 use deno_ast::view::Node;
 use deno_ast::view::NodeTrait;
-use deno_ast::CjsAnalysis;
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_ast::ParsedSource;
 use deno_ast::SourceRanged;
 use deno_core::error::AnyError;
-use deno_runtime::deno_node::analyze::CjsAnalysis as ExtNodeCjsAnalysis;
+use deno_runtime::deno_node::analyze::CjsAnalysis;
 use deno_runtime::deno_node::analyze::CjsEsmCodeAnalyzer;
 use deno_runtime::deno_node::analyze::NodeCodeTranslator;
 
@@ -63,10 +66,7 @@ impl CliCjsEsmCodeAnalyzer {
 
     let media_type = MediaType::from_specifier(specifier);
     if media_type == MediaType::Json {
-      return Ok(CjsAnalysis {
-        exports: vec![],
-        reexports: vec![],
-      });
+      return Ok(CjsAnalysis::default());
     }
 
     let parsed_source = deno_ast::parse_script(deno_ast::ParseParams {
@@ -77,7 +77,7 @@ impl CliCjsEsmCodeAnalyzer {
       scope_analysis: false,
       maybe_syntax: None,
     })?;
-    let analysis = parsed_source.analyze_cjs();
+    let analysis = analyze_cjs(&parsed_source);
     self
       .cache
       .set_cjs_analysis(specifier.as_str(), &source_hash, &analysis);
@@ -91,12 +91,8 @@ impl CjsEsmCodeAnalyzer for CliCjsEsmCodeAnalyzer {
     &self,
     specifier: &ModuleSpecifier,
     source: &str,
-  ) -> Result<ExtNodeCjsAnalysis, AnyError> {
-    let analysis = self.inner_cjs_analysis(specifier, source)?;
-    Ok(ExtNodeCjsAnalysis {
-      exports: analysis.exports,
-      reexports: analysis.reexports,
-    })
+  ) -> Result<CjsAnalysis, AnyError> {
+    self.inner_cjs_analysis(specifier, source)
   }
 
   fn analyze_esm_top_level_decls(
@@ -136,6 +132,152 @@ impl CjsEsmCodeAnalyzer for CliCjsEsmCodeAnalyzer {
   }
 }
 
+fn analyze_cjs(parsed_source: &ParsedSource) -> CjsAnalysis {
+  let analysis = parsed_source.analyze_cjs();
+  let mut collector = DynamicReexportsCollector::default();
+  parsed_source.program().visit_with(&mut collector);
+  CjsAnalysis {
+    exports: analysis.exports,
+    reexports: analysis.reexports,
+    conditional_reexports: collector.conditional_reexports,
+    member_reexports: collector.member_reexports,
+    member_requires: collector.member_requires,
+  }
+}
+
+/// Collects the re-exports that the CJS analysis of deno_ast misses, because
+/// the re-exported module is picked at runtime.
+#[derive(Default)]
+struct DynamicReexportsCollector {
+  conditional_reexports: Vec<String>,
+  member_reexports: Vec<String>,
+  member_requires: Vec<String>,
+}
+
+impl Visit for DynamicReexportsCollector {
+  noop_visit_type!();
+
+  fn visit_assign_expr(&mut self, assign_expr: &ast::AssignExpr) {
+    if assign_expr.op == ast::AssignOp::Assign {
+      if let Some(left) = assign_target_expr(&assign_expr.left) {
+        let right = unwrap_parens(&assign_expr.right);
+        if is_module_exports(left) {
+          self.visit_module_exports_value(right);
+        } else if is_exports_member(left) {
+          if let Some(request) = require_request(right) {
+            self.member_requires.push(request);
+          }
+        }
+      }
+    }
+    assign_expr.visit_children_with(self);
+  }
+}
+
+impl DynamicReexportsCollector {
+  fn visit_module_exports_value(&mut self, value: &ast::Expr) {
+    match value {
+      // module.exports = flag ? require("./a") : require("./b")
+      ast::Expr::Cond(cond_expr) => {
+        for expr in [&cond_expr.cons, &cond_expr.alt] {
+          if let Some(request) = require_request(unwrap_parens(expr)) {
+            self.conditional_reexports.push(request);
+          }
+        }
+      }
+      // module.exports = require("./impl")[flag]
+      ast::Expr::Member(member_expr)
+        if matches!(member_expr.prop, ast::MemberProp::Computed(_)) =>
+      {
+        if let Some(request) = require_request(unwrap_parens(&member_expr.obj))
+        {
+          self.member_reexports.push(request);
+        }
+      }
+      // module.exports = { dev: require("./dev") }
+      ast::Expr::Object(object_lit) => {
+        for prop in &object_lit.props {
+          if let ast::PropOrSpread::Prop(prop) = prop {
+            if let ast::Prop::KeyValue(key_value) = &**prop {
+              if let Some(request) =
+                require_request(unwrap_parens(&key_value.value))
+              {
+                self.member_requires.push(request);
+              }
+            }
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn assign_target_expr(target: &ast::PatOrExpr) -> Option<&ast::Expr> {
+  match target {
+    ast::PatOrExpr::Expr(expr) => Some(expr),
+    ast::PatOrExpr::Pat(pat) => match &**pat {
+      ast::Pat::Expr(expr) => Some(expr),
+      _ => None,
+    },
+  }
+}
+
+fn unwrap_parens(mut expr: &ast::Expr) -> &ast::Expr {
+  while let ast::Expr::Paren(paren_expr) = expr {
+    expr = &paren_expr.expr;
+  }
+  expr
+}
+
+fn is_ident(expr: &ast::Expr, name: &str) -> bool {
+  matches!(expr, ast::Expr::Ident(ident) if &*ident.sym == name)
+}
+
+/// Gets if the expression is `module.exports`.
+fn is_module_exports(expr: &ast::Expr) -> bool {
+  match expr {
+    ast::Expr::Member(member_expr) => {
+      is_ident(&member_expr.obj, "module")
+        && matches!(
+          &member_expr.prop,
+          ast::MemberProp::Ident(ident) if &*ident.sym == "exports"
+        )
+    }
+    _ => false,
+  }
+}
+
+/// Gets if the expression is a member of `exports` or `module.exports`.
+fn is_exports_member(expr: &ast::Expr) -> bool {
+  match expr {
+    ast::Expr::Member(member_expr) => {
+      is_ident(&member_expr.obj, "exports")
+        || is_module_exports(&member_expr.obj)
+    }
+    _ => false,
+  }
+}
+
+/// Gets the request of a `require("...")` call.
+fn require_request(expr: &ast::Expr) -> Option<String> {
+  let call_expr = match expr {
+    ast::Expr::Call(call_expr) => call_expr,
+    _ => return None,
+  };
+  match &call_expr.callee {
+    ast::Callee::Expr(callee) if is_ident(callee, "require") => {}
+    _ => return None,
+  }
+  match call_expr.args.as_slice() {
+    [arg] if arg.spread.is_none() => match &*arg.expr {
+      ast::Expr::Lit(ast::Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+      _ => None,
+    },
+    _ => None,
+  }
+}
+
 fn analyze_top_level_decls(
   parsed_source: &ParsedSource,
 ) -> Result<HashSet<String>, AnyError> {
@@ -199,3 +341,70 @@ fn is_local_declaration_ident(node: Node) -> bool {
     false
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn analyze(source: &str) -> CjsAnalysis {
+    let parsed_source = deno_ast::parse_script(deno_ast::ParseParams {
+      specifier: "file:///mod.cjs".to_string(),
+      text_info: deno_ast::SourceTextInfo::from_string(source.to_string()),
+      media_type: MediaType::Cjs,
+      capture_tokens: true,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })
+    .unwrap();
+    analyze_cjs(&parsed_source)
+  }
+
+  #[test]
+  fn analyze_conditional_reexports() {
+    let analysis = analyze(
+      "module.exports = process.env.NODE_ENV === 'production'\n  ? require('./prod.js')\n  : (require(\"./dev.js\"));",
+    );
+    assert_eq!(
+      analysis.conditional_reexports,
+      vec!["./prod.js".to_string(), "./dev.js".to_string()]
+    );
+    assert!(analysis.member_reexports.is_empty());
+    // a string that merely looks like the code isn't a re-export
+    let analysis = analyze(
+      "const s = \"module.exports = a ? require('./a') : require('./b')\";",
+    );
+    assert!(analysis.conditional_reexports.is_empty());
+  }
+
+  #[test]
+  fn analyze_member_reexports() {
+    let analysis = analyze(
+      "module.exports = require('./impl')[process.env.FLAG ? 'a' : 'b'];",
+    );
+    assert_eq!(analysis.member_reexports, vec!["./impl".to_string()]);
+    assert!(analysis.conditional_reexports.is_empty());
+    let analysis = analyze("module.exports = require('./impl');");
+    assert_eq!(analysis.reexports, vec!["./impl".to_string()]);
+    assert!(analysis.member_reexports.is_empty());
+  }
+
+  #[test]
+  fn analyze_member_requires() {
+    let analysis = analyze(
+      "exports.a = require('./a');\nmodule.exports.b = require(\"./b\");",
+    );
+    assert_eq!(
+      analysis.member_requires,
+      vec!["./a".to_string(), "./b".to_string()]
+    );
+    let analysis = analyze(
+      "module.exports = {\n  a: require('./a'),\n  'b': require('./b'),\n};",
+    );
+    assert_eq!(
+      analysis.member_requires,
+      vec!["./a".to_string(), "./b".to_string()]
+    );
+    let analysis = analyze("const a = require('./a');");
+    assert!(analysis.member_requires.is_empty());
+  }
+}
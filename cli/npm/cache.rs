@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
@@ -11,7 +12,11 @@ use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
+use deno_core::futures::future::BoxFuture;
+use deno_core::futures::future::Shared;
+use deno_core::futures::FutureExt;
 use deno_core::parking_lot::Mutex;
+use deno_core::task::spawn;
 use deno_core::url::Url;
 use deno_npm::registry::NpmPackageVersionDistInfo;
 use deno_npm::NpmPackageCacheFolderId;
@@ -26,6 +31,8 @@ use crate::util::fs::hard_link_dir_recursive;
 use crate::util::path::root_url_to_safe_local_dirname;
 use crate::util::progress_bar::ProgressBar;
 
+use super::fetch_queue::NpmFetchPriority;
+use super::fetch_queue::NpmFetchQueue;
 use super::tarball::verify_and_extract_tarball;
 
 static SHOULD_SYNC_DOWNLOAD: Lazy<bool> =
@@ -276,15 +283,36 @@ impl ReadonlyNpmCache {
   }
 }
 
+/// Resolves to whether the package was cached by the prefetch.
+type PrefetchFuture = Shared<BoxFuture<'static, bool>>;
+
 /// Stores a single copy of npm packages in a cache.
-#[derive(Debug)]
 pub struct NpmCache {
   readonly: ReadonlyNpmCache,
   cache_setting: CacheSetting,
   http_client: Arc<HttpClient>,
   progress_bar: ProgressBar,
+  fetch_queue: NpmFetchQueue,
   /// ensures a package is only downloaded once per run
   previously_reloaded_packages: Mutex<HashSet<NpmPackageNv>>,
+  prefetched_packages: Mutex<HashMap<NpmPackageNv, PrefetchFuture>>,
+}
+
+impl std::fmt::Debug for NpmCache {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("NpmCache")
+      .field("readonly", &self.readonly)
+      .field("cache_setting", &self.cache_setting)
+      .field("http_client", &self.http_client)
+      .field("progress_bar", &self.progress_bar)
+      .field("fetch_queue", &self.fetch_queue)
+      .field(
+        "previously_reloaded_packages",
+        &self.previously_reloaded_packages,
+      )
+      .field("prefetched_packages", &"<omitted>")
+      .finish()
+  }
 }
 
 impl NpmCache {
@@ -299,7 +327,9 @@ impl NpmCache {
       cache_setting,
       http_client,
       progress_bar,
+      fetch_queue: Default::default(),
       previously_reloaded_packages: Default::default(),
+      prefetched_packages: Default::default(),
     }
   }
 
@@ -315,6 +345,11 @@ impl NpmCache {
     self.readonly.root_dir_url()
   }
 
+  /// Queue shared by all requests to the npm registry.
+  pub fn fetch_queue(&self) -> &NpmFetchQueue {
+    &self.fetch_queue
+  }
+
   /// Checks if the cache should be used for the provided name and version.
   /// NOTE: Subsequent calls for the same package will always return `true`
   /// to ensure a package is only downloaded once per run of the CLI. This
@@ -337,12 +372,66 @@ impl NpmCache {
     dist: &NpmPackageVersionDistInfo,
     registry_url: &Url,
   ) -> Result<(), AnyError> {
+    let maybe_prefetch = self.prefetched_packages.lock().get(package).cloned();
+    if let Some(prefetch) = maybe_prefetch {
+      if prefetch.await {
+        return Ok(());
+      }
+    }
     self
       .ensure_package_inner(package, dist, registry_url)
       .await
       .with_context(|| format!("Failed caching npm package '{package}'."))
   }
 
+  /// Starts caching a package in the background as soon as its version is
+  /// pinned, rather than once the whole resolution is done.
+  ///
+  /// Any errors are left for `ensure_package` to surface, which caches the
+  /// package again if the prefetch failed.
+  pub fn prefetch_package(
+    self: &Arc<Self>,
+    package: &NpmPackageNv,
+    dist: &NpmPackageVersionDistInfo,
+    registry_url: &Url,
+  ) {
+    if should_sync_download() || self.cache_setting == CacheSetting::Only {
+      // keep the download order deterministic for the tests, and don't
+      // fill the cache of the lsp while the user is typing
+      return;
+    }
+    let mut prefetched_packages = self.prefetched_packages.lock();
+    if prefetched_packages.contains_key(package) {
+      return;
+    }
+    let cache = self.clone();
+    let package = package.clone();
+    let dist = dist.clone();
+    let registry_url = registry_url.clone();
+    let handle = spawn({
+      let package = package.clone();
+      async move {
+        match cache
+          .ensure_package_inner(&package, &dist, &registry_url)
+          .await
+        {
+          Ok(()) => true,
+          Err(err) => {
+            log::debug!("Failed prefetching npm package '{package}': {err:#}");
+            false
+          }
+        }
+      }
+    });
+    prefetched_packages.insert(
+      package,
+      handle
+        .map(|result| result.unwrap_or(false))
+        .boxed()
+        .shared(),
+    );
+  }
+
   async fn ensure_package_inner(
     &self,
     package: &NpmPackageNv,
@@ -370,11 +459,14 @@ impl NpmCache {
       );
     }
 
-    let guard = self.progress_bar.update(&dist.tarball);
-    let maybe_bytes = self
-      .http_client
-      .download_with_progress(&dist.tarball, &guard)
-      .await?;
+    let maybe_bytes = {
+      let _permit = self.fetch_queue.acquire(NpmFetchPriority::Tarball).await;
+      let guard = self.progress_bar.update(&dist.tarball);
+      self
+        .http_client
+        .download_with_progress(&dist.tarball, &guard)
+        .await?
+    };
     match maybe_bytes {
       Some(bytes) => {
         verify_and_extract_tarball(package, &bytes, dist, &package_folder)
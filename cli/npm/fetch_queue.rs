@@ -0,0 +1,214 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use deno_core::futures::channel::oneshot;
+use deno_core::parking_lot::Mutex;
+
+/// The maximum number of requests made to the npm registry at once.
+const MAX_CONCURRENT_FETCHES: usize = 16;
+
+/// The priority of a request to the npm registry.
+///
+/// Package information gates the rest of the resolution, so it's always
+/// requested before any tarballs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NpmFetchPriority {
+  Tarball,
+  PackageInfo,
+}
+
+struct Waiter {
+  priority: NpmFetchPriority,
+  /// Keeps waiters with the same priority in first-in first-out order.
+  sequence: u64,
+  sender: oneshot::Sender<()>,
+}
+
+impl PartialEq for Waiter {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Waiter {}
+
+impl PartialOrd for Waiter {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Waiter {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self
+      .priority
+      .cmp(&other.priority)
+      .then_with(|| other.sequence.cmp(&self.sequence))
+  }
+}
+
+#[derive(Default)]
+struct NpmFetchQueueState {
+  running: usize,
+  next_sequence: u64,
+  waiters: BinaryHeap<Waiter>,
+}
+
+/// Bounds the number of concurrent requests to the npm registry, handing
+/// out free slots by priority.
+pub struct NpmFetchQueue {
+  max_concurrent: usize,
+  state: Mutex<NpmFetchQueueState>,
+}
+
+impl std::fmt::Debug for NpmFetchQueue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("NpmFetchQueue")
+      .field("max_concurrent", &self.max_concurrent)
+      .finish()
+  }
+}
+
+impl Default for NpmFetchQueue {
+  fn default() -> Self {
+    Self::new(MAX_CONCURRENT_FETCHES)
+  }
+}
+
+impl NpmFetchQueue {
+  pub fn new(max_concurrent: usize) -> Self {
+    Self {
+      max_concurrent: max_concurrent.max(1),
+      state: Default::default(),
+    }
+  }
+
+  /// Waits for a free slot. The slot is held until the permit is dropped.
+  pub async fn acquire(
+    &self,
+    priority: NpmFetchPriority,
+  ) -> NpmFetchPermit<'_> {
+    let receiver = {
+      let mut state = self.state.lock();
+      if state.running < self.max_concurrent {
+        state.running += 1;
+        return NpmFetchPermit(self);
+      }
+      let (sender, receiver) = oneshot::channel();
+      let sequence = state.next_sequence;
+      state.next_sequence += 1;
+      state.waiters.push(Waiter {
+        priority,
+        sequence,
+        sender,
+      });
+      receiver
+    };
+
+    let mut pending = PendingPermit {
+      queue: self,
+      receiver: Some(receiver),
+    };
+    // the sender is only dropped without sending when the queue is dropped,
+    // which can't happen while it's borrowed here
+    let _ = pending.receiver.as_mut().unwrap().await;
+    pending.receiver = None;
+    NpmFetchPermit(self)
+  }
+
+  fn release(&self) {
+    let mut state = self.state.lock();
+    // hand the slot over to the next waiter that's still around
+    while let Some(waiter) = state.waiters.pop() {
+      if waiter.sender.send(()).is_ok() {
+        return;
+      }
+    }
+    state.running -= 1;
+  }
+}
+
+/// A slot in the [`NpmFetchQueue`] that's released on drop.
+pub struct NpmFetchPermit<'a>(&'a NpmFetchQueue);
+
+impl Drop for NpmFetchPermit<'_> {
+  fn drop(&mut self) {
+    self.0.release();
+  }
+}
+
+/// Gives back a slot that was handed over to a waiter whose future was
+/// dropped before it could make use of it.
+struct PendingPermit<'a> {
+  queue: &'a NpmFetchQueue,
+  receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for PendingPermit<'_> {
+  fn drop(&mut self) {
+    if let Some(mut receiver) = self.receiver.take() {
+      if let Ok(Some(())) = receiver.try_recv() {
+        self.queue.release();
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::sync::Arc;
+
+  use deno_core::futures::FutureExt;
+
+  use super::*;
+
+  #[tokio::test]
+  async fn hands_out_slots_by_priority() {
+    let queue = Arc::new(NpmFetchQueue::new(1));
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let permit = queue.acquire(NpmFetchPriority::Tarball).await;
+
+    let mut handles = Vec::new();
+    for (name, priority) in [
+      ("tarball-1", NpmFetchPriority::Tarball),
+      ("info-1", NpmFetchPriority::PackageInfo),
+      ("tarball-2", NpmFetchPriority::Tarball),
+      ("info-2", NpmFetchPriority::PackageInfo),
+    ] {
+      let queue = queue.clone();
+      let order = order.clone();
+      handles.push(deno_core::task::spawn(async move {
+        let _permit = queue.acquire(priority).await;
+        order.lock().push(name);
+      }));
+      // let the task register itself as a waiter
+      tokio::task::yield_now().await;
+    }
+
+    drop(permit);
+    for handle in handles {
+      handle.await.unwrap();
+    }
+    assert_eq!(
+      *order.lock(),
+      vec!["info-1", "info-2", "tarball-1", "tarball-2"]
+    );
+  }
+
+  #[tokio::test]
+  async fn dropped_waiters_do_not_leak_slots() {
+    let queue = NpmFetchQueue::new(1);
+    let permit = queue.acquire(NpmFetchPriority::Tarball).await;
+    let mut waiter = queue.acquire(NpmFetchPriority::PackageInfo).boxed();
+    assert!((&mut waiter).now_or_never().is_none());
+    drop(permit);
+    // the slot was handed to the waiter, which is dropped before using it
+    drop(waiter);
+    assert!(queue
+      .acquire(NpmFetchPriority::Tarball)
+      .now_or_never()
+      .is_some());
+  }
+}
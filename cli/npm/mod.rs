@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 mod cache;
+mod fetch_queue;
 mod installer;
 mod registry;
 mod resolution;
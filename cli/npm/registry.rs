@@ -22,6 +22,7 @@ use deno_core::TaskQueue;
 use deno_npm::registry::NpmPackageInfo;
 use deno_npm::registry::NpmRegistryApi;
 use deno_npm::registry::NpmRegistryPackageInfoLoadError;
+use deno_semver::npm::NpmPackageNv;
 use once_cell::sync::Lazy;
 
 use crate::args::CacheSetting;
@@ -33,6 +34,7 @@ use crate::util::sync::AtomicFlag;
 
 use super::cache::should_sync_download;
 use super::cache::NpmCache;
+use super::fetch_queue::NpmFetchPriority;
 
 static NPM_REGISTRY_DEFAULT_URL: Lazy<Url> = Lazy::new(|| {
   let env_var_name = "NPM_CONFIG_REGISTRY";
@@ -99,6 +101,16 @@ impl CliNpmRegistryApi {
     &self.inner().base_url
   }
 
+  /// Starts caching the tarball of a package whose version was pinned.
+  pub fn prefetch_package(&self, nv: &NpmPackageNv, info: &NpmPackageInfo) {
+    let inner = self.inner();
+    if let Some(version_info) = info.versions.get(&nv.version.to_string()) {
+      inner
+        .cache
+        .prefetch_package(nv, &version_info.dist, &inner.base_url);
+    }
+  }
+
   /// Marks that new requests for package information should retrieve it
   /// from the npm registry
   ///
@@ -346,6 +358,11 @@ impl CliNpmRegistryApiInner {
     }
 
     let package_url = self.get_package_url(name);
+    let _permit = self
+      .cache
+      .fetch_queue()
+      .acquire(NpmFetchPriority::PackageInfo)
+      .await;
     let guard = self.progress_bar.update(package_url.as_str());
 
     let maybe_bytes = self
@@ -233,8 +233,12 @@ impl NpmResolution {
     package_info: &NpmPackageInfo,
   ) -> Result<NpmPackageNv, NpmPackageVersionResolutionError> {
     debug_assert_eq!(pkg_req.name, package_info.name);
-    let mut snapshot = self.snapshot.write();
-    let nv = snapshot.resolve_package_req_as_pending(pkg_req, package_info)?;
+    let nv = self
+      .snapshot
+      .write()
+      .resolve_package_req_as_pending(pkg_req, package_info)?;
+    // the tarball can be downloaded while the rest of the graph is loaded
+    self.api.prefetch_package(&nv, package_info);
     Ok(nv)
   }
 
@@ -102,6 +102,8 @@ struct TestInfo<'s> {
   #[serde(default)]
   only: bool,
   location: TestLocation,
+  #[serde(default)]
+  suites: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -128,6 +130,7 @@ fn op_register_test<'a>(
     only: info.only,
     origin: origin.clone(),
     location: info.location,
+    suites: info.suites,
   };
   let function: v8::Local<v8::Function> = info.function.v8_value.try_into()?;
   let function = v8::Global::new(scope, function);
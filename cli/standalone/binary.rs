@@ -8,18 +8,18 @@ use std::io::SeekFrom;
 use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
-use deno_core::futures::io::AllowStdIo;
-use deno_core::futures::AsyncReadExt;
-use deno_core::futures::AsyncSeekExt;
+use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_npm::registry::PackageDepNpmSchemeValueParseError;
 use deno_npm::resolution::SerializedNpmResolutionSnapshot;
 use deno_npm::NpmSystemInfo;
+use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::permissions::PermissionsOptions;
 use deno_semver::npm::NpmPackageReq;
 use deno_semver::npm::NpmVersionReqSpecifierParseError;
@@ -32,9 +32,11 @@ use crate::args::package_json::PackageJsonDeps;
 use crate::args::CaData;
 use crate::args::CliOptions;
 use crate::args::CompileFlags;
+use crate::args::Lockfile;
 use crate::args::PackageJsonDepsProvider;
 use crate::cache::DenoDir;
 use crate::file_fetcher::FileFetcher;
+use crate::graph_util::asset_lock_or_exit;
 use crate::http_util::HttpClient;
 use crate::npm::CliNpmRegistryApi;
 use crate::npm::CliNpmResolver;
@@ -43,6 +45,8 @@ use crate::npm::NpmResolution;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
+use super::embedded_modules::EmbeddedModules;
+use super::embedded_modules::EmbeddedModulesIndex;
 use super::virtual_fs::FileBackedVfs;
 use super::virtual_fs::VfsBuilder;
 use super::virtual_fs::VfsRoot;
@@ -140,8 +144,11 @@ pub struct Metadata {
   pub entrypoint: ModuleSpecifier,
   /// Whether this uses a node_modules directory (true) or the global cache (false).
   pub node_modules_dir: bool,
+  pub node_conditions: Vec<String>,
   pub npm_snapshot: Option<SerializedNpmResolutionSnapshot>,
   pub package_json_deps: Option<SerializablePackageJsonDeps>,
+  /// Whether the V8 code cache of the modules is kept between runs.
+  pub code_cache: bool,
 }
 
 pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
@@ -175,16 +182,18 @@ fn write_binary_bytes(
   writer: &mut impl Write,
   original_bin: Vec<u8>,
   metadata: &Metadata,
-  eszip: eszip::EszipV2,
+  module_index: &EmbeddedModulesIndex,
+  module_sources: &[u8],
   npm_vfs: Option<&VirtualDirectory>,
   npm_files: &Vec<Vec<u8>>,
 ) -> Result<(), AnyError> {
   let metadata = serde_json::to_string(metadata)?.as_bytes().to_vec();
+  let module_index = serde_json::to_string(module_index)?.as_bytes().to_vec();
   let npm_vfs = serde_json::to_string(&npm_vfs)?.as_bytes().to_vec();
-  let eszip_archive = eszip.into_bytes();
 
   writer.write_all(&original_bin)?;
-  writer.write_all(&eszip_archive)?;
+  writer.write_all(module_sources)?;
+  writer.write_all(&module_index)?;
   writer.write_all(&metadata)?;
   writer.write_all(&npm_vfs)?;
   for file in npm_files {
@@ -194,12 +203,14 @@ fn write_binary_bytes(
   // write the trailer, which includes the positions
   // of the data blocks in the file
   writer.write_all(&{
-    let eszip_pos = original_bin.len() as u64;
-    let metadata_pos = eszip_pos + (eszip_archive.len() as u64);
+    let module_sources_pos = original_bin.len() as u64;
+    let module_index_pos = module_sources_pos + (module_sources.len() as u64);
+    let metadata_pos = module_index_pos + (module_index.len() as u64);
     let npm_vfs_pos = metadata_pos + (metadata.len() as u64);
     let npm_files_pos = npm_vfs_pos + (npm_vfs.len() as u64);
     Trailer {
-      eszip_pos,
+      module_sources_pos,
+      module_index_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
@@ -235,55 +246,76 @@ pub fn is_standalone_binary(exe_path: &Path) -> bool {
 /// binary by skipping over the trailer width at the end of the file,
 /// then checking for the magic trailer string `d3n0l4nd`. If found,
 /// the bundle is executed. If not, this function exits with `Ok(None)`.
-pub async fn extract_standalone(
+pub fn extract_standalone(
   exe_path: &Path,
   cli_args: Vec<String>,
-) -> Result<Option<(Metadata, eszip::EszipV2)>, AnyError> {
-  let file = std::fs::File::open(exe_path)?;
+) -> Result<Option<(Metadata, EmbeddedModules)>, AnyError> {
+  let Some((mut metadata, modules)) = read_standalone_binary(exe_path)? else {
+    return Ok(None);
+  };
+  metadata.argv.append(&mut cli_args[1..].to_vec());
 
-  let mut bufreader =
-    deno_core::futures::io::BufReader::new(AllowStdIo::new(file));
+  Ok(Some((metadata, modules)))
+}
 
-  let _trailer_pos = bufreader
-    .seek(SeekFrom::End(-(TRAILER_SIZE as i64)))
-    .await?;
+/// Reads the metadata and the index of the modules embedded in the binary at
+/// `exe_path`. The sources of the modules are only read once they are asked
+/// for. Returns `Ok(None)` if it is not a standalone binary.
+pub fn read_standalone_binary(
+  exe_path: &Path,
+) -> Result<Option<(Metadata, EmbeddedModules)>, AnyError> {
+  let mut file = std::fs::File::open(exe_path)?;
+  file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
   let mut trailer = [0; TRAILER_SIZE];
-  bufreader.read_exact(&mut trailer).await?;
-  let trailer = match Trailer::parse(&trailer)? {
-    None => return Ok(None),
-    Some(trailer) => trailer,
+  file.read_exact(&mut trailer)?;
+  let Some(trailer) = Trailer::parse(&trailer)? else {
+    return Ok(None);
   };
 
-  bufreader.seek(SeekFrom::Start(trailer.eszip_pos)).await?;
-
-  let (eszip, loader) = eszip::EszipV2::parse(bufreader)
-    .await
-    .context("Failed to parse eszip header")?;
+  file.seek(SeekFrom::Start(trailer.module_index_pos))?;
+  let mut module_index = vec![0; trailer.module_index_len() as usize];
+  file.read_exact(&mut module_index)?;
+  let module_index: EmbeddedModulesIndex =
+    serde_json::from_slice(&module_index)
+      .context("Failed to parse the module index of the standalone binary")?;
 
-  let mut bufreader = loader.await.context("Failed to parse eszip archive")?;
-
-  bufreader
-    .seek(SeekFrom::Start(trailer.metadata_pos))
-    .await?;
-
-  let mut metadata = String::new();
-
-  bufreader
-    .take(trailer.metadata_len())
-    .read_to_string(&mut metadata)
-    .await
+  let mut metadata = vec![0; trailer.metadata_len() as usize];
+  file
+    .read_exact(&mut metadata)
     .context("Failed to read metadata from the current executable")?;
+  let metadata: Metadata = serde_json::from_slice(&metadata)
+    .context("Failed to parse metadata of the standalone binary")?;
 
-  let mut metadata: Metadata = serde_json::from_str(&metadata).unwrap();
-  metadata.argv.append(&mut cli_args[1..].to_vec());
+  let modules =
+    EmbeddedModules::new(module_index, file, trailer.module_sources_pos);
+  Ok(Some((metadata, modules)))
+}
 
-  Ok(Some((metadata, eszip)))
+/// Reads the virtual file system of the npm packages embedded in the binary
+/// at `exe_path`, if any.
+pub fn read_npm_vfs(
+  exe_path: &Path,
+) -> Result<Option<VirtualDirectory>, AnyError> {
+  let mut file = std::fs::File::open(exe_path)?;
+  file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
+  let mut trailer = [0; TRAILER_SIZE];
+  file.read_exact(&mut trailer)?;
+  let Some(trailer) = Trailer::parse(&trailer)? else {
+    return Ok(None);
+  };
+  file.seek(SeekFrom::Start(trailer.npm_vfs_pos))?;
+  let mut vfs_data = vec![0; trailer.npm_vfs_len() as usize];
+  file.read_exact(&mut vfs_data)?;
+  let dir: Option<VirtualDirectory> = serde_json::from_slice(&vfs_data)
+    .context("Failed to parse npm virtual file system")?;
+  Ok(dir)
 }
 
 const TRAILER_SIZE: usize = std::mem::size_of::<Trailer>() + 8; // 8 bytes for the magic trailer string
 
 struct Trailer {
-  eszip_pos: u64,
+  module_sources_pos: u64,
+  module_index_pos: u64,
   metadata_pos: u64,
   npm_vfs_pos: u64,
   npm_files_pos: u64,
@@ -296,21 +328,28 @@ impl Trailer {
       return Ok(None);
     }
 
-    let (eszip_archive_pos, rest) = rest.split_at(8);
+    let (module_sources_pos, rest) = rest.split_at(8);
+    let (module_index_pos, rest) = rest.split_at(8);
     let (metadata_pos, rest) = rest.split_at(8);
     let (npm_vfs_pos, npm_files_pos) = rest.split_at(8);
-    let eszip_archive_pos = u64_from_bytes(eszip_archive_pos)?;
+    let module_sources_pos = u64_from_bytes(module_sources_pos)?;
+    let module_index_pos = u64_from_bytes(module_index_pos)?;
     let metadata_pos = u64_from_bytes(metadata_pos)?;
     let npm_vfs_pos = u64_from_bytes(npm_vfs_pos)?;
     let npm_files_pos = u64_from_bytes(npm_files_pos)?;
     Ok(Some(Trailer {
-      eszip_pos: eszip_archive_pos,
+      module_sources_pos,
+      module_index_pos,
       metadata_pos,
       npm_vfs_pos,
       npm_files_pos,
     }))
   }
 
+  pub fn module_index_len(&self) -> u64 {
+    self.metadata_pos - self.module_index_pos
+  }
+
   pub fn metadata_len(&self) -> u64 {
     self.npm_vfs_pos - self.metadata_pos
   }
@@ -321,7 +360,12 @@ impl Trailer {
 
   pub fn as_bytes(&self) -> Vec<u8> {
     let mut trailer = MAGIC_TRAILER.to_vec();
-    trailer.write_all(&self.eszip_pos.to_be_bytes()).unwrap();
+    trailer
+      .write_all(&self.module_sources_pos.to_be_bytes())
+      .unwrap();
+    trailer
+      .write_all(&self.module_index_pos.to_be_bytes())
+      .unwrap();
     trailer.write_all(&self.metadata_pos.to_be_bytes()).unwrap();
     trailer.write_all(&self.npm_vfs_pos.to_be_bytes()).unwrap();
     trailer
@@ -348,6 +392,7 @@ pub struct DenoCompileBinaryWriter<'a> {
   npm_resolver: &'a CliNpmResolver,
   npm_system_info: NpmSystemInfo,
   package_json_deps_provider: &'a PackageJsonDepsProvider,
+  maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
 }
 
 impl<'a> DenoCompileBinaryWriter<'a> {
@@ -362,6 +407,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     npm_resolver: &'a CliNpmResolver,
     npm_system_info: NpmSystemInfo,
     package_json_deps_provider: &'a PackageJsonDepsProvider,
+    maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
   ) -> Self {
     Self {
       file_fetcher,
@@ -373,6 +419,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       npm_system_info,
       npm_resolution,
       package_json_deps_provider,
+      maybe_lockfile,
     }
   }
 
@@ -380,6 +427,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     &self,
     writer: &mut impl Write,
     eszip: eszip::EszipV2,
+    assets: Vec<ModuleSpecifier>,
     module_specifier: &ModuleSpecifier,
     compile_flags: &CompileFlags,
     cli_options: &CliOptions,
@@ -393,6 +441,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         writer,
         original_binary,
         eszip,
+        assets,
         module_specifier,
         cli_options,
         compile_flags,
@@ -476,6 +525,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     writer: &mut impl Write,
     original_bin: Vec<u8>,
     eszip: eszip::EszipV2,
+    assets: Vec<ModuleSpecifier>,
     entrypoint: &ModuleSpecifier,
     cli_options: &CliOptions,
     compile_flags: &CompileFlags,
@@ -517,18 +567,42 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       entrypoint: entrypoint.clone(),
       maybe_import_map,
       node_modules_dir: self.npm_resolver.node_modules_path().is_some(),
+      node_conditions: cli_options.node_conditions(),
       npm_snapshot,
       package_json_deps: self
         .package_json_deps_provider
         .deps()
         .map(|deps| SerializablePackageJsonDeps::from_deps(deps.clone())),
+      code_cache: compile_flags.code_cache,
     };
+    let (mut module_index, mut module_sources) =
+      EmbeddedModulesIndex::from_eszip(eszip).await?;
+    for specifier in assets {
+      // data urls are decoded at runtime instead
+      if specifier.scheme() == "data" {
+        continue;
+      }
+      let (found_specifier, bytes) = self
+        .file_fetcher
+        .fetch_bytes(&specifier, PermissionsContainer::allow_all())
+        .await?;
+      if let Some(lockfile) = &self.maybe_lockfile {
+        asset_lock_or_exit(&mut lockfile.lock(), &found_specifier, &bytes);
+      }
+      module_index.add_asset(
+        &mut module_sources,
+        specifier.to_string(),
+        found_specifier.to_string(),
+        &bytes,
+      );
+    }
 
     write_binary_bytes(
       writer,
       original_bin,
       &metadata,
-      eszip,
+      &module_index,
+      &module_sources,
       npm_vfs.as_ref(),
       &npm_files,
     )
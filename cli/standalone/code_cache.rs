@@ -0,0 +1,246 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The V8 code cache of the modules of an executable that was compiled with
+//! `--code-cache`. It is created on the first run, and kept in a directory of
+//! the `DENO_DIR` so that V8 doesn't compile the modules again on the next
+//! runs.
+//!
+//! Whoever can write to the directory can make the executable run other code,
+//! so it is only used if it belongs to the current user and nobody else has
+//! access to it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::parking_lot::Mutex;
+use deno_core::ModuleCodeCache;
+use deno_core::ModuleSpecifier;
+use once_cell::sync::OnceCell;
+
+use crate::util::checksum;
+use crate::util::fs::atomic_write_file;
+
+/// The length of the checksum an entry starts with.
+const CHECKSUM_LEN: usize = 64;
+
+pub struct StandaloneCodeCache {
+  dir: PathBuf,
+  /// The V8 flags the executable runs with, as V8 rejects a code cache that
+  /// was created with other flags.
+  v8_flags: String,
+  /// Whether the directory is private to the current user, which is checked
+  /// once before it is first read.
+  is_dir_private: OnceCell<bool>,
+  /// The keys of the modules whose code cache is being created.
+  pending: Mutex<HashMap<ModuleSpecifier, String>>,
+}
+
+impl StandaloneCodeCache {
+  pub fn new(dir: PathBuf, v8_flags: &[String]) -> Self {
+    Self {
+      dir,
+      v8_flags: v8_flags.join(" "),
+      is_dir_private: Default::default(),
+      pending: Default::default(),
+    }
+  }
+
+  /// The code cache of a module. V8 only checks the length of the source
+  /// before using a code cache, so it is keyed by the source itself.
+  pub fn get(
+    &self,
+    specifier: &ModuleSpecifier,
+    source: &[u8],
+  ) -> ModuleCodeCache {
+    let key = checksum::gen(&[
+      crate::version::deno().as_bytes(),
+      self.v8_flags.as_bytes(),
+      specifier.as_str().as_bytes(),
+      source,
+    ]);
+    match self.read(&key) {
+      Some(code_cache) => ModuleCodeCache::Existing(code_cache),
+      // an entry that is missing or doesn't match its checksum is written
+      // again once the module is compiled
+      None => {
+        self.pending.lock().insert(specifier.clone(), key);
+        ModuleCodeCache::Missing
+      }
+    }
+  }
+
+  pub fn set(&self, specifier: &ModuleSpecifier, code_cache: &[u8]) {
+    let key = match self.pending.lock().remove(specifier) {
+      Some(key) => key,
+      None => return,
+    };
+    if let Err(err) = self.write(&key, code_cache) {
+      log::debug!("Failed to write the code cache of {}: {}", specifier, err);
+    }
+  }
+
+  fn read(&self, key: &str) -> Option<Vec<u8>> {
+    let is_dir_private = *self
+      .is_dir_private
+      .get_or_init(|| is_private_dir(&self.dir));
+    if !is_dir_private {
+      return None;
+    }
+    let mut entry = std::fs::read(self.dir.join(key)).ok()?;
+    if entry.len() < CHECKSUM_LEN {
+      return None;
+    }
+    let code_cache = entry.split_off(CHECKSUM_LEN);
+    if checksum::gen(&[&code_cache]).as_bytes() != entry.as_slice() {
+      log::debug!("Ignoring the corrupted code cache entry {}", key);
+      return None;
+    }
+    Some(code_cache)
+  }
+
+  fn write(&self, key: &str, code_cache: &[u8]) -> std::io::Result<()> {
+    create_private_dir(&self.dir)?;
+    if !is_private_dir(&self.dir) {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::PermissionDenied,
+        format!("{} is accessible by other users", self.dir.display()),
+      ));
+    }
+    let mut entry = checksum::gen(&[code_cache]).into_bytes();
+    entry.extend_from_slice(code_cache);
+    atomic_write_file(&self.dir.join(key), entry, 0o600)
+  }
+}
+
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+  let mut builder = std::fs::DirBuilder::new();
+  builder.recursive(true);
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::DirBuilderExt;
+    builder.mode(0o700);
+  }
+  builder.create(dir)
+}
+
+/// Whether the directory belongs to the current user, and only they have
+/// access to it.
+#[cfg(unix)]
+fn is_private_dir(dir: &Path) -> bool {
+  use std::os::unix::fs::MetadataExt;
+
+  match std::fs::symlink_metadata(dir) {
+    Ok(metadata) => {
+      metadata.is_dir()
+        && metadata.uid() == nix::unistd::geteuid().as_raw()
+        && metadata.mode() & 0o077 == 0
+    }
+    Err(_) => false,
+  }
+}
+
+/// The `DENO_DIR` is in the profile of the user on Windows, which other
+/// users don't have access to.
+#[cfg(not(unix))]
+fn is_private_dir(dir: &Path) -> bool {
+  std::fs::symlink_metadata(dir)
+    .map(|metadata| metadata.is_dir())
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn code_cache() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path().join("code_cache");
+    let cache = StandaloneCodeCache::new(dir.clone(), &[]);
+    let specifier = ModuleSpecifier::parse("file:///main.js").unwrap();
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Missing
+    ));
+    cache.set(&specifier, b"code cache");
+    let cache = StandaloneCodeCache::new(dir.clone(), &[]);
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Existing(code_cache) if code_cache == b"code cache"
+    ));
+    // a different source doesn't get the code cache of another one
+    assert!(matches!(
+      cache.get(&specifier, b"export { a };"),
+      ModuleCodeCache::Missing
+    ));
+    // only the code caches that were asked for are written
+    let other = ModuleSpecifier::parse("file:///other.js").unwrap();
+    cache.set(&other, b"code cache");
+    assert!(matches!(
+      cache.get(&other, b"export {};"),
+      ModuleCodeCache::Missing
+    ));
+    // other V8 flags don't get the code cache either
+    let cache =
+      StandaloneCodeCache::new(dir.clone(), &["--no-lazy".to_string()]);
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Missing
+    ));
+  }
+
+  #[test]
+  fn corrupted_entry() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path().join("code_cache");
+    let cache = StandaloneCodeCache::new(dir.clone(), &[]);
+    let specifier = ModuleSpecifier::parse("file:///main.js").unwrap();
+    cache.get(&specifier, b"export {};");
+    cache.set(&specifier, b"code cache");
+    for entry in std::fs::read_dir(&dir).unwrap() {
+      let path = entry.unwrap().path();
+      let mut bytes = std::fs::read(&path).unwrap();
+      *bytes.last_mut().unwrap() ^= 1;
+      std::fs::write(&path, bytes).unwrap();
+    }
+
+    // the entry is written again once the module is compiled
+    let cache = StandaloneCodeCache::new(dir, &[]);
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Missing
+    ));
+    cache.set(&specifier, b"code cache");
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Existing(code_cache) if code_cache == b"code cache"
+    ));
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn shared_dir() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let dir = temp_dir.path().join("code_cache");
+    let cache = StandaloneCodeCache::new(dir.clone(), &[]);
+    let specifier = ModuleSpecifier::parse("file:///main.js").unwrap();
+    cache.get(&specifier, b"export {};");
+    cache.set(&specifier, b"code cache");
+    assert_eq!(
+      std::fs::metadata(&dir).unwrap().permissions().mode() & 0o777,
+      0o700
+    );
+
+    // the code cache isn't used once other users have access to it
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777))
+      .unwrap();
+    let cache = StandaloneCodeCache::new(dir, &[]);
+    assert!(matches!(
+      cache.get(&specifier, b"export {};"),
+      ModuleCodeCache::Missing
+    ));
+  }
+}
@@ -0,0 +1,328 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The modules of an executable produced by `deno compile`. Their sources are
+//! written one after the other into the executable, along with an index of
+//! where each one is, so that a module is only read once it is loaded instead
+//! of reading all of them on startup.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How many redirects are followed to find the module of a specifier.
+const MAX_REDIRECTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EmbeddedModuleKind {
+  JavaScript,
+  Json,
+  Jsonc,
+  /// The raw contents of a file imported as bytes or text.
+  Bytes,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EmbeddedModuleEntry {
+  kind: EmbeddedModuleKind,
+  /// The position of the source, relative to the start of the sources.
+  offset: u64,
+  len: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddedModulesIndex {
+  modules: BTreeMap<String, EmbeddedModuleEntry>,
+  /// Files imported as bytes or text. These are kept apart from the modules,
+  /// as the same specifier can also be imported as a module.
+  assets: BTreeMap<String, EmbeddedModuleEntry>,
+  redirects: BTreeMap<String, String>,
+}
+
+impl EmbeddedModulesIndex {
+  /// Builds the index of the modules of an eszip, and the sources that it
+  /// points to.
+  pub async fn from_eszip(
+    eszip: eszip::EszipV2,
+  ) -> Result<(Self, Vec<u8>), AnyError> {
+    let mut index = Self::default();
+    let mut sources = Vec::new();
+    for specifier in eszip.specifiers() {
+      let Some(module) = eszip.get_module(&specifier) else {
+        continue;
+      };
+      if module.specifier != specifier {
+        index.redirects.insert(specifier, module.specifier.clone());
+        continue;
+      }
+      let Some(source) = module.source().await else {
+        continue;
+      };
+      let kind = match module.kind {
+        eszip::ModuleKind::JavaScript => EmbeddedModuleKind::JavaScript,
+        eszip::ModuleKind::Json => EmbeddedModuleKind::Json,
+        eszip::ModuleKind::Jsonc => EmbeddedModuleKind::Jsonc,
+      };
+      index.modules.insert(
+        specifier,
+        EmbeddedModuleEntry {
+          kind,
+          offset: sources.len() as u64,
+          len: source.len() as u64,
+        },
+      );
+      sources.extend_from_slice(&source);
+    }
+    Ok((index, sources))
+  }
+
+  /// Adds a file imported as bytes or text, which isn't part of the eszip,
+  /// and appends its contents to `sources`.
+  pub fn add_asset(
+    &mut self,
+    sources: &mut Vec<u8>,
+    specifier: String,
+    found_specifier: String,
+    bytes: &[u8],
+  ) {
+    if specifier != found_specifier {
+      self.redirects.insert(specifier, found_specifier.clone());
+    }
+    self.assets.insert(
+      found_specifier,
+      EmbeddedModuleEntry {
+        kind: EmbeddedModuleKind::Bytes,
+        offset: sources.len() as u64,
+        len: bytes.len() as u64,
+      },
+    );
+    sources.extend_from_slice(bytes);
+  }
+
+  /// The specifier of the module that `specifier` is, or redirects to.
+  pub fn resolve(&self, specifier: &str) -> Option<&str> {
+    resolve_entry(&self.modules, &self.redirects, specifier)
+  }
+
+  /// The specifier of the asset that `specifier` is, or redirects to.
+  pub fn resolve_asset(&self, specifier: &str) -> Option<&str> {
+    resolve_entry(&self.assets, &self.redirects, specifier)
+  }
+
+  /// The specifiers of the modules, assets and redirects, sorted.
+  pub fn specifiers(&self) -> Vec<&str> {
+    let mut specifiers = self
+      .modules
+      .keys()
+      .chain(
+        self
+          .assets
+          .keys()
+          .filter(|s| !self.modules.contains_key(*s)),
+      )
+      .chain(self.redirects.keys())
+      .map(|specifier| specifier.as_str())
+      .collect::<Vec<_>>();
+    specifiers.sort();
+    specifiers
+  }
+
+  /// The size of the source of the module or asset that `specifier` is, or
+  /// redirects to.
+  pub fn source_len(&self, specifier: &str) -> Option<u64> {
+    if let Some(specifier) = self.resolve(specifier) {
+      return Some(self.modules[specifier].len);
+    }
+    let specifier = self.resolve_asset(specifier)?;
+    Some(self.assets[specifier].len)
+  }
+}
+
+fn resolve_entry<'a>(
+  entries: &'a BTreeMap<String, EmbeddedModuleEntry>,
+  redirects: &'a BTreeMap<String, String>,
+  specifier: &str,
+) -> Option<&'a str> {
+  let mut specifier = specifier;
+  for _ in 0..=MAX_REDIRECTS {
+    if let Some((specifier, _)) = entries.get_key_value(specifier) {
+      return Some(specifier.as_str());
+    }
+    specifier = redirects.get(specifier)?.as_str();
+  }
+  None
+}
+
+pub struct EmbeddedModule<'a> {
+  /// The specifier of the module, once redirects are followed.
+  pub specifier: &'a str,
+  pub kind: EmbeddedModuleKind,
+  pub source: Vec<u8>,
+}
+
+/// Reads the sources of the modules from the executable, on demand.
+pub struct EmbeddedModules {
+  index: EmbeddedModulesIndex,
+  file: Mutex<File>,
+  sources_pos: u64,
+}
+
+impl EmbeddedModules {
+  pub fn new(
+    index: EmbeddedModulesIndex,
+    file: File,
+    sources_pos: u64,
+  ) -> Self {
+    Self {
+      index,
+      file: Mutex::new(file),
+      sources_pos,
+    }
+  }
+
+  pub fn index(&self) -> &EmbeddedModulesIndex {
+    &self.index
+  }
+
+  /// Reads the module that `specifier` is, or redirects to. Returns
+  /// `Ok(None)` if there is no such module.
+  pub fn read(
+    &self,
+    specifier: &str,
+  ) -> Result<Option<EmbeddedModule>, AnyError> {
+    let Some(specifier) = self.index.resolve(specifier) else {
+      return Ok(None);
+    };
+    self
+      .read_entry(specifier, &self.index.modules[specifier])
+      .map(Some)
+  }
+
+  /// Reads the asset that `specifier` is, or redirects to. Returns `Ok(None)`
+  /// if there is no such asset.
+  pub fn read_asset(
+    &self,
+    specifier: &str,
+  ) -> Result<Option<EmbeddedModule>, AnyError> {
+    let Some(specifier) = self.index.resolve_asset(specifier) else {
+      return Ok(None);
+    };
+    self
+      .read_entry(specifier, &self.index.assets[specifier])
+      .map(Some)
+  }
+
+  fn read_entry<'a>(
+    &self,
+    specifier: &'a str,
+    entry: &EmbeddedModuleEntry,
+  ) -> Result<EmbeddedModule<'a>, AnyError> {
+    let mut source = vec![0; entry.len as usize];
+    {
+      let mut file = self.file.lock();
+      file.seek(SeekFrom::Start(self.sources_pos + entry.offset))?;
+      file
+        .read_exact(&mut source)
+        .with_context(|| format!("Failed to read module {specifier}"))?;
+    }
+    Ok(EmbeddedModule {
+      specifier,
+      kind: entry.kind,
+      source,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+
+  use super::*;
+
+  #[test]
+  fn read_embedded_modules() {
+    let mut index = EmbeddedModulesIndex::default();
+    let mut sources = b"ignored".to_vec();
+    let sources_pos = sources.len() as u64;
+    for (specifier, kind, source) in [
+      (
+        "file:///main.ts",
+        EmbeddedModuleKind::JavaScript,
+        "import './data.json';",
+      ),
+      ("file:///data.json", EmbeddedModuleKind::Json, "{}"),
+    ] {
+      index.modules.insert(
+        specifier.to_string(),
+        EmbeddedModuleEntry {
+          kind,
+          offset: sources.len() as u64 - sources_pos,
+          len: source.len() as u64,
+        },
+      );
+      sources.extend_from_slice(source.as_bytes());
+    }
+    index.redirects.insert(
+      "https://example.com/data".to_string(),
+      "file:///data.json".to_string(),
+    );
+    index.redirects.insert(
+      "https://example.com/loop".to_string(),
+      "https://example.com/loop".to_string(),
+    );
+    let mut asset_sources = sources.split_off(sources_pos as usize);
+    index.add_asset(
+      &mut asset_sources,
+      "https://example.com/logo".to_string(),
+      "https://example.com/logo.png".to_string(),
+      &[0x89, b'P', b'N', b'G'],
+    );
+    sources.extend_from_slice(&asset_sources);
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&sources).unwrap();
+    let modules = EmbeddedModules::new(index, file, sources_pos);
+
+    let module = modules.read("file:///main.ts").unwrap().unwrap();
+    assert_eq!(module.specifier, "file:///main.ts");
+    assert_eq!(module.kind, EmbeddedModuleKind::JavaScript);
+    assert_eq!(module.source, b"import './data.json';");
+    let module = modules.read("https://example.com/data").unwrap().unwrap();
+    assert_eq!(module.specifier, "file:///data.json");
+    assert_eq!(module.kind, EmbeddedModuleKind::Json);
+    assert_eq!(module.source, b"{}");
+    assert!(modules.read("file:///other.ts").unwrap().is_none());
+    assert!(modules.read("https://example.com/loop").unwrap().is_none());
+    let asset = modules
+      .read_asset("https://example.com/logo")
+      .unwrap()
+      .unwrap();
+    assert_eq!(asset.specifier, "https://example.com/logo.png");
+    assert_eq!(asset.kind, EmbeddedModuleKind::Bytes);
+    assert_eq!(asset.source, [0x89, b'P', b'N', b'G']);
+    assert!(modules.read("https://example.com/logo").unwrap().is_none());
+    assert!(modules.read_asset("file:///main.ts").unwrap().is_none());
+
+    let index = modules.index();
+    assert_eq!(
+      index.specifiers(),
+      vec![
+        "file:///data.json",
+        "file:///main.ts",
+        "https://example.com/data",
+        "https://example.com/logo",
+        "https://example.com/logo.png",
+        "https://example.com/loop",
+      ]
+    );
+    assert_eq!(index.source_len("https://example.com/data"), Some(2));
+    assert_eq!(index.source_len("https://example.com/logo"), Some(4));
+  }
+}
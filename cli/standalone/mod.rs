@@ -1,6 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-use crate::args::get_root_cert_store;
+use crate::args::get_ca_store;
 use crate::args::npm_pkg_req_ref_to_binary_command;
 use crate::args::CaData;
 use crate::args::CacheSetting;
@@ -20,6 +20,7 @@ use crate::npm::CliNpmResolver;
 use crate::npm::NpmCache;
 use crate::npm::NpmResolution;
 use crate::resolver::MappedSpecifierResolver;
+use crate::util::phase_timer::PhaseTimer;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::util::v8::construct_v8_flags;
@@ -27,11 +28,12 @@ use crate::worker::CliMainWorkerFactory;
 use crate::worker::CliMainWorkerOptions;
 use crate::worker::HasNodeSpecifierChecker;
 use crate::worker::ModuleLoaderFactory;
+use data_url::DataUrl;
 use deno_ast::MediaType;
 use deno_core::anyhow::Context;
 use deno_core::error::type_error;
+use deno_core::error::uri_error;
 use deno_core::error::AnyError;
-use deno_core::futures::FutureExt;
 use deno_core::v8_set_flags;
 use deno_core::ModuleLoader;
 use deno_core::ModuleSpecifier;
@@ -42,6 +44,7 @@ use deno_runtime::deno_fs;
 use deno_runtime::deno_node::analyze::NodeCodeTranslator;
 use deno_runtime::deno_node::NodeResolver;
 use deno_runtime::deno_tls::rustls::RootCertStore;
+use deno_runtime::deno_tls::CaStore;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_runtime::deno_web::BlobStore;
 use deno_runtime::permissions::Permissions;
@@ -54,19 +57,29 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 mod binary;
+mod code_cache;
+mod embedded_modules;
 mod file_system;
 mod virtual_fs;
 
 pub use binary::extract_standalone;
 pub use binary::is_standalone_binary;
+pub use binary::read_npm_vfs;
+pub use binary::read_standalone_binary;
 pub use binary::DenoCompileBinaryWriter;
+pub use embedded_modules::EmbeddedModules;
+pub use virtual_fs::VfsEntry;
+pub use virtual_fs::VirtualDirectory;
 
 use self::binary::load_npm_vfs;
 use self::binary::Metadata;
+use self::code_cache::StandaloneCodeCache;
+use self::embedded_modules::EmbeddedModuleKind;
 use self::file_system::DenoCompileFileSystem;
 
 struct SharedModuleLoaderState {
-  eszip: eszip::EszipV2,
+  modules: EmbeddedModules,
+  code_cache: Option<StandaloneCodeCache>,
   mapped_specifier_resolver: MappedSpecifierResolver,
   npm_module_loader: Arc<NpmModuleLoader>,
 }
@@ -86,10 +99,8 @@ impl ModuleLoader for EmbeddedModuleLoader {
     kind: ResolutionKind,
   ) -> Result<ModuleSpecifier, AnyError> {
     // Try to follow redirects when resolving.
-    let referrer = match self.shared.eszip.get_module(referrer) {
-      Some(eszip::Module { ref specifier, .. }) => {
-        ModuleSpecifier::parse(specifier)?
-      }
+    let referrer = match self.shared.modules.index().resolve(referrer) {
+      Some(specifier) => ModuleSpecifier::parse(specifier)?,
       None => {
         let cwd = std::env::current_dir().context("Unable to get CWD")?;
         deno_core::resolve_url_or_path(referrer, &cwd)?
@@ -171,45 +182,119 @@ impl ModuleLoader for EmbeddedModuleLoader {
       };
     }
 
+    if let Some((source, _)) = is_data_uri {
+      return Box::pin(deno_core::futures::future::ready(Ok(
+        deno_core::ModuleSource::new(
+          deno_core::ModuleType::JavaScript,
+          source.into(),
+          module_specifier,
+        ),
+      )));
+    }
+
+    let result = self.load_embedded(module_specifier);
+    Box::pin(deno_core::futures::future::ready(result))
+  }
+
+  fn load_bytes(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dynamic: bool,
+  ) -> Pin<Box<deno_core::ModuleSourceFuture>> {
+    let result = self.load_embedded_asset(module_specifier);
+    Box::pin(deno_core::futures::future::ready(result))
+  }
+
+  fn code_cache_ready(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    code_cache: &[u8],
+  ) {
+    if let Some(cache) = &self.shared.code_cache {
+      cache.set(module_specifier, code_cache);
+    }
+  }
+}
+
+impl EmbeddedModuleLoader {
+  /// Reads the module from the binary. This is done synchronously, as the
+  /// module is only a read away.
+  fn load_embedded(
+    &self,
+    module_specifier: &ModuleSpecifier,
+  ) -> Result<deno_core::ModuleSource, AnyError> {
     let module = self
       .shared
-      .eszip
-      .get_module(module_specifier.as_str())
+      .modules
+      .read(module_specifier.as_str())?
       .ok_or_else(|| {
         type_error(format!("Module not found: {}", module_specifier))
-      });
-    // TODO(mmastrac): This clone can probably be removed in the future if ModuleSpecifier is no longer a full-fledged URL
-    let module_specifier = module_specifier.clone();
-
-    async move {
-      if let Some((source, _)) = is_data_uri {
-        return Ok(deno_core::ModuleSource::new(
-          deno_core::ModuleType::JavaScript,
-          source.into(),
-          &module_specifier,
-        ));
+      })?;
+    let module_type = match module.kind {
+      EmbeddedModuleKind::JavaScript => ModuleType::JavaScript,
+      EmbeddedModuleKind::Json => ModuleType::Json,
+      EmbeddedModuleKind::Jsonc => {
+        return Err(type_error("jsonc modules not supported"))
+      }
+      EmbeddedModuleKind::Bytes => {
+        return Err(type_error(format!(
+          "{module_specifier} can only be imported as bytes or text"
+        )))
       }
+    };
+    let code_cache = match (&self.shared.code_cache, module_type) {
+      (Some(cache), ModuleType::JavaScript) => {
+        Some(cache.get(module_specifier, &module.source))
+      }
+      _ => None,
+    };
+    let code = String::from_utf8(module.source)
+      .map_err(|_| type_error("Module source is not utf-8"))?;
+
+    let found_specifier = ModuleSpecifier::parse(module.specifier)?;
+    let module_source = deno_core::ModuleSource::new_with_redirect(
+      module_type,
+      code.into(),
+      module_specifier,
+      &found_specifier,
+    );
+    Ok(match code_cache {
+      Some(code_cache) => module_source.with_code_cache(code_cache),
+      None => module_source,
+    })
+  }
 
-      let module = module?;
-      let code = module.source().await.unwrap_or_default();
-      let code = std::str::from_utf8(&code)
-        .map_err(|_| type_error("Module source is not utf-8"))?
-        .to_owned()
-        .into();
-
-      Ok(deno_core::ModuleSource::new(
-        match module.kind {
-          eszip::ModuleKind::JavaScript => ModuleType::JavaScript,
-          eszip::ModuleKind::Json => ModuleType::Json,
-          eszip::ModuleKind::Jsonc => {
-            return Err(type_error("jsonc modules not supported"))
-          }
-        },
-        code,
-        &module_specifier,
-      ))
+  /// Reads a file imported as bytes or text from the binary.
+  fn load_embedded_asset(
+    &self,
+    module_specifier: &ModuleSpecifier,
+  ) -> Result<deno_core::ModuleSource, AnyError> {
+    if module_specifier.scheme() == "data" {
+      let data_url = DataUrl::process(module_specifier.as_str())
+        .map_err(|e| uri_error(format!("{e:?}")))?;
+      let (bytes, _) = data_url
+        .decode_to_vec()
+        .map_err(|e| uri_error(format!("{e:?}")))?;
+      return Ok(deno_core::ModuleSource::new_bytes(
+        bytes,
+        module_specifier,
+        module_specifier,
+      ));
     }
-    .boxed_local()
+    let asset = self
+      .shared
+      .modules
+      .read_asset(module_specifier.as_str())?
+      .ok_or_else(|| {
+        type_error(format!("Module not found: {}", module_specifier))
+      })?;
+    let found_specifier = ModuleSpecifier::parse(asset.specifier)?;
+    Ok(deno_core::ModuleSource::new_bytes(
+      asset.source,
+      module_specifier,
+      &found_specifier,
+    ))
   }
 }
 
@@ -260,20 +345,27 @@ impl HasNodeSpecifierChecker for StandaloneHasNodeSpecifierChecker {
 struct StandaloneRootCertStoreProvider {
   ca_stores: Option<Vec<String>>,
   ca_data: Option<CaData>,
-  cell: once_cell::sync::OnceCell<RootCertStore>,
+  cell: once_cell::sync::OnceCell<CaStore>,
 }
 
 impl RootCertStoreProvider for StandaloneRootCertStoreProvider {
   fn get_or_try_init(&self) -> Result<&RootCertStore, AnyError> {
-    self.cell.get_or_try_init(|| {
-      get_root_cert_store(None, self.ca_stores.clone(), self.ca_data.clone())
-        .map_err(|err| err.into())
-    })
+    Ok(self.get_ca_store()?.unwrap().root_cert_store())
+  }
+
+  fn get_ca_store(&self) -> Result<Option<&CaStore>, AnyError> {
+    self
+      .cell
+      .get_or_try_init(|| {
+        get_ca_store(None, self.ca_stores.clone(), self.ca_data.clone())
+      })
+      .map(Some)
+      .map_err(|err| err.into())
   }
 }
 
 pub async fn run(
-  eszip: eszip::EszipV2,
+  modules: EmbeddedModules,
   metadata: Metadata,
 ) -> Result<(), AnyError> {
   let main_module = &metadata.entrypoint;
@@ -293,9 +385,9 @@ pub async fn run(
   ));
   // use a dummy npm registry url
   let npm_registry_url = ModuleSpecifier::parse("https://localhost/").unwrap();
-  let root_path = std::env::temp_dir()
-    .join(format!("deno-compile-{}", current_exe_name))
-    .join("node_modules");
+  let temp_dir_path =
+    std::env::temp_dir().join(format!("deno-compile-{}", current_exe_name));
+  let root_path = temp_dir_path.join("node_modules");
 
   let npm_cache = Arc::new(NpmCache::new(
     root_path.clone(),
@@ -359,8 +451,11 @@ pub async fn run(
     npm_fs_resolver,
     None,
   ));
-  let node_resolver =
-    Arc::new(NodeResolver::new(fs.clone(), npm_resolver.clone()));
+  let node_resolver = Arc::new(NodeResolver::new(
+    fs.clone(),
+    npm_resolver.clone(),
+    metadata.node_conditions.clone(),
+  ));
   let cjs_resolutions = Arc::new(CjsResolutionStore::default());
   let cache_db = Caches::new(deno_dir_provider.clone());
   let node_analysis_cache = NodeAnalysisCache::new(cache_db.node_analysis_db());
@@ -379,9 +474,22 @@ pub async fn run(
   let maybe_import_map = metadata.maybe_import_map.map(|(base, source)| {
     Arc::new(parse_from_json(&base, &source).unwrap().import_map)
   });
+  let code_cache_dir = if metadata.code_cache {
+    match deno_dir_provider.get_or_create() {
+      Ok(deno_dir) => Some(deno_dir.compile_code_cache_folder_path()),
+      Err(err) => {
+        log::debug!("Not using the code cache: {}", err);
+        None
+      }
+    }
+  } else {
+    None
+  };
   let module_loader_factory = StandaloneModuleLoaderFactory {
     shared: Arc::new(SharedModuleLoaderState {
-      eszip,
+      modules,
+      code_cache: code_cache_dir
+        .map(|dir| StandaloneCodeCache::new(dir, &metadata.v8_flags)),
       mapped_specifier_resolver: MappedSpecifierResolver::new(
         maybe_import_map.clone(),
         package_json_deps_provider.clone(),
@@ -425,8 +533,10 @@ pub async fn run(
     fs,
     None,
     None,
+    Arc::new(PhaseTimer::disabled()),
     CliMainWorkerOptions {
       argv: metadata.argv,
+      broadcast_channel_socket: None,
       log_level: WorkerLogLevel::Info,
       coverage_dir: None,
       enable_testing_features: false,
@@ -441,8 +551,12 @@ pub async fn run(
       )
       .ok()
       .map(|req_ref| npm_pkg_req_ref_to_binary_command(&req_ref)),
+      node_conditions: metadata.node_conditions,
+      // the environment of the user doesn't change how the binary runs
+      node_options: Default::default(),
       origin_data_folder_path: None,
       seed: metadata.seed,
+      split_output: false,
       unsafely_ignore_certificate_errors: metadata
         .unsafely_ignore_certificate_errors,
       unstable: metadata.unstable,
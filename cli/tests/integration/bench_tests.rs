@@ -205,6 +205,12 @@ itest!(json_output {
   output: "bench/pass.json.out",
 });
 
+itest!(json_output_group_summary {
+  args: "bench --json bench/group_baseline.ts",
+  exit_code: 0,
+  output: "bench/group_baseline.json.out",
+});
+
 #[test]
 fn recursive_permissions_pledge() {
   let context = TestContext::default();
@@ -314,3 +314,37 @@ async fn listen_tls_alpn_fail() {
   let status = child.wait().unwrap();
   assert!(status.success());
 }
+
+#[flaky_test::flaky_test]
+fn node_extra_ca_certs() {
+  let context = TestContext::with_http_server();
+  let cafile = context.testdata_path().join("tls/RootCA.pem");
+
+  context
+    .new_command()
+    .args("run --quiet --allow-net cert/node_extra_ca_certs.ts")
+    .env("NODE_EXTRA_CA_CERTS", cafile.to_string_lossy())
+    .run()
+    .assert_exit_code(0)
+    .assert_matches_text(
+      "1\ntrue\nfetch: [WILDCARD]\nHello\nhttps: [WILDCARD]\nHello\n",
+    );
+}
+
+#[test]
+fn node_extra_ca_certs_missing_file() {
+  let context = TestContext::default();
+
+  context
+    .new_command()
+    .args_vec([
+      "eval",
+      "import tls from 'node:tls'; console.log(tls.getCACertificates('extra').length)",
+    ])
+    .env("NODE_EXTRA_CA_CERTS", "does_not_exist.pem")
+    .run()
+    .assert_exit_code(0)
+    .assert_matches_text(
+      "[WILDCARD]Ignoring extra certs from `does_not_exist.pem`, load failed: [WILDCARD]\n0\n",
+    );
+}
@@ -84,6 +84,13 @@ itest!(check_no_error_truncation {
     exit_code: 1,
   });
 
+itest!(check_warn_only_and_error_limit {
+  args: "check check/warn_only/main.ts --config check/warn_only/deno.json",
+  output: "check/warn_only/main.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
 itest!(check_broadcast_channel_stable {
   args: "check --quiet check/broadcast_channel.ts",
   output: "check/broadcast_channel.ts.error.out",
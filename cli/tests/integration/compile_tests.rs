@@ -51,6 +51,41 @@ fn compile_basic() {
   output.assert_matches_text("Welcome to Deno!\n");
 }
 
+#[test]
+fn compile_code_cache() {
+  let context = TestContextBuilder::new().build();
+  let dir = context.temp_dir();
+  let exe_name = format!("code_cache_{}", std::process::id());
+  let exe = if cfg!(windows) {
+    dir.path().join(format!("{exe_name}.exe"))
+  } else {
+    dir.path().join(&exe_name)
+  };
+  let output = context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--code-cache",
+      "--output",
+      &exe.to_string_lossy(),
+      "../../../test_util/std/examples/welcome.ts",
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let code_cache_dir = context.deno_dir().path().join("compile_code_cache_v1");
+  // the code cache is created on the first run, and used on the second one
+  for _ in 0..2 {
+    let output = context
+      .new_command()
+      .command_name(exe.to_string_lossy())
+      .run();
+    output.assert_matches_text("Welcome to Deno!\n");
+    assert_eq!(std::fs::read_dir(&code_cache_dir).unwrap().count(), 1);
+  }
+}
+
 fn make_dir_readonly(dir: &Path) {
   std::fs::create_dir_all(dir).unwrap();
   eprintln!("DIR: {}", dir.display());
@@ -190,6 +190,18 @@ itest!(fmt_check_parse_error {
   exit_code: 1,
 });
 
+itest!(fmt_check_unified_diff {
+  args: "fmt --check --diff-format=unified fmt/badly_formatted.mjs",
+  output: "fmt/fmt_check_unified_diff.out",
+  exit_code: 1,
+});
+
+itest!(fmt_check_json_diff {
+  args: "fmt --check --diff-format=json fmt/badly_formatted.mjs",
+  output: "fmt/fmt_check_json_diff.out",
+  exit_code: 1,
+});
+
 itest!(fmt_check_invalid_data {
   args: "fmt --check fmt/invalid_data.json",
   output: "fmt/invalid_data.out",
@@ -226,6 +238,18 @@ itest!(fmt_stdin_check_not_formatted {
   output_str: Some("Not formatted stdin\n"),
 });
 
+itest!(fmt_stdin_batch {
+  args: "fmt --stdin-batch",
+  input: Some("{\"path\":\"a.ts\",\"contents\":\"const a = 1\\n\"}\n{\"path\":\"b.md\",\"contents\":\"# Hello\\n\"}\n{\"path\":\"c.ts\",\"contents\":\"import {\"}\n"),
+  output_str: Some("{\"path\":\"a.ts\",\"changed\":true,\"contents\":\"const a = 1;\\n\"}\n{\"path\":\"b.md\",\"changed\":false,\"contents\":\"# Hello\\n\"}\n[WILDCARD]\"path\":\"c.ts\",\"error\":[WILDCARD]\n"),
+});
+
+itest!(fmt_stdin_batch_check {
+  args: "fmt --stdin-batch --check",
+  input: Some("{\"path\":\"a.ts\",\"contents\":\"const a = 1;\\n\"}\n"),
+  output_str: Some("{\"path\":\"a.ts\",\"changed\":false}\n"),
+});
+
 itest!(fmt_with_config {
   args: "fmt --config fmt/with_config/deno.jsonc fmt/with_config/subdir",
   output: "fmt/fmt_with_config.out",
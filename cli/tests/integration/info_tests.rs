@@ -1,8 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use test_util as util;
 use test_util::TempDir;
 use util::env_vars_for_npm_tests_no_sync_download;
+use util::TestContextBuilder;
 
 #[test]
 fn info_with_compiled_source() {
@@ -78,6 +81,108 @@ itest!(json_file {
   exit_code: 0,
 });
 
+#[test]
+fn info_json_cache_status() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "import { value } from './dep.ts';\nconsole.log(value);\n",
+  );
+  temp_dir.write("dep.ts", "export const value: number = 1;\n");
+
+  let cache_statuses = || {
+    let output = context
+      .new_command()
+      .args("info --quiet --json main.ts")
+      .split_output()
+      .run();
+    output.assert_exit_code(0);
+    let json: serde_json::Value =
+      serde_json::from_str(output.stdout()).unwrap();
+    json["modules"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|module| {
+        let specifier = module["specifier"].as_str().unwrap();
+        let name = specifier.rsplit('/').next().unwrap();
+        (name.to_string(), module["cacheStatus"].clone())
+      })
+      .collect::<Vec<_>>()
+  };
+  let status = |emit: serde_json::Value, type_check: serde_json::Value| json!({ "emit": emit, "typeCheck": type_check });
+
+  let missing = json!({ "status": "missing" });
+  assert_eq!(
+    cache_statuses(),
+    vec![
+      (
+        "dep.ts".to_string(),
+        status(missing.clone(), missing.clone())
+      ),
+      ("main.ts".to_string(), status(missing.clone(), missing)),
+    ]
+  );
+
+  let output = context.new_command().args("run --check main.ts").run();
+  output.assert_matches_text("Check [WILDCARD]main.ts\n1\n");
+  output.assert_exit_code(0);
+  let cached = json!({ "status": "cached" });
+  assert_eq!(
+    cache_statuses(),
+    vec![
+      ("dep.ts".to_string(), status(cached.clone(), cached.clone())),
+      (
+        "main.ts".to_string(),
+        status(cached.clone(), cached.clone())
+      ),
+    ]
+  );
+
+  temp_dir.write("dep.ts", "export const value: number = 2;\n");
+  let source_changed = json!({ "status": "stale", "reason": "sourceChanged" });
+  assert_eq!(
+    cache_statuses(),
+    vec![
+      (
+        "dep.ts".to_string(),
+        status(source_changed.clone(), source_changed.clone())
+      ),
+      (
+        "main.ts".to_string(),
+        status(
+          cached.clone(),
+          json!({ "status": "stale", "reason": "dependencyChanged" })
+        )
+      ),
+    ]
+  );
+
+  // the mode the modules were type checked with doesn't matter, and
+  // `deno check` doesn't emit
+  let output = context.new_command().args("check --all main.ts").run();
+  output.assert_matches_text("Check [WILDCARD]main.ts\n");
+  output.assert_exit_code(0);
+  assert_eq!(
+    cache_statuses(),
+    vec![
+      ("dep.ts".to_string(), status(source_changed, cached.clone())),
+      ("main.ts".to_string(), status(cached.clone(), cached)),
+    ]
+  );
+}
+
+itest!(graph_format_dot {
+  args: "info --quiet --graph-format=dot info/json_output/main.ts",
+  output: "info/graph_format/dot.out",
+});
+
+itest!(graph_format_mermaid {
+  args: "info --quiet --graph-format=mermaid info/json_output/main.ts",
+  output: "info/graph_format/mermaid.out",
+});
+
 itest!(import_map_info {
   args:
     "info --quiet --import-map=import_maps/import_map.json import_maps/test.ts",
@@ -21,6 +21,7 @@ util::unit_test_factory!(
     chmod_test,
     chown_test,
     command_test,
+    compression_stream_test,
     console_test,
     copy_file_test,
     custom_event_test,
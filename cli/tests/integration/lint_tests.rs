@@ -62,6 +62,13 @@ itest!(stdin_json {
   exit_code: 1,
 });
 
+itest!(stdin_sarif {
+  args: "lint --output=sarif -",
+  input: Some("let _a: any;"),
+  output: "lint/expected_from_stdin_sarif.out",
+  exit_code: 1,
+});
+
 itest!(rules {
   args: "lint --rules",
   output: "lint/expected_rules.out",
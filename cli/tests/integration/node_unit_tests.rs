@@ -39,6 +39,7 @@ util::unit_test_factory!(
     _fs_rm_test = _fs / _fs_rm_test,
     _fs_rmdir_test = _fs / _fs_rmdir_test,
     _fs_stat_test = _fs / _fs_stat_test,
+    _fs_statfs_test = _fs / _fs_statfs_test,
     _fs_symlink_test = _fs / _fs_symlink_test,
     _fs_truncate_test = _fs / _fs_truncate_test,
     _fs_unlink_test = _fs / _fs_unlink_test,
@@ -47,10 +48,14 @@ util::unit_test_factory!(
     _fs_write_test = _fs / _fs_write_test,
     async_hooks_test,
     child_process_test,
+    cluster_test,
     crypto_cipher_test = crypto / crypto_cipher_test,
+    crypto_ecdh_test = crypto / crypto_ecdh_test,
     crypto_hash_test = crypto / crypto_hash_test,
     crypto_key_test = crypto / crypto_key_test,
     crypto_sign_test = crypto / crypto_sign_test,
+    diagnostics_channel_test,
+    dns_test,
     fs_test,
     http_test,
     http2_test,
@@ -61,15 +66,20 @@ util::unit_test_factory!(
     scrypt_test = internal / scrypt_test,
     module_test,
     os_test,
+    perf_hooks_test,
     process_test,
+    punycode_test,
     querystring_test,
     readline_test,
     string_decoder_test,
+    testing_test,
     timers_test,
     tls_test,
     tty_test,
+    url_test,
     util_test,
     v8_test,
+    wasi_test,
     worker_threads_test
   ]
 );
@@ -111,6 +111,35 @@ itest!(conditional_exports_node_modules_dir {
     temp_cwd: true,
   });
 
+itest!(browser_field {
+  args: "run --quiet --allow-read npm/browser_field/main.js",
+  output: "npm/browser_field/main.out",
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(browser_field_browser_condition {
+  args:
+    "run --quiet --allow-read --conditions=browser npm/browser_field/main.js",
+  output: "npm/browser_field/main_browser.out",
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(custom_conditions {
+  args: "run --quiet --allow-read npm/custom_conditions/main.js",
+  output: "npm/custom_conditions/main.out",
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(custom_conditions_flag {
+  args: "run --quiet --allow-read --conditions=development npm/custom_conditions/main.js",
+  output: "npm/custom_conditions/main_development.out",
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
 itest!(dual_cjs_esm {
   args: "run -A --quiet npm/dual_cjs_esm/main.ts",
   output: "npm/dual_cjs_esm/main.out",
@@ -201,6 +230,24 @@ itest!(env_var_re_export_prod {
   http_server: true,
 });
 
+itest!(cjs_dynamic_reexport_dev {
+  args: "run --allow-read --allow-env --quiet npm/cjs_dynamic_reexport/main.js",
+  output_str: Some("dev undefined\n"),
+  envs: env_vars_for_npm_tests(),
+  http_server: true,
+});
+
+itest!(cjs_dynamic_reexport_prod {
+  args: "run --allow-read --allow-env --quiet npm/cjs_dynamic_reexport/main.js",
+  output_str: Some("prod true\n"),
+  envs: {
+    let mut vars = env_vars_for_npm_tests();
+    vars.push(("NODE_ENV".to_string(), "production".to_string()));
+    vars
+  },
+  http_server: true,
+});
+
 itest!(cached_only {
   args: "run --cached-only npm/cached_only/main.ts",
   output: "npm/cached_only/main.out",
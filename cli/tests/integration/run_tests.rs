@@ -38,6 +38,11 @@ itest!(_001_hello {
   output: "run/001_hello.js.out",
 });
 
+itest!(startup_report {
+  args: "run --quiet --reload --startup-report run/001_hello.js",
+  output: "run/startup_report.out",
+});
+
 itest!(_002_hello {
   args: "run --quiet --reload run/002_hello.ts",
   output: "run/002_hello.ts.out",
@@ -752,6 +757,51 @@ fn permissions_prompt_allow_all_lowercase_a() {
     });
 }
 
+#[test]
+fn node_readline_raw_mode() {
+  TestContext::default()
+    .new_command()
+    .args_vec(["run", "--quiet", "run/node_readline_raw_mode.mjs"])
+    .with_pty(|mut console| {
+      console.expect("What is your name? ");
+      console.write_raw("Deno\r");
+      console.expect("raw mode while reading: true");
+      console.expect("Hello Deno, raw mode: false");
+    });
+}
+
+#[test]
+fn node_permissions_prompt_api_name() {
+  TestContext::default()
+    .new_command()
+    .args_vec(["run", "--quiet", "run/node_permissions_prompt.mjs"])
+    .with_pty(|mut console| {
+      console.expect(concat!(
+        "┌ ⚠️  Deno requests write access to \"foo.txt\".\r\n",
+        "├ Requested by `node:fs.writeFileSync()` API.\r\n",
+        "├ Run again with --allow-write to bypass this prompt.\r\n",
+        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all write permissions)",
+      ));
+      console.write_line_raw("n");
+      console.expect("Denied write access to \"foo.txt\".");
+      console.expect("PermissionDenied");
+      console.expect(concat!(
+        "┌ ⚠️  Deno requests sys access to \"hostname\".\r\n",
+        "├ Requested by `node:os.hostname()` API.\r\n",
+        "├ Run again with --allow-sys to bypass this prompt.\r\n",
+        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all sys permissions)",
+      ));
+      console.write_line_raw("n");
+      console.expect("Denied sys access to \"hostname\".");
+      console.expect("PermissionDenied");
+    });
+}
+
+itest!(node_permissions_denied_callback {
+  args: "run --quiet --no-prompt run/node_permissions_denied_callback.mjs",
+  output: "run/node_permissions_denied_callback.mjs.out",
+});
+
 itest!(_091_use_define_for_class_fields {
   args: "run --check run/091_use_define_for_class_fields.ts",
   output: "run/091_use_define_for_class_fields.ts.out",
@@ -1348,6 +1398,16 @@ itest!(v8_flags_env_run {
   output: "run/v8_flags.js.out",
 });
 
+itest!(node_options_env_run {
+  envs: vec![(
+    "NODE_OPTIONS".to_string(),
+    "--max-old-space-size=512 --require ./run/node_options/preload.cjs --title=x"
+      .to_string(),
+  )],
+  args: "run --allow-read run/node_options/main.mjs",
+  output: "run/node_options/main.out",
+});
+
 itest!(v8_flags_unrecognized {
   args: "repl --v8-flags=--foo,bar,--trace-gc,-baz",
   output: "run/v8_flags_unrecognized.out",
@@ -1467,6 +1527,11 @@ itest!(unstable_enabled_js {
   output: "run/unstable_enabled_js.out",
 });
 
+itest!(broadcast_channel_stable {
+  args: "run --quiet --reload --no-check run/broadcast_channel_stable.ts",
+  output: "run/broadcast_channel_stable.ts.out",
+});
+
 itest!(unstable_worker {
   args: "run --reload --unstable --quiet --allow-read run/unstable_worker.ts",
   output: "run/unstable_worker.ts.out",
@@ -2930,6 +2995,11 @@ itest!(import_assertions_dynamic_error {
   exit_code: 1,
 });
 
+itest!(import_assertions_bytes_and_text {
+  args: "run --allow-read --check import_assertions/bytes_and_text.ts",
+  output: "import_assertions/bytes_and_text.out",
+});
+
 itest!(import_assertions_type_check {
   args: "run --allow-read --check import_assertions/type_check.ts",
   output: "import_assertions/type_check.out",
@@ -3132,6 +3202,50 @@ fn node_modules_dir_no_npm_specifiers_no_dir_created() {
   assert!(!temp_dir.path().join("node_modules").exists());
 }
 
+#[test]
+fn deno_version_requirement() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "console.log('ran');");
+
+  // a pinned version that differs only warns
+  temp_dir.write(".deno-version", "1.0.0");
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text(
+      "Warning [WILDCARD].deno-version pins Deno 1.0.0, but this is Deno [WILDCARD]\nran\n",
+    );
+  // and so does an invalid one
+  temp_dir.write(".deno-version", "latest");
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text(
+      "Warning Invalid version \"latest\" in [WILDCARD].deno-version\nran\n",
+    );
+  temp_dir.remove_file(".deno-version");
+
+  temp_dir.write("deno.json", r#"{ "deno": ">=1.0" }"#);
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text("ran\n");
+
+  temp_dir.write("deno.json", r#"{ "deno": "<1.0" }"#);
+  context
+    .new_command()
+    .args("run main.ts")
+    .run()
+    .assert_matches_text(
+      "error: Deno [WILDCARD] does not satisfy the version requirement \"<1.0\" of [WILDCARD]deno.json.\nRun `deno upgrade --version <version>` to install a matching version.\n",
+    )
+    .assert_exit_code(1);
+}
+
 itest!(wasm_streaming_panic_test {
   args: "run run/wasm_streaming_panic_test.js",
   output: "run/wasm_streaming_panic_test.js.out",
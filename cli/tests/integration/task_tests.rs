@@ -68,6 +68,13 @@ itest!(task_boolean_logic {
   envs: vec![("NO_COLOR".to_string(), "1".to_string())],
 });
 
+itest!(task_split_output {
+  args:
+    "task -q --config task/deno_json/deno.json --split-output boolean_logic",
+  output: "task/deno_json/task_split_output.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+});
+
 itest!(task_exit_code_5 {
   args: "task --config task/deno_json/deno.json exit_code_5",
   output: "task/deno_json/task_exit_code_5.out",
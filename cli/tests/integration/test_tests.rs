@@ -61,6 +61,18 @@ itest!(fail {
   output: "test/fail.out",
 });
 
+itest!(junit {
+  args: "test --reporter=junit test/junit.ts",
+  exit_code: 1,
+  output: "test/junit.out",
+});
+
+itest!(tap {
+  args: "test --reporter=tap test/tap.ts",
+  exit_code: 1,
+  output: "test/tap.out",
+});
+
 itest!(collect {
   args: "test --ignore=test/collect/ignore test/collect",
   exit_code: 0,
@@ -297,6 +309,22 @@ itest!(filter {
   output: "test/filter.out",
 });
 
+itest!(describe {
+  args: "test test/describe/main.ts",
+  exit_code: 1,
+  output: "test/describe/main.out",
+});
+
+itest!(describe_filter {
+  args_vec: vec![
+    "test",
+    "--filter=math > nested > runs",
+    "test/describe/main.ts",
+  ],
+  exit_code: 0,
+  output: "test/describe/filter.out",
+});
+
 itest!(shuffle {
   args: "test --shuffle test/shuffle",
   exit_code: 0,
@@ -315,6 +343,12 @@ itest!(aggregate_error {
   output: "test/aggregate_error.out",
 });
 
+itest!(temp_dir {
+  args: "test --allow-read --allow-write test/temp_dir.ts",
+  exit_code: 1,
+  output: "test/temp_dir.out",
+});
+
 itest!(steps_passing_steps {
   args: "test test/steps/passing_steps.ts",
   exit_code: 0,
@@ -398,6 +432,30 @@ fn captured_output() {
   assert_eq!(lines.join(" "), "0 1 2 3 4 5 6 7 8 9");
 }
 
+#[test]
+fn junit_reporter_output() {
+  let context = TestContext::default();
+  let report_path = context.temp_dir().path().join("report.xml");
+  let output = context
+    .new_command()
+    .args_vec([
+      "test",
+      "--reporter=junit",
+      &format!("--reporter-output={}", report_path.display()),
+      "test/junit.ts",
+    ])
+    .env("NO_COLOR", "1")
+    .run();
+  output.assert_exit_code(1);
+  // the results are still shown on the terminal
+  assert_contains!(output.combined_output(), "running 6 tests from");
+  let report = context.temp_dir().read_to_string("report.xml");
+  assert_contains!(
+    report,
+    r#"<testsuite name="./test/junit.ts" tests="7" failures="1" errors="0" skipped="1""#
+  );
+}
+
 #[test]
 fn recursive_permissions_pledge() {
   let context = TestContext::default();
@@ -393,6 +393,99 @@ fn existing_import_map_mixed_with_remote() {
   assert!(status.success());
 }
 
+#[test]
+fn eszip_import_source() {
+  let _server = http_server();
+  let t = TempDir::new();
+  t.write(
+    "my_app.ts",
+    "import {Logger} from 'http://localhost:4545/vendor/query_reexport.ts?testing'; new Logger().log('outputted');",
+  );
+
+  let deno = util::deno_cmd()
+    .current_dir(t.path())
+    .env("NO_COLOR", "1")
+    .arg("vendor")
+    .arg("--eszip")
+    .arg("out.eszip")
+    .arg("my_app.ts")
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let output = deno.wait_with_output().unwrap();
+  assert_eq!(
+    String::from_utf8_lossy(&output.stderr).trim(),
+    concat!(
+      "Download http://localhost:4545/vendor/query_reexport.ts?testing\n",
+      "Download http://localhost:4545/vendor/logger.ts?test\n",
+      "Vendored 3 modules into out.eszip.\n\n",
+      "To use it, specify the `--import-source=out.eszip` flag when running.",
+    )
+  );
+  assert!(output.status.success());
+  assert!(t.path().join("out.eszip").exists());
+  assert!(!t.path().join("vendor").exists());
+
+  // the archive is used as is, so changing the local module has no effect
+  // and nothing is downloaded into the empty deno dir
+  t.write("my_app.ts", "console.log('changed');");
+  let deno_dir = new_deno_dir();
+  let deno = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(t.path())
+    .env("NO_COLOR", "1")
+    .arg("run")
+    .arg("--check")
+    .arg("--import-source=out.eszip")
+    .arg("my_app.ts")
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let output = deno.wait_with_output().unwrap();
+  // the original sources are type checked
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(stderr.starts_with("Check file:///"), "{stderr}");
+  assert!(!stderr.contains("Download"), "{stderr}");
+  assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "outputted");
+  assert!(output.status.success());
+
+  // the remote modules of the archive are still subject to --no-remote
+  let deno = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(t.path())
+    .env("NO_COLOR", "1")
+    .arg("run")
+    .arg("--no-remote")
+    .arg("--import-source=out.eszip")
+    .arg("my_app.ts")
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let output = deno.wait_with_output().unwrap();
+  assert!(String::from_utf8_lossy(&output.stderr).contains(
+    "A remote specifier was requested: \"http://localhost:4545/vendor/query_reexport.ts?testing\", but --no-remote is specified."
+  ));
+  assert!(!output.status.success());
+
+  // refuses to overwrite the archive without --force
+  let deno = util::deno_cmd()
+    .current_dir(t.path())
+    .env("NO_COLOR", "1")
+    .arg("vendor")
+    .arg("--eszip")
+    .arg("out.eszip")
+    .arg("my_app.ts")
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let output = deno.wait_with_output().unwrap();
+  assert!(String::from_utf8_lossy(&output.stderr)
+    .contains("Output file already exists."));
+  assert!(!output.status.success());
+}
+
 #[test]
 fn dynamic_import() {
   let _server = http_server();
@@ -1241,6 +1241,54 @@ async fn test_watch_doc() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test]
+#[tokio::main]
+async fn test_watch_debug_failures() {
+  let t = TempDir::new();
+  let test_file = t.path().join("foo_test.ts");
+  write(
+    &test_file,
+    r#"
+    Deno.test("passes", () => {});
+    Deno.test("fails", () => {
+      throw new Error("fail");
+    });
+  "#,
+  )
+  .unwrap();
+
+  let mut child = util::deno_cmd()
+    .current_dir(util::testdata_path())
+    .arg("test")
+    .arg("--watch")
+    .arg("--debug-failures=127.0.0.1:9299")
+    .arg(&test_file)
+    .env("NO_COLOR", "1")
+    .stdout(std::process::Stdio::piped())
+    .stderr(std::process::Stdio::piped())
+    .spawn()
+    .unwrap();
+  let (_, mut stderr_lines) = child_lines(&mut child);
+
+  wait_contains("Debugging fails", &mut stderr_lines).await;
+  wait_contains(
+    "Debugger listening on ws://127.0.0.1:9299/ws/",
+    &mut stderr_lines,
+  )
+  .await;
+  wait_contains(
+    "Or open devtools://devtools/bundled/js_app.html?ws=127.0.0.1:9299/ws/",
+    &mut stderr_lines,
+  )
+  .await;
+  wait_contains(
+    "Deno is waiting for debugger to connect.",
+    &mut stderr_lines,
+  )
+  .await;
+  check_alive_then_kill(child);
+}
+
 #[tokio::test]
 async fn test_watch_module_graph_error_referrer() {
   let t = TempDir::new();
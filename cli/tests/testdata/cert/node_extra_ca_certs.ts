@@ -0,0 +1,14 @@
+import https from "node:https";
+import tls from "node:tls";
+
+console.log(tls.getCACertificates("extra").length);
+console.log(tls.getCACertificates("bundled").length > 0);
+
+const res = await fetch("https://localhost:5545/cert/cafile_ts_fetch.ts.out");
+console.log(`fetch: ${(await res.text()).trimEnd()}`);
+
+https.get("https://localhost:5545/cert/cafile_ts_fetch.ts.out", (res) => {
+  let body = "";
+  res.on("data", (chunk) => body += chunk);
+  res.on("end", () => console.log(`https: ${body.trimEnd()}`));
+});
@@ -0,0 +1,4 @@
+const _a: number = "a";
+const _b: string = 1;
+function f(_x: number) {}
+f("x");
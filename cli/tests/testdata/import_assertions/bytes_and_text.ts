@@ -0,0 +1,12 @@
+import bytes from "./data.json" assert { type: "bytes" };
+import text from "./data.json" assert { type: "text" };
+
+const decoded: string = new TextDecoder().decode(bytes);
+console.log(bytes instanceof Uint8Array, bytes.length);
+console.log(decoded === text);
+console.log(JSON.parse(text).c.d);
+
+const { default: again } = await import("./data.json", {
+  assert: { type: "bytes" },
+});
+console.log(again === bytes);
@@ -0,0 +1,3 @@
+import mod from "npm:@denotest/browser-field";
+
+console.log(mod);
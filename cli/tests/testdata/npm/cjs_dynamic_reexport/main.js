@@ -0,0 +1,3 @@
+import { getEnv, isProduction } from "npm:@denotest/cjs-dynamic-reexport";
+
+console.log(getEnv(), isProduction);
@@ -0,0 +1,5 @@
+import mod from "npm:@denotest/custom-conditions";
+import required from "npm:@denotest/custom-conditions/required";
+
+console.log(mod);
+console.log(required);
@@ -0,0 +1,4 @@
+import fs from "fs";
+import server from "./server.js";
+
+export default `browser ${JSON.stringify(fs)} ${JSON.stringify(server)}`;
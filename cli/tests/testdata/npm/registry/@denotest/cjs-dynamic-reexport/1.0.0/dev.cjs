@@ -0,0 +1,3 @@
+exports.getEnv = function () {
+  return "dev";
+};
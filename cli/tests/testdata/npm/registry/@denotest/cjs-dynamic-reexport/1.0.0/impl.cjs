@@ -0,0 +1,4 @@
+module.exports = {
+  dev: require('./dev.cjs'),
+  prod: require('./prod.cjs'),
+};
@@ -0,0 +1 @@
+module.exports = require('./impl.cjs')[process.env.NODE_ENV === 'production' ? 'prod' : 'dev'];
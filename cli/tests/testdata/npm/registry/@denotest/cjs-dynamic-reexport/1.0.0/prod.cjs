@@ -0,0 +1,4 @@
+exports.getEnv = function () {
+  return "prod";
+};
+exports.isProduction = true;
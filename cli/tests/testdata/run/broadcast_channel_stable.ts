@@ -0,0 +1,6 @@
+try {
+  new BroadcastChannel("foo");
+} catch (e) {
+  console.log(e instanceof Deno.errors.NotSupported, e.message);
+}
+console.log("still running");
@@ -0,0 +1,3 @@
+import process from "node:process";
+
+console.log("main", process.argv.length > 0);
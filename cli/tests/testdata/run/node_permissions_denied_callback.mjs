@@ -0,0 +1,26 @@
+import fs from "node:fs";
+
+// a denied permission is passed to the callback instead of being thrown
+function call(name, ...args) {
+  return new Promise((resolve) => {
+    fs[name](...args, (err) => {
+      console.log(name, err?.name);
+      resolve();
+    });
+  });
+}
+
+await call("mkdir", "foo");
+await call("rename", "foo.txt", "bar.txt");
+await call("unlink", "foo.txt");
+await call("copyFile", "foo.txt", "bar.txt");
+await call("rm", "foo.txt");
+await call("rmdir", "foo");
+await call("writeFile", "foo.txt", "hello");
+await call("chmod", "foo.txt", 0o644);
+await call("chown", "foo.txt", 1000, 1000);
+await call("link", "foo.txt", "bar.txt");
+await call("symlink", "foo.txt", "bar.txt");
+await call("truncate", "foo.txt", 0);
+await call("utimes", "foo.txt", 0, 0);
+await call("lutimes", "foo.txt", 0, 0);
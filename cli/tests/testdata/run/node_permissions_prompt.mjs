@@ -0,0 +1,14 @@
+import fs from "node:fs";
+import os from "node:os";
+
+try {
+  fs.writeFileSync("foo.txt", "hello");
+} catch (err) {
+  console.log(err.name);
+}
+
+try {
+  os.hostname();
+} catch (err) {
+  console.log(err.name);
+}
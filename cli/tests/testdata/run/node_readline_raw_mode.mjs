@@ -0,0 +1,11 @@
+import * as readline from "node:readline/promises";
+import process from "node:process";
+
+const rl = readline.createInterface({
+  input: process.stdin,
+  output: process.stdout,
+});
+const answer = await rl.question("What is your name? ");
+console.log(`raw mode while reading: ${process.stdin.isRaw}`);
+rl.close();
+console.log(`Hello ${answer}, raw mode: ${process.stdin.isRaw}`);
@@ -0,0 +1,65 @@
+const { afterEach, beforeEach, describe, it } = Deno.test;
+
+const calls: string[] = [];
+
+describe("math", () => {
+  let value = 0;
+
+  beforeEach(() => {
+    calls.push("outer before");
+    value = 1;
+  });
+
+  afterEach(() => {
+    calls.push("outer after");
+  });
+
+  it("starts from the hook", () => {
+    if (value !== 1) {
+      throw new Error(`expected 1, got ${value}`);
+    }
+  });
+
+  describe("nested", () => {
+    beforeEach(() => {
+      calls.push("inner before");
+      value++;
+    });
+
+    afterEach(() => {
+      calls.push("inner after");
+    });
+
+    it("runs the outer hooks first", () => {
+      if (value !== 2) {
+        throw new Error(`expected 2, got ${value}`);
+      }
+    });
+
+    it("fails", () => {
+      throw new Error("failed");
+    });
+  });
+
+  describe({ name: "ignored", ignore: true }, () => {
+    it("is ignored", () => {});
+  });
+});
+
+it("out of a suite", () => {
+  const expected = [
+    "outer before",
+    "outer after",
+    "outer before",
+    "inner before",
+    "inner after",
+    "outer after",
+    "outer before",
+    "inner before",
+    "inner after",
+    "outer after",
+  ].join();
+  if (calls.join() !== expected) {
+    throw new Error(`unexpected hook calls: ${calls.join()}`);
+  }
+});
@@ -0,0 +1,14 @@
+Deno.test("pass", () => {});
+Deno.test("fail", () => {
+  throw new Error("<unexpected> & \"quoted\"");
+});
+Deno.test({ name: "ignored", ignore: true, fn() {} });
+Deno.test("steps", async (t) => {
+  await t.step("inner", () => {});
+});
+Deno.test("output", () => {
+  console.log("<printed>");
+});
+Deno.test.describe("suite", () => {
+  Deno.test.it("case", () => {});
+});
@@ -0,0 +1,20 @@
+Deno.test("pass", () => {});
+Deno.test("fail", () => {
+  throw new Error("boom");
+});
+Deno.test({ name: "ignored # not a directive", ignore: true, fn() {} });
+Deno.test("steps", async (t) => {
+  await t.step("outer", async (t) => {
+    await t.step("inner", () => {});
+  });
+  await t.step({ name: "ignored step", ignore: true, fn() {} });
+});
+Deno.test("output", () => {
+  console.log("printed\nlines");
+});
+Deno.test.describe("suite", () => {
+  Deno.test.it("case", () => {});
+  Deno.test.describe("nested", () => {
+    Deno.test.it("nested case", () => {});
+  });
+});
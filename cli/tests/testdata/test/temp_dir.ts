@@ -0,0 +1,43 @@
+const paths: string[] = [];
+
+function assertRemoved(path: string) {
+  try {
+    Deno.statSync(path);
+  } catch (error) {
+    if (error instanceof Deno.errors.NotFound) {
+      return;
+    }
+    throw error;
+  }
+  throw new Error(`${path} was not removed`);
+}
+
+Deno.test("removed after the test", async (t) => {
+  const dir = await t.tempDir({ prefix: "deno_test_" });
+  paths.push(dir);
+  await Deno.writeTextFile(`${dir}/hello.txt`, "hello");
+});
+
+Deno.test("removed after a failure", async (t) => {
+  paths.push(await t.tempDir());
+  throw new Error("test failed");
+});
+
+Deno.test("removed after the step", async (t) => {
+  let dir = "";
+  await t.step("step", async (t) => {
+    dir = await t.tempDir();
+    await Deno.mkdir(`${dir}/nested`);
+  });
+  assertRemoved(dir);
+});
+
+Deno.test("already removed by the test", async (t) => {
+  await Deno.remove(await t.tempDir());
+});
+
+Deno.test("all removed", () => {
+  for (const path of paths) {
+    assertRemoved(path);
+  }
+});
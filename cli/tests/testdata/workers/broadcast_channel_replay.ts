@@ -0,0 +1,7 @@
+let count = 0;
+new BroadcastChannel("replay").onmessage = () => {
+  // the messages are kept to be replayed once they reach other isolates
+  if (++count === 3) self.postMessage("received");
+};
+
+self.postMessage("ready");
@@ -0,0 +1,6 @@
+// Posted while the worker is idle in the pool, before it's acquired.
+postMessage("ready");
+
+onmessage = function (e) {
+  postMessage(e.data);
+};
@@ -812,3 +812,38 @@ Deno.test({
     w.terminate();
   },
 });
+
+Deno.test({
+  name: "worker pool",
+  fn: async function () {
+    const pool = new Deno.WorkerPool(
+      import.meta.resolve("./pool_worker.ts"),
+      { size: 2 },
+    );
+    // the third worker is spawned when it's acquired since the pool is only
+    // refilled afterwards
+    const workers = [pool.acquire(), pool.acquire(), pool.acquire()];
+    const promises = workers.map((worker, i) => {
+      const received: unknown[] = [];
+      const promise = deferred<unknown[]>();
+      worker.onmessage = (e) => {
+        received.push(e.data);
+        if (received.length == 2) {
+          promise.resolve(received);
+        }
+      };
+      worker.postMessage(i);
+      return promise;
+    });
+    assertEquals(await Promise.all(promises), [
+      ["ready", 0],
+      ["ready", 1],
+      ["ready", 2],
+    ]);
+    for (const worker of workers) {
+      worker.terminate();
+    }
+    pool.close();
+    assertThrows(() => pool.acquire(), TypeError, "Worker pool is closed.");
+  },
+});
@@ -113,3 +113,67 @@ Deno.test(function blobCustomInspectFunction() {
   );
   assertStringIncludes(Deno.inspect(Blob.prototype), "Blob");
 });
+
+Deno.test(async function blobStreamLargePart() {
+  const data = new Uint8Array(3 * 1024 * 1024 + 1).map((_, i) => i % 251);
+  const blob = new Blob([data]);
+  const chunks = [];
+  for await (const chunk of blob.stream()) {
+    chunks.push(chunk);
+  }
+  // large parts are read in chunks
+  assert(chunks.length > 1);
+  assertEquals(concat(...chunks), data);
+  assertEquals(
+    new Uint8Array(await blob.slice(5, 10).arrayBuffer()),
+    new Uint8Array([5, 6, 7, 8, 9]),
+  );
+});
+
+Deno.test(function blobStoreMetrics() {
+  // @ts-ignore: Deno[Deno.internal].core allowed
+  const ops = Deno[Deno.internal].core.ops;
+  const before = ops.op_blob_store_metrics();
+  const url = URL.createObjectURL(new Blob([new Uint8Array(1000)]));
+  const metrics = ops.op_blob_store_metrics();
+  assertEquals(metrics.objectUrlCount, before.objectUrlCount + 1);
+  assert(metrics.totalSize >= before.totalSize + 1000);
+  assert(metrics.partSizes.includes(1000));
+  URL.revokeObjectURL(url);
+  assertEquals(
+    ops.op_blob_store_metrics().objectUrlCount,
+    before.objectUrlCount,
+  );
+});
+
+Deno.test(async function blobStreamNestedParts() {
+  const blob = new Blob(["ab", new Blob(["", "cd"]), new Uint8Array([101])]);
+  const chunks = [];
+  for await (const chunk of blob.stream()) {
+    chunks.push(chunk);
+  }
+  assertEquals(new TextDecoder().decode(concat(...chunks)), "abcde");
+
+  const url = URL.createObjectURL(blob);
+  try {
+    const response = await fetch(url);
+    assertEquals(await response.text(), "abcde");
+  } finally {
+    URL.revokeObjectURL(url);
+  }
+});
+
+Deno.test(async function blobStreamOpensReaderWhenRead() {
+  const blob = new Blob(["abc"]);
+  const before = Object.values(Deno.resources());
+  // a body made from a blob is often never read
+  new Response(blob);
+  const stream = blob.stream();
+  assertEquals(Object.values(Deno.resources()), before);
+
+  const reader = stream.getReader();
+  const { value } = await reader.read();
+  assertEquals(new TextDecoder().decode(value), "abc");
+  assert((await reader.read()).done);
+  assertEquals(Object.values(Deno.resources()), before);
+});
@@ -1,5 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { assertEquals } from "../../../test_util/std/testing/asserts.ts";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
 import { deferred } from "../../../test_util/std/async/deferred.ts";
 
 Deno.test("BroadcastChannel worker", async () => {
@@ -33,3 +37,108 @@ Deno.test("BroadcastChannel immediate close after post", () => {
   bc.postMessage("New listening connected!");
   bc.close();
 });
+
+Deno.test("BroadcastChannel replays the last messages", async () => {
+  const url = import.meta.resolve(
+    "../testdata/workers/broadcast_channel_replay.ts",
+  );
+  const worker = new Worker(url, { type: "module", name: "worker" });
+  const ready = deferred();
+  const sent = deferred();
+  worker.onmessage = (e) => {
+    if (e.data === "ready") ready.resolve();
+    else sent.resolve();
+  };
+  await ready;
+
+  const sender = new BroadcastChannel("replay");
+  for (const message of [1, 2, 3]) {
+    sender.postMessage(message);
+  }
+  // the worker receives the messages once they went past the subscription
+  // of this isolate
+  await sent;
+
+  const received: number[] = [];
+  const promise = deferred();
+  const late = new BroadcastChannel("replay", { replay: 2 });
+  late.onmessage = (e) => {
+    received.push(e.data);
+    if (received.length === 2) promise.resolve();
+  };
+  const other = new BroadcastChannel("replay");
+  other.onmessage = () => promise.reject(new Error("unexpected message"));
+
+  await promise;
+  assertEquals(received, [2, 3]);
+  worker.terminate();
+  sender.close();
+  late.close();
+  other.close();
+});
+
+Deno.test("BroadcastChannel errors", async () => {
+  const sender = new BroadcastChannel("errors");
+  const receiver = new BroadcastChannel("errors");
+  const promise = deferred<MessageEvent>();
+  receiver.onmessage = promise.resolve;
+  sender.postMessage(
+    new RangeError("outer", { cause: new TypeError("inner") }),
+  );
+
+  const { data } = await promise;
+  assert(data instanceof RangeError);
+  assertEquals(data.message, "outer");
+  assert(data.cause instanceof TypeError);
+  assertEquals(data.cause.message, "inner");
+  sender.close();
+  receiver.close();
+});
+
+Deno.test("BroadcastChannel pattern", async () => {
+  const jobs = new BroadcastChannel("jobs.*", { pattern: true });
+  const received: [string, string][] = [];
+  const promise = deferred();
+  jobs.onmessage = (e) => {
+    received.push([e.channel, e.data]);
+    if (received.length === 2) promise.resolve();
+  };
+  const a = new BroadcastChannel("jobs.a");
+  const b = new BroadcastChannel("jobs.b");
+  const other = new BroadcastChannel("events.a");
+  a.postMessage("a");
+  other.postMessage("other");
+  b.postMessage("b");
+
+  await promise;
+  assertEquals(received, [["jobs.a", "a"], ["jobs.b", "b"]]);
+  assertThrows(() => jobs.postMessage("jobs"), DOMException);
+  assertThrows(
+    () => new BroadcastChannel("jobs.*", { pattern: true, replay: 1 }),
+    DOMException,
+  );
+  jobs.close();
+  a.close();
+  b.close();
+  other.close();
+});
+
+Deno.test("BroadcastChannel stats", () => {
+  const { broadcastChannelStats } = Deno;
+  const a = new BroadcastChannel("stats");
+  const b = new BroadcastChannel("stats");
+  const stats = broadcastChannelStats();
+  assert(stats.subscribers >= 1);
+  assertEquals(
+    stats.openChannels.filter((name: string) => name === "stats").length,
+    2,
+  );
+  a.close();
+  b.close();
+  assertEquals(
+    broadcastChannelStats().openChannels.filter((name: string) =>
+      name === "stats"
+    ),
+    [],
+  );
+});
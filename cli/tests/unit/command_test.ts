@@ -232,6 +232,56 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { run: true, write: true, read: true } },
+  async function commandPipeLargeStdoutToFile() {
+    const tempDir = await Deno.makeTempDir();
+    const fileName = tempDir + "/large_stdout.txt";
+    const file = await Deno.open(fileName, {
+      create: true,
+      write: true,
+    });
+
+    const command = new Deno.Command(Deno.execPath(), {
+      args: [
+        "eval",
+        "const b = new Uint8Array(4 * 1024 * 1024).fill(97); let n = 0; " +
+          "while (n < b.length) n += await Deno.stdout.write(b.subarray(n));",
+      ],
+      stdout: "piped",
+      stderr: "null",
+    });
+    const child = command.spawn();
+    await child.stdout.pipeTo(file.writable);
+    assert(child.stdout.locked === false);
+    await child.status;
+
+    const { size } = await Deno.stat(fileName);
+    assertEquals(size, 4 * 1024 * 1024);
+  },
+);
+
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandAbortStdinWithPendingWrite() {
+    const command = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 10000)"],
+      stdin: "piped",
+      stdout: "null",
+      stderr: "null",
+    });
+    const child = command.spawn();
+    const writer = child.stdin.getWriter();
+    // the child never reads its stdin, so this write can't complete
+    const write = writer.write(new Uint8Array(4 * 1024 * 1024));
+    await writer.abort();
+    await assertRejects(() => write);
+
+    child.kill();
+    await child.status;
+  },
+);
+
 Deno.test(
   { permissions: { run: true, read: true } },
   async function commandKillSuccess() {
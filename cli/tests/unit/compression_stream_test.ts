@@ -0,0 +1,167 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { assertEquals, assertRejects, assertThrows } from "./test_util.ts";
+
+async function transform(
+  stream: TransformStream<Uint8Array, Uint8Array>,
+  chunks: Uint8Array[],
+): Promise<Uint8Array> {
+  const readable = new Blob(chunks).stream().pipeThrough(stream);
+  return new Uint8Array(await new Response(readable).arrayBuffer());
+}
+
+const encoder = new TextEncoder();
+const dictionary = encoder.encode(
+  "the quick brown fox jumps over the lazy dog",
+);
+const data = encoder.encode(
+  "the lazy dog jumps over the quick brown fox, ".repeat(3),
+);
+
+Deno.test(async function compressionStreamLevel() {
+  const levels: [string, number[]][] = [
+    ["deflate", [0, 1, 9]],
+    ["deflate-raw", [0, 1, 9]],
+    ["gzip", [0, 1, 9]],
+    ["br", [0, 1, 11]],
+    ["zstd", [1, 22]],
+  ];
+  for (const [format, formatLevels] of levels) {
+    for (const level of formatLevels) {
+      const compressed = await transform(
+        new CompressionStream(format, { level }),
+        [data],
+      );
+      const decompressed = await transform(
+        new DecompressionStream(format),
+        [compressed],
+      );
+      assertEquals(decompressed, data);
+    }
+  }
+});
+
+Deno.test(async function compressionStreamBrotliZstd() {
+  for (const format of ["br", "zstd"]) {
+    const compressed = await transform(new CompressionStream(format), [
+      data.subarray(0, 50),
+      data.subarray(50),
+    ]);
+    assertEquals(compressed.length < data.length, true);
+    const decompressed = await transform(
+      new DecompressionStream(format),
+      // one byte at a time, to cross every boundary of the format
+      Array.from(compressed, (byte) => new Uint8Array([byte])),
+    );
+    assertEquals(decompressed, data);
+  }
+
+  // the magic number of a zstd frame
+  const zstd = await transform(new CompressionStream("zstd"), [data]);
+  assertEquals(zstd.subarray(0, 4), new Uint8Array([0x28, 0xb5, 0x2f, 0xfd]));
+});
+
+Deno.test(async function compressionStreamDictionary() {
+  for (const format of ["deflate", "deflate-raw"]) {
+    const withoutDictionary = await transform(
+      new CompressionStream(format),
+      [data],
+    );
+    const compressed = await transform(
+      new CompressionStream(format, { dictionary }),
+      [data.subarray(0, 50), data.subarray(50)],
+    );
+    assertEquals(compressed.length < withoutDictionary.length, true);
+
+    const decompressed = await transform(
+      new DecompressionStream(format, { dictionary }),
+      // split the data so the zlib header arrives in pieces
+      [
+        compressed.subarray(0, 1),
+        compressed.subarray(1, 3),
+        compressed.subarray(3),
+      ],
+    );
+    assertEquals(decompressed, data);
+  }
+});
+
+Deno.test(async function decompressionStreamWrongDictionary() {
+  const compressed = await transform(
+    new CompressionStream("deflate", { dictionary }),
+    [data],
+  );
+  await assertRejects(
+    () =>
+      transform(
+        new DecompressionStream("deflate", {
+          dictionary: encoder.encode("something else"),
+        }),
+        [compressed],
+      ),
+    TypeError,
+    "Incorrect dictionary",
+  );
+});
+
+Deno.test(async function compressionStreamFlush() {
+  const formats = ["deflate", "deflate-raw", "gzip", "br", "zstd"];
+  for (const format of formats) {
+    for (const flush of ["sync", "full"] as const) {
+      const compression = new CompressionStream(format, { flush });
+      const reader = compression.readable
+        .pipeThrough(new DecompressionStream(format))
+        .getReader();
+      const input = compression.writable.getWriter();
+
+      // every chunk can be decompressed before the stream is closed
+      for (const chunk of [data.subarray(0, 50), data.subarray(50)]) {
+        const written = input.write(chunk);
+        let output = new Uint8Array();
+        while (output.length < chunk.length) {
+          const { value } = await reader.read();
+          output = new Uint8Array([...output, ...value!]);
+        }
+        assertEquals(output, chunk);
+        await written;
+      }
+      await input.close();
+      assertEquals((await reader.read()).done, true);
+    }
+
+    const compressed = await transform(
+      new CompressionStream(format, { flush: "none" }),
+      [data.subarray(0, 50), data.subarray(50)],
+    );
+    assertEquals(
+      await transform(new DecompressionStream(format), [compressed]),
+      data,
+    );
+  }
+});
+
+Deno.test(function compressionStreamInvalidOptions() {
+  assertThrows(
+    () => new CompressionStream("deflate", { level: 10 }),
+    RangeError,
+  );
+  assertThrows(
+    () => new CompressionStream("deflate", { level: -1 }),
+    TypeError,
+  );
+  assertThrows(
+    () => new CompressionStream("br", { level: 12 }),
+    RangeError,
+  );
+  assertThrows(
+    () => new CompressionStream("gzip", { dictionary }),
+    TypeError,
+    'A dictionary is not supported for the "gzip" format.',
+  );
+  assertThrows(
+    () =>
+      new CompressionStream("gzip", {
+        flush: "partial" as unknown as "sync",
+      }),
+    TypeError,
+  );
+});
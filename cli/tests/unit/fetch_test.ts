@@ -3,6 +3,7 @@ import {
   assert,
   assertEquals,
   assertRejects,
+  assertThrows,
   deferred,
   delay,
   fail,
@@ -1844,6 +1845,40 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchBlobUrlRange(): Promise<void> {
+    const blob = new Blob(["0123456789"], { type: "text/plain" });
+    const url = URL.createObjectURL(blob);
+    const fetchRange = (range: string) => fetch(url, { headers: { range } });
+
+    let res = await fetchRange("bytes=2-4");
+    assertEquals(res.status, 206);
+    assertEquals(res.statusText, "Partial Content");
+    assertEquals(res.headers.get("content-length"), "3");
+    assertEquals(res.headers.get("content-range"), "bytes 2-4/10");
+    assertEquals(res.headers.get("content-type"), "text/plain");
+    assertEquals(await res.text(), "234");
+
+    res = await fetchRange("bytes = 7-");
+    assertEquals(res.headers.get("content-range"), "bytes 7-9/10");
+    assertEquals(await res.text(), "789");
+
+    res = await fetchRange("bytes=-3");
+    assertEquals(res.headers.get("content-range"), "bytes 7-9/10");
+    assertEquals(await res.text(), "789");
+
+    res = await fetchRange("bytes=5-100");
+    assertEquals(res.headers.get("content-range"), "bytes 5-9/10");
+    assertEquals(await res.text(), "56789");
+
+    for (const range of ["bytes=10-", "bytes=4-2", "bytes=-", "items=0-1"]) {
+      await assertRejects(() => fetchRange(range), TypeError);
+    }
+    URL.revokeObjectURL(url);
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function fetchResponseStreamIsLockedWhileReading() {
@@ -1947,3 +1982,69 @@ Deno.test("Response with subarray TypedArray body", async () => {
   const expected = new Uint8Array([2, 3, 4, 5]);
   assertEquals(actual, expected);
 });
+
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientRetry() {
+    let requests = 0;
+    const ac = new AbortController();
+    const listening = deferred();
+    const server = Deno.serve({
+      handler: (req) => {
+        requests++;
+        if (req.method !== "GET" || requests < 3) {
+          return new Response("unavailable", { status: 503 });
+        }
+        return new Response("ok");
+      },
+      port: 4515,
+      signal: ac.signal,
+      onListen: () => listening.resolve(),
+    });
+    await listening;
+
+    const client = Deno.createHttpClient({
+      retry: { maxAttempts: 3, initialDelay: 10 },
+    });
+    const retries: Deno.HttpClientRetryEventDetail[] = [];
+    client.addEventListener("retry", (event) => {
+      retries.push((event as CustomEvent).detail);
+    });
+
+    const response = await fetch("http://localhost:4515/", { client });
+    assertEquals(response.status, 200);
+    assertEquals(await response.text(), "ok");
+    assertEquals(requests, 3);
+    assertEquals(retries.map(({ attempt, status }) => [attempt, status]), [
+      [1, 503],
+      [2, 503],
+    ]);
+    assertEquals(retries[0].method, "GET");
+    assertEquals(retries[0].url, "http://localhost:4515/");
+    assertEquals(retries[1].delay, 20);
+
+    // POST isn't idempotent, so it is only sent once
+    requests = 0;
+    const post = await fetch("http://localhost:4515/", {
+      client,
+      method: "POST",
+      body: "a",
+    });
+    assertEquals(post.status, 503);
+    await post.body?.cancel();
+    assertEquals(requests, 1);
+    assertEquals(retries.length, 2);
+
+    client.close();
+    ac.abort();
+    await server.finished;
+  },
+);
+
+Deno.test(function createHttpClientInvalidRetry() {
+  assertThrows(
+    () => Deno.createHttpClient({ retry: { maxAttempts: 0 } }),
+    TypeError,
+    "`retry.maxAttempts` must be at least 1",
+  );
+});
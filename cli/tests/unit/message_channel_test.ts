@@ -4,6 +4,7 @@
 import {
   assert,
   assertEquals,
+  assertThrows,
 } from "../../../test_util/std/testing/asserts.ts";
 import { deferred } from "../../../test_util/std/async/deferred.ts";
 
@@ -57,3 +58,76 @@ Deno.test("messagechannel clone port", async () => {
   mc.port2.close();
   mc2.port2.close();
 });
+
+Deno.test("messagechannel transfer readable stream", async () => {
+  const mc = new MessageChannel();
+  const readable = new ReadableStream({
+    start(controller) {
+      controller.enqueue("a");
+      controller.enqueue({ b: 1 });
+      controller.close();
+    },
+  });
+
+  const promise = deferred<ReadableStream>();
+  mc.port2.onmessage = (e) => {
+    assertEquals(e.ports.length, 0);
+    promise.resolve(e.data.stream);
+  };
+  mc.port1.postMessage({ stream: readable }, [readable]);
+  assert(readable.locked);
+
+  const stream = await promise;
+  assert(stream instanceof ReadableStream);
+  const chunks = [];
+  for await (const chunk of stream) {
+    chunks.push(chunk);
+  }
+  assertEquals(chunks, ["a", { b: 1 }]);
+
+  mc.port1.close();
+  mc.port2.close();
+});
+
+Deno.test("messagechannel transfer writable stream", async () => {
+  const mc = new MessageChannel();
+  const chunks: string[] = [];
+  const closed = deferred();
+  const writable = new WritableStream<string>({
+    write(chunk) {
+      chunks.push(chunk);
+    },
+    close() {
+      closed.resolve();
+    },
+  });
+
+  const promise = deferred<WritableStream<string>>();
+  mc.port2.onmessage = (e) => promise.resolve(e.data);
+  mc.port1.postMessage(writable, [writable]);
+
+  const stream = await promise;
+  assert(stream instanceof WritableStream);
+  const writer = stream.getWriter();
+  await writer.write("a");
+  await writer.write("b");
+  await writer.close();
+  await closed;
+  assertEquals(chunks, ["a", "b"]);
+
+  mc.port1.close();
+  mc.port2.close();
+});
+
+Deno.test("messagechannel transfer locked stream", () => {
+  const mc = new MessageChannel();
+  const readable = new ReadableStream();
+  readable.getReader();
+  assertThrows(
+    () => mc.port1.postMessage(readable, [readable]),
+    DOMException,
+    "Stream at index 0 is locked",
+  );
+  mc.port1.close();
+  mc.port2.close();
+});
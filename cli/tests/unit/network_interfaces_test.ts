@@ -1,6 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import { assert } from "./test_util.ts";
+import { assert, assertEquals } from "./test_util.ts";
 
 Deno.test(
   {
@@ -12,7 +12,7 @@ Deno.test(
     assert(Array.isArray(networkInterfaces));
     assert(networkInterfaces.length > 0);
     for (
-      const { name, family, address, netmask, scopeid, cidr, mac }
+      const { name, family, address, netmask, scopeid, cidr, mac, flags, mtu }
         of networkInterfaces
     ) {
       assert(typeof name === "string");
@@ -25,6 +25,14 @@ Deno.test(
       );
       assert(typeof cidr === "string");
       assert(typeof mac === "string");
+      assert(typeof flags.up === "boolean");
+      assert(typeof flags.loopback === "boolean");
+      assert(typeof flags.multicast === "boolean");
+      assert(mtu === null || (Number.isInteger(mtu) && mtu > 0));
     }
+    // the loopback interface is always there
+    assert(networkInterfaces.some(({ flags }) => flags.loopback));
+    const names = networkInterfaces.map(({ name }) => name);
+    assertEquals(names, [...names].sort());
   },
 );
@@ -184,3 +184,108 @@ Deno.test(function performanceIsEventTarget() {
     performance.dispatchEvent(new Event("test"));
   });
 });
+
+Deno.test(async function performanceObserver() {
+  const observed = deferred<PerformanceEntryList>();
+  const observer = new PerformanceObserver((list, obs) => {
+    assert(obs === observer);
+    observed.resolve(list.getEntries());
+  });
+  observer.observe({ entryTypes: ["mark", "measure", "unknown"] });
+  performance.mark("observed");
+  performance.measure("observed measure", "observed");
+  // the observers are notified from an unref'd timer
+  await new Promise((resolve) => setTimeout(resolve, 10));
+  const entries = await observed;
+  observer.disconnect();
+  assertEquals(
+    entries.map((entry) => [entry.name, entry.entryType]),
+    [["observed", "mark"], ["observed measure", "measure"]],
+  );
+  performance.clearMarks("observed");
+  performance.clearMeasures("observed measure");
+});
+
+Deno.test(async function performanceObserverBuffered() {
+  performance.mark("buffered");
+  const observed = deferred<PerformanceEntryList>();
+  const observer = new PerformanceObserver((list) => {
+    observed.resolve(list.getEntriesByName("buffered"));
+  });
+  observer.observe({ type: "mark", buffered: true });
+  await new Promise((resolve) => setTimeout(resolve, 10));
+  const entries = await observed;
+  observer.disconnect();
+  assertEquals(entries.length, 1);
+  assertEquals(entries[0].entryType, "mark");
+  performance.clearMarks("buffered");
+});
+
+Deno.test(async function performanceObserverTakeRecords() {
+  const observer = new PerformanceObserver(() => {
+    throw new Error("unreachable");
+  });
+  observer.observe({ type: "mark" });
+  performance.mark("taken");
+  const entries = observer.takeRecords();
+  assertEquals(entries.map((entry) => entry.name), ["taken"]);
+  assertEquals(observer.takeRecords(), []);
+  observer.disconnect();
+  performance.clearMarks("taken");
+  // let the task that was queued for the observer run
+  await new Promise((resolve) => setTimeout(resolve, 0));
+});
+
+Deno.test(function performanceObserverInvalidOptions() {
+  const observer = new PerformanceObserver(() => {});
+  assertThrows(() => observer.observe(), TypeError);
+  assertThrows(
+    () => observer.observe({ entryTypes: ["mark"], type: "mark" }),
+    TypeError,
+  );
+  observer.observe({ type: "mark" });
+  assertThrows(
+    () => observer.observe({ entryTypes: ["measure"] }),
+    DOMException,
+  );
+  observer.disconnect();
+  // node:perf_hooks adds the types of its own entries
+  for (const type of ["mark", "measure", "resource"]) {
+    assert(PerformanceObserver.supportedEntryTypes.includes(type));
+  }
+  assertThrows(
+    () => new PerformanceObserverEntryList(),
+    TypeError,
+    "Illegal constructor",
+  );
+});
+
+Deno.test(
+  { permissions: { net: true } },
+  async function performanceResourceTiming() {
+    const url = "http://localhost:4545/assets/fixture.json";
+    const observed = deferred<PerformanceEntryList>();
+    const observer = new PerformanceObserver((list) => {
+      observed.resolve(list.getEntriesByType("resource"));
+    });
+    observer.observe({ type: "resource" });
+    const response = await fetch(url);
+    await response.body?.cancel();
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    const entries = await observed;
+    observer.disconnect();
+
+    const entry = entries[0] as PerformanceResourceTiming;
+    assert(entry instanceof PerformanceResourceTiming);
+    assertEquals(entry.name, url);
+    assertEquals(entry.initiatorType, "fetch");
+    assertEquals(entry.responseStatus, 200);
+    assert(entry.fetchStart === entry.startTime);
+    assert(entry.responseStart >= entry.fetchStart);
+    assert(entry.responseEnd >= entry.responseStart);
+    assert(performance.getEntriesByType("resource").includes(entry));
+
+    performance.clearResourceTimings();
+    assertEquals(performance.getEntriesByType("resource"), []);
+  },
+);
@@ -167,6 +167,43 @@ Deno.test({ permissions: { net: true } }, async function httpServerBasic() {
   await server;
 });
 
+Deno.test(
+  { permissions: { net: true, run: true, read: true } },
+  async function httpServerChildStdoutBody() {
+    const ac = new AbortController();
+    const listeningPromise = deferred();
+
+    const server = Deno.serve({
+      handler: () => {
+        const child = new Deno.Command(Deno.execPath(), {
+          args: [
+            "eval",
+            "const b = new Uint8Array(1024 * 1024).fill(97); let n = 0; " +
+              "while (n < b.length) " +
+              "n += await Deno.stdout.write(b.subarray(n));",
+          ],
+          stdout: "piped",
+          stderr: "null",
+        }).spawn();
+        return new Response(child.stdout);
+      },
+      port: servePort,
+      signal: ac.signal,
+      onListen: onListen(listeningPromise),
+      onError: createOnErrorCb(ac),
+    });
+
+    await listeningPromise;
+    const resp = await fetch(`http://127.0.0.1:${servePort}/`, {
+      headers: { "connection": "close" },
+    });
+    const body = await resp.arrayBuffer();
+    assertEquals(body.byteLength, 1024 * 1024);
+    ac.abort();
+    await server;
+  },
+);
+
 // Test serving of HTTP on an arbitrary listener.
 Deno.test(
   { permissions: { net: true } },
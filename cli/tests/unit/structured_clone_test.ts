@@ -53,3 +53,32 @@ Deno.test("correct DataCloneError message", () => {
   // ab2 should not be detached after above failure
   structuredClone(ab2, { transfer: [ab2] });
 });
+
+Deno.test("structuredClone errors", () => {
+  const cause = { reason: "io" };
+  const error = new RangeError("outer", { cause });
+  const cloned = structuredClone({ error, cause });
+  assert(cloned.error instanceof RangeError);
+  assertEquals(cloned.error.message, "outer");
+  assertEquals(cloned.error.stack, error.stack);
+  assert(cloned.error.cause === cloned.cause);
+  assertEquals(cloned.error.cause, { reason: "io" });
+
+  const errorWithoutCause = structuredClone(new Error("plain"));
+  assert(!("cause" in errorWithoutCause));
+});
+
+Deno.test("MessageChannel errors", async () => {
+  const { port1, port2 } = new MessageChannel();
+  const promise = new Promise<MessageEvent>((resolve) => {
+    port2.onmessage = resolve;
+  });
+  port1.postMessage(new Error("outer", { cause: new SyntaxError("inner") }));
+  const { data } = await promise;
+  assert(data instanceof Error);
+  assertEquals(data.message, "outer");
+  assert(data.cause instanceof SyntaxError);
+  assertEquals(data.cause.message, "inner");
+  port1.close();
+  port2.close();
+});
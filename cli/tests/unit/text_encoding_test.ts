@@ -65,6 +65,70 @@ Deno.test(function atobThrows3() {
   assert(threw);
 });
 
+Deno.test(function uint8ArrayFromBase64() {
+  assertEquals(
+    Uint8Array.fromBase64("aGVs bG8=\n"),
+    new TextEncoder().encode("hello"),
+  );
+  assertEquals(
+    Uint8Array.fromBase64("_-8", { alphabet: "base64url" }),
+    new Uint8Array([0xff, 0xef]),
+  );
+  assertEquals(
+    Uint8Array.fromBase64("aGVsbA"),
+    new Uint8Array([104, 101, 108, 108]),
+  );
+  assertEquals(
+    Uint8Array.fromBase64("aGVsbA", {
+      lastChunkHandling: "stop-before-partial",
+    }),
+    new Uint8Array([104, 101, 108]),
+  );
+  assertThrows(
+    () => Uint8Array.fromBase64("aGVsbA", { lastChunkHandling: "strict" }),
+    SyntaxError,
+  );
+  assertThrows(() => Uint8Array.fromBase64("aGVsbG8!"), SyntaxError);
+  assertThrows(() => Uint8Array.fromBase64("_-8"), SyntaxError);
+  assertThrows(
+    () => Uint8Array.fromBase64("aGVs", { alphabet: "base32" }),
+    TypeError,
+  );
+});
+
+Deno.test(function uint8ArrayToBase64() {
+  const bytes = new Uint8Array([0xff, 0xef]);
+  assertEquals(bytes.toBase64(), "/+8=");
+  assertEquals(bytes.toBase64({ alphabet: "base64url" }), "_-8=");
+  assertEquals(bytes.toBase64({ omitPadding: true }), "/+8");
+  assertEquals(new Uint8Array().toBase64(), "");
+});
+
+Deno.test(function uint8ArraySetFromBase64() {
+  const input = btoa("hello world, in chunks");
+  const decoder = new TextDecoder();
+  const buffer = new Uint8Array(5);
+  let output = "";
+  let pending = "";
+  for (let i = 0; i < input.length; i += 7) {
+    pending += input.slice(i, i + 7);
+    while (true) {
+      const { read, written } = buffer.setFromBase64(pending, {
+        lastChunkHandling: "stop-before-partial",
+      });
+      output += decoder.decode(buffer.subarray(0, written));
+      pending = pending.slice(read);
+      if (written === 0) break;
+    }
+  }
+  assertEquals(pending, "");
+  assertEquals(output, "hello world, in chunks");
+
+  const { read, written } = buffer.setFromBase64("aGVsbG8gd29ybGQ=");
+  assertEquals([read, written], [4, 3]);
+  assertEquals(decoder.decode(buffer.subarray(0, written)), "hel");
+});
+
 Deno.test(function btoaFailed() {
   const text = "你好";
   assertThrows(() => {
@@ -92,6 +156,21 @@ Deno.test(function textDecoderASCII() {
   assertEquals(decoder.decode(fixture), "‰•Ÿ¿");
 });
 
+Deno.test(function textDecoderSingleByte() {
+  const fixture = new Uint8Array([0x63, 0x61, 0x66, 0xe9, 0x80, 0xaa]);
+  assertEquals(new TextDecoder("latin1").decode(fixture), "café€ª");
+  assertEquals(new TextDecoder("koi8-r").decode(fixture), "cafИ─╙");
+  // 0xaa isn't mapped by windows-1253
+  assertEquals(
+    new TextDecoder("windows-1253").decode(fixture),
+    "cafι€\ufffd",
+  );
+  assertThrows(
+    () => new TextDecoder("windows-1253", { fatal: true }).decode(fixture),
+    TypeError,
+  );
+});
+
 Deno.test(function textDecoderErrorEncoding() {
   let didThrow = false;
   try {
@@ -214,6 +293,22 @@ Deno.test(function swappedSurrogatePairEncodeInto4() {
   ]);
 });
 
+Deno.test(function twoByteStringEncodeInto() {
+  const fixture = "añ€𝓽\ud800b".repeat(3);
+  const encoder = new TextEncoder();
+  const expected = encoder.encode(fixture);
+  for (let size = 0; size <= expected.length + 1; size++) {
+    const bytes = new Uint8Array(size);
+    const { read, written } = encoder.encodeInto(fixture, bytes);
+    // only whole code points are written
+    assertEquals(
+      bytes.subarray(0, written),
+      encoder.encode(fixture.slice(0, read)),
+    );
+    assert(expected.length - written < 4 || size - written < 4);
+  }
+});
+
 Deno.test(function textDecoderSharedUint8Array() {
   const ab = new SharedArrayBuffer(6);
   const dataView = new DataView(ab);
@@ -240,6 +335,27 @@ Deno.test(function textDecoderSharedInt32Array() {
   assertEquals(actual, "ABCDEFGH");
 });
 
+Deno.test(function textDecoderStreamChunks() {
+  const text = "a".repeat(1000) + "σ😀";
+  const bytes = new TextEncoder().encode(text);
+  const decoder = new TextDecoder();
+  // a big chunk followed by small ones, which split the multi-byte
+  // characters and must not see what the big one left in the buffer
+  let decoded = decoder.decode(bytes.subarray(0, 1001), { stream: true });
+  for (let i = 1001; i < bytes.length; i++) {
+    decoded += decoder.decode(bytes.subarray(i, i + 1), { stream: true });
+  }
+  decoded += decoder.decode();
+  assertEquals(decoded, text);
+
+  const fatal = new TextDecoder("utf-8", { fatal: true });
+  assertEquals(
+    fatal.decode(bytes.subarray(0, 1002), { stream: true }),
+    "a".repeat(1000),
+  );
+  assertThrows(() => fatal.decode(new Uint8Array([0xff]), { stream: true }));
+});
+
 Deno.test(function toStringShouldBeWebCompatibility() {
   const encoder = new TextEncoder();
   assertEquals(encoder.toString(), "[object TextEncoder]");
@@ -3,6 +3,8 @@ import {
   assert,
   assertEquals,
   assertNotEquals,
+  assertRejects,
+  assertThrows,
   Deferred,
   deferred,
   delay,
@@ -249,6 +251,31 @@ Deno.test(async function callbackTakesLongerThanInterval() {
   await promise;
 });
 
+Deno.test(async function intervalDoesNotDriftWithSlowCallbacks() {
+  const promise = deferred();
+
+  const start = performance.now();
+  const ticks: number[] = [];
+  const interval = setInterval(() => {
+    ticks.push(performance.now() - start);
+    if (ticks.length === 4) {
+      clearInterval(interval);
+      promise.resolve();
+      return;
+    }
+    // Each period is counted from the previous deadline, not from the end of
+    // this callback.
+    Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, 50);
+  }, 100);
+
+  await promise;
+  // The fourth tick is due at 400ms. Had each period been counted from the
+  // end of the previous callback, it would only be due at 550ms, so the
+  // tolerance leaves room for a late wakeup without hiding the drift.
+  assert(ticks[3] > 390, `ticks at ${ticks.join(", ")}ms`);
+  assert(ticks[3] < 520, `ticks at ${ticks.join(", ")}ms`);
+});
+
 // https://github.com/denoland/deno/issues/11398
 Deno.test(async function clearTimeoutAfterNextTimerIsDue1() {
   const promise = deferred();
@@ -727,3 +754,106 @@ Deno.test({
     assertEquals(output, "");
   },
 });
+
+Deno.test(async function schedulerPostTaskRunsByPriority() {
+  const order: string[] = [];
+  await Promise.all([
+    scheduler.postTask(() => order.push("background"), {
+      priority: "background",
+    }),
+    scheduler.postTask(() => order.push("user-visible")),
+    scheduler.postTask(() => order.push("user-blocking"), {
+      priority: "user-blocking",
+    }),
+  ]);
+  assertEquals(order, ["user-blocking", "user-visible", "background"]);
+
+  assertEquals(await scheduler.postTask(() => 42), 42);
+  await assertRejects(
+    () =>
+      scheduler.postTask(() => {
+        throw new Error("boom");
+      }),
+    Error,
+    "boom",
+  );
+});
+
+Deno.test(async function schedulerPostTaskDelay() {
+  const order: string[] = [];
+  await Promise.all([
+    scheduler.postTask(() => order.push("delayed"), {
+      priority: "user-blocking",
+      delay: 20,
+    }),
+    scheduler.postTask(() => order.push("background"), {
+      priority: "background",
+    }),
+  ]);
+  assertEquals(order, ["background", "delayed"]);
+});
+
+Deno.test(async function schedulerPostTaskAbort() {
+  const controller = new AbortController();
+  controller.abort();
+  await assertRejects(
+    () => scheduler.postTask(() => {}, { signal: controller.signal }),
+    DOMException,
+  );
+
+  const delayed = new TaskController();
+  let ran = false;
+  const promise = scheduler.postTask(() => {
+    ran = true;
+  }, { signal: delayed.signal, delay: 1000 });
+  delayed.abort();
+  await assertRejects(() => promise, DOMException);
+  assert(!ran);
+});
+
+Deno.test(async function schedulerTaskControllerPriority() {
+  const controller = new TaskController({ priority: "background" });
+  assert(controller.signal instanceof TaskSignal);
+  assertEquals(controller.signal.priority, "background");
+
+  const order: string[] = [];
+  const promises = [
+    scheduler.postTask(() => order.push("signal"), {
+      signal: controller.signal,
+    }),
+    scheduler.postTask(() => order.push("user-visible")),
+  ];
+  const events: TaskPriorityChangeEvent[] = [];
+  controller.signal.onprioritychange = (event) => {
+    events.push(event);
+    assertThrows(
+      () => controller.setPriority("background"),
+      DOMException,
+      "already being changed",
+    );
+  };
+  controller.setPriority("user-blocking");
+  controller.setPriority("user-blocking");
+  await Promise.all(promises);
+  assertEquals(order, ["signal", "user-visible"]);
+  assertEquals(events.length, 1);
+  assertEquals(events[0].previousPriority, "background");
+  assertEquals(controller.signal.priority, "user-blocking");
+});
+
+Deno.test(async function schedulerYield() {
+  const order: string[] = [];
+  await scheduler.postTask(async () => {
+    order.push("task");
+    const other = scheduler.postTask(() => order.push("other"), {
+      priority: "background",
+    });
+    await scheduler.yield();
+    order.push("continuation");
+    await other;
+  }, { priority: "background" });
+  // the continuation inherits the priority of the task, and runs before the
+  // other tasks of that priority
+  assertEquals(order, ["task", "continuation", "other"]);
+  assertThrows(() => new Scheduler(), TypeError);
+});
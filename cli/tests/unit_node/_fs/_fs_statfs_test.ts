@@ -0,0 +1,51 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "../../../../test_util/std/testing/asserts.ts";
+import { promises, statfs, statfsSync } from "node:fs";
+
+Deno.test({
+  name: "statfsSync returns the statistics of the file system",
+  fn() {
+    const stats = statfsSync(Deno.cwd());
+    assertEquals(typeof stats.bsize, "number");
+    assert(stats.bsize > 0);
+    assert(stats.blocks > 0);
+    assert(stats.bavail <= stats.blocks);
+
+    const bigintStats = statfsSync(Deno.cwd(), { bigint: true });
+    assertEquals(typeof bigintStats.bsize, "bigint");
+    assertEquals(bigintStats.bsize, BigInt(stats.bsize));
+  },
+});
+
+Deno.test({
+  name: "statfs returns the statistics of the file system",
+  async fn() {
+    const stats = await new Promise<{ bsize: number }>((resolve, reject) => {
+      statfs(Deno.cwd(), (err, stats) => {
+        if (err) reject(err);
+        else resolve(stats!);
+      });
+    });
+    assert(stats.bsize > 0);
+
+    const promiseStats = await promises.statfs(Deno.cwd());
+    assertEquals(promiseStats.bsize, stats.bsize);
+  },
+});
+
+Deno.test({
+  name: "statfs fails for a path that doesn't exist",
+  async fn() {
+    assertThrows(
+      () => statfsSync("does/not/exist"),
+      Error,
+      "statfs",
+    );
+    await assertRejects(() => promises.statfs("does/not/exist"), Error);
+  },
+});
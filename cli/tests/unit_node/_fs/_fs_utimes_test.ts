@@ -4,7 +4,7 @@ import {
   assertThrows,
   fail,
 } from "../../../../test_util/std/testing/asserts.ts";
-import { utimes, utimesSync } from "node:fs";
+import { lutimes, lutimesSync, utimes, utimesSync } from "node:fs";
 
 const randomDate = new Date(Date.now() + 1000);
 
@@ -102,3 +102,33 @@ Deno.test({
     );
   },
 });
+
+Deno.test({
+  name: "change the timestamps of a symlink instead of its target",
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    const dir = Deno.makeTempDirSync();
+    const target = dir + "/target";
+    const link = dir + "/link";
+    Deno.writeTextFileSync(target, "");
+    Deno.symlinkSync(target, link);
+    const targetMtime = Deno.statSync(target).mtime;
+
+    try {
+      lutimesSync(link, randomDate, randomDate);
+      assertEquals(Deno.lstatSync(link).mtime, randomDate);
+
+      const otherDate = new Date(randomDate.getTime() + 1500);
+      await new Promise<void>((resolve, reject) => {
+        lutimes(link, otherDate, otherDate, (err: Error | null) => {
+          if (err !== null) reject(err);
+          else resolve();
+        });
+      });
+      assertEquals(Deno.lstatSync(link).mtime, otherDate);
+      assertEquals(Deno.statSync(target).mtime, targetMtime);
+    } finally {
+      Deno.removeSync(dir, { recursive: true });
+    }
+  },
+});
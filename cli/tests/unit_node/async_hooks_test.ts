@@ -81,3 +81,78 @@ Deno.test(async function nested() {
   assertEquals(await promise, { x: 1 });
   assertEquals(await promise1, null);
 });
+
+Deno.test(async function enterWith() {
+  const als = new AsyncLocalStorage();
+  const promise = deferred();
+  const promise1 = deferred();
+
+  als.run(null, () => {
+    als.enterWith({ x: 1 });
+    promise.resolve(als.getStore());
+  });
+
+  als.run(null, () => {
+    promise1.resolve(als.getStore());
+  });
+
+  assertEquals(await promise, { x: 1 });
+  assertEquals(await promise1, null);
+});
+
+Deno.test(function exitPassesArguments() {
+  const als = new AsyncLocalStorage();
+  const value = als.run(
+    1,
+    () => als.exit((a, b) => [a, b, als.getStore()], 2, 3),
+  );
+  assertEquals(value, [2, 3, undefined]);
+});
+
+Deno.test(async function contextIsNotVisibleOnPromise() {
+  const als = new AsyncLocalStorage();
+  await als.run(1, async () => {
+    const p = Promise.resolve();
+    assertEquals(Object.getOwnPropertySymbols(p), []);
+    await p;
+    assertEquals(als.getStore(), 1);
+  });
+});
+
+Deno.test(async function contextIsKeptByTimersAndMicrotasks() {
+  const als = new AsyncLocalStorage();
+  const timeout = deferred();
+  const interval = deferred();
+  const microtask = deferred();
+
+  als.run("store", () => {
+    setTimeout(() => timeout.resolve(als.getStore()), 0);
+    const id = setInterval(() => {
+      clearInterval(id);
+      interval.resolve(als.getStore());
+    }, 0);
+    queueMicrotask(() => microtask.resolve(als.getStore()));
+  });
+  assertEquals(als.getStore(), undefined);
+
+  assertEquals(await timeout, "store");
+  assertEquals(await interval, "store");
+  assertEquals(await microtask, "store");
+});
+
+Deno.test(async function contextIsKeptByEventListeners() {
+  const als = new AsyncLocalStorage();
+  const target = new EventTarget();
+  const stores: unknown[] = [];
+  als.run("added", () => {
+    target.addEventListener("foo", () => stores.push(als.getStore()));
+  });
+
+  // dispatched outside of any context, like the runtime does it
+  await new Promise((resolve) => setTimeout(resolve, 0));
+  target.dispatchEvent(new Event("foo"));
+  // the context of the dispatch takes precedence
+  als.run("dispatched", () => target.dispatchEvent(new Event("foo")));
+
+  assertEquals(stores, ["added", "dispatched"]);
+});
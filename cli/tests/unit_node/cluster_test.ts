@@ -0,0 +1,50 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+import { assertEquals } from "../../../test_util/std/testing/asserts.ts";
+import * as path from "../../../test_util/std/path/mod.ts";
+import cluster from "node:cluster";
+
+const script = path.join(
+  path.dirname(path.fromFileUrl(import.meta.url)),
+  "testdata",
+  "cluster_server.js",
+);
+
+/** Runs a primary with two workers, returning the ids of the workers that
+ * four connections from the same address went to. */
+async function runCluster(policy: string): Promise<string[]> {
+  const { code, stdout, stderr } = await new Deno.Command(Deno.execPath(), {
+    args: ["run", "-A", script],
+    env: { NODE_CLUSTER_SCHED_POLICY: policy, NO_COLOR: "1" },
+  }).output();
+  const decoder = new TextDecoder();
+  assertEquals(code, 0, decoder.decode(stderr));
+  const [ids, done] = decoder.decode(stdout).trim().split("\n");
+  assertEquals(done, "done");
+  return ids.split(",");
+}
+
+Deno.test("[node/cluster] the current process is the primary", () => {
+  assertEquals(cluster.isPrimary, true);
+  assertEquals(cluster.isWorker, false);
+  assertEquals(cluster.worker, undefined);
+  assertEquals(cluster.workers, {});
+});
+
+Deno.test({
+  name: "[node/cluster] sticky sessions keep a client on the same worker",
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    const ids = await runCluster("sticky");
+    assertEquals(ids.length, 4);
+    assertEquals(new Set(ids).size, 1);
+  },
+});
+
+Deno.test({
+  name: "[node/cluster] round-robin passes connections to workers in turn",
+  ignore: Deno.build.os === "windows",
+  async fn() {
+    assertEquals(await runCluster("rr"), ["1", "2", "1", "2"]);
+  },
+});
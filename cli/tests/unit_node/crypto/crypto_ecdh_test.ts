@@ -0,0 +1,79 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { createECDH, ECDH, getCurves } from "node:crypto";
+import { Buffer } from "node:buffer";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../../test_util/std/testing/asserts.ts";
+
+// The generator point of secp256k1, the public key of the private key 1
+const secp256k1G =
+  "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const secp256k1GY =
+  "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+Deno.test("[node/crypto.ECDH] convertKey", () => {
+  const uncompressed = "04" + secp256k1G + secp256k1GY;
+  assertEquals(
+    ECDH.convertKey(uncompressed, "secp256k1", "hex", "hex", "compressed"),
+    "02" + secp256k1G,
+  );
+  assertEquals(
+    ECDH.convertKey("02" + secp256k1G, "secp256k1", "hex", "hex"),
+    uncompressed,
+  );
+  assertEquals(
+    ECDH.convertKey("02" + secp256k1G, "secp256k1", "hex", "hex", "hybrid"),
+    "06" + secp256k1G + secp256k1GY,
+  );
+  const converted = ECDH.convertKey(
+    Buffer.from("06" + secp256k1G + secp256k1GY, "hex"),
+    "secp256k1",
+  );
+  assert(converted instanceof Buffer);
+  assertEquals(converted.toString("hex"), uncompressed);
+
+  assertThrows(
+    () => ECDH.convertKey("02" + secp256k1G, "secp256k1", "hex", "hex", "x"),
+    TypeError,
+    "Invalid ECDH format: x",
+  );
+  assertThrows(
+    () => ECDH.convertKey("0201", "secp256k1", "hex"),
+    Error,
+    "Public key is not valid for specified curve",
+  );
+});
+
+Deno.test("[node/crypto.ECDH] getPublicKey formats", () => {
+  const ecdh = createECDH("secp256k1");
+  ecdh.setPrivateKey(Buffer.from("01".padStart(64, "0"), "hex"));
+  assertEquals(
+    ecdh.getPublicKey("hex"),
+    "04" + secp256k1G + secp256k1GY,
+  );
+  assertEquals(ecdh.getPublicKey("hex", "compressed"), "02" + secp256k1G);
+});
+
+for (const curve of ["prime256v1", "secp384r1", "secp224r1"]) {
+  Deno.test(`[node/crypto.ECDH] ${curve}`, () => {
+    assert(getCurves().includes(curve));
+    const alice = createECDH(curve);
+    const bob = createECDH(curve);
+    const alicePublicKey = alice.generateKeys(undefined, "compressed");
+    const bobPublicKey = bob.generateKeys();
+    assertEquals(
+      alice.computeSecret(bobPublicKey),
+      bob.computeSecret(alicePublicKey),
+    );
+    assertEquals(
+      ECDH.convertKey(alicePublicKey, curve, undefined, "hex"),
+      alice.getPublicKey("hex"),
+    );
+  });
+}
+
+Deno.test("[node/crypto.ECDH] unknown curve", () => {
+  assertThrows(() => createECDH("secp0r1"), Error, "invalid curve");
+});
@@ -2,15 +2,18 @@
 
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import {
+  createCipheriv,
   createSecretKey,
   generateKeyPair,
   generateKeyPairSync,
+  hkdfSync,
   KeyObject,
   randomBytes,
 } from "node:crypto";
 import { promisify } from "node:util";
 import { Buffer } from "node:buffer";
 import {
+  assert,
   assertEquals,
   assertThrows,
 } from "../../../../test_util/std/testing/asserts.ts";
@@ -76,6 +79,135 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "derive a secret key with hkdf",
+  fn() {
+    const key = createSecretKey(
+      new Uint8Array(hkdfSync("sha256", "secret", "salt", "info", 32)),
+    );
+    assertEquals(
+      Buffer.from(key.export()).toString("hex"),
+      "f6d2fcc47cb939deafe3853a1e641a27e6924aff7a63d09cb04ccfffbe4776ef",
+    );
+    assertEquals(
+      createHmac("sha256", key).update("hello").digest("hex"),
+      "2798694ed903d62e0d3cc17d71841d81dd3e03557761ed22cf114d6931760a9e",
+    );
+
+    // the derived key can be derived from in turn
+    assertEquals(
+      Buffer.from(hkdfSync("sha256", key, "salt", "info", 16)),
+      Buffer.from(hkdfSync("sha256", key.export(), "salt", "info", 16)),
+    );
+
+    const iv = Buffer.alloc(16);
+    const encrypt = (key: KeyObject | Buffer) => {
+      const cipher = createCipheriv("aes-256-cbc", key, iv);
+      return Buffer.concat([cipher.update("hello"), cipher.final()]);
+    };
+    assertEquals(encrypt(key), encrypt(key.export()));
+  },
+});
+
+Deno.test({
+  name: "hkdf with a public key",
+  fn() {
+    const { publicKey } = generateKeyPairSync("ed25519");
+    assertThrows(
+      () => hkdfSync("sha256", publicKey, "salt", "info", 32),
+      TypeError,
+      "Invalid key object type public, expected secret.",
+    );
+  },
+});
+
+Deno.test({
+  name: "hkdf key length",
+  fn() {
+    assertEquals(hkdfSync("sha1", "a", "b", "c", 20 * 255).byteLength, 5100);
+    const err = assertThrows(
+      () => hkdfSync("sha1", "a", "b", "c", 20 * 255 + 1),
+      RangeError,
+      "Invalid key length",
+    ) as any;
+    assertEquals(err.code, "ERR_CRYPTO_INVALID_KEYLEN");
+  },
+});
+
+Deno.test({
+  name: "KeyObject from secret CryptoKey",
+  async fn() {
+    const cryptoKey = await crypto.subtle.importKey(
+      "raw",
+      new TextEncoder().encode("secret"),
+      { name: "HMAC", hash: "SHA-256" },
+      false,
+      ["sign"],
+    );
+    const key = KeyObject.from(cryptoKey);
+    assertEquals(key.type, "secret");
+    assertEquals(Buffer.from(key.export()), Buffer.from("secret"));
+  },
+});
+
+Deno.test({
+  name: "KeyObject from asymmetric CryptoKey",
+  async fn() {
+    const { publicKey, privateKey } = await crypto.subtle.generateKey(
+      { name: "ECDSA", namedCurve: "P-256" },
+      false,
+      ["sign", "verify"],
+    );
+    const key = KeyObject.from(publicKey);
+    assertEquals(key.type, "public");
+    assertEquals(key.asymmetricKeyType, "ec");
+    assertEquals(key.asymmetricKeyDetails, { namedCurve: "prime256v1" });
+    assertEquals(
+      key.export({ type: "spki", format: "der" }),
+      Buffer.from(await crypto.subtle.exportKey("spki", publicKey)),
+    );
+    assertEquals(KeyObject.from(privateKey).type, "private");
+  },
+});
+
+Deno.test({
+  name: "subtle.importKey of KeyObjects",
+  async fn() {
+    const { publicKey, privateKey } = generateKeyPairSync("ed25519");
+    const signingKey = await crypto.subtle.importKey(
+      "pkcs8",
+      privateKey as any,
+      "Ed25519",
+      false,
+      ["sign"],
+    );
+    const verifyingKey = await crypto.subtle.importKey(
+      "spki",
+      publicKey as any,
+      "Ed25519",
+      false,
+      ["verify"],
+    );
+    const data = new TextEncoder().encode("hello");
+    const signature = await crypto.subtle.sign("Ed25519", signingKey, data);
+    assert(
+      await crypto.subtle.verify("Ed25519", verifyingKey, signature, data),
+    );
+
+    const hmacKey = await crypto.subtle.importKey(
+      "raw",
+      createSecretKey(Buffer.from("secret")) as any,
+      { name: "HMAC", hash: "SHA-256" },
+      true,
+      ["sign"],
+    );
+    assertEquals(
+      new Uint8Array(await crypto.subtle.exportKey("raw", hmacKey)),
+      new TextEncoder().encode("secret"),
+    );
+  },
+});
+
 for (const type of ["rsa", "rsa-pss", "dsa"]) {
   for (const modulusLength of [2048, 3072]) {
     Deno.test({
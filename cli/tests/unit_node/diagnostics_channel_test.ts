@@ -0,0 +1,120 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import diagnosticsChannel from "node:diagnostics_channel";
+import { AsyncLocalStorage } from "node:async_hooks";
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
+import { deferred } from "../../../test_util/std/async/deferred.ts";
+
+Deno.test("[node/diagnostics_channel] bindStore and runStores", () => {
+  const channel = diagnosticsChannel.channel("test:run-stores");
+  const store = new AsyncLocalStorage();
+  const messages: unknown[] = [];
+  channel.subscribe((message) => messages.push(message));
+  channel.bindStore(store, (message) => ({ wrapped: message }));
+  assert(channel.hasSubscribers);
+
+  const result = channel.runStores(1, function (this: unknown, a, b) {
+    assertEquals(store.getStore(), { wrapped: 1 });
+    assertEquals(this, "this");
+    return [a, b];
+  }, "this", 2, 3);
+  assertEquals(result, [2, 3]);
+  assertEquals(messages, [1]);
+  assertEquals(store.getStore(), undefined);
+
+  assert(channel.unbindStore(store));
+  assert(!channel.unbindStore(store));
+});
+
+Deno.test("[node/diagnostics_channel] traceSync", () => {
+  const tracing = diagnosticsChannel.tracingChannel("test:sync");
+  assertEquals(tracing.start.name, "tracing:test:sync:start");
+  const events: string[] = [];
+  const handlers = {
+    start: () => events.push("start"),
+    end: () => events.push("end"),
+    error: () => events.push("error"),
+  };
+  tracing.subscribe(handlers);
+
+  const context: Record<string, unknown> = {};
+  assertEquals(tracing.traceSync(() => 42, context), 42);
+  assertEquals(context.result, 42);
+  assertThrows(() =>
+    tracing.traceSync(() => {
+      throw new Error("boom");
+    })
+  );
+  assertEquals(events, ["start", "end", "start", "error", "end"]);
+
+  assert(tracing.unsubscribe(handlers));
+  assert(!tracing.unsubscribe(handlers));
+});
+
+Deno.test("[node/diagnostics_channel] tracePromise", async () => {
+  const tracing = diagnosticsChannel.tracingChannel("test:promise");
+  const events: string[] = [];
+  tracing.subscribe({
+    start: () => events.push("start"),
+    end: () => events.push("end"),
+    asyncStart: () => events.push("asyncStart"),
+    asyncEnd: () => events.push("asyncEnd"),
+    error: () => events.push("error"),
+  });
+
+  const context: Record<string, unknown> = {};
+  assertEquals(await tracing.tracePromise(async () => 1, context), 1);
+  assertEquals(context.result, 1);
+  await assertRejects(() =>
+    tracing.tracePromise(() => Promise.reject(new Error("boom")))
+  );
+  assertEquals(events, [
+    "start",
+    "end",
+    "asyncStart",
+    "asyncEnd",
+    "start",
+    "end",
+    "error",
+    "asyncStart",
+    "asyncEnd",
+  ]);
+});
+
+Deno.test("[node/diagnostics_channel] traceCallback", async () => {
+  const tracing = diagnosticsChannel.tracingChannel("test:callback");
+  const store = new AsyncLocalStorage();
+  tracing.start.bindStore(store, () => "start");
+  tracing.asyncStart.bindStore(store, () => "asyncStart");
+  const events: string[] = [];
+  tracing.subscribe({
+    start: () => events.push("start"),
+    end: () => events.push("end"),
+    asyncStart: () => events.push("asyncStart"),
+    asyncEnd: () => events.push("asyncEnd"),
+  });
+
+  const done = deferred<unknown>();
+  const context: Record<string, unknown> = {};
+  tracing.traceCallback(
+    (value: unknown, callback: unknown) => {
+      assertEquals(store.getStore(), "start");
+      setTimeout(() => (callback as (...args: unknown[]) => void)(null, value));
+    },
+    -1,
+    context,
+    undefined,
+    "value",
+    (_err: unknown, value: unknown) => {
+      assertEquals(store.getStore(), "asyncStart");
+      done.resolve(value);
+    },
+  );
+  assertEquals(await done, "value");
+  assertEquals(context.result, "value");
+  assertEquals(events, ["start", "end", "asyncStart", "asyncEnd"]);
+});
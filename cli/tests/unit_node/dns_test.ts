@@ -0,0 +1,57 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+import dns from "node:dns";
+import {
+  assert,
+  assertEquals,
+  assertRejects,
+} from "../../../test_util/std/testing/asserts.ts";
+import { deferred } from "../../../test_util/std/async/deferred.ts";
+
+const { lookup } = dns.promises;
+
+Deno.test("[node/dns] lookup uses the hosts file", async () => {
+  // localhost is resolved by the hosts file, not by a DNS server
+  const { address, family } = await lookup("localhost", { family: 4 });
+  assertEquals(address, "127.0.0.1");
+  assertEquals(family, 4);
+});
+
+Deno.test("[node/dns] lookup with all", async () => {
+  const addresses = await lookup("localhost", { all: true, family: 4 });
+  assert(Array.isArray(addresses));
+  assert(addresses.length > 0);
+  for (const { address, family } of addresses) {
+    assertEquals(family, 4);
+    assert(address.startsWith("127."));
+  }
+});
+
+Deno.test("[node/dns] lookup orders IPv4 first unless verbatim", async () => {
+  const addresses = await lookup("localhost", { all: true, verbatim: false });
+  const families = addresses.map(({ family }) => family);
+  assertEquals(families, [...families].sort());
+});
+
+Deno.test("[node/dns] lookup of an IP address", async () => {
+  assertEquals(await lookup("::1", { all: true }), [
+    { address: "::1", family: 6 },
+  ]);
+});
+
+Deno.test("[node/dns] lookup of an unknown host", async () => {
+  await assertRejects(
+    () => lookup("nonexistent.invalid"),
+    Error,
+    "getaddrinfo ENOTFOUND nonexistent.invalid",
+  );
+});
+
+Deno.test("[node/dns] lookup with a callback", async () => {
+  const promise = deferred<[string, number]>();
+  dns.lookup("localhost", 4, (err, address, family) => {
+    assertEquals(err, null);
+    promise.resolve([address, family]);
+  });
+  assertEquals(await promise, ["127.0.0.1", 4]);
+});
@@ -102,3 +102,33 @@ Deno.test("[node/http2 server]", async () => {
 
   await new Promise((resolve) => server.close(resolve));
 });
+
+Deno.test("[node/http2 secure server] allowHTTP1", async () => {
+  const tlsTestdataDir = new URL("../testdata/tls/", import.meta.url);
+  const [key, cert, caCert] = await Promise.all(
+    ["localhost.key", "localhost.crt", "RootCA.pem"].map((file) =>
+      Deno.readTextFile(new URL(file, tlsTestdataDir))
+    ),
+  );
+  const server = http2.createSecureServer(
+    { key, cert, allowHTTP1: true },
+    (_req, res) => res.end("hello"),
+  );
+  const listening = deferred();
+  server.listen(0, () => listening.resolve());
+  await listening;
+  const port = (<net.AddressInfo> server.address()).port;
+
+  for (const http2 of [true, false]) {
+    const client = Deno.createHttpClient({
+      caCerts: [caCert],
+      http1: !http2,
+      http2,
+    });
+    const resp = await fetch(`https://localhost:${port}/`, { client });
+    assertEquals(await resp.text(), "hello");
+    client.close();
+  }
+
+  await new Promise((resolve) => server.close(resolve));
+});
@@ -6,6 +6,7 @@ import https from "node:https";
 import {
   assert,
   assertEquals,
+  assertThrows,
 } from "../../../test_util/std/testing/asserts.ts";
 import { assertSpyCalls, spy } from "../../../test_util/std/testing/mock.ts";
 import { deferred } from "../../../test_util/std/async/deferred.ts";
@@ -165,6 +166,194 @@ Deno.test("[node/http] empty chunk in the middle of response", async () => {
   await promise;
 });
 
+Deno.test("[node/http] server emits checkContinue", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer(() => {
+    throw new Error("'request' must not be emitted");
+  });
+  server.on("checkContinue", (req, res) => {
+    assertEquals(req.headers["expect"], "100-continue");
+    res.writeHead(413);
+    res.end();
+  });
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    const request = "POST / HTTP/1.1\r\nHost: localhost\r\n" +
+      "Content-Length: 4\r\nExpect: 100-continue\r\n\r\n";
+    await conn.write(new TextEncoder().encode(request));
+    const buf = new Uint8Array(1024);
+    const n = await conn.read(buf);
+    const response = new TextDecoder().decode(buf.subarray(0, n!));
+    assert(response.startsWith("HTTP/1.1 413 "), response);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server rejects unknown expectations", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer(() => {
+    throw new Error("'request' must not be emitted");
+  });
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const res = await fetch(`http://127.0.0.1:${port}/`, {
+      headers: { "expect": "something-else" },
+    });
+    assertEquals(res.status, 417);
+    await res.body?.cancel();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server closes idle connections", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer((_req, res) => {
+    res.end("ok");
+  });
+  server.keepAliveTimeout = 100;
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    const request = "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+    await conn.write(new TextEncoder().encode(request));
+    const buf = new Uint8Array(1024);
+    const n = await conn.read(buf);
+    const response = new TextDecoder().decode(buf.subarray(0, n!));
+    assert(response.startsWith("HTTP/1.1 200 "), response);
+    // the server closes the connection once the keep-alive timeout expires
+    assertEquals(await conn.read(buf), null);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server times out slow requests", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer({ requestTimeout: 200 }, (req, res) => {
+    req.resume();
+    req.on("end", () => res.end("ok"));
+  });
+  assertEquals(server.headersTimeout, 200);
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    // the body never arrives in full
+    const request =
+      "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 10\r\n\r\nab";
+    await conn.write(new TextEncoder().encode(request));
+    const buf = new Uint8Array(1024);
+    assertEquals(await conn.read(buf), null);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server answers 408 to slow headers", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer((_req, res) => {
+    res.end("ok");
+  });
+  server.headersTimeout = 100;
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    await conn.write(new TextEncoder().encode("GET / HTTP/1.1\r\n"));
+    const buf = new Uint8Array(1024);
+    const n = await conn.read(buf);
+    const response = new TextDecoder().decode(buf.subarray(0, n!));
+    assert(response.startsWith("HTTP/1.1 408 "), response);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server reports slow clients through clientError", async () => {
+  const promise = deferred<void>();
+  const clientError = deferred<[Error, { remotePort: number }]>();
+  const server = http.createServer((_req, res) => {
+    res.end("ok");
+  });
+  server.headersTimeout = 100;
+  server.on("clientError", (err, socket) => {
+    clientError.resolve([err, socket]);
+  });
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    await conn.write(new TextEncoder().encode("GET / HTTP/1.1\r\n"));
+    const [err, socket] = await clientError;
+    assertEquals(
+      (err as Error & { code: string }).code,
+      "ERR_HTTP_REQUEST_TIMEOUT",
+    );
+    assertEquals(socket.remotePort, (conn.localAddr as Deno.NetAddr).port);
+    // the listener handles the client, so it doesn't get a 408
+    const buf = new Uint8Array(1024);
+    assertEquals(await conn.read(buf), null);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server doesn't time out requests whose body was received", async () => {
+  const promise = deferred<void>();
+  const server = http.createServer({ requestTimeout: 200 }, (req, res) => {
+    // the body is only read once the request timeout expired
+    setTimeout(() => {
+      let body = "";
+      req.setEncoding("utf8");
+      req.on("data", (chunk) => body += chunk);
+      req.on("end", () => res.end(body));
+    }, 400);
+  });
+
+  server.listen(async () => {
+    const { port } = server.address() as { port: number };
+    const conn = await Deno.connect({ port });
+    const request =
+      "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Length: 10\r\n\r\n" +
+      "abcdefghij";
+    await conn.write(new TextEncoder().encode(request));
+    const buf = new Uint8Array(1024);
+    const n = await conn.read(buf);
+    const response = new TextDecoder().decode(buf.subarray(0, n!));
+    assert(response.startsWith("HTTP/1.1 200 "), response);
+    assert(response.endsWith("abcdefghij"), response);
+    conn.close();
+    server.close(() => promise.resolve());
+  });
+
+  await promise;
+});
+
+Deno.test("[node/http] server validates its timeouts", () => {
+  assertThrows(
+    () => http.createServer({ requestTimeout: 100, headersTimeout: 200 }),
+    RangeError,
+    'The value of "headersTimeout" is out of range.',
+  );
+});
+
 Deno.test("[node/http] server can respond with 101, 204, 205, 304 status", async () => {
   for (const status of [101, 204, 205, 304]) {
     const promise = deferred<void>();
@@ -3,6 +3,7 @@ import { pbkdf2, pbkdf2Sync } from "node:crypto";
 import {
   assert,
   assertEquals,
+  assertThrows,
 } from "../../../../test_util/std/testing/asserts.ts";
 
 type Algorithms =
@@ -380,6 +381,39 @@ Deno.test("pbkdf2Sync hashes data correctly", () => {
   });
 });
 
+Deno.test("pbkdf2Sync validates its arguments", () => {
+  const cases: [() => unknown, string][] = [
+    [() => pbkdf2Sync("a", "b", 0, 8, "sha1"), "ERR_OUT_OF_RANGE"],
+    [() => pbkdf2Sync("a", "b", 1.5, 8, "sha1"), "ERR_OUT_OF_RANGE"],
+    [() => pbkdf2Sync("a", "b", 1, -1, "sha1"), "ERR_OUT_OF_RANGE"],
+    [() => pbkdf2Sync("a", "b", 1, 2 ** 31, "sha1"), "ERR_OUT_OF_RANGE"],
+    // @ts-ignore the digest is required
+    [() => pbkdf2Sync("a", "b", 1, 8), "ERR_INVALID_ARG_TYPE"],
+    // @ts-ignore not a valid password
+    [() => pbkdf2Sync(1, "b", 1, 8, "sha1"), "ERR_INVALID_ARG_TYPE"],
+    // @ts-ignore not a known digest
+    [() => pbkdf2Sync("a", "b", 1, 8, "unknown"), "ERR_CRYPTO_INVALID_DIGEST"],
+    [
+      // @ts-ignore not a known digest
+      () => pbkdf2("a", "b", 1, 8, "unknown", () => {}),
+      "ERR_CRYPTO_INVALID_DIGEST",
+    ],
+    // @ts-ignore the callback is required
+    [() => pbkdf2("a", "b", 1, 8, "sha1"), "ERR_INVALID_ARG_TYPE"],
+  ];
+  for (const [fn, code] of cases) {
+    // deno-lint-ignore no-explicit-any
+    const err = assertThrows(fn) as any;
+    assertEquals(err.code, code);
+  }
+
+  // digests are case insensitive
+  assertEquals(
+    pbkdf2Sync("a", "b", 1, 8, "SHA256" as "sha256"),
+    pbkdf2Sync("a", "b", 1, 8, "sha256"),
+  );
+});
+
 // TODO(@littledivy): assertCallbackErrorUncaught exits for async operations on the thread pool.
 // Deno.test("[std/node/crypto] pbkdf2 callback isn't called twice if error is thrown", async () => {
 //   const importUrl = new URL("node:crypto", import.meta.url);
@@ -1,6 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import { Module } from "node:module";
+import { createRequire, Module } from "node:module";
 import {
   assert,
   assertEquals,
@@ -58,3 +58,75 @@ Deno.test("[node/module _nodeModulePaths] prevents duplicate root /node_modules"
     "Missing root 'node_modules' directory",
   );
 });
+
+Deno.test("[node/module require.cache] deleting an entry reloads the module", () => {
+  const dir = Deno.makeTempDirSync();
+  try {
+    const file = path.join(dir, "counter.js");
+    Deno.writeTextFileSync(file, "module.exports = 1;");
+    const require = createRequire(path.join(dir, "index.js"));
+    assertEquals(require("./counter.js"), 1);
+    const filename = require.resolve("./counter.js");
+    assert(Object.keys(require.cache).includes(filename));
+
+    Deno.writeTextFileSync(file, "module.exports = 2;");
+    assertEquals(require("./counter.js"), 1);
+    delete require.cache[filename];
+    assert(!(filename in require.cache));
+    assertEquals(require("./counter.js"), 2);
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
+
+Deno.test("[node/module _clearCache] rereads package.json", () => {
+  const dir = Deno.makeTempDirSync();
+  try {
+    const pkgDir = path.join(dir, "pkg");
+    Deno.mkdirSync(pkgDir);
+    Deno.writeTextFileSync(path.join(pkgDir, "a.js"), "module.exports = 'a';");
+    Deno.writeTextFileSync(path.join(pkgDir, "b.js"), "module.exports = 'b';");
+    const writeMain = (main: string) =>
+      Deno.writeTextFileSync(
+        path.join(pkgDir, "package.json"),
+        JSON.stringify({ main }),
+      );
+    writeMain("a.js");
+    const require = createRequire(path.join(dir, "index.js"));
+    assertEquals(require("./pkg"), "a");
+
+    // deleting the entry only evaluates the module again
+    writeMain("b.js");
+    delete require.cache[require.resolve("./pkg")];
+    assertEquals(require("./pkg"), "a");
+
+    // deno-lint-ignore no-explicit-any
+    (Module as any)._clearCache(require.resolve("./pkg"));
+    assertEquals(require("./pkg"), "b");
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
+
+Deno.test({
+  name: "[node/module require] follows a symlink changed since it was resolved",
+  ignore: Deno.build.os === "windows",
+  fn() {
+    const dir = Deno.makeTempDirSync();
+    try {
+      Deno.writeTextFileSync(path.join(dir, "a.js"), "module.exports = 'a';");
+      Deno.writeTextFileSync(path.join(dir, "b.js"), "module.exports = 'b';");
+      const link = path.join(dir, "link.js");
+      Deno.symlinkSync(path.join(dir, "a.js"), link);
+      const require = createRequire(path.join(dir, "index.js"));
+      assertEquals(require("./link.js"), "a");
+
+      Deno.removeSync(link);
+      Deno.symlinkSync(path.join(dir, "b.js"), link);
+      delete require.cache[require.resolve("./link.js")];
+      assertEquals(require("./link.js"), "b");
+    } finally {
+      Deno.removeSync(dir, { recursive: true });
+    }
+  },
+});
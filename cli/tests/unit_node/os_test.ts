@@ -28,6 +28,16 @@ Deno.test({
   },
 });
 
+Deno.test({
+  name: "machine type",
+  fn() {
+    assertEquals(typeof os.machine(), "string");
+    if (Deno.build.os !== "windows") {
+      assert(["x86_64", "aarch64", "arm64"].includes(os.machine()));
+    }
+  },
+});
+
 Deno.test({
   name: "home directory is a string",
   fn() {
@@ -237,16 +247,17 @@ Deno.test({
 Deno.test({
   name: "os.cpus()",
   fn() {
-    assertEquals(os.cpus().length, navigator.hardwareConcurrency);
+    const cpus = os.cpus();
+    assert(cpus.length > 0);
 
-    for (const cpu of os.cpus()) {
-      assertEquals(cpu.model, "");
-      assertEquals(cpu.speed, 0);
-      assertEquals(cpu.times.user, 0);
-      assertEquals(cpu.times.nice, 0);
-      assertEquals(cpu.times.sys, 0);
-      assertEquals(cpu.times.idle, 0);
-      assertEquals(cpu.times.irq, 0);
+    for (const cpu of cpus) {
+      assertEquals(typeof cpu.model, "string");
+      assertEquals(typeof cpu.speed, "number");
+      assert(cpu.times.user > 0);
+      assert(cpu.times.idle > 0);
+      assertEquals(typeof cpu.times.nice, "number");
+      assertEquals(typeof cpu.times.sys, "number");
+      assertEquals(typeof cpu.times.irq, "number");
     }
   },
 });
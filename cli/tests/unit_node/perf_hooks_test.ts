@@ -0,0 +1,217 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import {
+  createHistogram,
+  monitorEventLoopDelay,
+  performance,
+  PerformanceObserver,
+  PerformanceObserverEntryList,
+} from "node:perf_hooks";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
+
+// `close()` isn't part of the node types of histograms.
+function closeHistogram(histogram: object) {
+  (histogram as { close(): void }).close();
+}
+
+/**
+ * Waits for `entries`, which a `PerformanceObserver` resolves. The observers
+ * are notified from an unref'd timer, so the event loop is kept alive until
+ * then.
+ */
+async function observed<T>(entries: Promise<T>): Promise<T> {
+  const timer = setTimeout(() => {}, 10_000);
+  try {
+    return await entries;
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
+Deno.test({
+  name: "[perf_hooks] eventLoopUtilization",
+  async fn() {
+    const elu1 = performance.eventLoopUtilization();
+    assert(elu1.idle >= 0);
+    assert(elu1.active > 0);
+    assert(elu1.utilization >= 0 && elu1.utilization <= 1);
+
+    await new Promise((resolve) => setTimeout(resolve, 50));
+
+    const elu2 = performance.eventLoopUtilization(elu1);
+    // most of the time was spent waiting for the timer
+    assert(elu2.idle >= 30, `idle: ${elu2.idle}`);
+    assert(elu2.utilization >= 0 && elu2.utilization <= 1);
+
+    const elu3 = performance.eventLoopUtilization();
+    const diff = performance.eventLoopUtilization(elu3, elu1);
+    assert(diff.idle >= elu2.idle);
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] monitorEventLoopDelay",
+  async fn() {
+    const histogram = monitorEventLoopDelay({ resolution: 10 });
+    assert(histogram.enable());
+    assert(!histogram.enable());
+    await new Promise((resolve) => setTimeout(resolve, 100));
+    assert(histogram.disable());
+    assert(!histogram.disable());
+
+    assert(histogram.count > 0);
+    // values are reported in nanoseconds
+    assert(histogram.min >= 9_000_000, `min: ${histogram.min}`);
+    assert(histogram.max >= histogram.min);
+    assert(histogram.mean >= histogram.min);
+    assert(histogram.percentile(50) >= histogram.min);
+    assertEquals(histogram.percentiles.get(100), histogram.max);
+
+    histogram.reset();
+    assertEquals(histogram.count, 0);
+    closeHistogram(histogram);
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] monitorEventLoopDelay invalid resolution",
+  fn() {
+    assertThrows(() => monitorEventLoopDelay({ resolution: 0 }), RangeError);
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] createHistogram",
+  fn() {
+    const histogram = createHistogram();
+    histogram.record(1);
+    histogram.record(2);
+    histogram.record(3n);
+    assertEquals(histogram.count, 3);
+    assertEquals(histogram.min, 1);
+    assertEquals(histogram.max, 3);
+    assertEquals(histogram.mean, 2);
+    assertEquals(histogram.percentile(50), 2);
+    closeHistogram(histogram);
+    assertThrows(() => histogram.count, Deno.errors.BadResource);
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] timerify",
+  async fn() {
+    const histogram = createHistogram();
+    function add(a: number, b: number) {
+      return a + b;
+    }
+    const timerified = performance.timerify(add, { histogram });
+    assertEquals(timerified.name, "timerified add");
+    assertEquals(timerified.length, 2);
+
+    const entries = new Promise<PerformanceEntry[]>((resolve) => {
+      const observer = new PerformanceObserver((list, observer) => {
+        observer.disconnect();
+        resolve(list.getEntries());
+      });
+      observer.observe({ entryTypes: ["function"] });
+    });
+
+    assertEquals(timerified(1, 2), 3);
+    const [entry] = await observed(entries);
+    assertEquals(entry.name, "add");
+    assertEquals(entry.entryType, "function");
+    assert(entry.duration >= 0);
+    // deno-lint-ignore no-explicit-any
+    assertEquals((entry as any).detail, [1, 2]);
+    assertEquals(histogram.count, 1);
+    closeHistogram(histogram);
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] markResourceTiming",
+  async fn() {
+    const entries = new Promise<PerformanceEntry[]>((resolve) => {
+      const observer = new PerformanceObserver((list, observer) => {
+        observer.disconnect();
+        resolve(list.getEntriesByType("resource"));
+      });
+      observer.observe({ type: "resource" });
+    });
+
+    // deno-lint-ignore no-explicit-any
+    (performance as any).markResourceTiming(
+      {
+        startTime: 10,
+        endTime: 30,
+        finalNetworkRequestStartTime: 15,
+        finalNetworkResponseStartTime: 20,
+        encodedBodySize: 100,
+        decodedBodySize: 200,
+        finalConnectionTimingInfo: { ALPNNegotiatedProtocol: "h2" },
+      },
+      "https://example.com/",
+      "fetch",
+      globalThis,
+      "",
+    );
+
+    const [entry] = await observed(entries);
+    // deno-lint-ignore no-explicit-any
+    const resource = entry as any;
+    assertEquals(resource.name, "https://example.com/");
+    assertEquals(resource.startTime, 10);
+    assertEquals(resource.duration, 20);
+    assertEquals(resource.initiatorType, "fetch");
+    assertEquals(resource.nextHopProtocol, "h2");
+    assertEquals(resource.requestStart, 15);
+    assertEquals(resource.decodedBodySize, 200);
+    assertEquals(resource.transferSize, 400);
+    // it is buffered along with the resource timings of `fetch()`
+    assert(globalThis.performance.getEntriesByType("resource").includes(entry));
+    globalThis.performance.clearResourceTimings();
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] resource timing buffer",
+  fn() {
+    const timingInfo = {
+      startTime: 0,
+      endTime: 0,
+      encodedBodySize: 0,
+      decodedBodySize: 0,
+      finalConnectionTimingInfo: null,
+    };
+    // deno-lint-ignore no-explicit-any
+    const perf = performance as any;
+    perf.setResourceTimingBufferSize(1);
+    try {
+      perf.markResourceTiming(timingInfo, "a", "fetch", globalThis, "");
+      perf.markResourceTiming(timingInfo, "b", "fetch", globalThis, "");
+      const names = globalThis.performance.getEntriesByType("resource")
+        .map((entry) => entry.name);
+      assertEquals(names, ["a"]);
+
+      perf.clearResourceTimings();
+      assertEquals(globalThis.performance.getEntriesByType("resource"), []);
+    } finally {
+      perf.setResourceTimingBufferSize(250);
+      perf.clearResourceTimings();
+    }
+  },
+});
+
+Deno.test({
+  name: "[perf_hooks] PerformanceObserver is the web one",
+  fn() {
+    assert(PerformanceObserver === globalThis.PerformanceObserver);
+    assert(
+      PerformanceObserverEntryList === globalThis.PerformanceObserverEntryList,
+    );
+    assert(PerformanceObserver.supportedEntryTypes.includes("function"));
+  },
+});
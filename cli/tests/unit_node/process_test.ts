@@ -4,6 +4,7 @@
 import process, { argv, env } from "node:process";
 import { Readable } from "node:stream";
 import { once } from "node:events";
+import { clearTimeout, setTimeout } from "node:timers";
 import {
   assert,
   assertEquals,
@@ -85,6 +86,11 @@ Deno.test({
   name: "process.platform",
   fn() {
     assertEquals(typeof process.platform, "string");
+    if (Deno.build.os == "windows") {
+      assertEquals(process.platform, "win32");
+    } else {
+      assertEquals(process.platform, Deno.build.os);
+    }
   },
 });
 
@@ -764,3 +770,31 @@ Deno.test({
     assert(typeof process.stdout.isTTY === "boolean");
   },
 });
+
+Deno.test({
+  name: "process.getActiveResourcesInfo tracks timers and listeners",
+  fn() {
+    const initial = process.getActiveResourcesInfo();
+    const timeout = setTimeout(() => {}, 1000);
+    const listener = Deno.listen({ port: 0 });
+    try {
+      const info = process.getActiveResourcesInfo();
+      assertEquals(
+        info.filter((type) => type === "Timeout").length,
+        initial.filter((type) => type === "Timeout").length + 1,
+      );
+      assert(info.includes("TCPServerWrap"));
+      // deno-lint-ignore no-explicit-any
+      const { _getActiveHandles, _getActiveRequests } = process as any;
+      assert(_getActiveHandles().includes(timeout));
+      assertEquals(_getActiveRequests(), []);
+
+      timeout.unref();
+      assert(!_getActiveHandles().includes(timeout));
+    } finally {
+      clearTimeout(timeout);
+      listener.close();
+    }
+    assertEquals(process.getActiveResourcesInfo(), initial);
+  },
+});
@@ -0,0 +1,13 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import punycode from "node:punycode";
+import {
+  assert,
+  assertEquals,
+} from "../../../test_util/std/testing/asserts.ts";
+
+Deno.test("[node/punycode] toUnicode", () => {
+  assertEquals(punycode.toUnicode("xn--espaol-zwa.com"), "español.com");
+  // what can be converted of an invalid domain is, instead of throwing
+  const domain = punycode.toUnicode("xn--iñvalid.xn--espaol-zwa");
+  assert(domain.endsWith(".español"));
+});
@@ -28,3 +28,28 @@ Deno.test({
     });
   },
 });
+
+Deno.test({
+  name: "parse with custom separators and maxKeys",
+  fn() {
+    assertEquals(parse("a:%20x+y;b:2;a:3", ";", ":"), {
+      a: [" x y", "3"],
+      b: "2",
+    });
+    assertEquals(parse("a=1&b=2&c=3", "&", "=", { maxKeys: 2 }), {
+      a: "1",
+      b: "2",
+    });
+    assertEquals(parse("%E4%BD%A0=%zz%41"), { "你": "%zzA" });
+  },
+});
+
+Deno.test({
+  name: "stringify with custom separators",
+  fn() {
+    assertEquals(
+      stringify({ a: "x y", b: ["é", ""], c: null }, ";", ":"),
+      "a:x%20y;b:%C3%A9;b:;c:",
+    );
+  },
+});
@@ -122,12 +122,14 @@ Deno.test({
     assertEquals(str, "不");
     str += decoder.write(encodedBuffer.slice(4));
     assertEquals(str, "不完全な文字のテスト");
+    assertEquals(decoder.end(), "");
 
     decoder = new StringDecoder("utf8");
     str = "";
     str += decoder.write(encodedBuffer.slice(0, 4));
     str += decoder.write(encodedBuffer.slice(5));
     assertEquals(str, "不�全な文字のテスト");
+    assertEquals(decoder.end(), "");
   },
 });
 
@@ -165,3 +167,58 @@ Deno.test({
     assertEquals(decoder.end(), "\ufffd");
   },
 });
+
+Deno.test({
+  name: "String decoder keeps utf8 characters split across writes",
+  fn() {
+    const decoder = new StringDecoder("utf8");
+    const bytes = Buffer.from("a€😀b");
+    let result = "";
+    for (const byte of bytes) {
+      result += decoder.write(Buffer.from([byte]));
+    }
+    assertEquals(result, "a€😀b");
+    assertEquals(decoder.end(), "");
+
+    // the decoder is reset by end() and can be reused
+    assertEquals(decoder.write(Buffer.from("F09F", "hex")), "");
+    assertEquals(decoder.end(Buffer.from("98", "hex")), "\ufffd");
+    assertEquals(decoder.write(new Uint8Array([0xe2, 0x82, 0xac])), "€");
+    assertEquals(decoder.end(), "");
+  },
+});
+
+Deno.test({
+  name: "String decoder keeps utf16le characters split across writes",
+  fn() {
+    const decoder = new StringDecoder("utf16le");
+    assertEquals(decoder.encoding, "utf16le");
+    // "a€" and a surrogate pair, split in the middle of code units
+    assertEquals(decoder.write(Buffer.from("61", "hex")), "");
+    assertEquals(decoder.write(Buffer.from("00AC", "hex")), "a");
+    assertEquals(decoder.write(Buffer.from("203DD8", "hex")), "€");
+    assertEquals(decoder.write(Buffer.from("00DE", "hex")), "😀");
+    assertEquals(decoder.end(), "");
+
+    // an odd trailing byte is replaced
+    assertEquals(decoder.write(Buffer.from("6100", "hex")), "a");
+    assertEquals(decoder.end(Buffer.from("62", "hex")), "�");
+
+    assertEquals(new StringDecoder("ucs2").encoding, "utf16le");
+  },
+});
+
+Deno.test({
+  name: "String decoder with latin1 and ascii",
+  fn() {
+    let decoder;
+
+    decoder = new StringDecoder("latin1");
+    assertEquals(decoder.write(Buffer.from("41E9FF", "hex")), "Aéÿ");
+    assertEquals(decoder.end(Buffer.from("E9", "hex")), "é");
+
+    decoder = new StringDecoder("ascii");
+    assertEquals(decoder.write(Buffer.from("4142", "hex")), "AB");
+    assertEquals(decoder.end(), "");
+  },
+});
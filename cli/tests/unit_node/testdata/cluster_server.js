@@ -0,0 +1,40 @@
+import cluster from "node:cluster";
+import net from "node:net";
+
+function request(port) {
+  return new Promise((resolve, reject) => {
+    const socket = net.connect(port, "127.0.0.1");
+    let data = "";
+    socket.setEncoding("utf8");
+    socket.on("data", (chunk) => data += chunk);
+    socket.on("end", () => resolve(data));
+    socket.on("error", reject);
+  });
+}
+
+if (cluster.isPrimary) {
+  const workers = [cluster.fork(), cluster.fork()];
+  let listening = 0;
+  cluster.on("listening", async (_worker, address) => {
+    if (++listening < workers.length) {
+      return;
+    }
+    const ids = [];
+    for (let i = 0; i < 4; i++) {
+      ids.push(await request(address.port));
+    }
+    console.log(ids.join(","));
+    for (const worker of workers) {
+      worker.kill();
+    }
+  });
+  let exited = 0;
+  cluster.on("exit", () => {
+    if (++exited === workers.length) {
+      console.log("done");
+    }
+  });
+} else {
+  net.createServer((socket) => socket.end(`${cluster.worker.id}`))
+    .listen(0, "127.0.0.1");
+}
@@ -0,0 +1,3 @@
+self.onmessage = ({ data: { port } }) => {
+  port.onmessage = ({ data }) => port.postMessage(`echo: ${data}`);
+};
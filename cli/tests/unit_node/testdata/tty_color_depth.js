@@ -0,0 +1,3 @@
+import { WriteStream } from "node:tty";
+
+console.log(WriteStream.prototype.getColorDepth());
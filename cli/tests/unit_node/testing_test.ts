@@ -0,0 +1,171 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import * as nodeTest from "node:test";
+import { createRequire } from "node:module";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
+
+// The bundled @types/node predates most of the node:test API.
+const {
+  after,
+  afterEach,
+  before,
+  beforeEach,
+  describe,
+  it,
+  mock,
+  test,
+  // deno-lint-ignore no-explicit-any
+} = nodeTest as any;
+// deno-lint-ignore no-explicit-any
+type TestContext = any;
+
+test("[node/test] test with a context", async (t: TestContext) => {
+  assertEquals(t.name, "[node/test] test with a context");
+  assert(t.signal instanceof AbortSignal);
+  t.diagnostic("a diagnostic message");
+
+  const calls: string[] = [];
+  t.beforeEach(() => {
+    calls.push("beforeEach");
+  });
+  t.afterEach(() => {
+    calls.push("afterEach");
+  });
+  await t.test("subtest", () => {
+    calls.push("subtest");
+  });
+  await t.test("callback subtest", (_t: TestContext, done: () => void) => {
+    calls.push("callback subtest");
+    setTimeout(done, 0);
+  });
+  assertEquals(calls, [
+    "beforeEach",
+    "subtest",
+    "afterEach",
+    "beforeEach",
+    "callback subtest",
+    "afterEach",
+  ]);
+});
+
+test("[node/test] skipped test", { skip: true }, () => {
+  throw new Error("should not run");
+});
+
+test.skip("[node/test] test.skip", () => {
+  throw new Error("should not run");
+});
+
+test.todo("[node/test] failing todo test", () => {
+  throw new Error("failures of todo tests are ignored");
+});
+
+const suiteCalls: string[] = [];
+
+describe("[node/test] describe", () => {
+  before(() => {
+    suiteCalls.push("before");
+  });
+  after(() => {
+    suiteCalls.push("after");
+  });
+  beforeEach(() => {
+    suiteCalls.push("beforeEach");
+  });
+  afterEach(() => {
+    suiteCalls.push("afterEach");
+  });
+
+  it("first", () => {
+    suiteCalls.push("first");
+  });
+
+  describe("nested", () => {
+    it("second", async () => {
+      await Promise.resolve();
+      suiteCalls.push("second");
+    });
+  });
+
+  it.skip("skipped", () => {
+    suiteCalls.push("skipped");
+  });
+});
+
+Deno.test("[node/test] describe runs hooks around tests", () => {
+  assertEquals(suiteCalls, [
+    "before",
+    "beforeEach",
+    "first",
+    "afterEach",
+    "beforeEach",
+    "second",
+    "afterEach",
+    "after",
+  ]);
+});
+
+test("[node/test] mock.fn", () => {
+  const sum = mock.fn((a: number, b: number) => a + b);
+  assertEquals(sum(1, 2), 3);
+  assertEquals(sum.mock.callCount(), 1);
+  assertEquals(sum.mock.calls[0].arguments, [1, 2]);
+  assertEquals(sum.mock.calls[0].result, 3);
+
+  sum.mock.mockImplementationOnce(() => 42);
+  assertEquals(sum(1, 2), 42);
+  assertEquals(sum(1, 2), 3);
+  sum.mock.resetCalls();
+  assertEquals(sum.mock.callCount(), 0);
+});
+
+test("[node/test] mock.method is restored", async (t: TestContext) => {
+  const object = {
+    value: 1,
+    get() {
+      return this.value;
+    },
+  };
+  await t.test("mocked", (t: TestContext) => {
+    const method = t.mock.method(object, "get", () => 2);
+    assertEquals(object.get(), 2);
+    assertEquals(method.mock.calls[0].this, object);
+  });
+  assertEquals(object.get(), 1);
+
+  mock.method(object, "get", () => 3);
+  assertEquals(object.get(), 3);
+  mock.restoreAll();
+  assertEquals(object.get(), 1);
+});
+
+test("[node/test] mock timers", (t: TestContext) => {
+  t.mock.timers.enable({ apis: ["setTimeout", "setInterval", "Date"] });
+  const calls: string[] = [];
+  setTimeout(() => calls.push("timeout"), 100);
+  const interval = setInterval(() => calls.push("interval"), 40);
+  assertEquals(Date.now(), 0);
+
+  t.mock.timers.tick(99);
+  assertEquals(calls, ["interval", "interval"]);
+  assertEquals(Date.now(), 99);
+  t.mock.timers.tick(1);
+  assertEquals(calls, ["interval", "interval", "timeout"]);
+  clearInterval(interval);
+  t.mock.timers.runAll();
+  assertEquals(calls.length, 3);
+
+  assertThrows(() => t.mock.timers.enable(), Error, "already enabled");
+  t.mock.timers.reset();
+  assert(Date.now() > 0);
+});
+
+Deno.test("[node/test] require", () => {
+  const require = createRequire(import.meta.url);
+  assertEquals(require("node:test"), test);
+  assertThrows(() => require("test"));
+  assert(!require("node:module").builtinModules.includes("test"));
+});
@@ -1,8 +1,12 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // deno-lint-ignore-file no-explicit-any
 
-import { assert } from "../../../test_util/std/testing/asserts.ts";
-import { isatty } from "node:tty";
+import {
+  assert,
+  assertEquals,
+} from "../../../test_util/std/testing/asserts.ts";
+import { isatty, WriteStream } from "node:tty";
+import * as path from "node:path";
 
 Deno.test("[node/tty isatty] returns true when fd is a tty, false otherwise", () => {
   assert(Deno.isatty(Deno.stdin.rid) === isatty(Deno.stdin.rid));
@@ -29,3 +33,30 @@ Deno.test("[node/tty isatty] returns false for irrelevant values", () => {
   assert(!isatty(null as any));
   assert(!isatty(undefined as any));
 });
+
+Deno.test("[node/tty WriteStream] getColorDepth and hasColors", () => {
+  const { getColorDepth, hasColors } = WriteStream.prototype;
+  assertEquals(getColorDepth({ FORCE_COLOR: "3" }), 24);
+  assertEquals(getColorDepth({ FORCE_COLOR: "2" }), 8);
+  assertEquals(getColorDepth({ NO_COLOR: "1", TERM: "xterm" }), 1);
+  assertEquals(getColorDepth({ TERM: "dumb" }), 1);
+  assert(hasColors({ FORCE_COLOR: "1" }));
+  assert(!hasColors(256, { FORCE_COLOR: "1" }));
+  assert(hasColors(2 ** 24, { FORCE_COLOR: "3" }));
+  assert([1, 4, 8, 24].includes(getColorDepth()));
+});
+
+Deno.test("[node/tty WriteStream] getColorDepth without env permission", async () => {
+  const command = new Deno.Command(Deno.execPath(), {
+    args: [
+      "run",
+      "--quiet",
+      "--no-prompt",
+      "./testdata/tty_color_depth.js",
+    ],
+    cwd: path.dirname(path.fromFileUrl(import.meta.url)),
+    env: { FORCE_COLOR: "3" },
+  });
+  const { stdout } = await command.output();
+  assertEquals(new TextDecoder().decode(stdout).trim(), "1");
+});
@@ -0,0 +1,18 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import url from "node:url";
+import {
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
+
+Deno.test("[node/url] domainToASCII and domainToUnicode", () => {
+  assertEquals(url.domainToASCII("español.com"), "xn--espaol-zwa.com");
+  assertEquals(url.domainToASCII("faß.de"), "xn--fa-hia.de");
+  assertEquals(url.domainToASCII("xn--iñvalid.com"), "");
+  assertEquals(url.domainToUnicode("xn--espaol-zwa.com"), "español.com");
+  assertEquals(url.domainToUnicode("xn--iñvalid.com"), "");
+});
+
+Deno.test("[node/url] parse rejects invalid domains", () => {
+  assertThrows(() => url.parse("http://xn--iñvalid.com/"), TypeError);
+});
@@ -254,3 +254,276 @@ Deno.test({
     fn();
   },
 });
+
+// deno-lint-ignore no-explicit-any
+const parseArgs = (util as any).parseArgs;
+
+Deno.test({
+  name: "[util] parseArgs() options, positionals and short groups",
+  fn() {
+    const args = ["-f", "bar", "-ab", "--baz=qux", "--", "-c", "positional"];
+    const options = {
+      foo: { type: "string", short: "f" },
+      alpha: { type: "boolean", short: "a" },
+      beta: { type: "boolean", short: "b" },
+      baz: { type: "string" },
+    };
+    const { values, positionals } = parseArgs({
+      args,
+      options,
+      allowPositionals: true,
+    });
+    assertEquals({ ...values }, {
+      foo: "bar",
+      alpha: true,
+      beta: true,
+      baz: "qux",
+    });
+    assertEquals(positionals, ["-c", "positional"]);
+    assertStrictEquals(Object.getPrototypeOf(values), null);
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() short option group with a string value",
+  fn() {
+    const { values } = parseArgs({
+      args: ["-abfFILE"],
+      options: {
+        alpha: { type: "boolean", short: "a" },
+        beta: { type: "boolean", short: "b" },
+        file: { type: "string", short: "f" },
+      },
+    });
+    assertEquals({ ...values }, { alpha: true, beta: true, file: "FILE" });
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() multiple",
+  fn() {
+    const { values } = parseArgs({
+      args: ["--foo=a", "--foo", "b", "-v", "-v"],
+      options: {
+        foo: { type: "string", multiple: true },
+        verbose: { type: "boolean", short: "v", multiple: true },
+      },
+    });
+    assertEquals({ ...values }, { foo: ["a", "b"], verbose: [true, true] });
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() default values",
+  fn() {
+    const options = {
+      foo: { type: "string", default: "a" },
+      bar: { type: "boolean", default: false },
+      baz: { type: "string", multiple: true, default: ["b", "c"] },
+    };
+    assertEquals({ ...parseArgs({ args: [], options }).values }, {
+      foo: "a",
+      bar: false,
+      baz: ["b", "c"],
+    });
+    assertEquals(
+      { ...parseArgs({ args: ["--foo", "x", "--bar"], options }).values },
+      { foo: "x", bar: true, baz: ["b", "c"] },
+    );
+
+    assertThrows(
+      () =>
+        parseArgs({
+          args: [],
+          options: { foo: { type: "string", default: true } },
+        }),
+      TypeError,
+      'The "options.foo.default" property must be of type string',
+    );
+    assertThrows(
+      () =>
+        parseArgs({
+          args: [],
+          options: { foo: { type: "boolean", multiple: true, default: [1] } },
+        }),
+      TypeError,
+      'The "options.foo.default[0]" property must be of type boolean',
+    );
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() tokens",
+  fn() {
+    const { tokens } = parseArgs({
+      args: ["-f", "--bar=b", "-xy", "pos", "--", "--after"],
+      options: {
+        foo: { type: "boolean", short: "f" },
+        bar: { type: "string" },
+        x: { type: "boolean" },
+        y: { type: "boolean" },
+      },
+      allowPositionals: true,
+      tokens: true,
+    });
+    assertEquals(tokens, [
+      {
+        kind: "option",
+        name: "foo",
+        rawName: "-f",
+        index: 0,
+        value: undefined,
+        inlineValue: undefined,
+      },
+      {
+        kind: "option",
+        name: "bar",
+        rawName: "--bar",
+        index: 1,
+        value: "b",
+        inlineValue: true,
+      },
+      {
+        kind: "option",
+        name: "x",
+        rawName: "-x",
+        index: 2,
+        value: undefined,
+        inlineValue: undefined,
+      },
+      {
+        kind: "option",
+        name: "y",
+        rawName: "-y",
+        index: 2,
+        value: undefined,
+        inlineValue: undefined,
+      },
+      { kind: "positional", index: 3, value: "pos" },
+      { kind: "option-terminator", index: 4 },
+      { kind: "positional", index: 5, value: "--after" },
+    ]);
+
+    const { values, tokens: looseTokens } = parseArgs({
+      args: ["--unknown", "-s", "v"],
+      strict: false,
+      tokens: true,
+    });
+    assertEquals({ ...values }, { unknown: true, s: true });
+    assertEquals(looseTokens[1], {
+      kind: "option",
+      name: "s",
+      rawName: "-s",
+      index: 1,
+      value: undefined,
+      inlineValue: undefined,
+    });
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() strict mode errors",
+  fn() {
+    const options = {
+      foo: { type: "string", short: "f" },
+      bar: { type: "boolean" },
+    };
+    // deno-lint-ignore no-explicit-any
+    const assertCode = (fn: () => unknown, code: string): any => {
+      try {
+        fn();
+      } catch (err) {
+        assert(err instanceof TypeError);
+        // deno-lint-ignore no-explicit-any
+        assertEquals((err as any).code, code);
+        return err;
+      }
+      throw new Error(`Expected ${code} to be thrown`);
+    };
+
+    const unknown = assertCode(
+      () => parseArgs({ args: ["--baz"], options }),
+      "ERR_PARSE_ARGS_UNKNOWN_OPTION",
+    );
+    assertEquals(unknown.message, "Unknown option '--baz'");
+    const unknownWithPositionals = assertCode(
+      () => parseArgs({ args: ["-q"], options, allowPositionals: true }),
+      "ERR_PARSE_ARGS_UNKNOWN_OPTION",
+    );
+    assert(unknownWithPositionals.message.includes("after '--'"));
+
+    assertCode(
+      () => parseArgs({ args: ["pos"], options }),
+      "ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL",
+    );
+    const missing = assertCode(
+      () => parseArgs({ args: ["--foo"], options }),
+      "ERR_PARSE_ARGS_INVALID_OPTION_VALUE",
+    );
+    assertEquals(
+      missing.message,
+      "Option '-f, --foo <value>' argument missing",
+    );
+    const boolWithValue = assertCode(
+      () => parseArgs({ args: ["--bar=1"], options }),
+      "ERR_PARSE_ARGS_INVALID_OPTION_VALUE",
+    );
+    assertEquals(
+      boolWithValue.message,
+      "Option '--bar' does not take an argument",
+    );
+    const ambiguous = assertCode(
+      () => parseArgs({ args: ["-f", "--bar"], options }),
+      "ERR_PARSE_ARGS_INVALID_OPTION_VALUE",
+    );
+    assert(ambiguous.message.includes("'--foo=-XYZ' or '-f-XYZ'"));
+
+    // an option-like value is fine when given inline
+    assertEquals(
+      parseArgs({ args: ["--foo=--bar"], options }).values.foo,
+      "--bar",
+    );
+    // and all of these are accepted when not strict
+    const { values, positionals } = parseArgs({
+      args: ["--baz", "--bar=1", "pos"],
+      options,
+      strict: false,
+    });
+    assertEquals({ ...values }, { baz: true, bar: "1" });
+    assertEquals(positionals, ["pos"]);
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() validates its configuration",
+  fn() {
+    assertThrows(
+      () => parseArgs({ args: "--foo" }),
+      TypeError,
+      'The "args" argument must be an instance of Array',
+    );
+    assertThrows(
+      () => parseArgs({ options: { foo: { type: "number" } } }),
+      TypeError,
+      "options.foo.type",
+    );
+    assertThrows(
+      () => parseArgs({ options: { foo: { type: "string", short: "fo" } } }),
+      TypeError,
+      "must be a single character",
+    );
+    assertThrows(
+      () => parseArgs({ tokens: "yes" }),
+      TypeError,
+      'The "tokens" argument must be of type boolean',
+    );
+  },
+});
+
+Deno.test({
+  name: "[util] parseArgs() ignores __proto__",
+  fn() {
+    const { values } = parseArgs({ args: ["--__proto__=x"], strict: false });
+    assertStrictEquals(values.__proto__, undefined);
+  },
+});
@@ -1,10 +1,17 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import {
   cachedDataVersionTag,
+  getHeapSpaceStatistics,
   getHeapStatistics,
   setFlagsFromString,
+  startupSnapshot,
+  writeHeapSnapshot,
 } from "node:v8";
-import { assertEquals } from "../../../test_util/std/testing/asserts.ts";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
 
 // https://github.com/nodejs/node/blob/a2bbe5ff216bc28f8dac1c36a8750025a93c3827/test/parallel/test-v8-version-tag.js#L6
 Deno.test({
@@ -53,3 +60,59 @@ Deno.test({
     setFlagsFromString("--allow_natives_syntax");
   },
 });
+
+// https://github.com/nodejs/node/blob/a2bbe5ff216bc28f8dac1c36a8750025a93c3827/test/parallel/test-v8-stats.js#L38
+Deno.test({
+  name: "getHeapSpaceStatistics success",
+  fn() {
+    const spaces = getHeapSpaceStatistics();
+    assert(spaces.length > 0);
+    const names = spaces.map((space) => space.space_name);
+    assert(names.includes("new_space"));
+    assert(names.includes("old_space"));
+    for (const space of spaces) {
+      assertEquals(Object.keys(space).sort(), [
+        "physical_space_size",
+        "space_available_size",
+        "space_name",
+        "space_size",
+        "space_used_size",
+      ]);
+      assertEquals(typeof space.space_used_size, "number");
+    }
+  },
+});
+
+Deno.test({
+  name: "writeHeapSnapshot writes a parseable snapshot",
+  fn() {
+    const dir = Deno.makeTempDirSync();
+    try {
+      const filename = writeHeapSnapshot(`${dir}/test.heapsnapshot`);
+      assertEquals(filename, `${dir}/test.heapsnapshot`);
+      const snapshot = JSON.parse(Deno.readTextFileSync(filename));
+      assert(snapshot.snapshot.node_count > 0);
+      assert(Array.isArray(snapshot.nodes));
+    } finally {
+      Deno.removeSync(dir, { recursive: true });
+    }
+  },
+});
+
+Deno.test({
+  name: "startupSnapshot hooks throw when not building a snapshot",
+  fn() {
+    assertEquals(startupSnapshot.isBuildingSnapshot(), false);
+    for (
+      const hook of [
+        startupSnapshot.addSerializeCallback,
+        startupSnapshot.addDeserializeCallback,
+        startupSnapshot.setDeserializeMainFunction,
+      ]
+    ) {
+      // deno-lint-ignore no-explicit-any
+      const err = assertThrows(() => hook(() => {})) as any;
+      assertEquals(err.code, "ERR_NOT_BUILDING_SNAPSHOT");
+    }
+  },
+});
@@ -0,0 +1,179 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { WASI } from "node:wasi";
+import {
+  assert,
+  assertEquals,
+  assertThrows,
+} from "../../../test_util/std/testing/asserts.ts";
+
+const encoder = new TextEncoder();
+
+function encodeName(name: string): number[] {
+  return [name.length, ...encoder.encode(name)];
+}
+
+// A module equivalent to:
+//   (module
+//     (import "wasi_snapshot_preview1" "proc_exit" (func (param i32)))
+//     (memory (export "memory") 1)
+//     (func (export "_start") (call 0 (i32.const 42))))
+const exitModule = new Uint8Array([
+  ...[0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00],
+  ...[0x01, 0x08, 0x02, 0x60, 0x01, 0x7f, 0x00, 0x60, 0x00, 0x00],
+  ...[0x02, 0x24, 0x01],
+  ...encodeName("wasi_snapshot_preview1"),
+  ...encodeName("proc_exit"),
+  ...[0x00, 0x00],
+  ...[0x03, 0x02, 0x01, 0x01],
+  ...[0x05, 0x03, 0x01, 0x00, 0x01],
+  ...[0x07, 0x13, 0x02],
+  ...encodeName("memory"),
+  ...[0x02, 0x00],
+  ...encodeName("_start"),
+  ...[0x00, 0x01],
+  ...[0x0a, 0x08, 0x01, 0x06, 0x00, 0x41, 0x2a, 0x10, 0x00, 0x0b],
+]);
+
+function fakeInstance(
+  start: (view: DataView) => void,
+  memory = new WebAssembly.Memory({ initial: 1 }),
+) {
+  return {
+    exports: {
+      memory,
+      _start: () => start(new DataView(memory.buffer)),
+    },
+  } as unknown as WebAssembly.Instance;
+}
+
+Deno.test("[node/wasi] constructor validates options", () => {
+  assertThrows(() => new WASI({ version: "preview2" as "preview1" }));
+  assertThrows(() => new WASI({ args: "foo" as unknown as string[] }));
+  assertThrows(() => new WASI({ returnOnExit: 1 as unknown as boolean }));
+});
+
+Deno.test("[node/wasi] getImportObject", () => {
+  const wasi = new WASI({ version: "preview1" });
+  assertEquals(Object.keys(wasi.getImportObject()), [
+    "wasi_snapshot_preview1",
+  ]);
+  const unstable = new WASI({ version: "unstable" });
+  assertEquals(Object.keys(unstable.getImportObject()), ["wasi_unstable"]);
+});
+
+Deno.test("[node/wasi] start returns the exit code", async () => {
+  const wasi = new WASI({ version: "preview1", returnOnExit: true });
+  const { instance } = await WebAssembly.instantiate(
+    exitModule,
+    wasi.getImportObject(),
+  );
+  assertEquals(wasi.start(instance), 42);
+  assertThrows(
+    () => wasi.start(instance),
+    Error,
+    "WASI instance has already started",
+  );
+});
+
+Deno.test("[node/wasi] args and environ", () => {
+  const wasi = new WASI({ args: ["foo", "bar"], env: { KEY: "value" } });
+  const imports = wasi.wasiImport;
+  wasi.start(fakeInstance((view) => {
+    assertEquals(imports.args_sizes_get(0, 4), 0);
+    assertEquals(view.getUint32(0, true), 2);
+    assertEquals(view.getUint32(4, true), 8);
+    assertEquals(imports.args_get(16, 64), 0);
+    assertEquals(view.getUint32(16, true), 64);
+    assertEquals(view.getUint32(20, true), 68);
+    assertEquals(
+      new TextDecoder().decode(new Uint8Array(view.buffer, 64, 8)),
+      "foo\0bar\0",
+    );
+
+    assertEquals(imports.environ_sizes_get(0, 4), 0);
+    assertEquals(view.getUint32(0, true), 1);
+    assertEquals(view.getUint32(4, true), 10);
+  }));
+});
+
+Deno.test("[node/wasi] files are confined to preopens", () => {
+  const dir = Deno.makeTempDirSync();
+  const outside = Deno.makeTempDirSync();
+  Deno.symlinkSync(dir, `${dir}/inside`);
+  Deno.symlinkSync(outside, `${dir}/outside`);
+  try {
+    const wasi = new WASI({ preopens: { "/sandbox": dir } });
+    const imports = wasi.wasiImport;
+    wasi.start(fakeInstance((view) => {
+      const bytes = new Uint8Array(view.buffer);
+
+      assertEquals(imports.fd_prestat_get(3, 0), 0);
+      assertEquals(view.getUint32(4, true), "/sandbox".length);
+
+      const path = encoder.encode("hello.txt");
+      bytes.set(path, 1024);
+      // O_CREAT | O_TRUNC, rights fd_write
+      assertEquals(
+        imports.path_open(3, 0, 1024, path.length, 9, 0x40n, 0n, 0, 0),
+        0,
+      );
+      const fd = view.getUint32(0, true);
+
+      bytes.set(encoder.encode("hi"), 2048);
+      view.setUint32(16, 2048, true);
+      view.setUint32(20, 2, true);
+      assertEquals(imports.fd_write(fd, 16, 1, 32), 0);
+      assertEquals(view.getUint32(32, true), 2);
+      assertEquals(imports.fd_close(fd), 0);
+      // ERRNO_BADF
+      assertEquals(imports.fd_close(fd), 8);
+
+      const escape = encoder.encode("../escape.txt");
+      bytes.set(escape, 1024);
+      // ERRNO_NOTCAPABLE
+      assertEquals(
+        imports.path_open(3, 0, 1024, escape.length, 1, 0x40n, 0n, 0, 0),
+        76,
+      );
+
+      // names that start with ".." are still inside
+      const dots = encoder.encode("..dots.txt");
+      bytes.set(dots, 1024);
+      assertEquals(
+        imports.path_open(3, 0, 1024, dots.length, 1, 0x40n, 0n, 0, 0),
+        0,
+      );
+      assertEquals(imports.fd_close(view.getUint32(0, true)), 0);
+
+      // so are the links that point inside
+      const inside = encoder.encode("inside/hello.txt");
+      bytes.set(inside, 1024);
+      assertEquals(
+        imports.path_open(3, 0, 1024, inside.length, 0, 0x2n, 0n, 0, 0),
+        0,
+      );
+      assertEquals(imports.fd_close(view.getUint32(0, true)), 0);
+
+      // but not the ones that point outside
+      const link = encoder.encode("outside/escape.txt");
+      bytes.set(link, 1024);
+      assertEquals(
+        imports.path_open(3, 0, 1024, link.length, 1, 0x40n, 0n, 0, 0),
+        76,
+      );
+      // even once the link itself can be read
+      const linkName = encoder.encode("outside");
+      bytes.set(linkName, 1024);
+      assertEquals(
+        imports.path_readlink(3, 1024, linkName.length, 2048, 256, 32),
+        0,
+      );
+    }));
+    assertEquals(Deno.readTextFileSync(`${dir}/hello.txt`), "hi");
+    assert(Deno.statSync(`${dir}/..dots.txt`).isFile);
+    assertThrows(() => Deno.statSync(`${outside}/escape.txt`));
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+    Deno.removeSync(outside, { recursive: true });
+  }
+});
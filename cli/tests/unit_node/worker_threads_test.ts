@@ -192,3 +192,40 @@ Deno.test({
     worker.terminate();
   },
 });
+
+Deno.test({
+  name: "[worker_threads] MessagePort transferred to a web Worker",
+  async fn() {
+    const { port1, port2 } = new workerThreads.MessageChannel();
+    const worker = new Worker(
+      new URL("./testdata/message_port_echo.js", import.meta.url),
+      { type: "module" },
+    );
+    // deno-lint-ignore no-explicit-any
+    worker.postMessage({ port: port2 }, [port2 as any]);
+    const received = new Promise((resolve) => port1.once("message", resolve));
+    port1.postMessage("hello");
+    assertEquals(await received, "echo: hello");
+
+    const closed = new Promise((resolve) => port1.on("close", resolve));
+    worker.terminate();
+    port1.close();
+    await closed;
+  },
+});
+
+Deno.test({
+  name: "[worker_threads] web MessagePorts are left alone",
+  fn() {
+    const { port1, port2 } = new workerThreads.MessageChannel();
+    assert(port1 instanceof MessagePort);
+    assertEquals(typeof port1.on, "function");
+    assert(!("on" in MessagePort.prototype));
+    const channel = new MessageChannel();
+    assert(!("on" in channel.port1));
+    port1.close();
+    port2.close();
+    channel.port1.close();
+    channel.port2.close();
+  },
+});
@@ -97,7 +97,7 @@ pub struct BenchDescription {
   pub only: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BenchStats {
   pub n: u64,
   pub min: f64,
@@ -107,6 +107,52 @@ pub struct BenchStats {
   pub p99: f64,
   pub p995: f64,
   pub p999: f64,
+  /// The standard deviation of the measured iterations.
+  pub sd: f64,
+}
+
+impl BenchStats {
+  /// The standard error of the average, relative to the average.
+  fn relative_standard_error(&self) -> f64 {
+    if self.n == 0 || self.avg <= 0.0 {
+      return 0.0;
+    }
+    self.sd / (self.n as f64).sqrt() / self.avg
+  }
+}
+
+/// How a benchmark compares to the baseline of its group.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchComparison {
+  /// The average time of the benchmark divided by the one of the baseline,
+  /// so it's above 1 when the baseline is faster.
+  pub ratio: f64,
+  /// The bounds of the 95% confidence interval of the ratio.
+  pub ratio_low: f64,
+  pub ratio_high: f64,
+}
+
+impl BenchComparison {
+  pub fn new(baseline: &BenchStats, stats: &BenchStats) -> Self {
+    let ratio = stats.avg / baseline.avg;
+    let margin = ratio * Self::relative_margin(baseline, stats);
+    Self {
+      ratio,
+      ratio_low: (ratio - margin).max(0.0),
+      ratio_high: ratio + margin,
+    }
+  }
+
+  /// The margin of error of the ratio, relative to the ratio. It's the same
+  /// for the inverse ratio.
+  fn relative_margin(baseline: &BenchStats, stats: &BenchStats) -> f64 {
+    // z-score of the 95% confidence level
+    const Z: f64 = 1.96;
+    let a = baseline.relative_standard_error();
+    let b = stats.relative_standard_error();
+    Z * (a * a + b * b).sqrt()
+  }
 }
 
 impl BenchReport {
@@ -145,6 +191,7 @@ struct JsonReporterOutput {
   runtime: String,
   cpu: String,
   benches: Vec<JsonReporterBench>,
+  summaries: Vec<JsonReporterSummary>,
 }
 
 impl Default for JsonReporterOutput {
@@ -153,6 +200,7 @@ impl Default for JsonReporterOutput {
       runtime: format!("{} {}", get_user_agent(), env!("TARGET")),
       cpu: mitata::cpu::name(),
       benches: vec![],
+      summaries: vec![],
     }
   }
 }
@@ -166,6 +214,23 @@ struct JsonReporterBench {
   results: Vec<BenchResult>,
 }
 
+/// The comparison of the benchmarks of a group against its baseline, the
+/// same as the summary printed by the console reporter.
+#[derive(Debug, Serialize)]
+struct JsonReporterSummary {
+  origin: String,
+  group: Option<String>,
+  baseline: String,
+  comparisons: Vec<JsonReporterComparison>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonReporterComparison {
+  name: String,
+  #[serde(flatten)]
+  comparison: BenchComparison,
+}
+
 #[derive(Debug, Serialize)]
 struct JsonReporter(JsonReporterOutput);
 
@@ -173,6 +238,56 @@ impl JsonReporter {
   fn new() -> Self {
     Self(Default::default())
   }
+
+  fn summaries(&self) -> Vec<JsonReporterSummary> {
+    let mut groups = IndexMap::<
+      (&str, &Option<String>),
+      Vec<(&JsonReporterBench, &BenchStats)>,
+    >::new();
+    for bench in &self.0.benches {
+      let stats = bench.results.iter().find_map(|result| match result {
+        BenchResult::Ok(stats) => Some(stats),
+        BenchResult::Failed(_) => None,
+      });
+      if let Some(stats) = stats {
+        groups
+          .entry((&bench.origin, &bench.group))
+          .or_default()
+          .push((bench, stats));
+      }
+    }
+
+    let mut summaries = Vec::new();
+    for ((origin, group), benches) in groups {
+      let explicit_baseline = benches.iter().find(|(bench, _)| bench.baseline);
+      // like for the console, ungrouped benchmarks are only summarized when
+      // one of them is the baseline
+      if benches.len() < 2 || (group.is_none() && explicit_baseline.is_none()) {
+        continue;
+      }
+      let (baseline, baseline_stats) = explicit_baseline
+        .or_else(|| {
+          benches
+            .iter()
+            .min_by(|(_, a), (_, b)| a.avg.total_cmp(&b.avg))
+        })
+        .unwrap();
+      summaries.push(JsonReporterSummary {
+        origin: origin.to_string(),
+        group: group.clone(),
+        baseline: baseline.name.clone(),
+        comparisons: benches
+          .iter()
+          .filter(|(bench, _)| !std::ptr::eq(*bench, *baseline))
+          .map(|(bench, stats)| JsonReporterComparison {
+            name: bench.name.clone(),
+            comparison: BenchComparison::new(baseline_stats, stats),
+          })
+          .collect(),
+      });
+    }
+    summaries
+  }
 }
 
 impl BenchReporter for JsonReporter {
@@ -181,6 +296,7 @@ impl BenchReporter for JsonReporter {
   fn report_plan(&mut self, _plan: &BenchPlan) {}
 
   fn report_end(&mut self, _report: &BenchReport) {
+    self.0.summaries = self.summaries();
     match write_json_to_stdout(self) {
       Ok(_) => (),
       Err(e) => println!("{e}"),
@@ -392,15 +508,7 @@ impl BenchReporter for ConsoleReporter {
               name: d.name.clone(),
               baseline: d.baseline,
               group: d.group.as_deref().unwrap_or("").to_owned(),
-
-              stats: mitata::reporter::BenchmarkStats {
-                avg: s.avg,
-                min: s.min,
-                max: s.max,
-                p75: s.p75,
-                p99: s.p99,
-                p995: s.p995,
-              },
+              stats: s.clone(),
             })
             .collect::<Vec<mitata::reporter::GroupBenchmark>>(),
           options
@@ -835,12 +943,14 @@ pub async fn run_benchmarks_with_watch(
   };
 
   let clear_screen = !cli_options.no_clear_screen();
+  let restart_hooks = cli_options.resolve_watch_restart_hooks()?;
   file_watcher::watch_func(
     resolver,
     operation,
     file_watcher::PrintConfig {
       job_name: "Bench".to_string(),
       clear_screen,
+      restart_hooks,
     },
   )
   .await?;
@@ -857,6 +967,8 @@ mod mitata {
   //
   // THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
+  use super::BenchComparison;
+  use super::BenchStats;
   use crate::colors;
   use std::str::FromStr;
 
@@ -1005,7 +1117,7 @@ mod mitata {
       pub name: String,
       pub group: String,
       pub baseline: bool,
-      pub stats: BenchmarkStats,
+      pub stats: BenchStats,
     }
 
     #[derive(Clone, PartialEq)]
@@ -1168,6 +1280,12 @@ mod mitata {
       s
     }
 
+    /// Formats the 95% confidence interval of the ratio of the averages.
+    fn fmt_margin(baseline: &BenchStats, stats: &BenchStats) -> String {
+      let margin = BenchComparison::relative_margin(baseline, stats);
+      format!("(±{:.1}%)", margin * 100.0)
+    }
+
     pub fn summary(benchmarks: &[GroupBenchmark], options: &Options) -> String {
       let mut s = String::new();
       let mut benchmarks = benchmarks.to_owned();
@@ -1193,10 +1311,11 @@ mod mitata {
           ))
           .unwrap();
           s.push_str(&format!(
-            "\n   {}x times {} than {}",
+            "\n   {}x times {} than {} {}",
             if faster { diff } else { inv_diff },
             if faster { "faster" } else { "slower" },
-            b.name
+            b.name,
+            fmt_margin(&baseline.stats, &b.stats)
           ));
         }
       } else {
@@ -1219,14 +1338,15 @@ mod mitata {
           ))
           .unwrap();
           s.push_str(&format!(
-            "\n   {}x {} than {}",
+            "\n   {}x {} than {} {}",
             if faster {
               colors::green(diff.to_string()).to_string()
             } else {
               colors::red(inv_diff.to_string()).to_string()
             },
             if faster { "faster" } else { "slower" },
-            colors::cyan_bold(&b.name)
+            colors::cyan_bold(&b.name),
+            colors::gray(fmt_margin(&baseline.stats, &b.stats))
           ));
         }
       }
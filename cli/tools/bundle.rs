@@ -132,6 +132,7 @@ pub async fn bundle(
       util::file_watcher::PrintConfig {
         job_name: "Bundle".to_string(),
         clear_screen: !cli_options.no_clear_screen(),
+        restart_hooks: cli_options.resolve_watch_restart_hooks()?,
       },
     )
     .await?;
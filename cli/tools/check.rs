@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -18,8 +19,11 @@ use crate::args::TsConfig;
 use crate::args::TsConfigType;
 use crate::args::TsTypeLib;
 use crate::args::TypeCheckMode;
+use crate::cache::CacheStatus;
 use crate::cache::Caches;
 use crate::cache::FastInsecureHasher;
+use crate::cache::ModuleCheckHashes;
+use crate::cache::StaleReason;
 use crate::cache::TypeCheckCache;
 use crate::npm::CliNpmResolver;
 use crate::tsc;
@@ -109,6 +113,7 @@ impl TypeChecker {
     }
 
     let root_names = get_tsc_roots(&graph, check_js);
+    let config_hash = get_check_config_hash(&ts_config);
     // while there might be multiple roots, we can't "merge" the build info, so we
     // try to retrieve the build info for first root, which is the most common use
     // case.
@@ -165,18 +170,104 @@ impl TypeChecker {
       cache.set_tsbuildinfo(&graph.roots[0], &tsbuildinfo);
     }
 
+    // only cache when there is nothing to report, so that warnings keep
+    // being shown
     if diagnostics.is_empty() {
       cache.add_check_hash(check_hash);
+      let module_hashes =
+        get_module_check_hashes(&graph, config_hash, check_js);
+      // the modules whose diagnostics weren't reported aren't recorded, so
+      // that they keep what they were type checked with before
+      cache.set_module_check_hashes(
+        type_check_mode,
+        module_hashes.iter().filter(|(specifier, _)| {
+          self.is_reported(specifier, type_check_mode)
+        }),
+      );
     }
 
     log::debug!("{}", response.stats);
 
+    let check_config = self.cli_options.resolve_check_config()?;
+    let (mut diagnostics, warnings) =
+      diagnostics.split_warnings(&check_config.warn_only);
+    if !warnings.is_empty() {
+      log::warn!("{}\n", warnings);
+    }
+
     if diagnostics.is_empty() {
       Ok(())
     } else {
+      if let Some(error_limit) = check_config.error_limit {
+        diagnostics.truncate(error_limit);
+      }
       Err(diagnostics.into())
     }
   }
+
+  /// Whether the diagnostics of the module are reported when type checking
+  /// with the mode.
+  fn is_reported(
+    &self,
+    specifier: &ModuleSpecifier,
+    type_check_mode: TypeCheckMode,
+  ) -> bool {
+    match type_check_mode {
+      TypeCheckMode::All => true,
+      TypeCheckMode::Local => {
+        !specifier.as_str().starts_with("http")
+          && !self.node_resolver.in_npm_package(specifier)
+      }
+      TypeCheckMode::None => false,
+    }
+  }
+
+  /// Tells whether each type checked module of the graph was type checked
+  /// with its current inputs, and if not, why. A module only counts as type
+  /// checked when the mode it was type checked with reported its
+  /// diagnostics.
+  pub fn module_check_statuses(
+    &self,
+    graph: &ModuleGraph,
+    lib: TsTypeLib,
+  ) -> Result<HashMap<ModuleSpecifier, CacheStatus>, AnyError> {
+    let ts_config = self
+      .cli_options
+      .resolve_ts_config_for_emit(TsConfigType::Check { lib })?
+      .ts_config;
+    let cache = TypeCheckCache::new(self.caches.type_checking_cache_db());
+    let config_hash = get_check_config_hash(&ts_config);
+    let module_hashes =
+      get_module_check_hashes(graph, config_hash, ts_config.get_check_js());
+    Ok(
+      module_hashes
+        .into_iter()
+        .map(|(specifier, hashes)| {
+          let status = match cache.get_module_check_hashes(&specifier) {
+            None => CacheStatus::Missing,
+            Some((type_check_mode, _))
+              if !self.is_reported(&specifier, type_check_mode) =>
+            {
+              CacheStatus::Missing
+            }
+            Some((_, checked)) if checked.config != hashes.config => {
+              CacheStatus::Stale(StaleReason::ConfigChanged)
+            }
+            Some((_, checked)) if checked.source != hashes.source => {
+              CacheStatus::Stale(StaleReason::SourceChanged)
+            }
+            Some((_, checked))
+              if checked.dependencies != hashes.dependencies =>
+            {
+              CacheStatus::Stale(StaleReason::DependencyChanged)
+            }
+            Some(_) => CacheStatus::Cached,
+          };
+          (specifier, status)
+        })
+        .collect(),
+    )
+  }
 }
 
 enum CheckHashResult {
@@ -192,11 +283,7 @@ fn get_check_hash(
   ts_config: &TsConfig,
 ) -> CheckHashResult {
   let mut hasher = FastInsecureHasher::new();
-  hasher.write_u8(match type_check_mode {
-    TypeCheckMode::All => 0,
-    TypeCheckMode::Local => 1,
-    TypeCheckMode::None => 2,
-  });
+  hasher.write_u8(type_check_mode_hash(type_check_mode));
   hasher.write(&ts_config.as_bytes());
 
   let check_js = ts_config.get_check_js();
@@ -259,6 +346,234 @@ fn get_check_hash(
   }
 }
 
+fn type_check_mode_hash(type_check_mode: TypeCheckMode) -> u8 {
+  match type_check_mode {
+    TypeCheckMode::All => 0,
+    TypeCheckMode::Local => 1,
+    TypeCheckMode::None => 2,
+  }
+}
+
+/// Gets a hash of the inputs for type checking that aren't modules. The type
+/// checking mode is stored on its own.
+fn get_check_config_hash(ts_config: &TsConfig) -> u64 {
+  FastInsecureHasher::new()
+    .write(&ts_config.as_bytes())
+    .finish()
+}
+
+/// Gets the inputs of each module of the graph that is type checked, to
+/// tell why a module has to be type checked again. Unlike `get_check_hash`,
+/// this only accounts for the modules a module depends on.
+///
+/// The modules are hashed in a single pass, with the modules a module
+/// depends on hashed before it. The modules that depend on each other are
+/// hashed as a whole.
+fn get_module_check_hashes(
+  graph: &ModuleGraph,
+  config_hash: u64,
+  check_js: bool,
+) -> HashMap<ModuleSpecifier, ModuleCheckHashes> {
+  let modules = graph
+    .modules()
+    .filter_map(|module| module.esm())
+    .collect::<Vec<_>>();
+  let indexes = modules
+    .iter()
+    .enumerate()
+    .map(|(index, module)| (&module.specifier, index))
+    .collect::<HashMap<_, _>>();
+  let dependencies = modules
+    .iter()
+    .map(|module| {
+      let mut dependencies = esm_dependencies(module)
+        .filter_map(|specifier| indexes.get(&graph.resolve(specifier)))
+        .copied()
+        .collect::<Vec<_>>();
+      dependencies.sort_unstable();
+      dependencies.dedup();
+      dependencies
+    })
+    .collect::<Vec<_>>();
+  let source_hashes = modules
+    .iter()
+    .map(|module| {
+      is_type_checked(module, check_js).then(|| {
+        FastInsecureHasher::new()
+          .write_str(module.specifier.as_str())
+          .write_str(&module.source)
+          .finish()
+      })
+    })
+    .collect::<Vec<_>>();
+
+  let mut component_of = vec![0; modules.len()];
+  // the hash of the sources of the modules of a component and of everything
+  // they depend on
+  let mut component_hashes = Vec::new();
+  let mut module_hashes = HashMap::new();
+  for (component, members) in strongly_connected_components(&dependencies)
+    .into_iter()
+    .enumerate()
+  {
+    for member in &members {
+      component_of[*member] = component;
+    }
+    let mut dependency_components = members
+      .iter()
+      .flat_map(|member| &dependencies[*member])
+      .map(|dependency| component_of[*dependency])
+      .filter(|dependency| *dependency != component)
+      .collect::<Vec<_>>();
+    dependency_components.sort_unstable();
+    dependency_components.dedup();
+    // make it deterministic
+    let mut dependency_hashes = dependency_components
+      .into_iter()
+      .map(|dependency| component_hashes[dependency])
+      .collect::<Vec<u64>>();
+    dependency_hashes.sort_unstable();
+    let mut member_hashes = members
+      .iter()
+      .filter_map(|member| source_hashes[*member])
+      .collect::<Vec<_>>();
+    member_hashes.sort_unstable();
+
+    let hash_of = |source_hashes: &[u64]| {
+      let mut hasher = FastInsecureHasher::new();
+      for hash in source_hashes.iter().chain(&dependency_hashes) {
+        hasher.write_u64(*hash);
+      }
+      hasher.finish()
+    };
+    for member in &members {
+      let Some(source_hash) = source_hashes[*member] else {
+        continue;
+      };
+      // the other modules of the component are dependencies of the module
+      let other_member_hashes = member_hashes
+        .iter()
+        .copied()
+        .filter(|hash| *hash != source_hash)
+        .collect::<Vec<_>>();
+      let hashes = ModuleCheckHashes {
+        source: source_hash,
+        config: config_hash,
+        dependencies: hash_of(&other_member_hashes),
+      };
+      module_hashes.insert(modules[*member].specifier.clone(), hashes);
+    }
+    component_hashes.push(hash_of(&member_hashes));
+  }
+  module_hashes
+}
+
+/// The modules a module depends on, as `ModuleGraph::walk` follows them when
+/// type checking.
+fn esm_dependencies(
+  module: &deno_graph::EsmModule,
+) -> impl Iterator<Item = &ModuleSpecifier> {
+  let types_dependency = module
+    .maybe_types_dependency
+    .as_ref()
+    .and_then(|types_dependency| types_dependency.dependency.maybe_specifier());
+  module
+    .dependencies
+    .values()
+    .flat_map(|dependency| {
+      [
+        dependency.maybe_code.maybe_specifier(),
+        dependency.maybe_type.maybe_specifier(),
+      ]
+    })
+    .chain(std::iter::once(types_dependency))
+    .flatten()
+}
+
+/// Tarjan's algorithm. Gets the strongly connected components of a graph,
+/// given the nodes each node points to, with every component coming after
+/// the components it points to.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+  const UNVISITED: usize = usize::MAX;
+  let mut indexes = vec![UNVISITED; edges.len()];
+  let mut low_links = vec![0; edges.len()];
+  let mut on_stack = vec![false; edges.len()];
+  let mut stack = Vec::new();
+  let mut components = Vec::new();
+  let mut next_index = 0;
+  for root in 0..edges.len() {
+    if indexes[root] != UNVISITED {
+      continue;
+    }
+    // the nodes being visited, with the next of their edges to follow
+    let mut visiting = vec![(root, 0)];
+    indexes[root] = next_index;
+    low_links[root] = next_index;
+    next_index += 1;
+    stack.push(root);
+    on_stack[root] = true;
+    while let Some((node, edge)) = visiting.last_mut() {
+      let node = *node;
+      if let Some(next) = edges[node].get(*edge).copied() {
+        *edge += 1;
+        if indexes[next] == UNVISITED {
+          indexes[next] = next_index;
+          low_links[next] = next_index;
+          next_index += 1;
+          stack.push(next);
+          on_stack[next] = true;
+          visiting.push((next, 0));
+        } else if on_stack[next] {
+          low_links[node] = low_links[node].min(indexes[next]);
+        }
+        continue;
+      }
+
+      visiting.pop();
+      if let Some((parent, _)) = visiting.last() {
+        low_links[*parent] = low_links[*parent].min(low_links[node]);
+      }
+      if low_links[node] == indexes[node] {
+        let mut component = Vec::new();
+        loop {
+          let member = stack.pop().unwrap();
+          on_stack[member] = false;
+          component.push(member);
+          if member == node {
+            break;
+          }
+        }
+        components.push(component);
+      }
+    }
+  }
+  components
+}
+
+/// Whether the module is one `get_check_hash` accounts for.
+fn is_type_checked(module: &deno_graph::EsmModule, check_js: bool) -> bool {
+  match module.media_type {
+    MediaType::TypeScript
+    | MediaType::Dts
+    | MediaType::Dmts
+    | MediaType::Dcts
+    | MediaType::Mts
+    | MediaType::Cts
+    | MediaType::Tsx => true,
+    MediaType::JavaScript
+    | MediaType::Mjs
+    | MediaType::Cjs
+    | MediaType::Jsx => {
+      check_js || has_ts_check(module.media_type, &module.source)
+    }
+    MediaType::Json
+    | MediaType::TsBuildInfo
+    | MediaType::SourceMap
+    | MediaType::Wasm
+    | MediaType::Unknown => false,
+  }
+}
+
 /// Transform the graph into root specifiers that we can feed `tsc`. We have to
 /// provide the media type for root modules because `tsc` does not "resolve" the
 /// media type like other modules, as well as a root specifier needs any
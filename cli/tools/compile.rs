@@ -3,7 +3,13 @@
 use crate::args::CompileFlags;
 use crate::args::Flags;
 use crate::factory::CliFactory;
+use crate::graph_util::graph_asset_specifiers;
 use crate::standalone::is_standalone_binary;
+use crate::standalone::read_npm_vfs;
+use crate::standalone::read_standalone_binary;
+use crate::standalone::VfsEntry;
+use crate::standalone::VirtualDirectory;
+use crate::util::display;
 use crate::util::path::path_has_trailing_slash;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
@@ -12,6 +18,8 @@ use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_graph::GraphKind;
 use deno_runtime::colors;
+use deno_runtime::permissions::PermissionsOptions;
+use std::fmt::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -22,6 +30,10 @@ pub async fn compile(
   flags: Flags,
   compile_flags: CompileFlags,
 ) -> Result<(), AnyError> {
+  if compile_flags.show {
+    return show_compiled_binary(&compile_flags).await;
+  }
+
   let factory = CliFactory::from_flags(flags).await?;
   let cli_options = factory.cli_options();
   let module_graph_builder = factory.module_graph_builder().await?;
@@ -60,6 +72,7 @@ pub async fn compile(
     graph
   };
 
+  let assets = graph_asset_specifiers(&graph).into_iter().collect();
   let parser = parsed_source_cache.as_capturing_parser();
   let eszip = eszip::EszipV2::from_graph(graph, &parser, Default::default())?;
 
@@ -76,6 +89,7 @@ pub async fn compile(
     .write_bin(
       &mut file,
       eszip,
+      assets,
       &module_specifier,
       &compile_flags,
       cli_options,
@@ -95,6 +109,226 @@ pub async fn compile(
   Ok(())
 }
 
+/// Prints the metadata, embedded modules, npm packages and permissions baked
+/// into an existing executable produced by `deno compile`.
+async fn show_compiled_binary(
+  compile_flags: &CompileFlags,
+) -> Result<(), AnyError> {
+  let binary_path = PathBuf::from(&compile_flags.source_file);
+  if !compile_flags.args.is_empty() {
+    bail!("Script arguments cannot be used with --show.");
+  }
+  let Some((metadata, modules)) = read_standalone_binary(&binary_path)
+    .with_context(|| format!("Reading {}", binary_path.display()))?
+  else {
+    bail!(
+      "'{}' is not an executable produced by `deno compile`.",
+      binary_path.display()
+    );
+  };
+  let npm_vfs = read_npm_vfs(&binary_path)?;
+
+  let mut output = String::new();
+  let mut field = |name: &str, value: &dyn std::fmt::Display| {
+    writeln!(output, "{} {}", colors::bold(format!("{name}:")), value)
+  };
+  field("executable", &binary_path.display())?;
+  field("entrypoint", &metadata.entrypoint)?;
+  if !metadata.argv.is_empty() {
+    field("arguments", &metadata.argv.join(" "))?;
+  }
+  field("unstable", &metadata.unstable)?;
+  if let Some(seed) = metadata.seed {
+    field("seed", &seed)?;
+  }
+  if let Some(location) = &metadata.location {
+    field("location", location)?;
+  }
+  if !metadata.v8_flags.is_empty() {
+    field("v8 flags", &metadata.v8_flags.join(" "))?;
+  }
+  if let Some(log_level) = metadata.log_level {
+    field("log level", &log_level)?;
+  }
+  if let Some(ca_stores) = &metadata.ca_stores {
+    field("ca stores", &ca_stores.join(", "))?;
+  }
+  if let Some(ca_data) = &metadata.ca_data {
+    field("ca data", &display::human_size(ca_data.len() as f64))?;
+  }
+  if let Some(hosts) = &metadata.unsafely_ignore_certificate_errors {
+    field(
+      "unsafely ignore certificate errors",
+      &format_allow_list(hosts.iter()),
+    )?;
+  }
+  if let Some((base_url, _)) = &metadata.maybe_import_map {
+    field("import map", base_url)?;
+  }
+  field("node_modules dir", &metadata.node_modules_dir)?;
+  field("code cache", &metadata.code_cache)?;
+
+  writeln!(output)?;
+  writeln!(output, "{}", colors::bold("permissions:"))?;
+  for line in format_permissions(&metadata.permissions) {
+    writeln!(output, "  {line}")?;
+  }
+
+  let specifiers = modules.index().specifiers();
+  writeln!(output)?;
+  writeln!(
+    output,
+    "{} ({})",
+    colors::bold("modules:"),
+    specifiers.len()
+  )?;
+  for specifier in specifiers {
+    match modules.index().source_len(specifier) {
+      Some(size) => writeln!(
+        output,
+        "  {} {}",
+        specifier,
+        colors::gray(format!("({})", display::human_size(size as f64)))
+      )?,
+      None => writeln!(output, "  {specifier}")?,
+    }
+  }
+
+  if let Some(snapshot) = &metadata.npm_snapshot {
+    let mut package_ids = snapshot
+      .packages
+      .iter()
+      .map(|package| package.id.as_serialized())
+      .collect::<Vec<_>>();
+    package_ids.sort();
+    writeln!(output)?;
+    writeln!(
+      output,
+      "{} ({} packages, {} root)",
+      colors::bold("npm:"),
+      package_ids.len(),
+      snapshot.root_packages.len()
+    )?;
+    for id in package_ids {
+      writeln!(output, "  {id}")?;
+    }
+  }
+  if let Some(npm_vfs) = &npm_vfs {
+    let (file_count, total_size) = npm_vfs_summary(npm_vfs);
+    writeln!(
+      output,
+      "{} {} files ({})",
+      colors::bold("npm files:"),
+      file_count,
+      display::human_size(total_size as f64)
+    )?;
+  }
+
+  display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+  Ok(())
+}
+
+fn format_allow_list<T: std::fmt::Display>(
+  items: impl Iterator<Item = T>,
+) -> String {
+  let items = items.map(|item| item.to_string()).collect::<Vec<_>>();
+  if items.is_empty() {
+    "all".to_string()
+  } else {
+    items.join(", ")
+  }
+}
+
+/// Returns one line per granted or denied permission, mirroring the
+/// `--allow-*` and `--deny-*` flags the executable was compiled with.
+fn format_permissions(permissions: &PermissionsOptions) -> Vec<String> {
+  fn push<T: std::fmt::Display>(
+    lines: &mut Vec<String>,
+    flag: &str,
+    list: &Option<Vec<T>>,
+  ) {
+    if let Some(list) = list {
+      lines.push(format!("--{}: {}", flag, format_allow_list(list.iter())));
+    }
+  }
+
+  fn display_paths(
+    paths: &Option<Vec<PathBuf>>,
+  ) -> Option<Vec<std::path::Display<'_>>> {
+    paths
+      .as_ref()
+      .map(|paths| paths.iter().map(|p| p.display()).collect())
+  }
+
+  let mut lines = Vec::new();
+  push(&mut lines, "allow-env", &permissions.allow_env);
+  if permissions.allow_hrtime {
+    lines.push("--allow-hrtime".to_string());
+  }
+  push(&mut lines, "allow-net", &permissions.allow_net);
+  push(
+    &mut lines,
+    "allow-ffi",
+    &display_paths(&permissions.allow_ffi),
+  );
+  if let Some(allow_ffi_symbols) = &permissions.allow_ffi_symbols {
+    lines.push(format!(
+      "--allow-ffi-symbols: {}",
+      allow_ffi_symbols.display()
+    ));
+  }
+  push(
+    &mut lines,
+    "allow-read",
+    &display_paths(&permissions.allow_read),
+  );
+  push(&mut lines, "allow-run", &permissions.allow_run);
+  push(&mut lines, "allow-sys", &permissions.allow_sys);
+  push(
+    &mut lines,
+    "allow-write",
+    &display_paths(&permissions.allow_write),
+  );
+  push(&mut lines, "deny-env", &permissions.deny_env);
+  push(&mut lines, "deny-net", &permissions.deny_net);
+  push(
+    &mut lines,
+    "deny-ffi",
+    &display_paths(&permissions.deny_ffi),
+  );
+  push(
+    &mut lines,
+    "deny-read",
+    &display_paths(&permissions.deny_read),
+  );
+  push(&mut lines, "deny-run", &permissions.deny_run);
+  push(&mut lines, "deny-sys", &permissions.deny_sys);
+  push(
+    &mut lines,
+    "deny-write",
+    &display_paths(&permissions.deny_write),
+  );
+  if lines.is_empty() {
+    lines.push("none".to_string());
+  }
+  lines
+}
+
+/// Returns the number of files and their total size in bytes.
+fn npm_vfs_summary(dir: &VirtualDirectory) -> (usize, u64) {
+  dir
+    .entries
+    .iter()
+    .fold((0, 0), |(count, size), entry| match entry {
+      VfsEntry::Dir(dir) => {
+        let (dir_count, dir_size) = npm_vfs_summary(dir);
+        (count + dir_count, size + dir_size)
+      }
+      VfsEntry::File(file) => (count + 1, size + file.len),
+      VfsEntry::Symlink(_) => (count, size),
+    })
+}
+
 /// This function writes out a final binary to specified path. If output path
 /// is not already standalone binary it will return error instead.
 fn validate_output_path(output_path: &Path) -> Result<(), AnyError> {
@@ -213,6 +447,8 @@ mod test {
         args: Vec::new(),
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         include: vec![],
+        show: false,
+        code_cache: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -234,6 +470,8 @@ mod test {
         args: Vec::new(),
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
+        show: false,
+        code_cache: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -268,4 +506,29 @@ mod test {
     run_test("C:\\my-exe.0.1.2", Some("windows"), "C:\\my-exe.0.1.2.exe");
     run_test("my-exe-0.1.2", Some("linux"), "my-exe-0.1.2");
   }
+
+  #[test]
+  fn test_format_permissions() {
+    assert_eq!(
+      format_permissions(&PermissionsOptions::default()),
+      vec!["none"]
+    );
+    assert_eq!(
+      format_permissions(&PermissionsOptions {
+        allow_env: Some(vec![]),
+        allow_hrtime: true,
+        allow_net: Some(vec!["deno.land".to_string(), "localhost".to_string()]),
+        allow_read: Some(vec![PathBuf::from("data")]),
+        deny_net: Some(vec!["localhost:8080".to_string()]),
+        ..Default::default()
+      }),
+      vec![
+        "--allow-env: all",
+        "--allow-hrtime",
+        "--allow-net: deno.land, localhost",
+        "--allow-read: data",
+        "--deny-net: localhost:8080",
+      ]
+    );
+  }
 }
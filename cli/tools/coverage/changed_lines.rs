@@ -0,0 +1,174 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The lines that were added or modified since a git ref, which is what
+//! `deno coverage --changed-since` reports the coverage of.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ChangedLines {
+  /// The zero based indexes of the changed lines, by absolute file path. All
+  /// the lines of new files are changed, which is represented by `None`.
+  files: HashMap<PathBuf, Option<HashSet<usize>>>,
+}
+
+impl ChangedLines {
+  /// Asks git about the working tree of the repository that `cwd` is in,
+  /// including the files that are staged and the ones that are untracked.
+  pub fn from_git(cwd: &Path, git_ref: &str) -> Result<Self, AnyError> {
+    let root = git(cwd, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root.trim());
+    let diff = git(
+      &root,
+      &[
+        "diff",
+        "--unified=0",
+        "--no-color",
+        "--no-ext-diff",
+        "--no-renames",
+        // the ref can't be taken for an option
+        "--end-of-options",
+        git_ref,
+        "--",
+      ],
+    )?;
+    let mut changed_lines = Self::from_diff(&root, &diff);
+    let untracked =
+      git(&root, &["ls-files", "--others", "--exclude-standard"])?;
+    for path in untracked.lines().filter(|line| !line.is_empty()) {
+      changed_lines.files.insert(root.join(path), None);
+    }
+    Ok(changed_lines)
+  }
+
+  /// Parses the output of `git diff --unified=0`, whose paths are relative to
+  /// `root`.
+  pub fn from_diff(root: &Path, diff: &str) -> Self {
+    let mut files: HashMap<PathBuf, Option<HashSet<usize>>> = HashMap::new();
+    let mut current_path: Option<PathBuf> = None;
+    // the added lines of a hunk can look like headers
+    let mut in_header = false;
+    for line in diff.lines() {
+      if line.starts_with("diff --git ") {
+        in_header = true;
+        current_path = None;
+      } else if let Some(hunk) = line.strip_prefix("@@ ") {
+        in_header = false;
+        let (Some(path), Some(lines)) =
+          (&current_path, parse_hunk_new_lines(hunk))
+        else {
+          continue;
+        };
+        if let Some(Some(changed)) = files.get_mut(path) {
+          changed.extend(lines);
+        }
+      } else if let Some(path) = line.strip_prefix("+++ ") {
+        if !in_header {
+          continue;
+        }
+        // deleted files are diffed against /dev/null
+        current_path = path.strip_prefix("b/").map(|path| root.join(path));
+        if let Some(path) = &current_path {
+          files
+            .entry(path.clone())
+            .or_insert_with(|| Some(HashSet::new()));
+        }
+      }
+    }
+    Self { files }
+  }
+
+  pub fn is_changed(&self, path: &Path, line_index: usize) -> bool {
+    match self.files.get(path) {
+      Some(Some(lines)) => lines.contains(&line_index),
+      Some(None) => true,
+      None => false,
+    }
+  }
+}
+
+/// The zero based indexes of the lines of the new file of a hunk header,
+/// like `-10,2 +10,3 @@ fn main() {`.
+fn parse_hunk_new_lines(hunk: &str) -> Option<std::ops::Range<usize>> {
+  let new_range = hunk.split(' ').find_map(|part| part.strip_prefix('+'))?;
+  let (start, count) = match new_range.split_once(',') {
+    Some((start, count)) => (start.parse::<usize>().ok()?, count.parse().ok()?),
+    None => (new_range.parse::<usize>().ok()?, 1),
+  };
+  // a hunk that only removes lines has a count of 0
+  let start = start.saturating_sub(1);
+  Some(start..start + count)
+}
+
+fn git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let output = Command::new("git")
+    // paths that aren't ASCII are printed as they are instead of quoted
+    .args(["-c", "core.quotePath=false"])
+    .args(args)
+    .current_dir(cwd)
+    .output()
+    .context("Failed to run git, which --changed-since requires")?;
+  if !output.status.success() {
+    bail!(
+      "git {} failed: {}",
+      args[0],
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  Ok(String::from_utf8(output.stdout)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hunk_new_lines() {
+    assert_eq!(
+      parse_hunk_new_lines("-10,2 +10,3 @@ fn main() {"),
+      Some(9..12)
+    );
+    assert_eq!(parse_hunk_new_lines("-1 +1 @@"), Some(0..1));
+    assert_eq!(parse_hunk_new_lines("-5,2 +4,0 @@"), Some(3..3));
+    assert_eq!(parse_hunk_new_lines("garbage"), None);
+  }
+
+  #[test]
+  fn changed_lines_from_diff() {
+    let root = PathBuf::from("/repo");
+    let diff = r#"diff --git a/src/a.ts b/src/a.ts
+index 1111111..2222222 100644
+--- a/src/a.ts
++++ b/src/a.ts
+@@ -2,0 +3,2 @@ export function a() {
++  const b = 1;
++  return b;
+@@ -10 +12 @@ export function c() {
+-  return 1;
++  return 2;
+diff --git a/src/removed.ts b/src/removed.ts
+deleted file mode 100644
+--- a/src/removed.ts
++++ /dev/null
+@@ -1,2 +0,0 @@
+-export const a = 1;
+-export const b = 2;
+"#;
+    let changed_lines = ChangedLines::from_diff(&root, diff);
+    let a = root.join("src/a.ts");
+    let changed = (0..20)
+      .filter(|index| changed_lines.is_changed(&a, *index))
+      .collect::<Vec<_>>();
+    assert_eq!(changed, vec![2, 3, 11]);
+    assert!(!changed_lines.is_changed(&root.join("src/removed.ts"), 0));
+    assert!(!changed_lines.is_changed(&root.join("src/other.ts"), 0));
+  }
+}
@@ -7,6 +7,7 @@ use crate::colors;
 use crate::factory::CliFactory;
 use crate::tools::fmt::format_json;
 use crate::tools::test::is_supported_test_path;
+use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
 use crate::util::text_encoding::source_map_from_code;
 
@@ -33,10 +34,12 @@ use std::path::PathBuf;
 use text_lines::TextLines;
 use uuid::Uuid;
 
+mod changed_lines;
 mod json_types;
 mod merge;
 mod range_tree;
 
+use changed_lines::ChangedLines;
 use json_types::*;
 
 pub struct CoverageCollector {
@@ -170,6 +173,18 @@ struct CoverageReport {
   output: Option<PathBuf>,
 }
 
+impl CoverageReport {
+  /// Only keeps the lines, functions and branches that are on the lines
+  /// `keep` returns `true` for.
+  fn retain_lines(&mut self, keep: impl Fn(usize) -> bool) {
+    self.found_lines.retain(|(line_index, _)| keep(*line_index));
+    self
+      .named_functions
+      .retain(|function| keep(function.line_index));
+    self.branches.retain(|branch| keep(branch.line_index));
+  }
+}
+
 fn generate_coverage_report(
   script_coverage: &ScriptCoverage,
   script_source: String,
@@ -629,6 +644,13 @@ pub async fn cover_files(
   let cli_options = factory.cli_options();
   let emitter = factory.emitter()?;
 
+  let maybe_changed_lines = match &coverage_flags.changed_since {
+    Some(git_ref) => {
+      Some(ChangedLines::from_git(cli_options.initial_cwd(), git_ref)?)
+    }
+    None => None,
+  };
+
   let script_coverages = collect_coverages(coverage_flags.files)?;
   let script_coverages = filter_coverages(
     script_coverages,
@@ -667,6 +689,8 @@ pub async fn cover_files(
     None => None,
   };
 
+  let mut changed_lines_hit = 0;
+  let mut changed_lines_found = 0;
   for script_coverage in script_coverages {
     let module_specifier = deno_core::resolve_url_or_path(
       &script_coverage.url,
@@ -720,13 +744,28 @@ pub async fn cover_files(
     };
 
     let source_map = source_map_from_code(&transpiled_code);
-    let coverage_report = generate_coverage_report(
+    let mut coverage_report = generate_coverage_report(
       &script_coverage,
       transpiled_code.as_str().to_owned(),
       &source_map,
       &out_mode,
     );
 
+    if let Some(changed_lines) = &maybe_changed_lines {
+      let Ok(path) = coverage_report.url.to_file_path() else {
+        continue;
+      };
+      let path = canonicalize_path(&path).unwrap_or(path);
+      coverage_report
+        .retain_lines(|line_index| changed_lines.is_changed(&path, line_index));
+      changed_lines_found += coverage_report.found_lines.len();
+      changed_lines_hit += coverage_report
+        .found_lines
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .count();
+    }
+
     if !coverage_report.found_lines.is_empty() {
       reporter.report(&coverage_report, &original_source)?;
     }
@@ -734,5 +773,35 @@ pub async fn cover_files(
 
   reporter.done();
 
+  if maybe_changed_lines.is_some() {
+    check_changed_lines_coverage(
+      changed_lines_hit,
+      changed_lines_found,
+      coverage_flags.changed_threshold,
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Fails if less than `threshold` percent of the changed lines are covered.
+fn check_changed_lines_coverage(
+  lines_hit: usize,
+  lines_found: usize,
+  threshold: u8,
+) -> Result<(), AnyError> {
+  if lines_found == 0 {
+    log::info!("No changed lines to cover.");
+    return Ok(());
+  }
+  let ratio = lines_hit as f32 / lines_found as f32;
+  let line_coverage =
+    format!("{:.3}% ({}/{})", ratio * 100.0, lines_hit, lines_found);
+  if ratio * 100.0 < threshold as f32 {
+    return Err(generic_error(format!(
+      "Coverage of the changed lines is {line_coverage}, below the threshold of {threshold}%"
+    )));
+  }
+  log::info!("Coverage of the changed lines is {}", line_coverage);
   Ok(())
 }
@@ -9,12 +9,17 @@
 
 use crate::args::CliOptions;
 use crate::args::FilesConfig;
+use crate::args::FmtDiffFormat;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
 use crate::args::ProseWrap;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::util::diff::diff;
+use crate::util::diff::diff_hunks;
+use crate::util::diff::unified_diff;
+use crate::util::diff::DiffHunk;
+use crate::util::display;
 use crate::util::file_watcher;
 use crate::util::file_watcher::ResolutionResult;
 use crate::util::fs::FileCollector;
@@ -28,6 +33,9 @@ use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde::Deserialize;
+use deno_core::serde::Serialize;
+use deno_core::serde_json;
 use deno_core::task::spawn_blocking;
 use log::debug;
 use log::info;
@@ -35,6 +43,7 @@ use log::warn;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
+use std::io::BufRead;
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
@@ -50,6 +59,9 @@ pub async fn format(
   cli_options: CliOptions,
   fmt_options: FmtOptions,
 ) -> Result<(), AnyError> {
+  if fmt_options.is_stdin_batch {
+    return format_stdin_batch(fmt_options);
+  }
   if fmt_options.is_stdin {
     return format_stdin(
       fmt_options,
@@ -63,6 +75,8 @@ pub async fn format(
 
   let files = fmt_options.files;
   let check = fmt_options.check;
+  let diff_format = fmt_options.diff_format;
+  let diff_context = fmt_options.diff_context;
   let fmt_config_options = fmt_options.options;
 
   let resolver = |changed: Option<Vec<PathBuf>>| {
@@ -112,7 +126,14 @@ pub async fn format(
       &paths,
     ));
     if check {
-      check_source_files(paths, fmt_options, incremental_cache.clone()).await?;
+      check_source_files(
+        paths,
+        fmt_options,
+        incremental_cache.clone(),
+        diff_format,
+        diff_context,
+      )
+      .await?;
     } else {
       format_source_files(paths, fmt_options, incremental_cache.clone())
         .await?;
@@ -128,6 +149,7 @@ pub async fn format(
       file_watcher::PrintConfig {
         job_name: "Fmt".to_string(),
         clear_screen: !cli_options.no_clear_screen(),
+        restart_hooks: cli_options.resolve_watch_restart_hooks()?,
       },
     )
     .await?;
@@ -150,6 +172,7 @@ fn collect_fmt_files(files: &FilesConfig) -> Result<Vec<PathBuf>, AnyError> {
     .ignore_git_folder()
     .ignore_node_modules()
     .add_ignore_paths(&files.exclude)
+    .use_ignore_files(!files.no_ignore)
     .collect_files(&files.include)
 }
 
@@ -252,13 +275,32 @@ pub fn format_parsed_source(
   )
 }
 
+#[derive(Serialize)]
+struct JsonFileDiff {
+  file: String,
+  hunks: Vec<DiffHunk>,
+}
+
+/// Returns the path relative to the current directory with forward slashes,
+/// which is what tools applying patches expect.
+fn diff_path_display(file_path: &Path) -> String {
+  let path = std::env::current_dir()
+    .ok()
+    .and_then(|cwd| file_path.strip_prefix(cwd).ok().map(|p| p.to_path_buf()))
+    .unwrap_or_else(|| file_path.to_path_buf());
+  path.to_string_lossy().replace('\\', "/")
+}
+
 async fn check_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
   incremental_cache: Arc<IncrementalCache>,
+  diff_format: FmtDiffFormat,
+  diff_context: usize,
 ) -> Result<(), AnyError> {
   let not_formatted_files_count = Arc::new(AtomicUsize::new(0));
   let checked_files_count = Arc::new(AtomicUsize::new(0));
+  let json_diffs = Arc::new(Mutex::new(Vec::new()));
 
   // prevent threads outputting at the same time
   let output_lock = Arc::new(Mutex::new(0));
@@ -266,6 +308,7 @@ async fn check_source_files(
   run_parallelized(paths, {
     let not_formatted_files_count = not_formatted_files_count.clone();
     let checked_files_count = checked_files_count.clone();
+    let json_diffs = json_diffs.clone();
     move |file_path| {
       checked_files_count.fetch_add(1, Ordering::Relaxed);
       let file_text = read_file_contents(&file_path)?.text;
@@ -278,11 +321,31 @@ async fn check_source_files(
       match format_file(&file_path, &file_text, &fmt_options) {
         Ok(Some(formatted_text)) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
-          let _g = output_lock.lock();
-          let diff = diff(&file_text, &formatted_text);
-          info!("");
-          info!("{} {}:", colors::bold("from"), file_path.display());
-          info!("{}", diff);
+          match diff_format {
+            FmtDiffFormat::Pretty => {
+              let _g = output_lock.lock();
+              let diff = diff(&file_text, &formatted_text);
+              info!("");
+              info!("{} {}:", colors::bold("from"), file_path.display());
+              info!("{}", diff);
+            }
+            FmtDiffFormat::Unified => {
+              let diff = unified_diff(
+                &diff_path_display(&file_path),
+                &file_text,
+                &formatted_text,
+                diff_context,
+              );
+              let _g = output_lock.lock();
+              display::write_to_stdout_ignore_sigpipe(diff.as_bytes())?;
+            }
+            FmtDiffFormat::Json => {
+              json_diffs.lock().push(JsonFileDiff {
+                file: diff_path_display(&file_path),
+                hunks: diff_hunks(&file_text, &formatted_text, diff_context),
+              });
+            }
+          }
         }
         Ok(None) => {
           // When checking formatting, only update the incremental cache when
@@ -317,6 +380,12 @@ async fn check_source_files(
   })
   .await?;
 
+  if diff_format == FmtDiffFormat::Json {
+    let mut json_diffs = std::mem::take(&mut *json_diffs.lock());
+    json_diffs.sort_by(|a, b| a.file.cmp(&b.file));
+    display::write_json_to_stdout(&json_diffs)?;
+  }
+
   let not_formatted_files_count =
     not_formatted_files_count.load(Ordering::Relaxed);
   let checked_files_count = checked_files_count.load(Ordering::Relaxed);
@@ -486,6 +555,79 @@ fn format_stdin(fmt_options: FmtOptions, ext: &str) -> Result<(), AnyError> {
   Ok(())
 }
 
+#[derive(Deserialize)]
+struct StdinBatchFile {
+  path: String,
+  contents: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StdinBatchResult {
+  path: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  changed: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  contents: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+}
+
+/// Format the files read from stdin, one JSON object per line, and write a
+/// JSON line with the result of each one to stdout as soon as it's formatted,
+/// so editors and hooks can format many files without spawning a process
+/// for each. The paths are only used to pick the formatter.
+/// Compatible with `--check` flag.
+fn format_stdin_batch(fmt_options: FmtOptions) -> Result<(), AnyError> {
+  let mut not_formatted_files_count = 0;
+  let mut stdout = stdout().lock();
+  for line in stdin().lock().lines() {
+    let line = line.context("Failed to read from stdin")?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let file: StdinBatchFile = serde_json::from_str(&line)
+      .with_context(|| format!("Invalid stdin batch entry: {line}"))?;
+    let result = match format_file(
+      Path::new(&file.path),
+      &file.contents,
+      &fmt_options.options,
+    ) {
+      Ok(formatted_text) => {
+        let changed = formatted_text.is_some();
+        if changed {
+          not_formatted_files_count += 1;
+        }
+        StdinBatchResult {
+          path: file.path,
+          changed: Some(changed),
+          contents: (!fmt_options.check)
+            .then(|| formatted_text.unwrap_or(file.contents)),
+          error: None,
+        }
+      }
+      Err(e) => StdinBatchResult {
+        path: file.path,
+        changed: None,
+        contents: None,
+        error: Some(e.to_string()),
+      },
+    };
+    serde_json::to_writer(&mut stdout, &result)?;
+    stdout.write_all(b"\n")?;
+    stdout.flush()?;
+  }
+
+  if fmt_options.check && not_formatted_files_count > 0 {
+    bail!(
+      "Found {} not formatted {}",
+      not_formatted_files_count,
+      files_str(not_formatted_files_count),
+    );
+  }
+  Ok(())
+}
+
 fn files_str(len: usize) -> &'static str {
   if len <= 1 {
     "file"
@@ -1,11 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
 
 use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::bail;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_core::serde_json;
@@ -24,10 +26,18 @@ use deno_runtime::colors;
 use deno_semver::npm::NpmPackageNv;
 use deno_semver::npm::NpmPackageNvReference;
 use deno_semver::npm::NpmPackageReqReference;
+use indexmap::IndexMap;
+use serde::Serialize;
 
 use crate::args::Flags;
 use crate::args::InfoFlags;
+use crate::args::InfoGraphFormat;
+use crate::args::LicensesConfig;
+use crate::cache::CacheStatus;
+use crate::cache::HttpCache;
 use crate::display;
+use crate::emit::is_emittable;
+use crate::emit::Emitter;
 use crate::factory::CliFactory;
 use crate::graph_util::graph_lock_or_exit;
 use crate::npm::CliNpmResolver;
@@ -51,9 +61,56 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
       graph_lock_or_exit(&graph, &mut lockfile.lock());
     }
 
+    if info_flags.licenses {
+      let licenses_config = cli_options.resolve_licenses_config()?;
+      let report = LicenseReport::build(
+        &graph,
+        npm_resolver,
+        &factory.file_fetcher()?.http_cache,
+        &licenses_config,
+      );
+      if info_flags.json {
+        display::write_json_to_stdout(&report)?;
+      } else {
+        let mut output = String::new();
+        report.write(&mut output)?;
+        display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+      }
+      let denied_count = report.count(LicenseStatus::Denied);
+      if denied_count > 0 {
+        bail!(
+          "{} {} a denied license.",
+          denied_count,
+          if denied_count == 1 {
+            "dependency has"
+          } else {
+            "dependencies have"
+          }
+        );
+      }
+      return Ok(());
+    }
+
+    if let Some(format) = info_flags.graph_format {
+      let mut output = String::new();
+      GraphFormatWriter::write(&graph, npm_resolver, format, &mut output)?;
+      display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+      return Ok(());
+    }
+
     if info_flags.json {
       let mut json_graph = json!(graph);
       add_npm_packages_to_json(&mut json_graph, npm_resolver);
+      let check_statuses = factory
+        .type_checker()
+        .await?
+        .module_check_statuses(&graph, cli_options.ts_type_lib_window())?;
+      add_cache_statuses_to_json(
+        &mut json_graph,
+        &graph,
+        factory.emitter()?,
+        &check_statuses,
+      );
       display::write_json_to_stdout(&json_graph)?;
     } else {
       let mut output = String::new();
@@ -144,6 +201,42 @@ fn print_cache_info(
   }
 }
 
+/// Adds whether the emit and the type checking of each module are cached,
+/// and if not, why.
+fn add_cache_statuses_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+  emitter: &Emitter,
+  check_statuses: &HashMap<ModuleSpecifier, CacheStatus>,
+) {
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  let Some(modules) = modules else {
+    return;
+  };
+  for module in modules.iter_mut().filter_map(|m| m.as_object_mut()) {
+    let specifier = module
+      .get("specifier")
+      .and_then(|specifier| specifier.as_str())
+      .and_then(|specifier| ModuleSpecifier::parse(specifier).ok());
+    let maybe_module = specifier.and_then(|specifier| graph.get(&specifier));
+    let Some(Module::Esm(esm_module)) = maybe_module else {
+      continue;
+    };
+    let mut statuses = serde_json::Map::new();
+    if is_emittable(esm_module.media_type) {
+      let status =
+        emitter.emit_status(&esm_module.specifier, &esm_module.source);
+      statuses.insert("emit".to_string(), json!(status));
+    }
+    if let Some(status) = check_statuses.get(&esm_module.specifier) {
+      statuses.insert("typeCheck".to_string(), json!(status));
+    }
+    if !statuses.is_empty() {
+      module.insert("cacheStatus".to_string(), statuses.into());
+    }
+  }
+}
+
 fn add_npm_packages_to_json(
   json: &mut serde_json::Value,
   npm_resolver: &CliNpmResolver,
@@ -690,6 +783,471 @@ impl<'a> GraphDisplayContext<'a> {
   }
 }
 
+/// Where a module in the graph comes from, which its node is colored by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GraphNodeOrigin {
+  Local,
+  Remote,
+  Npm,
+  Node,
+  Other,
+}
+
+impl GraphNodeOrigin {
+  fn from_specifier(specifier: &ModuleSpecifier) -> Self {
+    match specifier.scheme() {
+      "file" => Self::Local,
+      "http" | "https" => Self::Remote,
+      "npm" => Self::Npm,
+      "node" => Self::Node,
+      _ => Self::Other,
+    }
+  }
+
+  fn color(&self) -> &'static str {
+    match self {
+      Self::Local => "#bbdefb",
+      Self::Remote => "#ffe0b2",
+      Self::Npm => "#ffcdd2",
+      Self::Node => "#c8e6c9",
+      Self::Other => "#e0e0e0",
+    }
+  }
+}
+
+struct GraphNode {
+  label: String,
+  size: Option<u64>,
+  origin: GraphNodeOrigin,
+  is_root: bool,
+}
+
+impl GraphNode {
+  /// How much bigger than the smallest ones the node is drawn, growing with
+  /// the logarithm of its size so that big modules don't dwarf the others.
+  fn scale(&self) -> f64 {
+    let size = self.size.unwrap_or(0) as f64;
+    1.0 + (1.0 + size / 1024.0).log10()
+  }
+
+  fn text(&self) -> Vec<String> {
+    let mut text = vec![self.label.clone()];
+    if let Some(size) = self.size {
+      text.push(display::human_size(size as f64));
+    }
+    text
+  }
+}
+
+/// Outputs the module graph in a graph description language, for
+/// `deno info --graph-format`.
+#[derive(Default)]
+struct GraphFormatWriter {
+  nodes: IndexMap<String, GraphNode>,
+  /// The edges between the indexes of the nodes, and whether they are only
+  /// for types.
+  edges: IndexMap<(usize, usize), bool>,
+}
+
+impl GraphFormatWriter {
+  pub fn write<TWrite: Write>(
+    graph: &ModuleGraph,
+    npm_resolver: &CliNpmResolver,
+    format: InfoGraphFormat,
+    writer: &mut TWrite,
+  ) -> fmt::Result {
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = NpmInfo::build(graph, npm_resolver, &npm_snapshot);
+    let mut this = Self::default();
+    this.add_modules(graph, &npm_info);
+    match format {
+      InfoGraphFormat::Dot => this.write_dot(writer),
+      InfoGraphFormat::Mermaid => this.write_mermaid(writer),
+    }
+  }
+
+  fn add_modules(&mut self, graph: &ModuleGraph, npm_info: &NpmInfo) {
+    let roots = graph
+      .roots
+      .iter()
+      .map(|root| graph.resolve(root))
+      .collect::<HashSet<_>>();
+    for module in graph.modules() {
+      let index = self.add_module(module, npm_info, &roots);
+      if let Some(package) = module
+        .npm()
+        .and_then(|npm| npm_info.resolve_package(&npm.nv_reference.nv))
+      {
+        self.add_npm_package_deps(index, package, npm_info);
+      }
+      let Some(module) = module.esm() else {
+        continue;
+      };
+      let mut add_edge = |resolution: &Resolution, type_only: bool| {
+        if let Some(specifier) = resolution.maybe_specifier() {
+          let specifier = graph.resolve(specifier);
+          if let Ok(Some(dep)) = graph.try_get(&specifier) {
+            let dep_index = self.add_module(dep, npm_info, &roots);
+            self.add_edge(index, dep_index, type_only);
+          }
+        }
+      };
+      if let Some(types_dep) = &module.maybe_types_dependency {
+        add_edge(&types_dep.dependency, true);
+      }
+      for dep in module.dependencies.values() {
+        add_edge(&dep.maybe_code, false);
+        add_edge(&dep.maybe_type, true);
+      }
+    }
+  }
+
+  fn add_module(
+    &mut self,
+    module: &Module,
+    npm_info: &NpmInfo,
+    roots: &HashSet<ModuleSpecifier>,
+  ) -> usize {
+    let specifier = module.specifier();
+    let maybe_package = module
+      .npm()
+      .and_then(|npm| npm_info.resolve_package(&npm.nv_reference.nv));
+    let (key, size) = match maybe_package {
+      Some(package) => (
+        format!("npm:{}", package.id.as_serialized()),
+        npm_info.package_sizes.get(&package.id).copied(),
+      ),
+      None => (
+        specifier.to_string(),
+        match module {
+          Module::Esm(module) => Some(module.size() as u64),
+          Module::Json(module) => Some(module.size() as u64),
+          Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+        },
+      ),
+    };
+    let entry = self.nodes.entry(key.clone());
+    let index = entry.index();
+    entry.or_insert_with(|| GraphNode {
+      label: key,
+      size,
+      origin: GraphNodeOrigin::from_specifier(specifier),
+      is_root: roots.contains(specifier),
+    });
+    index
+  }
+
+  fn add_npm_package_deps(
+    &mut self,
+    index: usize,
+    package: &NpmResolutionPackage,
+    npm_info: &NpmInfo,
+  ) {
+    let mut deps = package.dependencies.values().collect::<Vec<_>>();
+    deps.sort();
+    for dep_id in deps {
+      let key = format!("npm:{}", dep_id.as_serialized());
+      let is_new = !self.nodes.contains_key(&key);
+      let entry = self.nodes.entry(key.clone());
+      let dep_index = entry.index();
+      entry.or_insert_with(|| GraphNode {
+        label: key,
+        size: npm_info.package_sizes.get(dep_id).copied(),
+        origin: GraphNodeOrigin::Npm,
+        is_root: false,
+      });
+      self.add_edge(index, dep_index, false);
+      if is_new {
+        if let Some(dep) = npm_info.packages.get(dep_id) {
+          self.add_npm_package_deps(dep_index, dep, npm_info);
+        }
+      }
+    }
+  }
+
+  fn add_edge(&mut self, from: usize, to: usize, type_only: bool) {
+    // an edge that isn't only for types takes precedence
+    let edge_type_only = self.edges.entry((from, to)).or_insert(type_only);
+    *edge_type_only &= type_only;
+  }
+
+  fn write_dot<TWrite: Write>(&self, writer: &mut TWrite) -> fmt::Result {
+    fn escape(text: &str) -> String {
+      text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    writeln!(writer, "digraph {{")?;
+    writeln!(
+      writer,
+      "  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];"
+    )?;
+    for (index, node) in self.nodes.values().enumerate() {
+      let label = node
+        .text()
+        .iter()
+        .map(|text| escape(text))
+        .collect::<Vec<_>>()
+        .join("\\n");
+      let scale = node.scale();
+      write!(
+        writer,
+        "  n{index} [label=\"{label}\", fillcolor=\"{}\", width={:.2}, height={:.2}",
+        node.origin.color(),
+        0.75 * scale,
+        0.5 * scale,
+      )?;
+      if node.is_root {
+        write!(writer, ", penwidth=2")?;
+      }
+      writeln!(writer, "];")?;
+    }
+    for ((from, to), type_only) in &self.edges {
+      if *type_only {
+        writeln!(writer, "  n{from} -> n{to} [style=dashed];")?;
+      } else {
+        writeln!(writer, "  n{from} -> n{to};")?;
+      }
+    }
+    writeln!(writer, "}}")
+  }
+
+  fn write_mermaid<TWrite: Write>(&self, writer: &mut TWrite) -> fmt::Result {
+    fn escape(text: &str) -> String {
+      text.replace('"', "#quot;")
+    }
+
+    writeln!(writer, "flowchart LR")?;
+    for (index, node) in self.nodes.values().enumerate() {
+      let label = node
+        .text()
+        .iter()
+        .map(|text| escape(text))
+        .collect::<Vec<_>>()
+        .join("<br/>");
+      writeln!(writer, "  n{index}[\"{label}\"]")?;
+    }
+    for ((from, to), type_only) in &self.edges {
+      if *type_only {
+        writeln!(writer, "  n{from} -.-> n{to}")?;
+      } else {
+        writeln!(writer, "  n{from} --> n{to}")?;
+      }
+    }
+    for (index, node) in self.nodes.values().enumerate() {
+      write!(
+        writer,
+        "  style n{index} fill:{},font-size:{:.0}px",
+        node.origin.color(),
+        12.0 * node.scale(),
+      )?;
+      if node.is_root {
+        write!(writer, ",stroke-width:3px")?;
+      }
+      writeln!(writer)?;
+    }
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum LicenseStatus {
+  Allowed,
+  Denied,
+  Unknown,
+}
+
+#[derive(Debug, Serialize)]
+struct LicenseEntry {
+  package: String,
+  license: Option<String>,
+  status: LicenseStatus,
+}
+
+/// The licenses of all the npm packages and remote modules in a graph.
+#[derive(Debug, Serialize)]
+struct LicenseReport {
+  packages: Vec<LicenseEntry>,
+}
+
+impl LicenseReport {
+  pub fn build(
+    graph: &ModuleGraph,
+    npm_resolver: &CliNpmResolver,
+    http_cache: &HttpCache,
+    config: &LicensesConfig,
+  ) -> Self {
+    // sorted by package name so the output is deterministic
+    let mut licenses: BTreeMap<String, Option<String>> = BTreeMap::new();
+
+    for module in graph.modules() {
+      let specifier = module.specifier();
+      if !matches!(specifier.scheme(), "http" | "https") {
+        continue;
+      }
+      let license = licenses.entry(remote_package_name(specifier)).or_default();
+      if license.is_none() {
+        *license = http_cache
+          .get(specifier)
+          .ok()
+          .and_then(|(_, headers, _)| headers.get("x-license").cloned());
+      }
+    }
+
+    let npm_snapshot = npm_resolver.snapshot();
+    let npm_info = NpmInfo::build(graph, npm_resolver, &npm_snapshot);
+    for id in npm_info.packages.keys() {
+      let license = npm_resolver
+        .resolve_pkg_folder_from_pkg_id(id)
+        .ok()
+        .and_then(|folder| {
+          std::fs::read_to_string(folder.join("package.json")).ok()
+        })
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .and_then(|package_json| license_from_package_json(&package_json));
+      licenses.insert(format!("npm:{}", id.nv), license);
+    }
+
+    let packages = licenses
+      .into_iter()
+      .map(|(package, license)| {
+        let status = resolve_license_status(license.as_deref(), config);
+        LicenseEntry {
+          package,
+          license,
+          status,
+        }
+      })
+      .collect();
+    Self { packages }
+  }
+
+  pub fn count(&self, status: LicenseStatus) -> usize {
+    self
+      .packages
+      .iter()
+      .filter(|entry| entry.status == status)
+      .count()
+  }
+
+  pub fn write<TWrite: Write>(&self, writer: &mut TWrite) -> fmt::Result {
+    let package_width = self
+      .packages
+      .iter()
+      .map(|entry| entry.package.chars().count())
+      .max()
+      .unwrap_or(0);
+    for entry in &self.packages {
+      let padding = " ".repeat(package_width - entry.package.chars().count());
+      let license = entry.license.as_deref().unwrap_or("unknown");
+      let license_text = match entry.status {
+        LicenseStatus::Allowed => license.to_string(),
+        LicenseStatus::Denied => {
+          format!("{} {}", colors::red(license), colors::red_bold("(denied)"))
+        }
+        LicenseStatus::Unknown => colors::yellow(license).to_string(),
+      };
+      writeln!(writer, "{}{}  {}", entry.package, padding, license_text)?;
+    }
+    writeln!(writer)?;
+    writeln!(
+      writer,
+      "{} {} packages, {} denied, {} unknown",
+      colors::bold("licenses:"),
+      self.packages.len(),
+      self.count(LicenseStatus::Denied),
+      self.count(LicenseStatus::Unknown),
+    )
+  }
+}
+
+/// Groups remote modules into "packages" so that a registry module like
+/// `https://deno.land/x/oak@v12.0.0/mod.ts` is reported once for all of its
+/// files. Falls back to the origin for other hosts.
+fn remote_package_name(specifier: &ModuleSpecifier) -> String {
+  let origin = specifier.origin().ascii_serialization();
+  let mut segments = specifier.path_segments().into_iter().flatten();
+  match segments.next() {
+    Some("x") => match segments.next() {
+      Some(name) => format!("{origin}/x/{name}"),
+      None => origin,
+    },
+    Some(first) if first.contains('@') => format!("{origin}/{first}"),
+    _ => origin,
+  }
+}
+
+fn license_from_package_json(
+  package_json: &serde_json::Value,
+) -> Option<String> {
+  match package_json.get("license") {
+    Some(serde_json::Value::String(license)) => return Some(license.clone()),
+    // deprecated: { "license": { "type": "MIT", "url": "..." } }
+    Some(serde_json::Value::Object(license)) => {
+      if let Some(license) = license.get("type").and_then(|t| t.as_str()) {
+        return Some(license.to_string());
+      }
+    }
+    _ => {}
+  }
+  // deprecated: { "licenses": [{ "type": "MIT", "url": "..." }] }
+  let licenses = package_json
+    .get("licenses")?
+    .as_array()?
+    .iter()
+    .filter_map(|license| license.get("type")?.as_str())
+    .collect::<Vec<_>>();
+  if licenses.is_empty() {
+    None
+  } else {
+    Some(licenses.join(" OR "))
+  }
+}
+
+/// Checks a (possibly compound) SPDX license expression against the
+/// configuration. An `OR` expression is allowed when any of its alternatives
+/// is, an `AND` expression only when all of its licenses are.
+fn resolve_license_status(
+  license: Option<&str>,
+  config: &LicensesConfig,
+) -> LicenseStatus {
+  let license = match license {
+    Some(license) => license.trim(),
+    None => return LicenseStatus::Unknown,
+  };
+  if license.is_empty()
+    || license.eq_ignore_ascii_case("UNLICENSED")
+    || license.to_ascii_uppercase().starts_with("SEE LICENSE IN")
+  {
+    return LicenseStatus::Unknown;
+  }
+
+  let is_allowed = |id: &str| {
+    let matches_id = |other: &String| other.eq_ignore_ascii_case(id);
+    !config.deny.iter().any(matches_id)
+      && (config.allow.is_empty() || config.allow.iter().any(matches_id))
+  };
+  let expression = license.replace(['(', ')'], " ");
+  let tokens = expression.split_whitespace().collect::<Vec<_>>();
+  // exceptions like "GPL-2.0 WITH Classpath-exception-2.0" are ignored
+  let is_term_allowed =
+    |term: &[&str]| term.first().map(|id| is_allowed(id)).unwrap_or(false);
+  let allowed =
+    tokens
+      .split(|token| token.eq_ignore_ascii_case("OR"))
+      .any(|alternative| {
+        !alternative.is_empty()
+          && alternative
+            .split(|token| token.eq_ignore_ascii_case("AND"))
+            .all(is_term_allowed)
+      });
+  if allowed {
+    LicenseStatus::Allowed
+  } else {
+    LicenseStatus::Denied
+  }
+}
+
 fn maybe_size_to_text(maybe_size: Option<u64>) -> String {
   colors::gray(format!(
     "({})",
@@ -700,3 +1258,65 @@ fn maybe_size_to_text(maybe_size: Option<u64>) -> String {
   ))
   .to_string()
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_remote_package_name() {
+    let run = |specifier: &str| {
+      remote_package_name(&ModuleSpecifier::parse(specifier).unwrap())
+    };
+    assert_eq!(
+      run("https://deno.land/x/oak@v12.0.0/mod.ts"),
+      "https://deno.land/x/oak@v12.0.0"
+    );
+    assert_eq!(
+      run("https://deno.land/std@0.190.0/path/mod.ts"),
+      "https://deno.land/std@0.190.0"
+    );
+    assert_eq!(run("https://example.com/a/b.ts"), "https://example.com");
+  }
+
+  #[test]
+  fn test_license_from_package_json() {
+    assert_eq!(
+      license_from_package_json(&json!({ "license": "MIT" })),
+      Some("MIT".to_string())
+    );
+    assert_eq!(
+      license_from_package_json(&json!({ "license": { "type": "ISC" } })),
+      Some("ISC".to_string())
+    );
+    assert_eq!(
+      license_from_package_json(&json!({
+        "licenses": [{ "type": "MIT" }, { "type": "Apache-2.0" }]
+      })),
+      Some("MIT OR Apache-2.0".to_string())
+    );
+    assert_eq!(license_from_package_json(&json!({ "name": "a" })), None);
+  }
+
+  #[test]
+  fn test_resolve_license_status() {
+    let config = LicensesConfig {
+      allow: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+      deny: vec!["GPL-3.0".to_string()],
+    };
+    let run = |license: Option<&str>| resolve_license_status(license, &config);
+    assert_eq!(run(Some("MIT")), LicenseStatus::Allowed);
+    assert_eq!(run(Some("mit")), LicenseStatus::Allowed);
+    assert_eq!(run(Some("(GPL-3.0 OR MIT)")), LicenseStatus::Allowed);
+    assert_eq!(run(Some("MIT AND GPL-3.0")), LicenseStatus::Denied);
+    assert_eq!(run(Some("ISC")), LicenseStatus::Denied);
+    assert_eq!(run(Some("UNLICENSED")), LicenseStatus::Unknown);
+    assert_eq!(run(None), LicenseStatus::Unknown);
+
+    let config = LicensesConfig::default();
+    assert_eq!(
+      resolve_license_status(Some("ISC"), &config),
+      LicenseStatus::Allowed
+    );
+  }
+}
@@ -24,6 +24,8 @@ use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::error::JsStackFrame;
 use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::url::Url;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_lint::linter::Linter;
 use deno_lint::linter::LinterBuilder;
@@ -33,6 +35,8 @@ use deno_runtime::fmt_errors::format_location;
 use log::debug;
 use log::info;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs;
 use std::io::stdin;
 use std::io::Read;
@@ -43,15 +47,20 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use crate::cache::FastInsecureHasher;
 use crate::cache::IncrementalCache;
 
 static STDIN_FILE_NAME: &str = "_stdin.ts";
 
-fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
+fn create_reporter(
+  kind: LintReporterKind,
+  lint_rules: &[&'static dyn LintRule],
+) -> Box<dyn LintReporter + Send> {
   match kind {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new(lint_rules)),
   }
 }
 
@@ -114,8 +123,10 @@ pub async fn lint(
       &paths,
     ));
     let target_files_len = paths.len();
-    let reporter_lock =
-      Arc::new(Mutex::new(create_reporter(reporter_kind.clone())));
+    let reporter_lock = Arc::new(Mutex::new(create_reporter(
+      reporter_kind.clone(),
+      &lint_rules,
+    )));
 
     run_parallelized(paths, {
       let has_error = has_error.clone();
@@ -166,12 +177,14 @@ pub async fn lint(
       file_watcher::PrintConfig {
         job_name: "Lint".to_string(),
         clear_screen: !cli_options.no_clear_screen(),
+        restart_hooks: cli_options.resolve_watch_restart_hooks()?,
       },
     )
     .await?;
   } else {
     if lint_options.is_stdin {
-      let reporter_lock = Arc::new(Mutex::new(create_reporter(reporter_kind)));
+      let reporter_lock =
+        Arc::new(Mutex::new(create_reporter(reporter_kind, &lint_rules)));
       let r = lint_stdin(lint_rules);
       handle_lint_result(
         STDIN_FILE_NAME,
@@ -205,6 +218,7 @@ fn collect_lint_files(files: &FilesConfig) -> Result<Vec<PathBuf>, AnyError> {
     .ignore_git_folder()
     .ignore_node_modules()
     .add_ignore_paths(&files.exclude)
+    .use_ignore_files(!files.no_ignore)
     .collect_files(&files.include)
 }
 
@@ -513,25 +527,251 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+/// Reports the diagnostics as a SARIF 2.1.0 log, which code scanning tools
+/// such as GitHub's can display.
+///
+/// See <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+struct SarifLintReporter {
+  rules: Vec<serde_json::Value>,
+  rule_indexes: HashMap<&'static str, usize>,
+  results: Vec<(LintDiagnostic, String)>,
+  fingerprints: HashSet<String>,
+  errors: Vec<LintError>,
+  root_url: Option<Url>,
+}
+
+impl SarifLintReporter {
+  fn new(lint_rules: &[&'static dyn LintRule]) -> SarifLintReporter {
+    let mut lint_rules = lint_rules.to_vec();
+    lint_rules.sort_by_key(|rule| rule.code());
+    let rules = lint_rules
+      .iter()
+      .map(|rule| {
+        let docs = rule.docs();
+        let summary = docs.lines().next().unwrap_or_default();
+        json!({
+          "id": rule.code(),
+          "shortDescription": { "text": summary },
+          "fullDescription": { "text": docs, "markdown": docs },
+          "helpUri": format!("https://lint.deno.land/#{}", rule.code()),
+          "defaultConfiguration": { "level": "error" },
+          "properties": { "tags": rule.tags() },
+        })
+      })
+      .collect();
+    let rule_indexes = lint_rules
+      .iter()
+      .enumerate()
+      .map(|(index, rule)| (rule.code(), index))
+      .collect();
+    SarifLintReporter {
+      rules,
+      rule_indexes,
+      results: Vec::new(),
+      fingerprints: HashSet::new(),
+      errors: Vec::new(),
+      root_url: std::env::current_dir()
+        .ok()
+        .and_then(|cwd| Url::from_directory_path(cwd).ok()),
+    }
+  }
+
+  /// The URI of a file relative to the root, or `None` if the file isn't in
+  /// the root.
+  fn relative_uri(&self, filename: &str) -> Option<String> {
+    let url = Url::from_file_path(filename).ok()?;
+    let root_url = self.root_url.as_ref()?;
+    url
+      .as_str()
+      .strip_prefix(root_url.as_str())
+      .map(|s| s.to_string())
+  }
+
+  fn artifact_location(&self, filename: &str) -> serde_json::Value {
+    let path = Path::new(filename);
+    if !path.is_absolute() {
+      return json!({ "uri": filename.replace('\\', "/") });
+    }
+    if let Some(relative) = self.relative_uri(filename) {
+      return json!({ "uri": relative, "uriBaseId": "%SRCROOT%" });
+    }
+    match Url::from_file_path(path) {
+      Ok(url) => json!({ "uri": url.as_str() }),
+      Err(_) => json!({ "uri": filename }),
+    }
+  }
+
+  /// Hashes the rule, the file relative to the root and the trimmed source
+  /// of the first line of a diagnostic, so that the result can be tracked
+  /// across runs even when lines are added or removed above it or the
+  /// project is checked out elsewhere. `occurrence` tells apart the results
+  /// that would otherwise collide.
+  fn fingerprint(
+    &self,
+    d: &LintDiagnostic,
+    source_lines: &[&str],
+    occurrence: usize,
+  ) -> String {
+    let filename = self
+      .relative_uri(&d.filename)
+      .unwrap_or_else(|| d.filename.replace('\\', "/"));
+    let line = source_lines
+      .get(d.range.start.line_index)
+      .map(|line| line.trim())
+      .unwrap_or_default();
+    let hash = FastInsecureHasher::new()
+      .write_str(&d.code)
+      .write_str(&filename)
+      .write_str(line)
+      .write_u64(occurrence as u64)
+      .finish();
+    format!("{hash:016x}")
+  }
+
+  fn result(&self, d: &LintDiagnostic, fingerprint: &str) -> serde_json::Value {
+    let artifact_location = self.artifact_location(&d.filename);
+    let mut result = json!({
+      "ruleId": d.code,
+      "level": "error",
+      "message": { "text": d.message },
+      "locations": [{
+        "physicalLocation": {
+          "artifactLocation": artifact_location,
+          "region": {
+            "startLine": d.range.start.line_index + 1,
+            "startColumn": d.range.start.column_index + 1,
+            "endLine": d.range.end.line_index + 1,
+            "endColumn": d.range.end.column_index + 1,
+          },
+        },
+      }],
+      "partialFingerprints": { "denoLintHash/v1": fingerprint },
+    });
+    if let Some(index) = self.rule_indexes.get(d.code.as_str()) {
+      result["ruleIndex"] = json!(index);
+    }
+    // deno_lint doesn't provide edits for its hints, but a fix must have an
+    // artifact change, so the hint is reported with an empty replacement at
+    // the start of the diagnostic.
+    if let Some(hint) = &d.hint {
+      let start_line = d.range.start.line_index + 1;
+      let start_column = d.range.start.column_index + 1;
+      result["fixes"] = json!([{
+        "description": { "text": hint },
+        "artifactChanges": [{
+          "artifactLocation": artifact_location,
+          "replacements": [{
+            "deletedRegion": {
+              "startLine": start_line,
+              "startColumn": start_column,
+              "endLine": start_line,
+              "endColumn": start_column,
+            },
+          }],
+        }],
+      }]);
+    }
+    result
+  }
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic, source_lines: Vec<&str>) {
+    let mut occurrence = 0;
+    let fingerprint = loop {
+      let fingerprint = self.fingerprint(d, &source_lines, occurrence);
+      if self.fingerprints.insert(fingerprint.clone()) {
+        break fingerprint;
+      }
+      occurrence += 1;
+    };
+    self.results.push((d.clone(), fingerprint));
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    self.errors.push(LintError {
+      file_path: file_path.to_string(),
+      message: err.to_string(),
+    });
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    self
+      .results
+      .sort_by(|(a, _), (b, _)| compare_diagnostics(a, b));
+    self.errors.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    let results = self
+      .results
+      .iter()
+      .map(|(d, fingerprint)| self.result(d, fingerprint))
+      .collect::<Vec<_>>();
+    let notifications = self
+      .errors
+      .iter()
+      .map(|e| {
+        json!({
+          "level": "error",
+          "message": { "text": e.message },
+          "locations": [{
+            "physicalLocation": {
+              "artifactLocation": self.artifact_location(&e.file_path),
+            },
+          }],
+        })
+      })
+      .collect::<Vec<_>>();
+    let mut run = json!({
+      "tool": {
+        "driver": {
+          "name": "deno lint",
+          "informationUri": "https://lint.deno.land",
+          "semanticVersion": crate::version::deno(),
+          "rules": self.rules,
+        },
+      },
+      "columnKind": "unicodeCodePoints",
+      "invocations": [{
+        "executionSuccessful": self.errors.is_empty(),
+        "toolExecutionNotifications": notifications,
+      }],
+      "results": results,
+    });
+    if let Some(root_url) = &self.root_url {
+      run["originalUriBaseIds"] =
+        json!({ "%SRCROOT%": { "uri": root_url.as_str() } });
+    }
+    let log = json!({
+      "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+      "version": "2.1.0",
+      "runs": [run],
+    });
+    println!("{}", serde_json::to_string_pretty(&log).unwrap());
+  }
+}
+
 fn sort_diagnostics(diagnostics: &mut [LintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
-  diagnostics.sort_by(|a, b| {
-    use std::cmp::Ordering;
-    let file_order = a.filename.cmp(&b.filename);
-    match file_order {
-      Ordering::Equal => {
-        let line_order =
-          a.range.start.line_index.cmp(&b.range.start.line_index);
-        match line_order {
-          Ordering::Equal => {
-            a.range.start.column_index.cmp(&b.range.start.column_index)
-          }
-          _ => line_order,
+  diagnostics.sort_by(compare_diagnostics);
+}
+
+fn compare_diagnostics(
+  a: &LintDiagnostic,
+  b: &LintDiagnostic,
+) -> std::cmp::Ordering {
+  use std::cmp::Ordering;
+  let file_order = a.filename.cmp(&b.filename);
+  match file_order {
+    Ordering::Equal => {
+      let line_order = a.range.start.line_index.cmp(&b.range.start.line_index);
+      match line_order {
+        Ordering::Equal => {
+          a.range.start.column_index.cmp(&b.range.start.column_index)
         }
+        _ => line_order,
       }
-      _ => file_order,
     }
-  });
+    _ => file_order,
+  }
 }
 
 pub fn get_configured_rules(
@@ -520,6 +520,7 @@ impl ReplSession {
           &mut self.worker.js_runtime,
           self.has_node_modules_dir,
           None,
+          &Default::default(),
         )?;
         self.has_initialized_node_runtime = true;
       }
@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use std::io::Read;
+use std::sync::Arc;
 
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
@@ -14,8 +15,13 @@ use crate::factory::CliFactory;
 use crate::factory::CliFactoryBuilder;
 use crate::file_fetcher::File;
 use crate::util;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 
-pub async fn run_script(flags: Flags) -> Result<i32, AnyError> {
+pub async fn run_script(
+  flags: Flags,
+  startup_timer: Arc<PhaseTimer>,
+) -> Result<i32, AnyError> {
   if !flags.has_permission() && flags.has_permission_in_argv() {
     log::warn!(
       "{}",
@@ -33,7 +39,10 @@ To grant permissions, set them before the script argument. For example:
 
   // TODO(bartlomieju): actually I think it will also fail if there's an import
   // map specified and bare specifier is used on the command line
-  let factory = CliFactory::from_flags(flags).await?;
+  let factory = CliFactoryBuilder::new()
+    .with_startup_timer(startup_timer)
+    .build_from_flags(flags)
+    .await?;
   let deno_dir = factory.deno_dir()?;
   let http_client = factory.http_client();
   let cli_options = factory.cli_options();
@@ -57,12 +66,20 @@ To grant permissions, set them before the script argument. For example:
     .create_main_worker(main_module, permissions)
     .await?;
 
-  let exit_code = worker.run().await?;
+  let result = worker.run().await;
+  worker_factory.finish_output().await;
+  let exit_code = result?;
   Ok(exit_code)
 }
 
-pub async fn run_from_stdin(flags: Flags) -> Result<i32, AnyError> {
-  let factory = CliFactory::from_flags(flags).await?;
+pub async fn run_from_stdin(
+  flags: Flags,
+  startup_timer: Arc<PhaseTimer>,
+) -> Result<i32, AnyError> {
+  let factory = CliFactoryBuilder::new()
+    .with_startup_timer(startup_timer)
+    .build_from_flags(flags)
+    .await?;
   let cli_options = factory.cli_options();
   let main_module = cli_options.resolve_main_module()?;
 
@@ -91,7 +108,9 @@ pub async fn run_from_stdin(flags: Flags) -> Result<i32, AnyError> {
   let mut worker = worker_factory
     .create_main_worker(main_module, permissions)
     .await?;
-  let exit_code = worker.run().await?;
+  let result = worker.run().await;
+  worker_factory.finish_output().await;
+  let exit_code = result?;
   Ok(exit_code)
 }
 
@@ -106,6 +125,7 @@ async fn run_with_watch(flags: Flags) -> Result<i32, AnyError> {
   let file_watcher = factory.file_watcher()?;
   let cli_options = factory.cli_options();
   let clear_screen = !cli_options.no_clear_screen();
+  let restart_hooks = cli_options.resolve_watch_restart_hooks()?;
   let main_module = cli_options.resolve_main_module()?;
 
   maybe_npm_install(&factory).await?;
@@ -136,6 +156,7 @@ async fn run_with_watch(flags: Flags) -> Result<i32, AnyError> {
     util::file_watcher::PrintConfig {
       job_name: "Process".to_string(),
       clear_screen,
+      restart_hooks,
     },
   )
   .await?;
@@ -190,6 +211,7 @@ async fn maybe_npm_install(factory: &CliFactory) -> Result<(), AnyError> {
   // ensure an "npm install" is done if the user has explicitly
   // opted into using a node_modules directory
   if factory.cli_options().node_modules_dir_enablement() == Some(true) {
+    let _timer = factory.startup_timer().measure(StartupPhase::NpmResolution);
     factory
       .package_json_deps_installer()
       .await?
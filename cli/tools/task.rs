@@ -7,20 +7,29 @@ use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::util::fs::canonicalize_path;
+use crate::util::output_panes::OutputPanes;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
+use deno_core::futures::future::join_all;
 use deno_core::futures::future::LocalBoxFuture;
 use deno_runtime::deno_node::NodeResolver;
 use deno_semver::npm::NpmPackageNv;
+use deno_task_shell::parser::SequentialList;
+use deno_task_shell::parser::SequentialListItem;
 use deno_task_shell::ExecuteResult;
 use deno_task_shell::ShellCommand;
 use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellPipeWriter;
+use deno_task_shell::ShellState;
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Duration;
 use tokio::task::LocalSet;
 
 pub async fn execute_script(
@@ -60,10 +69,15 @@ pub async fn execute_script(
     let seq_list = deno_task_shell::parser::parse(&script)
       .with_context(|| format!("Error parsing script '{task_name}'."))?;
     let env_vars = collect_env_vars();
-    let local = LocalSet::new();
-    let future =
-      deno_task_shell::execute(seq_list, env_vars, &cwd, Default::default());
-    let exit_code = local.run_until(future).await;
+    let exit_code = execute_seq_list(
+      task_name,
+      seq_list,
+      env_vars,
+      &cwd,
+      Default::default(),
+      cli_options.split_output(),
+    )
+    .await;
     Ok(exit_code)
   } else if package_json_scripts.contains_key(task_name) {
     let package_json_deps_provider = factory.package_json_deps_provider();
@@ -122,10 +136,15 @@ pub async fn execute_script(
           .with_context(|| format!("Error parsing script '{task_name}'."))?;
         let npx_commands = resolve_npm_commands(npm_resolver, node_resolver)?;
         let env_vars = collect_env_vars();
-        let local = LocalSet::new();
-        let future =
-          deno_task_shell::execute(seq_list, env_vars, &cwd, npx_commands);
-        let exit_code = local.run_until(future).await;
+        let exit_code = execute_seq_list(
+          &task_name,
+          seq_list,
+          env_vars,
+          &cwd,
+          npx_commands,
+          cli_options.split_output(),
+        )
+        .await;
         if exit_code > 0 {
           return Ok(exit_code);
         }
@@ -140,6 +159,71 @@ pub async fn execute_script(
   }
 }
 
+async fn execute_seq_list(
+  task_name: &str,
+  seq_list: SequentialList,
+  env_vars: HashMap<String, String>,
+  cwd: &Path,
+  custom_commands: HashMap<String, Rc<dyn ShellCommand>>,
+  split_output: bool,
+) -> i32 {
+  let local = LocalSet::new();
+  // only the commands that run at the same time, like `a & b`, get a pane
+  let is_parallel = seq_list.items.len() > 1
+    && seq_list.items[..seq_list.items.len() - 1]
+      .iter()
+      .all(|item| item.is_async);
+  if !split_output || !is_parallel {
+    let future =
+      deno_task_shell::execute(seq_list, env_vars, cwd, custom_commands);
+    return local.run_until(future).await;
+  }
+
+  let panes = OutputPanes::default();
+  let last_is_async = seq_list.items.last().unwrap().is_async;
+  let mut futures = Vec::with_capacity(seq_list.items.len());
+  for (index, item) in seq_list.items.into_iter().enumerate() {
+    let label = format!("{} [{}]", task_name, index + 1);
+    let list = SequentialList {
+      items: vec![SequentialListItem {
+        is_async: false,
+        sequence: item.sequence,
+      }],
+    };
+    let state = ShellState::new(env_vars.clone(), cwd, custom_commands.clone());
+    let pipes = panes.pipe(&label).and_then(|stdout| {
+      let stderr = stdout.try_clone()?;
+      Ok((stdout, stderr))
+    });
+    let (stdout, stderr) = match pipes {
+      Ok((stdout, stderr)) => (
+        ShellPipeWriter::OsPipe(stdout),
+        ShellPipeWriter::OsPipe(stderr),
+      ),
+      Err(err) => {
+        log::debug!("Failed to create the pane of {}: {}", label, err);
+        (ShellPipeWriter::stdout(), ShellPipeWriter::stderr())
+      }
+    };
+    futures.push(deno_task_shell::execute_with_pipes(
+      list,
+      state,
+      ShellPipeReader::stdin(),
+      stdout,
+      stderr,
+    ));
+  }
+  let exit_codes = local.run_until(join_all(futures)).await;
+  panes.finish(Duration::from_secs(1)).await;
+  // like the shell, the exit code is the one of the last command, unless
+  // that one ran in the background too
+  if last_is_async {
+    0
+  } else {
+    *exit_codes.last().unwrap()
+  }
+}
+
 fn get_script_with_args(script: &str, options: &CliOptions) -> String {
   let additional_args = options
     .argv()
@@ -3,6 +3,7 @@
 use crate::args::CliOptions;
 use crate::args::FilesConfig;
 use crate::args::TestOptions;
+use crate::args::TestReporterConfig;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
@@ -15,6 +16,8 @@ use crate::util::checksum;
 use crate::util::file_watcher;
 use crate::util::file_watcher::ResolutionResult;
 use crate::util::fs::collect_specifiers;
+use crate::util::output_panes::pipe_writer_to_file;
+use crate::util::output_panes::OutputPanes;
 use crate::util::path::get_extension;
 use crate::util::path::is_supported_ext;
 use crate::util::path::mapped_specifier_for_tsc;
@@ -23,6 +26,7 @@ use crate::worker::CliMainWorkerFactory;
 use deno_ast::swc::common::comments::CommentKind;
 use deno_ast::MediaType;
 use deno_ast::SourceRangedForSpanned;
+use deno_core::anyhow::Context as _;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
@@ -33,6 +37,7 @@ use deno_core::futures::FutureExt;
 use deno_core::futures::StreamExt;
 use deno_core::located_script_name;
 use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
 use deno_core::serde_v8;
 use deno_core::task::spawn;
 use deno_core::task::spawn_blocking;
@@ -60,6 +65,7 @@ use std::collections::HashSet;
 use std::fmt::Write as _;
 use std::io::Read;
 use std::io::Write;
+use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
@@ -151,17 +157,34 @@ pub struct TestLocation {
 #[serde(rename_all = "camelCase")]
 pub struct TestDescription {
   pub id: usize,
+  /// The name of a case of a suite is prefixed with the names of the suites
+  /// it is in, like "suite > case".
   pub name: String,
   pub ignore: bool,
   pub only: bool,
   pub origin: String,
   pub location: TestLocation,
+  /// The names of the suites that the test is in, from the outermost one.
+  #[serde(default)]
+  pub suites: Vec<String>,
 }
 
 impl TestDescription {
   pub fn static_id(&self) -> String {
     checksum::gen(&[self.location.file_name.as_bytes(), self.name.as_bytes()])
   }
+
+  /// The name of the test without the names of its suites.
+  pub fn case_name(&self) -> &str {
+    let mut name = self.name.as_str();
+    for suite in &self.suites {
+      name = name
+        .strip_prefix(suite.as_str())
+        .and_then(|name| name.strip_prefix(" > "))
+        .unwrap_or(name);
+    }
+    name
+  }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -316,6 +339,9 @@ pub enum TestEvent {
   Plan(TestPlan),
   Wait(usize),
   Output(Vec<u8>),
+  /// Output of a test module that runs with `--split-output`, along with the
+  /// module's specifier.
+  ModuleOutput(String, Vec<u8>),
   Result(usize, TestResult, u64),
   UncaughtError(String, Box<JsError>),
   StepRegister(TestStepDescription),
@@ -345,6 +371,16 @@ struct TestSpecifiersOptions {
   fail_fast: Option<NonZeroUsize>,
   log_level: Option<log::Level>,
   specifier: TestSpecifierOptions,
+  /// Collects the tests that failed, so they can be re-run with
+  /// `--debug-failures`.
+  failed_tests: Option<Arc<Mutex<Vec<TestDescription>>>>,
+  /// Keeps the output of each module in its own pane instead of interleaving
+  /// it, see `--split-output`.
+  split_output: bool,
+  reporter: TestReporterConfig,
+  /// The file the report is written to instead of stdout, in which case the
+  /// results are also shown by the pretty reporter.
+  reporter_output: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -376,6 +412,68 @@ impl TestSummary {
   }
 }
 
+trait TestReporter {
+  fn report_register(&mut self, description: &TestDescription);
+  fn report_plan(&mut self, plan: &TestPlan);
+  fn report_wait(&mut self, description: &TestDescription);
+  fn report_output(&mut self, output: &[u8]);
+  fn report_module_output(&mut self, origin: &str, output: &[u8]);
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  );
+  fn report_uncaught_error(&mut self, origin: &str, error: &JsError);
+  fn report_step_register(&mut self, description: &TestStepDescription);
+  fn report_step_wait(&mut self, description: &TestStepDescription);
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  );
+  fn report_summary(&mut self, summary: &TestSummary, elapsed: &Duration);
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  );
+  /// Writes out a report that is only complete once all tests are done,
+  /// like the one of `--reporter=junit`.
+  fn flush_report(&mut self) -> Result<(), AnyError> {
+    Ok(())
+  }
+}
+
+fn create_reporter(
+  options: &TestSpecifiersOptions,
+) -> Result<Box<dyn TestReporter + Send>, AnyError> {
+  let parallel = options.concurrent_jobs.get() > 1;
+  let echo_output = options.log_level != Some(Level::Error);
+  let reporter: Box<dyn TestReporter + Send> = match options.reporter {
+    TestReporterConfig::Pretty => {
+      return Ok(Box::new(PrettyTestReporter::new(parallel, echo_output)))
+    }
+    TestReporterConfig::Junit => {
+      Box::new(JunitTestReporter::new(options.reporter_output.clone()))
+    }
+    TestReporterConfig::Tap => {
+      Box::new(TapTestReporter::new(options.reporter_output.as_deref())?)
+    }
+  };
+  Ok(match options.reporter_output {
+    Some(_) => Box::new(CompoundTestReporter(vec![
+      Box::new(PrettyTestReporter::new(parallel, echo_output)),
+      reporter,
+    ])),
+    None => reporter,
+  })
+}
+
 struct PrettyTestReporter {
   parallel: bool,
   echo_output: bool,
@@ -386,6 +484,9 @@ struct PrettyTestReporter {
   started_tests: bool,
   child_results_buffer:
     HashMap<usize, IndexMap<usize, (TestStepDescription, TestStepResult, u64)>>,
+  /// The suites whose headers were printed last, which the following cases
+  /// of the same suites are printed under.
+  current_suites: Vec<String>,
 }
 
 impl PrettyTestReporter {
@@ -399,6 +500,7 @@ impl PrettyTestReporter {
       did_have_user_output: false,
       started_tests: false,
       child_results_buffer: Default::default(),
+      current_suites: vec![],
     }
   }
 
@@ -414,25 +516,38 @@ impl PrettyTestReporter {
           self.to_relative_path_or_remote_url(&description.origin)
         ))
       );
+      print!("{} ...", description.name);
+    } else {
+      self.report_suites(&description.suites);
+      print!(
+        "{}{} ...",
+        "  ".repeat(description.suites.len()),
+        description.case_name()
+      );
     }
-    print!("{} ...", description.name);
     self.in_new_line = false;
     // flush for faster feedback when line buffered
     std::io::stdout().flush().unwrap();
     self.scope_test_id = Some(description.id);
   }
 
-  fn to_relative_path_or_remote_url(&self, path_or_url: &str) -> String {
-    let url = Url::parse(path_or_url).unwrap();
-    if url.scheme() == "file" {
-      if let Some(mut r) = self.cwd.make_relative(&url) {
-        if !r.starts_with("../") {
-          r = format!("./{r}");
-        }
-        return r;
-      }
+  /// Prints the headers of the suites that weren't printed for the previous
+  /// test, indented by their depth.
+  fn report_suites(&mut self, suites: &[String]) {
+    let common = self
+      .current_suites
+      .iter()
+      .zip(suites)
+      .take_while(|(a, b)| a == b)
+      .count();
+    for (depth, suite) in suites.iter().enumerate().skip(common) {
+      println!("{}{}", "  ".repeat(depth), suite);
     }
-    path_or_url.to_string()
+    self.current_suites = suites.to_vec();
+  }
+
+  fn to_relative_path_or_remote_url(&self, path_or_url: &str) -> String {
+    to_relative_path_or_remote_url(&self.cwd, path_or_url)
   }
 
   fn force_report_step_wait(&mut self, description: &TestStepDescription) {
@@ -440,7 +555,11 @@ impl PrettyTestReporter {
     if !self.in_new_line {
       println!();
     }
-    print!("{}{} ...", "  ".repeat(description.level), description.name);
+    print!(
+      "{}{} ...",
+      "  ".repeat(self.current_suites.len() + description.level),
+      description.name
+    );
     self.in_new_line = false;
     // flush for faster feedback when line buffered
     std::io::stdout().flush().unwrap();
@@ -510,12 +629,47 @@ impl PrettyTestReporter {
     }
   }
 
+  fn format_test_for_summary(&self, desc: &TestDescription) -> String {
+    format!(
+      "{} {}",
+      &desc.name,
+      colors::gray(format!(
+        "=> {}:{}:{}",
+        self.to_relative_path_or_remote_url(&desc.location.file_name),
+        desc.location.line_number,
+        desc.location.column_number
+      ))
+    )
+  }
+
+  fn format_test_step_for_summary(
+    &self,
+    desc: &TestStepDescription,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> String {
+    let long_name = format_test_step_ancestry(desc, tests, test_steps);
+    format!(
+      "{} {}",
+      long_name,
+      colors::gray(format!(
+        "=> {}:{}:{}",
+        self.to_relative_path_or_remote_url(&desc.location.file_name),
+        desc.location.line_number,
+        desc.location.column_number
+      ))
+    )
+  }
+}
+
+impl TestReporter for PrettyTestReporter {
   fn report_register(&mut self, _description: &TestDescription) {}
 
   fn report_plan(&mut self, plan: &TestPlan) {
     if self.parallel {
       return;
     }
+    self.current_suites.clear();
     let inflection = if plan.total == 1 { "test" } else { "tests" };
     println!(
       "{}",
@@ -555,6 +709,31 @@ impl PrettyTestReporter {
     std::io::stdout().write_all(output).unwrap();
   }
 
+  /// Prints output that was collected from a single module in one piece.
+  fn report_module_output(&mut self, origin: &str, output: &[u8]) {
+    if !self.echo_output || output.is_empty() {
+      return;
+    }
+
+    self.write_output_end();
+    if !self.in_new_line {
+      println!();
+    }
+    println!(
+      "{}",
+      colors::gray(format!(
+        "------- output from {} -------",
+        self.to_relative_path_or_remote_url(origin)
+      ))
+    );
+    std::io::stdout().write_all(output).unwrap();
+    if !output.ends_with(b"\n") {
+      println!();
+    }
+    println!("{}", colors::gray("----- output end -----"));
+    self.in_new_line = true;
+  }
+
   fn report_result(
     &mut self,
     description: &TestDescription,
@@ -627,7 +806,7 @@ impl PrettyTestReporter {
           "{} =>",
           self.to_relative_path_or_remote_url(&desc.origin)
         )),
-        self.format_test_step_ancestry(desc, tests, test_steps)
+        format_test_step_ancestry(desc, tests, test_steps)
       );
       self.in_new_line = false;
       self.scope_test_id = Some(desc.id);
@@ -794,68 +973,864 @@ impl PrettyTestReporter {
     println!();
     self.in_new_line = true;
   }
+}
 
-  fn format_test_step_ancestry(
-    &self,
+fn format_test_step_ancestry(
+  desc: &TestStepDescription,
+  tests: &IndexMap<usize, TestDescription>,
+  test_steps: &IndexMap<usize, TestStepDescription>,
+) -> String {
+  let root;
+  let mut ancestor_names = vec![];
+  let mut current_desc = desc;
+  loop {
+    if let Some(step_desc) = test_steps.get(&current_desc.parent_id) {
+      ancestor_names.push(&step_desc.name);
+      current_desc = step_desc;
+    } else {
+      root = tests.get(&current_desc.parent_id).unwrap();
+      break;
+    }
+  }
+  ancestor_names.reverse();
+  let mut result = String::new();
+  result.push_str(&root.name);
+  result.push_str(" ... ");
+  for name in ancestor_names {
+    result.push_str(name);
+    result.push_str(" ... ");
+  }
+  result.push_str(&desc.name);
+  result
+}
+
+fn to_relative_path_or_remote_url(cwd: &Url, path_or_url: &str) -> String {
+  let url = Url::parse(path_or_url).unwrap();
+  if url.scheme() == "file" {
+    if let Some(mut r) = cwd.make_relative(&url) {
+      if !r.starts_with("../") {
+        r = format!("./{r}");
+      }
+      return r;
+    }
+  }
+  path_or_url.to_string()
+}
+
+/// Passes the events on to several reporters, so the results can be shown on
+/// the terminal while a report is written to a file.
+struct CompoundTestReporter(Vec<Box<dyn TestReporter + Send>>);
+
+impl TestReporter for CompoundTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_register(description);
+    }
+  }
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    for reporter in &mut self.0 {
+      reporter.report_plan(plan);
+    }
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_wait(description);
+    }
+  }
+
+  fn report_output(&mut self, output: &[u8]) {
+    for reporter in &mut self.0 {
+      reporter.report_output(output);
+    }
+  }
+
+  fn report_module_output(&mut self, origin: &str, output: &[u8]) {
+    for reporter in &mut self.0 {
+      reporter.report_module_output(origin, output);
+    }
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    for reporter in &mut self.0 {
+      reporter.report_result(description, result, elapsed);
+    }
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: &JsError) {
+    for reporter in &mut self.0 {
+      reporter.report_uncaught_error(origin, error);
+    }
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_step_register(description);
+    }
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    for reporter in &mut self.0 {
+      reporter.report_step_wait(description);
+    }
+  }
+
+  fn report_step_result(
+    &mut self,
     desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
-  ) -> String {
-    let root;
-    let mut ancestor_names = vec![];
-    let mut current_desc = desc;
-    loop {
-      if let Some(step_desc) = test_steps.get(&current_desc.parent_id) {
-        ancestor_names.push(&step_desc.name);
-        current_desc = step_desc;
-      } else {
-        root = tests.get(&current_desc.parent_id).unwrap();
-        break;
-      }
+  ) {
+    for reporter in &mut self.0 {
+      reporter.report_step_result(desc, result, elapsed, tests, test_steps);
     }
-    ancestor_names.reverse();
-    let mut result = String::new();
-    result.push_str(&root.name);
-    result.push_str(" ... ");
-    for name in ancestor_names {
-      result.push_str(name);
-      result.push_str(" ... ");
+  }
+
+  fn report_summary(&mut self, summary: &TestSummary, elapsed: &Duration) {
+    for reporter in &mut self.0 {
+      reporter.report_summary(summary, elapsed);
     }
-    result.push_str(&desc.name);
-    result
   }
 
-  fn format_test_for_summary(&self, desc: &TestDescription) -> String {
-    format!(
-      "{} {}",
-      &desc.name,
-      colors::gray(format!(
-        "=> {}:{}:{}",
-        self.to_relative_path_or_remote_url(&desc.location.file_name),
-        desc.location.line_number,
-        desc.location.column_number
-      ))
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    for reporter in &mut self.0 {
+      reporter.report_sigint(tests_pending, tests, test_steps);
+    }
+  }
+
+  fn flush_report(&mut self) -> Result<(), AnyError> {
+    for reporter in &mut self.0 {
+      reporter.flush_report()?;
+    }
+    Ok(())
+  }
+}
+
+enum JunitOutcome {
+  Passed,
+  Skipped,
+  Failure(String),
+  Error(String),
+}
+
+struct JunitTestCase {
+  name: String,
+  /// The suites the test is in, like "suite > nested", or its module if it
+  /// isn't in one.
+  classname: String,
+  /// The module of the test, relative to the current directory.
+  file: String,
+  line: Option<u32>,
+  elapsed: u64,
+  outcome: JunitOutcome,
+  /// What the test printed, for `<system-out>`.
+  output: Vec<u8>,
+}
+
+/// Collects the results into a JUnit XML report with a `<testsuite>` per
+/// module, which is printed or written to `--reporter-output` at the end.
+struct JunitTestReporter {
+  output_path: Option<PathBuf>,
+  cwd: Url,
+  suites: IndexMap<String, Vec<JunitTestCase>>,
+  elapsed: Duration,
+  /// The test or step that is running, which is what printed the output
+  /// that comes in.
+  running_id: Option<usize>,
+  /// What the running tests and steps printed so far, by their id.
+  outputs: HashMap<usize, Vec<u8>>,
+  /// What the tests of each module printed with `--split-output`, which is
+  /// reported right before the result of the test.
+  module_outputs: HashMap<String, Vec<u8>>,
+}
+
+impl JunitTestReporter {
+  fn new(output_path: Option<PathBuf>) -> JunitTestReporter {
+    JunitTestReporter {
+      output_path,
+      cwd: Url::from_directory_path(std::env::current_dir().unwrap()).unwrap(),
+      suites: IndexMap::new(),
+      elapsed: Duration::ZERO,
+      running_id: None,
+      outputs: HashMap::new(),
+      module_outputs: HashMap::new(),
+    }
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn add_case(
+    &mut self,
+    origin: &str,
+    name: String,
+    suites: &[String],
+    location: Option<&TestLocation>,
+    elapsed: u64,
+    outcome: JunitOutcome,
+    output: Vec<u8>,
+  ) {
+    let file = to_relative_path_or_remote_url(
+      &self.cwd,
+      location.map(|l| l.file_name.as_str()).unwrap_or(origin),
+    );
+    let classname = if suites.is_empty() {
+      file.clone()
+    } else {
+      suites.join(" > ")
+    };
+    self
+      .suites
+      .entry(origin.to_string())
+      .or_default()
+      .push(JunitTestCase {
+        name,
+        classname,
+        file,
+        line: location.map(|l| l.line_number),
+        elapsed,
+        outcome,
+        output,
+      });
+  }
+
+  /// Takes what the test or step with the given id printed.
+  fn take_output(&mut self, id: usize, origin: &str) -> Vec<u8> {
+    let mut output = self.outputs.remove(&id).unwrap_or_default();
+    if let Some(module_output) = self.module_outputs.remove(origin) {
+      output.extend(module_output);
+    }
+    output
+  }
+
+  fn to_xml(&self) -> String {
+    let count = |cases: &[&JunitTestCase], f: fn(&JunitOutcome) -> bool| {
+      cases.iter().filter(|case| f(&case.outcome)).count()
+    };
+    let is_failure = |o: &JunitOutcome| matches!(o, JunitOutcome::Failure(_));
+    let is_error = |o: &JunitOutcome| matches!(o, JunitOutcome::Error(_));
+    let is_skipped = |o: &JunitOutcome| matches!(o, JunitOutcome::Skipped);
+
+    let all_cases = self.suites.values().flatten().collect::<Vec<_>>();
+    let mut xml = String::new();
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+      xml,
+      r#"<testsuites name="deno test" tests="{}" failures="{}" errors="{}" time="{}">"#,
+      all_cases.len(),
+      count(&all_cases, is_failure),
+      count(&all_cases, is_error),
+      format_junit_time(self.elapsed.as_millis() as u64),
     )
+    .unwrap();
+    for (origin, cases) in &self.suites {
+      let cases = cases.iter().collect::<Vec<_>>();
+      writeln!(
+        xml,
+        r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{}">"#,
+        escape_xml(&to_relative_path_or_remote_url(&self.cwd, origin)),
+        cases.len(),
+        count(&cases, is_failure),
+        count(&cases, is_error),
+        count(&cases, is_skipped),
+        format_junit_time(cases.iter().map(|case| case.elapsed).sum()),
+      )
+      .unwrap();
+      for case in cases {
+        write!(
+          xml,
+          r#"    <testcase name="{}" classname="{}" time="{}" file="{}""#,
+          escape_xml(&case.name),
+          escape_xml(&case.classname),
+          format_junit_time(case.elapsed),
+          escape_xml(&case.file),
+        )
+        .unwrap();
+        if let Some(line) = case.line {
+          write!(xml, r#" line="{line}""#).unwrap();
+        }
+        if matches!(case.outcome, JunitOutcome::Passed)
+          && case.output.is_empty()
+        {
+          xml.push_str("/>\n");
+          continue;
+        }
+        xml.push_str(">\n");
+        let failure = match &case.outcome {
+          JunitOutcome::Passed => None,
+          JunitOutcome::Skipped => {
+            xml.push_str("      <skipped/>\n");
+            None
+          }
+          JunitOutcome::Failure(text) => Some(("failure", text)),
+          JunitOutcome::Error(text) => Some(("error", text)),
+        };
+        if let Some((tag, text)) = failure {
+          let text = console_static_text::ansi::strip_ansi_codes(text);
+          let message = text.lines().next().unwrap_or_default();
+          writeln!(
+            xml,
+            r#"      <{tag} message="{}">{}</{tag}>"#,
+            escape_xml(message),
+            escape_xml(&text),
+          )
+          .unwrap();
+        }
+        if !case.output.is_empty() {
+          let output = String::from_utf8_lossy(&case.output);
+          let output = console_static_text::ansi::strip_ansi_codes(&output);
+          writeln!(
+            xml,
+            "      <system-out>{}</system-out>",
+            escape_xml(&output)
+          )
+          .unwrap();
+        }
+        xml.push_str("    </testcase>\n");
+      }
+      xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
   }
+}
 
-  fn format_test_step_for_summary(
-    &self,
+impl TestReporter for JunitTestReporter {
+  fn report_register(&mut self, _description: &TestDescription) {}
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    // modules without tests still get an empty suite
+    self.suites.entry(plan.origin.clone()).or_default();
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    self.running_id = Some(description.id);
+  }
+
+  // the output is collected into the `<system-out>` of the running test, as
+  // it would otherwise end up in the middle of the report
+  fn report_output(&mut self, output: &[u8]) {
+    if let Some(id) = self.running_id {
+      self
+        .outputs
+        .entry(id)
+        .or_default()
+        .extend_from_slice(output);
+    }
+  }
+
+  fn report_module_output(&mut self, origin: &str, output: &[u8]) {
+    self
+      .module_outputs
+      .entry(origin.to_string())
+      .or_default()
+      .extend_from_slice(output);
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let outcome = match result {
+      TestResult::Ok => JunitOutcome::Passed,
+      TestResult::Ignored => JunitOutcome::Skipped,
+      TestResult::Failed(failure) => JunitOutcome::Failure(failure.to_string()),
+      TestResult::Cancelled => JunitOutcome::Error("Cancelled".to_string()),
+    };
+    self.running_id = None;
+    let output = self.take_output(description.id, &description.origin);
+    self.add_case(
+      &description.origin,
+      description.case_name().to_string(),
+      &description.suites,
+      Some(&description.location),
+      elapsed,
+      outcome,
+      output,
+    );
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: &JsError) {
+    let output = self.module_outputs.remove(origin).unwrap_or_default();
+    self.add_case(
+      origin,
+      "(uncaught error)".to_string(),
+      &[],
+      None,
+      0,
+      JunitOutcome::Error(format_test_error(error)),
+      output,
+    );
+  }
+
+  fn report_step_register(&mut self, _description: &TestStepDescription) {}
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    self.running_id = Some(description.id);
+  }
+
+  fn report_step_result(
+    &mut self,
     desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
-  ) -> String {
-    let long_name = self.format_test_step_ancestry(desc, tests, test_steps);
-    format!(
-      "{} {}",
-      long_name,
-      colors::gray(format!(
-        "=> {}:{}:{}",
-        self.to_relative_path_or_remote_url(&desc.location.file_name),
-        desc.location.line_number,
-        desc.location.column_number
-      ))
-    )
+  ) {
+    let outcome = match result {
+      TestStepResult::Ok => JunitOutcome::Passed,
+      TestStepResult::Ignored => JunitOutcome::Skipped,
+      TestStepResult::Failed(failure) => {
+        JunitOutcome::Failure(failure.to_string())
+      }
+    };
+    self.running_id = Some(desc.parent_id);
+    let output = self.outputs.remove(&desc.id).unwrap_or_default();
+    let suites = tests
+      .get(&desc.root_id)
+      .map(|test| test.suites.as_slice())
+      .unwrap_or_default();
+    self.add_case(
+      &desc.origin,
+      format_test_step_ancestry(desc, tests, test_steps),
+      suites,
+      Some(&desc.location),
+      elapsed,
+      outcome,
+      output,
+    );
+  }
+
+  fn report_summary(&mut self, _summary: &TestSummary, elapsed: &Duration) {
+    self.elapsed = *elapsed;
+  }
+
+  fn report_sigint(
+    &mut self,
+    _tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+  }
+
+  fn flush_report(&mut self) -> Result<(), AnyError> {
+    let xml = self.to_xml();
+    match &self.output_path {
+      Some(path) => std::fs::write(path, xml).with_context(|| {
+        format!("Failed writing the test report to {}", path.display())
+      })?,
+      None => std::io::stdout().write_all(xml.as_bytes())?,
+    }
+    Ok(())
+  }
+}
+
+/// Formats milliseconds as the seconds JUnit reports use.
+fn format_junit_time(elapsed: u64) -> String {
+  format!("{:.3}", elapsed as f64 / 1000.0)
+}
+
+fn escape_xml(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&apos;"),
+      '\t' | '\n' | '\r' => escaped.push(c),
+      // other control characters aren't allowed in XML 1.0
+      c if c.is_control() => {}
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+/// Prints the results in the TAP version 13 format as the tests finish. The
+/// steps of a test are printed as a subtest before the test itself, which is
+/// why their results are held back until the test is done. The same goes for
+/// the cases of a `describe()` suite, which is done once a test that isn't in
+/// it finishes.
+struct TapTestReporter {
+  output: Box<dyn Write + Send>,
+  /// The first error writing the report, after which nothing more is
+  /// written, so that a closed pipe doesn't fail every test.
+  write_error: Option<std::io::Error>,
+  cwd: Url,
+  /// The number of the last test point on the top level.
+  count: usize,
+  /// The finished steps of each test or step, by its id.
+  subtests: HashMap<usize, TapSubtest>,
+  /// The suites of each module that have unfinished cases, from the
+  /// outermost one.
+  suites: HashMap<String, Vec<TapSuite>>,
+  /// The output of the tests after its last line break.
+  partial_output: Vec<u8>,
+}
+
+#[derive(Default)]
+struct TapSubtest {
+  count: usize,
+  lines: Vec<String>,
+}
+
+struct TapSuite {
+  name: String,
+  subtest: TapSubtest,
+  failed: bool,
+}
+
+impl TapTestReporter {
+  fn new(output_path: Option<&Path>) -> Result<TapTestReporter, AnyError> {
+    let output: Box<dyn Write + Send> = match output_path {
+      Some(path) => Box::new(std::io::BufWriter::new(
+        std::fs::File::create(path).with_context(|| {
+          format!("Failed creating the test report {}", path.display())
+        })?,
+      )),
+      None => Box::new(std::io::stdout()),
+    };
+    let mut reporter = TapTestReporter {
+      output,
+      write_error: None,
+      cwd: Url::from_directory_path(std::env::current_dir().unwrap()).unwrap(),
+      count: 0,
+      subtests: HashMap::new(),
+      suites: HashMap::new(),
+      partial_output: Vec::new(),
+    };
+    reporter.write_line("TAP version 13");
+    Ok(reporter)
+  }
+
+  fn write_line(&mut self, line: &str) {
+    if self.write_error.is_none() {
+      if let Err(err) = writeln!(self.output, "{line}") {
+        self.write_error = Some(err);
+      }
+    }
+  }
+
+  /// Writes the output of a test as comments, so that it can't be mistaken
+  /// for test points.
+  fn write_output_line(&mut self, line: &[u8]) {
+    let line = String::from_utf8_lossy(line);
+    let line = console_static_text::ansi::strip_ansi_codes(&line);
+    let line = line.trim_end_matches('\r');
+    if line.is_empty() {
+      self.write_line("#");
+    } else {
+      self.write_line(&format!("# {line}"));
+    }
+  }
+
+  fn flush_partial_output(&mut self) {
+    if !self.partial_output.is_empty() {
+      let line = std::mem::take(&mut self.partial_output);
+      self.write_output_line(&line);
+    }
+  }
+
+  /// Opens and closes the suites of the module, so that the suites that are
+  /// open are the given ones.
+  fn enter_suites(&mut self, origin: &str, suites: &[String]) {
+    let open = self
+      .suites
+      .get(origin)
+      .map(Vec::as_slice)
+      .unwrap_or_default();
+    let depth = open
+      .iter()
+      .zip(suites)
+      .take_while(|(suite, name)| &suite.name == *name)
+      .count();
+    while self.suites.get(origin).map(Vec::len).unwrap_or(0) > depth {
+      self.close_suite(origin);
+    }
+    if depth < suites.len() {
+      let open = self.suites.entry(origin.to_string()).or_default();
+      open.extend(suites[depth..].iter().map(|name| TapSuite {
+        name: name.clone(),
+        subtest: TapSubtest::default(),
+        failed: false,
+      }));
+    }
+  }
+
+  /// Adds the subtest of the innermost suite of the module as a test point
+  /// to the suite around it, or the top level.
+  fn close_suite(&mut self, origin: &str) {
+    let suite = match self.suites.get_mut(origin).and_then(Vec::pop) {
+      Some(suite) => suite,
+      None => return,
+    };
+    let mut lines = format_tap_subtest(&suite.name, suite.subtest);
+    let number = self.next_number(origin);
+    let name = escape_tap_description(&suite.name);
+    if suite.failed {
+      lines.push(format!("not ok {number} - {name}"));
+    } else {
+      lines.push(format!("ok {number} - {name}"));
+    }
+    self.add_test_point(origin, lines, suite.failed);
+  }
+
+  /// The number of the next test point in the innermost open suite of the
+  /// module, or on the top level.
+  fn next_number(&self, origin: &str) -> usize {
+    match self.suites.get(origin).and_then(|suites| suites.last()) {
+      Some(suite) => suite.subtest.count + 1,
+      None => self.count + 1,
+    }
+  }
+
+  fn add_test_point(&mut self, origin: &str, lines: Vec<String>, failed: bool) {
+    match self
+      .suites
+      .get_mut(origin)
+      .and_then(|suites| suites.last_mut())
+    {
+      Some(suite) => {
+        suite.subtest.count += 1;
+        suite.subtest.lines.extend(lines);
+        suite.failed |= failed;
+      }
+      None => {
+        self.count += 1;
+        self.flush_partial_output();
+        for line in lines {
+          self.write_line(&line);
+        }
+      }
+    }
+  }
+
+  /// Formats the test point of a test or step, preceded by the subtest of
+  /// its steps if it has any.
+  fn format_test_point(
+    &mut self,
+    id: usize,
+    number: usize,
+    name: &str,
+    outcome: TapOutcome,
+    location: &TestLocation,
+  ) -> Vec<String> {
+    let mut lines = match self.subtests.remove(&id) {
+      Some(subtest) => format_tap_subtest(name, subtest),
+      None => vec![],
+    };
+    let name = escape_tap_description(name);
+    match outcome {
+      TapOutcome::Ok => lines.push(format!("ok {number} - {name}")),
+      TapOutcome::Skip => lines.push(format!("ok {number} - {name} # SKIP")),
+      TapOutcome::NotOk(message) => {
+        lines.push(format!("not ok {number} - {name}"));
+        lines.extend(self.format_diagnostics(&message, Some(location)));
+      }
+    }
+    lines
+  }
+
+  /// Formats a YAML diagnostics block about a failure.
+  fn format_diagnostics(
+    &self,
+    message: &str,
+    location: Option<&TestLocation>,
+  ) -> Vec<String> {
+    let message = console_static_text::ansi::strip_ansi_codes(message);
+    let mut lines = vec!["  ---".to_string(), "  message: |-".to_string()];
+    for line in message.lines() {
+      lines.push(format!("    {line}"));
+    }
+    lines.push("  severity: fail".to_string());
+    if let Some(location) = location {
+      let file = to_relative_path_or_remote_url(&self.cwd, &location.file_name);
+      lines.push("  at:".to_string());
+      lines.push(format!(
+        "    file: {}",
+        serde_json::to_string(&file).unwrap()
+      ));
+      lines.push(format!("    line: {}", location.line_number));
+      lines.push(format!("    column: {}", location.column_number));
+    }
+    lines.push("  ...".to_string());
+    lines
+  }
+}
+
+/// Formats the indented subtest that comes before the test point of `name`.
+fn format_tap_subtest(name: &str, subtest: TapSubtest) -> Vec<String> {
+  let mut lines = vec![format!("    # Subtest: {name}")];
+  for line in subtest.lines {
+    lines.push(format!("    {line}"));
+  }
+  lines.push(format!("    1..{}", subtest.count));
+  lines
+}
+
+enum TapOutcome {
+  Ok,
+  Skip,
+  NotOk(String),
+}
+
+impl TestReporter for TapTestReporter {
+  fn report_register(&mut self, _description: &TestDescription) {}
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    let origin = to_relative_path_or_remote_url(&self.cwd, &plan.origin);
+    self.write_line(&format!("# {origin}"));
+  }
+
+  fn report_wait(&mut self, _description: &TestDescription) {}
+
+  fn report_output(&mut self, output: &[u8]) {
+    self.partial_output.extend_from_slice(output);
+    while let Some(index) = self.partial_output.iter().position(|b| *b == b'\n')
+    {
+      let line = self.partial_output.drain(..=index).collect::<Vec<_>>();
+      self.write_output_line(&line[..index]);
+    }
+  }
+
+  fn report_module_output(&mut self, _origin: &str, output: &[u8]) {
+    self.report_output(output);
+    self.flush_partial_output();
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    _elapsed: u64,
+  ) {
+    let outcome = match result {
+      TestResult::Ok => TapOutcome::Ok,
+      TestResult::Ignored => TapOutcome::Skip,
+      TestResult::Failed(failure) => TapOutcome::NotOk(failure.to_string()),
+      TestResult::Cancelled => TapOutcome::NotOk("Cancelled".to_string()),
+    };
+    let failed = matches!(outcome, TapOutcome::NotOk(_));
+    let origin = &description.origin;
+    self.enter_suites(origin, &description.suites);
+    let number = self.next_number(origin);
+    let lines = self.format_test_point(
+      description.id,
+      number,
+      description.case_name(),
+      outcome,
+      &description.location,
+    );
+    self.add_test_point(origin, lines, failed);
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: &JsError) {
+    self.enter_suites(origin, &[]);
+    let name = to_relative_path_or_remote_url(&self.cwd, origin);
+    let name = escape_tap_description(&name);
+    let mut lines = vec![format!(
+      "not ok {} - {name} (uncaught error)",
+      self.count + 1
+    )];
+    lines.extend(self.format_diagnostics(&format_test_error(error), None));
+    self.add_test_point(origin, lines, true);
   }
+
+  fn report_step_register(&mut self, _description: &TestStepDescription) {}
+
+  fn report_step_wait(&mut self, _description: &TestStepDescription) {}
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    _elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let outcome = match result {
+      TestStepResult::Ok => TapOutcome::Ok,
+      TestStepResult::Ignored => TapOutcome::Skip,
+      TestStepResult::Failed(failure) => TapOutcome::NotOk(failure.to_string()),
+    };
+    let number = self
+      .subtests
+      .get(&desc.parent_id)
+      .map(|subtest| subtest.count)
+      .unwrap_or(0)
+      + 1;
+    let lines = self.format_test_point(
+      desc.id,
+      number,
+      &desc.name,
+      outcome,
+      &desc.location,
+    );
+    let subtest = self.subtests.entry(desc.parent_id).or_default();
+    subtest.count = number;
+    subtest.lines.extend(lines);
+  }
+
+  fn report_summary(&mut self, summary: &TestSummary, _elapsed: &Duration) {
+    let origins = self.suites.keys().cloned().collect::<Vec<_>>();
+    for origin in origins {
+      self.enter_suites(&origin, &[]);
+    }
+    self.flush_partial_output();
+    self.write_line(&format!("1..{}", self.count));
+    self.write_line(&format!("# pass {}", summary.passed));
+    self.write_line(&format!("# fail {}", summary.failed));
+    self.write_line(&format!("# skip {}", summary.ignored));
+  }
+
+  fn report_sigint(
+    &mut self,
+    _tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self.flush_partial_output();
+    self.write_line("Bail out! Interrupted by SIGINT");
+    if self.write_error.is_none() {
+      let _ = self.output.flush();
+    }
+  }
+
+  fn flush_report(&mut self) -> Result<(), AnyError> {
+    if let Some(err) = self.write_error.take() {
+      // the reader going away isn't worth failing the test run for
+      if err.kind() != std::io::ErrorKind::BrokenPipe {
+        return Err(err).context("Failed writing the test report");
+      }
+      return Ok(());
+    }
+    self.output.flush()?;
+    Ok(())
+  }
+}
+
+/// Escapes the characters with a meaning in the description of a TAP test
+/// point, `#` starting a directive.
+fn escape_tap_description(name: &str) -> String {
+  name
+    .replace('\\', "\\\\")
+    .replace('#', "\\#")
+    .replace('\n', " ")
 }
 
 fn abbreviate_test_error(js_error: &JsError) -> JsError {
@@ -1329,8 +2304,8 @@ async fn test_specifiers(
     specifiers
   };
 
-  let (sender, mut receiver) = unbounded_channel::<TestEvent>();
-  let sender = TestEventSender::new(sender);
+  let (raw_sender, mut receiver) = unbounded_channel::<TestEvent>();
+  let sender = TestEventSender::new(raw_sender.clone());
   let concurrent_jobs = options.concurrent_jobs;
 
   let sender_ = sender.downgrade();
@@ -1343,7 +2318,11 @@ async fn test_specifiers(
   let join_handles = specifiers.into_iter().map(move |specifier| {
     let worker_factory = worker_factory.clone();
     let permissions = permissions.clone();
-    let sender = sender.clone();
+    let sender = if options.split_output {
+      TestEventSender::for_module(raw_sender.clone(), specifier.to_string())
+    } else {
+      sender.clone()
+    };
     let fail_fast_tracker = FailFastTracker::new(options.fail_fast);
     let specifier_options = options.specifier.clone();
     spawn_blocking(move || {
@@ -1362,10 +2341,9 @@ async fn test_specifiers(
     .buffer_unordered(concurrent_jobs.get())
     .collect::<Vec<Result<Result<(), AnyError>, tokio::task::JoinError>>>();
 
-  let mut reporter = Box::new(PrettyTestReporter::new(
-    concurrent_jobs.get() > 1,
-    options.log_level != Some(Level::Error),
-  ));
+  let mut reporter = create_reporter(&options)?;
+  let failed_tests = options.failed_tests;
+  let panes = options.split_output.then(OutputPanes::default);
 
   let handler = {
     spawn(async move {
@@ -1376,8 +2354,16 @@ async fn test_specifiers(
       let mut tests_with_result = HashSet::new();
       let mut summary = TestSummary::new();
       let mut used_only = false;
+      // the tests of each module that are yet to report a result, to know
+      // when its pane can be removed
+      let mut remaining_tests = HashMap::<String, usize>::new();
 
       while let Some(event) = receiver.recv().await {
+        // stop drawing the panes while the reporter prints
+        let _suspend_guard = match (&panes, &event) {
+          (_, TestEvent::ModuleOutput(_, _)) | (None, _) => None,
+          (Some(panes), _) => Some(panes.suspend()),
+        };
         match event {
           TestEvent::Register(description) => {
             reporter.report_register(&description);
@@ -1391,6 +2377,10 @@ async fn test_specifiers(
             if plan.used_only {
               used_only = true;
             }
+            if panes.is_some() {
+              *remaining_tests.entry(plan.origin.clone()).or_default() +=
+                plan.total;
+            }
 
             reporter.report_plan(&plan);
           }
@@ -1405,6 +2395,11 @@ async fn test_specifiers(
             reporter.report_output(&output);
           }
 
+          TestEvent::ModuleOutput(origin, output) => match &panes {
+            Some(panes) => panes.write(&origin, &output),
+            None => reporter.report_output(&output),
+          },
+
           TestEvent::Result(id, result, elapsed) => {
             if tests_with_result.insert(id) {
               let description = tests.get(&id).unwrap();
@@ -1420,16 +2415,37 @@ async fn test_specifiers(
                   summary
                     .failures
                     .push((description.clone(), failure.clone()));
+                  if let Some(failed_tests) = &failed_tests {
+                    failed_tests.lock().push(description.clone());
+                  }
                 }
                 TestResult::Cancelled => {
                   summary.failed += 1;
                 }
               }
+              if let Some(panes) = &panes {
+                let origin = &description.origin;
+                let output = match remaining_tests.get_mut(origin) {
+                  Some(remaining) if *remaining > 1 => {
+                    *remaining -= 1;
+                    panes.take_output(origin)
+                  }
+                  _ => {
+                    remaining_tests.remove(origin);
+                    panes.remove(origin)
+                  }
+                };
+                reporter.report_module_output(origin, &output);
+              }
               reporter.report_result(description, &result, elapsed);
             }
           }
 
           TestEvent::UncaughtError(origin, error) => {
+            if let Some(panes) = &panes {
+              remaining_tests.remove(&origin);
+              reporter.report_module_output(&origin, &panes.remove(&origin));
+            }
             reporter.report_uncaught_error(&origin, &error);
             summary.failed += 1;
             summary.uncaught_errors.push((origin.clone(), error));
@@ -1461,7 +2477,7 @@ async fn test_specifiers(
                   summary.failures.push((
                     TestDescription {
                       id: description.id,
-                      name: reporter.format_test_step_ancestry(
+                      name: format_test_step_ancestry(
                         description,
                         &tests,
                         &test_steps,
@@ -1470,6 +2486,7 @@ async fn test_specifiers(
                       only: false,
                       origin: description.origin.clone(),
                       location: description.location.clone(),
+                      suites: vec![],
                     },
                     failure.clone(),
                   ))
@@ -1503,8 +2520,15 @@ async fn test_specifiers(
       sigint_handler_handle.abort();
       HAS_TEST_RUN_SIGINT_HANDLER.store(false, Ordering::Relaxed);
 
+      if let Some(panes) = &panes {
+        for (origin, output) in panes.remove_all() {
+          reporter.report_module_output(&origin, &output);
+        }
+      }
+
       let elapsed = Instant::now().duration_since(earlier);
       reporter.report_summary(&summary, &elapsed);
+      reporter.flush_report()?;
 
       if used_only {
         return Err(generic_error(
@@ -1700,6 +2724,10 @@ pub async fn run_tests(
         shuffle: test_options.shuffle,
         trace_ops: test_options.trace_ops,
       },
+      failed_tests: None,
+      split_output: test_options.split_output,
+      reporter: test_options.reporter,
+      reporter_output: test_options.reporter_output,
     },
   )
   .await?;
@@ -1861,7 +2889,10 @@ pub async fn run_tests_with_watch(
         return Ok(());
       }
 
-      test_specifiers(
+      let failed_tests = test_options
+        .debug_failures
+        .map(|_| Arc::new(Mutex::new(Vec::new())));
+      let result = test_specifiers(
         worker_factory,
         permissions,
         specifiers_with_mode
@@ -1880,11 +2911,25 @@ pub async fn run_tests_with_watch(
             shuffle: test_options.shuffle,
             trace_ops: test_options.trace_ops,
           },
+          failed_tests: failed_tests.clone(),
+          split_output: test_options.split_output,
+          reporter: test_options.reporter,
+          reporter_output: test_options.reporter_output.clone(),
         },
       )
-      .await?;
+      .await;
 
-      Ok(())
+      if let (Err(_), Some(host), Some(failed_tests)) =
+        (&result, test_options.debug_failures, failed_tests)
+      {
+        let maybe_failed_test = failed_tests.lock().first().cloned();
+        if let Some(description) = maybe_failed_test {
+          debug_failed_test(&cli_options, permissions, host, description)
+            .await?;
+        }
+      }
+
+      result
     }
   };
 
@@ -1902,12 +2947,14 @@ pub async fn run_tests_with_watch(
   });
 
   let clear_screen = !cli_options.no_clear_screen();
+  let restart_hooks = cli_options.resolve_watch_restart_hooks()?;
   file_watcher::watch_func(
     resolver,
     operation,
     file_watcher::PrintConfig {
       job_name: "Test".to_string(),
       clear_screen,
+      restart_hooks,
     },
   )
   .await?;
@@ -1915,6 +2962,53 @@ pub async fn run_tests_with_watch(
   Ok(())
 }
 
+/// Re-runs a test that failed in watch mode on its own, paused on the first
+/// statement of its module until a debugger connects to `host`.
+async fn debug_failed_test(
+  cli_options: &CliOptions,
+  permissions: &Permissions,
+  host: SocketAddr,
+  description: TestDescription,
+) -> Result<(), AnyError> {
+  let specifier = ModuleSpecifier::parse(&description.origin)?;
+  log::info!("{} {}", colors::yellow("Debugging"), description.name);
+
+  let factory =
+    CliFactory::from_cli_options(Arc::new(cli_options.with_inspect_brk(host)?));
+  if let Some(inspector_server) = factory.maybe_inspector_server() {
+    inspector_server.set_print_frontend_url(true);
+  }
+  let worker_factory =
+    Arc::new(factory.create_cli_main_worker_factory().await?);
+
+  // the test is expected to fail again, which was already reported
+  let _ = test_specifiers(
+    worker_factory,
+    permissions,
+    vec![specifier],
+    TestSpecifiersOptions {
+      concurrent_jobs: NonZeroUsize::new(1).unwrap(),
+      fail_fast: None,
+      log_level: cli_options.log_level(),
+      specifier: TestSpecifierOptions {
+        filter: TestFilter {
+          include: Some(vec![description.name]),
+          ..Default::default()
+        },
+        shuffle: None,
+        trace_ops: false,
+      },
+      failed_tests: None,
+      split_output: false,
+      reporter: TestReporterConfig::Pretty,
+      reporter_output: None,
+    },
+  )
+  .await;
+
+  Ok(())
+}
+
 /// Tracks failures for the `--fail-fast` argument in
 /// order to tell when to stop running tests.
 #[derive(Clone)]
@@ -1961,8 +3055,21 @@ pub struct TestEventSender {
 impl TestEventSender {
   pub fn new(sender: UnboundedSender<TestEvent>) -> Self {
     Self {
-      stdout_writer: TestOutputPipe::new(sender.clone()),
-      stderr_writer: TestOutputPipe::new(sender.clone()),
+      stdout_writer: TestOutputPipe::new(sender.clone(), None),
+      stderr_writer: TestOutputPipe::new(sender.clone(), None),
+      sender,
+    }
+  }
+
+  /// Creates a sender whose output is reported as
+  /// [`TestEvent::ModuleOutput`] of the given module.
+  pub fn for_module(
+    sender: UnboundedSender<TestEvent>,
+    origin: String,
+  ) -> Self {
+    Self {
+      stdout_writer: TestOutputPipe::new(sender.clone(), Some(origin.clone())),
+      stderr_writer: TestOutputPipe::new(sender.clone(), Some(origin)),
       sender,
     }
   }
@@ -2022,11 +3129,14 @@ impl Clone for TestOutputPipe {
 }
 
 impl TestOutputPipe {
-  pub fn new(sender: UnboundedSender<TestEvent>) -> Self {
+  pub fn new(
+    sender: UnboundedSender<TestEvent>,
+    origin: Option<String>,
+  ) -> Self {
     let (reader, writer) = os_pipe::pipe().unwrap();
     let state = Arc::new(Mutex::new(None));
 
-    start_output_redirect_thread(reader, sender, state.clone());
+    start_output_redirect_thread(reader, sender, origin, state.clone());
 
     Self { writer, state }
   }
@@ -2054,25 +3164,10 @@ impl TestOutputPipe {
   }
 }
 
-#[cfg(windows)]
-fn pipe_writer_to_file(writer: os_pipe::PipeWriter) -> std::fs::File {
-  use std::os::windows::prelude::FromRawHandle;
-  use std::os::windows::prelude::IntoRawHandle;
-  // SAFETY: Requires consuming ownership of the provided handle
-  unsafe { std::fs::File::from_raw_handle(writer.into_raw_handle()) }
-}
-
-#[cfg(unix)]
-fn pipe_writer_to_file(writer: os_pipe::PipeWriter) -> std::fs::File {
-  use std::os::unix::io::FromRawFd;
-  use std::os::unix::io::IntoRawFd;
-  // SAFETY: Requires consuming ownership of the provided handle
-  unsafe { std::fs::File::from_raw_fd(writer.into_raw_fd()) }
-}
-
 fn start_output_redirect_thread(
   mut pipe_reader: os_pipe::PipeReader,
   sender: UnboundedSender<TestEvent>,
+  origin: Option<String>,
   flush_state: Arc<Mutex<Option<std::sync::mpsc::Sender<()>>>>,
 ) {
   spawn_blocking(move || loop {
@@ -2087,12 +3182,14 @@ fn start_output_redirect_thread(
       data = &data[0..data.len() - ZERO_WIDTH_SPACE.len()];
     }
 
-    if !data.is_empty()
-      && sender
-        .send(TestEvent::Output(buffer[0..size].to_vec()))
-        .is_err()
-    {
-      break;
+    if !data.is_empty() {
+      let event = match &origin {
+        Some(origin) => TestEvent::ModuleOutput(origin.clone(), data.to_vec()),
+        None => TestEvent::Output(buffer[0..size].to_vec()),
+      };
+      if sender.send(event).is_err() {
+        break;
+      }
     }
 
     // Always respond back if this was set. Ideally we would also check to
@@ -2165,4 +3262,19 @@ mod inner_test {
     assert!(!is_supported_test_path(Path::new("notatest.js")));
     assert!(!is_supported_test_path(Path::new("NotAtest.ts")));
   }
+
+  #[test]
+  fn test_escape_xml() {
+    assert_eq!(
+      escape_xml("<a href=\"x\">'&'</a>"),
+      "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+    );
+    assert_eq!(escape_xml("a\tb\nc\u{1b}d"), "a\tb\ncd");
+  }
+
+  #[test]
+  fn test_escape_tap_description() {
+    assert_eq!(escape_tap_description("a # b"), "a \\# b");
+    assert_eq!(escape_tap_description("a\\b\nc"), "a\\\\b c");
+  }
 }
@@ -2,8 +2,10 @@
 
 //! This module provides feature to upgrade deno executable
 
+use crate::args::discover_pinned_version;
 use crate::args::Flags;
 use crate::args::UpgradeFlags;
+use crate::args::DENO_VERSION_FILE_NAME;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::http_util::HttpClient;
@@ -290,7 +292,35 @@ pub async fn upgrade(
     ), current_exe_path.display());
   }
 
-  let install_version = match upgrade_flags.version {
+  let mut maybe_version = upgrade_flags.version.clone();
+  if upgrade_flags.pinned {
+    let cwd = std::env::current_dir()?;
+    let Some(pinned) = discover_pinned_version(&cwd) else {
+      bail!(
+        "No {} file with a valid version was found in {} or its parents.",
+        DENO_VERSION_FILE_NAME,
+        cwd.display()
+      );
+    };
+    log::info!(
+      "Using version {} pinned by {}",
+      pinned.version,
+      pinned.path.display()
+    );
+    maybe_version = Some(pinned.version.to_string());
+  } else if maybe_version.is_none() && !upgrade_flags.canary {
+    // the latest version is installed, which may not be the pinned one
+    let cwd = std::env::current_dir()?;
+    if let Some(pinned) = discover_pinned_version(&cwd) {
+      log::info!(
+        "{} pins Deno {}. Run `deno upgrade --pinned` to install it instead.",
+        pinned.path.display(),
+        pinned.version
+      );
+    }
+  }
+
+  let install_version = match maybe_version {
     Some(passed_version) => {
       let re_hash = lazy_regex::regex!("^[0-9a-f]{40}$");
       let passed_version = passed_version
@@ -391,15 +421,29 @@ pub async fn upgrade(
   fs::set_permissions(&new_exe_path, permissions)?;
   check_exe(&new_exe_path)?;
 
+  let output_exe_path =
+    upgrade_flags.output.as_ref().unwrap_or(&current_exe_path);
   if upgrade_flags.dry_run {
     fs::remove_file(&new_exe_path)?;
+    if *output_exe_path == current_exe_path {
+      log::info!(
+        "Would replace {} (version {}) with version {}",
+        output_exe_path.display(),
+        version::deno(),
+        install_version
+      );
+    } else {
+      log::info!(
+        "Would write version {} to {}",
+        install_version,
+        output_exe_path.display()
+      );
+    }
     log::info!("Upgraded successfully (dry run)");
     if !upgrade_flags.canary {
       print_release_notes(version::deno(), &install_version);
     }
   } else {
-    let output_exe_path =
-      upgrade_flags.output.as_ref().unwrap_or(&current_exe_path);
     let output_result = if *output_exe_path == current_exe_path {
       replace_exe(&new_exe_path, output_exe_path)
     } else {
@@ -9,6 +9,7 @@ use deno_core::serde::Serializer;
 use lazy_regex::lazy_regex;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
 
@@ -34,6 +35,8 @@ const UNSTABLE_DENO_PROPS: &[&str] = &[
   "ServeTlsInit",
   "Handler",
   "osUptime",
+  "WorkerPool",
+  "broadcastChannelStats",
 ];
 
 static MSG_MISSING_PROPERTY_DENO: Lazy<Regex> =
@@ -315,12 +318,19 @@ impl fmt::Display for Diagnostic {
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct Diagnostics(Vec<Diagnostic>);
+pub struct Diagnostics {
+  items: Vec<Diagnostic>,
+  /// How many diagnostics were left out by [`Diagnostics::truncate`].
+  omitted: usize,
+}
 
 impl Diagnostics {
   #[cfg(test)]
   pub fn new(diagnostics: Vec<Diagnostic>) -> Self {
-    Diagnostics(diagnostics)
+    Self {
+      items: diagnostics,
+      omitted: 0,
+    }
   }
 
   /// Return a set of diagnostics where only the values where the predicate
@@ -329,12 +339,47 @@ impl Diagnostics {
   where
     P: FnMut(&Diagnostic) -> Option<Diagnostic>,
   {
-    let diagnostics = self.0.iter().filter_map(predicate).collect();
-    Self(diagnostics)
+    let items = self.items.iter().filter_map(predicate).collect();
+    Self { items, omitted: 0 }
+  }
+
+  /// Splits off the diagnostics with one of the given codes, which are
+  /// returned second, as warnings.
+  pub fn split_warnings(self, codes: &HashSet<u64>) -> (Self, Self) {
+    let (warnings, errors): (Vec<_>, Vec<_>) = self
+      .items
+      .into_iter()
+      .partition(|diagnostic| codes.contains(&diagnostic.code));
+    let warnings = warnings
+      .into_iter()
+      .map(|diagnostic| Diagnostic {
+        category: DiagnosticCategory::Warning,
+        ..diagnostic
+      })
+      .collect();
+    (
+      Self {
+        items: errors,
+        omitted: 0,
+      },
+      Self {
+        items: warnings,
+        omitted: 0,
+      },
+    )
+  }
+
+  /// Keeps only the first `len` diagnostics, while still counting the others
+  /// when displayed.
+  pub fn truncate(&mut self, len: usize) {
+    if self.items.len() > len {
+      self.omitted += self.items.len() - len;
+      self.items.truncate(len);
+    }
   }
 
   pub fn is_empty(&self) -> bool {
-    self.0.is_empty()
+    self.items.is_empty()
   }
 }
 
@@ -344,7 +389,7 @@ impl<'de> Deserialize<'de> for Diagnostics {
     D: Deserializer<'de>,
   {
     let items: Vec<Diagnostic> = Deserialize::deserialize(deserializer)?;
-    Ok(Diagnostics(items))
+    Ok(Diagnostics { items, omitted: 0 })
   }
 }
 
@@ -353,14 +398,14 @@ impl Serialize for Diagnostics {
   where
     S: Serializer,
   {
-    Serialize::serialize(&self.0, serializer)
+    Serialize::serialize(&self.items, serializer)
   }
 }
 
 impl fmt::Display for Diagnostics {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let mut i = 0;
-    for item in &self.0 {
+    for item in &self.items {
       if i > 0 {
         write!(f, "\n\n")?;
       }
@@ -368,8 +413,16 @@ impl fmt::Display for Diagnostics {
       i += 1;
     }
 
-    if i > 1 {
-      write!(f, "\n\nFound {i} errors.")?;
+    let kind = if self.items.iter().all(|item| !item.is_error()) {
+      "warnings"
+    } else {
+      "errors"
+    };
+    if self.omitted > 0 {
+      let total = i + self.omitted;
+      write!(f, "\n\nFound {total} {kind}, only the first {i} are shown.")?;
+    } else if i > 1 {
+      write!(f, "\n\nFound {i} {kind}.")?;
     }
 
     Ok(())
@@ -476,28 +529,28 @@ mod tests {
     ]);
     let diagnostics: Diagnostics =
       serde_json::from_value(value).expect("cannot deserialize");
-    assert_eq!(diagnostics.0.len(), 4);
-    assert!(diagnostics.0[0].source_line.is_none());
-    assert!(diagnostics.0[0].file_name.is_none());
-    assert!(diagnostics.0[0].start.is_none());
-    assert!(diagnostics.0[0].end.is_none());
-    assert!(diagnostics.0[0].message_text.is_some());
-    assert!(diagnostics.0[0].message_chain.is_none());
-    assert!(diagnostics.0[0].related_information.is_none());
-    assert!(diagnostics.0[1].source_line.is_some());
-    assert!(diagnostics.0[1].file_name.is_some());
-    assert!(diagnostics.0[1].start.is_some());
-    assert!(diagnostics.0[1].end.is_some());
-    assert!(diagnostics.0[1].message_text.is_some());
-    assert!(diagnostics.0[1].message_chain.is_none());
-    assert!(diagnostics.0[1].related_information.is_none());
-    assert!(diagnostics.0[2].source_line.is_some());
-    assert!(diagnostics.0[2].file_name.is_some());
-    assert!(diagnostics.0[2].start.is_some());
-    assert!(diagnostics.0[2].end.is_some());
-    assert!(diagnostics.0[2].message_text.is_some());
-    assert!(diagnostics.0[2].message_chain.is_none());
-    assert!(diagnostics.0[2].related_information.is_some());
+    assert_eq!(diagnostics.items.len(), 4);
+    assert!(diagnostics.items[0].source_line.is_none());
+    assert!(diagnostics.items[0].file_name.is_none());
+    assert!(diagnostics.items[0].start.is_none());
+    assert!(diagnostics.items[0].end.is_none());
+    assert!(diagnostics.items[0].message_text.is_some());
+    assert!(diagnostics.items[0].message_chain.is_none());
+    assert!(diagnostics.items[0].related_information.is_none());
+    assert!(diagnostics.items[1].source_line.is_some());
+    assert!(diagnostics.items[1].file_name.is_some());
+    assert!(diagnostics.items[1].start.is_some());
+    assert!(diagnostics.items[1].end.is_some());
+    assert!(diagnostics.items[1].message_text.is_some());
+    assert!(diagnostics.items[1].message_chain.is_none());
+    assert!(diagnostics.items[1].related_information.is_none());
+    assert!(diagnostics.items[2].source_line.is_some());
+    assert!(diagnostics.items[2].file_name.is_some());
+    assert!(diagnostics.items[2].start.is_some());
+    assert!(diagnostics.items[2].end.is_some());
+    assert!(diagnostics.items[2].message_text.is_some());
+    assert!(diagnostics.items[2].message_chain.is_none());
+    assert!(diagnostics.items[2].related_information.is_some());
   }
 
   #[test]
@@ -581,4 +634,33 @@ mod tests {
     let actual = diagnostics.to_string();
     assert_eq!(strip_ansi_codes(&actual), "TS2552 [ERROR]: Cannot find name \'foo_Bar\'. Did you mean \'foo_bar\'?\nfoo_Bar();\n~~~~~~~\n    at test.ts:8:1\n\n    \'foo_bar\' is declared here.\n    function foo_bar() {\n             ~~~~~~~\n        at test.ts:4:10");
   }
+
+  #[test]
+  fn test_diagnostics_warnings_and_truncate() {
+    let diagnostic = |code: u64| {
+      json!({
+        "messageText": format!("message {code}"),
+        "category": 1,
+        "code": code,
+      })
+    };
+    let value = json!([
+      diagnostic(2345),
+      diagnostic(2304),
+      diagnostic(2345),
+      diagnostic(2322)
+    ]);
+    let diagnostics: Diagnostics = serde_json::from_value(value).unwrap();
+    let (mut errors, warnings) =
+      diagnostics.split_warnings(&HashSet::from([2345]));
+    assert_eq!(
+      strip_ansi_codes(&warnings.to_string()),
+      "TS2345 [WARN]: message 2345\n\nTS2345 [WARN]: message 2345\n\nFound 2 warnings."
+    );
+    errors.truncate(1);
+    assert_eq!(
+      strip_ansi_codes(&errors.to_string()),
+      "TS2304 [ERROR]: message 2304\n\nFound 2 errors, only the first 1 are shown."
+    );
+  }
 }
@@ -361,10 +361,31 @@ declare namespace Deno {
     cidr: string;
     /** The MAC address. */
     mac: string;
+    /** The flags of the network interface. */
+    flags: NetworkInterfaceFlags;
+    /** The maximum transmission unit of the network interface, in bytes, or
+     * `null` if it is unknown. */
+    mtu: number | null;
   }
 
   /**
-   * Returns an array of the network interface information.
+   * The flags of a network interface, which are part of the information
+   * returned from a call to {@linkcode Deno.networkInterfaces}.
+   *
+   * @category Network
+   */
+  export interface NetworkInterfaceFlags {
+    /** Whether the network interface is up. */
+    up: boolean;
+    /** Whether the network interface is a loopback interface. */
+    loopback: boolean;
+    /** Whether the network interface supports multicast. */
+    multicast: boolean;
+  }
+
+  /**
+   * Returns an array of the network interface information, which is sorted
+   * by the name of the interface.
    *
    * ```ts
    * console.log(Deno.networkInterfaces());
@@ -709,6 +730,23 @@ declare namespace Deno {
      * ```
      */
     step(fn: (t: TestContext) => void | Promise<void>): Promise<boolean>;
+
+    /** Creates a new temporary directory that is removed, along with its
+     * contents, once the current test or step finishes, even if it fails.
+     * Resolves to the full path of the directory.
+     *
+     * Requires `allow-write` permission.
+     *
+     * ```ts
+     * Deno.test("writes a file", async (t) => {
+     *   const dir = await t.tempDir({ prefix: "fixture_" });
+     *   await Deno.writeTextFile(`${dir}/hello.txt`, "hello");
+     * });
+     * ```
+     *
+     * @tags allow-write
+     */
+    tempDir(options?: MakeTempOptions): Promise<string>;
   }
 
   /** @category Testing */
@@ -972,6 +1010,91 @@ declare namespace Deno {
     fn: (t: TestContext) => void | Promise<void>,
   ): void;
 
+  /** @category Testing */
+  export interface TestSuiteDefinition {
+    fn: () => void;
+    /** The name of the suite, which the names of its cases are prefixed
+     * with. */
+    name: string;
+    /** If truthy the cases of the suite will be ignored. */
+    ignore?: boolean;
+    /** Runs the cases of the suite as if they had `only` set to `true`. */
+    only?: boolean;
+  }
+
+  /** @category Testing */
+  export namespace test {
+    /** Register a suite of tests, the cases registered by `fn` with
+     * {@linkcode Deno.test.it} and the hooks registered with
+     * {@linkcode Deno.test.beforeEach} and {@linkcode Deno.test.afterEach}.
+     * `fn` is called right away and must register them synchronously. Suites
+     * can be nested.
+     *
+     * Each case is a test named after the suites it is in, like
+     * `"math > add > adds numbers"`, which is what `deno test --filter`
+     * matches.
+     *
+     * ```ts
+     * import { assertEquals } from "https://deno.land/std/testing/asserts.ts";
+     *
+     * Deno.test.describe("math", () => {
+     *   let values: number[];
+     *
+     *   Deno.test.beforeEach(() => {
+     *     values = [1, 2];
+     *   });
+     *
+     *   Deno.test.it("adds numbers", () => {
+     *     assertEquals(values[0] + values[1], 3);
+     *   });
+     * });
+     * ```
+     *
+     * @category Testing
+     */
+    export function describe(name: string, fn: () => void): void;
+    /** Register a suite of tests.
+     *
+     * @category Testing
+     */
+    export function describe(t: TestSuiteDefinition): void;
+    /** Register a suite of tests.
+     *
+     * @category Testing
+     */
+    export function describe(
+      options: Omit<TestSuiteDefinition, "fn">,
+      fn: () => void,
+    ): void;
+
+    /** Register a case of the current suite. It takes the same arguments as
+     * {@linkcode Deno.test}, which it is the same as out of a suite.
+     *
+     * @category Testing
+     */
+    export const it: typeof Deno.test;
+
+    /** Register a hook of the current suite, which is run before each of its
+     * cases, including the ones of the suites nested in it. The hooks of the
+     * outer suites run first.
+     *
+     * @category Testing
+     */
+    export function beforeEach(
+      fn: (t: TestContext) => void | Promise<void>,
+    ): void;
+
+    /** Register a hook of the current suite, which is run after each of its
+     * cases, including the ones of the suites nested in it, even if the case
+     * failed. The hooks of the inner suites run first.
+     *
+     * @category Testing
+     */
+    export function afterEach(
+      fn: (t: TestContext) => void | Promise<void>,
+    ): void;
+  }
+
   /**
    * The interface for defining a benchmark test using {@linkcode Deno.bench}.
    *
@@ -987,12 +1110,15 @@ declare namespace Deno {
     /** Group name for the benchmark.
      *
      * Grouped benchmarks produce a group time summary, where the difference
-     * in performance between each test of the group is compared. */
+     * in performance between each test of the group and the baseline is
+     * compared, along with the 95% confidence interval of the difference. The
+     * summary is also part of the `--json` output. */
     group?: string;
     /** Benchmark should be used as the baseline for other benchmarks.
      *
      * If there are multiple baselines in a group, the first one is used as the
-     * baseline. */
+     * baseline. Without a baseline, the fastest benchmark of the group is
+     * used. */
     baseline?: boolean;
     /** If at least one bench has `only` set to true, only run benches that have
      * `only` set to `true` and fail the bench suite. */
@@ -4109,8 +4235,16 @@ declare namespace Deno {
    * @category Sub Process
    */
   export class ChildProcess {
+    /** The stdin of the child. Closing the stream signals the end of the
+     * input to the child, while aborting it also cancels a write that the
+     * child isn't consuming. */
     get stdin(): WritableStream<Uint8Array>;
+    /** The stdout of the child. Piping it to another resource backed stream,
+     * like the `writable` of a {@linkcode Deno.FsFile}, or using it as the
+     * body of a response returned from {@linkcode Deno.serve} copies the
+     * output without passing it through JavaScript. */
     get stdout(): ReadableStream<Uint8Array>;
+    /** The stderr of the child. See {@linkcode ChildProcess.stdout}. */
     get stderr(): ReadableStream<Uint8Array>;
     readonly pid: number;
     /** Get the status of the child. */
@@ -4434,7 +4568,8 @@ declare namespace Deno {
       | "osRelease"
       | "osUptime"
       | "uid"
-      | "gid";
+      | "gid"
+      | "cpus";
   }
 
   /** The permission descriptor for the `allow-ffi` permissions, which controls
@@ -556,6 +556,14 @@ declare class Performance extends EventTarget {
   /** Removes stored timestamp with the associated name. */
   clearMeasures(measureName?: string): void;
 
+  /** Removes all the `"resource"` entries from the performance timeline. */
+  clearResourceTimings(): void;
+
+  /** Sets how many `"resource"` entries the performance timeline keeps, which
+   * is 250 by default. A `"resourcetimingbufferfull"` event is dispatched when
+   * a fetch completes while there is no room left. */
+  setResourceTimingBufferSize(maxSize: number): void;
+
   getEntries(): PerformanceEntryList;
   getEntriesByName(name: string, type?: string): PerformanceEntryList;
   getEntriesByType(type: string): PerformanceEntryList;
@@ -661,6 +669,78 @@ declare class PerformanceMeasure extends PerformanceEntry {
   readonly entryType: "measure";
 }
 
+/** `PerformanceResourceTiming` is a `PerformanceEntry` with an entryType of
+ * `"resource"`, which is added to the performance timeline for each `fetch()`
+ * once the headers of its response are received.
+ *
+ * @category Performance
+ */
+declare class PerformanceResourceTiming extends PerformanceEntry {
+  readonly entryType: "resource";
+  /** Always `"fetch"`. */
+  readonly initiatorType: string;
+  /** Always an empty string, as the protocol isn't known. */
+  readonly nextHopProtocol: string;
+  readonly fetchStart: number;
+  readonly responseStart: number;
+  readonly responseEnd: number;
+  readonly responseStatus: number;
+}
+
+/** @category Performance */
+declare interface PerformanceObserverInit {
+  /** The types of entries to observe. Can't be used with `type` or
+   * `buffered`. */
+  entryTypes?: string[];
+  /** The type of entries to observe. */
+  type?: string;
+  /** Whether the entries of `type` that are already in the performance
+   * timeline are observed as well. */
+  buffered?: boolean;
+}
+
+/** @category Performance */
+declare type PerformanceObserverCallback = (
+  entries: PerformanceObserverEntryList,
+  observer: PerformanceObserver,
+) => void;
+
+/** The list of entries that is given to a `PerformanceObserver` callback.
+ *
+ * @category Performance
+ */
+declare class PerformanceObserverEntryList {
+  getEntries(): PerformanceEntryList;
+  getEntriesByName(name: string, type?: string): PerformanceEntryList;
+  getEntriesByType(type: string): PerformanceEntryList;
+}
+
+/** Observes the entries that are added to the performance timeline. The
+ * callback is called asynchronously with the entries that were added since it
+ * was last called.
+ *
+ * ```ts
+ * const observer = new PerformanceObserver((list) => {
+ *   for (const entry of list.getEntries()) {
+ *     console.log(entry.name, entry.duration);
+ *   }
+ * });
+ * observer.observe({ entryTypes: ["measure", "resource"] });
+ * ```
+ *
+ * @category Performance
+ */
+declare class PerformanceObserver {
+  constructor(callback: PerformanceObserverCallback);
+  /** The entry types that can be observed. */
+  static readonly supportedEntryTypes: readonly string[];
+  observe(options?: PerformanceObserverInit): void;
+  disconnect(): void;
+  /** Returns the entries that are yet to be given to the callback, which
+   * won't be given to it. */
+  takeRecords(): PerformanceEntryList;
+}
+
 /** @category DOM Events */
 declare interface CustomEventInit<T = any> extends EventInit {
   detail?: T;
@@ -794,15 +794,83 @@ declare namespace Deno {
    * const req = await fetch("https://myserver.com", { client });
    * ```
    *
+   * If the client has a {@linkcode Deno.RetryOptions retry policy}, a
+   * `"retry"` event is dispatched on it for every retry of its requests, once
+   * the request completed. It is a `CustomEvent` whose `detail` is a
+   * {@linkcode Deno.HttpClientRetryEventDetail}.
+   *
    * @category Fetch API
    */
-  export interface HttpClient {
+  export interface HttpClient extends EventTarget {
     /** The resource ID associated with the client. */
     rid: number;
     /** Close the HTTP client. */
     close(): void;
   }
 
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The retry policy of a {@linkcode Deno.HttpClient}. A request is retried
+   * with an exponential backoff when it fails before a response was received
+   * or when the response has one of the given statuses. Requests with a
+   * streamed body are never retried, as their body can't be sent again.
+   *
+   * @category Fetch API
+   */
+  export interface RetryOptions {
+    /** How many times a request is sent at most, including the first time.
+     *
+     * @default {3}
+     */
+    maxAttempts?: number;
+    /** The delay in milliseconds before the first retry, which doubles for
+     * every further retry.
+     *
+     * @default {100}
+     */
+    initialDelay?: number;
+    /** The longest delay in milliseconds between two attempts. It also caps
+     * the delay a `Retry-After` header asks for.
+     *
+     * @default {10000}
+     */
+    maxDelay?: number;
+    /** The response statuses that cause a retry.
+     *
+     * @default {[429, 502, 503, 504]}
+     */
+    retryOnStatus?: number[];
+    /** Whether a request is retried when it failed before a response was
+     * received.
+     *
+     * @default {true}
+     */
+    retryOnConnectionError?: boolean;
+    /** The methods whose requests are retried. Defaults to the idempotent
+     * methods: `GET`, `HEAD`, `OPTIONS`, `TRACE`, `PUT` and `DELETE`. */
+    methods?: string[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Describes a retry of a request in the `"retry"` events of a
+   * {@linkcode Deno.HttpClient}.
+   *
+   * @category Fetch API
+   */
+  export interface HttpClientRetryEventDetail {
+    method: string;
+    url: string;
+    /** The attempt that failed, starting at 1. */
+    attempt: number;
+    /** The status of the response, if one was received. */
+    status: number | null;
+    /** Why the request failed, if no response was received. */
+    error: string | null;
+    /** How long in milliseconds it was waited for before the next attempt. */
+    delay: number;
+  }
+
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * The options used when creating a {@linkcode Deno.HttpClient}.
@@ -837,6 +905,8 @@ declare namespace Deno {
      * @default {true}
      */
     http2?: boolean;
+    /** Retry failed requests according to this policy. */
+    retry?: RetryOptions;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -2118,6 +2188,117 @@ declare namespace Deno {
     /** The value of this unsigned 64-bit integer, represented as a bigint. */
     readonly value: bigint;
   }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options which can be set when calling {@linkcode Deno.WorkerPool}.
+   *
+   * @category Web Workers
+   */
+  export interface WorkerPoolOptions {
+    /** The name given to the workers of the pool. */
+    name?: string;
+    /** The number of workers that are kept ready to be acquired. Defaults to
+     * `1`. */
+    size?: number;
+    /** The permissions of the workers of the pool, see
+     * {@linkcode WorkerOptions}. */
+    deno?: {
+      permissions?: Deno.PermissionOptions;
+    };
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * A pool of module workers for a specifier which are spawned ahead of time,
+   * so that a latency-sensitive server can hand work to a worker which has
+   * already loaded and evaluated its module.
+   *
+   * Each call to `acquire()` hands out an idle worker and spawns a new one in
+   * its place, so the pool keeps `size` workers warm. An acquired worker is
+   * an ordinary {@linkcode Worker} which is owned by the caller.
+   *
+   * The permissions in `deno.permissions` are derived from the permissions
+   * of the caller whenever a worker is spawned, so a worker spawned after
+   * `Deno.permissions.revoke()` doesn't get the revoked permission. Idle
+   * workers that were spawned with permissions that have been revoked since
+   * are terminated instead of being handed out.
+   *
+   * The idle workers are terminated by `close()`, or once the pool is
+   * garbage collected.
+   *
+   * ```ts
+   * const pool = new Deno.WorkerPool(
+   *   new URL("./handler.ts", import.meta.url),
+   *   { size: 4 },
+   * );
+   * const worker = pool.acquire();
+   * worker.postMessage({ url: "/" });
+   * ```
+   *
+   * @category Web Workers
+   */
+  export class WorkerPool {
+    constructor(specifier: string | URL, options?: WorkerPoolOptions);
+    /** Take an idle worker out of the pool. */
+    acquire(): Worker;
+    /** Terminate the idle workers of the pool. Workers that were acquired
+     * keep running. */
+    close(): void;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The stats of a channel of {@linkcode BroadcastChannelStats}.
+   *
+   * @category Broadcast Channel
+   */
+  export interface BroadcastChannelChannelStats {
+    /** The name the messages of the channel are sent with. */
+    name: string;
+    /** How many subscriptions receive the messages of the channel. */
+    subscribers: number;
+    /** How many messages of the channel are yet to be received, summed over
+     * the subscriptions. */
+    queuedMessages: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * What {@linkcode Deno.broadcastChannelStats} returns.
+   *
+   * @category Broadcast Channel
+   */
+  export interface BroadcastChannelStats {
+    /** How many subscriptions there are. The channels of a worker that
+     * aren't patterns share a single subscription, while each pattern
+     * channel has its own. */
+    subscribers: number;
+    /** How many messages were dropped before they could be received. */
+    droppedMessages: number;
+    /** The channels of every worker of the process, sorted by name. Besides
+     * the channels with messages yet to be received, this includes the
+     * channels whose last messages are kept to be replayed, until they
+     * expire, so it can list channels that nobody listens to anymore. */
+    channels: BroadcastChannelChannelStats[];
+    /** The names of the channels that are open in the current worker. */
+    openChannels: string[];
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Returns a snapshot of the state of the {@linkcode BroadcastChannel}
+   * backend, to check its health and to find channels that were never
+   * closed.
+   *
+   * ```ts
+   * const channel = new BroadcastChannel("jobs");
+   * console.log(Deno.broadcastChannelStats().openChannels); // ["jobs"]
+   * ```
+   *
+   * @category Broadcast Channel
+   */
+  export function broadcastChannelStats(): BroadcastChannelStats;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -436,6 +436,18 @@ fn op_load(state: &mut OpState, args: Value) -> Result<Value, AnyError> {
     hash = Some("1".to_string());
     media_type = MediaType::Dts;
     Some(Cow::Borrowed("declare const __: any;\nexport = __;\n"))
+  } else if &v.specifier == "internal:///bytes_module.d.ts" {
+    hash = Some("1".to_string());
+    media_type = MediaType::Dts;
+    Some(Cow::Borrowed(
+      "declare const bytes: Uint8Array;\nexport default bytes;\n",
+    ))
+  } else if &v.specifier == "internal:///text_module.d.ts" {
+    hash = Some("1".to_string());
+    media_type = MediaType::Dts;
+    Some(Cow::Borrowed(
+      "declare const text: string;\nexport default text;\n",
+    ))
   } else if let Some(name) = v.specifier.strip_prefix("asset:///") {
     let maybe_source = get_lazily_loaded_asset(name);
     hash = get_maybe_hash(maybe_source, state.hash_data);
@@ -550,11 +562,33 @@ fn op_resolve(
     }
 
     let graph = &state.graph;
-    let resolved_dep = graph
+    let dependency = graph
       .get(&referrer)
       .and_then(|m| m.esm())
-      .and_then(|m| m.dependencies.get(&specifier))
-      .and_then(|d| d.maybe_type.ok().or_else(|| d.maybe_code.ok()));
+      .and_then(|m| m.dependencies.get(&specifier));
+
+    // assets imported as bytes or text aren't part of the graph, so give
+    // them a declaration of their default export instead
+    match dependency.and_then(|d| d.maybe_assert_type.as_deref()) {
+      Some("bytes") => {
+        resolved.push((
+          "internal:///bytes_module.d.ts".to_string(),
+          ".d.ts".to_string(),
+        ));
+        continue;
+      }
+      Some("text") => {
+        resolved.push((
+          "internal:///text_module.d.ts".to_string(),
+          ".d.ts".to_string(),
+        ));
+        continue;
+      }
+      _ => {}
+    }
+
+    let resolved_dep =
+      dependency.and_then(|d| d.maybe_type.ok().or_else(|| d.maybe_code.ok()));
 
     let maybe_result = match resolved_dep {
       Some(ResolutionResolved { specifier, .. }) => {
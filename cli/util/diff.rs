@@ -1,8 +1,10 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use crate::colors;
+use deno_core::serde::Serialize;
 use dissimilar::diff as difference;
 use dissimilar::Chunk;
+use std::collections::HashMap;
 use std::fmt::Write as _;
 
 /// Print diff of the same file_path, before and after formatting.
@@ -145,6 +147,180 @@ impl DiffBuilder {
   }
 }
 
+const NO_NEWLINE_AT_EOF: &str = "\\ No newline at end of file";
+
+/// A group of changed lines along with the unchanged lines around them, as
+/// found in a unified diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+  /// 1-based line in the original text where the hunk starts.
+  pub orig_start: usize,
+  pub orig_lines: usize,
+  /// 1-based line in the edited text where the hunk starts.
+  pub edit_start: usize,
+  pub edit_lines: usize,
+  /// The lines of the hunk, prefixed with `' '`, `'-'` or `'+'`.
+  pub lines: Vec<String>,
+}
+
+impl DiffHunk {
+  pub fn header(&self) -> String {
+    format!(
+      "@@ -{} +{} @@",
+      hunk_range(self.orig_start, self.orig_lines),
+      hunk_range(self.edit_start, self.edit_lines)
+    )
+  }
+}
+
+fn hunk_range(start: usize, len: usize) -> String {
+  // an empty range refers to the line before it
+  let start = if len == 0 { start - 1 } else { start };
+  if len == 1 {
+    start.to_string()
+  } else {
+    format!("{start},{len}")
+  }
+}
+
+/// Computes the line based diff hunks between the two texts, including
+/// `context` unchanged lines around each change.
+pub fn diff_hunks(
+  orig_text: &str,
+  edit_text: &str,
+  context: usize,
+) -> Vec<DiffHunk> {
+  let orig_text = orig_text.replace("\r\n", "\n");
+  let edit_text = edit_text.replace("\r\n", "\n");
+  let ops = line_diff(&orig_text, &edit_text);
+
+  let changes = ops
+    .iter()
+    .enumerate()
+    .filter(|(_, (kind, _))| *kind != ' ')
+    .map(|(i, _)| i)
+    .collect::<Vec<_>>();
+
+  let mut hunks = Vec::new();
+  let mut i = 0;
+  while i < changes.len() {
+    // merge changes that are separated by no more than 2 * context lines
+    let mut j = i;
+    while j + 1 < changes.len()
+      && changes[j + 1] - changes[j] <= 2 * context + 1
+    {
+      j += 1;
+    }
+    let start = changes[i].saturating_sub(context);
+    let end = (changes[j] + context + 1).min(ops.len());
+
+    let count = |ops: &[(char, &str)], kind: char| {
+      ops.iter().filter(|(k, _)| *k == ' ' || *k == kind).count()
+    };
+    let mut hunk = DiffHunk {
+      orig_start: count(&ops[..start], '-') + 1,
+      orig_lines: count(&ops[start..end], '-'),
+      edit_start: count(&ops[..start], '+') + 1,
+      edit_lines: count(&ops[start..end], '+'),
+      lines: Vec::with_capacity(end - start),
+    };
+    for (kind, line) in &ops[start..end] {
+      match line.strip_suffix('\n') {
+        Some(line) => hunk.lines.push(format!("{kind}{line}")),
+        None => {
+          hunk.lines.push(format!("{kind}{line}"));
+          hunk.lines.push(NO_NEWLINE_AT_EOF.to_string());
+        }
+      }
+    }
+    hunks.push(hunk);
+    i = j + 1;
+  }
+  hunks
+}
+
+/// Prints a unified diff of the two texts, which can be applied with
+/// `git apply` or `patch`.
+pub fn unified_diff(
+  file_name: &str,
+  orig_text: &str,
+  edit_text: &str,
+  context: usize,
+) -> String {
+  let hunks = diff_hunks(orig_text, edit_text, context);
+  if hunks.is_empty() {
+    return String::new();
+  }
+  let mut output = String::new();
+  writeln!(output, "--- a/{file_name}").unwrap();
+  writeln!(output, "+++ b/{file_name}").unwrap();
+  for hunk in hunks {
+    writeln!(output, "{}", hunk.header()).unwrap();
+    for line in hunk.lines {
+      writeln!(output, "{line}").unwrap();
+    }
+  }
+  output
+}
+
+/// Diffs the texts line by line. Each unique line is mapped to a single
+/// character so that the character based diff can be reused.
+fn line_diff<'a>(
+  orig_text: &'a str,
+  edit_text: &'a str,
+) -> Vec<(char, &'a str)> {
+  let mut line_chars = HashMap::new();
+  let mut lines = Vec::new();
+  let mut encode = |text: &'a str| {
+    text
+      .split_inclusive('\n')
+      .map(|line| {
+        *line_chars.entry(line).or_insert_with(|| {
+          lines.push(line);
+          index_to_char(lines.len() - 1)
+        })
+      })
+      .collect::<String>()
+  };
+  let orig = encode(orig_text);
+  let edit = encode(edit_text);
+
+  let mut ops = Vec::new();
+  for chunk in difference(&orig, &edit) {
+    let (kind, text) = match chunk {
+      Chunk::Equal(text) => (' ', text),
+      Chunk::Delete(text) => ('-', text),
+      Chunk::Insert(text) => ('+', text),
+    };
+    for c in text.chars() {
+      ops.push((kind, lines[char_to_index(c)]));
+    }
+  }
+  ops
+}
+
+fn index_to_char(index: usize) -> char {
+  // skip the surrogate range, which can't be represented as a char
+  let value = index as u32;
+  let value = if value >= 0xD800 {
+    value + 0x800
+  } else {
+    value
+  };
+  char::from_u32(value).expect("too many unique lines to diff")
+}
+
+fn char_to_index(c: char) -> usize {
+  let value = c as u32;
+  let value = if value >= 0xE000 {
+    value - 0x800
+  } else {
+    value
+  };
+  value as usize
+}
+
 fn fmt_add() -> String {
   colors::green_bold("+").to_string()
 }
@@ -219,6 +395,54 @@ mod tests {
     run_test("test\n", "test\r\n", " | Text differed by line endings.\n");
   }
 
+  #[test]
+  fn test_unified_diff() {
+    let orig = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+    let edit = "a\nB\nc\nd\ne\nf\ng\nh\nI\n";
+    assert_eq!(
+      unified_diff("mod.ts", orig, edit, 1),
+      concat!(
+        "--- a/mod.ts\n",
+        "+++ b/mod.ts\n",
+        "@@ -1,3 +1,3 @@\n",
+        " a\n",
+        "-b\n",
+        "+B\n",
+        " c\n",
+        "@@ -8,2 +8,2 @@\n",
+        " h\n",
+        "-i\n",
+        "+I\n",
+      ),
+    );
+    // hunks that are close to each other are merged
+    assert_eq!(diff_hunks(orig, edit, 3).len(), 1);
+    assert_eq!(unified_diff("mod.ts", orig, orig, 3), "");
+  }
+
+  #[test]
+  fn test_diff_hunks_no_newline_at_eof() {
+    let hunks = diff_hunks("a\nb", "a\nb\n", 0);
+    assert_eq!(
+      hunks,
+      vec![DiffHunk {
+        orig_start: 2,
+        orig_lines: 1,
+        edit_start: 2,
+        edit_lines: 1,
+        lines: vec![
+          "-b".to_string(),
+          NO_NEWLINE_AT_EOF.to_string(),
+          "+b".to_string(),
+        ],
+      }]
+    );
+    assert_eq!(hunks[0].header(), "@@ -2 +2 @@");
+
+    let hunks = diff_hunks("a\n", "", 0);
+    assert_eq!(hunks[0].header(), "@@ -1 +0,0 @@");
+  }
+
   fn run_test(diff_text1: &str, diff_text2: &str, expected_output: &str) {
     assert_eq!(
       test_util::strip_ansi_codes(&diff(diff_text1, diff_text2,)),
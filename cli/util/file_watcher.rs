@@ -3,10 +3,15 @@
 use crate::colors;
 use crate::util::fs::canonicalize_path;
 
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
+use deno_core::futures::future;
 use deno_core::futures::Future;
 use deno_runtime::fmt_errors::format_js_error;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellState;
 use log::info;
 use notify::event::Event as NotifyEvent;
 use notify::event::EventKind;
@@ -15,12 +20,15 @@ use notify::RecommendedWatcher;
 use notify::RecursiveMode;
 use notify::Watcher;
 use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::task::LocalSet;
 use tokio::time::sleep;
 
 const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
@@ -125,6 +133,131 @@ pub struct PrintConfig {
   pub job_name: String,
   /// determine whether to clear the terminal screen; applicable to TTY environments only.
   pub clear_screen: bool,
+  /// tasks or commands to run around restarts.
+  pub restart_hooks: RestartHooks,
+}
+
+/// A task or command run by the watcher around a restart.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestartHook {
+  /// Used to prefix the output of the command, usually the task name.
+  pub name: String,
+  pub command: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RestartHooks {
+  /// Run after a file change is detected, before the job is restarted. No
+  /// files are being watched at that point, so files written by these
+  /// commands don't trigger another restart.
+  pub pre_restart: Vec<RestartHook>,
+  /// Run once the job has been restarted, alongside it.
+  pub post_restart: Vec<RestartHook>,
+  /// Directory the commands are run in.
+  pub cwd: PathBuf,
+}
+
+impl RestartHooks {
+  async fn run_pre_restart(&self, restarted: bool) {
+    if restarted {
+      run_restart_hooks(&self.pre_restart, &self.cwd).await;
+    }
+  }
+
+  async fn run_post_restart(&self, restarted: bool) {
+    if restarted {
+      run_restart_hooks(&self.post_restart, &self.cwd).await;
+    }
+  }
+}
+
+async fn run_restart_hooks(hooks: &[RestartHook], cwd: &Path) {
+  for hook in hooks {
+    if let Err(err) = run_restart_hook(hook, cwd).await {
+      eprintln!("{}: {:#}", colors::red_bold("error"), err);
+    }
+  }
+}
+
+async fn run_restart_hook(
+  hook: &RestartHook,
+  cwd: &Path,
+) -> Result<(), AnyError> {
+  info!(
+    "{} Running {} {}",
+    colors::intense_blue("Watcher"),
+    colors::cyan(&hook.name),
+    hook.command,
+  );
+  let seq_list = deno_task_shell::parser::parse(&hook.command)
+    .with_context(|| format!("Error parsing command '{}'.", hook.name))?;
+  let env_vars = std::env::vars().collect();
+  let state = ShellState::new(env_vars, cwd, Default::default());
+
+  // prefix every line of output with the name of the hook so it can be told
+  // apart from the output of the job
+  let prefix = format!("{} ", colors::gray(format!("[{}]", hook.name)));
+  let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+  let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+  let stdout_prefix = prefix.clone();
+  let stdout_task = tokio::task::spawn_blocking(move || {
+    stdout_reader
+      .pipe_to(&mut PrefixedWriter::new(std::io::stdout(), stdout_prefix))
+  });
+  let stderr_task = tokio::task::spawn_blocking(move || {
+    stderr_reader.pipe_to(&mut PrefixedWriter::new(std::io::stderr(), prefix))
+  });
+
+  let exit_code = LocalSet::new()
+    .run_until(deno_task_shell::execute_with_pipes(
+      seq_list,
+      state,
+      ShellPipeReader::stdin(),
+      stdout_writer,
+      stderr_writer,
+    ))
+    .await;
+  stdout_task.await??;
+  stderr_task.await??;
+
+  if exit_code != 0 {
+    bail!("'{}' failed with exit code {}", hook.name, exit_code);
+  }
+  Ok(())
+}
+
+/// Writes `prefix` before every line written to `inner`.
+struct PrefixedWriter<W: Write> {
+  inner: W,
+  prefix: String,
+  at_line_start: bool,
+}
+
+impl<W: Write> PrefixedWriter<W> {
+  fn new(inner: W, prefix: String) -> Self {
+    Self {
+      inner,
+      prefix,
+      at_line_start: true,
+    }
+  }
+}
+
+impl<W: Write> Write for PrefixedWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    for line in buf.split_inclusive(|b| *b == b'\n') {
+      if self.at_line_start {
+        self.inner.write_all(self.prefix.as_bytes())?;
+      }
+      self.inner.write_all(line)?;
+      self.at_line_start = line.ends_with(b"\n");
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.inner.flush()
+  }
 }
 
 fn create_print_after_restart_fn(clear_screen: bool) -> impl Fn() {
@@ -165,6 +298,7 @@ where
   let PrintConfig {
     job_name,
     clear_screen,
+    restart_hooks,
   } = print_config;
 
   // Store previous data. If module resolution fails at some point, the watcher will try to
@@ -208,13 +342,20 @@ where
 
   info!("{} {} started.", colors::intense_blue("Watcher"), job_name,);
 
+  let mut restarted = false;
   loop {
+    restart_hooks.run_pre_restart(restarted).await;
+
     let mut watcher = new_watcher(sender.clone())?;
     add_paths_to_watcher(&mut watcher, &paths_to_watch);
 
     match resolution_result {
       Ok(operation_arg) => {
-        let fut = error_handler(operation(operation_arg));
+        let fut = future::join(
+          error_handler(operation(operation_arg)),
+          restart_hooks.run_post_restart(restarted),
+        );
+        restarted = true;
         select! {
           (paths, result) = next_restart(&mut resolver, &mut receiver) => {
             if result.is_ok() {
@@ -249,6 +390,7 @@ where
       paths_to_watch = paths;
     }
     resolution_result = result;
+    restarted = true;
 
     print_after_restart();
 
@@ -277,6 +419,7 @@ where
   let PrintConfig {
     job_name,
     clear_screen,
+    restart_hooks,
   } = print_config;
 
   let print_after_restart = create_print_after_restart_fn(clear_screen);
@@ -303,6 +446,7 @@ where
     }
   }
 
+  let mut restarted = false;
   loop {
     // We may need to give the runtime a tick to settle, as cancellations may need to propagate
     // to tasks. We choose yielding 10 times to the runtime as a decent heuristic. If watch tests
@@ -311,6 +455,8 @@ where
       tokio::task::yield_now().await;
     }
 
+    restart_hooks.run_pre_restart(restarted).await;
+
     let mut watcher = new_watcher(watcher_sender.clone())?;
     consume_paths_to_watch(&mut watcher, &mut paths_to_watch_receiver);
 
@@ -320,7 +466,11 @@ where
         add_paths_to_watcher(&mut watcher, &maybe_paths.unwrap());
       }
     };
-    let operation_future = error_handler(operation(operation_args.clone())?);
+    let operation_future = future::join(
+      error_handler(operation(operation_args.clone())?),
+      restart_hooks.run_post_restart(restarted),
+    );
+    restarted = true;
 
     select! {
       _ = receiver_future => {},
@@ -387,3 +537,20 @@ fn add_paths_to_watcher(watcher: &mut RecommendedWatcher, paths: &[PathBuf]) {
   }
   log::debug!("Watching paths: {:?}", paths);
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn prefixed_writer() {
+    let mut output = Vec::new();
+    let mut writer = PrefixedWriter::new(&mut output, "[task] ".to_string());
+    writer.write_all(b"foo\nba").unwrap();
+    writer.write_all(b"r\n\nbaz").unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      "[task] foo\n[task] bar\n[task] \n[task] baz"
+    );
+  }
+}
@@ -20,6 +20,7 @@ use std::time::Duration;
 use walkdir::WalkDir;
 
 use crate::args::FilesConfig;
+use crate::util::ignore_file::IgnoreFile;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 use crate::util::progress_bar::ProgressMessagePrompt;
@@ -140,6 +141,7 @@ pub struct FileCollector<TFilter: Fn(&Path) -> bool> {
   file_filter: TFilter,
   ignore_git_folder: bool,
   ignore_node_modules: bool,
+  use_ignore_files: bool,
 }
 
 impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
@@ -149,6 +151,7 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
       file_filter,
       ignore_git_folder: false,
       ignore_node_modules: false,
+      use_ignore_files: false,
     }
   }
 
@@ -170,6 +173,17 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
     self
   }
 
+  /// Ignores the files listed in the `.denoignore` files of the current
+  /// directory and of the collected directories.
+  ///
+  /// The excluded paths take precedence over these files, while the paths
+  /// that are explicitly specified are never ignored by them. A deeper
+  /// `.denoignore` file takes precedence over the ones above it.
+  pub fn use_ignore_files(mut self, use_ignore_files: bool) -> Self {
+    self.use_ignore_files = use_ignore_files;
+    self
+  }
+
   pub fn collect_files(
     &self,
     files: &[PathBuf],
@@ -181,8 +195,27 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
     } else {
       Cow::Borrowed(files)
     };
+    let cwd = if self.use_ignore_files {
+      current_dir().and_then(|cwd| canonicalize_path(&cwd)).ok()
+    } else {
+      None
+    };
     for file in files.iter() {
       if let Ok(file) = canonicalize_path(file) {
+        // the ignore files that apply to the current entry, along with the
+        // minimum depth of the entries that they apply to
+        let mut ignore_files: Vec<(usize, IgnoreFile)> = Vec::new();
+        if let Some(cwd) = &cwd {
+          let mut ancestors = file
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(cwd))
+            .filter_map(IgnoreFile::read)
+            .map(|ignore_file| (0, ignore_file))
+            .collect::<Vec<_>>();
+          ancestors.reverse();
+          ignore_files.extend(ancestors);
+        }
         // use an iterator like this in order to minimize the number of file system operations
         let mut iterator = WalkDir::new(&file).into_iter();
         loop {
@@ -194,7 +227,14 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
           let file_type = e.file_type();
           let is_dir = file_type.is_dir();
           if let Ok(c) = canonicalize_path(e.path()) {
-            if self.canonicalized_ignore.iter().any(|i| c.starts_with(i)) {
+            // drop the ignore files of the directories that were left
+            ignore_files.retain(|(depth, _)| *depth <= e.depth());
+            // the explicitly specified paths are never ignored by the
+            // ignore files
+            if self.canonicalized_ignore.iter().any(|i| c.starts_with(i))
+              || (e.depth() > 0
+                && is_ignored_by_files(&ignore_files, &c, is_dir))
+            {
               if is_dir {
                 iterator.skip_current_dir();
               }
@@ -212,6 +252,10 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
                 .unwrap_or(false);
               if should_ignore_dir {
                 iterator.skip_current_dir();
+              } else if self.use_ignore_files {
+                if let Some(ignore_file) = IgnoreFile::read(&c) {
+                  ignore_files.push((e.depth() + 1, ignore_file));
+                }
               }
             } else if (self.file_filter)(e.path()) {
               target_files.push(c);
@@ -227,6 +271,18 @@ impl<TFilter: Fn(&Path) -> bool> FileCollector<TFilter> {
   }
 }
 
+fn is_ignored_by_files(
+  ignore_files: &[(usize, IgnoreFile)],
+  path: &Path,
+  is_dir: bool,
+) -> bool {
+  ignore_files
+    .iter()
+    .rev()
+    .find_map(|(_, ignore_file)| ignore_file.matches(path, is_dir))
+    .unwrap_or(false)
+}
+
 /// Collects module specifiers that satisfy the given predicate as a file path, by recursively walking `include`.
 /// Specifiers that start with http and https are left intact.
 /// Note: This ignores all .git and node_modules folders.
@@ -238,7 +294,8 @@ pub fn collect_specifiers(
   let file_collector = FileCollector::new(predicate)
     .add_ignore_paths(&files.exclude)
     .ignore_git_folder()
-    .ignore_node_modules();
+    .ignore_node_modules()
+    .use_ignore_files(!files.no_ignore);
 
   let root_path = current_dir()?;
   let include_files = if files.include.is_empty() {
@@ -768,6 +825,62 @@ mod tests {
     assert_eq!(file_names, expected);
   }
 
+  #[test]
+  fn test_collect_files_ignore_files() {
+    let t = TempDir::new();
+    t.create_dir_all("root/vendor");
+    t.create_dir_all("root/sub");
+    t.write("root/.denoignore", "*.gen.ts\n!keep.gen.ts\nvendor/\n");
+    t.write("root/a.ts", "");
+    t.write("root/a.gen.ts", "");
+    t.write("root/keep.gen.ts", "");
+    t.write("root/vendor/v.ts", "");
+    t.write("root/sub/.denoignore", "!b.gen.ts\nlocal.ts\n");
+    t.write("root/sub/b.gen.ts", "");
+    t.write("root/sub/c.gen.ts", "");
+    t.write("root/sub/local.ts", "");
+    let root_dir_path = t.path().join("root");
+
+    let collect = |use_ignore_files: bool, files: &[PathBuf]| {
+      let mut file_names = FileCollector::new(|path| {
+        path.extension().map(|ext| ext == "ts").unwrap_or(false)
+      })
+      .use_ignore_files(use_ignore_files)
+      .collect_files(files)
+      .unwrap()
+      .into_iter()
+      .map(|r| r.file_name().unwrap().to_string_lossy().to_string())
+      .collect::<Vec<_>>();
+      file_names.sort();
+      file_names
+    };
+
+    assert_eq!(
+      collect(true, &[root_dir_path.clone()]),
+      ["a.ts", "b.gen.ts", "keep.gen.ts"]
+    );
+    assert_eq!(
+      collect(false, &[root_dir_path.clone()]),
+      [
+        "a.gen.ts",
+        "a.ts",
+        "b.gen.ts",
+        "c.gen.ts",
+        "keep.gen.ts",
+        "local.ts",
+        "v.ts"
+      ]
+    );
+    // explicitly specified paths are never ignored
+    assert_eq!(
+      collect(
+        true,
+        &[root_dir_path.clone(), root_dir_path.join("sub/local.ts")]
+      ),
+      ["a.ts", "b.gen.ts", "keep.gen.ts", "local.ts"]
+    );
+  }
+
   #[test]
   fn test_collect_specifiers() {
     fn create_files(dir_path: &Path, files: &[&str]) {
@@ -823,6 +936,7 @@ mod tests {
           PathBuf::from("https://localhost:8080".to_string()),
         ],
         exclude: vec![ignore_dir_path],
+        no_ignore: false,
       },
       predicate,
     )
@@ -867,6 +981,7 @@ mod tests {
             .replace('\\', "/")
         ))],
         exclude: vec![],
+        no_ignore: false,
       },
       predicate,
     )
@@ -0,0 +1,133 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use glob::MatchOptions;
+use glob::Pattern;
+
+/// The name of the files, in gitignore syntax, that list the files that
+/// should be ignored when collecting files.
+pub const IGNORE_FILE_NAME: &str = ".denoignore";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+  case_sensitive: true,
+  require_literal_separator: true,
+  require_literal_leading_dot: false,
+};
+
+#[derive(Debug)]
+struct IgnoreRule {
+  pattern: Pattern,
+  negated: bool,
+  dir_only: bool,
+}
+
+impl IgnoreRule {
+  fn parse(line: &str) -> Option<Self> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+      return None;
+    }
+    let (negated, pattern) = if let Some(pattern) = line.strip_prefix('!') {
+      (true, pattern)
+    } else if line.starts_with("\\!") || line.starts_with("\\#") {
+      (false, &line[1..])
+    } else {
+      (false, line)
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+      Some(pattern) => (true, pattern),
+      None => (false, pattern),
+    };
+    if pattern.is_empty() {
+      return None;
+    }
+    // like in gitignore, a pattern with a slash is relative to the directory
+    // of the ignore file, while one without matches at any depth
+    let pattern = if pattern.contains('/') {
+      Pattern::new(pattern.strip_prefix('/').unwrap_or(pattern))
+    } else {
+      Pattern::new(&format!("**/{pattern}"))
+    };
+    // invalid patterns are skipped, like git does
+    Some(Self {
+      pattern: pattern.ok()?,
+      negated,
+      dir_only,
+    })
+  }
+}
+
+/// A `.denoignore` file.
+#[derive(Debug)]
+pub struct IgnoreFile {
+  dir: PathBuf,
+  rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreFile {
+  /// Reads the ignore file of the given directory, if there is one.
+  pub fn read(dir: &Path) -> Option<Self> {
+    let text = std::fs::read_to_string(dir.join(IGNORE_FILE_NAME)).ok()?;
+    Some(Self::parse(dir.to_path_buf(), &text))
+  }
+
+  pub fn parse(dir: PathBuf, text: &str) -> Self {
+    Self {
+      dir,
+      rules: text.lines().filter_map(IgnoreRule::parse).collect(),
+    }
+  }
+
+  /// Gets whether the path is ignored (`Some(true)`) or explicitly not
+  /// ignored (`Some(false)`) by this file. The last matching rule wins.
+  pub fn matches(&self, path: &Path, is_dir: bool) -> Option<bool> {
+    let relative = path.strip_prefix(&self.dir).ok()?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    self
+      .rules
+      .iter()
+      .rev()
+      .find(|rule| {
+        (is_dir || !rule.dir_only)
+          && rule.pattern.matches_with(&relative, MATCH_OPTIONS)
+      })
+      .map(|rule| !rule.negated)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn matches_gitignore_syntax() {
+    let file = IgnoreFile::parse(
+      PathBuf::from("/project"),
+      r#"
+# generated code
+*.gen.ts
+!keep.gen.ts
+/dist
+build/
+src/vendor/**
+\#hash.ts
+"#,
+    );
+    let matches = |path: &str, is_dir| {
+      file.matches(&Path::new("/project").join(path), is_dir)
+    };
+    assert_eq!(matches("a.gen.ts", false), Some(true));
+    assert_eq!(matches("nested/a.gen.ts", false), Some(true));
+    assert_eq!(matches("nested/keep.gen.ts", false), Some(false));
+    assert_eq!(matches("dist", true), Some(true));
+    assert_eq!(matches("nested/dist", true), None);
+    assert_eq!(matches("nested/build", true), Some(true));
+    assert_eq!(matches("build", false), None);
+    assert_eq!(matches("src/vendor/a/b.ts", false), Some(true));
+    assert_eq!(matches("#hash.ts", false), Some(true));
+    assert_eq!(matches("main.ts", false), None);
+    assert_eq!(file.matches(Path::new("/other/a.gen.ts"), false), None);
+  }
+}
@@ -8,8 +8,11 @@ pub mod display;
 pub mod draw_thread;
 pub mod file_watcher;
 pub mod fs;
+pub mod ignore_file;
 pub mod logger;
+pub mod output_panes;
 pub mod path;
+pub mod phase_timer;
 pub mod progress_bar;
 pub mod sync;
 pub mod text_encoding;
@@ -0,0 +1,395 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::VecDeque;
+use std::io::Read;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use deno_core::futures::future::join_all;
+use deno_core::parking_lot::Mutex;
+use deno_core::task::spawn_blocking;
+use deno_core::task::JoinHandle;
+use deno_runtime::ops::tty::ConsoleSize;
+use indexmap::IndexMap;
+use unicode_width::UnicodeWidthChar;
+
+use crate::colors;
+
+use super::draw_thread::DrawThread;
+use super::draw_thread::DrawThreadGuard;
+use super::draw_thread::DrawThreadRenderer;
+
+/// The number of lines each pane keeps around to be drawn.
+const SCROLLBACK_LINES: usize = 100;
+
+#[derive(Debug, Default)]
+struct Pane {
+  /// What was written since the output was last taken.
+  output: Vec<u8>,
+  /// The last complete lines that were written.
+  lines: VecDeque<String>,
+  /// The line that is still being written.
+  partial_line: String,
+}
+
+impl Pane {
+  fn write(&mut self, output: &[u8]) {
+    self.output.extend_from_slice(output);
+    let text = String::from_utf8_lossy(output);
+    let mut parts = text.split('\n');
+    if let Some(first) = parts.next() {
+      self.partial_line.push_str(first);
+    }
+    for part in parts {
+      let line = std::mem::replace(&mut self.partial_line, part.to_string());
+      if self.lines.len() == SCROLLBACK_LINES {
+        self.lines.pop_front();
+      }
+      self
+        .lines
+        .push_back(line.trim_end_matches('\r').to_string());
+    }
+  }
+
+  /// The last `count` lines, including the one being written.
+  fn last_lines(&self, count: usize) -> impl Iterator<Item = &str> {
+    let partial_line =
+      (!self.partial_line.is_empty()).then_some(self.partial_line.as_str());
+    let len = self.lines.len() + usize::from(partial_line.is_some());
+    self
+      .lines
+      .iter()
+      .map(String::as_str)
+      .chain(partial_line)
+      .skip(len.saturating_sub(count))
+  }
+}
+
+#[derive(Debug, Default)]
+struct InternalState {
+  /// Whether the panes are only collected, and never drawn.
+  is_hidden: bool,
+  /// If this guard exists, then the panes are displayed by the draw thread.
+  draw_thread_guard: Option<DrawThreadGuard>,
+  panes: IndexMap<String, Pane>,
+  /// The threads that read the pipes of the panes.
+  readers: Vec<JoinHandle<()>>,
+}
+
+/// Keeps the output of things that run at the same time, like the modules
+/// of `deno test --parallel`, apart instead of interleaving it. The last lines
+/// of each one are drawn in a labelled pane below the regular output while it
+/// runs, and the whole output can then be taken to be printed in one piece.
+#[derive(Clone, Debug, Default)]
+pub struct OutputPanes {
+  state: Arc<Mutex<InternalState>>,
+}
+
+impl OutputPanes {
+  /// Panes that are never drawn, for when something else writes to the
+  /// console at the same time and would be overwritten by the draw thread.
+  pub fn hidden() -> Self {
+    Self {
+      state: Arc::new(Mutex::new(InternalState {
+        is_hidden: true,
+        ..Default::default()
+      })),
+    }
+  }
+
+  /// Creates a pipe whose output is kept in a new pane. The label is made
+  /// unique by a number if another pane already has it. Once every writer
+  /// of the pipe is closed, the pane is removed and its output is printed in
+  /// one piece.
+  pub fn pipe(&self, label: &str) -> std::io::Result<os_pipe::PipeWriter> {
+    let (mut reader, writer) = os_pipe::pipe()?;
+    let mut state = self.state.lock();
+    let mut unique_label = label.to_string();
+    let mut index = 1;
+    while state.panes.contains_key(&unique_label) {
+      index += 1;
+      unique_label = format!("{label} ({index})");
+    }
+    state.panes.insert(unique_label.clone(), Pane::default());
+
+    let panes = self.clone();
+    state.readers.push(spawn_blocking(move || {
+      let mut buffer = [0; 512];
+      loop {
+        match reader.read(&mut buffer) {
+          Ok(0) | Err(_) => break,
+          Ok(size) => panes.write_existing(&unique_label, &buffer[..size]),
+        }
+      }
+      let output = panes.remove(&unique_label);
+      let _suspend_guard = panes.suspend();
+      print_output(&unique_label, &output);
+    }));
+    Ok(writer)
+  }
+
+  /// Waits for the pipes of the panes to be closed, and prints the output of
+  /// the panes. Gives up waiting after the timeout, as a process that was
+  /// spawned in the background may keep a pipe open.
+  pub async fn finish(&self, timeout: Duration) {
+    let readers = std::mem::take(&mut self.state.lock().readers);
+    let _ = tokio::time::timeout(timeout, join_all(readers)).await;
+    let _suspend_guard = self.suspend();
+    for (label, output) in self.remove_all() {
+      print_output(&label, &output);
+    }
+  }
+
+  /// Appends output to the pane with the given label, which is created if
+  /// it doesn't exist yet.
+  pub fn write(&self, label: &str, output: &[u8]) {
+    self
+      .state
+      .lock()
+      .panes
+      .entry(label.to_string())
+      .or_default();
+    self.write_existing(label, output);
+  }
+
+  /// Appends output to the pane with the given label, if it still exists.
+  /// The output of a pipe that is still written to after the panes were
+  /// finished is dropped.
+  fn write_existing(&self, label: &str, output: &[u8]) {
+    let mut state = self.state.lock();
+    match state.panes.get_mut(label) {
+      Some(pane) => pane.write(output),
+      None => return,
+    }
+    if !state.is_hidden
+      && state.draw_thread_guard.is_none()
+      && DrawThread::is_supported()
+    {
+      state.draw_thread_guard =
+        Some(DrawThread::add_entry(Arc::new(self.clone())));
+    }
+  }
+
+  /// Takes what was written to a pane since the last time, while the pane
+  /// keeps displaying it.
+  pub fn take_output(&self, label: &str) -> Vec<u8> {
+    let mut state = self.state.lock();
+    state
+      .panes
+      .get_mut(label)
+      .map(|pane| std::mem::take(&mut pane.output))
+      .unwrap_or_default()
+  }
+
+  /// Removes a pane and returns what was written to it since the output was
+  /// last taken.
+  pub fn remove(&self, label: &str) -> Vec<u8> {
+    let mut state = self.state.lock();
+    let output = state
+      .panes
+      .shift_remove(label)
+      .map(|pane| pane.output)
+      .unwrap_or_default();
+    if state.panes.is_empty() {
+      state.draw_thread_guard.take();
+    }
+    output
+  }
+
+  /// Removes all the panes, see [`OutputPanes::remove`].
+  pub fn remove_all(&self) -> Vec<(String, Vec<u8>)> {
+    let mut state = self.state.lock();
+    state.draw_thread_guard.take();
+    std::mem::take(&mut state.panes)
+      .into_iter()
+      .map(|(label, pane)| (label, pane.output))
+      .collect()
+  }
+
+  /// Stops drawing the panes until the returned guard is dropped, so that
+  /// the regular output can be printed without the draw thread clearing it.
+  pub fn suspend(&self) -> SuspendGuard {
+    let is_drawn = self.state.lock().draw_thread_guard.is_some();
+    if is_drawn {
+      DrawThread::hide();
+    }
+    SuspendGuard(is_drawn)
+  }
+}
+
+impl DrawThreadRenderer for OutputPanes {
+  fn render(&self, size: &ConsoleSize) -> String {
+    let state = self.state.lock();
+    if state.panes.is_empty() {
+      return String::new();
+    }
+    // the panes take up to half of the console, and each one gets at least
+    // its label and one line
+    let rows = (size.rows as usize / 2).max(state.panes.len() * 2);
+    let lines_per_pane = (rows / state.panes.len()).saturating_sub(1).max(1);
+    let cols = size.cols as usize;
+    let mut text = String::new();
+    for (label, pane) in &state.panes {
+      if !text.is_empty() {
+        text.push('\n');
+      }
+      let label = sanitize_line(&format!("── {label} ──"), cols);
+      text.push_str(&colors::gray(label).to_string());
+      for line in pane.last_lines(lines_per_pane) {
+        text.push('\n');
+        text.push_str(&sanitize_line(line, cols));
+      }
+    }
+    text
+  }
+}
+
+/// Makes a line of output fit in a single row of the console. The escape
+/// sequences and control characters, which could move the cursor and mess up
+/// the drawing, are removed, and the line is cut to the width of the console.
+fn sanitize_line(line: &str, cols: usize) -> String {
+  // a carriage return goes back to the start of the line, which leaves what
+  // comes after the last one visible
+  let line = line.rsplit('\r').next().unwrap_or_default();
+  let line = console_static_text::ansi::strip_ansi_codes(line);
+  let mut text = String::with_capacity(line.len());
+  let mut width = 0;
+  for c in line.chars() {
+    let c = if c == '\t' { ' ' } else { c };
+    if c.is_control() {
+      continue;
+    }
+    let char_width = c.width().unwrap_or(0);
+    if cols > 0 && width + char_width > cols {
+      break;
+    }
+    width += char_width;
+    text.push(c);
+  }
+  text
+}
+
+/// Prints the output of a pane in one piece, framed by its label.
+pub fn print_output(label: &str, output: &[u8]) {
+  if output.is_empty() {
+    return;
+  }
+  let mut stdout = std::io::stdout().lock();
+  let _ = writeln!(
+    stdout,
+    "{}",
+    colors::gray(format!("------- output from {label} -------"))
+  );
+  let _ = stdout.write_all(output);
+  if !output.ends_with(b"\n") {
+    let _ = writeln!(stdout);
+  }
+  let _ = writeln!(stdout, "{}", colors::gray("----- output end -----"));
+}
+
+/// Turns the writer of a pipe into a file, which is what the stdio of a
+/// worker takes.
+#[cfg(windows)]
+pub fn pipe_writer_to_file(writer: os_pipe::PipeWriter) -> std::fs::File {
+  use std::os::windows::prelude::FromRawHandle;
+  use std::os::windows::prelude::IntoRawHandle;
+  // SAFETY: Requires consuming ownership of the provided handle
+  unsafe { std::fs::File::from_raw_handle(writer.into_raw_handle()) }
+}
+
+/// Turns the writer of a pipe into a file, which is what the stdio of a
+/// worker takes.
+#[cfg(unix)]
+pub fn pipe_writer_to_file(writer: os_pipe::PipeWriter) -> std::fs::File {
+  use std::os::unix::io::FromRawFd;
+  use std::os::unix::io::IntoRawFd;
+  // SAFETY: Requires consuming ownership of the provided handle
+  unsafe { std::fs::File::from_raw_fd(writer.into_raw_fd()) }
+}
+
+#[derive(Debug)]
+pub struct SuspendGuard(bool);
+
+impl Drop for SuspendGuard {
+  fn drop(&mut self) {
+    if self.0 {
+      DrawThread::show();
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn pane_lines() {
+    let mut pane = Pane::default();
+    pane.write(b"one\r\ntw");
+    pane.write(b"o\nthree");
+    assert_eq!(
+      pane.last_lines(5).collect::<Vec<_>>(),
+      ["one", "two", "three"]
+    );
+    assert_eq!(pane.last_lines(2).collect::<Vec<_>>(), ["two", "three"]);
+    pane.write(b"\n");
+    assert_eq!(pane.last_lines(1).collect::<Vec<_>>(), ["three"]);
+    assert_eq!(pane.output, b"one\r\ntwo\nthree\n");
+
+    for i in 0..SCROLLBACK_LINES {
+      pane.write(format!("{i}\n").as_bytes());
+    }
+    assert_eq!(pane.lines.len(), SCROLLBACK_LINES);
+    assert_eq!(pane.lines.front().unwrap(), "0");
+  }
+
+  #[test]
+  fn panes_output() {
+    let panes = OutputPanes::default();
+    panes.write("a", b"1\n");
+    panes.write("b", b"2\n");
+    panes.write("a", b"3\n");
+    assert_eq!(panes.take_output("a"), b"1\n3\n");
+    assert_eq!(panes.take_output("a"), b"");
+    assert_eq!(panes.remove("b"), b"2\n");
+    panes.write("a", b"4\n");
+    assert_eq!(panes.remove_all(), [("a".to_string(), b"4\n".to_vec())]);
+
+    panes.write("c", b"first\nsecond\n");
+    let text = test_util::strip_ansi_codes(
+      &panes.render(&ConsoleSize { cols: 80, rows: 4 }),
+    )
+    .to_string();
+    assert_eq!(text, "── c ──\nsecond");
+  }
+
+  #[test]
+  fn sanitizes_lines() {
+    assert_eq!(sanitize_line("\x1b[31mred\x1b[0m", 80), "red");
+    assert_eq!(sanitize_line("\x1b[2J\x1b[1Aup", 80), "up");
+    assert_eq!(sanitize_line("10%\r50%\r90%", 80), "90%");
+    assert_eq!(sanitize_line("a\tb\x07c", 80), "a bc");
+    assert_eq!(sanitize_line("abcdef", 4), "abcd");
+    assert_eq!(sanitize_line("日本語", 5), "日本");
+    assert_eq!(sanitize_line("abcdef", 0), "abcdef");
+  }
+
+  #[tokio::test]
+  async fn pipes() {
+    let panes = OutputPanes::hidden();
+    let mut writer = panes.pipe("a").unwrap();
+    let other = panes.pipe("a").unwrap();
+    assert_eq!(
+      panes.state.lock().panes.keys().collect::<Vec<_>>(),
+      ["a", "a (2)"]
+    );
+    writer.write_all(b"1\n").unwrap();
+    drop(writer);
+    drop(other);
+    // the panes are removed once their pipes are closed
+    panes.finish(Duration::from_secs(5)).await;
+    assert!(panes.state.lock().panes.is_empty());
+    assert!(panes.state.lock().readers.is_empty());
+  }
+}
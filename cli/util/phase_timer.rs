@@ -0,0 +1,158 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::fmt::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::parking_lot::Mutex;
+
+use super::sync::AtomicFlag;
+
+/// A phase of the startup reported by `--startup-report`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupPhase {
+  FlagParse,
+  ConfigDiscovery,
+  GraphBuild,
+  NpmResolution,
+  TypeCheck,
+  Emit,
+  SnapshotBoot,
+}
+
+impl StartupPhase {
+  const ALL: [StartupPhase; 7] = [
+    StartupPhase::FlagParse,
+    StartupPhase::ConfigDiscovery,
+    StartupPhase::GraphBuild,
+    StartupPhase::NpmResolution,
+    StartupPhase::TypeCheck,
+    StartupPhase::Emit,
+    StartupPhase::SnapshotBoot,
+  ];
+
+  fn name(&self) -> &'static str {
+    match self {
+      StartupPhase::FlagParse => "flag parse",
+      StartupPhase::ConfigDiscovery => "config discovery",
+      StartupPhase::GraphBuild => "graph build",
+      StartupPhase::NpmResolution => "npm resolution",
+      StartupPhase::TypeCheck => "type check",
+      StartupPhase::Emit => "emit",
+      StartupPhase::SnapshotBoot => "snapshot boot",
+    }
+  }
+}
+
+/// Adds up the time spent in each phase of the startup and prints a
+/// breakdown once the first user code is about to run.
+///
+/// A phase may be entered more than once (ex. emit happens per module), in
+/// which case the durations are summed. Measuring is a no-op when the timer
+/// is disabled.
+#[derive(Debug)]
+pub struct PhaseTimer {
+  enabled: bool,
+  start: Instant,
+  durations: Mutex<[Duration; StartupPhase::ALL.len()]>,
+  reported: AtomicFlag,
+}
+
+impl PhaseTimer {
+  /// Creates a timer for a process that started at `start`.
+  pub fn new(start: Instant, enabled: bool) -> Self {
+    Self {
+      enabled,
+      start,
+      durations: Default::default(),
+      reported: Default::default(),
+    }
+  }
+
+  pub fn disabled() -> Self {
+    Self::new(Instant::now(), false)
+  }
+
+  pub fn record(&self, phase: StartupPhase, duration: Duration) {
+    if self.enabled {
+      self.durations.lock()[phase as usize] += duration;
+    }
+  }
+
+  /// Measures the given phase until the returned guard is dropped.
+  pub fn measure(&self, phase: StartupPhase) -> PhaseTimerGuard {
+    PhaseTimerGuard {
+      timer: self,
+      phase,
+      start: Instant::now(),
+    }
+  }
+
+  /// Prints the report to stderr. Only the first call has an effect.
+  pub fn report_first_user_code(&self) {
+    if self.enabled && self.reported.raise() {
+      eprint!("{}", self.format_report(self.start.elapsed()));
+    }
+  }
+
+  fn format_report(&self, first_user_code: Duration) -> String {
+    let durations = *self.durations.lock();
+    let mut text = String::from("Startup report:\n");
+    for phase in StartupPhase::ALL {
+      write_line(&mut text, phase.name(), durations[phase as usize]);
+    }
+    write_line(&mut text, "first user code", first_user_code);
+    text
+  }
+}
+
+fn write_line(text: &mut String, name: &str, duration: Duration) {
+  let millis = duration.as_secs_f64() * 1000.0;
+  writeln!(text, "  {name:<18}{millis:>10.2}ms").unwrap();
+}
+
+pub struct PhaseTimerGuard<'a> {
+  timer: &'a PhaseTimer,
+  phase: StartupPhase,
+  start: Instant,
+}
+
+impl Drop for PhaseTimerGuard<'_> {
+  fn drop(&mut self) {
+    self.timer.record(self.phase, self.start.elapsed());
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn sums_phases() {
+    let timer = PhaseTimer::new(Instant::now(), true);
+    timer.record(StartupPhase::Emit, Duration::from_micros(1_500));
+    timer.record(StartupPhase::Emit, Duration::from_micros(500));
+    timer.record(StartupPhase::GraphBuild, Duration::from_millis(12));
+    assert_eq!(
+      timer.format_report(Duration::from_millis(30)),
+      concat!(
+        "Startup report:\n",
+        "  flag parse              0.00ms\n",
+        "  config discovery        0.00ms\n",
+        "  graph build            12.00ms\n",
+        "  npm resolution          0.00ms\n",
+        "  type check              0.00ms\n",
+        "  emit                    2.00ms\n",
+        "  snapshot boot           0.00ms\n",
+        "  first user code        30.00ms\n",
+      )
+    );
+  }
+
+  #[test]
+  fn disabled_does_not_record() {
+    let timer = PhaseTimer::disabled();
+    drop(timer.measure(StartupPhase::Emit));
+    assert_eq!(*timer.durations.lock(), [Duration::ZERO; 7]);
+  }
+}
@@ -3,6 +3,7 @@
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::Context;
@@ -12,6 +13,7 @@ use deno_core::futures::FutureExt;
 use deno_core::located_script_name;
 use deno_core::parking_lot::Mutex;
 use deno_core::url::Url;
+use deno_core::v8;
 use deno_core::CompiledWasmModuleStore;
 use deno_core::Extension;
 use deno_core::ModuleId;
@@ -20,9 +22,13 @@ use deno_core::SharedArrayBufferStore;
 use deno_core::SourceMapGetter;
 use deno_lockfile::Lockfile;
 use deno_runtime::colors;
-use deno_runtime::deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_runtime::deno_broadcast_channel::LocalIpcBroadcastChannel;
+use deno_runtime::deno_broadcast_channel::RuntimeBroadcastChannel;
 use deno_runtime::deno_fs;
+use deno_runtime::deno_io::Stdio;
+use deno_runtime::deno_io::StdioPipe;
 use deno_runtime::deno_node;
+use deno_runtime::deno_node::NodeOptions;
 use deno_runtime::deno_node::NodeResolution;
 use deno_runtime::deno_node::NodeResolver;
 use deno_runtime::deno_tls::RootCertStoreProvider;
@@ -32,6 +38,7 @@ use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::ops::worker_host::CreateWebWorkerCb;
 use deno_runtime::ops::worker_host::WorkerEventCb;
 use deno_runtime::permissions::PermissionsContainer;
+use deno_runtime::resource_limits;
 use deno_runtime::web_worker::WebWorker;
 use deno_runtime::web_worker::WebWorkerOptions;
 use deno_runtime::worker::MainWorker;
@@ -47,6 +54,10 @@ use crate::ops;
 use crate::tools;
 use crate::tools::coverage::CoverageCollector;
 use crate::util::checksum;
+use crate::util::output_panes::pipe_writer_to_file;
+use crate::util::output_panes::OutputPanes;
+use crate::util::phase_timer::PhaseTimer;
+use crate::util::phase_timer::StartupPhase;
 use crate::version;
 
 pub trait ModuleLoaderFactory: Send + Sync {
@@ -74,6 +85,9 @@ pub trait HasNodeSpecifierChecker: Send + Sync {
 #[derive(Clone)]
 pub struct CliMainWorkerOptions {
   pub argv: Vec<String>,
+  /// The socket to exchange `BroadcastChannel` messages with other processes
+  /// on, if they should be.
+  pub broadcast_channel_socket: Option<PathBuf>,
   pub log_level: WorkerLogLevel,
   pub coverage_dir: Option<String>,
   pub enable_testing_features: bool,
@@ -84,8 +98,15 @@ pub struct CliMainWorkerOptions {
   pub is_npm_main: bool,
   pub location: Option<Url>,
   pub maybe_binary_npm_command_name: Option<String>,
+  pub node_conditions: Vec<String>,
+  /// The options from `NODE_OPTIONS`, which only apply to programs that use
+  /// the Node compatibility layer.
+  pub node_options: NodeOptions,
   pub origin_data_folder_path: Option<PathBuf>,
   pub seed: Option<u64>,
+  /// Whether the output of each web worker is printed in one piece once the
+  /// worker is done.
+  pub split_output: bool,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub unstable: bool,
 }
@@ -97,7 +118,7 @@ struct SharedWorkerState {
   node_resolver: Arc<NodeResolver>,
   has_node_specifier_checker: Box<dyn HasNodeSpecifierChecker>,
   blob_store: BlobStore,
-  broadcast_channel: InMemoryBroadcastChannel,
+  broadcast_channel: RuntimeBroadcastChannel,
   shared_array_buffer_store: SharedArrayBufferStore,
   compiled_wasm_module_store: CompiledWasmModuleStore,
   module_loader_factory: Box<dyn ModuleLoaderFactory>,
@@ -105,6 +126,11 @@ struct SharedWorkerState {
   fs: Arc<dyn deno_fs::FileSystem>,
   maybe_inspector_server: Option<Arc<InspectorServer>>,
   maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
+  startup_timer: Arc<PhaseTimer>,
+  /// The panes the output of the web workers is kept in with
+  /// `--split-output`. They are never drawn, as the main worker writes to
+  /// the console directly.
+  maybe_output_panes: Option<OutputPanes>,
 }
 
 impl SharedWorkerState {
@@ -139,6 +165,7 @@ impl CliMainWorker {
 
     if self.is_main_cjs {
       self.initialize_main_module_for_node()?;
+      self.shared.startup_timer.report_first_user_code();
       deno_node::load_cjs_module(
         &mut self.worker.js_runtime,
         &self.main_module.to_file_path().unwrap().to_string_lossy(),
@@ -267,14 +294,20 @@ impl CliMainWorker {
     if self.shared.should_initialize_node_runtime() {
       self.initialize_main_module_for_node()?;
     }
+    self.shared.startup_timer.report_first_user_code();
     self.worker.evaluate_module(id).await
   }
 
   fn initialize_main_module_for_node(&mut self) -> Result<(), AnyError> {
+    let node_options = &self.shared.options.node_options;
+    for warning in &node_options.warnings {
+      log::warn!("{} {}", colors::yellow("Warning"), warning);
+    }
     deno_node::initialize_runtime(
       &mut self.worker.js_runtime,
       self.shared.options.has_node_modules_dir,
       self.shared.options.maybe_binary_npm_command_name.as_deref(),
+      node_options,
     )?;
 
     Ok(())
@@ -317,8 +350,16 @@ impl CliMainWorkerFactory {
     fs: Arc<dyn deno_fs::FileSystem>,
     maybe_inspector_server: Option<Arc<InspectorServer>>,
     maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
+    startup_timer: Arc<PhaseTimer>,
     options: CliMainWorkerOptions,
   ) -> Self {
+    let broadcast_channel = match &options.broadcast_channel_socket {
+      Some(socket_path) => {
+        LocalIpcBroadcastChannel::new(socket_path.clone()).into()
+      }
+      None => RuntimeBroadcastChannel::default(),
+    };
+    let maybe_output_panes = options.split_output.then(OutputPanes::hidden);
     Self {
       shared: Arc::new(SharedWorkerState {
         options,
@@ -327,7 +368,7 @@ impl CliMainWorkerFactory {
         node_resolver,
         has_node_specifier_checker,
         blob_store,
-        broadcast_channel: Default::default(),
+        broadcast_channel,
         shared_array_buffer_store: Default::default(),
         compiled_wasm_module_store: Default::default(),
         module_loader_factory,
@@ -335,10 +376,20 @@ impl CliMainWorkerFactory {
         fs,
         maybe_inspector_server,
         maybe_lockfile,
+        startup_timer,
+        maybe_output_panes,
       }),
     }
   }
 
+  /// Prints the output of the web workers that is still kept in panes, once
+  /// the main worker is done.
+  pub async fn finish_output(&self) {
+    if let Some(panes) = &self.shared.maybe_output_panes {
+      panes.finish(Duration::from_secs(1)).await;
+    }
+  }
+
   pub async fn create_main_worker(
     &self,
     main_module: ModuleSpecifier,
@@ -432,9 +483,7 @@ impl CliMainWorkerFactory {
     let options = WorkerOptions {
       bootstrap: BootstrapOptions {
         args: shared.options.argv.clone(),
-        cpu_count: std::thread::available_parallelism()
-          .map(|p| p.get())
-          .unwrap_or(1),
+        cpu_count: resource_limits::resource_limits().cpu_count,
         log_level: shared.options.log_level,
         enable_testing_features: shared.options.enable_testing_features,
         locale: deno_core::v8::icu::get_language_tag(),
@@ -449,7 +498,7 @@ impl CliMainWorkerFactory {
       },
       extensions,
       startup_snapshot: Some(crate::js::deno_isolate_init()),
-      create_params: None,
+      create_params: create_main_isolate_params(shared),
       unsafely_ignore_certificate_errors: shared
         .options
         .unsafely_ignore_certificate_errors
@@ -467,6 +516,7 @@ impl CliMainWorkerFactory {
       module_loader,
       fs: shared.fs.clone(),
       npm_resolver: Some(shared.npm_resolver.clone()),
+      node_conditions: shared.options.node_conditions.clone(),
       get_error_class_fn: Some(&errors::get_error_class_name),
       cache_storage_dir,
       origin_storage_dir,
@@ -479,11 +529,14 @@ impl CliMainWorkerFactory {
       stdio,
     };
 
-    let worker = MainWorker::bootstrap_from_options(
-      main_module.clone(),
-      permissions,
-      options,
-    );
+    let worker = {
+      let _timer = shared.startup_timer.measure(StartupPhase::SnapshotBoot);
+      MainWorker::bootstrap_from_options(
+        main_module.clone(),
+        permissions,
+        options,
+      )
+    };
 
     Ok(CliMainWorker {
       main_module,
@@ -496,6 +549,22 @@ impl CliMainWorkerFactory {
 
 // TODO(bartlomieju): this callback could have default value
 // and not be required
+/// The parameters of the main isolate. `--max-old-space-size` from
+/// `NODE_OPTIONS` is applied if it is already known that the program uses npm
+/// packages, as the heap can't be resized once the isolate is created.
+fn create_main_isolate_params(
+  shared: &SharedWorkerState,
+) -> Option<v8::CreateParams> {
+  let uses_npm =
+    shared.options.is_npm_main || shared.npm_resolver.has_packages();
+  match shared.options.node_options.max_heap_size() {
+    Some(max_heap_size) if uses_npm => {
+      Some(v8::CreateParams::default().heap_limits(0, max_heap_size))
+    }
+    _ => resource_limits::v8_create_params(),
+  }
+}
+
 fn create_web_worker_preload_module_callback(
   _shared: &Arc<SharedWorkerState>,
 ) -> Arc<WorkerEventCb> {
@@ -517,6 +586,7 @@ fn create_web_worker_pre_execute_module_callback(
           &mut worker.js_runtime,
           shared.options.has_node_modules_dir,
           None,
+          &shared.options.node_options,
         )?;
       }
 
@@ -526,6 +596,34 @@ fn create_web_worker_pre_execute_module_callback(
   })
 }
 
+/// The stdio of a web worker, whose output goes to a pane of its own with
+/// `--split-output`.
+fn create_web_worker_stdio(
+  shared: &SharedWorkerState,
+  stdio: &Stdio,
+  label: &str,
+) -> Stdio {
+  let panes = match &shared.maybe_output_panes {
+    Some(panes) => panes,
+    None => return stdio.clone(),
+  };
+  let pipes = panes.pipe(label).and_then(|stdout| {
+    let stderr = stdout.try_clone()?;
+    Ok((stdout, stderr))
+  });
+  match pipes {
+    Ok((stdout, stderr)) => Stdio {
+      stdin: stdio.stdin.clone(),
+      stdout: StdioPipe::File(pipe_writer_to_file(stdout)),
+      stderr: StdioPipe::File(pipe_writer_to_file(stderr)),
+    },
+    Err(err) => {
+      log::debug!("Failed to create the pane of {}: {}", label, err);
+      stdio.clone()
+    }
+  }
+}
+
 fn create_web_worker_callback(
   shared: Arc<SharedWorkerState>,
   stdio: deno_runtime::deno_io::Stdio,
@@ -561,9 +659,7 @@ fn create_web_worker_callback(
     let options = WebWorkerOptions {
       bootstrap: BootstrapOptions {
         args: shared.options.argv.clone(),
-        cpu_count: std::thread::available_parallelism()
-          .map(|p| p.get())
-          .unwrap_or(1),
+        cpu_count: resource_limits::resource_limits().cpu_count,
         log_level: shared.options.log_level,
         enable_testing_features: shared.options.enable_testing_features,
         locale: deno_core::v8::icu::get_language_tag(),
@@ -592,6 +688,7 @@ fn create_web_worker_callback(
       module_loader,
       fs: shared.fs.clone(),
       npm_resolver: Some(shared.npm_resolver.clone()),
+      node_conditions: shared.options.node_conditions.clone(),
       worker_type: args.worker_type,
       maybe_inspector_server,
       get_error_class_fn: Some(&errors::get_error_class_name),
@@ -601,7 +698,15 @@ fn create_web_worker_callback(
       compiled_wasm_module_store: Some(
         shared.compiled_wasm_module_store.clone(),
       ),
-      stdio: stdio.clone(),
+      stdio: create_web_worker_stdio(
+        &shared,
+        &stdio,
+        if args.name.is_empty() {
+          args.main_module.as_str()
+        } else {
+          &args.name
+        },
+      ),
       cache_storage_dir,
     };
 
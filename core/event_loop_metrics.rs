@@ -0,0 +1,83 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::time::Duration;
+use std::time::Instant;
+
+/// Tracks how much time the event loop spends idle, ie. waiting to be polled
+/// again, versus running JavaScript and ops.
+#[derive(Debug)]
+pub struct EventLoopMetrics {
+  loop_start: Instant,
+  idle_time: Duration,
+  idle_since: Option<Instant>,
+  iterations: u64,
+}
+
+impl Default for EventLoopMetrics {
+  fn default() -> Self {
+    Self {
+      loop_start: Instant::now(),
+      idle_time: Duration::ZERO,
+      idle_since: None,
+      iterations: 0,
+    }
+  }
+}
+
+impl EventLoopMetrics {
+  /// The instant the event loop was created.
+  pub fn loop_start(&self) -> Instant {
+    self.loop_start
+  }
+
+  /// Total time the event loop has spent idle so far.
+  pub fn idle_time(&self) -> Duration {
+    match self.idle_since {
+      Some(idle_since) => self.idle_time + idle_since.elapsed(),
+      None => self.idle_time,
+    }
+  }
+
+  /// Total time the event loop has spent doing work so far.
+  pub fn active_time(&self) -> Duration {
+    self.loop_start.elapsed().saturating_sub(self.idle_time())
+  }
+
+  /// Number of times the event loop has been polled.
+  pub fn iterations(&self) -> u64 {
+    self.iterations
+  }
+
+  pub(crate) fn on_poll_start(&mut self) {
+    if let Some(idle_since) = self.idle_since.take() {
+      self.idle_time += idle_since.elapsed();
+    }
+    self.iterations += 1;
+  }
+
+  pub(crate) fn on_poll_end(&mut self, is_pending: bool) {
+    if is_pending {
+      self.idle_since = Some(Instant::now());
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn idle_time_is_accumulated_between_polls() {
+    let mut metrics = EventLoopMetrics::default();
+    metrics.on_poll_start();
+    metrics.on_poll_end(true);
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(metrics.idle_time() >= Duration::from_millis(20));
+    metrics.on_poll_start();
+    let idle_time = metrics.idle_time();
+    assert!(idle_time >= Duration::from_millis(20));
+    std::thread::sleep(Duration::from_millis(10));
+    assert_eq!(metrics.idle_time(), idle_time);
+    assert_eq!(metrics.iterations(), 2);
+  }
+}
@@ -4,6 +4,7 @@ mod async_cell;
 mod bindings;
 pub mod error;
 mod error_codes;
+mod event_loop_metrics;
 mod extensions;
 mod fast_string;
 mod flags;
@@ -57,6 +58,7 @@ pub use crate::async_cell::AsyncRefCell;
 pub use crate::async_cell::AsyncRefFuture;
 pub use crate::async_cell::RcLike;
 pub use crate::async_cell::RcRef;
+pub use crate::event_loop_metrics::EventLoopMetrics;
 pub use crate::extensions::Extension;
 pub use crate::extensions::ExtensionBuilder;
 pub use crate::extensions::ExtensionFileSource;
@@ -82,6 +84,7 @@ pub use crate::module_specifier::ModuleSpecifier;
 pub use crate::modules::ExtModuleLoaderCb;
 pub use crate::modules::FsModuleLoader;
 pub use crate::modules::ModuleCode;
+pub use crate::modules::ModuleCodeCache;
 pub use crate::modules::ModuleId;
 pub use crate::modules::ModuleLoader;
 pub use crate::modules::ModuleSource;
@@ -47,7 +47,7 @@ fn strip_bom(source_code: &[u8]) -> &[u8] {
   }
 }
 
-const SUPPORTED_TYPE_ASSERTIONS: &[&str] = &["json"];
+const SUPPORTED_TYPE_ASSERTIONS: &[&str] = &["json", "bytes", "text"];
 
 /// Throws V8 exception if assertions are invalid
 pub(crate) fn validate_import_assertions(
@@ -115,12 +115,11 @@ pub(crate) fn get_asserted_module_type_from_assertions(
 ) -> AssertedModuleType {
   assertions
     .get("type")
-    .map(|ty| {
-      if ty == "json" {
-        AssertedModuleType::Json
-      } else {
-        AssertedModuleType::JavaScriptOrWasm
-      }
+    .map(|ty| match ty.as_str() {
+      "json" => AssertedModuleType::Json,
+      "bytes" => AssertedModuleType::Bytes,
+      "text" => AssertedModuleType::Text,
+      _ => AssertedModuleType::JavaScriptOrWasm,
     })
     .unwrap_or(AssertedModuleType::JavaScriptOrWasm)
 }
@@ -128,7 +127,7 @@ pub(crate) fn get_asserted_module_type_from_assertions(
 // Clippy thinks the return value doesn't need to be an Option, it's unaware
 // of the mapping that MapFnFrom<F> does for ResolveModuleCallback.
 #[allow(clippy::unnecessary_wraps)]
-fn json_module_evaluation_steps<'a>(
+fn synthetic_module_evaluation_steps<'a>(
   context: v8::Local<'a, v8::Context>,
   module: v8::Local<v8::Module>,
 ) -> Option<v8::Local<'a, v8::Value>> {
@@ -140,7 +139,7 @@ fn json_module_evaluation_steps<'a>(
   let handle = v8::Global::<v8::Module>::new(tc_scope, module);
   let value_handle = module_map
     .borrow_mut()
-    .json_value_store
+    .synthetic_module_value_store
     .remove(&handle)
     .unwrap();
   let value_local = v8::Local::new(tc_scope, value_handle);
@@ -171,6 +170,10 @@ fn json_module_evaluation_steps<'a>(
 pub enum ModuleType {
   JavaScript,
   Json,
+  /// The raw contents of the module, exposed as a `Uint8Array`.
+  Bytes,
+  /// The contents of the module decoded as UTF-8, exposed as a string.
+  Text,
 }
 
 impl std::fmt::Display for ModuleType {
@@ -178,6 +181,8 @@ impl std::fmt::Display for ModuleType {
     match self {
       Self::JavaScript => write!(f, "JavaScript"),
       Self::Json => write!(f, "JSON"),
+      Self::Bytes => write!(f, "bytes"),
+      Self::Text => write!(f, "text"),
     }
   }
 }
@@ -205,6 +210,23 @@ pub struct ModuleSource {
   module_url_specified: ModuleName,
   /// If the module was found somewhere other than the specified address, this will be [`Some`].
   module_url_found: Option<ModuleName>,
+  /// The V8 code cache of the module, if the loader keeps one. Only used for
+  /// JavaScript modules.
+  code_cache: Option<ModuleCodeCache>,
+  /// The raw contents of a `Bytes` or `Text` module. These are not
+  /// necessarily valid UTF-8, so they can't be stored in `code`.
+  bytes: Option<Box<[u8]>>,
+}
+
+/// The V8 code cache of a module, which lets V8 skip compiling it.
+#[derive(Debug)]
+pub enum ModuleCodeCache {
+  /// There is no code cache for the module yet. One is created once the
+  /// module is compiled, and passed to [`ModuleLoader::code_cache_ready`].
+  Missing,
+  /// The code cache that was created when the module was compiled before.
+  /// V8 rejects it if the module or V8 itself changed since then.
+  Existing(Vec<u8>),
 }
 
 impl ModuleSource {
@@ -220,6 +242,8 @@ impl ModuleSource {
       module_type: module_type.into(),
       module_url_specified,
       module_url_found: None,
+      code_cache: None,
+      bytes: None,
     }
   }
 
@@ -242,9 +266,35 @@ impl ModuleSource {
       module_type: module_type.into(),
       module_url_specified,
       module_url_found,
+      code_cache: None,
+      bytes: None,
     }
   }
 
+  /// Create a [`ModuleSource`] holding the raw contents of an asset, as
+  /// returned by [`ModuleLoader::load_bytes`]. If the `specifier_found`
+  /// parameter is the same as the specifier, no redirect is recorded.
+  pub fn new_bytes(
+    bytes: impl Into<Box<[u8]>>,
+    specifier: &ModuleSpecifier,
+    specifier_found: &ModuleSpecifier,
+  ) -> Self {
+    let mut source = Self::new_with_redirect(
+      ModuleType::Bytes,
+      Default::default(),
+      specifier,
+      specifier_found,
+    );
+    source.bytes = Some(bytes.into());
+    source
+  }
+
+  /// Passes the V8 code cache of the module along with it.
+  pub fn with_code_cache(mut self, code_cache: ModuleCodeCache) -> Self {
+    self.code_cache = Some(code_cache);
+    self
+  }
+
   #[cfg(test)]
   pub fn for_test(code: &'static str, file: impl AsRef<str>) -> Self {
     Self {
@@ -252,6 +302,8 @@ impl ModuleSource {
       module_type: ModuleType::JavaScript,
       module_url_specified: file.as_ref().to_owned().into(),
       module_url_found: None,
+      code_cache: None,
+      bytes: None,
     }
   }
 
@@ -274,6 +326,8 @@ impl ModuleSource {
       module_type: ModuleType::JavaScript,
       module_url_specified: specified.into(),
       module_url_found: found,
+      code_cache: None,
+      bytes: None,
     }
   }
 }
@@ -329,6 +383,24 @@ pub trait ModuleLoader {
     is_dyn_import: bool,
   ) -> Pin<Box<ModuleSourceFuture>>;
 
+  /// Given ModuleSpecifier, load its raw contents. This is used for modules
+  /// imported with a `bytes` or `text` type assertion, and the returned
+  /// source should be created with [`ModuleSource::new_bytes`].
+  ///
+  /// It's not required to implement this method; by default such imports
+  /// are rejected.
+  fn load_bytes(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dyn_import: bool,
+  ) -> Pin<Box<ModuleSourceFuture>> {
+    futures::future::err(generic_error(format!(
+      "Loading \"{module_specifier}\" as bytes or text is not supported."
+    )))
+    .boxed_local()
+  }
+
   /// This hook can be used by implementors to do some preparation
   /// work before starting loading of modules.
   ///
@@ -345,6 +417,18 @@ pub trait ModuleLoader {
   ) -> Pin<Box<dyn Future<Output = Result<(), Error>>>> {
     async { Ok(()) }.boxed_local()
   }
+
+  /// Called once V8 created the code cache of a module whose source was
+  /// loaded with [`ModuleCodeCache::Missing`], so that the implementor can
+  /// store it for the next time the module is loaded.
+  ///
+  /// It's not required to implement this method.
+  fn code_cache_ready(
+    &self,
+    _module_specifier: &ModuleSpecifier,
+    _code_cache: &[u8],
+  ) {
+  }
 }
 
 /// Placeholder structure used when creating
@@ -531,6 +615,31 @@ impl ModuleLoader for FsModuleLoader {
 
     futures::future::ready(load(module_specifier)).boxed_local()
   }
+
+  fn load_bytes(
+    &self,
+    module_specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dynamic: bool,
+  ) -> Pin<Box<ModuleSourceFuture>> {
+    fn load_bytes(
+      module_specifier: &ModuleSpecifier,
+    ) -> Result<ModuleSource, AnyError> {
+      let path = module_specifier.to_file_path().map_err(|_| {
+        generic_error(format!(
+          "Provided module specifier \"{module_specifier}\" is not a file URL."
+        ))
+      })?;
+      let bytes = std::fs::read(path)?;
+      Ok(ModuleSource::new_bytes(
+        bytes,
+        module_specifier,
+        module_specifier,
+      ))
+    }
+
+    futures::future::ready(load_bytes(module_specifier)).boxed_local()
+  }
 }
 
 /// Describes the entrypoint of a recursive module load.
@@ -749,20 +858,33 @@ impl RecursiveModuleLoad {
         id
       }
       None => match module_source.module_type {
-        ModuleType::JavaScript => {
-          self.module_map_rc.borrow_mut().new_es_module(
+        ModuleType::JavaScript => self
+          .module_map_rc
+          .borrow_mut()
+          .new_es_module_with_code_cache(
             scope,
             self.is_currently_loading_main_module(),
             module_url_found,
             module_source.code,
             self.is_dynamic_import(),
-          )?
-        }
+            module_source.code_cache,
+          )?,
         ModuleType::Json => self.module_map_rc.borrow_mut().new_json_module(
           scope,
           module_url_found,
           module_source.code,
         )?,
+        ModuleType::Bytes | ModuleType::Text => {
+          let bytes = module_source
+            .bytes
+            .unwrap_or_else(|| module_source.code.as_bytes().into());
+          let mut module_map = self.module_map_rc.borrow_mut();
+          if module_source.module_type == ModuleType::Bytes {
+            module_map.new_bytes_module(scope, module_url_found, &bytes)
+          } else {
+            module_map.new_text_module(scope, module_url_found, &bytes)
+          }
+        }
       },
     };
 
@@ -794,19 +916,16 @@ impl RecursiveModuleLoad {
           ) {
             already_registered.push_back((module_id, module_request.clone()));
           } else {
-            let request = module_request.clone();
             let specifier =
               ModuleSpecifier::parse(&module_request.specifier).unwrap();
-            let referrer = referrer.clone();
-            let loader = self.loader.clone();
-            let is_dynamic_import = self.is_dynamic_import();
-            let fut = async move {
-              let load_result = loader
-                .load(&specifier, Some(&referrer), is_dynamic_import)
-                .await;
-              load_result.map(|s| (request, s))
-            };
-            self.pending.push(fut.boxed_local());
+            let fut = load_module_request(
+              self.loader.clone(),
+              module_request.clone(),
+              specifier,
+              Some(referrer.clone()),
+              self.is_dynamic_import(),
+            );
+            self.pending.push(fut);
           }
           self.visited.insert(module_request);
         }
@@ -827,6 +946,40 @@ impl RecursiveModuleLoad {
   }
 }
 
+/// Loads the module for `module_request`. Modules imported with a `bytes` or
+/// `text` type assertion are loaded with [`ModuleLoader::load_bytes`].
+fn load_module_request(
+  loader: Rc<dyn ModuleLoader>,
+  module_request: ModuleRequest,
+  specifier: ModuleSpecifier,
+  maybe_referrer: Option<ModuleSpecifier>,
+  is_dynamic_import: bool,
+) -> Pin<Box<ModuleLoadFuture>> {
+  async move {
+    let module_source = match module_request.asserted_module_type {
+      AssertedModuleType::Bytes | AssertedModuleType::Text => {
+        let mut module_source = loader
+          .load_bytes(&specifier, maybe_referrer.as_ref(), is_dynamic_import)
+          .await?;
+        module_source.module_type =
+          if module_request.asserted_module_type == AssertedModuleType::Text {
+            ModuleType::Text
+          } else {
+            ModuleType::Bytes
+          };
+        module_source
+      }
+      AssertedModuleType::JavaScriptOrWasm | AssertedModuleType::Json => {
+        loader
+          .load(&specifier, maybe_referrer.as_ref(), is_dynamic_import)
+          .await?
+      }
+    };
+    Ok((module_request, module_source))
+  }
+  .boxed_local()
+}
+
 impl Stream for RecursiveModuleLoad {
   type Item = Result<(ModuleRequest, ModuleSource), Error>;
 
@@ -880,19 +1033,13 @@ impl Stream for RecursiveModuleLoad {
             specifier: module_specifier.to_string(),
             asserted_module_type,
           };
-          let loader = inner.loader.clone();
-          let is_dynamic_import = inner.is_dynamic_import();
-          async move {
-            let result = loader
-              .load(
-                &module_specifier,
-                maybe_referrer.as_ref(),
-                is_dynamic_import,
-              )
-              .await;
-            result.map(|s| (module_request, s))
-          }
-          .boxed_local()
+          load_module_request(
+            inner.loader.clone(),
+            module_request,
+            module_specifier,
+            maybe_referrer,
+            inner.is_dynamic_import(),
+          )
         };
         inner.pending.push(load_fut);
         inner.state = LoadState::LoadingRoot;
@@ -915,6 +1062,8 @@ impl Stream for RecursiveModuleLoad {
 pub(crate) enum AssertedModuleType {
   JavaScriptOrWasm,
   Json,
+  Bytes,
+  Text,
 }
 
 impl From<ModuleType> for AssertedModuleType {
@@ -922,6 +1071,8 @@ impl From<ModuleType> for AssertedModuleType {
     match module_type {
       ModuleType::JavaScript => AssertedModuleType::JavaScriptOrWasm,
       ModuleType::Json => AssertedModuleType::Json,
+      ModuleType::Bytes => AssertedModuleType::Bytes,
+      ModuleType::Text => AssertedModuleType::Text,
     }
   }
 }
@@ -931,14 +1082,17 @@ impl std::fmt::Display for AssertedModuleType {
     match self {
       Self::JavaScriptOrWasm => write!(f, "JavaScriptOrWasm"),
       Self::Json => write!(f, "JSON"),
+      Self::Bytes => write!(f, "bytes"),
+      Self::Text => write!(f, "text"),
     }
   }
 }
 
 /// Describes a request for a module as parsed from the source code.
 /// Usually executable (`JavaScriptOrWasm`) is used, except when an
-/// import assertions explicitly constrains an import to JSON, in
-/// which case this will have a `AssertedModuleType::Json`.
+/// import assertions explicitly constrains an import to JSON, bytes or
+/// text, in which case this will have the corresponding
+/// `AssertedModuleType`.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub(crate) struct ModuleRequest {
   pub specifier: String,
@@ -980,6 +1134,8 @@ pub(crate) struct ModuleMap {
   pub info: Vec<ModuleInfo>,
   pub(crate) by_name_js: HashMap<ModuleName, SymbolicModule>,
   pub(crate) by_name_json: HashMap<ModuleName, SymbolicModule>,
+  pub(crate) by_name_bytes: HashMap<ModuleName, SymbolicModule>,
+  pub(crate) by_name_text: HashMap<ModuleName, SymbolicModule>,
   pub(crate) next_load_id: ModuleLoadId,
 
   // Handling of futures for loading module sources
@@ -991,9 +1147,11 @@ pub(crate) struct ModuleMap {
   pub(crate) pending_dynamic_imports:
     FuturesUnordered<StreamFuture<RecursiveModuleLoad>>,
 
-  // This store is used temporarly, to forward parsed JSON
-  // value from `new_json_module` to `json_module_evaluation_steps`
-  json_value_store: HashMap<v8::Global<v8::Module>, v8::Global<v8::Value>>,
+  // This store is used temporarly, to forward the default export of
+  // synthetic modules (JSON, bytes and text) from their constructors
+  // to `synthetic_module_evaluation_steps`
+  synthetic_module_value_store:
+    HashMap<v8::Global<v8::Module>, v8::Global<v8::Value>>,
 }
 
 impl ModuleMap {
@@ -1004,6 +1162,8 @@ impl ModuleMap {
     for module_type in [
       AssertedModuleType::JavaScriptOrWasm,
       AssertedModuleType::Json,
+      AssertedModuleType::Bytes,
+      AssertedModuleType::Text,
     ] {
       output.extend(
         self
@@ -1201,6 +1361,8 @@ impl ModuleMap {
           let asserted_module_type = match asserted_module_type_no {
             0 => AssertedModuleType::JavaScriptOrWasm,
             1 => AssertedModuleType::Json,
+            2 => AssertedModuleType::Bytes,
+            3 => AssertedModuleType::Text,
             _ => unreachable!(),
           };
           requests.push(ModuleRequest {
@@ -1218,6 +1380,8 @@ impl ModuleMap {
         let module_type = match module_type_no {
           0 => ModuleType::JavaScript,
           1 => ModuleType::Json,
+          2 => ModuleType::Bytes,
+          3 => ModuleType::Text,
           _ => unreachable!(),
         };
 
@@ -1238,6 +1402,8 @@ impl ModuleMap {
       .by_name_mut(AssertedModuleType::JavaScriptOrWasm)
       .clear();
     self.by_name_mut(AssertedModuleType::Json).clear();
+    self.by_name_mut(AssertedModuleType::Bytes).clear();
+    self.by_name_mut(AssertedModuleType::Text).clear();
 
     {
       let by_name_arr: v8::Local<v8::Array> =
@@ -1262,6 +1428,8 @@ impl ModuleMap {
         {
           0 => AssertedModuleType::JavaScriptOrWasm,
           1 => AssertedModuleType::Json,
+          2 => AssertedModuleType::Bytes,
+          3 => AssertedModuleType::Text,
           _ => unreachable!(),
         };
 
@@ -1296,12 +1464,14 @@ impl ModuleMap {
       info: vec![],
       by_name_js: HashMap::new(),
       by_name_json: HashMap::new(),
+      by_name_bytes: HashMap::new(),
+      by_name_text: HashMap::new(),
       next_load_id: 1,
       loader,
       dynamic_import_map: HashMap::new(),
       preparing_dynamic_imports: FuturesUnordered::new(),
       pending_dynamic_imports: FuturesUnordered::new(),
-      json_value_store: HashMap::new(),
+      synthetic_module_value_store: HashMap::new(),
     }
   }
 
@@ -1356,22 +1526,85 @@ impl ModuleMap {
       }
     };
 
-    let export_names = [v8::String::new(tc_scope, "default").unwrap()];
-    let module = v8::Module::create_synthetic_module(
+    Ok(self.new_synthetic_module(
       tc_scope,
+      name,
+      name_str,
+      ModuleType::Json,
+      parsed_json,
+    ))
+  }
+
+  /// Create a module whose default export is a `Uint8Array` holding a copy
+  /// of `bytes`.
+  fn new_bytes_module(
+    &mut self,
+    scope: &mut v8::HandleScope,
+    name: ModuleName,
+    bytes: &[u8],
+  ) -> ModuleId {
+    let name_str = name.v8(scope);
+    let backing_store =
+      v8::ArrayBuffer::new_backing_store_from_vec(bytes.to_vec()).make_shared();
+    let buffer = v8::ArrayBuffer::with_backing_store(scope, &backing_store);
+    let value = v8::Uint8Array::new(scope, buffer, 0, bytes.len()).unwrap();
+    self.new_synthetic_module(
+      scope,
+      name,
+      name_str,
+      ModuleType::Bytes,
+      value.into(),
+    )
+  }
+
+  /// Create a module whose default export is `bytes` decoded as UTF-8, with
+  /// invalid sequences replaced and the byte order mark stripped.
+  fn new_text_module(
+    &mut self,
+    scope: &mut v8::HandleScope,
+    name: ModuleName,
+    bytes: &[u8],
+  ) -> ModuleId {
+    let name_str = name.v8(scope);
+    let text = String::from_utf8_lossy(strip_bom(bytes));
+    let value = v8::String::new_from_utf8(
+      scope,
+      text.as_bytes(),
+      v8::NewStringType::Normal,
+    )
+    .unwrap();
+    self.new_synthetic_module(
+      scope,
+      name,
+      name_str,
+      ModuleType::Text,
+      value.into(),
+    )
+  }
+
+  fn new_synthetic_module(
+    &mut self,
+    scope: &mut v8::HandleScope,
+    name: ModuleName,
+    name_str: v8::Local<v8::String>,
+    module_type: ModuleType,
+    value: v8::Local<v8::Value>,
+  ) -> ModuleId {
+    let export_names = [v8::String::new(scope, "default").unwrap()];
+    let module = v8::Module::create_synthetic_module(
+      scope,
       name_str,
       &export_names,
-      json_module_evaluation_steps,
+      synthetic_module_evaluation_steps,
     );
 
-    let handle = v8::Global::<v8::Module>::new(tc_scope, module);
-    let value_handle = v8::Global::<v8::Value>::new(tc_scope, parsed_json);
-    self.json_value_store.insert(handle.clone(), value_handle);
-
-    let id =
-      self.create_module_info(name, ModuleType::Json, handle, false, vec![]);
+    let handle = v8::Global::<v8::Module>::new(scope, module);
+    let value_handle = v8::Global::<v8::Value>::new(scope, value);
+    self
+      .synthetic_module_value_store
+      .insert(handle.clone(), value_handle);
 
-    Ok(id)
+    self.create_module_info(name, module_type, handle, false, vec![])
   }
 
   /// Create and compile an ES module.
@@ -1382,16 +1615,55 @@ impl ModuleMap {
     name: ModuleName,
     source: ModuleCode,
     is_dynamic_import: bool,
+  ) -> Result<ModuleId, ModuleError> {
+    self.new_es_module_with_code_cache(
+      scope,
+      main,
+      name,
+      source,
+      is_dynamic_import,
+      None,
+    )
+  }
+
+  /// Create and compile an ES module, with its code cache if the loader
+  /// keeps one.
+  pub(crate) fn new_es_module_with_code_cache(
+    &mut self,
+    scope: &mut v8::HandleScope,
+    main: bool,
+    name: ModuleName,
+    source: ModuleCode,
+    is_dynamic_import: bool,
+    code_cache: Option<ModuleCodeCache>,
   ) -> Result<ModuleId, ModuleError> {
     let name_str = name.v8(scope);
     let source_str = source.v8(scope);
 
     let origin = bindings::module_origin(scope, name_str);
-    let source = v8::script_compiler::Source::new(source_str, Some(&origin));
 
     let tc_scope = &mut v8::TryCatch::new(scope);
 
-    let maybe_module = v8::script_compiler::compile_module(tc_scope, source);
+    let maybe_module = match &code_cache {
+      Some(ModuleCodeCache::Existing(code_cache)) => {
+        let source = v8::script_compiler::Source::new_with_cached_data(
+          source_str,
+          Some(&origin),
+          v8::CachedData::new(code_cache),
+        );
+        v8::script_compiler::compile_module2(
+          tc_scope,
+          source,
+          v8::script_compiler::CompileOptions::ConsumeCodeCache,
+          v8::script_compiler::NoCacheReason::NoReason,
+        )
+      }
+      _ => {
+        let source =
+          v8::script_compiler::Source::new(source_str, Some(&origin));
+        v8::script_compiler::compile_module(tc_scope, source)
+      }
+    };
 
     if tc_scope.has_caught() {
       assert!(maybe_module.is_none());
@@ -1402,6 +1674,16 @@ impl ModuleMap {
 
     let module = maybe_module.unwrap();
 
+    if let Some(ModuleCodeCache::Missing) = code_cache {
+      let unbound_module_script = module.get_unbound_module_script(tc_scope);
+      if let (Some(code_cache), Ok(specifier)) = (
+        unbound_module_script.create_code_cache(),
+        ModuleSpecifier::parse(name.as_ref()),
+      ) {
+        self.loader.code_cache_ready(&specifier, &code_cache);
+      }
+    }
+
     let mut requests: Vec<ModuleRequest> = vec![];
     let module_requests = module.get_module_requests();
     for i in 0..module_requests.length() {
@@ -1545,6 +1827,8 @@ impl ModuleMap {
   ) -> &HashMap<ModuleName, SymbolicModule> {
     match asserted_module_type {
       AssertedModuleType::Json => &self.by_name_json,
+      AssertedModuleType::Bytes => &self.by_name_bytes,
+      AssertedModuleType::Text => &self.by_name_text,
       AssertedModuleType::JavaScriptOrWasm => &self.by_name_js,
     }
   }
@@ -1555,6 +1839,8 @@ impl ModuleMap {
   ) -> &mut HashMap<ModuleName, SymbolicModule> {
     match asserted_module_type {
       AssertedModuleType::Json => &mut self.by_name_json,
+      AssertedModuleType::Bytes => &mut self.by_name_bytes,
+      AssertedModuleType::Text => &mut self.by_name_text,
       AssertedModuleType::JavaScriptOrWasm => &mut self.by_name_js,
     }
   }
@@ -1714,6 +2000,7 @@ mod tests {
   use futures::future::poll_fn;
   use futures::future::FutureExt;
   use parking_lot::Mutex;
+  use std::cell::Cell;
   use std::fmt;
   use std::future::Future;
   use std::io;
@@ -2689,6 +2976,87 @@ import "/a.js";
     .await;
   }
 
+  #[tokio::test]
+  async fn bytes_and_text_modules() {
+    struct AssetLoader;
+
+    impl ModuleLoader for AssetLoader {
+      fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &str,
+        _kind: ResolutionKind,
+      ) -> Result<ModuleSpecifier, Error> {
+        Ok(resolve_import(specifier, referrer)?)
+      }
+
+      fn load(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        _maybe_referrer: Option<&ModuleSpecifier>,
+        _is_dyn_import: bool,
+      ) -> Pin<Box<ModuleSourceFuture>> {
+        assert_eq!(module_specifier.as_str(), "file:///main.js");
+        let code = r#"
+          import bytes from "./asset.bin" assert { type: "bytes" };
+          import text from "./asset.bin" assert { type: "text" };
+          if (!(bytes instanceof Uint8Array)) throw Error("not bytes");
+          if (bytes.length != 9) throw Error(`bad length ${bytes.length}`);
+          if (text != "héllo") throw Error(`bad text ${text}`);
+          const again = await import("./asset.bin", {
+            assert: { type: "bytes" },
+          });
+          if (again.default !== bytes) throw Error("not cached");
+        "#;
+        futures::future::ok(ModuleSource::new(
+          ModuleType::JavaScript,
+          ModuleCode::from_static(code),
+          module_specifier,
+        ))
+        .boxed_local()
+      }
+
+      fn load_bytes(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        _maybe_referrer: Option<&ModuleSpecifier>,
+        _is_dyn_import: bool,
+      ) -> Pin<Box<ModuleSourceFuture>> {
+        assert_eq!(module_specifier.as_str(), "file:///asset.bin");
+        let bytes = "\u{FEFF}héllo".as_bytes().to_vec();
+        futures::future::ok(ModuleSource::new_bytes(
+          bytes,
+          module_specifier,
+          module_specifier,
+        ))
+        .boxed_local()
+      }
+    }
+
+    let mut runtime = JsRuntime::new(RuntimeOptions {
+      module_loader: Some(Rc::new(AssetLoader)),
+      ..Default::default()
+    });
+
+    let spec = resolve_url("file:///main.js").unwrap();
+    let main_id = runtime.load_main_module(&spec, None).await.unwrap();
+    let receiver = runtime.mod_evaluate(main_id);
+    runtime.run_event_loop(false).await.unwrap();
+    receiver.await.unwrap().unwrap();
+
+    let module_map = runtime.module_map().clone();
+    let module_map = module_map.borrow();
+    assert!(module_map
+      .get_id("file:///asset.bin", AssertedModuleType::Bytes)
+      .is_some());
+    assert!(module_map
+      .get_id("file:///asset.bin", AssertedModuleType::Text)
+      .is_some());
+    assert!(module_map
+      .get_id("file:///asset.bin", AssertedModuleType::JavaScriptOrWasm)
+      .is_none());
+  }
+
   #[tokio::test]
   async fn loader_disappears_after_error() {
     let loader = MockLoader::new();
@@ -2865,6 +3233,73 @@ if (import.meta.url != 'file:///main_with_code.js') throw Error();
     futures::executor::block_on(runtime.run_event_loop(false)).unwrap();
   }
 
+  #[test]
+  fn code_cache() {
+    #[derive(Default)]
+    struct CodeCacheLoader {
+      code_cache: RefCell<Option<Vec<u8>>>,
+      created: Cell<usize>,
+    }
+
+    impl ModuleLoader for CodeCacheLoader {
+      fn resolve(
+        &self,
+        specifier: &str,
+        referrer: &str,
+        _kind: ResolutionKind,
+      ) -> Result<ModuleSpecifier, Error> {
+        Ok(resolve_import(specifier, referrer)?)
+      }
+
+      fn load(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        _maybe_referrer: Option<&ModuleSpecifier>,
+        _is_dyn_import: bool,
+      ) -> Pin<Box<ModuleSourceFuture>> {
+        let code_cache = match self.code_cache.borrow().clone() {
+          Some(code_cache) => ModuleCodeCache::Existing(code_cache),
+          None => ModuleCodeCache::Missing,
+        };
+        let module_source = ModuleSource::new(
+          ModuleType::JavaScript,
+          ascii_str!("export const a = [1, 2, 3].map((n) => n * 2);"),
+          module_specifier,
+        )
+        .with_code_cache(code_cache);
+        async move { Ok(module_source) }.boxed()
+      }
+
+      fn code_cache_ready(
+        &self,
+        module_specifier: &ModuleSpecifier,
+        code_cache: &[u8],
+      ) {
+        assert_eq!(module_specifier.as_str(), "file:///main.js");
+        assert!(!code_cache.is_empty());
+        *self.code_cache.borrow_mut() = Some(code_cache.to_vec());
+        self.created.set(self.created.get() + 1);
+      }
+    }
+
+    let loader = Rc::new(CodeCacheLoader::default());
+    let specifier = resolve_url("file:///main.js").unwrap();
+    for _ in 0..2 {
+      let mut runtime = JsRuntime::new(RuntimeOptions {
+        module_loader: Some(loader.clone()),
+        ..Default::default()
+      });
+      let main_id_fut =
+        runtime.load_main_module(&specifier, None).boxed_local();
+      let main_id = futures::executor::block_on(main_id_fut).unwrap();
+      #[allow(clippy::let_underscore_future)]
+      let _ = runtime.mod_evaluate(main_id);
+      futures::executor::block_on(runtime.run_event_loop(false)).unwrap();
+    }
+    // the code cache of the first run is used by the second one
+    assert_eq!(loader.created.get(), 1);
+  }
+
   #[test]
   fn dynamic_imports_snapshot() {
     //TODO: Once the issue with the ModuleNamespaceEntryGetter is fixed, we can maintain a reference to the module
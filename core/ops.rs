@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use crate::error::AnyError;
+use crate::event_loop_metrics::EventLoopMetrics;
 use crate::gotham_state::GothamState;
 use crate::realm::ContextState;
 use crate::resources::ResourceTable;
@@ -184,6 +185,7 @@ pub struct OpState {
   pub resource_table: ResourceTable,
   pub get_error_class_fn: GetErrorClassFn,
   pub tracker: OpsTracker,
+  pub event_loop_metrics: EventLoopMetrics,
   pub last_fast_op_error: Option<AnyError>,
   pub(crate) gotham_state: GothamState,
   pub waker: Arc<AtomicWaker>,
@@ -197,6 +199,7 @@ impl OpState {
       gotham_state: Default::default(),
       last_fast_op_error: None,
       tracker: OpsTracker::new(ops_count),
+      event_loop_metrics: Default::default(),
       waker: Arc::new(AtomicWaker::new()),
     }
   }
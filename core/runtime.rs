@@ -1317,6 +1317,21 @@ impl JsRuntime {
     &mut self,
     cx: &mut Context,
     wait_for_inspector: bool,
+  ) -> Poll<Result<(), Error>> {
+    let op_state = self.op_state();
+    op_state.borrow_mut().event_loop_metrics.on_poll_start();
+    let poll = self.poll_event_loop_inner(cx, wait_for_inspector);
+    op_state
+      .borrow_mut()
+      .event_loop_metrics
+      .on_poll_end(poll.is_pending());
+    poll
+  }
+
+  fn poll_event_loop_inner(
+    &mut self,
+    cx: &mut Context,
+    wait_for_inspector: bool,
   ) -> Poll<Result<(), Error>> {
     let has_inspector: bool;
 
@@ -13,17 +13,25 @@ import {
 import DOMException from "ext:deno_web/01_dom_exception.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayPrototypeConcat,
   ArrayPrototypeIndexOf,
+  ArrayPrototypeMap,
   ArrayPrototypeSplice,
   ArrayPrototypePush,
+  ObjectDefineProperty,
   Symbol,
   Uint8Array,
 } = primordials;
 
 const _name = Symbol("[[name]]");
+const _pattern = Symbol("[[pattern]]");
 const _closed = Symbol("[[closed]]");
+const _rid = Symbol("[[rid]]");
 
+// The channels of this isolate that aren't patterns share a single
+// subscription, while each pattern channel has its own.
 const channels = [];
+const patternChannels = [];
 let rid = null;
 
 async function recv() {
@@ -34,7 +42,7 @@ async function recv() {
       break;
     }
 
-    const { 0: name, 1: data } = message;
+    const { name, data } = message;
     dispatch(null, name, new Uint8Array(data));
   }
 
@@ -42,6 +50,27 @@ async function recv() {
   rid = null;
 }
 
+/**
+ * Receives the messages of the channels the pattern channel matches, which
+ * include the ones sent from this isolate.
+ * @param {BroadcastChannel} channel
+ */
+async function recvPattern(channel) {
+  const rid = channel[_rid];
+  while (true) {
+    const message = await core.opAsync("op_broadcast_recv", rid);
+
+    if (message === null) {
+      break;
+    }
+
+    const { name, data } = message;
+    deliver(channel, name, new Uint8Array(data));
+  }
+
+  core.close(rid);
+}
+
 function dispatch(source, name, data) {
   for (let i = 0; i < channels.length; ++i) {
     const channel = channels[i];
@@ -50,20 +79,30 @@ function dispatch(source, name, data) {
     if (channel[_name] !== name) continue;
     if (channel[_closed]) continue;
 
-    const go = () => {
-      if (channel[_closed]) return;
-      const event = new MessageEvent("message", {
-        data: core.deserialize(data), // TODO(bnoordhuis) Cache immutables.
-        origin: "http://127.0.0.1",
-      });
-      setTarget(event, channel);
-      channel.dispatchEvent(event);
-    };
-
-    defer(go);
+    deliver(channel, name, data);
   }
 }
 
+function deliver(channel, name, data) {
+  const go = () => {
+    if (channel[_closed]) return;
+    const event = new MessageEvent("message", {
+      data: core.deserialize(data), // TODO(bnoordhuis) Cache immutables.
+      origin: "http://127.0.0.1",
+    });
+    // Non-standard: the name the message was sent with, which tells pattern
+    // channels apart from the channels they match.
+    ObjectDefineProperty(event, "channel", {
+      value: name,
+      enumerable: true,
+    });
+    setTarget(event, channel);
+    channel.dispatchEvent(event);
+  };
+
+  defer(go);
+}
+
 // Defer to avoid starving the event loop. Not using queueMicrotask()
 // for that reason: it lets promises make forward progress but can
 // still starve other parts of the event loop.
@@ -73,29 +112,73 @@ function defer(go) {
 
 class BroadcastChannel extends EventTarget {
   [_name];
+  [_pattern] = false;
   [_closed] = false;
 
   get name() {
     return this[_name];
   }
 
-  constructor(name) {
+  constructor(name, options = undefined) {
     super();
 
     const prefix = "Failed to construct 'BroadcastChannel'";
     webidl.requiredArguments(arguments.length, 1, prefix);
 
     this[_name] = webidl.converters["DOMString"](name, prefix, "Argument 1");
+    // Non-standard: how many of the last messages sent on the channel are
+    // delivered to it first, if the backend keeps them.
+    let replay = 0;
+    if (options?.replay !== undefined) {
+      replay = webidl.converters["unsigned long"](
+        options.replay,
+        prefix,
+        "Argument 2",
+      );
+    }
+    // Non-standard: the name is a pattern in which `*` stands for any
+    // sequence of characters, and the channel receives the messages of all
+    // the channels it matches.
+    if (options?.pattern !== undefined) {
+      this[_pattern] = webidl.converters["boolean"](
+        options.pattern,
+        prefix,
+        "Argument 2",
+      );
+    }
+    if (this[_pattern] && replay > 0) {
+      throw new DOMException(
+        "Pattern channels can't replay messages",
+        "NotSupportedError",
+      );
+    }
 
     this[webidl.brand] = webidl.brand;
 
-    ArrayPrototypePush(channels, this);
+    if (this[_pattern]) {
+      // Throws without --unstable, before the channel is registered.
+      this[_rid] = ops.op_broadcast_subscribe_pattern(this[_name]);
+      ArrayPrototypePush(patternChannels, this);
+      recvPattern(this);
+      return;
+    }
 
     if (rid === null) {
       // Create the rid immediately, otherwise there is a time window (and a
       // race condition) where messages can get lost, because recv() is async.
+      // Throws without --unstable, before the channel is registered.
       rid = ops.op_broadcast_subscribe();
+      ArrayPrototypePush(channels, this);
       recv();
+    } else {
+      ArrayPrototypePush(channels, this);
+    }
+
+    if (replay > 0) {
+      const messages = ops.op_broadcast_replay(rid, this[_name], replay);
+      for (let i = 0; i < messages.length; ++i) {
+        deliver(this, this[_name], messages[i]);
+      }
     }
   }
 
@@ -109,6 +192,13 @@ class BroadcastChannel extends EventTarget {
       throw new DOMException("Already closed", "InvalidStateError");
     }
 
+    if (this[_pattern]) {
+      throw new DOMException(
+        "Can't post to a pattern channel",
+        "NotSupportedError",
+      );
+    }
+
     if (typeof message === "function" || typeof message === "symbol") {
       throw new DOMException("Uncloneable value", "DataCloneError");
     }
@@ -130,6 +220,15 @@ class BroadcastChannel extends EventTarget {
     webidl.assertBranded(this, BroadcastChannelPrototype);
     this[_closed] = true;
 
+    if (this[_pattern]) {
+      const index = ArrayPrototypeIndexOf(patternChannels, this);
+      if (index === -1) return;
+
+      ArrayPrototypeSplice(patternChannels, index, 1);
+      ops.op_broadcast_unsubscribe(this[_rid]);
+      return;
+    }
+
     const index = ArrayPrototypeIndexOf(channels, this);
     if (index === -1) return;
 
@@ -140,8 +239,22 @@ class BroadcastChannel extends EventTarget {
   }
 }
 
+/**
+ * The stats of the backend, which cover the channels of every worker of the
+ * process, along with the names of the channels that are open in this
+ * isolate. Exposed as the unstable `Deno.broadcastChannelStats()`.
+ */
+function broadcastChannelStats() {
+  const stats = ops.op_broadcast_stats();
+  stats.openChannels = ArrayPrototypeMap(
+    ArrayPrototypeConcat(channels, patternChannels),
+    (channel) => channel[_name],
+  );
+  return stats;
+}
+
 defineEventHandler(BroadcastChannel.prototype, "message");
 defineEventHandler(BroadcastChannel.prototype, "messageerror");
 const BroadcastChannelPrototype = BroadcastChannel.prototype;
 
-export { BroadcastChannel };
+export { BroadcastChannel, broadcastChannelStats };
@@ -1,56 +1,271 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use async_trait::async_trait;
+use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
-use tokio::sync::broadcast;
 use tokio::sync::mpsc;
+use tokio::sync::Notify;
 use uuid::Uuid;
 
+use crate::matches_pattern;
 use crate::BroadcastChannel;
+use crate::BroadcastChannelStats;
+use crate::ChannelStats;
+
+/// How many messages are kept for each channel name by default, to be
+/// replayed to late subscribers.
+pub const DEFAULT_HISTORY_LIMIT: usize = 16;
+/// How long the messages are kept to be replayed by default. Which names
+/// subscribers listen to isn't known, so the history of a name can't be
+/// dropped once nobody listens to it anymore.
+pub const DEFAULT_HISTORY_TTL: Duration = Duration::from_secs(60);
+/// How many messages each subscriber can have yet to receive by default.
+pub const DEFAULT_BUFFER_LIMIT: usize = 256;
+
+/// What happens to a message sent while a subscriber has as many messages
+/// yet to receive as its buffer can hold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowPolicy {
+  /// The oldest message of the buffer is dropped to make room, and counted
+  /// as such.
+  DropOldest,
+  /// The message isn't sent to any subscriber, and sending it fails.
+  Error,
+}
 
 #[derive(Clone)]
-pub struct InMemoryBroadcastChannel(Arc<Mutex<broadcast::Sender<Message>>>);
+pub struct InMemoryBroadcastChannel(Arc<Mutex<State>>);
+
+struct State {
+  subscribers: HashMap<Uuid, Arc<Subscriber>>,
+  /// The sequence number of the last message that was sent.
+  seq: u64,
+  /// The last messages sent with each name, oldest first. A name is removed
+  /// once all its messages expired.
+  history: HashMap<Arc<String>, VecDeque<Message>>,
+  history_limit: usize,
+  history_ttl: Duration,
+  buffer_limit: usize,
+  overflow_policy: OverflowPolicy,
+  /// How many messages were dropped from the buffers of all the subscribers,
+  /// including the ones that are gone.
+  dropped: u64,
+}
+
+impl State {
+  /// Drops the messages of the history that were sent more than the TTL of
+  /// the history before `now`.
+  fn prune_history(&mut self, now: Instant) {
+    let history_ttl = self.history_ttl;
+    let is_expired = |message: &Message| {
+      now.saturating_duration_since(message.sent_at) >= history_ttl
+    };
+    self.history.retain(|_, history| {
+      while history.front().map_or(false, is_expired) {
+        history.pop_front();
+      }
+      !history.is_empty()
+    });
+  }
+}
+
+struct Subscriber {
+  /// Only the messages sent with a name that matches it are received.
+  pattern: Option<String>,
+  /// The messages the resource has yet to receive, oldest first.
+  buffer: Mutex<VecDeque<Message>>,
+  notify: Notify,
+  dropped: AtomicU64,
+}
 
 pub struct InMemoryBroadcastChannelResource {
-  rx: tokio::sync::Mutex<(
-    broadcast::Receiver<Message>,
-    mpsc::UnboundedReceiver<()>,
-  )>,
+  channel: Arc<Mutex<State>>,
+  subscriber: Arc<Subscriber>,
+  cancel_rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<()>>,
   cancel_tx: mpsc::UnboundedSender<()>,
   uuid: Uuid,
 }
 
+impl InMemoryBroadcastChannelResource {
+  /// How many messages were dropped from the buffer of this resource before
+  /// it could receive them.
+  pub fn dropped_messages(&self) -> u64 {
+    self.subscriber.dropped.load(Ordering::Relaxed)
+  }
+}
+
+impl Drop for InMemoryBroadcastChannelResource {
+  fn drop(&mut self) {
+    self.channel.lock().subscribers.remove(&self.uuid);
+  }
+}
+
 #[derive(Clone, Debug)]
 struct Message {
   name: Arc<String>,
   data: Arc<Vec<u8>>,
-  uuid: Uuid,
+  seq: u64,
+  sent_at: Instant,
 }
 
 impl Default for InMemoryBroadcastChannel {
   fn default() -> Self {
-    let (tx, _) = broadcast::channel(256);
-    Self(Arc::new(Mutex::new(tx)))
+    Self::new(DEFAULT_HISTORY_LIMIT)
   }
 }
 
-#[async_trait]
-impl BroadcastChannel for InMemoryBroadcastChannel {
-  type Resource = InMemoryBroadcastChannelResource;
+impl InMemoryBroadcastChannel {
+  /// `history_limit` is how many messages are kept for each channel name to
+  /// be replayed. `0` disables replaying messages.
+  pub fn new(history_limit: usize) -> Self {
+    Self::with_buffer_limit(
+      history_limit,
+      DEFAULT_BUFFER_LIMIT,
+      OverflowPolicy::DropOldest,
+    )
+  }
 
-  fn subscribe(&self) -> Result<Self::Resource, AnyError> {
+  /// Like `new`, where `buffer_limit` is how many messages each subscriber
+  /// can have yet to receive, past which `overflow_policy` applies.
+  pub fn with_buffer_limit(
+    history_limit: usize,
+    buffer_limit: usize,
+    overflow_policy: OverflowPolicy,
+  ) -> Self {
+    Self(Arc::new(Mutex::new(State {
+      subscribers: HashMap::new(),
+      seq: 0,
+      history: HashMap::new(),
+      history_limit,
+      history_ttl: DEFAULT_HISTORY_TTL,
+      buffer_limit: buffer_limit.max(1),
+      overflow_policy,
+      dropped: 0,
+    })))
+  }
+
+  /// Sets how long the messages are kept to be replayed.
+  pub fn with_history_ttl(self, history_ttl: Duration) -> Self {
+    self.0.lock().history_ttl = history_ttl;
+    self
+  }
+
+  /// How many messages were dropped from the buffers of the subscribers
+  /// before they could receive them.
+  pub fn dropped_messages(&self) -> u64 {
+    self.0.lock().dropped
+  }
+
+  /// Sends a message on behalf of `uuid`, which is the sender of the message
+  /// instead of one of the resources of this channel when it was received
+  /// from another process.
+  pub(crate) fn publish(
+    &self,
+    uuid: Uuid,
+    name: String,
+    data: Vec<u8>,
+  ) -> Result<(), AnyError> {
+    let mut state = self.0.lock();
+    let recipients = state
+      .subscribers
+      .iter()
+      .filter(|(subscriber_uuid, _)| **subscriber_uuid != uuid)
+      .map(|(_, subscriber)| subscriber)
+      .filter(|subscriber| match &subscriber.pattern {
+        Some(pattern) => matches_pattern(pattern, &name),
+        None => true,
+      })
+      .cloned()
+      .collect::<Vec<_>>();
+    if state.overflow_policy == OverflowPolicy::Error {
+      let buffer_limit = state.buffer_limit;
+      let is_full = |subscriber: &Arc<Subscriber>| {
+        subscriber.buffer.lock().len() >= buffer_limit
+      };
+      if recipients.iter().any(is_full) {
+        return Err(generic_error(
+          "The buffer of a subscriber of the broadcast channel is full.",
+        ));
+      }
+    }
+
+    state.seq += 1;
+    let now = Instant::now();
+    let message = Message {
+      name: Arc::new(name),
+      data: Arc::new(data),
+      seq: state.seq,
+      sent_at: now,
+    };
+    state.prune_history(now);
+    if state.history_limit > 0 {
+      let history_limit = state.history_limit;
+      let history = state.history.entry(message.name.clone()).or_default();
+      if history.len() == history_limit {
+        history.pop_front();
+      }
+      history.push_back(message.clone());
+    }
+    for subscriber in recipients {
+      let mut buffer = subscriber.buffer.lock();
+      if buffer.len() >= state.buffer_limit {
+        buffer.pop_front();
+        subscriber.dropped.fetch_add(1, Ordering::Relaxed);
+        state.dropped += 1;
+      }
+      buffer.push_back(message.clone());
+      drop(buffer);
+      subscriber.notify.notify_one();
+    }
+    Ok(())
+  }
+
+  fn subscribe_with(
+    &self,
+    pattern: Option<String>,
+  ) -> InMemoryBroadcastChannelResource {
     let (cancel_tx, cancel_rx) = mpsc::unbounded_channel();
-    let broadcast_rx = self.0.lock().subscribe();
-    let rx = tokio::sync::Mutex::new((broadcast_rx, cancel_rx));
+    let subscriber = Arc::new(Subscriber {
+      pattern,
+      buffer: Default::default(),
+      notify: Notify::new(),
+      dropped: AtomicU64::new(0),
+    });
     let uuid = Uuid::new_v4();
-    Ok(Self::Resource {
-      rx,
+    self.0.lock().subscribers.insert(uuid, subscriber.clone());
+    InMemoryBroadcastChannelResource {
+      channel: self.0.clone(),
+      subscriber,
+      cancel_rx: tokio::sync::Mutex::new(cancel_rx),
       cancel_tx,
       uuid,
-    })
+    }
+  }
+}
+
+#[async_trait]
+impl BroadcastChannel for InMemoryBroadcastChannel {
+  type Resource = InMemoryBroadcastChannelResource;
+
+  fn subscribe(&self) -> Result<Self::Resource, AnyError> {
+    Ok(self.subscribe_with(None))
+  }
+
+  fn subscribe_pattern(
+    &self,
+    pattern: &str,
+  ) -> Result<Self::Resource, AnyError> {
+    Ok(self.subscribe_with(Some(pattern.to_string())))
   }
 
   fn unsubscribe(&self, resource: &Self::Resource) -> Result<(), AnyError> {
@@ -63,37 +278,279 @@ impl BroadcastChannel for InMemoryBroadcastChannel {
     name: String,
     data: Vec<u8>,
   ) -> Result<(), AnyError> {
-    let name = Arc::new(name);
-    let data = Arc::new(data);
-    let uuid = resource.uuid;
-    self.0.lock().send(Message { name, data, uuid })?;
-    Ok(())
+    self.publish(resource.uuid, name, data)
   }
 
   async fn recv(
     &self,
     resource: &Self::Resource,
   ) -> Result<Option<crate::Message>, AnyError> {
-    let mut g = resource.rx.lock().await;
-    let (broadcast_rx, cancel_rx) = &mut *g;
+    let mut cancel_rx = resource.cancel_rx.lock().await;
     loop {
-      let result = tokio::select! {
-        r = broadcast_rx.recv() => r,
+      let message = resource.subscriber.buffer.lock().pop_front();
+      if let Some(message) = message {
+        return Ok(Some(crate::Message {
+          name: String::clone(&message.name),
+          data: Vec::clone(&message.data),
+          pattern: resource.subscriber.pattern.clone(),
+        }));
+      }
+      tokio::select! {
+        _ = resource.subscriber.notify.notified() => {},
         _ = cancel_rx.recv() => return Ok(None),
-      };
-      use tokio::sync::broadcast::error::RecvError::*;
-      match result {
-        Err(Closed) => return Ok(None),
-        Err(Lagged(_)) => (), // Backlogged, messages dropped.
-        Ok(message) if message.uuid == resource.uuid => (), // Self-send.
-        Ok(message) => {
-          let name = String::clone(&message.name);
-          let data = Vec::clone(&message.data);
-          return Ok(Some((name, data)));
-        }
       }
     }
   }
+
+  fn replay(
+    &self,
+    resource: &Self::Resource,
+    name: &str,
+    limit: usize,
+  ) -> Result<Vec<Vec<u8>>, AnyError> {
+    let mut state = self.0.lock();
+    state.prune_history(Instant::now());
+    let Some(history) = state.history.get(&name.to_string()) else {
+      return Ok(Vec::new());
+    };
+    // the buffered messages are still on their way to the resource
+    let buffered = resource
+      .subscriber
+      .buffer
+      .lock()
+      .iter()
+      .map(|message| message.seq)
+      .collect::<HashSet<_>>();
+    let messages = history
+      .iter()
+      .filter(|message| !buffered.contains(&message.seq))
+      .collect::<Vec<_>>();
+    Ok(
+      messages[messages.len().saturating_sub(limit)..]
+        .iter()
+        .map(|message| Vec::clone(&message.data))
+        .collect(),
+    )
+  }
+
+  /// The channels are the ones with messages that are kept to be replayed or
+  /// are yet to be received.
+  fn stats(&self) -> Result<BroadcastChannelStats, AnyError> {
+    let mut state = self.0.lock();
+    state.prune_history(Instant::now());
+    let mut queued_messages = HashMap::<Arc<String>, usize>::new();
+    for name in state.history.keys() {
+      queued_messages.insert(name.clone(), 0);
+    }
+    for subscriber in state.subscribers.values() {
+      for message in subscriber.buffer.lock().iter() {
+        *queued_messages.entry(message.name.clone()).or_default() += 1;
+      }
+    }
+    let mut channels = queued_messages
+      .into_iter()
+      .map(|(name, queued_messages)| ChannelStats {
+        subscribers: state
+          .subscribers
+          .values()
+          .filter(|subscriber| match &subscriber.pattern {
+            Some(pattern) => matches_pattern(pattern, &name),
+            None => true,
+          })
+          .count(),
+        name: String::clone(&name),
+        queued_messages,
+      })
+      .collect::<Vec<_>>();
+    channels.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(BroadcastChannelStats {
+      subscribers: state.subscribers.len(),
+      dropped_messages: state.dropped,
+      channels,
+    })
+  }
 }
 
 impl deno_core::Resource for InMemoryBroadcastChannelResource {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn received(name: &str, data: &[u8]) -> crate::Message {
+    crate::Message {
+      name: name.to_string(),
+      data: data.to_vec(),
+      pattern: None,
+    }
+  }
+
+  #[tokio::test]
+  async fn replay() {
+    let bc = InMemoryBroadcastChannel::new(2);
+    let sender = bc.subscribe().unwrap();
+    for data in [b"1", b"2", b"3"] {
+      bc.send(&sender, "a".to_string(), data.to_vec())
+        .await
+        .unwrap();
+    }
+    bc.send(&sender, "b".to_string(), b"4".to_vec())
+      .await
+      .unwrap();
+
+    // only the two last messages of "a" are kept
+    let late = bc.subscribe().unwrap();
+    assert_eq!(bc.replay(&late, "a", 5).unwrap(), [b"2", b"3"]);
+    assert_eq!(bc.replay(&late, "a", 1).unwrap(), [b"3"]);
+    assert!(bc.replay(&late, "c", 1).unwrap().is_empty());
+
+    // a message that is yet to be received isn't replayed as well
+    bc.send(&sender, "a".to_string(), b"5".to_vec())
+      .await
+      .unwrap();
+    assert_eq!(bc.replay(&late, "a", 5).unwrap(), [b"2", b"3"]);
+    let message = bc.recv(&late).await.unwrap().unwrap();
+    assert_eq!(message, received("a", b"5"));
+    assert_eq!(bc.replay(&late, "a", 5).unwrap(), [b"3", b"5"]);
+  }
+
+  #[tokio::test]
+  async fn history_ttl() {
+    let bc = InMemoryBroadcastChannel::new(2)
+      .with_history_ttl(Duration::from_secs(10));
+    let sender = bc.subscribe().unwrap();
+    for name in ["a", "b"] {
+      bc.send(&sender, name.to_string(), b"1".to_vec())
+        .await
+        .unwrap();
+    }
+    let sent = Instant::now();
+
+    let late = bc.subscribe().unwrap();
+    bc.0.lock().prune_history(sent + Duration::from_secs(5));
+    assert_eq!(bc.replay(&late, "a", 5).unwrap(), [b"1"]);
+
+    // the names whose messages all expired are forgotten
+    bc.0.lock().prune_history(sent + Duration::from_secs(10));
+    assert!(bc.replay(&late, "a", 5).unwrap().is_empty());
+    assert!(bc.stats().unwrap().channels.is_empty());
+  }
+
+  #[tokio::test]
+  async fn drop_oldest() {
+    let bc = InMemoryBroadcastChannel::with_buffer_limit(
+      0,
+      2,
+      OverflowPolicy::DropOldest,
+    );
+    let sender = bc.subscribe().unwrap();
+    let receiver = bc.subscribe().unwrap();
+    for data in [b"1", b"2", b"3"] {
+      bc.send(&sender, "a".to_string(), data.to_vec())
+        .await
+        .unwrap();
+    }
+
+    // the oldest message made room for the last one
+    assert_eq!(receiver.dropped_messages(), 1);
+    assert_eq!(sender.dropped_messages(), 0);
+    assert_eq!(bc.dropped_messages(), 1);
+    let message = bc.recv(&receiver).await.unwrap().unwrap();
+    assert_eq!(message, received("a", b"2"));
+    let message = bc.recv(&receiver).await.unwrap().unwrap();
+    assert_eq!(message, received("a", b"3"));
+
+    // the count outlives the resource
+    drop(receiver);
+    assert_eq!(bc.dropped_messages(), 1);
+  }
+
+  #[tokio::test]
+  async fn error_on_overflow() {
+    let bc =
+      InMemoryBroadcastChannel::with_buffer_limit(0, 1, OverflowPolicy::Error);
+    let sender = bc.subscribe().unwrap();
+    let receiver = bc.subscribe().unwrap();
+    bc.send(&sender, "a".to_string(), b"1".to_vec())
+      .await
+      .unwrap();
+    assert!(bc
+      .send(&sender, "a".to_string(), b"2".to_vec())
+      .await
+      .is_err());
+    assert_eq!(bc.dropped_messages(), 0);
+
+    // there is room again once the first message was received
+    let message = bc.recv(&receiver).await.unwrap().unwrap();
+    assert_eq!(message, received("a", b"1"));
+    bc.send(&sender, "a".to_string(), b"3".to_vec())
+      .await
+      .unwrap();
+    let message = bc.recv(&receiver).await.unwrap().unwrap();
+    assert_eq!(message, received("a", b"3"));
+  }
+
+  #[tokio::test]
+  async fn subscribe_pattern() {
+    let bc = InMemoryBroadcastChannel::default();
+    let sender = bc.subscribe().unwrap();
+    let jobs = bc.subscribe_pattern("jobs.*").unwrap();
+    for name in ["jobs.a", "events.a", "jobs.b"] {
+      bc.send(&sender, name.to_string(), b"data".to_vec())
+        .await
+        .unwrap();
+    }
+
+    // the messages of other channels are left out, and the name of the
+    // channel of each message is received with it
+    for name in ["jobs.a", "jobs.b"] {
+      let message = bc.recv(&jobs).await.unwrap().unwrap();
+      assert_eq!(
+        message,
+        crate::Message {
+          pattern: Some("jobs.*".to_string()),
+          ..received(name, b"data")
+        }
+      );
+    }
+    bc.unsubscribe(&jobs).unwrap();
+    assert!(bc.recv(&jobs).await.unwrap().is_none());
+  }
+
+  #[tokio::test]
+  async fn stats() {
+    let bc = InMemoryBroadcastChannel::default();
+    let sender = bc.subscribe().unwrap();
+    let jobs = bc.subscribe_pattern("jobs.*").unwrap();
+    for name in ["jobs.a", "events.a", "jobs.a"] {
+      bc.send(&sender, name.to_string(), b"data".to_vec())
+        .await
+        .unwrap();
+    }
+    bc.recv(&jobs).await.unwrap().unwrap();
+
+    assert_eq!(
+      bc.stats().unwrap(),
+      BroadcastChannelStats {
+        subscribers: 2,
+        dropped_messages: 0,
+        channels: vec![
+          ChannelStats {
+            name: "events.a".to_string(),
+            subscribers: 1,
+            queued_messages: 0,
+          },
+          ChannelStats {
+            name: "jobs.a".to_string(),
+            subscribers: 2,
+            queued_messages: 1,
+          },
+        ],
+      }
+    );
+
+    // closed subscriptions are no longer counted
+    drop(jobs);
+    assert_eq!(bc.stats().unwrap().subscribers, 1);
+  }
+}
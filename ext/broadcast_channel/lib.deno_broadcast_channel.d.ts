@@ -7,8 +7,28 @@
 
 /** @category Broadcast Channel */
 interface BroadcastChannelEventMap {
-  "message": MessageEvent;
-  "messageerror": MessageEvent;
+  "message": BroadcastChannelMessageEvent;
+  "messageerror": BroadcastChannelMessageEvent;
+}
+
+/** @category Broadcast Channel */
+interface BroadcastChannelMessageEvent<T = any> extends MessageEvent<T> {
+  /** The name of the channel the message was sent on, which differs from the
+   * name of a pattern channel. Non-standard. */
+  readonly channel: string;
+}
+
+/** @category Broadcast Channel */
+interface BroadcastChannelOptions {
+  /** How many of the last messages sent on the channel are delivered to the
+   * new channel first. Non-standard, and only messages that are still kept
+   * by the runtime are delivered. Defaults to `0`. */
+  replay?: number;
+  /** Whether the name is a pattern in which `*` stands for any sequence of
+   * characters, e.g. `"jobs.*"`. Such a channel receives the messages sent on
+   * all the channels it matches, can't be posted to and can't replay
+   * messages. Non-standard. Defaults to `false`. */
+  pattern?: boolean;
 }
 
 /** @category Broadcast Channel */
@@ -17,8 +37,12 @@ interface BroadcastChannel extends EventTarget {
    * Returns the channel name (as passed to the constructor).
    */
   readonly name: string;
-  onmessage: ((this: BroadcastChannel, ev: MessageEvent) => any) | null;
-  onmessageerror: ((this: BroadcastChannel, ev: MessageEvent) => any) | null;
+  onmessage:
+    | ((this: BroadcastChannel, ev: BroadcastChannelMessageEvent) => any)
+    | null;
+  onmessageerror:
+    | ((this: BroadcastChannel, ev: BroadcastChannelMessageEvent) => any)
+    | null;
   /**
    * Closes the BroadcastChannel object, opening it up to garbage collection.
    */
@@ -54,5 +78,5 @@ interface BroadcastChannel extends EventTarget {
 /** @category Broadcast Channel */
 declare var BroadcastChannel: {
   prototype: BroadcastChannel;
-  new (name: string): BroadcastChannel;
+  new (name: string, options?: BroadcastChannelOptions): BroadcastChannel;
 };
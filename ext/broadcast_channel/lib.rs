@@ -1,21 +1,30 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 mod in_memory_broadcast_channel;
+mod local_ipc_broadcast_channel;
 
 pub use in_memory_broadcast_channel::InMemoryBroadcastChannel;
 pub use in_memory_broadcast_channel::InMemoryBroadcastChannelResource;
+pub use in_memory_broadcast_channel::OverflowPolicy;
+pub use in_memory_broadcast_channel::DEFAULT_BUFFER_LIMIT;
+pub use in_memory_broadcast_channel::DEFAULT_HISTORY_LIMIT;
+pub use in_memory_broadcast_channel::DEFAULT_HISTORY_TTL;
+pub use local_ipc_broadcast_channel::socket_path_for_deno_dir;
+pub use local_ipc_broadcast_channel::LocalIpcBroadcastChannel;
 
 use std::cell::RefCell;
 use std::path::PathBuf;
 use std::rc::Rc;
 
 use async_trait::async_trait;
+use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use deno_core::op;
 use deno_core::OpState;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
+use serde::Serialize;
 
 #[async_trait]
 pub trait BroadcastChannel: Clone {
@@ -23,6 +32,18 @@ pub trait BroadcastChannel: Clone {
 
   fn subscribe(&self) -> Result<Self::Resource, AnyError>;
 
+  /// Like `subscribe`, but the resource only receives the messages sent with
+  /// a name that matches `pattern`, as told by `matches_pattern`.
+  fn subscribe_pattern(
+    &self,
+    _pattern: &str,
+  ) -> Result<Self::Resource, AnyError> {
+    Err(custom_error(
+      "NotSupported",
+      "Pattern subscriptions are not supported by this broadcast channel.",
+    ))
+  }
+
   fn unsubscribe(&self, resource: &Self::Resource) -> Result<(), AnyError>;
 
   async fn send(
@@ -36,12 +57,197 @@ pub trait BroadcastChannel: Clone {
     &self,
     resource: &Self::Resource,
   ) -> Result<Option<Message>, AnyError>;
+
+  /// Returns the data of the last messages sent with the given name, at most
+  /// `limit` of them and oldest first, for a channel that has just been
+  /// created. Messages that `recv` is still going to return for the resource
+  /// must be left out. Backends that don't keep messages return none.
+  fn replay(
+    &self,
+    _resource: &Self::Resource,
+    _name: &str,
+    _limit: usize,
+  ) -> Result<Vec<Vec<u8>>, AnyError> {
+    Ok(Vec::new())
+  }
+
+  /// Returns what the backend knows about its subscribers and channels.
+  /// Backends that don't keep track of them return empty stats.
+  fn stats(&self) -> Result<BroadcastChannelStats, AnyError> {
+    Ok(BroadcastChannelStats::default())
+  }
+}
+
+/// The broadcast channel of the runtime, which reaches the workers of the
+/// process, and also the other processes that use the same socket when it is
+/// a [`LocalIpcBroadcastChannel`].
+#[derive(Clone)]
+pub enum RuntimeBroadcastChannel {
+  InMemory(InMemoryBroadcastChannel),
+  LocalIpc(LocalIpcBroadcastChannel),
+}
+
+impl Default for RuntimeBroadcastChannel {
+  fn default() -> Self {
+    Self::InMemory(Default::default())
+  }
+}
+
+impl From<InMemoryBroadcastChannel> for RuntimeBroadcastChannel {
+  fn from(bc: InMemoryBroadcastChannel) -> Self {
+    Self::InMemory(bc)
+  }
+}
+
+impl From<LocalIpcBroadcastChannel> for RuntimeBroadcastChannel {
+  fn from(bc: LocalIpcBroadcastChannel) -> Self {
+    Self::LocalIpc(bc)
+  }
+}
+
+#[async_trait]
+impl BroadcastChannel for RuntimeBroadcastChannel {
+  type Resource = InMemoryBroadcastChannelResource;
+
+  fn subscribe(&self) -> Result<Self::Resource, AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.subscribe(),
+      Self::LocalIpc(bc) => bc.subscribe(),
+    }
+  }
+
+  fn subscribe_pattern(
+    &self,
+    pattern: &str,
+  ) -> Result<Self::Resource, AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.subscribe_pattern(pattern),
+      Self::LocalIpc(bc) => bc.subscribe_pattern(pattern),
+    }
+  }
+
+  fn unsubscribe(&self, resource: &Self::Resource) -> Result<(), AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.unsubscribe(resource),
+      Self::LocalIpc(bc) => bc.unsubscribe(resource),
+    }
+  }
+
+  async fn send(
+    &self,
+    resource: &Self::Resource,
+    name: String,
+    data: Vec<u8>,
+  ) -> Result<(), AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.send(resource, name, data).await,
+      Self::LocalIpc(bc) => bc.send(resource, name, data).await,
+    }
+  }
+
+  async fn recv(
+    &self,
+    resource: &Self::Resource,
+  ) -> Result<Option<Message>, AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.recv(resource).await,
+      Self::LocalIpc(bc) => bc.recv(resource).await,
+    }
+  }
+
+  fn replay(
+    &self,
+    resource: &Self::Resource,
+    name: &str,
+    limit: usize,
+  ) -> Result<Vec<Vec<u8>>, AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.replay(resource, name, limit),
+      Self::LocalIpc(bc) => bc.replay(resource, name, limit),
+    }
+  }
+
+  fn stats(&self) -> Result<BroadcastChannelStats, AnyError> {
+    match self {
+      Self::InMemory(bc) => bc.stats(),
+      Self::LocalIpc(bc) => bc.stats(),
+    }
+  }
 }
 
-pub type Message = (String, Vec<u8>);
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Message {
+  /// The name the message was sent with.
+  pub name: String,
+  pub data: Vec<u8>,
+  /// The pattern of the subscription the message was received through, if
+  /// the resource was created with `subscribe_pattern`.
+  pub pattern: Option<String>,
+}
+
+/// A snapshot of the state of a broadcast channel backend, for health checks
+/// and for finding subscriptions that were never closed.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastChannelStats {
+  /// How many resources are subscribed.
+  pub subscribers: usize,
+  /// How many messages were dropped before they could be received.
+  pub dropped_messages: u64,
+  /// The channels, sorted by name.
+  pub channels: Vec<ChannelStats>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelStats {
+  pub name: String,
+  /// How many resources receive the messages sent on the channel.
+  pub subscribers: usize,
+  /// How many messages sent on the channel are yet to be received, summed
+  /// over the resources.
+  pub queued_messages: usize,
+}
+
+/// Whether a message sent with `name` is received through a subscription to
+/// `pattern`, in which `*` stands for any sequence of characters, including
+/// none. `jobs.*` matches `jobs.a` and `jobs.a.b`, but not `jobs`.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+  let mut parts = pattern.split('*');
+  let first = parts.next().unwrap();
+  let Some(mut rest) = name.strip_prefix(first) else {
+    return false;
+  };
+  let mut parts = parts.collect::<Vec<_>>();
+  let Some(last) = parts.pop() else {
+    // there is no wildcard
+    return rest.is_empty();
+  };
+  for part in parts {
+    match rest.find(part) {
+      Some(index) => rest = &rest[index + part.len()..],
+      None => return false,
+    }
+  }
+  rest.ends_with(last)
+}
 
 struct Unstable(bool); // --unstable
 
+/// Fails with a `NotSupported` error, which JS code can catch, if the
+/// --unstable flag was not provided. Unlike the checks that quit the process,
+/// this is safe to use in embedders.
+fn check_unstable(state: &OpState, api_name: &str) -> Result<(), AnyError> {
+  if state.borrow::<Unstable>().0 {
+    return Ok(());
+  }
+  Err(custom_error(
+    "NotSupported",
+    format!("Unstable API '{api_name}'. The --unstable flag must be provided."),
+  ))
+}
+
 #[op]
 pub fn op_broadcast_subscribe<BC>(
   state: &mut OpState,
@@ -49,20 +255,30 @@ pub fn op_broadcast_subscribe<BC>(
 where
   BC: BroadcastChannel + 'static,
 {
-  let unstable = state.borrow::<Unstable>().0;
-
-  if !unstable {
-    eprintln!(
-      "Unstable API 'BroadcastChannel'. The --unstable flag must be provided.",
-    );
-    std::process::exit(70);
-  }
+  check_unstable(state, "BroadcastChannel")?;
 
   let bc = state.borrow::<BC>();
   let resource = bc.subscribe()?;
   Ok(state.resource_table.add(resource))
 }
 
+/// Subscribes to the messages sent with a name that matches `pattern`, for a
+/// single pattern channel.
+#[op]
+pub fn op_broadcast_subscribe_pattern<BC>(
+  state: &mut OpState,
+  pattern: String,
+) -> Result<ResourceId, AnyError>
+where
+  BC: BroadcastChannel + 'static,
+{
+  check_unstable(state, "BroadcastChannel")?;
+
+  let bc = state.borrow::<BC>();
+  let resource = bc.subscribe_pattern(&pattern)?;
+  Ok(state.resource_table.add(resource))
+}
+
 #[op]
 pub fn op_broadcast_unsubscribe<BC>(
   state: &mut OpState,
@@ -104,14 +320,46 @@ where
   bc.recv(&resource).await
 }
 
+#[op]
+pub fn op_broadcast_replay<BC>(
+  state: &mut OpState,
+  rid: ResourceId,
+  name: String,
+  limit: usize,
+) -> Result<Vec<ZeroCopyBuf>, AnyError>
+where
+  BC: BroadcastChannel + 'static,
+{
+  let resource = state.resource_table.get::<BC::Resource>(rid)?;
+  let bc = state.borrow::<BC>();
+  let messages = bc.replay(&resource, &name, limit)?;
+  Ok(messages.into_iter().map(ZeroCopyBuf::from).collect())
+}
+
+#[op]
+pub fn op_broadcast_stats<BC>(
+  state: &mut OpState,
+) -> Result<BroadcastChannelStats, AnyError>
+where
+  BC: BroadcastChannel + 'static,
+{
+  check_unstable(state, "Deno.broadcastChannelStats")?;
+
+  let bc = state.borrow::<BC>();
+  bc.stats()
+}
+
 deno_core::extension!(deno_broadcast_channel,
   deps = [ deno_webidl, deno_web ],
   parameters = [BC: BroadcastChannel],
   ops = [
     op_broadcast_subscribe<BC>,
+    op_broadcast_subscribe_pattern<BC>,
     op_broadcast_unsubscribe<BC>,
     op_broadcast_send<BC>,
     op_broadcast_recv<BC>,
+    op_broadcast_replay<BC>,
+    op_broadcast_stats<BC>,
   ],
   esm = [ "01_broadcast_channel.js" ],
   options = {
@@ -128,3 +376,25 @@ pub fn get_declaration() -> PathBuf {
   PathBuf::from(env!("CARGO_MANIFEST_DIR"))
     .join("lib.deno_broadcast_channel.d.ts")
 }
+
+#[cfg(test)]
+mod tests {
+  use super::matches_pattern;
+
+  #[test]
+  fn patterns() {
+    assert!(matches_pattern("jobs", "jobs"));
+    assert!(!matches_pattern("jobs", "jobs.a"));
+    assert!(matches_pattern("jobs.*", "jobs.a"));
+    assert!(matches_pattern("jobs.*", "jobs.a.b"));
+    assert!(matches_pattern("jobs.*", "jobs."));
+    assert!(!matches_pattern("jobs.*", "jobs"));
+    assert!(matches_pattern("*", ""));
+    assert!(matches_pattern("*.done", "jobs.a.done"));
+    assert!(!matches_pattern("*.done", "jobs.a.failed"));
+    assert!(matches_pattern("jobs.*.done", "jobs.a.done"));
+    assert!(!matches_pattern("jobs.*.done", "jobs.done"));
+    assert!(matches_pattern("a*a", "aa"));
+    assert!(!matches_pattern("a*a", "a"));
+  }
+}
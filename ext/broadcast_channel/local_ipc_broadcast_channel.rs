@@ -0,0 +1,791 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! A [`BroadcastChannel`] that also exchanges messages with the other
+//! processes on the machine that use the same socket, without an external
+//! broker.
+//!
+//! The first process to use the socket listens on it and becomes the hub,
+//! which relays the messages of every process to the others. The other
+//! processes connect to the hub. When the hub goes away, the remaining
+//! processes negotiate a new one on their own.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::BroadcastChannel;
+use crate::BroadcastChannelStats;
+use crate::InMemoryBroadcastChannel;
+use crate::InMemoryBroadcastChannelResource;
+
+/// How long to wait before negotiating the hub again when neither
+/// connecting to the socket nor listening on it worked.
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+/// How often the hub checks that its socket wasn't taken over.
+const HUB_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Frames larger than this are considered corrupted.
+const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+/// How many frames may wait to be written to another process. Past that,
+/// the messages of this process are dropped, and the hub disconnects the
+/// processes that don't keep up, which then connect again.
+const FRAME_QUEUE_LEN: usize = 1024;
+
+/// A frame as written to the socket: the length of the rest of the frame,
+/// the length of the name, the name and the data.
+type Frame = Arc<[u8]>;
+
+#[derive(Clone)]
+pub struct LocalIpcBroadcastChannel {
+  /// Delivers the messages to the subscribers of this process, including
+  /// the ones received from other processes.
+  local: InMemoryBroadcastChannel,
+  socket_path: Arc<PathBuf>,
+  /// Forwards the messages sent in this process to the bridge thread, which
+  /// is started with the first subscription.
+  outgoing: Arc<Mutex<Option<mpsc::Sender<Frame>>>>,
+}
+
+impl LocalIpcBroadcastChannel {
+  /// `socket_path` is the path of a unix socket, or the name of a named pipe
+  /// on Windows, that all the processes exchanging messages use.
+  pub fn new(socket_path: PathBuf) -> Self {
+    Self {
+      local: InMemoryBroadcastChannel::default(),
+      socket_path: Arc::new(socket_path),
+      outgoing: Default::default(),
+    }
+  }
+
+  /// Exchanges messages with the processes that use the same `DENO_DIR`.
+  pub fn for_deno_dir(deno_dir: &Path) -> Self {
+    Self::new(socket_path_for_deno_dir(deno_dir))
+  }
+
+  fn ensure_bridge(&self) -> Result<(), AnyError> {
+    let mut outgoing = self.outgoing.lock();
+    if outgoing.as_ref().map(|tx| tx.is_closed()).unwrap_or(true) {
+      *outgoing = Some(start_bridge(
+        PathBuf::clone(&self.socket_path),
+        self.local.clone(),
+      )?);
+    }
+    Ok(())
+  }
+}
+
+/// The path of the socket, or the name of the named pipe on Windows, that
+/// the processes using `deno_dir` meet on.
+pub fn socket_path_for_deno_dir(deno_dir: &Path) -> PathBuf {
+  let hash = fnv1a(deno_dir.to_string_lossy().as_bytes());
+  #[cfg(windows)]
+  {
+    PathBuf::from(format!(r"\\.\pipe\deno-broadcast-channel-{hash:016x}"))
+  }
+  #[cfg(unix)]
+  {
+    let path = deno_dir.join("broadcast_channel.sock");
+    // the path of a unix socket can't be much longer than 100 bytes
+    if path.as_os_str().len() < 100 {
+      path
+    } else {
+      ipc::user_runtime_dir()
+        .join(format!("deno-broadcast-channel-{hash:016x}.sock"))
+    }
+  }
+}
+
+/// A hash that doesn't change between builds, so that different versions of
+/// Deno agree on the socket path.
+fn fnv1a(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+    (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+  })
+}
+
+#[async_trait]
+impl BroadcastChannel for LocalIpcBroadcastChannel {
+  type Resource = InMemoryBroadcastChannelResource;
+
+  fn subscribe(&self) -> Result<Self::Resource, AnyError> {
+    self.ensure_bridge()?;
+    self.local.subscribe()
+  }
+
+  fn subscribe_pattern(
+    &self,
+    pattern: &str,
+  ) -> Result<Self::Resource, AnyError> {
+    self.ensure_bridge()?;
+    self.local.subscribe_pattern(pattern)
+  }
+
+  fn unsubscribe(&self, resource: &Self::Resource) -> Result<(), AnyError> {
+    self.local.unsubscribe(resource)
+  }
+
+  async fn send(
+    &self,
+    resource: &Self::Resource,
+    name: String,
+    data: Vec<u8>,
+  ) -> Result<(), AnyError> {
+    let frame = encode_frame(&name, &data);
+    self.local.send(resource, name, data).await?;
+    if let Some(outgoing) = self.outgoing.lock().as_ref() {
+      // fails when the other processes don't keep up, in which case the
+      // message is only delivered in this process
+      let _ = outgoing.try_send(frame);
+    }
+    Ok(())
+  }
+
+  async fn recv(
+    &self,
+    resource: &Self::Resource,
+  ) -> Result<Option<crate::Message>, AnyError> {
+    self.local.recv(resource).await
+  }
+
+  fn replay(
+    &self,
+    resource: &Self::Resource,
+    name: &str,
+    limit: usize,
+  ) -> Result<Vec<Vec<u8>>, AnyError> {
+    self.local.replay(resource, name, limit)
+  }
+
+  /// Only the subscribers of this process are counted.
+  fn stats(&self) -> Result<BroadcastChannelStats, AnyError> {
+    self.local.stats()
+  }
+}
+
+fn encode_frame(name: &str, data: &[u8]) -> Frame {
+  let len = 4 + name.len() + data.len();
+  let mut frame = Vec::with_capacity(4 + len);
+  frame.extend_from_slice(&(len as u32).to_le_bytes());
+  frame.extend_from_slice(&(name.len() as u32).to_le_bytes());
+  frame.extend_from_slice(name.as_bytes());
+  frame.extend_from_slice(data);
+  frame.into()
+}
+
+fn decode_frame(frame: &[u8]) -> Option<(String, Vec<u8>)> {
+  let rest = frame.get(4..)?;
+  let name_len = u32::from_le_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+  let name = rest.get(4..4 + name_len)?;
+  let data = &rest[4 + name_len..];
+  Some((String::from_utf8(name.to_vec()).ok()?, data.to_vec()))
+}
+
+/// Reads the next frame, or `None` once the other end is closed.
+async fn read_frame(
+  reader: &mut (impl AsyncRead + Unpin),
+) -> io::Result<Option<Frame>> {
+  let mut len = [0; 4];
+  match reader.read_exact(&mut len).await {
+    Ok(_) => {}
+    Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+    Err(err) => return Err(err),
+  }
+  let len = u32::from_le_bytes(len) as usize;
+  if len < 4 || len > MAX_FRAME_LEN {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      "Invalid broadcast channel frame.",
+    ));
+  }
+  let mut frame = vec![0; 4 + len];
+  frame[..4].copy_from_slice(&(len as u32).to_le_bytes());
+  reader.read_exact(&mut frame[4..]).await?;
+  Ok(Some(frame.into()))
+}
+
+/// Delivers a message received from another process to the subscribers of
+/// this one.
+fn deliver(local: &InMemoryBroadcastChannel, frame: &[u8]) {
+  if let Some((name, data)) = decode_frame(frame) {
+    // fails when the buffer of a local subscriber is full and overflowing
+    // is an error, in which case the message is lost for this process only
+    let _ = local.publish(Uuid::nil(), name, data);
+  }
+}
+
+/// Starts the thread that exchanges the messages of `local` with the other
+/// processes. It runs until the returned sender is dropped.
+fn start_bridge(
+  socket_path: PathBuf,
+  local: InMemoryBroadcastChannel,
+) -> Result<mpsc::Sender<Frame>, AnyError> {
+  let (tx, rx) = mpsc::channel(FRAME_QUEUE_LEN);
+  std::thread::Builder::new()
+    .name("broadcast-channel-ipc".to_string())
+    .spawn(move || {
+      let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+      runtime.block_on(run_bridge(socket_path, local, rx));
+    })?;
+  Ok(tx)
+}
+
+enum BridgeExit {
+  /// The connection with the other processes was lost, so the hub has to
+  /// be negotiated again.
+  Disconnected,
+  /// The channel was dropped.
+  Closed,
+}
+
+async fn run_bridge(
+  socket_path: PathBuf,
+  local: InMemoryBroadcastChannel,
+  mut outgoing: mpsc::Receiver<Frame>,
+) {
+  if let Err(err) = ipc::ensure_private_dir(&socket_path) {
+    // retrying wouldn't help, so only the subscribers of this process get
+    // the messages from now on
+    eprintln!(
+      "Warning: BroadcastChannel messages are not exchanged with other processes: {err}"
+    );
+    while outgoing.recv().await.is_some() {}
+    return;
+  }
+  loop {
+    let exit = match ipc::connect(&socket_path).await {
+      Ok(stream) => run_client(stream, &local, &mut outgoing).await,
+      Err(_) => match ipc::listen(&socket_path) {
+        Ok(listener) => run_hub(listener, &local, &mut outgoing).await,
+        Err(_) => {
+          tokio::time::sleep(RETRY_INTERVAL).await;
+          BridgeExit::Disconnected
+        }
+      },
+    };
+    if let BridgeExit::Closed = exit {
+      return;
+    }
+  }
+}
+
+async fn run_client(
+  stream: ipc::Stream,
+  local: &InMemoryBroadcastChannel,
+  outgoing: &mut mpsc::Receiver<Frame>,
+) -> BridgeExit {
+  let (mut reader, mut writer) = tokio::io::split(stream);
+  let read_loop = async {
+    while let Ok(Some(frame)) = read_frame(&mut reader).await {
+      deliver(local, &frame);
+    }
+  };
+  let write_loop = async {
+    while let Some(frame) = outgoing.recv().await {
+      if writer.write_all(&frame).await.is_err() {
+        return BridgeExit::Disconnected;
+      }
+    }
+    BridgeExit::Closed
+  };
+  tokio::select! {
+    _ = read_loop => BridgeExit::Disconnected,
+    exit = write_loop => exit,
+  }
+}
+
+enum HubEvent {
+  Frame(u64, Frame),
+  Disconnected(u64),
+}
+
+/// A process connected to the hub.
+struct HubClient {
+  tx: mpsc::Sender<Frame>,
+  task: tokio::task::JoinHandle<()>,
+}
+
+/// Queues `frame` to be written to each of `clients`, but the one it came
+/// from. The clients whose queue is full are disconnected.
+fn relay(
+  clients: &mut HashMap<u64, HubClient>,
+  from: Option<u64>,
+  frame: &Frame,
+) {
+  clients.retain(|id, client| {
+    if Some(*id) == from {
+      return true;
+    }
+    match client.tx.try_send(frame.clone()) {
+      Err(mpsc::error::TrySendError::Full(_)) => {
+        client.task.abort();
+        false
+      }
+      _ => true,
+    }
+  });
+}
+
+async fn run_hub(
+  mut listener: ipc::Listener,
+  local: &InMemoryBroadcastChannel,
+  outgoing: &mut mpsc::Receiver<Frame>,
+) -> BridgeExit {
+  let mut clients = HashMap::<u64, HubClient>::new();
+  let mut next_client_id = 0;
+  let (events_tx, mut events_rx) = mpsc::channel(FRAME_QUEUE_LEN);
+  let mut check_interval = tokio::time::interval(HUB_CHECK_INTERVAL);
+
+  loop {
+    tokio::select! {
+      stream = listener.accept() => {
+        let Ok(stream) = stream else {
+          continue;
+        };
+        let id = next_client_id;
+        next_client_id += 1;
+        let (tx, rx) = mpsc::channel(FRAME_QUEUE_LEN);
+        let task =
+          tokio::spawn(serve_client(id, stream, rx, events_tx.clone()));
+        clients.insert(id, HubClient { tx, task });
+      }
+      frame = outgoing.recv() => {
+        let Some(frame) = frame else {
+          return BridgeExit::Closed;
+        };
+        relay(&mut clients, None, &frame);
+      }
+      Some(event) = events_rx.recv() => match event {
+        HubEvent::Frame(from, frame) => {
+          deliver(local, &frame);
+          relay(&mut clients, Some(from), &frame);
+        }
+        HubEvent::Disconnected(id) => {
+          clients.remove(&id);
+        }
+      },
+      _ = check_interval.tick() => {
+        if !listener.is_current() {
+          // another process took the socket over, so connect to it instead
+          return BridgeExit::Disconnected;
+        }
+      }
+    }
+  }
+}
+
+async fn serve_client(
+  id: u64,
+  stream: ipc::Stream,
+  mut client_rx: mpsc::Receiver<Frame>,
+  events_tx: mpsc::Sender<HubEvent>,
+) {
+  let (mut reader, mut writer) = tokio::io::split(stream);
+  let read_loop = async {
+    while let Ok(Some(frame)) = read_frame(&mut reader).await {
+      if events_tx.send(HubEvent::Frame(id, frame)).await.is_err() {
+        return;
+      }
+    }
+  };
+  let write_loop = async {
+    while let Some(frame) = client_rx.recv().await {
+      if writer.write_all(&frame).await.is_err() {
+        return;
+      }
+    }
+  };
+  tokio::select! {
+    _ = read_loop => {},
+    _ = write_loop => {},
+  }
+  let _ = events_tx.send(HubEvent::Disconnected(id)).await;
+}
+
+/// A stream between two processes.
+pub trait IpcStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> IpcStream for T {}
+
+#[cfg(unix)]
+mod ipc {
+  use std::io;
+  use std::os::unix::fs::DirBuilderExt;
+  use std::os::unix::fs::MetadataExt;
+  use std::os::unix::fs::PermissionsExt;
+  use std::path::Path;
+  use std::path::PathBuf;
+
+  use tokio::net::UnixListener;
+  use tokio::net::UnixStream;
+
+  use super::IpcStream;
+
+  pub type Stream = Box<dyn IpcStream>;
+
+  /// A directory that belongs to the current user, for sockets whose path
+  /// in the `DENO_DIR` would be too long.
+  pub fn user_runtime_dir() -> PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+      Some(dir) if Path::new(&dir).is_absolute() => PathBuf::from(dir),
+      _ => std::env::temp_dir().join(format!("deno-{}", euid())),
+    }
+  }
+
+  fn euid() -> u32 {
+    // SAFETY: geteuid always succeeds
+    unsafe { libc::geteuid() }
+  }
+
+  /// Creates the directory of the socket if needed, and makes sure that it
+  /// belongs to the current user and that nobody else can write to it, as
+  /// they could otherwise replace the socket with their own.
+  pub fn ensure_private_dir(path: &Path) -> io::Result<()> {
+    let Some(dir) = path.parent() else {
+      return Ok(());
+    };
+    std::fs::DirBuilder::new()
+      .recursive(true)
+      .mode(0o700)
+      .create(dir)?;
+    let metadata = std::fs::metadata(dir)?;
+    if metadata.uid() != euid() || metadata.mode() & 0o022 != 0 {
+      return Err(io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("{} is writable by other users", dir.display()),
+      ));
+    }
+    Ok(())
+  }
+
+  pub async fn connect(path: &Path) -> io::Result<Stream> {
+    ensure_private_dir(path)?;
+    Ok(Box::new(UnixStream::connect(path).await?))
+  }
+
+  pub struct Listener {
+    listener: UnixListener,
+    path: PathBuf,
+    /// The inode of the socket, to tell whether it was replaced.
+    ino: u64,
+  }
+
+  pub fn listen(path: &Path) -> io::Result<Listener> {
+    ensure_private_dir(path)?;
+    let listener = match UnixListener::bind(path) {
+      Ok(listener) => listener,
+      Err(err) if err.kind() == io::ErrorKind::AddrInUse => {
+        // connecting failed, so the socket was left behind by a hub that
+        // exited without cleaning up
+        std::fs::remove_file(path)?;
+        UnixListener::bind(path)?
+      }
+      Err(err) => return Err(err),
+    };
+    // only the current user may connect
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    let ino = std::fs::metadata(path)?.ino();
+    Ok(Listener {
+      listener,
+      path: path.to_path_buf(),
+      ino,
+    })
+  }
+
+  impl Listener {
+    pub async fn accept(&mut self) -> io::Result<Stream> {
+      let (stream, _) = self.listener.accept().await?;
+      Ok(Box::new(stream))
+    }
+
+    /// Whether the socket at the path is still the one listened on, as a
+    /// process that found it unresponsive may have replaced it.
+    pub fn is_current(&self) -> bool {
+      std::fs::metadata(&self.path)
+        .map(|metadata| metadata.ino() == self.ino)
+        .unwrap_or(false)
+    }
+  }
+
+  impl Drop for Listener {
+    fn drop(&mut self) {
+      if self.is_current() {
+        let _ = std::fs::remove_file(&self.path);
+      }
+    }
+  }
+}
+
+#[cfg(windows)]
+mod ipc {
+  use std::ffi::c_void;
+  use std::ffi::OsStr;
+  use std::io;
+  use std::path::Path;
+  use std::ptr::null_mut;
+
+  use tokio::net::windows::named_pipe::ClientOptions;
+  use tokio::net::windows::named_pipe::NamedPipeServer;
+  use tokio::net::windows::named_pipe::ServerOptions;
+  use windows_sys::Win32::Foundation::CloseHandle;
+  use windows_sys::Win32::Foundation::LocalFree;
+  use windows_sys::Win32::Security::Authorization::ConvertSidToStringSidW;
+  use windows_sys::Win32::Security::Authorization::ConvertStringSecurityDescriptorToSecurityDescriptorW;
+  use windows_sys::Win32::Security::Authorization::SDDL_REVISION_1;
+  use windows_sys::Win32::Security::GetTokenInformation;
+  use windows_sys::Win32::Security::TokenUser;
+  use windows_sys::Win32::Security::PSECURITY_DESCRIPTOR;
+  use windows_sys::Win32::Security::SECURITY_ATTRIBUTES;
+  use windows_sys::Win32::Security::TOKEN_QUERY;
+  use windows_sys::Win32::Security::TOKEN_USER;
+  use windows_sys::Win32::System::Threading::GetCurrentProcess;
+  use windows_sys::Win32::System::Threading::OpenProcessToken;
+
+  use super::IpcStream;
+
+  pub type Stream = Box<dyn IpcStream>;
+
+  /// Named pipes don't live in a directory.
+  pub fn ensure_private_dir(_path: &Path) -> io::Result<()> {
+    Ok(())
+  }
+
+  pub async fn connect(path: &Path) -> io::Result<Stream> {
+    Ok(Box::new(ClientOptions::new().open(path)?))
+  }
+
+  pub struct Listener {
+    /// The instance of the pipe waiting for the next client.
+    server: NamedPipeServer,
+    name: std::ffi::OsString,
+  }
+
+  pub fn listen(path: &Path) -> io::Result<Listener> {
+    // fails if another process already created the pipe
+    let server = create_pipe(path.as_os_str(), true)?;
+    Ok(Listener {
+      server,
+      name: path.as_os_str().to_owned(),
+    })
+  }
+
+  /// Creates an instance of the pipe that only the current user can connect
+  /// to, and only from this machine.
+  fn create_pipe(name: &OsStr, first: bool) -> io::Result<NamedPipeServer> {
+    let sddl = format!("D:P(A;;GA;;;{})", current_user_sid()?);
+    let sddl = sddl.encode_utf16().chain(Some(0)).collect::<Vec<_>>();
+    let mut descriptor: PSECURITY_DESCRIPTOR = null_mut();
+    // SAFETY: the string is nul terminated and the descriptor is freed
+    // below
+    let ok = unsafe {
+      ConvertStringSecurityDescriptorToSecurityDescriptorW(
+        sddl.as_ptr(),
+        SDDL_REVISION_1,
+        &mut descriptor,
+        null_mut(),
+      )
+    };
+    if ok == 0 {
+      return Err(io::Error::last_os_error());
+    }
+    let mut attributes = SECURITY_ATTRIBUTES {
+      nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+      lpSecurityDescriptor: descriptor,
+      bInheritHandle: 0,
+    };
+    // SAFETY: the attributes are valid for the duration of the call
+    let result = unsafe {
+      ServerOptions::new()
+        .first_pipe_instance(first)
+        .reject_remote_clients(true)
+        .create_with_security_attributes_raw(
+          name,
+          &mut attributes as *mut SECURITY_ATTRIBUTES as *mut c_void,
+        )
+    };
+    // SAFETY: the descriptor was allocated by the conversion above
+    unsafe { LocalFree(descriptor as _) };
+    result
+  }
+
+  /// The SID of the user the process runs as, as a string.
+  fn current_user_sid() -> io::Result<String> {
+    // SAFETY: the token is closed, and the buffer is large enough for the
+    // token information as told by the first call
+    unsafe {
+      let mut token = 0;
+      if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      let mut len = 0;
+      GetTokenInformation(token, TokenUser, null_mut(), 0, &mut len);
+      // a buffer of u64s, as the token information needs to be aligned
+      let mut buf = vec![0u64; (len as usize + 7) / 8];
+      let ok = GetTokenInformation(
+        token,
+        TokenUser,
+        buf.as_mut_ptr() as *mut c_void,
+        len,
+        &mut len,
+      );
+      let err = io::Error::last_os_error();
+      CloseHandle(token);
+      if ok == 0 {
+        return Err(err);
+      }
+      let user = &*(buf.as_ptr() as *const TOKEN_USER);
+      let mut sid = null_mut();
+      if ConvertSidToStringSidW(user.User.Sid, &mut sid) == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      let len = (0..).take_while(|i| *sid.add(*i) != 0).count();
+      let string =
+        String::from_utf16_lossy(std::slice::from_raw_parts(sid, len));
+      LocalFree(sid as _);
+      Ok(string)
+    }
+  }
+
+  impl Listener {
+    pub async fn accept(&mut self) -> io::Result<Stream> {
+      self.server.connect().await?;
+      let next = create_pipe(&self.name, false)?;
+      Ok(Box::new(std::mem::replace(&mut self.server, next)))
+    }
+
+    /// Named pipes go away with the process that created them, so they
+    /// can't be taken over.
+    pub fn is_current(&self) -> bool {
+      true
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn frames() {
+    let frame = encode_frame("channel", b"data");
+    assert_eq!(
+      decode_frame(&frame),
+      Some(("channel".to_string(), b"data".to_vec()))
+    );
+    assert_eq!(decode_frame(&frame[..6]), None);
+  }
+
+  #[cfg(unix)]
+  #[tokio::test]
+  async fn private_socket() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir()
+      .join(format!("deno_broadcast_channel_test_{}", Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+    let path = dir.join("broadcast_channel.sock");
+
+    // other users could replace the socket in a directory they can write to
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777))
+      .unwrap();
+    match ipc::listen(&path) {
+      Err(err) => assert_eq!(err.kind(), io::ErrorKind::PermissionDenied),
+      Ok(_) => panic!("listened in a directory writable by others"),
+    }
+    assert!(ipc::connect(&path).await.is_err());
+
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755))
+      .unwrap();
+    let listener = ipc::listen(&path).unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    drop(listener);
+
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+
+  #[cfg(unix)]
+  #[tokio::test]
+  async fn bridge_gives_up_on_insecure_dir() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir()
+      .join(format!("deno_broadcast_channel_test_{}", Uuid::new_v4()));
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o777))
+      .unwrap();
+
+    // the bridge doesn't keep trying to connect, and stops with the channel
+    let (tx, rx) = mpsc::channel(FRAME_QUEUE_LEN);
+    let bridge = tokio::spawn(run_bridge(
+      dir.join("broadcast_channel.sock"),
+      InMemoryBroadcastChannel::default(),
+      rx,
+    ));
+    tx.send(encode_frame("channel", b"data")).await.unwrap();
+    drop(tx);
+    tokio::time::timeout(Duration::from_secs(10), bridge)
+      .await
+      .unwrap()
+      .unwrap();
+
+    let _ = std::fs::remove_dir_all(&dir);
+  }
+
+  #[tokio::test]
+  async fn exchange_messages_between_processes() {
+    let deno_dir = std::env::temp_dir()
+      .join(format!("deno_broadcast_channel_test_{}", Uuid::new_v4()));
+    // each channel stands for a different process using the same DENO_DIR
+    let a = LocalIpcBroadcastChannel::for_deno_dir(&deno_dir);
+    let b = LocalIpcBroadcastChannel::for_deno_dir(&deno_dir);
+    let resource_a = a.subscribe().unwrap();
+    let resource_b = b.subscribe().unwrap();
+
+    // the hub is negotiated in the background, so keep sending until the
+    // processes are connected
+    let message = loop {
+      a.send(&resource_a, "channel".to_string(), b"ping".to_vec())
+        .await
+        .unwrap();
+      let recv = b.recv(&resource_b);
+      if let Ok(message) = tokio::time::timeout(RETRY_INTERVAL, recv).await {
+        break message.unwrap().unwrap();
+      }
+    };
+    assert_eq!(message.name, "channel");
+    assert_eq!(message.data, b"ping");
+
+    b.send(&resource_b, "channel".to_string(), b"pong".to_vec())
+      .await
+      .unwrap();
+    let message = tokio::time::timeout(Duration::from_secs(10), async {
+      loop {
+        let message = a.recv(&resource_a).await.unwrap().unwrap();
+        if message.data != b"ping" {
+          return message;
+        }
+      }
+    })
+    .await
+    .unwrap();
+    assert_eq!(message.name, "channel");
+    assert_eq!(message.data, b"pong");
+
+    let _ = std::fs::remove_dir_all(&deno_dir);
+  }
+}
@@ -306,6 +306,9 @@ const _algorithm = Symbol("[[algorithm]]");
 const _extractable = Symbol("[[extractable]]");
 const _usages = Symbol("[[usages]]");
 const _type = Symbol("[[type]]");
+// Objects with this method, like `node:crypto` KeyObjects, can be imported as
+// the key data it returns for the requested format.
+const _importKeyData = Symbol("[[importKeyData]]");
 
 class CryptoKey {
   /** @type {string} */
@@ -911,6 +914,9 @@ class SubtleCrypto {
     const prefix = "Failed to execute 'importKey' on 'SubtleCrypto'";
     webidl.requiredArguments(arguments.length, 4, prefix);
     format = webidl.converters.KeyFormat(format, prefix, "Argument 1");
+    if (typeof keyData?.[_importKeyData] === "function") {
+      keyData = keyData[_importKeyData](format);
+    }
     keyData = webidl.converters["BufferSource or JsonWebKey"](
       keyData,
       prefix,
@@ -1044,48 +1050,7 @@ class SubtleCrypto {
     format = webidl.converters.KeyFormat(format, prefix, "Argument 1");
     key = webidl.converters.CryptoKey(key, prefix, "Argument 2");
 
-    const handle = key[_handle];
-    // 2.
-    const innerKey = WeakMapPrototypeGet(KEY_STORE, handle);
-
-    const algorithmName = key[_algorithm].name;
-
-    let result;
-
-    switch (algorithmName) {
-      case "HMAC": {
-        result = exportKeyHMAC(format, key, innerKey);
-        break;
-      }
-      case "RSASSA-PKCS1-v1_5":
-      case "RSA-PSS":
-      case "RSA-OAEP": {
-        result = exportKeyRSA(format, key, innerKey);
-        break;
-      }
-      case "ECDH":
-      case "ECDSA": {
-        result = exportKeyEC(format, key, innerKey);
-        break;
-      }
-      case "Ed25519": {
-        result = exportKeyEd25519(format, key, innerKey);
-        break;
-      }
-      case "X25519": {
-        result = exportKeyX25519(format, key, innerKey);
-        break;
-      }
-      case "AES-CTR":
-      case "AES-CBC":
-      case "AES-GCM":
-      case "AES-KW": {
-        result = exportKeyAES(format, key, innerKey);
-        break;
-      }
-      default:
-        throw new DOMException("Not implemented", "NotSupportedError");
-    }
+    const result = exportKeyInner(format, key);
 
     if (key.extractable === false) {
       throw new DOMException(
@@ -3846,6 +3811,51 @@ function importKeyPBKDF2(
   return key;
 }
 
+/**
+ * Exports `key` like `SubtleCrypto.exportKey()` does, but regardless of whether
+ * it is extractable, which `node:crypto` needs to turn CryptoKeys into
+ * KeyObjects.
+ * @param {string} format
+ * @param {CryptoKey} key
+ * @returns {ArrayBuffer | object}
+ */
+function exportKeyInner(format, key) {
+  const handle = key[_handle];
+  // 2.
+  const innerKey = WeakMapPrototypeGet(KEY_STORE, handle);
+
+  const algorithmName = key[_algorithm].name;
+
+  switch (algorithmName) {
+    case "HMAC": {
+      return exportKeyHMAC(format, key, innerKey);
+    }
+    case "RSASSA-PKCS1-v1_5":
+    case "RSA-PSS":
+    case "RSA-OAEP": {
+      return exportKeyRSA(format, key, innerKey);
+    }
+    case "ECDH":
+    case "ECDSA": {
+      return exportKeyEC(format, key, innerKey);
+    }
+    case "Ed25519": {
+      return exportKeyEd25519(format, key, innerKey);
+    }
+    case "X25519": {
+      return exportKeyX25519(format, key, innerKey);
+    }
+    case "AES-CTR":
+    case "AES-CBC":
+    case "AES-GCM":
+    case "AES-KW": {
+      return exportKeyAES(format, key, innerKey);
+    }
+    default:
+      throw new DOMException("Not implemented", "NotSupportedError");
+  }
+}
+
 function exportKeyHMAC(format, key, innerKey) {
   // 1.
   if (innerKey == null) {
@@ -5246,4 +5256,11 @@ const dictEcdhKeyDeriveParams = [
 webidl.converters.EcdhKeyDeriveParams = webidl
   .createDictionaryConverter("EcdhKeyDeriveParams", dictEcdhKeyDeriveParams);
 
-export { Crypto, crypto, CryptoKey, SubtleCrypto };
+export {
+  _importKeyData,
+  Crypto,
+  crypto,
+  CryptoKey,
+  exportKeyInner,
+  SubtleCrypto,
+};
@@ -12,6 +12,18 @@
 
 const core = globalThis.Deno.core;
 const ops = core.ops;
+import { CustomEvent, EventTarget } from "ext:deno_web/02_event.js";
+const primordials = globalThis.__bootstrap.primordials;
+const {
+  ArrayPrototypeForEach,
+  MapPrototypeDelete,
+  MapPrototypeGet,
+  MapPrototypeSet,
+  SafeMap,
+} = primordials;
+
+/** @type {Map<number, HttpClient>} */
+const clientsWithRetry = new SafeMap();
 
 /**
  * @param {Deno.CreateHttpClientOptions} options
@@ -19,24 +31,51 @@ const ops = core.ops;
  */
 function createHttpClient(options) {
   options.caCerts ??= [];
-  return new HttpClient(
+  const client = new HttpClient(
     ops.op_fetch_custom_client(
       options,
     ),
   );
+  if (options.retry) {
+    MapPrototypeSet(clientsWithRetry, client.rid, client);
+  }
+  return client;
+}
+
+/**
+ * Dispatches a "retry" event on the client for every retry of its requests
+ * since the last time.
+ * @param {number} rid
+ */
+function dispatchRetryEvents(rid) {
+  const client = MapPrototypeGet(clientsWithRetry, rid);
+  if (client === undefined) {
+    return;
+  }
+  const events = ops.op_fetch_client_take_retry_events(rid);
+  ArrayPrototypeForEach(events, (detail) => {
+    client.dispatchEvent(new CustomEvent("retry", { detail }));
+  });
 }
 
-class HttpClient {
+class HttpClient extends EventTarget {
   /**
    * @param {number} rid
    */
   constructor(rid) {
+    super();
     this.rid = rid;
   }
   close() {
+    MapPrototypeDelete(clientsWithRetry, this.rid);
     core.close(this.rid);
   }
 }
 const HttpClientPrototype = HttpClient.prototype;
 
-export { createHttpClient, HttpClient, HttpClientPrototype };
+export {
+  createHttpClient,
+  dispatchRetryEvents,
+  HttpClient,
+  HttpClientPrototype,
+};
@@ -13,14 +13,19 @@
 const core = globalThis.Deno.core;
 const ops = core.ops;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
-import { byteLowerCase } from "ext:deno_web/00_infra.js";
+import {
+  byteLowerCase,
+  collectSequenceOfCodepoints,
+} from "ext:deno_web/00_infra.js";
 import { BlobPrototype } from "ext:deno_web/09_file.js";
 import {
   errorReadableStream,
   readableStreamForRid,
   ReadableStreamPrototype,
 } from "ext:deno_web/06_streams.js";
+import { getHeader } from "ext:deno_fetch/20_headers.js";
 import { extractBody, InnerBody } from "ext:deno_fetch/22_body.js";
+import { dispatchRetryEvents } from "ext:deno_fetch/22_http_client.js";
 import { processUrlList, toInnerRequest } from "ext:deno_fetch/23_request.js";
 import {
   abortedNetworkError,
@@ -31,12 +36,16 @@ import {
   toInnerResponse,
 } from "ext:deno_fetch/23_response.js";
 import * as abortSignal from "ext:deno_web/03_abort_signal.js";
+import { opNow } from "ext:deno_web/02_timers.js";
+import { markResourceTiming } from "ext:deno_web/15_performance.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayPrototypePush,
   ArrayPrototypeSplice,
   ArrayPrototypeFilter,
   ArrayPrototypeIncludes,
+  MathMax,
+  NumberParseInt,
   ObjectPrototypeIsPrototypeOf,
   Promise,
   PromisePrototypeThen,
@@ -109,38 +118,136 @@ function createResponseBodyStream(responseBodyRid, terminator) {
 }
 
 /**
+ * https://fetch.spec.whatwg.org/#simple-range-header-value
+ * @param {string} value
+ * @param {boolean} allowWhitespace
+ * @returns {[number | null, number | null] | null}
+ */
+function parseSingleRangeHeaderValue(value, allowWhitespace) {
+  const isTabOrSpace = (c) => c === "\u0009" || c === "\u0020";
+  const isDigit = (c) => c >= "0" && c <= "9";
+  let position = 0;
+  const skipWhitespace = () => {
+    if (allowWhitespace) {
+      position =
+        collectSequenceOfCodepoints(value, position, isTabOrSpace).position;
+    }
+  };
+  const collectNumber = () => {
+    const res = collectSequenceOfCodepoints(value, position, isDigit);
+    position = res.position;
+    return res.result === "" ? null : NumberParseInt(res.result, 10);
+  };
+
+  if (!StringPrototypeStartsWith(value, "bytes")) {
+    return null;
+  }
+  position += 5;
+  skipWhitespace();
+  if (value[position] !== "=") {
+    return null;
+  }
+  position++;
+  skipWhitespace();
+  const rangeStart = collectNumber();
+  skipWhitespace();
+  if (value[position] !== "-") {
+    return null;
+  }
+  position++;
+  skipWhitespace();
+  const rangeEnd = collectNumber();
+  if (position < value.length) {
+    return null;
+  }
+  if (rangeStart === null && rangeEnd === null) {
+    return null;
+  }
+  if (rangeStart !== null && rangeEnd !== null && rangeStart > rangeEnd) {
+    return null;
+  }
+  return [rangeStart, rangeEnd];
+}
+
+/**
+ * The blob case of https://fetch.spec.whatwg.org/#scheme-fetch. The body
+ * reads the parts of the blob as it is consumed, and a `Range` header only
+ * reads the parts of the range.
  * @param {InnerRequest} req
  * @param {boolean} recursive
  * @param {AbortSignal} terminator
- * @returns {Promise<InnerResponse>}
+ * @returns {InnerResponse}
  */
-async function mainFetch(req, recursive, terminator) {
-  if (req.blobUrlEntry !== null) {
-    if (req.method !== "GET") {
-      throw new TypeError("Blob URL fetch only supports GET method.");
+function blobUrlFetch(req, recursive, terminator) {
+  if (req.method !== "GET") {
+    throw new TypeError("Blob URL fetch only supports GET method.");
+  }
+
+  let blob = req.blobUrlEntry;
+  const fullLength = blob.size;
+  const type = blob.type;
+  let status = 200;
+  let statusMessage = "OK";
+  const headerList = [
+    ["content-length", String(fullLength)],
+    ["content-type", type],
+  ];
+
+  const rangeHeader = getHeader(req.headerList, "range");
+  if (rangeHeader !== null) {
+    const rangeValue = parseSingleRangeHeaderValue(rangeHeader, true);
+    if (rangeValue === null) {
+      return networkError("Invalid range header for a blob URL fetch.");
+    }
+    let { 0: rangeStart, 1: rangeEnd } = rangeValue;
+    if (rangeStart === null) {
+      rangeStart = MathMax(fullLength - rangeEnd, 0);
+      rangeEnd = fullLength - 1;
+    } else {
+      if (rangeStart >= fullLength) {
+        return networkError("Range start is past the end of the blob.");
+      }
+      if (rangeEnd === null || rangeEnd >= fullLength) {
+        rangeEnd = fullLength - 1;
+      }
     }
+    blob = blob.slice(rangeStart, rangeEnd + 1, type);
+    status = 206;
+    statusMessage = "Partial Content";
+    headerList[0][1] = String(blob.size);
+    ArrayPrototypePush(headerList, [
+      "content-range",
+      `bytes ${rangeStart}-${rangeEnd}/${fullLength}`,
+    ]);
+  }
 
-    const body = new InnerBody(req.blobUrlEntry.stream());
-    terminator[abortSignal.add](() => body.error(terminator.reason));
-    processUrlList(req.urlList, req.urlListProcessed);
+  const body = new InnerBody(blob.stream());
+  terminator[abortSignal.add](() => body.error(terminator.reason));
+  processUrlList(req.urlList, req.urlListProcessed);
 
-    return {
-      headerList: [
-        ["content-length", String(req.blobUrlEntry.size)],
-        ["content-type", req.blobUrlEntry.type],
-      ],
-      status: 200,
-      statusMessage: "OK",
-      body,
-      type: "basic",
-      url() {
-        if (this.urlList.length == 0) return null;
-        return this.urlList[this.urlList.length - 1];
-      },
-      urlList: recursive
-        ? []
-        : [...new SafeArrayIterator(req.urlListProcessed)],
-    };
+  return {
+    headerList,
+    status,
+    statusMessage,
+    body,
+    type: "basic",
+    url() {
+      if (this.urlList.length == 0) return null;
+      return this.urlList[this.urlList.length - 1];
+    },
+    urlList: recursive ? [] : [...new SafeArrayIterator(req.urlListProcessed)],
+  };
+}
+
+/**
+ * @param {InnerRequest} req
+ * @param {boolean} recursive
+ * @param {AbortSignal} terminator
+ * @returns {Promise<InnerResponse>}
+ */
+async function mainFetch(req, recursive, terminator) {
+  if (req.blobUrlEntry !== null) {
+    return blobUrlFetch(req, recursive, terminator);
   }
 
   /** @type {ReadableStream<Uint8Array> | Uint8Array | null} */
@@ -278,6 +385,9 @@ async function mainFetch(req, recursive, terminator) {
     if (cancelHandleRid !== null) {
       core.tryClose(cancelHandleRid);
     }
+    if (req.clientRid !== null) {
+      dispatchRetryEvents(req.clientRid);
+    }
   }
   if (terminator.aborted) return abortedNetworkError();
 
@@ -410,6 +520,7 @@ function fetch(input, init = {}) {
   // awaiting `opPromise` in an inner function also named `fetch()` and
   // returning the result from that.
   let opPromise = undefined;
+  const startTime = opNow();
   // 1.
   const result = new Promise((resolve, reject) => {
     const prefix = "Failed to call 'fetch'";
@@ -473,6 +584,7 @@ function fetch(input, init = {}) {
             requestObject.signal[abortSignal.remove](onabort);
             return;
           }
+          markResourceTiming(requestObject.url, startTime, response.status);
           responseObject = fromInnerResponse(response, "immutable");
           resolve(responseObject);
           requestObject.signal[abortSignal.remove](onabort);
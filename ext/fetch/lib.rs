@@ -2,6 +2,7 @@
 
 mod byte_stream;
 mod fs_fetch_handler;
+mod retry;
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -67,6 +68,8 @@ pub use reqwest;
 pub use fs_fetch_handler::FsFetchHandler;
 
 pub use crate::byte_stream::MpscByteStream;
+pub use crate::retry::RetryEvent;
+pub use crate::retry::RetryPolicy;
 
 #[derive(Clone)]
 pub struct Options {
@@ -110,6 +113,7 @@ deno_core::extension!(deno_fetch,
     op_fetch<FP>,
     op_fetch_send,
     op_fetch_custom_client<FP>,
+    op_fetch_client_take_retry_events,
   ],
   esm = [
     "20_headers.js",
@@ -233,14 +237,19 @@ pub fn op_fetch<FP>(
 where
   FP: FetchPermissions + 'static,
 {
-  let client = if let Some(rid) = client_rid {
+  let method = Method::from_bytes(&method)?;
+  let (client, retry) = if let Some(rid) = client_rid {
     let r = state.resource_table.get::<HttpClientResource>(rid)?;
-    r.client.clone()
+    let retry = r
+      .retry
+      .clone()
+      .filter(|policy| policy.applies_to(&method))
+      .map(|policy| (policy, r.retry_events.clone()));
+    (r.client.clone(), retry)
   } else {
-    get_or_create_client_from_state(state)?
+    (get_or_create_client_from_state(state)?, None)
   };
 
-  let method = Method::from_bytes(&method)?;
   let url = Url::parse(&url)?;
 
   // Check scheme before asking for net permission
@@ -283,6 +292,7 @@ where
         return Err(type_error("Invalid URL"));
       }
 
+      let retry = retry.map(|retry| (retry, url.to_string()));
       let mut request = client.request(method.clone(), url);
 
       let request_body_rid = if has_body {
@@ -353,8 +363,15 @@ where
       let cancel_handle_ = cancel_handle.clone();
 
       let fut = async move {
-        request
-          .send()
+        let send = async move {
+          match retry {
+            Some(((policy, events), url)) => {
+              policy.send(method, url, request, &events).await
+            }
+            None => request.send().await,
+          }
+        };
+        send
           .or_cancel(cancel_handle_)
           .await
           .map(|res| res.map_err(|err| type_error(err.to_string())))
@@ -600,6 +617,9 @@ impl Resource for FetchResponseBodyResource {
 
 pub struct HttpClientResource {
   pub client: Client,
+  pub retry: Option<Rc<RetryPolicy>>,
+  /// The retries of this client's requests that weren't reported to JS yet.
+  pub retry_events: Rc<RefCell<Vec<RetryEvent>>>,
 }
 
 impl Resource for HttpClientResource {
@@ -609,8 +629,12 @@ impl Resource for HttpClientResource {
 }
 
 impl HttpClientResource {
-  fn new(client: Client) -> Self {
-    Self { client }
+  fn new(client: Client, retry: Option<RetryPolicy>) -> Self {
+    Self {
+      client,
+      retry: retry.map(Rc::new),
+      retry_events: Default::default(),
+    }
   }
 }
 
@@ -634,6 +658,7 @@ pub struct CreateHttpClientArgs {
   http1: bool,
   #[serde(default = "default_true")]
   http2: bool,
+  retry: Option<RetryPolicy>,
 }
 
 fn default_true() -> bool {
@@ -654,6 +679,10 @@ where
     permissions.check_net_url(&url, "Deno.createHttpClient()")?;
   }
 
+  if let Some(retry) = &args.retry {
+    retry.validate()?;
+  }
+
   let client_cert_chain_and_key = {
     if args.cert_chain.is_some() || args.private_key.is_some() {
       let cert_chain = args
@@ -699,10 +728,23 @@ where
     },
   )?;
 
-  let rid = state.resource_table.add(HttpClientResource::new(client));
+  let rid = state
+    .resource_table
+    .add(HttpClientResource::new(client, args.retry));
   Ok(rid)
 }
 
+/// Takes the retries of a client's requests, to dispatch them as events.
+#[op]
+pub fn op_fetch_client_take_retry_events(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<Vec<RetryEvent>, AnyError> {
+  let client = state.resource_table.get::<HttpClientResource>(rid)?;
+  let events = client.retry_events.take();
+  Ok(events)
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateHttpClientOptions {
   pub root_cert_store: Option<RootCertStore>,
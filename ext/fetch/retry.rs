@@ -0,0 +1,223 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The retry policy of a `Deno.HttpClient`. Requests are retried with an
+//! exponential backoff when they fail to connect or get a response with one
+//! of the configured statuses, which by default only happens for idempotent
+//! methods. Every retry is recorded, so that it can be dispatched as an event
+//! on the client.
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use reqwest::header::RETRY_AFTER;
+use reqwest::Method;
+use reqwest::RequestBuilder;
+use reqwest::Response;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetryPolicy {
+  /// How many times a request is sent at most, including the first time.
+  max_attempts: u32,
+  /// The delay, in milliseconds, before the first retry. It doubles for
+  /// every further retry.
+  initial_delay: u64,
+  /// The longest delay, in milliseconds, between two attempts.
+  max_delay: u64,
+  /// The response statuses that cause a retry.
+  retry_on_status: Vec<u16>,
+  /// Whether a request is retried when it failed before getting a response.
+  retry_on_connection_error: bool,
+  /// The methods whose requests are retried. Defaults to the idempotent ones.
+  methods: Vec<String>,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_attempts: 3,
+      initial_delay: 100,
+      max_delay: 10_000,
+      retry_on_status: vec![429, 502, 503, 504],
+      retry_on_connection_error: true,
+      methods: ["GET", "HEAD", "OPTIONS", "TRACE", "PUT", "DELETE"]
+        .into_iter()
+        .map(String::from)
+        .collect(),
+    }
+  }
+}
+
+/// A retry of a request, as it is reported to JS.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryEvent {
+  pub method: String,
+  pub url: String,
+  /// The attempt that failed, starting at 1.
+  pub attempt: u32,
+  /// The status of the response, if one was received.
+  pub status: Option<u16>,
+  /// Why the request failed, if no response was received.
+  pub error: Option<String>,
+  /// How long, in milliseconds, it is waited for before the next attempt.
+  pub delay: u64,
+}
+
+impl RetryPolicy {
+  pub fn validate(&self) -> Result<(), AnyError> {
+    if self.max_attempts == 0 {
+      return Err(type_error("`retry.maxAttempts` must be at least 1"));
+    }
+    if self.initial_delay > self.max_delay {
+      return Err(type_error(
+        "`retry.initialDelay` must not be greater than `retry.maxDelay`",
+      ));
+    }
+    Ok(())
+  }
+
+  pub fn applies_to(&self, method: &Method) -> bool {
+    self.max_attempts > 1
+      && self
+        .methods
+        .iter()
+        .any(|m| m.eq_ignore_ascii_case(method.as_str()))
+  }
+
+  /// The delay before the attempt that follows `attempt`.
+  fn backoff(&self, attempt: u32) -> Duration {
+    let delay = self
+      .initial_delay
+      .saturating_mul(1u64 << (attempt - 1).min(32))
+      .min(self.max_delay);
+    Duration::from_millis(delay)
+  }
+
+  /// The delay a response asks for with its `Retry-After` header, when it is
+  /// given in seconds. It is capped at the longest delay.
+  fn retry_after(&self, response: &Response) -> Option<Duration> {
+    let seconds = response
+      .headers()
+      .get(RETRY_AFTER)?
+      .to_str()
+      .ok()?
+      .trim()
+      .parse::<u64>()
+      .ok()?;
+    Some(
+      Duration::from_secs(seconds).min(Duration::from_millis(self.max_delay)),
+    )
+  }
+
+  /// Sends a request, and retries it as long as the policy allows it. A
+  /// request whose body is streamed can't be sent again, so it is only sent
+  /// once.
+  pub async fn send(
+    &self,
+    method: Method,
+    url: String,
+    mut request: RequestBuilder,
+    events: &RefCell<Vec<RetryEvent>>,
+  ) -> Result<Response, reqwest::Error> {
+    let mut attempt = 1;
+    loop {
+      let next_request = if attempt < self.max_attempts {
+        request.try_clone()
+      } else {
+        None
+      };
+      let Some(next_request) = next_request else {
+        return request.send().await;
+      };
+
+      let (status, error, delay) = match request.send().await {
+        Ok(response)
+          if self.retry_on_status.contains(&response.status().as_u16()) =>
+        {
+          let delay = self
+            .retry_after(&response)
+            .unwrap_or_else(|| self.backoff(attempt));
+          (Some(response.status().as_u16()), None, delay)
+        }
+        Err(err) if self.retry_on_connection_error && !err.is_builder() => {
+          (None, Some(err.to_string()), self.backoff(attempt))
+        }
+        result => return result,
+      };
+
+      events.borrow_mut().push(RetryEvent {
+        method: method.to_string(),
+        url: url.clone(),
+        attempt,
+        status,
+        error,
+        delay: delay.as_millis() as u64,
+      });
+      tokio::time::sleep(delay).await;
+      request = next_request;
+      attempt += 1;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn backoff() {
+    let policy = RetryPolicy {
+      initial_delay: 100,
+      max_delay: 1000,
+      ..Default::default()
+    };
+    let delays = (1..=6)
+      .map(|attempt| policy.backoff(attempt).as_millis())
+      .collect::<Vec<_>>();
+    assert_eq!(delays, [100, 200, 400, 800, 1000, 1000]);
+    assert_eq!(policy.backoff(100).as_millis(), 1000);
+  }
+
+  #[test]
+  fn applies_to() {
+    let policy = RetryPolicy::default();
+    assert!(policy.applies_to(&Method::GET));
+    assert!(policy.applies_to(&Method::PUT));
+    assert!(!policy.applies_to(&Method::POST));
+    assert!(!policy.applies_to(&Method::PATCH));
+
+    let policy = RetryPolicy {
+      methods: vec!["post".to_string()],
+      ..Default::default()
+    };
+    assert!(policy.applies_to(&Method::POST));
+    assert!(!policy.applies_to(&Method::GET));
+
+    let policy = RetryPolicy {
+      max_attempts: 1,
+      ..Default::default()
+    };
+    assert!(!policy.applies_to(&Method::GET));
+  }
+
+  #[test]
+  fn validate() {
+    assert!(RetryPolicy::default().validate().is_ok());
+    let policy = RetryPolicy {
+      max_attempts: 0,
+      ..Default::default()
+    };
+    assert!(policy.validate().is_err());
+    let policy = RetryPolicy {
+      initial_delay: 2000,
+      max_delay: 1000,
+      ..Default::default()
+    };
+    assert!(policy.validate().is_err());
+  }
+}
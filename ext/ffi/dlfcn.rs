@@ -20,6 +20,7 @@ use serde_value::ValueDeserializer;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::ffi::c_void;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -89,13 +90,12 @@ fn default_optional() -> bool {
   false
 }
 
-// ForeignStatic's name and type fields are read and used by
-// serde_v8 to determine which variant a ForeignSymbol is.
-// They are not used beyond that and are thus marked with underscores.
+// ForeignStatic's type field is read and used by serde_v8 to determine
+// which variant a ForeignSymbol is. It is not used beyond that and is thus
+// marked with an underscore.
 #[derive(Deserialize, Debug)]
 struct ForeignStatic {
-  #[serde(rename(deserialize = "name"))]
-  _name: Option<String>,
+  name: Option<String>,
   #[serde(rename(deserialize = "type"))]
   _type: String,
 }
@@ -145,6 +145,14 @@ where
   check_unstable(state, "Deno.dlopen");
   let permissions = state.borrow_mut::<FP>();
   permissions.check(Some(&PathBuf::from(&path)))?;
+  for (symbol_key, foreign_symbol) in &args.symbols {
+    let name = match foreign_symbol {
+      ForeignSymbol::ForeignFunction(foreign_fn) => &foreign_fn.name,
+      ForeignSymbol::ForeignStatic(foreign_static) => &foreign_static.name,
+    };
+    permissions
+      .check_symbol(Path::new(&path), name.as_deref().unwrap_or(symbol_key))?;
+  }
 
   let lib = Library::open(&path).map_err(|e| {
     dlopen::Error::OpeningLibraryError(std::io::Error::new(
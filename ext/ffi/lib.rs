@@ -65,6 +65,16 @@ pub fn check_unstable2(state: &Rc<RefCell<OpState>>, api_name: &str) {
 
 pub trait FfiPermissions {
   fn check(&mut self, path: Option<&Path>) -> Result<(), AnyError>;
+
+  /// Checks that `symbol` may be bound from the library at `path`, which
+  /// happens before the library is opened.
+  fn check_symbol(
+    &mut self,
+    _path: &Path,
+    _symbol: &str,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
 }
 
 pub(crate) type PendingFfiAsyncWork = Box<dyn FnOnce()>;
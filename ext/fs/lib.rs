@@ -2,6 +2,7 @@
 
 mod interface;
 mod ops;
+mod realpath_cache;
 mod std_fs;
 pub mod sync;
 
@@ -10,6 +11,7 @@ pub use crate::interface::FileSystemRc;
 pub use crate::interface::FsDirEntry;
 pub use crate::interface::FsFileType;
 pub use crate::interface::OpenOptions;
+pub use crate::realpath_cache::RealPathCache;
 pub use crate::std_fs::RealFs;
 pub use crate::sync::MaybeSend;
 pub use crate::sync::MaybeSync;
@@ -124,6 +126,8 @@ deno_core::extension!(deno_fs,
     op_fs_truncate_async<P>,
     op_fs_utime_sync<P>,
     op_fs_utime_async<P>,
+    op_fs_lutime_sync<P>,
+    op_fs_lutime_async<P>,
     op_fs_make_temp_dir_sync<P>,
     op_fs_make_temp_dir_async<P>,
     op_fs_make_temp_file_sync<P>,
@@ -161,5 +165,6 @@ deno_core::extension!(deno_fs,
   state = |state, options| {
     state.put(UnstableChecker { unstable: options.unstable });
     state.put(options.fs);
+    state.put(RealPathCache::default());
   },
 );
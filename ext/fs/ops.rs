@@ -32,6 +32,7 @@ use crate::interface::FsDirEntry;
 use crate::interface::FsFileType;
 use crate::FsPermissions;
 use crate::OpenOptions;
+use crate::RealPathCache;
 
 #[op]
 pub fn op_fs_cwd<P>(state: &mut OpState) -> Result<String, AnyError>
@@ -54,6 +55,8 @@ where
 {
   let d = PathBuf::from(&directory);
   state.borrow_mut::<P>().check_read(&d, "Deno.chdir()")?;
+  // relative paths lead elsewhere now
+  state.borrow_mut::<RealPathCache>().clear();
   state
     .borrow::<FileSystemRc>()
     .chdir(&d)
@@ -273,6 +276,7 @@ where
     .borrow_mut::<P>()
     .check_write(&path, "Deno.removeSync()")?;
 
+  state.borrow_mut::<RealPathCache>().clear();
   let fs = state.borrow::<FileSystemRc>();
   fs.remove_sync(&path, recursive)
     .context_path("remove", &path)?;
@@ -299,9 +303,10 @@ where
     state.borrow::<FileSystemRc>().clone()
   };
 
-  fs.remove_async(path.clone(), recursive)
-    .await
-    .context_path("remove", &path)?;
+  let result = fs.remove_async(path.clone(), recursive).await;
+  // even a failed recursive removal can have removed some of the entries
+  state.borrow_mut().borrow_mut::<RealPathCache>().clear();
+  result.context_path("remove", &path)?;
 
   Ok(())
 }
@@ -556,6 +561,7 @@ where
   permissions.check_write(&oldpath, "Deno.renameSync()")?;
   permissions.check_write(&newpath, "Deno.renameSync()")?;
 
+  state.borrow_mut::<RealPathCache>().clear();
   let fs = state.borrow::<FileSystemRc>();
   fs.rename_sync(&oldpath, &newpath)
     .context_two_path("rename", &oldpath, &newpath)?;
@@ -587,6 +593,7 @@ where
   fs.rename_async(oldpath.clone(), newpath.clone())
     .await
     .context_two_path("rename", &oldpath, &newpath)?;
+  state.borrow_mut().borrow_mut::<RealPathCache>().clear();
 
   Ok(())
 }
@@ -662,6 +669,7 @@ where
   permissions.check_write_all("Deno.symlinkSync()")?;
   permissions.check_read_all("Deno.symlinkSync()")?;
 
+  state.borrow_mut::<RealPathCache>().clear();
   let fs = state.borrow::<FileSystemRc>();
   fs.symlink_sync(&oldpath, &newpath, file_type)
     .context_two_path("symlink", &oldpath, &newpath)?;
@@ -693,6 +701,7 @@ where
   fs.symlink_async(oldpath.clone(), newpath.clone(), file_type)
     .await
     .context_two_path("symlink", &oldpath, &newpath)?;
+  state.borrow_mut().borrow_mut::<RealPathCache>().clear();
 
   Ok(())
 }
@@ -848,6 +857,66 @@ where
   Ok(())
 }
 
+#[op]
+fn op_fs_lutime_sync<P>(
+  state: &mut OpState,
+  path: &str,
+  atime_secs: i64,
+  atime_nanos: u32,
+  mtime_secs: i64,
+  mtime_nanos: u32,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_write(&path, "node:fs.lutimes()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  fs.lutime_sync(&path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+    .context_path("lutime", &path)?;
+
+  Ok(())
+}
+
+#[op]
+async fn op_fs_lutime_async<P>(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+  atime_secs: i64,
+  atime_nanos: u32,
+  mtime_secs: i64,
+  mtime_nanos: u32,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    state
+      .borrow_mut::<P>()
+      .check_write(&path, "node:fs.lutimes()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  fs.lutime_async(
+    path.clone(),
+    atime_secs,
+    atime_nanos,
+    mtime_secs,
+    mtime_nanos,
+  )
+  .await
+  .context_path("lutime", &path)?;
+
+  Ok(())
+}
+
 #[op]
 fn op_fs_make_temp_dir_sync<P>(
   state: &mut OpState,
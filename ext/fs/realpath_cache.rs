@@ -0,0 +1,41 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_io::fs::FsResult;
+
+use crate::interface::FileSystem;
+
+/// Canonicalized paths, for callers like the CommonJS resolution of node that
+/// canonicalize the same paths over and over while walking `node_modules`.
+///
+/// The ops of this extension that change where existing paths lead, like
+/// removing, renaming or symlinking, clear it. Changes made to the file system
+/// by other means, like another worker or process, aren't picked up.
+#[derive(Debug, Default)]
+pub struct RealPathCache {
+  paths: HashMap<PathBuf, PathBuf>,
+}
+
+impl RealPathCache {
+  /// Canonicalizes the path, like `FileSystem::realpath_sync()` but with the
+  /// result cached. Failures aren't cached.
+  pub fn realpath(
+    &mut self,
+    fs: &dyn FileSystem,
+    path: &Path,
+  ) -> FsResult<PathBuf> {
+    if let Some(real_path) = self.paths.get(path) {
+      return Ok(real_path.clone());
+    }
+    let real_path = fs.realpath_sync(path)?;
+    self.paths.insert(path.to_path_buf(), real_path.clone());
+    Ok(real_path)
+  }
+
+  pub fn clear(&mut self) {
+    self.paths.clear();
+  }
+}
@@ -56,7 +56,9 @@ const {
   op_http_get_request_method_and_url,
   op_http_read_request_body,
   op_http_serve,
+  op_http_serve_next_timed_out_client,
   op_http_serve_on,
+  op_http_serve_set_respond_on_timeout,
   op_http_set_promise_complete,
   op_http_set_response_body_bytes,
   op_http_set_response_body_resource,
@@ -575,6 +577,26 @@ function mapToCallback(context, callback, onError) {
   };
 }
 
+/**
+ * Internal option of `serve()`, used by `node:http` to enforce
+ * `server.keepAliveTimeout`, `server.headersTimeout` and
+ * `server.requestTimeout`.
+ *
+ * `onClientTimeout(address, port)` is called for the clients that took too
+ * long to send a request, which get a 408 response unless
+ * `respondOnTimeout` is false. The server it returns can change that later
+ * through its `kRespondOnTimeout` method.
+ */
+const kConnectionTimeouts = Symbol("kConnectionTimeouts");
+const kRespondOnTimeout = Symbol("kRespondOnTimeout");
+
+/**
+ * Internal option of `serve()`, used by `node:https` and `node:http2` to pick
+ * the protocols offered through ALPN, which decide whether clients talk
+ * HTTP/1.1 or HTTP/2 to the server.
+ */
+const kAlpnProtocols = Symbol("kAlpnProtocols");
+
 function serve(arg1, arg2) {
   let options = undefined;
   let handler = undefined;
@@ -622,7 +644,7 @@ function serve(arg1, arg2) {
     }
     listenOpts.cert = options.cert;
     listenOpts.key = options.key;
-    listenOpts.alpnProtocols = ["h2", "http/1.1"];
+    listenOpts.alpnProtocols = options[kAlpnProtocols] ?? ["h2", "http/1.1"];
     listener = listenTls(listenOpts);
     listenOpts.port = listener.addr.port;
   } else {
@@ -645,19 +667,78 @@ function serve(arg1, arg2) {
     }
   };
 
-  return serveHttpOnListener(listener, signal, handler, onError, onListen);
+  return serveHttpOnListener(
+    listener,
+    signal,
+    handler,
+    onError,
+    onListen,
+    options[kConnectionTimeouts],
+  );
 }
 
 /**
  * Serve HTTP/1.1 and/or HTTP/2 on an arbitrary listener.
  */
-function serveHttpOnListener(listener, signal, handler, onError, onListen) {
-  const context = new CallbackContext(signal, op_http_serve(listener.rid));
+function serveHttpOnListener(
+  listener,
+  signal,
+  handler,
+  onError,
+  onListen,
+  timeouts,
+) {
+  const context = new CallbackContext(
+    signal,
+    op_http_serve(
+      listener.rid,
+      timeouts === undefined ? undefined : {
+        keepAliveTimeout: timeouts.keepAliveTimeout,
+        headersTimeout: timeouts.headersTimeout,
+        requestTimeout: timeouts.requestTimeout,
+      },
+    ),
+  );
   const callback = mapToCallback(context, handler, onError);
 
   onListen(context.scheme);
 
-  return serveHttpOn(context, callback);
+  const server = serveHttpOn(context, callback);
+  if (timeouts !== undefined) {
+    const rid = context.serverRid;
+    server[kRespondOnTimeout] = (respond) =>
+      op_http_serve_set_respond_on_timeout(rid, respond);
+    server[kRespondOnTimeout](timeouts.respondOnTimeout ?? true);
+    if (timeouts.onClientTimeout) {
+      reportTimedOutClients(rid, timeouts.onClientTimeout);
+    }
+  }
+  return server;
+}
+
+/**
+ * Calls `onClientTimeout` for every client that took too long to send a
+ * request, until the server is closed. It doesn't keep the server alive.
+ */
+async function reportTimedOutClients(rid, onClientTimeout) {
+  const promiseIdSymbol = SymbolFor("Deno.core.internalPromiseId");
+  while (true) {
+    let client;
+    try {
+      const promise = op_http_serve_next_timed_out_client(rid);
+      core.unrefOp(promise[promiseIdSymbol]);
+      client = await promise;
+    } catch (error) {
+      if (ObjectPrototypeIsPrototypeOf(BadResourcePrototype, error)) {
+        break;
+      }
+      throw error;
+    }
+    if (client === null) {
+      break;
+    }
+    onClientTimeout(client[0], client[1]);
+  }
 }
 
 /**
@@ -749,6 +830,9 @@ internals.serveHttpOnConnection = serveHttpOnConnection;
 
 export {
   addTrailers,
+  kAlpnProtocols,
+  kConnectionTimeouts,
+  kRespondOnTimeout,
   serve,
   serveHttpOnConnection,
   serveHttpOnListener,
@@ -0,0 +1,547 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Timeouts for the connections of an HTTP server, which node's `http.Server`
+//! exposes as `server.keepAliveTimeout`, `server.headersTimeout` and
+//! `server.requestTimeout`. hyper doesn't implement them, so they are
+//! enforced by failing the reads of the connection, which makes hyper close
+//! it. Like in Node, a client that is too slow to send a request is reported
+//! to the `clientError` listeners of the server, and gets a 408 response
+//! first when there are none, unless the server already started responding.
+
+use crate::http_next::HTTP2_PREFIX;
+use serde::Deserialize;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+use std::time::Duration;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::Sleep;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HttpServeTimeouts {
+  /// How long, in milliseconds, a connection may stay idle between two
+  /// requests. 0 means there is no limit.
+  keep_alive_timeout: u64,
+  /// How long, in milliseconds, a client may take to send the headers of a
+  /// request once it started sending them. 0 means there is no limit.
+  headers_timeout: u64,
+  /// How long, in milliseconds, a client may take to send a whole request,
+  /// body included, once it started sending it. 0 means there is no limit.
+  request_timeout: u64,
+}
+
+/// Sent to clients that took too long to send a request.
+const REQUEST_TIMEOUT_RESPONSE: &[u8] =
+  b"HTTP/1.1 408 Request Timeout\r\nConnection: close\r\n\r\n";
+
+/// The address and port of a client that took too long to send a request.
+pub type TimedOutClient = (String, Option<u16>);
+
+/// How many timed out clients are kept until JavaScript is told about them.
+const TIMED_OUT_CLIENTS_CAPACITY: usize = 64;
+
+/// The clients of a server that took too long to send a request, which
+/// node's `http.Server` reports through its `clientError` event.
+#[derive(Debug)]
+pub struct ClientTimeouts {
+  /// Whether those clients get a 408 response, which node leaves to the
+  /// `clientError` listeners when there are any.
+  respond: AtomicBool,
+  tx: mpsc::Sender<TimedOutClient>,
+}
+
+impl ClientTimeouts {
+  pub fn new() -> (Arc<Self>, mpsc::Receiver<TimedOutClient>) {
+    let (tx, rx) = mpsc::channel(TIMED_OUT_CLIENTS_CAPACITY);
+    let clients = Self {
+      respond: AtomicBool::new(true),
+      tx,
+    };
+    (Arc::new(clients), rx)
+  }
+
+  pub fn set_respond(&self, respond: bool) {
+    self.respond.store(respond, Ordering::Relaxed);
+  }
+
+  /// Reports the client, and returns whether it should get a 408 response.
+  fn timed_out(&self, client: &TimedOutClient) -> bool {
+    // the client isn't reported when JavaScript is too far behind, or the
+    // server is closed
+    let _ = self.tx.try_send(client.clone());
+    self.respond.load(Ordering::Relaxed)
+  }
+}
+
+/// The timeouts of the connections of a server.
+#[derive(Clone, Debug)]
+pub struct ServerTimeouts {
+  timeouts: HttpServeTimeouts,
+  clients: Arc<ClientTimeouts>,
+}
+
+impl ServerTimeouts {
+  pub fn new(
+    timeouts: HttpServeTimeouts,
+    clients: Arc<ClientTimeouts>,
+  ) -> Self {
+    Self { timeouts, clients }
+  }
+}
+
+/// A deadline of a connection, whether the client is reported when it
+/// expires, and whether it can get a 408 response then.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Deadline {
+  at: Instant,
+  client_error: bool,
+  can_respond: bool,
+}
+
+#[derive(Debug)]
+struct ConnectionState {
+  requests_in_flight: usize,
+  idle_since: Instant,
+  headers_since: Option<Instant>,
+  /// When the client started sending the request whose body is still being
+  /// received.
+  request_since: Option<Instant>,
+  upgraded: bool,
+  /// `None` until the first bytes of the connection were read.
+  http2: Option<bool>,
+  read_waker: Option<Waker>,
+}
+
+/// Tracks the requests of a connection, to know which timeout applies to it.
+#[derive(Clone, Debug)]
+pub struct ConnectionTimeouts {
+  timeouts: HttpServeTimeouts,
+  clients: Arc<ClientTimeouts>,
+  client: TimedOutClient,
+  state: Arc<Mutex<ConnectionState>>,
+}
+
+impl ConnectionTimeouts {
+  /// Returns `None` when no timeout is set, so connections don't pay for it.
+  pub fn new(server: &ServerTimeouts, client: TimedOutClient) -> Option<Self> {
+    let timeouts = server.timeouts;
+    if timeouts.keep_alive_timeout == 0
+      && timeouts.headers_timeout == 0
+      && timeouts.request_timeout == 0
+    {
+      return None;
+    }
+    Some(Self {
+      timeouts,
+      clients: server.clients.clone(),
+      client,
+      state: Arc::new(Mutex::new(ConnectionState {
+        requests_in_flight: 0,
+        idle_since: Instant::now(),
+        headers_since: None,
+        request_since: None,
+        upgraded: false,
+        http2: None,
+        read_waker: None,
+      })),
+    })
+  }
+
+  /// Called once the headers of a request were received. The timeouts stop
+  /// applying for good if the connection is about to be upgraded.
+  pub fn start_request(&self, upgrade: bool, body_received: bool) {
+    let mut state = self.state.lock().unwrap();
+    state.requests_in_flight += 1;
+    state.headers_since = None;
+    if body_received {
+      state.request_since = None;
+    }
+    state.upgraded |= upgrade;
+  }
+
+  /// Whether the body of a request has to be received by the time the request
+  /// timeout expires.
+  pub fn has_request_timeout(&self) -> bool {
+    self.timeouts.request_timeout > 0
+  }
+
+  /// Called once hyper received the body of a request, whether or not it was
+  /// read yet.
+  pub fn finish_request_body(&self) {
+    self.state.lock().unwrap().request_since = None;
+  }
+
+  /// Called once the response to a request was sent.
+  pub fn finish_request(&self) {
+    let mut state = self.state.lock().unwrap();
+    state.requests_in_flight = state.requests_in_flight.saturating_sub(1);
+    state.idle_since = Instant::now();
+    state.request_since = None;
+    // the pending read has to arm the keep-alive timeout
+    if let Some(waker) = state.read_waker.take() {
+      waker.wake();
+    }
+  }
+
+  fn on_read(&self, bytes: &[u8]) {
+    let mut state = self.state.lock().unwrap();
+    if state.http2.is_none() {
+      let len = bytes.len().min(HTTP2_PREFIX.len());
+      state.http2 = Some(bytes[..len] == HTTP2_PREFIX[..len]);
+    }
+    if state.requests_in_flight == 0 && state.headers_since.is_none() {
+      let now = Instant::now();
+      state.headers_since = Some(now);
+      state.request_since = Some(now);
+    }
+  }
+
+  fn deadline(&self, waker: &Waker) -> Option<Deadline> {
+    let mut state = self.state.lock().unwrap();
+    state.read_waker = Some(waker.clone());
+    if state.upgraded {
+      return None;
+    }
+    // raw HTTP/1.1 responses can only be written between two requests
+    let can_respond =
+      state.requests_in_flight == 0 && state.http2 == Some(false);
+    let after = |since: Instant, msecs: u64, client_error: bool| Deadline {
+      at: since + Duration::from_millis(msecs),
+      client_error,
+      can_respond: client_error && can_respond,
+    };
+    let connection_deadline = if state.requests_in_flight > 0 {
+      None
+    } else {
+      match state.headers_since {
+        Some(since) if self.timeouts.headers_timeout > 0 => {
+          Some(after(since, self.timeouts.headers_timeout, true))
+        }
+        None if self.timeouts.keep_alive_timeout > 0 => Some(after(
+          state.idle_since,
+          self.timeouts.keep_alive_timeout,
+          false,
+        )),
+        _ => None,
+      }
+    };
+    let request_deadline = match state.request_since {
+      Some(since) if self.timeouts.request_timeout > 0 => {
+        Some(after(since, self.timeouts.request_timeout, true))
+      }
+      _ => None,
+    };
+    match (connection_deadline, request_deadline) {
+      (Some(a), Some(b)) => Some(if a.at <= b.at { a } else { b }),
+      (a, b) => a.or(b),
+    }
+  }
+
+  /// Reports the client once a deadline expired, and returns whether it
+  /// should get a 408 response.
+  fn expired(&self, deadline: Deadline) -> bool {
+    if !deadline.client_error {
+      return false;
+    }
+    let respond = self.clients.timed_out(&self.client);
+    respond && deadline.can_respond
+  }
+}
+
+/// A stream whose reads fail once a timeout of its connection expired.
+pub struct TimeoutStream<S> {
+  inner: S,
+  timeouts: ConnectionTimeouts,
+  sleep: Pin<Box<Sleep>>,
+  /// How much of the 408 response was written, once a timeout expired.
+  timed_out: Option<usize>,
+}
+
+impl<S> TimeoutStream<S> {
+  pub fn new(inner: S, timeouts: ConnectionTimeouts) -> Self {
+    Self {
+      inner,
+      timeouts,
+      sleep: Box::pin(tokio::time::sleep_until(Instant::now())),
+      timed_out: None,
+    }
+  }
+}
+
+impl<S: AsyncWrite + Unpin> TimeoutStream<S> {
+  /// Writes what is left of the 408 response, then fails the read. Failing
+  /// to write the response only means the client won't get it.
+  fn poll_timed_out(
+    &mut self,
+    cx: &mut Context<'_>,
+    written: usize,
+  ) -> Poll<io::Result<()>> {
+    let mut written = written;
+    while written < REQUEST_TIMEOUT_RESPONSE.len() {
+      let remaining = &REQUEST_TIMEOUT_RESPONSE[written..];
+      match Pin::new(&mut self.inner).poll_write(cx, remaining) {
+        Poll::Ready(Ok(0)) | Poll::Ready(Err(_)) => break,
+        Poll::Ready(Ok(n)) => written += n,
+        Poll::Pending => {
+          self.timed_out = Some(written);
+          return Poll::Pending;
+        }
+      }
+    }
+    self.timed_out = Some(REQUEST_TIMEOUT_RESPONSE.len());
+    if Pin::new(&mut self.inner).poll_flush(cx).is_pending() {
+      return Poll::Pending;
+    }
+    Poll::Ready(Err(io::Error::new(
+      io::ErrorKind::TimedOut,
+      "connection timed out",
+    )))
+  }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRead for TimeoutStream<S> {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    let this = self.get_mut();
+    if let Some(written) = this.timed_out {
+      return this.poll_timed_out(cx, written);
+    }
+    let filled = buf.filled().len();
+    match Pin::new(&mut this.inner).poll_read(cx, buf) {
+      Poll::Ready(Ok(())) => {
+        if buf.filled().len() > filled {
+          this.timeouts.on_read(&buf.filled()[filled..]);
+        }
+        return Poll::Ready(Ok(()));
+      }
+      Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+      Poll::Pending => {}
+    }
+
+    if let Some(deadline) = this.timeouts.deadline(cx.waker()) {
+      if this.sleep.deadline() != deadline.at {
+        this.sleep.as_mut().reset(deadline.at);
+      }
+      if this.sleep.as_mut().poll(cx).is_ready() {
+        if this.timeouts.expired(deadline) {
+          return this.poll_timed_out(cx, 0);
+        }
+        return Poll::Ready(Err(io::Error::new(
+          io::ErrorKind::TimedOut,
+          "connection timed out",
+        )));
+      }
+    }
+    Poll::Pending
+  }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for TimeoutStream<S> {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+  }
+
+  fn poll_write_vectored(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    bufs: &[io::IoSlice<'_>],
+  ) -> Poll<io::Result<usize>> {
+    Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+  }
+
+  fn is_write_vectored(&self) -> bool {
+    self.inner.is_write_vectored()
+  }
+
+  fn poll_flush(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+  }
+
+  fn poll_shutdown(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::io::AsyncReadExt;
+  use tokio::io::AsyncWriteExt;
+
+  fn timeouts(
+    keep_alive_timeout: u64,
+    headers_timeout: u64,
+    request_timeout: u64,
+  ) -> ConnectionTimeouts {
+    let (clients, _) = ClientTimeouts::new();
+    server_timeouts(
+      keep_alive_timeout,
+      headers_timeout,
+      request_timeout,
+      clients,
+    )
+  }
+
+  fn server_timeouts(
+    keep_alive_timeout: u64,
+    headers_timeout: u64,
+    request_timeout: u64,
+    clients: Arc<ClientTimeouts>,
+  ) -> ConnectionTimeouts {
+    let server = ServerTimeouts::new(
+      HttpServeTimeouts {
+        keep_alive_timeout,
+        headers_timeout,
+        request_timeout,
+      },
+      clients,
+    );
+    ConnectionTimeouts::new(&server, ("127.0.0.1".to_string(), Some(4545)))
+      .unwrap()
+  }
+
+  async fn assert_pending(stream: &mut TimeoutStream<tokio::io::DuplexStream>) {
+    let mut buf = [0; 8];
+    let read = stream.read(&mut buf);
+    let result = tokio::time::timeout(Duration::from_millis(300), read).await;
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn disabled() {
+    let (clients, _) = ClientTimeouts::new();
+    let server = ServerTimeouts::new(HttpServeTimeouts::default(), clients);
+    let client = ("127.0.0.1".to_string(), None);
+    assert!(ConnectionTimeouts::new(&server, client).is_none());
+  }
+
+  #[tokio::test]
+  async fn keep_alive_timeout() {
+    let (_client, server) = tokio::io::duplex(64);
+    let timeouts = timeouts(100, 0, 0);
+    let mut stream = TimeoutStream::new(server, timeouts.clone());
+
+    // no timeout while a request is in flight
+    timeouts.start_request(false, true);
+    assert_pending(&mut stream).await;
+
+    timeouts.finish_request();
+    let err = stream.read(&mut [0; 8]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+  }
+
+  #[tokio::test]
+  async fn headers_timeout() {
+    let (mut client, server) = tokio::io::duplex(64);
+    let mut stream = TimeoutStream::new(server, timeouts(0, 100, 0));
+
+    // an idle connection is kept without a keep-alive timeout
+    assert_pending(&mut stream).await;
+
+    client.write_all(b"GET").await.unwrap();
+    assert_eq!(stream.read(&mut [0; 8]).await.unwrap(), 3);
+    let err = stream.read(&mut [0; 8]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+    let mut response = vec![0; REQUEST_TIMEOUT_RESPONSE.len()];
+    client.read_exact(&mut response).await.unwrap();
+    assert_eq!(response, REQUEST_TIMEOUT_RESPONSE);
+  }
+
+  #[tokio::test]
+  async fn client_error_listener() {
+    let (mut client, server) = tokio::io::duplex(64);
+    let (clients, mut timed_out) = ClientTimeouts::new();
+    let timeouts = server_timeouts(100, 100, 0, clients.clone());
+    let mut stream = TimeoutStream::new(server, timeouts);
+
+    // the listener answers instead, so the client gets no 408
+    clients.set_respond(false);
+    client.write_all(b"GET").await.unwrap();
+    assert_eq!(stream.read(&mut [0; 8]).await.unwrap(), 3);
+    let err = stream.read(&mut [0; 8]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(
+      timed_out.try_recv().unwrap(),
+      ("127.0.0.1".to_string(), Some(4545))
+    );
+    drop(stream);
+    assert_eq!(client.read(&mut [0; 8]).await.unwrap(), 0);
+
+    // idle connections aren't reported
+    let (_client, server) = tokio::io::duplex(64);
+    let timeouts = server_timeouts(100, 100, 0, clients);
+    let mut stream = TimeoutStream::new(server, timeouts);
+    stream.read(&mut [0; 8]).await.unwrap_err();
+    assert!(timed_out.try_recv().is_err());
+  }
+
+  #[tokio::test]
+  async fn request_timeout() {
+    let (mut client, server) = tokio::io::duplex(64);
+    let timeouts = timeouts(0, 0, 100);
+    let mut stream = TimeoutStream::new(server, timeouts.clone());
+
+    // a request whose body was received is no longer subject to it
+    client.write_all(b"GET").await.unwrap();
+    assert_eq!(stream.read(&mut [0; 8]).await.unwrap(), 3);
+    timeouts.start_request(false, true);
+    assert_pending(&mut stream).await;
+    timeouts.finish_request();
+
+    client.write_all(b"POST").await.unwrap();
+    assert_eq!(stream.read(&mut [0; 8]).await.unwrap(), 4);
+    timeouts.start_request(false, false);
+    let err = stream.read(&mut [0; 8]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+  }
+
+  #[tokio::test]
+  async fn http2() {
+    let (mut client, server) = tokio::io::duplex(64);
+    let mut stream = TimeoutStream::new(server, timeouts(0, 100, 0));
+
+    // HTTP/2 connections are closed without a response
+    client.write_all(b"PRI * HTTP/2.0").await.unwrap();
+    assert_eq!(stream.read(&mut [0; 16]).await.unwrap(), 14);
+    let err = stream.read(&mut [0; 8]).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    drop(stream);
+    assert_eq!(client.read(&mut [0; 8]).await.unwrap(), 0);
+  }
+
+  #[tokio::test]
+  async fn upgraded() {
+    let (_client, server) = tokio::io::duplex(64);
+    let timeouts = timeouts(100, 100, 100);
+    let mut stream = TimeoutStream::new(server, timeouts.clone());
+    timeouts.start_request(true, true);
+    timeouts.finish_request();
+    assert_pending(&mut stream).await;
+  }
+}
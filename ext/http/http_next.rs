@@ -1,5 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 use crate::compressible::is_content_compressible;
+use crate::connection_timeouts::ClientTimeouts;
+use crate::connection_timeouts::ConnectionTimeouts;
+use crate::connection_timeouts::HttpServeTimeouts;
+use crate::connection_timeouts::ServerTimeouts;
+use crate::connection_timeouts::TimedOutClient;
+use crate::connection_timeouts::TimeoutStream;
 use crate::extract_network_stream;
 use crate::network_buffered_stream::NetworkStreamPrefixCheck;
 use crate::request_body::HttpRequestBody;
@@ -46,6 +52,7 @@ use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_RANGE;
 use http::header::CONTENT_TYPE;
 use http::HeaderMap;
+use hyper1::body::Body;
 use hyper1::body::Incoming;
 use hyper1::header::COOKIE;
 use hyper1::http::HeaderName;
@@ -65,6 +72,7 @@ use std::future::Future;
 use std::io;
 use std::pin::Pin;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
@@ -94,7 +102,7 @@ static USE_WRITEV: Lazy<bool> = Lazy::new(|| {
 ///
 /// That is, the connection preface starts with the string PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n). This sequence
 /// MUST be followed by a SETTINGS frame (Section 6.5), which MAY be empty.
-const HTTP2_PREFIX: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+pub const HTTP2_PREFIX: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
 /// ALPN negotation for "h2"
 const TLS_ALPN_HTTP_2: &[u8] = b"h2";
@@ -370,8 +378,8 @@ pub fn op_http_read_request_body(
   slab_id: SlabId,
 ) -> ResourceId {
   let mut http = slab_get(slab_id);
-  let incoming = http.take_body();
-  let body_resource = Rc::new(HttpRequestBody::new(incoming));
+  let body = http.take_body();
+  let body_resource = Rc::new(HttpRequestBody::new(body));
   state.resource_table.add_rc(body_resource)
 }
 
@@ -652,9 +660,21 @@ pub fn new_slab_future(
   request: Request,
   request_info: HttpConnectionProperties,
   tx: tokio::sync::mpsc::Sender<SlabId>,
+  timeouts: Option<ConnectionTimeouts>,
 ) -> SlabFuture<impl Future<Output = ()>> {
-  let index = slab_insert(request, request_info);
+  let upgrade = request.headers().contains_key(hyper1::header::UPGRADE);
+  let body_received = request.body().is_end_stream();
+  let index = slab_insert(request, request_info, timeouts.clone());
   let rx = slab_get(index).promise();
+  if let Some(timeouts) = timeouts {
+    // The connection is idle again once the response body was sent
+    timeouts.start_request(upgrade, body_received);
+    let body_promise = slab_get(index).body_promise();
+    spawn(async move {
+      body_promise.await;
+      timeouts.finish_request();
+    });
+  }
   SlabFuture(index, async move {
     if tx.send(index).await.is_ok() {
       // We only need to wait for completion if we aren't closed
@@ -721,27 +741,51 @@ async fn serve_http2_autodetect(
   }
 }
 
+async fn serve_https_handshaken(
+  io: impl HttpServeStream,
+  alpn_protocol: Option<Vec<u8>>,
+  svc: impl HttpService<Incoming, ResBody = ResponseBytes> + 'static,
+) -> Result<(), AnyError> {
+  // If the client specifically negotiates a protocol, we will use it. If not, we'll auto-detect
+  // based on the prefix bytes
+  let handshake = alpn_protocol.as_deref();
+  if handshake == Some(TLS_ALPN_HTTP_2) {
+    serve_http2_unconditional(io, svc).await
+  } else if handshake == Some(TLS_ALPN_HTTP_11) {
+    serve_http11_unconditional(io, svc).await
+  } else {
+    serve_http2_autodetect(io, svc).await
+  }
+}
+
 fn serve_https(
   mut io: TlsStream,
   request_info: HttpConnectionProperties,
   cancel: Rc<CancelHandle>,
   tx: tokio::sync::mpsc::Sender<SlabId>,
+  timeouts: Option<ServerTimeouts>,
 ) -> JoinHandle<Result<(), AnyError>> {
+  let timeouts = timeouts.and_then(|timeouts| {
+    let client = (
+      request_info.peer_address.to_string(),
+      request_info.peer_port,
+    );
+    ConnectionTimeouts::new(&timeouts, client)
+  });
+  let svc_timeouts = timeouts.clone();
   let svc = service_fn(move |req: Request| {
-    new_slab_future(req, request_info.clone(), tx.clone())
+    new_slab_future(req, request_info.clone(), tx.clone(), svc_timeouts.clone())
   });
   spawn(
-    async {
+    async move {
       io.handshake().await?;
-      // If the client specifically negotiates a protocol, we will use it. If not, we'll auto-detect
-      // based on the prefix bytes
-      let handshake = io.get_ref().1.alpn_protocol();
-      if handshake == Some(TLS_ALPN_HTTP_2) {
-        serve_http2_unconditional(io, svc).await
-      } else if handshake == Some(TLS_ALPN_HTTP_11) {
-        serve_http11_unconditional(io, svc).await
-      } else {
-        serve_http2_autodetect(io, svc).await
+      let alpn_protocol = io.get_ref().1.alpn_protocol().map(|p| p.to_vec());
+      match timeouts {
+        Some(timeouts) => {
+          let io = TimeoutStream::new(io, timeouts);
+          serve_https_handshaken(io, alpn_protocol, svc).await
+        }
+        None => serve_https_handshaken(io, alpn_protocol, svc).await,
       }
     }
     .try_or_cancel(cancel),
@@ -753,11 +797,26 @@ fn serve_http(
   request_info: HttpConnectionProperties,
   cancel: Rc<CancelHandle>,
   tx: tokio::sync::mpsc::Sender<SlabId>,
+  timeouts: Option<ServerTimeouts>,
 ) -> JoinHandle<Result<(), AnyError>> {
+  let timeouts = timeouts.and_then(|timeouts| {
+    let client = (
+      request_info.peer_address.to_string(),
+      request_info.peer_port,
+    );
+    ConnectionTimeouts::new(&timeouts, client)
+  });
+  let svc_timeouts = timeouts.clone();
   let svc = service_fn(move |req: Request| {
-    new_slab_future(req, request_info.clone(), tx.clone())
+    new_slab_future(req, request_info.clone(), tx.clone(), svc_timeouts.clone())
   });
-  spawn(serve_http2_autodetect(io, svc).try_or_cancel(cancel))
+  match timeouts {
+    Some(timeouts) => {
+      let io = TimeoutStream::new(io, timeouts);
+      spawn(serve_http2_autodetect(io, svc).try_or_cancel(cancel))
+    }
+    None => spawn(serve_http2_autodetect(io, svc).try_or_cancel(cancel)),
+  }
 }
 
 fn serve_http_on<HTTP>(
@@ -765,6 +824,7 @@ fn serve_http_on<HTTP>(
   listen_properties: &HttpListenProperties,
   cancel: Rc<CancelHandle>,
   tx: tokio::sync::mpsc::Sender<SlabId>,
+  timeouts: Option<ServerTimeouts>,
 ) -> JoinHandle<Result<(), AnyError>>
 where
   HTTP: HttpPropertyExtractor,
@@ -776,14 +836,14 @@ where
 
   match network_stream {
     NetworkStream::Tcp(conn) => {
-      serve_http(conn, connection_properties, cancel, tx)
+      serve_http(conn, connection_properties, cancel, tx, timeouts)
     }
     NetworkStream::Tls(conn) => {
-      serve_https(conn, connection_properties, cancel, tx)
+      serve_https(conn, connection_properties, cancel, tx, timeouts)
     }
     #[cfg(unix)]
     NetworkStream::Unix(conn) => {
-      serve_http(conn, connection_properties, cancel, tx)
+      serve_http(conn, connection_properties, cancel, tx, timeouts)
     }
   }
 }
@@ -793,6 +853,11 @@ struct HttpJoinHandle(
   // Cancel handle must live in a separate Rc to avoid keeping the outer join handle ref'd
   Rc<CancelHandle>,
   AsyncRefCell<tokio::sync::mpsc::Receiver<SlabId>>,
+  // The clients that took too long to send a request, when there are timeouts
+  Option<(
+    Arc<ClientTimeouts>,
+    AsyncRefCell<tokio::sync::mpsc::Receiver<TimedOutClient>>,
+  )>,
 );
 
 impl HttpJoinHandle {
@@ -822,6 +887,7 @@ impl Drop for HttpJoinHandle {
 pub fn op_http_serve<HTTP>(
   state: Rc<RefCell<OpState>>,
   listener_rid: ResourceId,
+  timeouts: Option<HttpServeTimeouts>,
 ) -> Result<(ResourceId, &'static str, String), AnyError>
 where
   HTTP: HttpPropertyExtractor,
@@ -831,11 +897,23 @@ where
 
   let listen_properties = HTTP::listen_properties_from_listener(&listener)?;
 
+  let (timeouts, client_timeouts) = match timeouts {
+    Some(timeouts) => {
+      let (clients, rx) = ClientTimeouts::new();
+      (
+        Some(ServerTimeouts::new(timeouts, clients.clone())),
+        Some((clients, AsyncRefCell::new(rx))),
+      )
+    }
+    None => (None, None),
+  };
+
   let (tx, rx) = tokio::sync::mpsc::channel(10);
   let resource: Rc<HttpJoinHandle> = Rc::new(HttpJoinHandle(
     AsyncRefCell::new(None),
     CancelHandle::new_rc(),
     AsyncRefCell::new(rx),
+    client_timeouts,
   ));
   let cancel_clone = resource.cancel_handle();
 
@@ -850,6 +928,7 @@ where
         &listen_properties_clone,
         cancel_clone.clone(),
         tx.clone(),
+        timeouts.clone(),
       );
     }
     #[allow(unreachable_code)]
@@ -886,6 +965,7 @@ where
     AsyncRefCell::new(None),
     CancelHandle::new_rc(),
     AsyncRefCell::new(rx),
+    None,
   ));
 
   let handle: JoinHandle<Result<(), deno_core::anyhow::Error>> =
@@ -894,6 +974,7 @@ where
       &listen_properties,
       resource.cancel_handle(),
       tx,
+      None,
     );
 
   // Set the handle after we start the future
@@ -986,6 +1067,49 @@ pub async fn op_http_wait(
   Ok(SlabId::MAX)
 }
 
+/// Sets whether the clients that take too long to send a request get a 408
+/// response, which node leaves to the `clientError` listeners of the server.
+#[op]
+pub fn op_http_serve_set_respond_on_timeout(
+  state: &mut OpState,
+  rid: ResourceId,
+  respond: bool,
+) -> Result<(), AnyError> {
+  let join_handle = state.resource_table.get::<HttpJoinHandle>(rid)?;
+  if let Some((clients, _)) = &join_handle.3 {
+    clients.set_respond(respond);
+  }
+  Ok(())
+}
+
+/// Waits for the next client that took too long to send a request. Returns
+/// `None` once the server is closed, or when it has no timeouts.
+#[op]
+pub async fn op_http_serve_next_timed_out_client(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<TimedOutClient>, AnyError> {
+  let join_handle = state
+    .borrow_mut()
+    .resource_table
+    .get::<HttpJoinHandle>(rid)?;
+  if join_handle.3.is_none() {
+    return Ok(None);
+  }
+
+  let cancel = join_handle.cancel_handle();
+  let next = async {
+    let mut recv = RcRef::map(&join_handle, |this| &this.3.as_ref().unwrap().1)
+      .borrow_mut()
+      .await;
+    recv.recv().await
+  }
+  .or_cancel(cancel)
+  .unwrap_or_else(|_| None)
+  .await;
+  Ok(next)
+}
+
 struct UpgradeStream {
   read: AsyncRefCell<tokio::io::ReadHalf<tokio::io::DuplexStream>>,
   write: AsyncRefCell<tokio::io::WriteHalf<tokio::io::DuplexStream>>,
@@ -75,6 +75,7 @@ use crate::reader_stream::ExternallyAbortableReaderStream;
 use crate::reader_stream::ShutdownHandle;
 
 pub mod compressible;
+mod connection_timeouts;
 mod http_next;
 mod network_buffered_stream;
 mod reader_stream;
@@ -109,6 +110,8 @@ deno_core::extension!(
     http_next::op_http_read_request_body,
     http_next::op_http_serve_on<HTTP>,
     http_next::op_http_serve<HTTP>,
+    http_next::op_http_serve_next_timed_out_client,
+    http_next::op_http_serve_set_respond_on_timeout,
     http_next::op_http_set_promise_complete,
     http_next::op_http_set_response_body_bytes,
     http_next::op_http_set_response_body_resource,
@@ -1,9 +1,12 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+use crate::connection_timeouts::ConnectionTimeouts;
 use bytes::Bytes;
 use deno_core::error::AnyError;
+use deno_core::futures::ready;
 use deno_core::futures::stream::Peekable;
 use deno_core::futures::Stream;
 use deno_core::futures::StreamExt;
+use deno_core::task::spawn;
 use deno_core::AsyncRefCell;
 use deno_core::AsyncResult;
 use deno_core::BufView;
@@ -13,11 +16,41 @@ use hyper1::body::Body;
 use hyper1::body::Incoming;
 use hyper1::body::SizeHint;
 use std::borrow::Cow;
+use std::future::poll_fn;
 use std::pin::Pin;
 use std::rc::Rc;
+use tokio::sync::mpsc;
+
+/// How many frames of a request body are received ahead of JavaScript reading
+/// them, when the connection has a request timeout.
+const RECEIVED_FRAMES: usize = 16;
+
+/// The body of a request as it is kept until JavaScript reads it.
+pub struct RequestBody(ReadFuture, SizeHint);
+
+impl RequestBody {
+  /// With a request timeout, the body is received in the background so that
+  /// the timeout stops applying once the client sent all of it, rather than
+  /// once JavaScript read it.
+  pub fn new(body: Incoming, timeouts: Option<ConnectionTimeouts>) -> Self {
+    let size_hint = body.size_hint();
+    let read = match timeouts {
+      Some(timeouts)
+        if timeouts.has_request_timeout() && !body.is_end_stream() =>
+      {
+        ReadFuture::Received(receive_body(body, timeouts))
+      }
+      _ => ReadFuture::Incoming(body),
+    };
+    Self(read, size_hint)
+  }
+}
 
 /// Converts a hyper incoming body stream into a stream of [`Bytes`] that we can use to read in V8.
-struct ReadFuture(Incoming);
+enum ReadFuture {
+  Incoming(Incoming),
+  Received(mpsc::Receiver<Result<Bytes, AnyError>>),
+}
 
 impl Stream for ReadFuture {
   type Item = Result<Bytes, AnyError>;
@@ -26,9 +59,20 @@ impl Stream for ReadFuture {
     self: Pin<&mut Self>,
     cx: &mut std::task::Context<'_>,
   ) -> std::task::Poll<Option<Self::Item>> {
-    let res = Pin::new(&mut self.get_mut().0).poll_frame(cx);
-    match res {
-      std::task::Poll::Ready(Some(Ok(frame))) => {
+    match self.get_mut() {
+      ReadFuture::Incoming(body) => poll_data(body, cx),
+      ReadFuture::Received(rx) => rx.poll_recv(cx),
+    }
+  }
+}
+
+fn poll_data(
+  body: &mut Incoming,
+  cx: &mut std::task::Context<'_>,
+) -> std::task::Poll<Option<Result<Bytes, AnyError>>> {
+  loop {
+    match ready!(Pin::new(&mut *body).poll_frame(cx)) {
+      Some(Ok(frame)) => {
         if let Ok(data) = frame.into_data() {
           // Ensure that we never yield an empty frame
           if !data.is_empty() {
@@ -36,19 +80,48 @@ impl Stream for ReadFuture {
           }
         }
       }
-      std::task::Poll::Ready(None) => return std::task::Poll::Ready(None),
-      _ => {}
+      Some(Err(err)) => return std::task::Poll::Ready(Some(Err(err.into()))),
+      None => return std::task::Poll::Ready(None),
     }
-    std::task::Poll::Pending
   }
 }
 
+/// Receives the body from hyper as it arrives, up to [`RECEIVED_FRAMES`]
+/// ahead of JavaScript, and tells the timeouts of the connection once the
+/// client sent all of it.
+fn receive_body(
+  mut body: Incoming,
+  timeouts: ConnectionTimeouts,
+) -> mpsc::Receiver<Result<Bytes, AnyError>> {
+  let (tx, rx) = mpsc::channel(RECEIVED_FRAMES);
+  spawn(async move {
+    loop {
+      match poll_fn(|cx| poll_data(&mut body, cx)).await {
+        Some(Ok(data)) => {
+          // JavaScript is no longer interested in the body
+          if tx.send(Ok(data)).await.is_err() {
+            return;
+          }
+        }
+        Some(Err(err)) => {
+          let _ = tx.send(Err(err)).await;
+          return;
+        }
+        None => {
+          timeouts.finish_request_body();
+          return;
+        }
+      }
+    }
+  });
+  rx
+}
+
 pub struct HttpRequestBody(AsyncRefCell<Peekable<ReadFuture>>, SizeHint);
 
 impl HttpRequestBody {
-  pub fn new(body: Incoming) -> Self {
-    let size_hint = body.size_hint();
-    Self(AsyncRefCell::new(ReadFuture(body).peekable()), size_hint)
+  pub fn new(body: RequestBody) -> Self {
+    Self(AsyncRefCell::new(body.0.peekable()), body.1)
   }
 
   async fn read(self: Rc<Self>, limit: usize) -> Result<BufView, AnyError> {
@@ -1,4 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+use crate::connection_timeouts::ConnectionTimeouts;
+use crate::request_body::RequestBody;
 use crate::request_properties::HttpConnectionProperties;
 use crate::response_body::CompletionHandle;
 use crate::response_body::ResponseBytes;
@@ -21,7 +23,7 @@ pub type SlabId = u32;
 pub struct HttpSlabRecord {
   request_info: HttpConnectionProperties,
   request_parts: Parts,
-  request_body: Option<Incoming>,
+  request_body: Option<RequestBody>,
   // The response may get taken before we tear this down
   response: Option<Response>,
   promise: CompletionHandle,
@@ -79,7 +81,7 @@ pub fn slab_get(index: SlabId) -> SlabEntry {
 #[allow(clippy::let_and_return)]
 fn slab_insert_raw(
   request_parts: Parts,
-  request_body: Option<Incoming>,
+  request_body: Option<RequestBody>,
   request_info: HttpConnectionProperties,
 ) -> SlabId {
   let index = SLAB.with(|slab| {
@@ -105,8 +107,10 @@ fn slab_insert_raw(
 pub fn slab_insert(
   request: Request,
   request_info: HttpConnectionProperties,
+  timeouts: Option<ConnectionTimeouts>,
 ) -> SlabId {
   let (request_parts, request_body) = request.into_parts();
+  let request_body = RequestBody::new(request_body, timeouts);
   slab_insert_raw(request_parts, Some(request_body), request_info)
 }
 
@@ -161,8 +165,8 @@ impl SlabEntry {
       .ok_or_else(|| AnyError::msg("upgrade unavailable"))
   }
 
-  /// Take the Hyper body from this entry.
-  pub fn take_body(&mut self) -> Incoming {
+  /// Take the body from this entry.
+  pub fn take_body(&mut self) -> RequestBody {
     self.self_mut().request_body.take().unwrap()
   }
 
@@ -253,7 +253,7 @@ class Stdin {
 
   setRaw(mode, options = {}) {
     const cbreak = !!(options.cbreak ?? false);
-    ops.op_stdin_set_raw(mode, cbreak);
+    ops.op_set_raw(this.rid, mode, cbreak);
   }
 }
 
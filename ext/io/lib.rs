@@ -167,12 +167,14 @@ pub struct Stdio {
 #[derive(Debug)]
 pub struct WriteOnlyResource<S> {
   stream: AsyncRefCell<S>,
+  cancel_handle: CancelHandle,
 }
 
 impl<S: 'static> From<S> for WriteOnlyResource<S> {
   fn from(stream: S) -> Self {
     Self {
       stream: stream.into(),
+      cancel_handle: Default::default(),
     }
   }
 }
@@ -185,9 +187,20 @@ where
     RcRef::map(self, |r| &r.stream).borrow_mut()
   }
 
+  pub fn cancel_handle(self: &Rc<Self>) -> RcRef<CancelHandle> {
+    RcRef::map(self, |r| &r.cancel_handle)
+  }
+
+  pub fn cancel_write_ops(&self) {
+    self.cancel_handle.cancel()
+  }
+
   async fn write(self: Rc<Self>, data: &[u8]) -> Result<usize, AnyError> {
     let mut stream = self.borrow_mut().await;
-    let nwritten = stream.write(data).await?;
+    let nwritten = stream
+      .write(data)
+      .try_or_cancel(self.cancel_handle())
+      .await?;
     Ok(nwritten)
   }
 
@@ -256,6 +269,10 @@ impl Resource for ChildStdinResource {
   fn shutdown(self: Rc<Self>) -> AsyncResult<()> {
     Box::pin(self.shutdown())
   }
+
+  fn close(self: Rc<Self>) {
+    self.cancel_write_ops();
+  }
 }
 
 pub type ChildStdoutResource = ReadOnlyResource<process::ChildStdout>;
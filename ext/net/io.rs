@@ -10,8 +10,10 @@ use deno_core::CancelTryFuture;
 use deno_core::RcRef;
 use deno_core::Resource;
 use socket2::SockRef;
+use socket2::TcpKeepalive;
 use std::borrow::Cow;
 use std::rc::Rc;
+use std::time::Duration;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWrite;
@@ -121,6 +123,33 @@ impl TcpStreamResource {
       .map_socket(Box::new(move |socket| Ok(socket.set_keepalive(keepalive)?)))
   }
 
+  /// Enables keepalive with the idle time before the first probe set to
+  /// `delay` seconds, and probes sent every second after that where the
+  /// platform allows it, like `uv_tcp_keepalive()` does. A delay of 0, or
+  /// disabling keepalive, leaves the timing as it was.
+  pub fn set_keepalive_with_delay(
+    self: Rc<Self>,
+    keepalive: bool,
+    delay: u32,
+  ) -> Result<(), AnyError> {
+    self.map_socket(Box::new(move |socket| {
+      if !keepalive || delay == 0 {
+        return Ok(socket.set_keepalive(keepalive)?);
+      }
+      let params =
+        TcpKeepalive::new().with_time(Duration::from_secs(delay.into()));
+      #[cfg(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "linux",
+        target_vendor = "apple",
+        windows,
+      ))]
+      let params = params.with_interval(Duration::from_secs(1));
+      Ok(socket.set_tcp_keepalive(&params)?)
+    }))
+  }
+
   #[allow(clippy::type_complexity)]
   fn map_socket(
     self: Rc<Self>,
@@ -107,12 +107,14 @@ deno_core::extension!(deno_net,
     ops::op_dns_resolve<P>,
     ops::op_set_nodelay,
     ops::op_set_keepalive,
+    ops::op_set_keepalive_with_delay,
 
     ops_tls::op_tls_start<P>,
     ops_tls::op_net_connect_tls<P>,
     ops_tls::op_net_listen_tls<P>,
     ops_tls::op_net_accept_tls,
     ops_tls::op_tls_handshake,
+    ops_tls::op_tls_ca_certificates,
 
     #[cfg(unix)] ops_unix::op_net_accept_unix,
     #[cfg(unix)] ops_unix::op_net_connect_unix<P>,
@@ -662,6 +662,18 @@ pub fn op_set_keepalive(
   resource.set_keepalive(keepalive)
 }
 
+#[op]
+pub fn op_set_keepalive_with_delay(
+  state: &mut OpState,
+  rid: ResourceId,
+  keepalive: bool,
+  delay: u32,
+) -> Result<(), AnyError> {
+  let resource: Rc<TcpStreamResource> =
+    state.resource_table.get::<TcpStreamResource>(rid)?;
+  resource.set_keepalive_with_delay(keepalive, delay)
+}
+
 fn rdata_to_return_record(
   ty: RecordType,
 ) -> impl Fn(&RData) -> Result<Option<DnsReturnRecord>, AnyError> {
@@ -1009,6 +1021,27 @@ mod tests {
     check_sockopt(String::from("127.0.0.1:4146"), set_keepalive, test_fn).await;
   }
 
+  #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+  async fn tcp_set_keepalive_with_delay() {
+    let set_keepalive = Box::new(|state: &mut OpState, rid| {
+      op_set_keepalive_with_delay::call(state, rid, true, 30).unwrap();
+    });
+    let test_fn = Box::new(|socket: SockRef| {
+      assert!(socket.keepalive().unwrap());
+      #[cfg(any(target_os = "linux", target_vendor = "apple"))]
+      assert_eq!(
+        socket.keepalive_time().unwrap(),
+        std::time::Duration::from_secs(30)
+      );
+      #[cfg(target_os = "linux")]
+      assert_eq!(
+        socket.keepalive_interval().unwrap(),
+        std::time::Duration::from_secs(1)
+      );
+    });
+    check_sockopt(String::from("127.0.0.1:4147"), set_keepalive, test_fn).await;
+  }
+
   #[allow(clippy::type_complexity)]
   async fn check_sockopt(
     addr: String,
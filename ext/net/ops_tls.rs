@@ -47,6 +47,7 @@ use deno_tls::rustls::PrivateKey;
 use deno_tls::rustls::ServerConfig;
 use deno_tls::rustls::ServerConnection;
 use deno_tls::rustls::ServerName;
+use deno_tls::CaCertificateKind;
 use io::Error;
 use io::Read;
 use io::Write;
@@ -1150,3 +1151,32 @@ pub async fn op_tls_handshake(
     .get::<TlsStreamResource>(rid)?;
   resource.handshake().await
 }
+
+/// Lists the certificates in the default CA store, PEM encoded. Backs
+/// `tls.getCACertificates()` in `node:tls`.
+#[op]
+pub fn op_tls_ca_certificates(
+  state: &mut OpState,
+  kind: String,
+) -> Result<Vec<String>, AnyError> {
+  let kind = match kind.as_str() {
+    "default" => CaCertificateKind::Default,
+    "bundled" => CaCertificateKind::Bundled,
+    "system" => CaCertificateKind::System,
+    "extra" => CaCertificateKind::Extra,
+    _ => {
+      return Err(type_error(format!("Invalid CA certificate type: {kind}")))
+    }
+  };
+  let provider = state
+    .borrow::<DefaultTlsOptions>()
+    .root_cert_store_provider
+    .clone();
+  let Some(provider) = provider else {
+    return Ok(vec![]);
+  };
+  Ok(match provider.get_ca_store()? {
+    Some(ca_store) => ca_store.to_pem(kind),
+    None => vec![],
+  })
+}
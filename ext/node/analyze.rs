@@ -9,6 +9,8 @@ use std::path::PathBuf;
 use deno_core::anyhow::Context;
 use deno_core::ModuleSpecifier;
 use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
 
 use deno_core::error::AnyError;
 
@@ -35,10 +37,24 @@ static NODE_GLOBALS: &[&str] = &[
   "performance",
 ];
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CjsAnalysis {
   pub exports: Vec<String>,
   pub reexports: Vec<String>,
+  /// Modules one of which is re-exported depending on a condition that is
+  /// only known at runtime, like in
+  /// `module.exports = flag ? require("./a") : require("./b")`.
+  #[serde(default)]
+  pub conditional_reexports: Vec<String>,
+  /// Modules one of whose members is re-exported, like in
+  /// `module.exports = require("./impl")[flag]`.
+  #[serde(default)]
+  pub member_reexports: Vec<String>,
+  /// Modules that are required into members of the exports, like in
+  /// `exports.dev = require("./dev")` or
+  /// `module.exports = { dev: require("./dev") }`.
+  #[serde(default)]
+  pub member_requires: Vec<String>,
 }
 
 /// Code analyzer for CJS and ESM files.
@@ -116,6 +132,7 @@ impl<TCjsEsmCodeAnalyzer: CjsEsmCodeAnalyzer>
   ) -> Result<String, AnyError> {
     let mut temp_var_count = 0;
     let mut handled_reexports: HashSet<String> = HashSet::default();
+    let mut handled_member_reexports: HashSet<String> = HashSet::default();
 
     let analysis = self.cjs_esm_code_analyzer.analyze_cjs(specifier, source)?;
 
@@ -131,58 +148,77 @@ impl<TCjsEsmCodeAnalyzer: CjsEsmCodeAnalyzer>
       .map(|s| s.to_string())
       .collect::<HashSet<_>>();
 
-    // (request, referrer)
     let mut reexports_to_handle = VecDeque::new();
-    for reexport in analysis.reexports {
-      reexports_to_handle.push_back((reexport, specifier.clone()));
-    }
+    queue_reexports(&mut reexports_to_handle, &analysis, specifier, false);
 
-    while let Some((reexport, referrer)) = reexports_to_handle.pop_front() {
-      if handled_reexports.contains(&reexport) {
+    while let Some(QueuedReexport {
+      request,
+      referrer,
+      kind,
+      is_candidate,
+    }) = reexports_to_handle.pop_front()
+    {
+      let handled = match kind {
+        ReexportKind::Module => &mut handled_reexports,
+        ReexportKind::Members => &mut handled_member_reexports,
+      };
+      if handled.contains(&request) {
         continue;
       }
 
-      handled_reexports.insert(reexport.to_string());
-
-      // First, resolve relate reexport specifier
-      let resolved_reexport = self.resolve(
-        &reexport,
-        &referrer,
-        // FIXME(bartlomieju): check if these conditions are okay, probably
-        // should be `deno-require`, because `deno` is already used in `esm_resolver.rs`
-        &["deno", "require", "default"],
-        NodeResolutionMode::Execution,
-        permissions,
-      )?;
-      // Second, read the source code from disk
-      let reexport_specifier =
-        ModuleSpecifier::from_file_path(&resolved_reexport).unwrap();
-      let reexport_file_text = self
-        .fs
-        .read_to_string(&resolved_reexport)
-        .map_err(AnyError::from)
-        .with_context(|| {
-          format!(
-            "Could not find '{}' ({}) referenced from {}",
-            reexport, reexport_specifier, referrer
-          )
-        })?;
-      {
-        let analysis = self
-          .cjs_esm_code_analyzer
-          .analyze_cjs(&reexport_specifier, &reexport_file_text)?;
-
-        for reexport in analysis.reexports {
-          reexports_to_handle.push_back((reexport, reexport_specifier.clone()));
+      handled.insert(request.to_string());
+
+      let loaded = self
+        .load_reexport(&request, &referrer, permissions)
+        .and_then(|(reexport_specifier, reexport_file_text)| {
+          let analysis = self
+            .cjs_esm_code_analyzer
+            .analyze_cjs(&reexport_specifier, &reexport_file_text)?;
+          Ok((reexport_specifier, analysis))
+        });
+      let (reexport_specifier, analysis) = match loaded {
+        Ok(loaded) => loaded,
+        // a candidate that can't be loaded is most likely never picked at
+        // runtime, like a module that is only part of the dev build
+        Err(err) if is_candidate => {
+          log::debug!(
+            "Skipping re-export candidate '{}' of {}: {:#}",
+            request,
+            referrer,
+            err
+          );
+          continue;
         }
-
-        all_exports.extend(
-          analysis
-            .exports
-            .into_iter()
-            .filter(|e| e.as_str() != "default"),
-        );
+        Err(err) => return Err(err),
+      };
+
+      if kind == ReexportKind::Members {
+        // the re-exported member is picked at runtime, so every module that
+        // is required into a member could be the one providing the exports
+        for request in analysis.member_requires {
+          reexports_to_handle.push_back(QueuedReexport {
+            request,
+            referrer: reexport_specifier.clone(),
+            kind: ReexportKind::Module,
+            is_candidate: true,
+          });
+        }
+        continue;
       }
+
+      queue_reexports(
+        &mut reexports_to_handle,
+        &analysis,
+        &reexport_specifier,
+        is_candidate,
+      );
+
+      all_exports.extend(
+        analysis
+          .exports
+          .into_iter()
+          .filter(|e| e.as_str() != "default"),
+      );
     }
 
     source.push(format!(
@@ -214,6 +250,41 @@ impl<TCjsEsmCodeAnalyzer: CjsEsmCodeAnalyzer>
     Ok(translated_source)
   }
 
+  /// Resolves a re-exported module and reads its source code.
+  fn load_reexport(
+    &self,
+    reexport: &str,
+    referrer: &ModuleSpecifier,
+    permissions: &dyn NodePermissions,
+  ) -> Result<(ModuleSpecifier, String), AnyError> {
+    // First, resolve relate reexport specifier
+    let resolved_reexport = self.resolve(
+      reexport,
+      referrer,
+      // FIXME(bartlomieju): check if these conditions are okay, probably
+      // should be `deno-require`, because `deno` is already used in `esm_resolver.rs`
+      &self
+        .node_resolver
+        .conditions(&["deno", "require", "default"]),
+      NodeResolutionMode::Execution,
+      permissions,
+    )?;
+    // Second, read the source code from disk
+    let reexport_specifier =
+      ModuleSpecifier::from_file_path(&resolved_reexport).unwrap();
+    let reexport_file_text = self
+      .fs
+      .read_to_string(&resolved_reexport)
+      .map_err(AnyError::from)
+      .with_context(|| {
+        format!(
+          "Could not find '{}' ({}) referenced from {}",
+          reexport, reexport_specifier, referrer
+        )
+      })?;
+    Ok((reexport_specifier, reexport_file_text))
+  }
+
   fn resolve(
     &self,
     specifier: &str,
@@ -328,6 +399,61 @@ impl<TCjsEsmCodeAnalyzer: CjsEsmCodeAnalyzer>
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReexportKind {
+  /// All the exports of the module are re-exported.
+  Module,
+  /// One of the members of the module's exports is re-exported, but which
+  /// one is only known at runtime.
+  Members,
+}
+
+struct QueuedReexport {
+  request: String,
+  referrer: ModuleSpecifier,
+  kind: ReexportKind,
+  /// Whether the module is only one of the modules that could be re-exported
+  /// at runtime, in which case failing to load it isn't an error.
+  is_candidate: bool,
+}
+
+/// Queues the re-exports of an analyzed module.
+///
+/// Named exports of an ES module are fixed before it's evaluated, so when the
+/// re-exported module is picked at runtime, every candidate is queued rather
+/// than the one used at runtime.
+fn queue_reexports(
+  queue: &mut VecDeque<QueuedReexport>,
+  analysis: &CjsAnalysis,
+  referrer: &ModuleSpecifier,
+  is_candidate: bool,
+) {
+  let reexports = analysis
+    .reexports
+    .iter()
+    .map(|request| (request, ReexportKind::Module, is_candidate))
+    .chain(
+      analysis
+        .conditional_reexports
+        .iter()
+        .map(|request| (request, ReexportKind::Module, true)),
+    )
+    .chain(
+      analysis
+        .member_reexports
+        .iter()
+        .map(|request| (request, ReexportKind::Members, true)),
+    );
+  for (request, kind, is_candidate) in reexports {
+    queue.push_back(QueuedReexport {
+      request: request.clone(),
+      referrer: referrer.clone(),
+      kind,
+      is_candidate,
+    });
+  }
+}
+
 fn esm_code_from_top_level_decls(
   file_text: &str,
   top_level_decls: &HashSet<String>,
@@ -7,7 +7,6 @@ use std::rc::Rc;
 
 use deno_core::error::AnyError;
 use deno_core::located_script_name;
-use deno_core::op;
 use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_core::JsRuntime;
@@ -24,34 +23,58 @@ use once_cell::sync::Lazy;
 
 pub mod analyze;
 pub mod errors;
+mod node_options;
 mod ops;
 mod package_json;
 mod path;
 mod polyfill;
 mod resolution;
 
+pub use node_options::NodeOptions;
 pub use package_json::PackageJson;
 pub use path::PathClean;
 pub use polyfill::is_builtin_node_module;
+pub use polyfill::requires_node_scheme;
 pub use polyfill::NodeModulePolyfill;
 pub use polyfill::SUPPORTED_BUILTIN_NODE_MODULES;
 pub use resolution::NodeModuleKind;
 pub use resolution::NodeResolution;
 pub use resolution::NodeResolutionMode;
 pub use resolution::NodeResolver;
+pub use resolution::EMPTY_MODULE_SPECIFIER;
 
 pub trait NodePermissions {
+  fn check_net(
+    &mut self,
+    host: (&str, Option<u16>),
+    api_name: &str,
+  ) -> Result<(), AnyError>;
   fn check_net_url(
     &mut self,
     url: &Url,
     api_name: &str,
   ) -> Result<(), AnyError>;
   fn check_read(&self, path: &Path) -> Result<(), AnyError>;
+  fn check_write(
+    &mut self,
+    path: &Path,
+    api_name: &str,
+  ) -> Result<(), AnyError>;
+  fn check_env(&mut self, var: &str) -> Result<(), AnyError>;
+  fn check_run(&mut self, cmd: &str, api_name: &str) -> Result<(), AnyError>;
+  fn check_sys(&mut self, kind: &str, api_name: &str) -> Result<(), AnyError>;
 }
 
 pub(crate) struct AllowAllNodePermissions;
 
 impl NodePermissions for AllowAllNodePermissions {
+  fn check_net(
+    &mut self,
+    _host: (&str, Option<u16>),
+    _api_name: &str,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
   fn check_net_url(
     &mut self,
     _url: &Url,
@@ -62,6 +85,26 @@ impl NodePermissions for AllowAllNodePermissions {
   fn check_read(&self, _path: &Path) -> Result<(), AnyError> {
     Ok(())
   }
+  fn check_write(
+    &mut self,
+    _path: &Path,
+    _api_name: &str,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
+  fn check_env(&mut self, _var: &str) -> Result<(), AnyError> {
+    Ok(())
+  }
+  fn check_run(&mut self, _cmd: &str, _api_name: &str) -> Result<(), AnyError> {
+    Ok(())
+  }
+  fn check_sys(
+    &mut self,
+    _kind: &str,
+    _api_name: &str,
+  ) -> Result<(), AnyError> {
+    Ok(())
+  }
 }
 
 #[allow(clippy::disallowed_types)]
@@ -124,23 +167,21 @@ pub static NODE_ENV_VAR_ALLOWLIST: Lazy<HashSet<String>> = Lazy::new(|| {
   let mut set = HashSet::new();
   set.insert("NODE_DEBUG".to_string());
   set.insert("NODE_OPTIONS".to_string());
+  set.insert("NODE_UNIQUE_ID".to_string());
+  set.insert("NODE_CLUSTER_SCHED_POLICY".to_string());
   set
 });
 
-#[op]
-fn op_node_build_os() -> String {
-  std::env::var("TARGET")
-    .unwrap()
-    .split('-')
-    .nth(2)
-    .unwrap()
-    .to_string()
-}
-
 deno_core::extension!(deno_node,
-  deps = [ deno_io, deno_fs ],
+  deps = [ deno_io, deno_fs, deno_net, deno_web ],
   parameters = [P: NodePermissions],
   ops = [
+    ops::async_hooks::op_node_async_context_enable,
+    ops::cluster::op_node_cluster_listen,
+    ops::cluster::op_node_cluster_accept,
+    ops::cluster::op_node_cluster_connect<P>,
+    ops::cluster::op_node_cluster_send,
+    ops::cluster::op_node_cluster_recv,
     ops::crypto::op_node_create_decipheriv,
     ops::crypto::op_node_cipheriv_encrypt,
     ops::crypto::op_node_cipheriv_final,
@@ -192,6 +233,9 @@ deno_core::extension!(deno_node,
     ops::crypto::op_node_ecdh_generate_keys,
     ops::crypto::op_node_ecdh_compute_secret,
     ops::crypto::op_node_ecdh_compute_public_key,
+    ops::crypto::op_node_ecdh_convert_key,
+    ops::crypto::keys::op_node_key_object_from_der,
+    ops::crypto::keys::op_node_key_object_to_der,
     ops::crypto::x509::op_node_x509_parse,
     ops::crypto::x509::op_node_x509_ca,
     ops::crypto::x509::op_node_x509_check_email,
@@ -205,12 +249,29 @@ deno_core::extension!(deno_node,
     ops::crypto::x509::op_node_x509_get_serial_number,
     ops::crypto::x509::op_node_x509_key_usage,
     ops::winerror::op_node_sys_to_uv_error,
+    ops::winerror::op_node_uv_to_sys_error,
     ops::v8::op_v8_cached_data_version_tag,
     ops::v8::op_v8_get_heap_statistics,
+    ops::v8::op_v8_get_heap_space_statistics,
+    ops::v8::op_v8_take_heap_snapshot,
+    ops::buffer::op_node_buffer_base64_decode,
+    ops::buffer::op_node_buffer_base64_encode,
+    ops::buffer::op_node_buffer_hex_decode,
+    ops::buffer::op_node_buffer_hex_encode,
+    ops::buffer::op_node_buffer_latin1_decode,
+    ops::buffer::op_node_buffer_latin1_encode,
     ops::idna::op_node_idna_domain_to_ascii,
     ops::idna::op_node_idna_domain_to_unicode,
     ops::idna::op_node_idna_punycode_decode,
     ops::idna::op_node_idna_punycode_encode,
+    ops::idna::op_node_idna_punycode_to_unicode,
+    ops::perf_hooks::op_node_event_loop_utilization,
+    ops::perf_hooks::op_node_histogram_new,
+    ops::perf_hooks::op_node_histogram_record,
+    ops::perf_hooks::op_node_histogram_reset,
+    ops::perf_hooks::op_node_histogram_stats,
+    ops::perf_hooks::op_node_histogram_percentile,
+    ops::perf_hooks::op_node_histogram_percentiles,
     ops::zlib::op_zlib_new,
     ops::zlib::op_zlib_close,
     ops::zlib::op_zlib_close_if_pending,
@@ -218,8 +279,21 @@ deno_core::extension!(deno_node,
     ops::zlib::op_zlib_write_async,
     ops::zlib::op_zlib_init,
     ops::zlib::op_zlib_reset,
+    ops::dns::op_node_getaddrinfo,
+    ops::fs::op_node_statfs<P>,
+    ops::fs::op_node_statfs_sync<P>,
     ops::http::op_node_http_request<P>,
-    op_node_build_os,
+    ops::os::op_node_os_platform,
+    ops::os::op_node_os_arch,
+    ops::os::op_node_os_machine,
+    ops::permissions::op_node_check_net<P>,
+    ops::permissions::op_node_check_write<P>,
+    ops::permissions::op_node_check_env<P>,
+    ops::permissions::op_node_check_run<P>,
+    ops::permissions::op_node_check_sys<P>,
+    ops::querystring::op_node_querystring_parse,
+    ops::querystring::op_node_querystring_stringify,
+    ops::tty::op_node_tty_color_depth<P>,
     ops::require::op_require_init_paths,
     ops::require::op_require_node_module_paths<P>,
     ops::require::op_require_proxy_path,
@@ -237,6 +311,7 @@ deno_core::extension!(deno_node,
     ops::require::op_require_path_basename,
     ops::require::op_require_read_file<P>,
     ops::require::op_require_as_file_path,
+    ops::require::op_require_clear_cache,
     ops::require::op_require_resolve_exports<P>,
     ops::require::op_require_read_closest_package_json<P>,
     ops::require::op_require_read_package_scope<P>,
@@ -281,6 +356,7 @@ deno_core::extension!(deno_node,
     "_fs/_fs_rm.ts",
     "_fs/_fs_rmdir.ts",
     "_fs/_fs_stat.ts",
+    "_fs/_fs_statfs.ts",
     "_fs/_fs_symlink.ts",
     "_fs/_fs_truncate.ts",
     "_fs/_fs_unlink.ts",
@@ -423,11 +499,14 @@ deno_core::extension!(deno_node,
     "internal/streams/writable.mjs",
     "internal/test/binding.ts",
     "internal/timers.mjs",
+    "internal/tty.ts",
     "internal/url.ts",
     "internal/util.mjs",
     "internal/util/comparisons.ts",
     "internal/util/debuglog.ts",
     "internal/util/inspect.mjs",
+    "internal/util/parse_args/parse_args.mjs",
+    "internal/util/parse_args/utils.mjs",
     "internal/util/types.ts",
     "internal/validators.mjs",
     "net.ts",
@@ -456,6 +535,7 @@ deno_core::extension!(deno_node,
     "stream/web.ts",
     "string_decoder.ts",
     "sys.ts",
+    "testing.ts",
     "timers.ts",
     "timers/promises.ts",
     "tls.ts",
@@ -472,6 +552,7 @@ deno_core::extension!(deno_node,
   options = {
     maybe_npm_resolver: Option<NpmResolverRc>,
     fs: deno_fs::FileSystemRc,
+    conditions: Vec<String>,
   },
   state = |state, options| {
     let fs = options.fs;
@@ -481,6 +562,7 @@ deno_core::extension!(deno_node,
       state.put(Rc::new(NodeResolver::new(
         fs,
         npm_resolver,
+        options.conditions,
       )))
     }
   },
@@ -490,23 +572,26 @@ pub fn initialize_runtime(
   js_runtime: &mut JsRuntime,
   uses_local_node_modules_dir: bool,
   maybe_binary_command_name: Option<&str>,
+  node_options: &NodeOptions,
 ) -> Result<(), AnyError> {
   let argv0 = if let Some(binary_command_name) = maybe_binary_command_name {
     serde_json::to_string(binary_command_name)?
   } else {
     "undefined".to_string()
   };
+  let node_options = serde_json::to_string(node_options)?;
   let source_code = format!(
-    r#"(function loadBuiltinNodeModules(nodeGlobalThisName, usesLocalNodeModulesDir, argv0) {{
+    r#"(function loadBuiltinNodeModules(nodeGlobalThisName, usesLocalNodeModulesDir, argv0, nodeOptions) {{
       Deno[Deno.internal].node.initialize(
         nodeGlobalThisName,
         usesLocalNodeModulesDir,
-        argv0
+        argv0,
+        nodeOptions
       );
       // Make the nodeGlobalThisName unconfigurable here.
       Object.defineProperty(globalThis, nodeGlobalThisName, {{ configurable: false }});
-    }})('{}', {}, {});"#,
-    NODE_GLOBAL_THIS_NAME, uses_local_node_modules_dir, argv0
+    }})('{}', {}, {}, {});"#,
+    NODE_GLOBAL_THIS_NAME, uses_local_node_modules_dir, argv0, node_options
   );
 
   js_runtime.execute_script(located_script_name!(), source_code.into())?;
@@ -0,0 +1,206 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use serde::Serialize;
+
+/// The options from the `NODE_OPTIONS` environment variable that are
+/// understood, so configurations written for Node keep working.
+///
+/// Options that are not listed here, or whose value is invalid, are ignored
+/// with a warning rather than rejected.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeOptions {
+  /// `--max-old-space-size`, in megabytes.
+  pub max_old_space_size: Option<usize>,
+  /// `--require` and `-r`, modules that are required before the main one.
+  pub require: Vec<String>,
+  /// `--no-warnings`. Process warnings aren't printed in the first place,
+  /// so this is only reported back to user code.
+  pub no_warnings: bool,
+  /// `--enable-source-maps`. Stack traces are always source mapped, so this
+  /// is likewise only reported back.
+  pub enable_source_maps: bool,
+  /// What was ignored, to be reported to the user.
+  #[serde(skip)]
+  pub warnings: Vec<String>,
+}
+
+impl NodeOptions {
+  pub fn from_env() -> Self {
+    match std::env::var("NODE_OPTIONS") {
+      Ok(value) => Self::parse(&value),
+      Err(_) => Self::default(),
+    }
+  }
+
+  pub fn parse(value: &str) -> Self {
+    let mut options = Self::default();
+    let args = match split_args(value) {
+      Some(args) => args,
+      None => {
+        options.warnings.push(
+          "Ignoring NODE_OPTIONS with an unterminated string".to_string(),
+        );
+        return options;
+      }
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let (name, inline_value) = match arg.split_once('=') {
+        Some((name, value)) => (name.to_string(), Some(value.to_string())),
+        None => (arg.clone(), None),
+      };
+      // node treats underscores in option names like dashes
+      let name = if name.starts_with("--") {
+        name.replace('_', "-")
+      } else {
+        name
+      };
+      let takes_value =
+        matches!(name.as_str(), "--max-old-space-size" | "--require" | "-r");
+      let value = match inline_value {
+        Some(value) => Some(value),
+        None if takes_value => args.next(),
+        None => None,
+      };
+      let warning = match (name.as_str(), value) {
+        ("--max-old-space-size", Some(value)) => match value.parse() {
+          Ok(size) => {
+            options.max_old_space_size = Some(size);
+            continue;
+          }
+          Err(_) => format!("invalid --max-old-space-size={value}"),
+        },
+        ("--require" | "-r", Some(value)) => {
+          options.require.push(value);
+          continue;
+        }
+        ("--no-warnings", None) => {
+          options.no_warnings = true;
+          continue;
+        }
+        ("--enable-source-maps", None) => {
+          options.enable_source_maps = true;
+          continue;
+        }
+        (name, _) if takes_value => format!("{name} without a value"),
+        _ => format!("unsupported option {arg}"),
+      };
+      options
+        .warnings
+        .push(format!("Ignoring {warning} in NODE_OPTIONS"));
+    }
+    options
+  }
+
+  /// The heap limit from `--max-old-space-size`, in bytes.
+  pub fn max_heap_size(&self) -> Option<usize> {
+    self.max_old_space_size.map(|size| size * 1024 * 1024)
+  }
+}
+
+/// Splits the value of `NODE_OPTIONS` on spaces, the way node does.
+///
+/// Double quotes group an argument and a backslash escapes the next
+/// character within them. Returns `None` if a string isn't terminated.
+fn split_args(value: &str) -> Option<Vec<String>> {
+  let mut args = Vec::new();
+  let mut current = String::new();
+  let mut in_arg = false;
+  let mut in_quotes = false;
+  let mut chars = value.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' if in_quotes => {
+        if let Some(c) = chars.next() {
+          current.push(c);
+        }
+      }
+      '"' => {
+        in_quotes = !in_quotes;
+        in_arg = true;
+      }
+      ' ' if !in_quotes => {
+        if in_arg {
+          args.push(std::mem::take(&mut current));
+          in_arg = false;
+        }
+      }
+      c => {
+        current.push(c);
+        in_arg = true;
+      }
+    }
+  }
+  if in_quotes {
+    return None;
+  }
+  if in_arg {
+    args.push(current);
+  }
+  Some(args)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_supported_options() {
+    let options = NodeOptions::parse(
+      "--max-old-space-size=4096 -r ./a.js --require=\"./b c.js\" \
+       --no-warnings --enable-source-maps",
+    );
+    assert_eq!(
+      options,
+      NodeOptions {
+        max_old_space_size: Some(4096),
+        require: vec!["./a.js".to_string(), "./b c.js".to_string()],
+        no_warnings: true,
+        enable_source_maps: true,
+        warnings: vec![],
+      }
+    );
+    assert_eq!(options.max_heap_size(), Some(4096 * 1024 * 1024));
+  }
+
+  #[test]
+  fn underscores_and_separate_values() {
+    let options =
+      NodeOptions::parse("  --max_old_space_size 512   --require  x ");
+    assert_eq!(options.max_old_space_size, Some(512));
+    assert_eq!(options.require, vec!["x".to_string()]);
+  }
+
+  #[test]
+  fn invalid_options() {
+    let options = NodeOptions::parse("-r ./a.js --require \"./b.js");
+    assert!(options.require.is_empty());
+    assert_eq!(options.warnings.len(), 1);
+
+    // the valid options are still applied
+    let options = NodeOptions::parse(
+      "--inspect --max-old-space-size=lots -r ./a.js --no-warnings=1 --require",
+    );
+    assert_eq!(options.max_old_space_size, None);
+    assert_eq!(options.require, vec!["./a.js".to_string()]);
+    assert!(!options.no_warnings);
+    assert_eq!(
+      options.warnings,
+      vec![
+        "Ignoring unsupported option --inspect in NODE_OPTIONS",
+        "Ignoring invalid --max-old-space-size=lots in NODE_OPTIONS",
+        "Ignoring unsupported option --no-warnings=1 in NODE_OPTIONS",
+        "Ignoring --require without a value in NODE_OPTIONS",
+      ]
+    );
+  }
+
+  #[test]
+  fn split_args_escapes() {
+    assert_eq!(
+      split_args(r#"a "b \"c\" d" "" e"#).unwrap(),
+      vec!["a", "b \"c\" d", "", "e"]
+    );
+  }
+}
@@ -0,0 +1,106 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Carries the frames of `AsyncLocalStorage` across promise reactions with a
+//! native promise hook, so that propagating the context doesn't call into JS
+//! for every promise. The current frame is the async context of ext/web.
+
+use deno_core::op;
+use deno_core::v8;
+use deno_web::get_async_context;
+use deno_web::set_async_context;
+
+/// Name of the private symbol used to attach an async context frame to a
+/// promise. Using a V8 private symbol (instead of a regular JS symbol) keeps
+/// the frame invisible to user code, so it survives frozen promises, proxies
+/// and reflection-based cloning done by some npm frameworks.
+const ASYNC_CONTEXT_KEY: &str = "node:async_hooks:asyncContext";
+
+/// The async contexts that were current before the reactions that are
+/// running, to restore them once they are done.
+#[derive(Default)]
+struct AsyncContextStack(Vec<v8::Global<v8::Value>>);
+
+fn async_context_key<'a>(
+  scope: &mut v8::HandleScope<'a>,
+) -> v8::Local<'a, v8::Private> {
+  let name = v8::String::new(scope, ASYNC_CONTEXT_KEY).unwrap();
+  v8::Private::for_api(scope, Some(name))
+}
+
+fn get_frame<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  promise: v8::Local<v8::Promise>,
+) -> Option<v8::Local<'a, v8::Value>> {
+  let key = async_context_key(scope);
+  promise
+    .get_private(scope, key)
+    .filter(|frame| !frame.is_undefined())
+}
+
+extern "C" fn promise_hook(
+  hook_type: v8::PromiseHookType,
+  promise: v8::Local<v8::Promise>,
+  parent: v8::Local<v8::Value>,
+) {
+  let scope = &mut unsafe { v8::CallbackScope::new(promise) };
+  let key = async_context_key(scope);
+  match hook_type {
+    v8::PromiseHookType::Init => {
+      let current = get_async_context(scope);
+      let frame = if !current.is_undefined() {
+        Some(current)
+      } else {
+        // Promises created by V8 internally (e.g. the throwaway promises of
+        // `await` or `.then()` chains) are reported with their parent.
+        // Without a frame of our own, inherit the one of the parent so that
+        // the context survives across awaits.
+        v8::Local::<v8::Promise>::try_from(parent)
+          .ok()
+          .and_then(|parent| get_frame(scope, parent))
+      };
+      if let Some(frame) = frame {
+        promise.set_private(scope, key, frame);
+      }
+    }
+    v8::PromiseHookType::Before => {
+      // The frame isn't detached from the promise afterwards: it is
+      // collected along with the promise, and handlers of a rejection still
+      // need it.
+      let frame = get_frame(scope, promise)
+        .unwrap_or_else(|| v8::undefined(scope).into());
+      let previous = set_async_context(scope, frame);
+      let previous = v8::Global::new(scope, previous);
+      if scope.get_slot::<AsyncContextStack>().is_none() {
+        scope.set_slot(AsyncContextStack::default());
+      }
+      let stack = scope.get_slot_mut::<AsyncContextStack>().unwrap();
+      stack.0.push(previous);
+    }
+    v8::PromiseHookType::After => {
+      let previous = scope
+        .get_slot_mut::<AsyncContextStack>()
+        .and_then(|stack| stack.0.pop());
+      if let Some(previous) = previous {
+        let previous = v8::Local::new(scope, previous);
+        set_async_context(scope, previous);
+      }
+    }
+    v8::PromiseHookType::Resolve => {
+      let current = get_async_context(scope);
+      if !current.is_undefined()
+        && matches!(promise.state(), v8::PromiseState::Rejected)
+        && get_frame(scope, promise).is_none()
+      {
+        promise.set_private(scope, key, current);
+      }
+    }
+  }
+}
+
+/// Sets the promise hook that carries the frames across promise reactions.
+/// It runs for every promise once it is set, so it is only set once a
+/// context other than the root one is entered.
+#[op(v8)]
+pub fn op_node_async_context_enable(scope: &mut v8::HandleScope) {
+  scope.set_promise_hook(promise_hook);
+}
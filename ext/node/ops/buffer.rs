@@ -0,0 +1,135 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Conversions between buffers and the binary-to-text encodings of node's
+//! `Buffer`. "Encoding" turns bytes into a string, like `buf.toString()`,
+//! and "decoding" turns a string back into bytes, like `Buffer.from()`.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::ZeroCopyBuf;
+
+#[op]
+pub fn op_node_buffer_base64_encode(buf: &[u8], url: bool) -> String {
+  if url {
+    base64_simd::URL_SAFE_NO_PAD.encode_to_string(buf)
+  } else {
+    base64_simd::STANDARD.encode_to_string(buf)
+  }
+}
+
+/// Decodes both the standard and the URL safe alphabets, like node does for
+/// the "base64" and "base64url" encodings alike.
+#[op]
+pub fn op_node_buffer_base64_decode(input: String) -> ZeroCopyBuf {
+  base64_decode(&input).into()
+}
+
+#[op]
+pub fn op_node_buffer_hex_encode(buf: &[u8]) -> String {
+  hex::encode(buf)
+}
+
+#[op]
+pub fn op_node_buffer_hex_decode(input: String) -> ZeroCopyBuf {
+  hex_decode(&input).into()
+}
+
+/// Every byte becomes the code point of the same value.
+#[op(v8)]
+pub fn op_node_buffer_latin1_encode<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  buf: &[u8],
+) -> Result<serde_v8::Value<'a>, AnyError> {
+  match v8::String::new_from_one_byte(scope, buf, v8::NewStringType::Normal) {
+    Some(text) => Ok(serde_v8::from_v8(scope, text.into())?),
+    None => Err(type_error("buffer exceeds maximum length")),
+  }
+}
+
+/// Keeps the low byte of every UTF-16 code unit, like node does, so
+/// characters outside of latin1 are truncated instead of being rejected and
+/// the halves of a surrogate pair become a byte each.
+#[op(v8)]
+pub fn op_node_buffer_latin1_decode<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  input: serde_v8::Value<'a>,
+) -> Result<ZeroCopyBuf, AnyError> {
+  let input = v8::Local::<v8::String>::try_from(input.v8_value)
+    .map_err(|_| type_error("Expected a string"))?;
+  let mut bytes = vec![0; input.length()];
+  input.write_one_byte(
+    scope,
+    &mut bytes,
+    0,
+    v8::WriteOptions::NO_NULL_TERMINATION,
+  );
+  Ok(bytes.into())
+}
+
+fn base64_decode(input: &str) -> Vec<u8> {
+  // node stops at the first padding character and skips the ones that
+  // aren't part of either alphabet, like whitespace
+  let mut bytes = input
+    .bytes()
+    .take_while(|&byte| byte != b'=')
+    .filter_map(|byte| match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' => Some(byte),
+      b'-' => Some(b'+'),
+      b'_' => Some(b'/'),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  // a dangling character doesn't make up a whole byte
+  if bytes.len() % 4 == 1 {
+    bytes.pop();
+  }
+  let len = base64_simd::forgiving_decode_inplace(&mut bytes)
+    .map(|decoded| decoded.len())
+    .unwrap_or(0);
+  bytes.truncate(len);
+  bytes
+}
+
+/// Decodes pairs of hex digits up to the first invalid one, which is where
+/// node stops writing.
+fn hex_decode(input: &str) -> Vec<u8> {
+  fn digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|digit| digit as u8)
+  }
+
+  input
+    .as_bytes()
+    .chunks_exact(2)
+    .map_while(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn base64() {
+    assert_eq!(base64_decode("aGVsbG8="), b"hello");
+    assert_eq!(base64_decode("aGVsbG8"), b"hello");
+    assert_eq!(base64_decode(" aGVs\nbG8 "), b"hello");
+    assert_eq!(base64_decode("aGVsbG8=aGVsbG8="), b"hello");
+    assert_eq!(base64_decode("-_-_"), base64_decode("+/+/"));
+    assert_eq!(base64_decode("aGVsbG8gd"), b"hello ");
+    assert_eq!(base64_decode("a"), b"");
+    assert_eq!(base64_decode(""), b"");
+  }
+
+  #[test]
+  fn hex() {
+    assert_eq!(hex_decode("abcdef01"), [0xab, 0xcd, 0xef, 0x01]);
+    assert_eq!(hex_decode("ABCD"), [0xab, 0xcd]);
+    assert_eq!(hex_decode("abcdxx"), [0xab, 0xcd]);
+    assert_eq!(hex_decode("cdxxab"), [0xcd]);
+    assert_eq!(hex_decode("xxabcd"), b"");
+    assert_eq!(hex_decode("abc"), [0xab]);
+  }
+}
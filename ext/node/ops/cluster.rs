@@ -0,0 +1,602 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The channel between the primary of `node:cluster` and its workers. It
+//! carries the internal messages of the cluster and the ones of
+//! `worker.send()`, along with the connections that the primary accepts for
+//! the servers of the workers. The socket of such a connection is passed to
+//! the worker that handles it (`SCM_RIGHTS`), so it doesn't go through the
+//! primary afterwards.
+//!
+//! The primary listens on a Unix socket in a directory that only the current
+//! user has access to, and the workers get its path from
+//! `NODE_CLUSTER_CHANNEL`. Each frame is the length of the message, whether a
+//! socket comes with it, and the message itself.
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::OpState;
+use deno_core::ResourceId;
+use deno_net::ops::IpAddr;
+use serde::Serialize;
+
+use crate::NodePermissions;
+
+/// The variable the path of the channel of the primary is passed to the
+/// workers in.
+const CHANNEL_ENV_VAR: &str = "NODE_CLUSTER_CHANNEL";
+
+#[derive(Serialize)]
+pub struct ClusterMessage {
+  message: String,
+  /// The connection that was passed along with the message.
+  conn: Option<(ResourceId, IpAddr, IpAddr)>,
+}
+
+/// Creates the channel of a primary, returning its resource and its path.
+#[op]
+pub fn op_node_cluster_listen(
+  state: &mut OpState,
+) -> Result<(ResourceId, String), AnyError> {
+  let (server, path) = imp::ClusterServer::bind()?;
+  Ok((state.resource_table.add(server), path))
+}
+
+/// Waits for the next worker to connect to the channel of the primary.
+#[op]
+pub async fn op_node_cluster_accept(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<ResourceId, AnyError> {
+  let server = state
+    .borrow()
+    .resource_table
+    .get::<imp::ClusterServer>(rid)?;
+  let channel = server.accept().await?;
+  Ok(state.borrow_mut().resource_table.add(channel))
+}
+
+/// Connects a worker to the channel of its primary, or returns `null` if the
+/// process isn't a worker. The path is only taken from the environment, and
+/// it is checked like any other socket that is connected to.
+#[op]
+pub async fn op_node_cluster_connect<P>(
+  state: Rc<RefCell<OpState>>,
+) -> Result<Option<ResourceId>, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let Some(path) = std::env::var_os(CHANNEL_ENV_VAR) else {
+    return Ok(None);
+  };
+  let path = Path::new(&path);
+  {
+    let mut state = state.borrow_mut();
+    let permissions = state.borrow_mut::<P>();
+    permissions.check_read(path)?;
+    permissions.check_write(path, "node:cluster")?;
+  }
+  let channel = imp::ClusterChannel::connect(path).await?;
+  Ok(Some(state.borrow_mut().resource_table.add(channel)))
+}
+
+/// Sends a message over the channel, along with the TCP connection `conn_rid`
+/// if it is given. The connection is closed in this process once it is sent.
+#[op]
+pub async fn op_node_cluster_send(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  message: String,
+  conn_rid: Option<ResourceId>,
+) -> Result<(), AnyError> {
+  let (channel, conn) = {
+    let mut state = state.borrow_mut();
+    let channel = state.resource_table.get::<imp::ClusterChannel>(rid)?;
+    let conn = match conn_rid {
+      Some(conn_rid) => Some(imp::take_tcp_stream(&mut state, conn_rid)?),
+      None => None,
+    };
+    (channel, conn)
+  };
+  channel.send(message.as_bytes(), conn).await
+}
+
+/// Receives the next message from the channel, or `null` once the other side
+/// closed it.
+#[op]
+pub async fn op_node_cluster_recv(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<ClusterMessage>, AnyError> {
+  let channel = state
+    .borrow()
+    .resource_table
+    .get::<imp::ClusterChannel>(rid)?;
+  let Some((message, conn)) = channel.recv().await? else {
+    return Ok(None);
+  };
+  let message = String::from_utf8(message)?;
+  let conn = match conn {
+    Some(conn) => Some(imp::add_tcp_stream(&mut state.borrow_mut(), conn)?),
+    None => None,
+  };
+  Ok(Some(ClusterMessage { message, conn }))
+}
+
+#[cfg(unix)]
+mod imp {
+  use std::borrow::Cow;
+  use std::collections::VecDeque;
+  use std::io::ErrorKind;
+  use std::mem::size_of;
+  use std::os::unix::fs::DirBuilderExt;
+  use std::os::unix::io::AsRawFd;
+  use std::os::unix::io::FromRawFd;
+  use std::os::unix::io::OwnedFd;
+  use std::os::unix::io::RawFd;
+  use std::path::Path;
+  use std::path::PathBuf;
+  use std::rc::Rc;
+
+  use deno_core::error::custom_error;
+  use deno_core::error::generic_error;
+  use deno_core::error::AnyError;
+  use deno_core::AsyncRefCell;
+  use deno_core::CancelHandle;
+  use deno_core::CancelTryFuture;
+  use deno_core::OpState;
+  use deno_core::RcRef;
+  use deno_core::Resource;
+  use deno_core::ResourceId;
+  use deno_net::io::TcpStreamResource;
+  use deno_net::ops::IpAddr;
+  use tokio::io::Interest;
+  use tokio::net::UnixListener;
+  use tokio::net::UnixStream;
+
+  /// The length of the header of a frame: the length of the message, and
+  /// whether a socket comes with it.
+  const HEADER_LEN: usize = 5;
+
+  pub struct ClusterServer {
+    listener: UnixListener,
+    dir: PathBuf,
+    cancel: CancelHandle,
+  }
+
+  impl ClusterServer {
+    pub fn bind() -> Result<(Self, String), AnyError> {
+      let dir = std::env::temp_dir().join(format!(
+        "deno-cluster-{}-{:016x}",
+        std::process::id(),
+        rand::random::<u64>()
+      ));
+      std::fs::DirBuilder::new().mode(0o700).create(&dir)?;
+      let path = dir.join("channel.sock");
+      let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(err) => {
+          let _ = std::fs::remove_dir_all(&dir);
+          return Err(err.into());
+        }
+      };
+      let path = path.to_string_lossy().to_string();
+      Ok((
+        Self {
+          listener,
+          dir,
+          cancel: Default::default(),
+        },
+        path,
+      ))
+    }
+
+    pub async fn accept(self: Rc<Self>) -> Result<ClusterChannel, AnyError> {
+      let cancel = RcRef::map(&self, |r| &r.cancel);
+      let (stream, _) = self.listener.accept().try_or_cancel(cancel).await?;
+      Ok(ClusterChannel::new(stream))
+    }
+  }
+
+  impl Drop for ClusterServer {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.dir);
+    }
+  }
+
+  impl Resource for ClusterServer {
+    fn name(&self) -> Cow<str> {
+      "clusterServer".into()
+    }
+
+    fn close(self: Rc<Self>) {
+      self.cancel.cancel();
+    }
+  }
+
+  #[derive(Default)]
+  struct ReadState {
+    buffer: Vec<u8>,
+    /// The sockets that were received, in the order of the frames they came
+    /// with.
+    fds: VecDeque<OwnedFd>,
+  }
+
+  impl ReadState {
+    fn take_frame(
+      &mut self,
+    ) -> Result<Option<(Vec<u8>, Option<OwnedFd>)>, AnyError> {
+      if self.buffer.len() < HEADER_LEN {
+        return Ok(None);
+      }
+      let len = u32::from_le_bytes(self.buffer[..4].try_into().unwrap());
+      let len = HEADER_LEN + len as usize;
+      if self.buffer.len() < len {
+        return Ok(None);
+      }
+      let has_fd = self.buffer[4] != 0;
+      let message = self.buffer[HEADER_LEN..len].to_vec();
+      self.buffer.drain(..len);
+      let fd = if has_fd {
+        // the socket arrives along with the first byte of its frame
+        let fd = self.fds.pop_front().ok_or_else(|| {
+          generic_error("A cluster message is missing its connection")
+        })?;
+        Some(fd)
+      } else {
+        None
+      };
+      Ok(Some((message, fd)))
+    }
+  }
+
+  pub struct ClusterChannel {
+    stream: UnixStream,
+    read_state: AsyncRefCell<ReadState>,
+    write_lock: AsyncRefCell<()>,
+    cancel: CancelHandle,
+  }
+
+  impl ClusterChannel {
+    fn new(stream: UnixStream) -> Self {
+      Self {
+        stream,
+        read_state: Default::default(),
+        write_lock: Default::default(),
+        cancel: Default::default(),
+      }
+    }
+
+    pub async fn connect(path: &Path) -> Result<Self, AnyError> {
+      Ok(Self::new(UnixStream::connect(path).await?))
+    }
+
+    pub async fn send(
+      self: Rc<Self>,
+      message: &[u8],
+      mut fd: Option<OwnedFd>,
+    ) -> Result<(), AnyError> {
+      let _lock = RcRef::map(&self, |r| &r.write_lock).borrow_mut().await;
+      let len = u32::try_from(message.len())
+        .map_err(|_| generic_error("The cluster message is too large"))?;
+      let mut frame = Vec::with_capacity(HEADER_LEN + message.len());
+      frame.extend_from_slice(&len.to_le_bytes());
+      frame.push(u8::from(fd.is_some()));
+      frame.extend_from_slice(message);
+
+      let mut written = 0;
+      while written < frame.len() {
+        self.stream.writable().await?;
+        let result = self.stream.try_io(Interest::WRITABLE, || {
+          send_with_fd(
+            self.stream.as_raw_fd(),
+            &frame[written..],
+            fd.as_ref().map(|fd| fd.as_raw_fd()),
+          )
+        });
+        match result {
+          Ok(n) => {
+            written += n;
+            // the socket went along with the first bytes, and the copy of
+            // this process is closed
+            fd = None;
+          }
+          Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+          Err(err) => return Err(err.into()),
+        }
+      }
+      Ok(())
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub async fn recv(
+      self: Rc<Self>,
+    ) -> Result<Option<(Vec<u8>, Option<OwnedFd>)>, AnyError> {
+      let mut state = RcRef::map(&self, |r| &r.read_state).borrow_mut().await;
+      let mut chunk = vec![0; 64 * 1024];
+      loop {
+        if let Some(frame) = state.take_frame()? {
+          return Ok(Some(frame));
+        }
+        let cancel = RcRef::map(&self, |r| &r.cancel);
+        let n = self
+          .recv_chunk(&mut chunk, &mut state.fds)
+          .try_or_cancel(cancel)
+          .await?;
+        if n == 0 {
+          return Ok(None);
+        }
+        state.buffer.extend_from_slice(&chunk[..n]);
+      }
+    }
+
+    async fn recv_chunk(
+      &self,
+      buf: &mut [u8],
+      fds: &mut VecDeque<OwnedFd>,
+    ) -> std::io::Result<usize> {
+      loop {
+        self.stream.readable().await?;
+        let result = self.stream.try_io(Interest::READABLE, || {
+          recv_with_fds(self.stream.as_raw_fd(), buf, fds)
+        });
+        match result {
+          Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+          result => return result,
+        }
+      }
+    }
+  }
+
+  impl Resource for ClusterChannel {
+    fn name(&self) -> Cow<str> {
+      "clusterChannel".into()
+    }
+
+    fn close(self: Rc<Self>) {
+      self.cancel.cancel();
+    }
+  }
+
+  /// Room for the control message of one socket, aligned like `cmsghdr`.
+  #[repr(C)]
+  union ControlBuffer {
+    buf: [u8; 64],
+    _align: libc::cmsghdr,
+  }
+
+  fn send_with_fd(
+    socket: RawFd,
+    data: &[u8],
+    fd: Option<RawFd>,
+  ) -> std::io::Result<usize> {
+    let mut iov = libc::iovec {
+      iov_base: data.as_ptr() as *mut libc::c_void,
+      iov_len: data.len(),
+    };
+    let mut control = ControlBuffer { buf: [0; 64] };
+    // SAFETY: msghdr is a plain struct, for which zeroes mean no address and
+    // no control messages. The control buffer has room for the header and
+    // the data of one control message, and outlives the call.
+    let n = unsafe {
+      let mut msg: libc::msghdr = std::mem::zeroed();
+      msg.msg_iov = &mut iov;
+      msg.msg_iovlen = 1;
+      if let Some(fd) = fd {
+        msg.msg_control = control.buf.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = libc::CMSG_SPACE(size_of::<RawFd>() as u32) as _;
+        let cmsg = libc::CMSG_FIRSTHDR(&msg);
+        (*cmsg).cmsg_level = libc::SOL_SOCKET;
+        (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+        (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<RawFd>() as u32) as _;
+        std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut RawFd, fd);
+      }
+      libc::sendmsg(socket, &msg, 0)
+    };
+    if n < 0 {
+      return Err(std::io::Error::last_os_error());
+    }
+    Ok(n as usize)
+  }
+
+  fn recv_with_fds(
+    socket: RawFd,
+    buf: &mut [u8],
+    fds: &mut VecDeque<OwnedFd>,
+  ) -> std::io::Result<usize> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const FLAGS: libc::c_int = libc::MSG_CMSG_CLOEXEC;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const FLAGS: libc::c_int = 0;
+
+    let mut iov = libc::iovec {
+      iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+      iov_len: buf.len(),
+    };
+    let mut control = ControlBuffer { buf: [0; 64] };
+    // SAFETY: see send_with_fd(). The control messages are only read as
+    // far as recvmsg() filled them in, and each socket in them is owned by
+    // this process from then on.
+    unsafe {
+      let mut msg: libc::msghdr = std::mem::zeroed();
+      msg.msg_iov = &mut iov;
+      msg.msg_iovlen = 1;
+      msg.msg_control = control.buf.as_mut_ptr() as *mut libc::c_void;
+      msg.msg_controllen = std::mem::size_of_val(&control.buf) as _;
+      let n = libc::recvmsg(socket, &mut msg, FLAGS);
+      if n < 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+      while !cmsg.is_null() {
+        if (*cmsg).cmsg_level == libc::SOL_SOCKET
+          && (*cmsg).cmsg_type == libc::SCM_RIGHTS
+        {
+          let data = libc::CMSG_DATA(cmsg) as *const RawFd;
+          let len = (*cmsg).cmsg_len as usize - libc::CMSG_LEN(0) as usize;
+          for i in 0..len / size_of::<RawFd>() {
+            let fd = std::ptr::read_unaligned(data.add(i));
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC);
+            fds.push_back(OwnedFd::from_raw_fd(fd));
+          }
+        }
+        cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+      }
+      if msg.msg_flags & libc::MSG_CTRUNC != 0 {
+        return Err(std::io::Error::new(
+          ErrorKind::InvalidData,
+          "Received too many sockets with a cluster message",
+        ));
+      }
+      Ok(n as usize)
+    }
+  }
+
+  /// Takes a TCP connection out of the resource table to be passed to
+  /// another process.
+  pub fn take_tcp_stream(
+    state: &mut OpState,
+    rid: ResourceId,
+  ) -> Result<OwnedFd, AnyError> {
+    let resource = state.resource_table.take::<TcpStreamResource>(rid)?;
+    let resource = Rc::try_unwrap(resource).map_err(|_| {
+      custom_error("Busy", "The connection is being read from or written to")
+    })?;
+    let (read_half, write_half) = resource.into_inner();
+    let stream = read_half.reunite(write_half)?.into_std()?;
+    Ok(OwnedFd::from(stream))
+  }
+
+  /// Adds a TCP connection that was received from another process to the
+  /// resource table.
+  pub fn add_tcp_stream(
+    state: &mut OpState,
+    fd: OwnedFd,
+  ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError> {
+    let stream = std::net::TcpStream::from(fd);
+    stream.set_nonblocking(true)?;
+    let stream = tokio::net::TcpStream::from_std(stream)?;
+    let local_addr = stream.local_addr()?;
+    let remote_addr = stream.peer_addr()?;
+    let rid = state
+      .resource_table
+      .add(TcpStreamResource::new(stream.into_split()));
+    Ok((rid, IpAddr::from(local_addr), IpAddr::from(remote_addr)))
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn passes_messages_and_sockets() {
+      let (a, b) = UnixStream::pair().unwrap();
+      let a = Rc::new(ClusterChannel::new(a));
+      let b = Rc::new(ClusterChannel::new(b));
+
+      let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+      let client =
+        std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+      let (server, _) = listener.accept().unwrap();
+
+      // a large message doesn't fit in the buffer of the socket, so it is
+      // received while it is sent
+      let large = "x".repeat(1_000_000);
+      let send = async {
+        a.clone().send(b"first", None).await.unwrap();
+        a.clone()
+          .send(b"second", Some(OwnedFd::from(server)))
+          .await
+          .unwrap();
+        a.clone().send(large.as_bytes(), None).await.unwrap();
+      };
+      let recv = async {
+        let (message, fd) = b.clone().recv().await.unwrap().unwrap();
+        assert_eq!(message, b"first");
+        assert!(fd.is_none());
+        let (message, fd) = b.clone().recv().await.unwrap().unwrap();
+        assert_eq!(message, b"second");
+        let server = std::net::TcpStream::from(fd.unwrap());
+        assert_eq!(server.peer_addr().unwrap(), client.local_addr().unwrap());
+        let (message, fd) = b.clone().recv().await.unwrap().unwrap();
+        assert_eq!(message.len(), 1_000_000);
+        assert!(fd.is_none());
+      };
+      tokio::join!(send, recv);
+
+      drop(a);
+      assert!(b.recv().await.unwrap().is_none());
+    }
+  }
+}
+
+/// The channel needs to pass sockets between processes, which is only
+/// implemented with Unix sockets.
+#[cfg(not(unix))]
+mod imp {
+  use std::path::Path;
+  use std::rc::Rc;
+
+  use deno_core::error::not_supported;
+  use deno_core::error::AnyError;
+  use deno_core::OpState;
+  use deno_core::Resource;
+  use deno_core::ResourceId;
+  use deno_net::ops::IpAddr;
+
+  pub struct ClusterServer;
+
+  impl ClusterServer {
+    pub fn bind() -> Result<(Self, String), AnyError> {
+      Err(not_supported())
+    }
+
+    pub async fn accept(self: Rc<Self>) -> Result<ClusterChannel, AnyError> {
+      Err(not_supported())
+    }
+  }
+
+  impl Resource for ClusterServer {}
+
+  pub struct ClusterChannel;
+
+  impl ClusterChannel {
+    pub async fn connect(_path: &Path) -> Result<Self, AnyError> {
+      Err(not_supported())
+    }
+
+    pub async fn send(
+      self: Rc<Self>,
+      _message: &[u8],
+      _fd: Option<()>,
+    ) -> Result<(), AnyError> {
+      Err(not_supported())
+    }
+
+    pub async fn recv(
+      self: Rc<Self>,
+    ) -> Result<Option<(Vec<u8>, Option<()>)>, AnyError> {
+      Err(not_supported())
+    }
+  }
+
+  impl Resource for ClusterChannel {}
+
+  pub fn take_tcp_stream(
+    _state: &mut OpState,
+    _rid: ResourceId,
+  ) -> Result<(), AnyError> {
+    Err(not_supported())
+  }
+
+  pub fn add_tcp_stream(
+    _state: &mut OpState,
+    _fd: (),
+  ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError> {
+    Err(not_supported())
+  }
+}
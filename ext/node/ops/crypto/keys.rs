@@ -0,0 +1,255 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Conversions between the key material of asymmetric `KeyObject`s, which is
+//! kept in the format the key generation ops return, and the SPKI and PKCS#8
+//! encodings that WebCrypto imports and exports.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::ZeroCopyBuf;
+use elliptic_curve::pkcs8::DecodePublicKey as _;
+use elliptic_curve::pkcs8::EncodePublicKey as _;
+use elliptic_curve::sec1::ToEncodedPoint;
+use rsa::pkcs1::DecodeRsaPrivateKey;
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::pkcs1::EncodeRsaPrivateKey;
+use rsa::pkcs1::EncodeRsaPublicKey;
+use rsa::pkcs8::spki::SubjectPublicKeyInfo;
+use rsa::pkcs8::DecodePrivateKey;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::pkcs8::EncodePrivateKey;
+use rsa::pkcs8::EncodePublicKey;
+use rsa::pkcs8::PrivateKeyInfo;
+use rsa::RsaPrivateKey;
+use rsa::RsaPublicKey;
+
+#[op]
+pub fn op_node_key_object_to_der(
+  key_type: &str,
+  asymmetric_key_type: &str,
+  named_curve: Option<String>,
+  key: &[u8],
+) -> Result<ZeroCopyBuf, AnyError> {
+  let der = match key_type {
+    "public" => public_key_to_spki(asymmetric_key_type, named_curve, key)?,
+    "private" => private_key_to_pkcs8(asymmetric_key_type, key)?,
+    _ => return Err(type_error("Invalid key type")),
+  };
+  Ok(der.into())
+}
+
+#[op]
+pub fn op_node_key_object_from_der(
+  key_type: &str,
+  asymmetric_key_type: &str,
+  named_curve: Option<String>,
+  der: &[u8],
+) -> Result<ZeroCopyBuf, AnyError> {
+  let key = match key_type {
+    "public" => public_key_from_spki(asymmetric_key_type, named_curve, der)?,
+    "private" => private_key_from_pkcs8(asymmetric_key_type, der)?,
+    _ => return Err(type_error("Invalid key type")),
+  };
+  Ok(key.into())
+}
+
+/// The OIDs of X25519 and Ed25519, 1.3.101.110 and 1.3.101.112, only differ
+/// in their last byte.
+fn curve25519_oid(asymmetric_key_type: &str) -> Option<u8> {
+  match asymmetric_key_type {
+    "x25519" => Some(110),
+    "ed25519" => Some(112),
+    _ => None,
+  }
+}
+
+enum NamedCurve {
+  P256,
+  P384,
+}
+
+impl NamedCurve {
+  fn new(named_curve: Option<String>) -> Result<Self, AnyError> {
+    match named_curve.as_deref() {
+      Some("P-256" | "prime256v1") => Ok(Self::P256),
+      Some("P-384" | "secp384r1") => Ok(Self::P384),
+      _ => Err(type_error("Unsupported named curve")),
+    }
+  }
+}
+
+fn public_key_to_spki(
+  asymmetric_key_type: &str,
+  named_curve: Option<String>,
+  key: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  match asymmetric_key_type {
+    "rsa" | "rsa-pss" => Ok(
+      RsaPublicKey::from_pkcs1_der(key)?
+        .to_public_key_der()?
+        .as_ref()
+        .to_vec(),
+    ),
+    // the key generation ops return DSA keys as SPKI already
+    "dsa" => Ok(key.to_vec()),
+    "ec" => {
+      let spki = match NamedCurve::new(named_curve)? {
+        NamedCurve::P256 => {
+          p256::PublicKey::from_sec1_bytes(key)?.to_public_key_der()
+        }
+        NamedCurve::P384 => {
+          p384::PublicKey::from_sec1_bytes(key)?.to_public_key_der()
+        }
+      };
+      Ok(
+        spki
+          .map_err(|_| type_error("Invalid EC public key"))?
+          .as_ref()
+          .to_vec(),
+      )
+    }
+    _ => {
+      let oid = curve25519_oid(asymmetric_key_type)
+        .ok_or_else(|| type_error("Unsupported key type"))?;
+      let mut spki = vec![
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, oid, 0x03, 0x21, 0x00,
+      ];
+      spki.extend_from_slice(key);
+      Ok(spki)
+    }
+  }
+}
+
+fn private_key_to_pkcs8(
+  asymmetric_key_type: &str,
+  key: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  match asymmetric_key_type {
+    "rsa" | "rsa-pss" => Ok(
+      RsaPrivateKey::from_pkcs1_der(key)?
+        .to_pkcs8_der()?
+        .as_bytes()
+        .to_vec(),
+    ),
+    // the key generation ops return DSA and EC keys as PKCS#8 already
+    "dsa" | "ec" => Ok(key.to_vec()),
+    _ => {
+      let oid = curve25519_oid(asymmetric_key_type)
+        .ok_or_else(|| type_error("Unsupported key type"))?;
+      let mut pkcs8 = vec![
+        0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, oid,
+      ];
+      pkcs8.extend_from_slice(&[0x04, 0x22, 0x04, 0x20]);
+      pkcs8.extend_from_slice(key);
+      Ok(pkcs8)
+    }
+  }
+}
+
+fn public_key_from_spki(
+  asymmetric_key_type: &str,
+  named_curve: Option<String>,
+  der: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  match asymmetric_key_type {
+    "rsa" | "rsa-pss" => Ok(
+      RsaPublicKey::from_public_key_der(der)?
+        .to_pkcs1_der()?
+        .as_ref()
+        .to_vec(),
+    ),
+    "dsa" => Ok(der.to_vec()),
+    "ec" => {
+      let point = match NamedCurve::new(named_curve)? {
+        NamedCurve::P256 => p256::PublicKey::from_public_key_der(der)
+          .map(|key| key.to_encoded_point(false).as_bytes().to_vec()),
+        NamedCurve::P384 => p384::PublicKey::from_public_key_der(der)
+          .map(|key| key.to_encoded_point(false).as_bytes().to_vec()),
+      };
+      point.map_err(|_| type_error("Invalid EC public key"))
+    }
+    _ => {
+      let oid = curve25519_oid(asymmetric_key_type)
+        .ok_or_else(|| type_error("Unsupported key type"))?;
+      let spki = SubjectPublicKeyInfo::try_from(der)?;
+      if spki.algorithm.oid.as_bytes() != [0x2b, 0x65, oid]
+        || spki.subject_public_key.len() != 32
+      {
+        return Err(type_error("Invalid public key"));
+      }
+      Ok(spki.subject_public_key.to_vec())
+    }
+  }
+}
+
+fn private_key_from_pkcs8(
+  asymmetric_key_type: &str,
+  der: &[u8],
+) -> Result<Vec<u8>, AnyError> {
+  match asymmetric_key_type {
+    "rsa" | "rsa-pss" => Ok(
+      RsaPrivateKey::from_pkcs8_der(der)?
+        .to_pkcs1_der()?
+        .as_bytes()
+        .to_vec(),
+    ),
+    "dsa" | "ec" => Ok(der.to_vec()),
+    _ => {
+      let oid = curve25519_oid(asymmetric_key_type)
+        .ok_or_else(|| type_error("Unsupported key type"))?;
+      let pkcs8 = PrivateKeyInfo::try_from(der)?;
+      // the private key is an OCTET STRING wrapped in another one
+      match pkcs8.private_key {
+        [0x04, 0x20, key @ ..]
+          if pkcs8.algorithm.oid.as_bytes() == [0x2b, 0x65, oid]
+            && key.len() == 32 =>
+        {
+          Ok(key.to_vec())
+        }
+        _ => Err(type_error("Invalid private key")),
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn curve25519_roundtrip() {
+    for asymmetric_key_type in ["ed25519", "x25519"] {
+      let key = [7; 32];
+      let spki = public_key_to_spki(asymmetric_key_type, None, &key).unwrap();
+      assert_eq!(spki.len(), 44);
+      assert_eq!(
+        public_key_from_spki(asymmetric_key_type, None, &spki).unwrap(),
+        key
+      );
+      let pkcs8 = private_key_to_pkcs8(asymmetric_key_type, &key).unwrap();
+      assert_eq!(pkcs8.len(), 48);
+      assert_eq!(
+        private_key_from_pkcs8(asymmetric_key_type, &pkcs8).unwrap(),
+        key
+      );
+    }
+
+    let spki = public_key_to_spki("ed25519", None, &[7; 32]).unwrap();
+    assert!(public_key_from_spki("x25519", None, &spki).is_err());
+  }
+
+  #[test]
+  fn ec_roundtrip() {
+    let secret = p256::SecretKey::random(&mut rand::thread_rng());
+    let point = secret.public_key().to_encoded_point(false);
+    let named_curve = || Some("prime256v1".to_string());
+    let spki =
+      public_key_to_spki("ec", named_curve(), point.as_bytes()).unwrap();
+    assert_eq!(
+      public_key_from_spki("ec", named_curve(), &spki).unwrap(),
+      point.as_bytes()
+    );
+    assert!(public_key_from_spki("ec", Some("P-384".into()), &spki).is_err());
+  }
+}
@@ -20,6 +20,12 @@ use rand::Rng;
 use std::future::Future;
 use std::rc::Rc;
 
+use elliptic_curve::sec1::FromEncodedPoint;
+use elliptic_curve::sec1::ModulusSize;
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::AffinePoint;
+use elliptic_curve::CurveArithmetic;
+use elliptic_curve::FieldBytesSize;
 use p224::NistP224;
 use p256::NistP256;
 use p384::NistP384;
@@ -36,6 +42,7 @@ use secp256k1::SecretKey;
 mod cipher;
 mod dh;
 mod digest;
+pub mod keys;
 mod primes;
 pub mod x509;
 
@@ -925,39 +932,20 @@ pub fn op_node_ecdh_generate_keys(
   pubbuf: &mut [u8],
   privbuf: &mut [u8],
 ) -> Result<ResourceId, AnyError> {
-  let mut rng = rand::thread_rng();
   match curve {
     "secp256k1" => {
+      let mut rng = rand::thread_rng();
       let secp = Secp256k1::new();
       let (privkey, pubkey) = secp.generate_keypair(&mut rng);
       pubbuf.copy_from_slice(&pubkey.serialize_uncompressed());
       privbuf.copy_from_slice(&privkey.secret_bytes());
-
-      Ok(0)
-    }
-    "prime256v1" | "secp256r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP256>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_sec1_bytes().as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-      Ok(0)
     }
-    "secp384r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP384>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_sec1_bytes().as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-      Ok(0)
-    }
-    "secp224r1" => {
-      let privkey = elliptic_curve::SecretKey::<NistP224>::random(&mut rng);
-      let pubkey = privkey.public_key();
-      pubbuf.copy_from_slice(pubkey.to_sec1_bytes().as_ref());
-      privbuf.copy_from_slice(privkey.to_nonzero_scalar().to_bytes().as_ref());
-      Ok(0)
-    }
-    &_ => todo!(),
+    "prime256v1" | "secp256r1" => ec_generate_keys::<NistP256>(pubbuf, privbuf),
+    "secp384r1" => ec_generate_keys::<NistP384>(pubbuf, privbuf),
+    "secp224r1" => ec_generate_keys::<NistP224>(pubbuf, privbuf),
+    _ => return Err(unsupported_curve(curve)),
   }
+  Ok(0)
 }
 
 #[op]
@@ -967,14 +955,14 @@ pub fn op_node_ecdh_compute_secret(
   their_pub: &mut [u8],
   secret: &mut [u8],
 ) -> Result<(), AnyError> {
+  let this_priv =
+    this_priv.ok_or_else(|| type_error("No private key has been set"))?;
   match curve {
     "secp256k1" => {
-      let this_secret_key = SecretKey::from_slice(
-        this_priv.expect("no private key provided?").as_ref(),
-      )
-      .unwrap();
-      let their_public_key =
-        secp256k1::PublicKey::from_slice(their_pub).unwrap();
+      let this_secret_key = SecretKey::from_slice(this_priv.as_ref())
+        .map_err(|_| invalid_private_key())?;
+      let their_public_key = secp256k1::PublicKey::from_slice(their_pub)
+        .map_err(|_| invalid_public_key())?;
       let shared_secret =
         SharedSecret::new(&their_public_key, &this_secret_key);
 
@@ -982,54 +970,11 @@ pub fn op_node_ecdh_compute_secret(
       Ok(())
     }
     "prime256v1" | "secp256r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP256>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP256>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    "secp384r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP384>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP384>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
+      ec_compute_secret::<NistP256>(&this_priv, their_pub, secret)
     }
-    "secp224r1" => {
-      let their_public_key =
-        elliptic_curve::PublicKey::<NistP224>::from_sec1_bytes(their_pub)
-          .expect("bad public key");
-      let this_private_key = elliptic_curve::SecretKey::<NistP224>::from_slice(
-        &this_priv.expect("must supply private key"),
-      )
-      .expect("bad private key");
-      let shared_secret = elliptic_curve::ecdh::diffie_hellman(
-        this_private_key.to_nonzero_scalar(),
-        their_public_key.as_affine(),
-      );
-      secret.copy_from_slice(shared_secret.raw_secret_bytes());
-
-      Ok(())
-    }
-    &_ => todo!(),
+    "secp384r1" => ec_compute_secret::<NistP384>(&this_priv, their_pub, secret),
+    "secp224r1" => ec_compute_secret::<NistP224>(&this_priv, their_pub, secret),
+    _ => Err(unsupported_curve(curve)),
   }
 }
 
@@ -1042,7 +987,8 @@ pub fn op_node_ecdh_compute_public_key(
   match curve {
     "secp256k1" => {
       let secp = Secp256k1::new();
-      let secret_key = SecretKey::from_slice(privkey).unwrap();
+      let secret_key =
+        SecretKey::from_slice(privkey).map_err(|_| invalid_private_key())?;
       let public_key =
         secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
 
@@ -1051,33 +997,111 @@ pub fn op_node_ecdh_compute_public_key(
       Ok(())
     }
     "prime256v1" | "secp256r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP256>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
+      ec_compute_public_key::<NistP256>(privkey, pubkey)
     }
-    "secp384r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP384>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
-    }
-    "secp224r1" => {
-      let this_private_key =
-        elliptic_curve::SecretKey::<NistP224>::from_slice(privkey)
-          .expect("bad private key");
-      let public_key = this_private_key.public_key();
-      pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
-      Ok(())
-    }
-    &_ => todo!(),
+    "secp384r1" => ec_compute_public_key::<NistP384>(privkey, pubkey),
+    "secp224r1" => ec_compute_public_key::<NistP224>(privkey, pubkey),
+    _ => Err(unsupported_curve(curve)),
   }
 }
 
+/// Re-encodes a SEC1 public key in its compressed or uncompressed form.
+/// Returns `None` when the key isn't a valid point of the curve.
+#[op]
+pub fn op_node_ecdh_convert_key(
+  curve: &str,
+  key: &[u8],
+  compress: bool,
+) -> Result<Option<ZeroCopyBuf>, AnyError> {
+  let converted = match curve {
+    "secp256k1" => secp256k1::PublicKey::from_slice(key).ok().map(|key| {
+      if compress {
+        key.serialize().to_vec()
+      } else {
+        key.serialize_uncompressed().to_vec()
+      }
+    }),
+    "prime256v1" | "secp256r1" => ec_convert_key::<NistP256>(key, compress),
+    "secp384r1" => ec_convert_key::<NistP384>(key, compress),
+    "secp224r1" => ec_convert_key::<NistP224>(key, compress),
+    _ => return Err(unsupported_curve(curve)),
+  };
+  Ok(converted.map(Into::into))
+}
+
+fn unsupported_curve(curve: &str) -> AnyError {
+  type_error(format!("Unsupported curve: {curve}"))
+}
+
+fn invalid_private_key() -> AnyError {
+  type_error("Private key is not valid for specified curve")
+}
+
+fn invalid_public_key() -> AnyError {
+  type_error("Public key is not valid for specified curve")
+}
+
+fn ec_generate_keys<C>(pubbuf: &mut [u8], privbuf: &mut [u8])
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let privkey = elliptic_curve::SecretKey::<C>::random(&mut rand::thread_rng());
+  let pubkey = privkey.public_key();
+  pubbuf.copy_from_slice(pubkey.to_sec1_bytes().as_ref());
+  privbuf.copy_from_slice(privkey.to_bytes().as_ref());
+}
+
+fn ec_compute_secret<C>(
+  this_priv: &[u8],
+  their_pub: &[u8],
+  secret: &mut [u8],
+) -> Result<(), AnyError>
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let their_public_key =
+    elliptic_curve::PublicKey::<C>::from_sec1_bytes(their_pub)
+      .map_err(|_| invalid_public_key())?;
+  let this_private_key = elliptic_curve::SecretKey::<C>::from_slice(this_priv)
+    .map_err(|_| invalid_private_key())?;
+  let shared_secret = elliptic_curve::ecdh::diffie_hellman(
+    this_private_key.to_nonzero_scalar(),
+    their_public_key.as_affine(),
+  );
+  secret.copy_from_slice(shared_secret.raw_secret_bytes());
+  Ok(())
+}
+
+fn ec_compute_public_key<C>(
+  privkey: &[u8],
+  pubkey: &mut [u8],
+) -> Result<(), AnyError>
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let this_private_key = elliptic_curve::SecretKey::<C>::from_slice(privkey)
+    .map_err(|_| invalid_private_key())?;
+  let public_key = this_private_key.public_key();
+  pubkey.copy_from_slice(public_key.to_sec1_bytes().as_ref());
+  Ok(())
+}
+
+fn ec_convert_key<C>(key: &[u8], compress: bool) -> Option<Vec<u8>>
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let key = elliptic_curve::PublicKey::<C>::from_sec1_bytes(key).ok()?;
+  Some(key.to_encoded_point(compress).as_bytes().to_vec())
+}
+
 #[inline]
 fn gen_prime(size: usize) -> ZeroCopyBuf {
   primes::Prime::generate(size).0.to_bytes_be().into()
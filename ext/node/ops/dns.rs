@@ -0,0 +1,275 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! `dns.lookup()`, which asks the resolver of the system like node does, so
+//! that the hosts file (`/etc/hosts`, or the one of Windows) is honored. The
+//! other functions of `node:dns` query DNS servers directly instead.
+
+use std::net::IpAddr;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::task::spawn_blocking;
+
+// The error codes of libuv for `getaddrinfo()`, which are the same on every
+// platform.
+const UV_EAI_AGAIN: i32 = -3001;
+const UV_EAI_BADFLAGS: i32 = -3002;
+const UV_EAI_FAIL: i32 = -3004;
+const UV_EAI_FAMILY: i32 = -3005;
+const UV_EAI_MEMORY: i32 = -3006;
+const UV_EAI_NODATA: i32 = -3007;
+const UV_EAI_NONAME: i32 = -3008;
+#[cfg(unix)]
+const UV_EAI_OVERFLOW: i32 = -3009;
+const UV_EAI_SERVICE: i32 = -3010;
+const UV_EAI_SOCKTYPE: i32 = -3011;
+
+/// Resolves `hostname` to the addresses of `family` (4, 6, or 0 for both).
+/// Returns the libuv error code of the lookup, which is 0 if it succeeded,
+/// along with the addresses. The net permission is checked beforehand by
+/// `op_node_check_net`, so that it throws synchronously.
+#[op]
+pub async fn op_node_getaddrinfo(
+  hostname: String,
+  family: u8,
+  v4_mapped: bool,
+  all: bool,
+  verbatim: bool,
+) -> Result<(i32, Vec<String>), AnyError> {
+  let addresses = match spawn_blocking(move || lookup_host(&hostname)).await? {
+    Ok(addresses) => addresses,
+    Err(code) => return Ok((code, vec![])),
+  };
+  let addresses = select_addresses(addresses, family, v4_mapped, all, verbatim);
+  if addresses.is_empty() {
+    // there are addresses, but none of the family that was asked for
+    return Ok((UV_EAI_NODATA, vec![]));
+  }
+  Ok((
+    0,
+    addresses
+      .into_iter()
+      .map(|address| address.to_string())
+      .collect(),
+  ))
+}
+
+/// Calls `getaddrinfo()` directly, as the error that the standard library
+/// returns for it doesn't keep the code of the error.
+#[cfg(unix)]
+fn lookup_host(hostname: &str) -> Result<Vec<IpAddr>, i32> {
+  use std::net::Ipv4Addr;
+  use std::net::Ipv6Addr;
+
+  let hostname = std::ffi::CString::new(hostname).map_err(|_| UV_EAI_NONAME)?;
+  // SAFETY: addrinfo is a plain struct, for which zeroes are the defaults
+  // of the hints.
+  let mut hints: libc::addrinfo = unsafe { std::mem::zeroed() };
+  hints.ai_family = libc::AF_UNSPEC;
+  // only to not get every address once per socket type
+  hints.ai_socktype = libc::SOCK_STREAM;
+  let mut list = std::ptr::null_mut();
+  // SAFETY: `hostname` is nul terminated, and `list` is only read if the
+  // call succeeded.
+  let code = unsafe {
+    libc::getaddrinfo(hostname.as_ptr(), std::ptr::null(), &hints, &mut list)
+  };
+  if code != 0 {
+    return Err(uv_getaddrinfo_error(code));
+  }
+
+  let mut addresses = Vec::new();
+  let mut next = list;
+  while !next.is_null() {
+    // SAFETY: `next` is a node of the list that getaddrinfo() returned, and
+    // `ai_addr` points to the socket address of `ai_family`.
+    unsafe {
+      let info = &*next;
+      match info.ai_family {
+        libc::AF_INET => {
+          let address = &*(info.ai_addr as *const libc::sockaddr_in);
+          addresses.push(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+            address.sin_addr.s_addr,
+          ))));
+        }
+        libc::AF_INET6 => {
+          let address = &*(info.ai_addr as *const libc::sockaddr_in6);
+          addresses.push(IpAddr::V6(Ipv6Addr::from(address.sin6_addr.s6_addr)));
+        }
+        _ => {}
+      }
+      next = info.ai_next;
+    }
+  }
+  // SAFETY: `list` was returned by getaddrinfo() and isn't used anymore.
+  unsafe { libc::freeaddrinfo(list) };
+  Ok(addresses)
+}
+
+/// Translates the error of `getaddrinfo()` like libuv does.
+#[cfg(unix)]
+fn uv_getaddrinfo_error(code: i32) -> i32 {
+  match code {
+    libc::EAI_AGAIN => UV_EAI_AGAIN,
+    libc::EAI_BADFLAGS => UV_EAI_BADFLAGS,
+    libc::EAI_FAIL => UV_EAI_FAIL,
+    libc::EAI_FAMILY => UV_EAI_FAMILY,
+    libc::EAI_MEMORY => UV_EAI_MEMORY,
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    libc::EAI_NODATA => UV_EAI_NODATA,
+    libc::EAI_NONAME => UV_EAI_NONAME,
+    libc::EAI_OVERFLOW => UV_EAI_OVERFLOW,
+    libc::EAI_SERVICE => UV_EAI_SERVICE,
+    libc::EAI_SOCKTYPE => UV_EAI_SOCKTYPE,
+    // the errors of libuv for errno values are their negation on unix
+    libc::EAI_SYSTEM => -std::io::Error::last_os_error()
+      .raw_os_error()
+      .unwrap_or(libc::EIO),
+    _ => UV_EAI_FAIL,
+  }
+}
+
+/// The standard library keeps the code of the error on Windows, where
+/// `getaddrinfo()` sets it as the last WSA error.
+#[cfg(windows)]
+fn lookup_host(hostname: &str) -> Result<Vec<IpAddr>, i32> {
+  use std::net::ToSocketAddrs;
+
+  match (hostname, 0).to_socket_addrs() {
+    Ok(addresses) => Ok(addresses.map(|address| address.ip()).collect()),
+    Err(err) => Err(uv_getaddrinfo_error(err.raw_os_error().unwrap_or(0))),
+  }
+}
+
+/// Translates the error of `getaddrinfo()` like libuv does.
+#[cfg(windows)]
+fn uv_getaddrinfo_error(code: i32) -> i32 {
+  // the WSA error codes, which winapi only has with features that aren't
+  // enabled
+  const WSA_NOT_ENOUGH_MEMORY: i32 = 8;
+  const WSAEAFNOSUPPORT: i32 = 10047;
+  const WSAESOCKTNOSUPPORT: i32 = 10044;
+  const WSAHOST_NOT_FOUND: i32 = 11001;
+  const WSATRY_AGAIN: i32 = 11002;
+  const WSANO_RECOVERY: i32 = 11003;
+  const WSANO_DATA: i32 = 11004;
+  const WSATYPE_NOT_FOUND: i32 = 10109;
+  const WSAEINVAL: i32 = 10022;
+
+  match code {
+    WSATRY_AGAIN => UV_EAI_AGAIN,
+    WSAEINVAL => UV_EAI_BADFLAGS,
+    WSANO_RECOVERY => UV_EAI_FAIL,
+    WSAEAFNOSUPPORT => UV_EAI_FAMILY,
+    WSA_NOT_ENOUGH_MEMORY => UV_EAI_MEMORY,
+    WSAHOST_NOT_FOUND => UV_EAI_NONAME,
+    WSANO_DATA => UV_EAI_NODATA,
+    WSATYPE_NOT_FOUND => UV_EAI_SERVICE,
+    WSAESOCKTNOSUPPORT => UV_EAI_SOCKTYPE,
+    _ => UV_EAI_FAIL,
+  }
+}
+
+/// Applies the family and the hints of `getaddrinfo()`, and the ordering of
+/// node, to the addresses that the resolver returned.
+fn select_addresses(
+  addresses: Vec<IpAddr>,
+  family: u8,
+  v4_mapped: bool,
+  all: bool,
+  verbatim: bool,
+) -> Vec<IpAddr> {
+  let mut selected: Vec<IpAddr> = Vec::with_capacity(addresses.len());
+  let mut push = |address: IpAddr| {
+    if !selected.contains(&address) {
+      selected.push(address);
+    }
+  };
+  match family {
+    4 => addresses
+      .into_iter()
+      .filter(IpAddr::is_ipv4)
+      .for_each(&mut push),
+    6 => {
+      let has_ipv6 = addresses.iter().any(IpAddr::is_ipv6);
+      // IPv4 addresses are only returned as IPv4-mapped IPv6 addresses if
+      // asked for, and then only if there are no IPv6 addresses unless all
+      // of them are asked for
+      let map_ipv4 = v4_mapped && (all || !has_ipv6);
+      for address in addresses {
+        match address {
+          IpAddr::V6(_) => push(address),
+          IpAddr::V4(ipv4) if map_ipv4 => {
+            push(IpAddr::V6(ipv4.to_ipv6_mapped()))
+          }
+          IpAddr::V4(_) => {}
+        }
+      }
+    }
+    _ => addresses.into_iter().for_each(&mut push),
+  }
+  if !verbatim {
+    // the sort is stable, so the order of each family is kept
+    selected.sort_by_key(|address| address.is_ipv6());
+  }
+  selected
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn select(
+    family: u8,
+    v4_mapped: bool,
+    all: bool,
+    verbatim: bool,
+  ) -> Vec<String> {
+    let addresses = ["::1", "127.0.0.1", "10.0.0.2", "::1", "fe80::1"]
+      .into_iter()
+      .map(|address| address.parse().unwrap())
+      .collect();
+    select_addresses(addresses, family, v4_mapped, all, verbatim)
+      .into_iter()
+      .map(|address| address.to_string())
+      .collect()
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn lookup_host_errors() {
+    assert_eq!(lookup_host("a\0b"), Err(UV_EAI_NONAME));
+    assert_eq!(uv_getaddrinfo_error(libc::EAI_AGAIN), UV_EAI_AGAIN);
+    assert_eq!(uv_getaddrinfo_error(libc::EAI_NONAME), UV_EAI_NONAME);
+    let addresses = lookup_host("localhost").unwrap();
+    assert!(addresses.iter().all(IpAddr::is_loopback));
+  }
+
+  #[test]
+  fn select_addresses_order() {
+    assert_eq!(
+      select(0, false, false, true),
+      vec!["::1", "127.0.0.1", "10.0.0.2", "fe80::1"]
+    );
+    assert_eq!(
+      select(0, false, false, false),
+      vec!["127.0.0.1", "10.0.0.2", "::1", "fe80::1"]
+    );
+  }
+
+  #[test]
+  fn select_addresses_family() {
+    assert_eq!(select(4, false, false, true), vec!["127.0.0.1", "10.0.0.2"]);
+    assert_eq!(select(6, false, false, true), vec!["::1", "fe80::1"]);
+    assert_eq!(select(6, true, false, true), vec!["::1", "fe80::1"]);
+    assert_eq!(
+      select(6, true, true, true),
+      vec!["::1", "::ffff:127.0.0.1", "::ffff:10.0.0.2", "fe80::1"]
+    );
+    let addresses = vec!["127.0.0.1".parse().unwrap()];
+    assert_eq!(
+      select_addresses(addresses, 6, true, false, true),
+      vec!["::ffff:127.0.0.1".parse::<IpAddr>().unwrap()]
+    );
+  }
+}
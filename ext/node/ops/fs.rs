@@ -0,0 +1,138 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::task::spawn_blocking;
+use deno_core::OpState;
+use serde::Serialize;
+
+use crate::NodePermissions;
+
+/// The statistics of a mounted file system, as `fs.statfs()` returns them.
+#[derive(Debug, Default, Serialize)]
+pub struct StatFs {
+  #[serde(rename = "type")]
+  pub typ: u64,
+  pub bsize: u64,
+  pub blocks: u64,
+  pub bfree: u64,
+  pub bavail: u64,
+  pub files: u64,
+  pub ffree: u64,
+}
+
+#[op]
+pub fn op_node_statfs_sync<P>(
+  state: &mut OpState,
+  path: String,
+) -> Result<StatFs, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = PathBuf::from(path);
+  state.borrow::<P>().check_read(&path)?;
+  statfs(&path)
+}
+
+#[op]
+pub async fn op_node_statfs<P>(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+) -> Result<StatFs, AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  let path = PathBuf::from(path);
+  state.borrow().borrow::<P>().check_read(&path)?;
+  spawn_blocking(move || statfs(&path)).await?
+}
+
+// the field types of `statfs` differ between platforms
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn statfs(path: &Path) -> Result<StatFs, AnyError> {
+  use std::os::unix::ffi::OsStrExt;
+
+  let path = std::ffi::CString::new(path.as_os_str().as_bytes())?;
+  // SAFETY: statfs is a plain struct of integers, which statfs() fills in on
+  // success, and `path` is nul terminated.
+  let stat = unsafe {
+    let mut stat: libc::statfs = std::mem::zeroed();
+    if libc::statfs(path.as_ptr(), &mut stat) != 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    stat
+  };
+  Ok(StatFs {
+    typ: stat.f_type as u64,
+    bsize: stat.f_bsize as u64,
+    blocks: stat.f_blocks as u64,
+    bfree: stat.f_bfree as u64,
+    bavail: stat.f_bavail as u64,
+    files: stat.f_files as u64,
+    ffree: stat.f_ffree as u64,
+  })
+}
+
+/// Only the sizes are known on windows, like in libuv.
+#[cfg(windows)]
+fn statfs(path: &Path) -> Result<StatFs, AnyError> {
+  use std::os::windows::ffi::OsStrExt;
+  use winapi::um::fileapi::GetDiskFreeSpaceW;
+
+  // GetDiskFreeSpaceW() wants the root of a volume, so use the path of a
+  // directory on it with a trailing separator
+  let mut root = std::fs::canonicalize(path)?;
+  if !root.is_dir() {
+    root.pop();
+  }
+  let mut root = root.into_os_string();
+  root.push("\\");
+  let root = root.encode_wide().chain(Some(0)).collect::<Vec<_>>();
+
+  let mut sectors_per_cluster = 0;
+  let mut bytes_per_sector = 0;
+  let mut free_clusters = 0;
+  let mut total_clusters = 0;
+  // SAFETY: `root` is nul terminated and the out pointers are valid.
+  let ok = unsafe {
+    GetDiskFreeSpaceW(
+      root.as_ptr(),
+      &mut sectors_per_cluster,
+      &mut bytes_per_sector,
+      &mut free_clusters,
+      &mut total_clusters,
+    )
+  };
+  if ok == 0 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(StatFs {
+    bsize: u64::from(sectors_per_cluster) * u64::from(bytes_per_sector),
+    blocks: total_clusters.into(),
+    bfree: free_clusters.into(),
+    bavail: free_clusters.into(),
+    ..Default::default()
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn statfs_current_dir() {
+    let stat = statfs(&std::env::current_dir().unwrap()).unwrap();
+    assert!(stat.bsize > 0);
+    assert!(stat.blocks > 0);
+    assert!(stat.bfree <= stat.blocks);
+    assert!(stat.bavail <= stat.blocks);
+
+    assert!(statfs(Path::new("/does/not/exist")).is_err());
+  }
+}
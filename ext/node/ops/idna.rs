@@ -1,18 +1,49 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op;
+use serde::Deserialize;
+
+/// The UTS #46 processing flags that can be set from JS.
+///
+/// All of them are off by default, which is how the WHATWG URL standard
+/// processes host names.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct IdnaOptions {
+  /// Map deviation characters, like "ß", the way IDNA2003 did instead of
+  /// keeping them.
+  transitional_processing: bool,
+  /// Reject code points that aren't allowed in host names by STD3.
+  use_std3_ascii_rules: bool,
+  /// Reject empty labels, and labels or domains too long for DNS.
+  verify_dns_length: bool,
+}
+
+impl IdnaOptions {
+  fn config(&self) -> idna::Config {
+    idna::Config::default()
+      .transitional_processing(self.transitional_processing)
+      .use_std3_ascii_rules(self.use_std3_ascii_rules)
+      .verify_dns_length(self.verify_dns_length)
+  }
+}
 
 #[op]
 pub fn op_node_idna_domain_to_ascii(
   domain: String,
+  options: Option<IdnaOptions>,
 ) -> Result<String, AnyError> {
-  Ok(idna::domain_to_ascii(&domain)?)
+  domain_to_ascii(&domain, &options.unwrap_or_default())
 }
 
 #[op]
-pub fn op_node_idna_domain_to_unicode(domain: String) -> String {
-  idna::domain_to_unicode(&domain).0
+pub fn op_node_idna_domain_to_unicode(
+  domain: String,
+  options: Option<IdnaOptions>,
+) -> Result<String, AnyError> {
+  domain_to_unicode(&domain, &options.unwrap_or_default())
 }
 
 #[op]
@@ -24,3 +55,78 @@ pub fn op_node_idna_punycode_decode(domain: String) -> String {
 pub fn op_node_idna_punycode_encode(domain: String) -> String {
   idna::punycode::encode_str(&domain).unwrap_or_default()
 }
+
+/// `punycode.toUnicode()`, which converts what it can of an invalid domain
+/// instead of throwing.
+#[op]
+pub fn op_node_idna_punycode_to_unicode(domain: String) -> String {
+  idna::domain_to_unicode(&domain).0
+}
+
+fn domain_to_ascii(
+  domain: &str,
+  options: &IdnaOptions,
+) -> Result<String, AnyError> {
+  options.config().to_ascii(domain).map_err(|errors| {
+    type_error(format!("Cannot convert name to ASCII: {errors}"))
+  })
+}
+
+fn domain_to_unicode(
+  domain: &str,
+  options: &IdnaOptions,
+) -> Result<String, AnyError> {
+  let (domain, result) = options.config().to_unicode(domain);
+  match result {
+    Ok(()) => Ok(domain),
+    Err(errors) => Err(type_error(format!(
+      "Cannot convert name to Unicode: {errors}"
+    ))),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn uts46_options() {
+    let default = IdnaOptions::default();
+    assert_eq!(
+      domain_to_ascii("faß.de", &default).unwrap(),
+      "xn--fa-hia.de"
+    );
+    assert_eq!(
+      domain_to_ascii(
+        "faß.de",
+        &IdnaOptions {
+          transitional_processing: true,
+          ..Default::default()
+        }
+      )
+      .unwrap(),
+      "fass.de"
+    );
+
+    assert_eq!(domain_to_ascii("a_b.com", &default).unwrap(), "a_b.com");
+    let std3 = IdnaOptions {
+      use_std3_ascii_rules: true,
+      ..Default::default()
+    };
+    assert!(domain_to_ascii("a_b.com", &std3).is_err());
+
+    assert_eq!(domain_to_ascii("a..com", &default).unwrap(), "a..com");
+    let dns_length = IdnaOptions {
+      verify_dns_length: true,
+      ..Default::default()
+    };
+    assert!(domain_to_ascii("a..com", &dns_length).is_err());
+    assert!(domain_to_ascii(&"a".repeat(64), &dns_length).is_err());
+
+    assert_eq!(
+      domain_to_unicode("xn--fa-hia.de", &default).unwrap(),
+      "faß.de"
+    );
+    assert!(domain_to_unicode("xn--a.com", &default).is_err());
+  }
+}
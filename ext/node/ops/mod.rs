@@ -1,9 +1,19 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+pub mod async_hooks;
+pub mod buffer;
+pub mod cluster;
 pub mod crypto;
+pub mod dns;
+pub mod fs;
 pub mod http;
 pub mod idna;
+pub mod os;
+pub mod perf_hooks;
+pub mod permissions;
+pub mod querystring;
 pub mod require;
+pub mod tty;
 pub mod v8;
 pub mod winerror;
 pub mod zlib;
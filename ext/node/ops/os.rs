@@ -0,0 +1,61 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::op;
+
+/// `process.platform`, named the way node does.
+#[op]
+pub fn op_node_os_platform() -> String {
+  match std::env::consts::OS {
+    "windows" => "win32",
+    "macos" => "darwin",
+    os => os,
+  }
+  .to_string()
+}
+
+/// `process.arch`, named the way node does.
+#[op]
+pub fn op_node_os_arch() -> String {
+  match std::env::consts::ARCH {
+    "x86_64" => "x64",
+    "x86" => "ia32",
+    "aarch64" => "arm64",
+    "powerpc" => "ppc",
+    "powerpc64" => "ppc64",
+    "loongarch64" => "loong64",
+    arch => arch,
+  }
+  .to_string()
+}
+
+/// `os.machine()`, the machine type reported by the kernel.
+#[op]
+pub fn op_node_os_machine() -> String {
+  machine()
+}
+
+#[cfg(unix)]
+fn machine() -> String {
+  // SAFETY: utsname is a plain struct of char arrays and uname() fills it
+  // with nul terminated strings on success.
+  unsafe {
+    let mut name: libc::utsname = std::mem::zeroed();
+    if libc::uname(&mut name) != 0 {
+      return std::env::consts::ARCH.to_string();
+    }
+    std::ffi::CStr::from_ptr(name.machine.as_ptr())
+      .to_string_lossy()
+      .into_owned()
+  }
+}
+
+#[cfg(windows)]
+fn machine() -> String {
+  // matches the names libuv reports on windows
+  match std::env::consts::ARCH {
+    "x86" => "i686",
+    "aarch64" => "arm64",
+    arch => arch,
+  }
+  .to_string()
+}
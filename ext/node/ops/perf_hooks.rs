@@ -0,0 +1,300 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::OpState;
+use deno_core::Resource;
+use deno_core::ResourceId;
+
+/// Writes the event loop idle and active time, in milliseconds, into `buf`.
+#[op(fast)]
+pub fn op_node_event_loop_utilization(state: &mut OpState, buf: &mut [f64]) {
+  let metrics = &state.event_loop_metrics;
+  buf[0] = metrics.idle_time().as_secs_f64() * 1000.0;
+  buf[1] = metrics.active_time().as_secs_f64() * 1000.0;
+}
+
+/// Number of linearly spaced sub-buckets per power of two. Recorded values
+/// are accurate to within 1/128 (< 1%) of their magnitude.
+const SUB_BUCKET_BITS: u32 = 7;
+const SUB_BUCKET_COUNT: usize = 1 << SUB_BUCKET_BITS;
+const BUCKET_COUNT: usize =
+  (64 - SUB_BUCKET_BITS as usize) * SUB_BUCKET_COUNT + 2 * SUB_BUCKET_COUNT;
+
+/// A log-linear histogram of `u64` values with constant memory usage, similar
+/// to the HdrHistogram used by Node.js.
+#[derive(Debug)]
+pub struct Histogram {
+  counts: Vec<u64>,
+  count: u64,
+  min: u64,
+  max: u64,
+  mean: f64,
+  // sum of squares of differences from the mean (Welford's algorithm)
+  m2: f64,
+  exceeds: u64,
+}
+
+impl Default for Histogram {
+  fn default() -> Self {
+    Self {
+      counts: vec![0; BUCKET_COUNT],
+      count: 0,
+      min: u64::MAX,
+      max: 0,
+      mean: 0.0,
+      m2: 0.0,
+      exceeds: 0,
+    }
+  }
+}
+
+impl Histogram {
+  fn bucket_index(value: u64) -> usize {
+    if value < 2 * SUB_BUCKET_COUNT as u64 {
+      return value as usize;
+    }
+    let shift = 64 - value.leading_zeros() - 1 - SUB_BUCKET_BITS;
+    (shift as usize) * SUB_BUCKET_COUNT + (value >> shift) as usize
+  }
+
+  /// Returns the highest value that falls into the bucket at `index`.
+  fn bucket_value(index: usize) -> u64 {
+    if index < 2 * SUB_BUCKET_COUNT {
+      return index as u64;
+    }
+    let shift = (index / SUB_BUCKET_COUNT - 1) as u32;
+    let sub_bucket = (index - shift as usize * SUB_BUCKET_COUNT) as u64;
+    (sub_bucket << shift) + ((1 << shift) - 1)
+  }
+
+  pub fn record(&mut self, value: u64) -> bool {
+    if value == 0 {
+      self.exceeds += 1;
+      return false;
+    }
+    self.counts[Self::bucket_index(value)] += 1;
+    self.count += 1;
+    self.min = self.min.min(value);
+    self.max = self.max.max(value);
+    let delta = value as f64 - self.mean;
+    self.mean += delta / self.count as f64;
+    self.m2 += delta * (value as f64 - self.mean);
+    true
+  }
+
+  pub fn reset(&mut self) {
+    *self = Self::default();
+  }
+
+  pub fn count(&self) -> u64 {
+    self.count
+  }
+
+  pub fn min(&self) -> u64 {
+    if self.count == 0 {
+      0
+    } else {
+      self.min
+    }
+  }
+
+  pub fn max(&self) -> u64 {
+    self.max
+  }
+
+  pub fn mean(&self) -> f64 {
+    if self.count == 0 {
+      f64::NAN
+    } else {
+      self.mean
+    }
+  }
+
+  pub fn stddev(&self) -> f64 {
+    if self.count == 0 {
+      f64::NAN
+    } else {
+      (self.m2 / self.count as f64).sqrt()
+    }
+  }
+
+  /// Returns the value at the given percentile (0 to 100).
+  pub fn percentile(&self, percentile: f64) -> u64 {
+    if self.count == 0 {
+      return 0;
+    }
+    let percentile = percentile.clamp(0.0, 100.0);
+    let target =
+      ((percentile / 100.0 * self.count as f64).ceil() as u64).max(1);
+    let mut seen = 0;
+    for (index, count) in self.counts.iter().enumerate() {
+      seen += count;
+      if seen >= target {
+        return Self::bucket_value(index).clamp(self.min, self.max);
+      }
+    }
+    self.max
+  }
+
+  /// Returns the distinct (percentile, value) pairs of the recorded values,
+  /// following the same halving steps as HdrHistogram's percentile iterator.
+  pub fn percentiles(&self) -> Vec<(f64, u64)> {
+    let mut percentiles = vec![(0.0, self.min())];
+    if self.count == 0 {
+      return percentiles;
+    }
+    let mut percentile = 50.0;
+    let mut step = 50.0;
+    while percentile < 100.0 {
+      let value = self.percentile(percentile);
+      if percentiles.last().map(|(_, v)| *v) != Some(value) {
+        percentiles.push((percentile, value));
+      }
+      if value == self.max || step < 1e-3 {
+        break;
+      }
+      step /= 2.0;
+      percentile += step;
+    }
+    percentiles.push((100.0, self.max));
+    percentiles
+  }
+}
+
+pub struct HistogramResource(RefCell<Histogram>);
+
+impl Resource for HistogramResource {
+  fn name(&self) -> Cow<str> {
+    "histogram".into()
+  }
+}
+
+fn get_histogram(
+  state: &OpState,
+  rid: ResourceId,
+) -> Result<Rc<HistogramResource>, AnyError> {
+  state.resource_table.get::<HistogramResource>(rid)
+}
+
+#[op]
+pub fn op_node_histogram_new(state: &mut OpState) -> ResourceId {
+  state
+    .resource_table
+    .add(HistogramResource(RefCell::new(Histogram::default())))
+}
+
+#[op]
+pub fn op_node_histogram_record(
+  state: &mut OpState,
+  rid: ResourceId,
+  value: f64,
+) -> Result<bool, AnyError> {
+  let histogram = get_histogram(state, rid)?;
+  let value = if value.is_finite() && value > 0.0 {
+    value as u64
+  } else {
+    0
+  };
+  let recorded = histogram.0.borrow_mut().record(value);
+  Ok(recorded)
+}
+
+#[op]
+pub fn op_node_histogram_reset(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<(), AnyError> {
+  get_histogram(state, rid)?.0.borrow_mut().reset();
+  Ok(())
+}
+
+/// Writes count, min, max, mean, stddev and exceeds into `buf`.
+#[op]
+pub fn op_node_histogram_stats(
+  state: &mut OpState,
+  rid: ResourceId,
+  buf: &mut [f64],
+) -> Result<(), AnyError> {
+  let histogram = get_histogram(state, rid)?;
+  let histogram = histogram.0.borrow();
+  buf[0] = histogram.count() as f64;
+  buf[1] = histogram.min() as f64;
+  buf[2] = histogram.max() as f64;
+  buf[3] = histogram.mean();
+  buf[4] = histogram.stddev();
+  buf[5] = histogram.exceeds as f64;
+  Ok(())
+}
+
+#[op]
+pub fn op_node_histogram_percentile(
+  state: &mut OpState,
+  rid: ResourceId,
+  percentile: f64,
+) -> Result<f64, AnyError> {
+  let histogram = get_histogram(state, rid)?;
+  let value = histogram.0.borrow().percentile(percentile);
+  Ok(value as f64)
+}
+
+#[op]
+pub fn op_node_histogram_percentiles(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<Vec<(f64, f64)>, AnyError> {
+  let histogram = get_histogram(state, rid)?;
+  let percentiles = histogram
+    .0
+    .borrow()
+    .percentiles()
+    .into_iter()
+    .map(|(percentile, value)| (percentile, value as f64))
+    .collect();
+  Ok(percentiles)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bucket_index_roundtrip() {
+    for value in [1, 255, 256, 257, 1000, 10_000_000, u64::MAX / 3, u64::MAX] {
+      let index = Histogram::bucket_index(value);
+      assert!(index < BUCKET_COUNT);
+      let upper = Histogram::bucket_value(index);
+      assert!(upper >= value);
+      assert!((upper - value) as f64 <= value as f64 / 128.0);
+    }
+  }
+
+  #[test]
+  fn histogram_stats() {
+    let mut histogram = Histogram::default();
+    for value in 1..=100 {
+      assert!(histogram.record(value));
+    }
+    assert!(!histogram.record(0));
+    assert_eq!(histogram.count(), 100);
+    assert_eq!(histogram.exceeds, 1);
+    assert_eq!(histogram.min(), 1);
+    assert_eq!(histogram.max(), 100);
+    assert_eq!(histogram.mean(), 50.5);
+    assert_eq!(histogram.percentile(50.0), 50);
+    assert_eq!(histogram.percentile(99.0), 99);
+    assert_eq!(histogram.percentile(100.0), 100);
+    let percentiles = histogram.percentiles();
+    assert_eq!(percentiles.first(), Some(&(0.0, 1)));
+    assert_eq!(percentiles.last(), Some(&(100.0, 100)));
+
+    histogram.reset();
+    assert_eq!(histogram.count(), 0);
+    assert_eq!(histogram.min(), 0);
+    assert!(histogram.mean().is_nan());
+  }
+}
@@ -0,0 +1,76 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Permission checks made by the node polyfills before they call into the
+//! `Deno` namespace, so prompts and errors name the node API that was used
+//! (ex. `node:fs.writeFile()`) instead of the `Deno` API underneath it.
+
+use std::path::Path;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::OpState;
+
+use crate::NodePermissions;
+
+#[op]
+pub fn op_node_check_net<P>(
+  state: &mut OpState,
+  host: String,
+  api_name: String,
+) -> Result<(), AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  state
+    .borrow_mut::<P>()
+    .check_net((host.as_str(), None), &api_name)
+}
+
+#[op]
+pub fn op_node_check_write<P>(
+  state: &mut OpState,
+  path: String,
+  api_name: String,
+) -> Result<(), AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  state
+    .borrow_mut::<P>()
+    .check_write(Path::new(&path), &api_name)
+}
+
+#[op]
+pub fn op_node_check_env<P>(
+  state: &mut OpState,
+  variable: String,
+) -> Result<(), AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  state.borrow_mut::<P>().check_env(&variable)
+}
+
+#[op]
+pub fn op_node_check_run<P>(
+  state: &mut OpState,
+  cmd: String,
+  api_name: String,
+) -> Result<(), AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  state.borrow_mut::<P>().check_run(&cmd, &api_name)
+}
+
+#[op]
+pub fn op_node_check_sys<P>(
+  state: &mut OpState,
+  kind: String,
+  api_name: String,
+) -> Result<(), AnyError>
+where
+  P: NodePermissions + 'static,
+{
+  state.borrow_mut::<P>().check_sys(&kind, &api_name)
+}
@@ -0,0 +1,407 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Native implementations of `querystring.parse()` and
+//! `querystring.stringify()` for the default `querystring.unescape()` and
+//! `querystring.escape()`. They work on UTF-16 code units, so that the
+//! results match the JavaScript implementation for any input, including
+//! lone surrogates.
+
+use deno_core::op;
+use deno_core::U16String;
+
+const PERCENT: u16 = b'%' as u16;
+const PLUS: u16 = b'+' as u16;
+const SPACE: u16 = b' ' as u16;
+
+/// Parses `input` into a flat list of decoded keys and values, in the order
+/// they appear. Repeated keys are kept, it's up to the caller to group them.
+///
+/// A `max_keys` that is `0`, negative or not an integer means there is no
+/// limit to the number of keys.
+#[op]
+pub fn op_node_querystring_parse(
+  input: U16String,
+  sep: U16String,
+  eq: U16String,
+  max_keys: f64,
+) -> Vec<U16String> {
+  parse(&input, &sep, &eq, max_keys)
+    .into_iter()
+    .map(U16String::from)
+    .collect()
+}
+
+/// Joins a flat list of keys and values into a query string, percent
+/// encoding them. Returns `None` if one of them is not a valid URI
+/// component.
+#[op]
+pub fn op_node_querystring_stringify(
+  fields: Vec<U16String>,
+  sep: U16String,
+  eq: U16String,
+) -> Option<U16String> {
+  let mut out = Vec::new();
+  for (i, pair) in fields.chunks(2).enumerate() {
+    if i > 0 {
+      out.extend_from_slice(&sep);
+    }
+    encode_into(&mut out, &pair[0])?;
+    out.extend_from_slice(&eq);
+    if let Some(value) = pair.get(1) {
+      encode_into(&mut out, value)?;
+    }
+  }
+  Some(out.into())
+}
+
+/// A port of the parsing loop of `querystring.parse()`.
+fn parse(s: &[u16], sep: &[u16], eq: &[u16], max_keys: f64) -> Vec<Vec<u16>> {
+  let mut out = Vec::new();
+  if s.is_empty() {
+    return out;
+  }
+  let sep = if sep.is_empty() {
+    &[b'&' as u16][..]
+  } else {
+    sep
+  };
+  let eq = if eq.is_empty() {
+    &[b'=' as u16][..]
+  } else {
+    eq
+  };
+
+  // decremented for every pair and checked for exactly 0, so anything
+  // that never gets there means unlimited, like in JavaScript
+  let mut pairs = if max_keys > 0.0 { max_keys } else { -1.0 };
+
+  let mut last_pos = 0;
+  let mut sep_idx = 0;
+  let mut eq_idx = 0;
+  let mut key = Vec::new();
+  let mut value = Vec::new();
+  let mut key_encoded = false;
+  let mut val_encoded = false;
+  let mut encode_check = 0;
+  for (i, &code) in s.iter().enumerate() {
+    // try matching the key/value pair separator (ex. '&')
+    if code == sep[sep_idx] {
+      sep_idx += 1;
+      if sep_idx == sep.len() {
+        let end = i + 1 - sep_idx;
+        if eq_idx < eq.len() {
+          // we didn't find the (entire) key/value separator
+          if last_pos < end {
+            // treat the substring as part of the key instead of the value
+            key.extend_from_slice(&s[last_pos..end]);
+          } else if key.is_empty() {
+            // we saw an empty substring between separators
+            pairs -= 1.0;
+            if pairs == 0.0 {
+              return out;
+            }
+            last_pos = i + 1;
+            sep_idx = 0;
+            eq_idx = 0;
+            continue;
+          }
+        } else if last_pos < end {
+          value.extend_from_slice(&s[last_pos..end]);
+        }
+
+        push_pair(
+          &mut out,
+          std::mem::take(&mut key),
+          std::mem::take(&mut value),
+          key_encoded,
+          val_encoded,
+        );
+
+        pairs -= 1.0;
+        if pairs == 0.0 {
+          return out;
+        }
+        encode_check = 0;
+        last_pos = i + 1;
+        sep_idx = 0;
+        eq_idx = 0;
+      }
+      continue;
+    }
+
+    sep_idx = 0;
+    // try matching the key/value separator (ex. '=') if we haven't already
+    if eq_idx < eq.len() {
+      if code == eq[eq_idx] {
+        eq_idx += 1;
+        if eq_idx == eq.len() {
+          let end = i + 1 - eq_idx;
+          if last_pos < end {
+            key.extend_from_slice(&s[last_pos..end]);
+          }
+          encode_check = 0;
+          last_pos = i + 1;
+        }
+        continue;
+      }
+      eq_idx = 0;
+      if !key_encoded {
+        // only look for a valid encoded byte once, to avoid decoding
+        // keys that don't need it
+        if code == PERCENT {
+          encode_check = 1;
+          continue;
+        } else if encode_check > 0 {
+          if is_hex(code) {
+            encode_check += 1;
+            if encode_check == 3 {
+              key_encoded = true;
+            }
+            continue;
+          }
+          encode_check = 0;
+        }
+      }
+      if code == PLUS {
+        if last_pos < i {
+          key.extend_from_slice(&s[last_pos..i]);
+        }
+        key.push(SPACE);
+        last_pos = i + 1;
+        continue;
+      }
+    }
+    if code == PLUS {
+      if last_pos < i {
+        value.extend_from_slice(&s[last_pos..i]);
+      }
+      value.push(SPACE);
+      last_pos = i + 1;
+    } else if !val_encoded {
+      if code == PERCENT {
+        encode_check = 1;
+      } else if encode_check > 0 {
+        if is_hex(code) {
+          encode_check += 1;
+          if encode_check == 3 {
+            val_encoded = true;
+          }
+        } else {
+          encode_check = 0;
+        }
+      }
+    }
+  }
+
+  // deal with any leftover key or value data
+  if last_pos < s.len() {
+    if eq_idx < eq.len() {
+      key.extend_from_slice(&s[last_pos..]);
+    } else if sep_idx < sep.len() {
+      value.extend_from_slice(&s[last_pos..]);
+    }
+  } else if eq_idx == 0 && key.is_empty() {
+    // we ended on an empty substring
+    return out;
+  }
+
+  push_pair(&mut out, key, value, key_encoded, val_encoded);
+  out
+}
+
+fn push_pair(
+  out: &mut Vec<Vec<u16>>,
+  key: Vec<u16>,
+  value: Vec<u16>,
+  key_encoded: bool,
+  val_encoded: bool,
+) {
+  out.push(if key_encoded && !key.is_empty() {
+    unescape(&key)
+  } else {
+    key
+  });
+  out.push(if val_encoded && !value.is_empty() {
+    unescape(&value)
+  } else {
+    value
+  });
+}
+
+fn is_hex(code: u16) -> bool {
+  code < 0x80 && (code as u8).is_ascii_hexdigit()
+}
+
+fn hex_value(code: u16) -> Option<u8> {
+  if code < 0x80 {
+    (code as u8 as char).to_digit(16).map(|d| d as u8)
+  } else {
+    None
+  }
+}
+
+/// The byte encoded by the `%XX` sequence at `index`, if there is one.
+fn percent_byte(s: &[u16], index: usize) -> Option<u8> {
+  if s.get(index) != Some(&PERCENT) {
+    return None;
+  }
+  let high = hex_value(*s.get(index + 1)?)?;
+  let low = hex_value(*s.get(index + 2)?)?;
+  Some(high * 16 + low)
+}
+
+/// `querystring.unescape()`, which is `decodeURIComponent()` falling back to
+/// `querystring.unescapeBuffer()` for malformed input.
+fn unescape(s: &[u16]) -> Vec<u16> {
+  decode_uri_component(s).unwrap_or_else(|| unescape_buffer(s))
+}
+
+/// `decodeURIComponent()`, returning `None` where it would throw.
+fn decode_uri_component(s: &[u16]) -> Option<Vec<u16>> {
+  let mut out = Vec::with_capacity(s.len());
+  let mut i = 0;
+  while i < s.len() {
+    if s[i] != PERCENT {
+      out.push(s[i]);
+      i += 1;
+      continue;
+    }
+    let first = percent_byte(s, i)?;
+    i += 3;
+    let len = match first.leading_ones() {
+      0 => {
+        out.push(first as u16);
+        continue;
+      }
+      n @ 2..=4 => n as usize,
+      _ => return None,
+    };
+    let mut bytes = [first, 0, 0, 0];
+    for byte in bytes.iter_mut().take(len).skip(1) {
+      *byte = percent_byte(s, i)?;
+      i += 3;
+    }
+    let decoded = std::str::from_utf8(&bytes[..len]).ok()?;
+    out.extend(decoded.encode_utf16());
+  }
+  Some(out)
+}
+
+/// `querystring.unescapeBuffer()` followed by decoding the buffer as UTF-8.
+fn unescape_buffer(s: &[u16]) -> Vec<u16> {
+  let mut bytes = Vec::with_capacity(s.len());
+  let mut i = 0;
+  while i < s.len() {
+    if let Some(byte) = percent_byte(s, i) {
+      bytes.push(byte);
+      i += 3;
+    } else {
+      // like a `Buffer`, only the lowest byte of the code unit is kept
+      bytes.push(s[i] as u8);
+      i += 1;
+    }
+  }
+  String::from_utf8_lossy(&bytes).encode_utf16().collect()
+}
+
+/// `querystring.escape()`, appending to `out`. Returns `None` where it
+/// would throw.
+fn encode_into(out: &mut Vec<u16>, s: &[u16]) -> Option<()> {
+  let mut i = 0;
+  while i < s.len() {
+    let c = s[i] as u32;
+    i += 1;
+    if c < 0x80 {
+      if no_escape(c as u8) {
+        out.push(c as u16);
+      } else {
+        push_hex(out, c);
+      }
+    } else if c < 0x800 {
+      push_hex(out, 0xc0 | (c >> 6));
+      push_hex(out, 0x80 | (c & 0x3f));
+    } else if !(0xd800..0xe000).contains(&c) {
+      push_hex(out, 0xe0 | (c >> 12));
+      push_hex(out, 0x80 | ((c >> 6) & 0x3f));
+      push_hex(out, 0x80 | (c & 0x3f));
+    } else {
+      // a surrogate pair, which the JavaScript implementation doesn't
+      // validate beyond there being a second code unit
+      let c2 = *s.get(i)? as u32 & 0x3ff;
+      i += 1;
+      let c = 0x10000 + (((c & 0x3ff) << 10) | c2);
+      push_hex(out, 0xf0 | (c >> 18));
+      push_hex(out, 0x80 | ((c >> 12) & 0x3f));
+      push_hex(out, 0x80 | ((c >> 6) & 0x3f));
+      push_hex(out, 0x80 | (c & 0x3f));
+    }
+  }
+  Some(())
+}
+
+/// The characters that are left as is: `A-Z a-z 0-9 - _ . ! ~ * ' ( )`.
+fn no_escape(c: u8) -> bool {
+  c.is_ascii_alphanumeric()
+    || matches!(
+      c,
+      b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')'
+    )
+}
+
+fn push_hex(out: &mut Vec<u16>, byte: u32) {
+  const HEX: &[u8; 16] = b"0123456789ABCDEF";
+  out.push(PERCENT);
+  out.push(HEX[(byte >> 4) as usize & 0xf] as u16);
+  out.push(HEX[byte as usize & 0xf] as u16);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn utf16(s: &str) -> Vec<u16> {
+    s.encode_utf16().collect()
+  }
+
+  fn parse_str(s: &str, sep: &str, eq: &str, max_keys: f64) -> Vec<String> {
+    parse(&utf16(s), &utf16(sep), &utf16(eq), max_keys)
+      .iter()
+      .map(|s| String::from_utf16(s).unwrap())
+      .collect()
+  }
+
+  #[test]
+  fn parses_pairs() {
+    assert_eq!(
+      parse_str("a=1&b=%20x+y&a=2&&c&=d&", "&", "=", 1000.0),
+      vec!["a", "1", "b", " x y", "a", "2", "c", "", "", "d"]
+    );
+    assert_eq!(
+      parse_str("foo==>bar, bar==>baz", ", ", "==>", 1000.0),
+      vec!["foo", "bar", "bar", "baz"]
+    );
+    assert_eq!(
+      parse_str("%E4%BD%A0=%zz%41&%FF=1", "&", "=", 1000.0),
+      vec!["你", "%zzA", "\u{FFFD}", "1"]
+    );
+  }
+
+  #[test]
+  fn max_keys() {
+    assert_eq!(parse_str("a&b&c", "&", "=", 2.0), vec!["a", "", "b", ""]);
+    assert_eq!(parse_str("&&a", "&", "=", 2.0), Vec::<String>::new());
+    assert_eq!(parse_str("a&b&c", "&", "=", 1.5).len(), 6);
+    assert_eq!(parse_str("a&b&c", "&", "=", 0.0).len(), 6);
+  }
+
+  #[test]
+  fn encodes() {
+    let mut out = Vec::new();
+    encode_into(&mut out, &utf16("a b&c=d!~*'()é😀")).unwrap();
+    assert_eq!(
+      String::from_utf16(&out).unwrap(),
+      "a%20b%26c%3Dd!~*'()%C3%A9%F0%9F%98%80"
+    );
+    assert!(encode_into(&mut Vec::new(), &[0xd800]).is_none());
+  }
+}
@@ -10,6 +10,7 @@ use deno_core::JsRuntimeInspector;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
 use deno_fs::FileSystemRc;
+use deno_fs::RealPathCache;
 use std::cell::RefCell;
 use std::path::Path;
 use std::path::PathBuf;
@@ -279,9 +280,10 @@ where
 {
   let path = PathBuf::from(request);
   ensure_read_permission::<P>(state, &path)?;
-  let fs = state.borrow::<FileSystemRc>();
-  let canonicalized_path =
-    deno_core::strip_unc_prefix(fs.realpath_sync(&path)?);
+  let fs = state.borrow::<FileSystemRc>().clone();
+  let canonicalized_path = deno_core::strip_unc_prefix(
+    state.borrow_mut::<RealPathCache>().realpath(&*fs, &path)?,
+  );
   Ok(canonicalized_path.to_string_lossy().to_string())
 }
 
@@ -404,7 +406,7 @@ where
         exports,
         &referrer,
         NodeModuleKind::Cjs,
-        resolution::REQUIRE_CONDITIONS,
+        &node_resolver.conditions(resolution::REQUIRE_CONDITIONS),
         NodeResolutionMode::Execution,
         permissions,
       )
@@ -428,6 +430,13 @@ where
   Ok(fs.read_to_string(&file_path)?)
 }
 
+#[op]
+fn op_require_clear_cache(filename: String) {
+  if let Some(dir) = Path::new(&filename).parent() {
+    PackageJson::clear_cache_for_dir(dir);
+  }
+}
+
 #[op]
 pub fn op_require_as_file_path(file_or_url: String) -> String {
   if let Ok(url) = Url::parse(&file_or_url) {
@@ -485,7 +494,7 @@ where
         exports,
         &referrer,
         NodeModuleKind::Cjs,
-        resolution::REQUIRE_CONDITIONS,
+        &node_resolver.conditions(resolution::REQUIRE_CONDITIONS),
         NodeResolutionMode::Execution,
         permissions,
       )
@@ -544,22 +553,20 @@ where
   ensure_read_permission::<P>(state, &parent_path)?;
   let node_resolver = state.borrow::<Rc<NodeResolver>>();
   let permissions = state.borrow::<P>();
-  let pkg = node_resolver
-    .load_package_json(permissions, parent_path.join("package.json"))?;
+  let referrer = Url::from_file_path(&parent_path).unwrap();
+  let pkg = node_resolver.get_package_scope_config(&referrer, permissions)?;
 
   if pkg.imports.is_some() {
-    let referrer =
-      deno_core::url::Url::from_file_path(&parent_filename).unwrap();
     node_resolver
       .package_imports_resolve(
         &request,
         &referrer,
         NodeModuleKind::Cjs,
-        resolution::REQUIRE_CONDITIONS,
+        &node_resolver.conditions(resolution::REQUIRE_CONDITIONS),
         NodeResolutionMode::Execution,
         permissions,
       )
-      .map(|r| Some(Url::from_file_path(r).unwrap().to_string()))
+      .map(|r| Some(r.to_string_lossy().to_string()))
   } else {
     Ok(None)
   }
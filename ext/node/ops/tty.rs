@@ -0,0 +1,230 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use deno_core::op;
+use deno_core::OpState;
+
+use crate::NodePermissions;
+
+const COLORS_2: u8 = 1;
+const COLORS_16: u8 = 4;
+const COLORS_256: u8 = 8;
+const COLORS_16M: u8 = 24;
+
+const TERM_ENVS: &[(&str, u8)] = &[
+  ("eterm", COLORS_16),
+  ("cons25", COLORS_16),
+  ("console", COLORS_16),
+  ("cygwin", COLORS_16),
+  ("dtterm", COLORS_16),
+  ("gnome", COLORS_16),
+  ("hurd", COLORS_16),
+  ("jfbterm", COLORS_16),
+  ("konsole", COLORS_16),
+  ("kterm", COLORS_16),
+  ("mlterm", COLORS_16),
+  ("mosh", COLORS_16M),
+  ("putty", COLORS_16),
+  ("st", COLORS_16),
+  ("rxvt-unicode-24bit", COLORS_16M),
+  ("terminator", COLORS_16M),
+  ("xterm-kitty", COLORS_16M),
+];
+
+const CI_ENVS: &[(&str, u8)] = &[
+  ("APPVEYOR", COLORS_256),
+  ("BUILDKITE", COLORS_256),
+  ("CIRCLECI", COLORS_16M),
+  ("DRONE", COLORS_256),
+  ("GITEA_ACTIONS", COLORS_16M),
+  ("GITHUB_ACTIONS", COLORS_16M),
+  ("GITLAB_CI", COLORS_256),
+  ("TRAVIS", COLORS_256),
+];
+
+/// Gets the number of bits per color that the terminal supports, the way
+/// `tty.WriteStream.prototype.getColorDepth()` does in node.
+///
+/// When no environment is given, the one of the process is used, which
+/// requires env access to the variables that are looked at. Without it, no
+/// colors are assumed.
+#[op]
+pub fn op_node_tty_color_depth<P>(
+  state: &mut OpState,
+  env: Option<HashMap<String, String>>,
+) -> u8
+where
+  P: NodePermissions + 'static,
+{
+  let Some(env) = env else {
+    let permissions = RefCell::new(state.borrow_mut::<P>());
+    let denied = Cell::new(false);
+    let depth = color_depth(|name| {
+      if permissions.borrow_mut().check_env(name).is_ok() {
+        std::env::var(name).ok()
+      } else {
+        denied.set(true);
+        None
+      }
+    });
+    return if denied.get() { COLORS_2 } else { depth };
+  };
+  color_depth(|name| env.get(name).cloned())
+}
+
+fn color_depth(env: impl Fn(&str) -> Option<String>) -> u8 {
+  let has = |name: &str| env(name).is_some();
+
+  if let Some(force_color) = env("FORCE_COLOR") {
+    return match force_color.as_str() {
+      "" | "1" | "true" => COLORS_16,
+      "2" => COLORS_256,
+      "3" => COLORS_16M,
+      _ => COLORS_2,
+    };
+  }
+
+  let term = env("TERM");
+  if has("NODE_DISABLE_COLORS")
+    || has("NO_COLOR")
+    // the "dumb" terminal doesn't support ANSI color control codes
+    || term.as_deref() == Some("dumb")
+  {
+    return COLORS_2;
+  }
+
+  if cfg!(windows) {
+    // Windows 10 build 14931, the first one with true color support in the
+    // console, is older than any version deno runs on
+    return COLORS_16M;
+  }
+
+  if has("TMUX") {
+    return COLORS_16M;
+  }
+
+  // Azure DevOps
+  if has("TF_BUILD") && has("AGENT_NAME") {
+    return COLORS_16;
+  }
+
+  if has("CI") {
+    if let Some((_, colors)) = CI_ENVS.iter().find(|(name, _)| has(name)) {
+      return *colors;
+    }
+    if env("CI_NAME").as_deref() == Some("codeship") {
+      return COLORS_256;
+    }
+    return COLORS_2;
+  }
+
+  if let Some(version) = env("TEAMCITY_VERSION") {
+    return if lazy_regex::regex_is_match!(
+      r"^(9\.(0*[1-9]\d*)\.|\d{2,}\.)",
+      &version
+    ) {
+      COLORS_16
+    } else {
+      COLORS_2
+    };
+  }
+
+  match env("TERM_PROGRAM").as_deref() {
+    Some("iTerm.app") => {
+      return match env("TERM_PROGRAM_VERSION") {
+        Some(version)
+          if !lazy_regex::regex_is_match!(r"^[0-2]\.", &version) =>
+        {
+          COLORS_16M
+        }
+        _ => COLORS_256,
+      };
+    }
+    Some("HyperTerm" | "MacTerm") => return COLORS_16M,
+    Some("Apple_Terminal") => return COLORS_256,
+    _ => {}
+  }
+
+  let colorterm = env("COLORTERM");
+  if matches!(colorterm.as_deref(), Some("truecolor" | "24bit")) {
+    return COLORS_16M;
+  }
+
+  if let Some(term) = term {
+    if term.starts_with("xterm-256") {
+      return COLORS_256;
+    }
+    let term = term.to_lowercase();
+    if let Some((_, colors)) = TERM_ENVS.iter().find(|(name, _)| *name == term)
+    {
+      return *colors;
+    }
+    if ["ansi", "color", "linux"]
+      .iter()
+      .any(|pattern| term.contains(pattern))
+      || lazy_regex::regex_is_match!(r"^con[0-9]*x[0-9]", &term)
+      || ["rxvt", "screen", "xterm", "vt100"]
+        .iter()
+        .any(|prefix| term.starts_with(prefix))
+    {
+      return COLORS_16;
+    }
+  }
+
+  // a 16 color COLORTERM comes after the 256 and true color terminals
+  if colorterm.is_some() {
+    return COLORS_16;
+  }
+  COLORS_2
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn depth(vars: &[(&str, &str)]) -> u8 {
+    let env = vars
+      .iter()
+      .map(|(name, value)| (name.to_string(), value.to_string()))
+      .collect::<HashMap<_, _>>();
+    color_depth(|name| env.get(name).cloned())
+  }
+
+  #[test]
+  fn force_and_disable_colors() {
+    assert_eq!(depth(&[("FORCE_COLOR", "")]), COLORS_16);
+    assert_eq!(depth(&[("FORCE_COLOR", "3"), ("NO_COLOR", "")]), COLORS_16M);
+    assert_eq!(depth(&[("FORCE_COLOR", "0")]), COLORS_2);
+    assert_eq!(depth(&[("NO_COLOR", ""), ("TMUX", "1")]), COLORS_2);
+    assert_eq!(depth(&[("TERM", "dumb")]), COLORS_2);
+  }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn terminals() {
+    assert_eq!(depth(&[]), COLORS_2);
+    assert_eq!(depth(&[("TMUX", "1")]), COLORS_16M);
+    assert_eq!(depth(&[("CI", "1"), ("GITHUB_ACTIONS", "1")]), COLORS_16M);
+    assert_eq!(depth(&[("CI", "1"), ("TERM", "xterm-256color")]), COLORS_2);
+    assert_eq!(depth(&[("TEAMCITY_VERSION", "2023.05")]), COLORS_16);
+    assert_eq!(depth(&[("TEAMCITY_VERSION", "8.1.0")]), COLORS_2);
+    assert_eq!(depth(&[("TERM_PROGRAM", "iTerm.app")]), COLORS_256);
+    assert_eq!(
+      depth(&[
+        ("TERM_PROGRAM", "iTerm.app"),
+        ("TERM_PROGRAM_VERSION", "3.4.19")
+      ]),
+      COLORS_16M
+    );
+    assert_eq!(depth(&[("COLORTERM", "truecolor")]), COLORS_16M);
+    assert_eq!(depth(&[("TERM", "xterm-256color")]), COLORS_256);
+    assert_eq!(depth(&[("TERM", "xterm-kitty")]), COLORS_16M);
+    assert_eq!(depth(&[("TERM", "screen")]), COLORS_16);
+    assert_eq!(depth(&[("TERM", "con80x25")]), COLORS_16);
+    assert_eq!(depth(&[("TERM", "unknown")]), COLORS_2);
+    assert_eq!(depth(&[("TERM", "unknown"), ("COLORTERM", "1")]), COLORS_16);
+  }
+}
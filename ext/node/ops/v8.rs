@@ -1,6 +1,8 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 use deno_core::op;
 use deno_core::v8;
+use deno_core::ZeroCopyBuf;
+use serde::Serialize;
 
 #[op]
 fn op_v8_cached_data_version_tag() -> u32 {
@@ -27,3 +29,40 @@ fn op_v8_get_heap_statistics(scope: &mut v8::HandleScope, buffer: &mut [f64]) {
   buffer[12] = stats.used_global_handles_size() as f64;
   buffer[13] = stats.external_memory() as f64;
 }
+
+#[derive(Serialize)]
+struct HeapSpaceStatistics {
+  space_name: String,
+  space_size: f64,
+  space_used_size: f64,
+  space_available_size: f64,
+  physical_space_size: f64,
+}
+
+#[op(v8)]
+fn op_v8_get_heap_space_statistics(
+  scope: &mut v8::HandleScope,
+) -> Vec<HeapSpaceStatistics> {
+  (0..scope.number_of_heap_spaces())
+    .filter_map(|index| scope.get_heap_space_statistics(index))
+    .map(|stats| HeapSpaceStatistics {
+      space_name: stats.space_name().to_string_lossy().into_owned(),
+      space_size: stats.space_size() as f64,
+      space_used_size: stats.space_used_size() as f64,
+      space_available_size: stats.space_available_size() as f64,
+      physical_space_size: stats.physical_space_size() as f64,
+    })
+    .collect()
+}
+
+/// Takes a heap snapshot and returns it serialized in the `.heapsnapshot`
+/// JSON format understood by Chrome DevTools.
+#[op(v8)]
+fn op_v8_take_heap_snapshot(scope: &mut v8::HandleScope) -> ZeroCopyBuf {
+  let mut snapshot = Vec::new();
+  scope.take_heap_snapshot(|chunk| {
+    snapshot.extend_from_slice(chunk);
+    true
+  });
+  snapshot.into()
+}
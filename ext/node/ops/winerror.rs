@@ -26,109 +26,140 @@
 
 use deno_core::op;
 
+/// The system errors that libuv translates, along with their libuv error
+/// names.
+///
+/// When several system errors translate to the same name, the first one is
+/// the one the name translates back to.
+const UV_ERRORS: &[(i32, &str)] = &[
+  (ERROR_NOACCESS, "EACCES"),
+  (WSAEACCES, "EACCES"),
+  (ERROR_ELEVATION_REQUIRED, "EACCES"),
+  (ERROR_CANT_ACCESS_FILE, "EACCES"),
+  (ERROR_ADDRESS_ALREADY_ASSOCIATED, "EADDRINUSE"),
+  (WSAEADDRINUSE, "EADDRINUSE"),
+  (WSAEADDRNOTAVAIL, "EADDRNOTAVAIL"),
+  (WSAEAFNOSUPPORT, "EAFNOSUPPORT"),
+  (WSAEWOULDBLOCK, "EAGAIN"),
+  (WSAEALREADY, "EALREADY"),
+  (ERROR_INVALID_HANDLE, "EBADF"),
+  (ERROR_INVALID_FLAGS, "EBADF"),
+  (ERROR_LOCK_VIOLATION, "EBUSY"),
+  (ERROR_PIPE_BUSY, "EBUSY"),
+  (ERROR_SHARING_VIOLATION, "EBUSY"),
+  (ERROR_OPERATION_ABORTED, "ECANCELED"),
+  (WSAEINTR, "ECANCELED"),
+  (ERROR_NO_UNICODE_TRANSLATION, "ECHARSET"),
+  (ERROR_CONNECTION_ABORTED, "ECONNABORTED"),
+  (WSAECONNABORTED, "ECONNABORTED"),
+  (ERROR_CONNECTION_REFUSED, "ECONNREFUSED"),
+  (WSAECONNREFUSED, "ECONNREFUSED"),
+  (ERROR_NETNAME_DELETED, "ECONNRESET"),
+  (WSAECONNRESET, "ECONNRESET"),
+  (ERROR_FILE_EXISTS, "EEXIST"),
+  (ERROR_ALREADY_EXISTS, "EEXIST"),
+  (ERROR_BUFFER_OVERFLOW, "EFAULT"),
+  (WSAEFAULT, "EFAULT"),
+  (ERROR_HOST_UNREACHABLE, "EHOSTUNREACH"),
+  (WSAEHOSTUNREACH, "EHOSTUNREACH"),
+  (ERROR_INVALID_PARAMETER, "EINVAL"),
+  (ERROR_INSUFFICIENT_BUFFER, "EINVAL"),
+  (ERROR_INVALID_DATA, "EINVAL"),
+  (ERROR_INVALID_NAME, "EINVAL"),
+  (WSAEINVAL, "EINVAL"),
+  (WSAEPFNOSUPPORT, "EINVAL"),
+  (ERROR_NOT_A_REPARSE_POINT, "EINVAL"),
+  (ERROR_IO_DEVICE, "EIO"),
+  (ERROR_BEGINNING_OF_MEDIA, "EIO"),
+  (ERROR_BUS_RESET, "EIO"),
+  (ERROR_CRC, "EIO"),
+  (ERROR_DEVICE_DOOR_OPEN, "EIO"),
+  (ERROR_DEVICE_REQUIRES_CLEANING, "EIO"),
+  (ERROR_DISK_CORRUPT, "EIO"),
+  (ERROR_EOM_OVERFLOW, "EIO"),
+  (ERROR_FILEMARK_DETECTED, "EIO"),
+  (ERROR_GEN_FAILURE, "EIO"),
+  (ERROR_INVALID_BLOCK_LENGTH, "EIO"),
+  (ERROR_NO_DATA_DETECTED, "EIO"),
+  (ERROR_NO_SIGNAL_SENT, "EIO"),
+  (ERROR_OPEN_FAILED, "EIO"),
+  (ERROR_SETMARK_DETECTED, "EIO"),
+  (ERROR_SIGNAL_REFUSED, "EIO"),
+  (WSAEISCONN, "EISCONN"),
+  (ERROR_CANT_RESOLVE_FILENAME, "ELOOP"),
+  (ERROR_TOO_MANY_OPEN_FILES, "EMFILE"),
+  (WSAEMFILE, "EMFILE"),
+  (WSAEMSGSIZE, "EMSGSIZE"),
+  (ERROR_FILENAME_EXCED_RANGE, "ENAMETOOLONG"),
+  (ERROR_NETWORK_UNREACHABLE, "ENETUNREACH"),
+  (WSAENETUNREACH, "ENETUNREACH"),
+  (WSAENOBUFS, "ENOBUFS"),
+  (ERROR_FILE_NOT_FOUND, "ENOENT"),
+  (ERROR_BAD_PATHNAME, "ENOENT"),
+  (ERROR_ENVVAR_NOT_FOUND, "ENOENT"),
+  (ERROR_INVALID_DRIVE, "ENOENT"),
+  (ERROR_INVALID_REPARSE_DATA, "ENOENT"),
+  (ERROR_MOD_NOT_FOUND, "ENOENT"),
+  (ERROR_PATH_NOT_FOUND, "ENOENT"),
+  (WSAHOST_NOT_FOUND, "ENOENT"),
+  (WSANO_DATA, "ENOENT"),
+  (ERROR_DIRECTORY, "ENOTDIR"),
+  (ERROR_NOT_ENOUGH_MEMORY, "ENOMEM"),
+  (ERROR_OUTOFMEMORY, "ENOMEM"),
+  (ERROR_CANNOT_MAKE, "ENOSPC"),
+  (ERROR_DISK_FULL, "ENOSPC"),
+  (ERROR_EA_TABLE_FULL, "ENOSPC"),
+  (ERROR_END_OF_MEDIA, "ENOSPC"),
+  (ERROR_HANDLE_DISK_FULL, "ENOSPC"),
+  (ERROR_NOT_CONNECTED, "ENOTCONN"),
+  (WSAENOTCONN, "ENOTCONN"),
+  (ERROR_DIR_NOT_EMPTY, "ENOTEMPTY"),
+  (WSAENOTSOCK, "ENOTSOCK"),
+  (ERROR_NOT_SUPPORTED, "ENOTSUP"),
+  (ERROR_BROKEN_PIPE, "EOF"),
+  (ERROR_ACCESS_DENIED, "EPERM"),
+  (ERROR_PRIVILEGE_NOT_HELD, "EPERM"),
+  (ERROR_BAD_PIPE, "EPIPE"),
+  (ERROR_NO_DATA, "EPIPE"),
+  (ERROR_PIPE_NOT_CONNECTED, "EPIPE"),
+  (WSAESHUTDOWN, "EPIPE"),
+  (WSAEPROTONOSUPPORT, "EPROTONOSUPPORT"),
+  (ERROR_WRITE_PROTECT, "EROFS"),
+  (ERROR_SEM_TIMEOUT, "ETIMEDOUT"),
+  (WSAETIMEDOUT, "ETIMEDOUT"),
+  (ERROR_NOT_SAME_DEVICE, "EXDEV"),
+  (ERROR_INVALID_FUNCTION, "EISDIR"),
+  (ERROR_META_EXPANSION_TOO_LONG, "E2BIG"),
+  (WSAESOCKTNOSUPPORT, "ESOCKTNOSUPPORT"),
+  (ERROR_BAD_EXE_FORMAT, "EFTYPE"),
+];
+
+/// Translates a Windows system error to the name of the libuv error, like
+/// `uv_translate_sys_error()` does.
+pub fn sys_to_uv_error(err: i32) -> &'static str {
+  UV_ERRORS
+    .iter()
+    .find(|(sys_err, _)| *sys_err == err)
+    .map(|(_, uv_err)| *uv_err)
+    .unwrap_or("UNKNOWN")
+}
+
+/// Translates the name of a libuv error back to a Windows system error.
+pub fn uv_to_sys_error(name: &str) -> Option<i32> {
+  UV_ERRORS
+    .iter()
+    .find(|(_, uv_err)| *uv_err == name)
+    .map(|(sys_err, _)| *sys_err)
+}
+
 #[op]
 fn op_node_sys_to_uv_error(err: i32) -> String {
-  let uv_err = match err {
-    ERROR_ACCESS_DENIED => "EACCES",
-    ERROR_NOACCESS => "EACCES",
-    WSAEACCES => "EACCES",
-    ERROR_CANT_ACCESS_FILE => "EACCES",
-    ERROR_ADDRESS_ALREADY_ASSOCIATED => "EADDRINUSE",
-    WSAEADDRINUSE => "EADDRINUSE",
-    WSAEADDRNOTAVAIL => "EADDRNOTAVAIL",
-    WSAEAFNOSUPPORT => "EAFNOSUPPORT",
-    WSAEWOULDBLOCK => "EAGAIN",
-    WSAEALREADY => "EALREADY",
-    ERROR_INVALID_FLAGS => "EBADF",
-    ERROR_INVALID_HANDLE => "EBADF",
-    ERROR_LOCK_VIOLATION => "EBUSY",
-    ERROR_PIPE_BUSY => "EBUSY",
-    ERROR_SHARING_VIOLATION => "EBUSY",
-    ERROR_OPERATION_ABORTED => "ECANCELED",
-    WSAEINTR => "ECANCELED",
-    ERROR_NO_UNICODE_TRANSLATION => "ECHARSET",
-    ERROR_CONNECTION_ABORTED => "ECONNABORTED",
-    WSAECONNABORTED => "ECONNABORTED",
-    ERROR_CONNECTION_REFUSED => "ECONNREFUSED",
-    WSAECONNREFUSED => "ECONNREFUSED",
-    ERROR_NETNAME_DELETED => "ECONNRESET",
-    WSAECONNRESET => "ECONNRESET",
-    ERROR_ALREADY_EXISTS => "EEXIST",
-    ERROR_FILE_EXISTS => "EEXIST",
-    ERROR_BUFFER_OVERFLOW => "EFAULT",
-    WSAEFAULT => "EFAULT",
-    ERROR_HOST_UNREACHABLE => "EHOSTUNREACH",
-    WSAEHOSTUNREACH => "EHOSTUNREACH",
-    ERROR_INSUFFICIENT_BUFFER => "EINVAL",
-    ERROR_INVALID_DATA => "EINVAL",
-    ERROR_INVALID_NAME => "EINVAL",
-    ERROR_INVALID_PARAMETER => "EINVAL",
-    WSAEINVAL => "EINVAL",
-    WSAEPFNOSUPPORT => "EINVAL",
-    ERROR_BEGINNING_OF_MEDIA => "EIO",
-    ERROR_BUS_RESET => "EIO",
-    ERROR_CRC => "EIO",
-    ERROR_DEVICE_DOOR_OPEN => "EIO",
-    ERROR_DEVICE_REQUIRES_CLEANING => "EIO",
-    ERROR_DISK_CORRUPT => "EIO",
-    ERROR_EOM_OVERFLOW => "EIO",
-    ERROR_FILEMARK_DETECTED => "EIO",
-    ERROR_GEN_FAILURE => "EIO",
-    ERROR_INVALID_BLOCK_LENGTH => "EIO",
-    ERROR_IO_DEVICE => "EIO",
-    ERROR_NO_DATA_DETECTED => "EIO",
-    ERROR_NO_SIGNAL_SENT => "EIO",
-    ERROR_OPEN_FAILED => "EIO",
-    ERROR_SETMARK_DETECTED => "EIO",
-    ERROR_SIGNAL_REFUSED => "EIO",
-    WSAEISCONN => "EISCONN",
-    ERROR_CANT_RESOLVE_FILENAME => "ELOOP",
-    ERROR_TOO_MANY_OPEN_FILES => "EMFILE",
-    WSAEMFILE => "EMFILE",
-    WSAEMSGSIZE => "EMSGSIZE",
-    ERROR_FILENAME_EXCED_RANGE => "ENAMETOOLONG",
-    ERROR_NETWORK_UNREACHABLE => "ENETUNREACH",
-    WSAENETUNREACH => "ENETUNREACH",
-    WSAENOBUFS => "ENOBUFS",
-    ERROR_BAD_PATHNAME => "ENOENT",
-    ERROR_DIRECTORY => "ENOTDIR",
-    ERROR_ENVVAR_NOT_FOUND => "ENOENT",
-    ERROR_FILE_NOT_FOUND => "ENOENT",
-    ERROR_INVALID_DRIVE => "ENOENT",
-    ERROR_INVALID_REPARSE_DATA => "ENOENT",
-    ERROR_MOD_NOT_FOUND => "ENOENT",
-    ERROR_PATH_NOT_FOUND => "ENOENT",
-    WSAHOST_NOT_FOUND => "ENOENT",
-    WSANO_DATA => "ENOENT",
-    ERROR_NOT_ENOUGH_MEMORY => "ENOMEM",
-    ERROR_OUTOFMEMORY => "ENOMEM",
-    ERROR_CANNOT_MAKE => "ENOSPC",
-    ERROR_DISK_FULL => "ENOSPC",
-    ERROR_EA_TABLE_FULL => "ENOSPC",
-    ERROR_END_OF_MEDIA => "ENOSPC",
-    ERROR_HANDLE_DISK_FULL => "ENOSPC",
-    ERROR_NOT_CONNECTED => "ENOTCONN",
-    WSAENOTCONN => "ENOTCONN",
-    ERROR_DIR_NOT_EMPTY => "ENOTEMPTY",
-    WSAENOTSOCK => "ENOTSOCK",
-    ERROR_NOT_SUPPORTED => "ENOTSUP",
-    ERROR_BROKEN_PIPE => "EOF",
-    ERROR_PRIVILEGE_NOT_HELD => "EPERM",
-    ERROR_BAD_PIPE => "EPIPE",
-    ERROR_NO_DATA => "EPIPE",
-    ERROR_PIPE_NOT_CONNECTED => "EPIPE",
-    WSAESHUTDOWN => "EPIPE",
-    WSAEPROTONOSUPPORT => "EPROTONOSUPPORT",
-    ERROR_WRITE_PROTECT => "EROFS",
-    ERROR_SEM_TIMEOUT => "ETIMEDOUT",
-    WSAETIMEDOUT => "ETIMEDOUT",
-    ERROR_NOT_SAME_DEVICE => "EXDEV",
-    ERROR_INVALID_FUNCTION => "EISDIR",
-    ERROR_META_EXPANSION_TOO_LONG => "E2BIG",
-    WSAESOCKTNOSUPPORT => "ESOCKTNOSUPPORT",
-    _ => "UNKNOWN",
-  };
-  uv_err.to_string()
+  sys_to_uv_error(err).to_string()
+}
+
+#[op]
+fn op_node_uv_to_sys_error(name: String) -> Option<i32> {
+  uv_to_sys_error(&name)
 }
 
 /*++
@@ -1944,6 +1975,15 @@ pub const ERROR_PIPE_CONNECTED: i32 = 535;
 //
 pub const ERROR_PIPE_LISTENING: i32 = 536;
 
+//
+// MessageId: ERROR_ELEVATION_REQUIRED
+//
+// MessageText:
+//
+//  The requested operation requires elevation.
+//
+pub const ERROR_ELEVATION_REQUIRED: i32 = 740;
+
 //
 // MessageId: ERROR_EA_ACCESS_DENIED
 //
@@ -17003,3 +17043,29 @@ pub const ERROR_IPSEC_IKE_NEGOTIATION_DISABLED: i32 = 13883;
 //  ERROR_IPSEC_IKE_NEG_STATUS_END
 //
 pub const ERROR_IPSEC_IKE_NEG_STATUS_END: i32 = 13884;
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn translates_sys_errors() {
+    assert_eq!(sys_to_uv_error(ERROR_ACCESS_DENIED), "EPERM");
+    assert_eq!(sys_to_uv_error(ERROR_FILE_NOT_FOUND), "ENOENT");
+    assert_eq!(sys_to_uv_error(WSAECONNRESET), "ECONNRESET");
+    assert_eq!(sys_to_uv_error(ERROR_BAD_EXE_FORMAT), "EFTYPE");
+    assert_eq!(sys_to_uv_error(ERROR_SUCCESS), "UNKNOWN");
+  }
+
+  #[test]
+  fn translates_uv_errors_back() {
+    assert_eq!(uv_to_sys_error("ENOENT"), Some(ERROR_FILE_NOT_FOUND));
+    assert_eq!(uv_to_sys_error("EPERM"), Some(ERROR_ACCESS_DENIED));
+    assert_eq!(uv_to_sys_error("EINVAL"), Some(ERROR_INVALID_PARAMETER));
+    assert_eq!(uv_to_sys_error("EXYZ"), None);
+    for (_, name) in UV_ERRORS {
+      let sys_err = uv_to_sys_error(name).unwrap();
+      assert_eq!(sys_to_uv_error(sys_err), *name);
+    }
+  }
+}
@@ -2,6 +2,7 @@
 
 use crate::NodeModuleKind;
 use crate::NodePermissions;
+use crate::PathClean;
 
 use super::NpmResolver;
 
@@ -17,6 +18,7 @@ use serde::Serialize;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::ErrorKind;
+use std::path::Path;
 use std::path::PathBuf;
 
 thread_local! {
@@ -29,6 +31,7 @@ pub struct PackageJson {
   pub exports: Option<Map<String, Value>>,
   pub imports: Option<Map<String, Value>>,
   pub bin: Option<Value>,
+  browser: Option<Value>, // use .browser_*(...)
   main: Option<String>,   // use .main(...)
   module: Option<String>, // use .main(...)
   pub name: Option<String>,
@@ -48,6 +51,7 @@ impl PackageJson {
       exports: None,
       imports: None,
       bin: None,
+      browser: None,
       main: None,
       module: None,
       name: None,
@@ -114,6 +118,10 @@ impl PackageJson {
     let version_val = package_json.get("version");
     let type_val = package_json.get("type");
     let bin = package_json.get("bin").map(ToOwned::to_owned);
+    let browser = package_json
+      .get("browser")
+      .filter(|browser| browser.is_string() || browser.is_object())
+      .map(ToOwned::to_owned);
     let exports = package_json.get("exports").map(|exports| {
       if is_conditional_exports_main_sugar(exports) {
         let mut map = Map::new();
@@ -188,6 +196,7 @@ impl PackageJson {
       exports,
       imports,
       bin,
+      browser,
       dependencies,
       dev_dependencies,
       scripts,
@@ -201,6 +210,17 @@ impl PackageJson {
     Ok(package_json)
   }
 
+  /// Drops the cached `package.json` files that apply to modules in `dir`,
+  /// so they are read again when one of those modules is loaded next.
+  pub fn clear_cache_for_dir(dir: &Path) {
+    CACHE.with(|cache| {
+      cache.borrow_mut().retain(|path, _| match path.parent() {
+        Some(package_dir) => !dir.starts_with(package_dir),
+        None => true,
+      });
+    });
+  }
+
   pub fn main(&self, referrer_kind: NodeModuleKind) -> Option<&String> {
     if referrer_kind == NodeModuleKind::Esm && self.typ == "module" {
       self.module.as_ref().or(self.main.as_ref())
@@ -209,11 +229,104 @@ impl PackageJson {
     }
   }
 
+  /// Returns the entrypoint the "browser" field uses in place of "main".
+  ///
+  /// Mapping the main entrypoint to `false` is not supported, so it's
+  /// ignored.
+  pub fn browser_main(&self) -> Option<PathBuf> {
+    let dir = self.path.parent().unwrap();
+    match self.browser.as_ref()? {
+      Value::String(main) => Some(dir.join(main).clean()),
+      Value::Object(_) => {
+        let main = dir
+          .join(self.main.as_deref().unwrap_or("./index.js"))
+          .clean();
+        match self.browser_replacement_for_path(&main)? {
+          BrowserReplacement::Path(path) => Some(path),
+          _ => None,
+        }
+      }
+      _ => None,
+    }
+  }
+
+  /// Looks up a file of this package in the object form of the "browser"
+  /// field. Keys may omit the extension or point to a directory's index.
+  pub fn browser_replacement_for_path(
+    &self,
+    path: &Path,
+  ) -> Option<BrowserReplacement> {
+    let map = self.browser.as_ref()?.as_object()?;
+    let dir = self.path.parent().unwrap();
+    map.iter().find_map(|(key, value)| {
+      if !key.starts_with('.') {
+        return None;
+      }
+      let key_path = dir.join(key).clean();
+      let matches = key_path == path
+        || with_appended_ext(&key_path, "js") == path
+        || key_path.join("index.js") == path;
+      if matches {
+        browser_replacement(dir, value)
+      } else {
+        None
+      }
+    })
+  }
+
+  /// Looks up a bare specifier (ex. `fs` or `some-package`) in the object
+  /// form of the "browser" field.
+  pub fn browser_replacement_for_module(
+    &self,
+    specifier: &str,
+  ) -> Option<BrowserReplacement> {
+    if specifier.starts_with('.') {
+      return None;
+    }
+    let map = self.browser.as_ref()?.as_object()?;
+    let value = map.get(specifier)?;
+    browser_replacement(self.path.parent().unwrap(), value)
+  }
+
   pub fn specifier(&self) -> ModuleSpecifier {
     ModuleSpecifier::from_file_path(&self.path).unwrap()
   }
 }
 
+/// What a module is replaced with according to the "browser" field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserReplacement {
+  /// A file of the package.
+  Path(PathBuf),
+  /// Another package, or a subpath of it.
+  Package(String),
+  /// An empty module, for `false`.
+  Empty,
+}
+
+fn browser_replacement(
+  dir: &Path,
+  value: &Value,
+) -> Option<BrowserReplacement> {
+  match value {
+    Value::Bool(false) => Some(BrowserReplacement::Empty),
+    Value::String(replacement) if replacement.starts_with('.') => {
+      Some(BrowserReplacement::Path(dir.join(replacement).clean()))
+    }
+    Value::String(replacement) => {
+      Some(BrowserReplacement::Package(replacement.to_string()))
+    }
+    _ => None,
+  }
+}
+
+fn with_appended_ext(path: &Path, ext: &str) -> PathBuf {
+  let mut path = path.as_os_str().to_owned();
+  path.push(".");
+  path.push(ext);
+  PathBuf::from(path)
+}
+
 fn is_conditional_exports_main_sugar(exports: &Value) -> bool {
   if exports.is_string() || exports.is_array() {
     return true;
@@ -240,3 +353,72 @@ fn is_conditional_exports_main_sugar(exports: &Value) -> bool {
 
   is_conditional_sugar
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn load(source: Value) -> PackageJson {
+    let path = if cfg!(windows) {
+      PathBuf::from("C:\\package\\package.json")
+    } else {
+      PathBuf::from("/package/package.json")
+    };
+    PackageJson::load_from_string(path, source.to_string()).unwrap()
+  }
+
+  #[test]
+  fn browser_string_form() {
+    let package_json = load(serde_json::json!({
+      "main": "./lib/node.js",
+      "browser": "./lib/browser.js"
+    }));
+    let dir = package_json.path.parent().unwrap().to_path_buf();
+    assert_eq!(
+      package_json.browser_main(),
+      Some(dir.join("lib").join("browser.js"))
+    );
+    assert_eq!(
+      package_json.browser_replacement_for_path(&dir.join("lib/node.js")),
+      None
+    );
+  }
+
+  #[test]
+  fn browser_object_form() {
+    let package_json = load(serde_json::json!({
+      "main": "lib/node.js",
+      "browser": {
+        "./lib/node": "./lib/browser.js",
+        "./lib/server.js": false,
+        "./lib/ws.js": "isomorphic-ws",
+        "fs": false,
+        "stream": "readable-stream"
+      }
+    }));
+    let dir = package_json.path.parent().unwrap().to_path_buf();
+    let lib = dir.join("lib");
+    assert_eq!(package_json.browser_main(), Some(lib.join("browser.js")));
+    assert_eq!(
+      package_json.browser_replacement_for_path(&lib.join("server.js")),
+      Some(BrowserReplacement::Empty)
+    );
+    assert_eq!(
+      package_json.browser_replacement_for_path(&lib.join("ws.js")),
+      Some(BrowserReplacement::Package("isomorphic-ws".to_string()))
+    );
+    assert_eq!(
+      package_json.browser_replacement_for_path(&lib.join("other.js")),
+      None
+    );
+    assert_eq!(
+      package_json.browser_replacement_for_module("fs"),
+      Some(BrowserReplacement::Empty)
+    );
+    assert_eq!(
+      package_json.browser_replacement_for_module("stream"),
+      Some(BrowserReplacement::Package("readable-stream".to_string()))
+    );
+    assert_eq!(package_json.browser_replacement_for_module("path"), None);
+  }
+}
@@ -7,6 +7,12 @@ pub fn is_builtin_node_module(module_name: &str) -> bool {
     .any(|m| m.module_name() == module_name)
 }
 
+/// Builtin modules that can only be imported with the `node:` scheme, so
+/// that packages of the same name keep resolving, e.g. `node:test`.
+pub fn requires_node_scheme(module_name: &str) -> bool {
+  matches!(module_name, "test")
+}
+
 pub struct NodeModulePolyfill {
   /// Name of the module like "assert" or "timers/promises"
   pub specifier: &'static str,
@@ -170,6 +176,10 @@ pub static SUPPORTED_BUILTIN_NODE_MODULES: &[NodeModulePolyfill] = &[
     specifier: "node:sys",
     ext_specifier: "ext:deno_node/sys.ts",
   },
+  NodeModulePolyfill {
+    specifier: "node:test",
+    ext_specifier: "ext:deno_node/testing.ts",
+  },
   NodeModulePolyfill {
     specifier: "node:timers",
     ext_specifier: "ext:deno_node/timers.ts",
@@ -206,6 +216,10 @@ pub static SUPPORTED_BUILTIN_NODE_MODULES: &[NodeModulePolyfill] = &[
     specifier: "node:vm",
     ext_specifier: "ext:deno_node/vm.ts",
   },
+  NodeModulePolyfill {
+    specifier: "node:wasi",
+    ext_specifier: "ext:deno_node/wasi.ts",
+  },
   NodeModulePolyfill {
     specifier: "node:worker_threads",
     ext_specifier: "ext:deno_node/worker_threads.ts",
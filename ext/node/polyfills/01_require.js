@@ -24,6 +24,9 @@ const {
   ObjectCreate,
   Proxy,
   SafeMap,
+  SafeSet,
+  SafeSetIterator,
+  SetPrototypeHas,
   SafeWeakMap,
   SafeArrayIterator,
   JSONParse,
@@ -116,6 +119,7 @@ import streamPromises from "ext:deno_node/stream/promises.mjs";
 import streamWeb from "ext:deno_node/stream/web.ts";
 import stringDecoder from "ext:deno_node/string_decoder.ts";
 import sys from "ext:deno_node/sys.ts";
+import test from "ext:deno_node/testing.ts";
 import timers from "ext:deno_node/timers.ts";
 import timersPromises from "ext:deno_node/timers/promises.ts";
 import tls from "ext:deno_node/tls.ts";
@@ -131,6 +135,9 @@ import zlib from "ext:deno_node/zlib.ts";
 
 const nativeModuleExports = ObjectCreate(null);
 const builtinModules = [];
+// Builtin modules that can only be required with the `node:` scheme, so that
+// packages of the same name keep working.
+const schemelessBlockList = new SafeSet(["test"]);
 
 // NOTE(bartlomieju): keep this list in sync with `ext/node/polyfill.rs`
 function setupBuiltinModules() {
@@ -218,6 +225,7 @@ function setupBuiltinModules() {
     "stream/web": streamWeb,
     string_decoder: stringDecoder,
     sys,
+    test,
     timers,
     "timers/promises": timersPromises,
     tls,
@@ -233,7 +241,9 @@ function setupBuiltinModules() {
   };
   for (const [name, moduleExports] of ObjectEntries(nodeModules)) {
     nativeModuleExports[name] = moduleExports;
-    ArrayPrototypePush(builtinModules, name);
+    if (!SetPrototypeHas(schemelessBlockList, name)) {
+      ArrayPrototypePush(builtinModules, name);
+    }
   }
 }
 setupBuiltinModules();
@@ -353,15 +363,9 @@ function tryPackage(requestPath, exts, isMain, originalPath) {
   return actual;
 }
 
-const realpathCache = new SafeMap();
+// The op caches the paths it resolved, until the file system changes.
 function toRealPath(requestPath) {
-  const maybeCached = realpathCache.get(requestPath);
-  if (maybeCached) {
-    return maybeCached;
-  }
-  const rp = ops.op_require_real_path(requestPath);
-  realpathCache.set(requestPath, rp);
-  return rp;
+  return ops.op_require_real_path(requestPath);
 }
 
 function tryExtensions(p, exts, isMain) {
@@ -463,6 +467,38 @@ Module._pathCache = ObjectCreate(null);
 let modulePaths = [];
 Module.globalPaths = modulePaths;
 
+// The keys of `Module._pathCache` that resolve to each filename, so that the
+// entries of a single module can be dropped without scanning the whole cache.
+const pathCacheKeysByFilename = new SafeMap();
+
+function setPathCache(cacheKey, filename) {
+  Module._pathCache[cacheKey] = filename;
+  let keys = pathCacheKeysByFilename.get(filename);
+  if (keys === undefined) {
+    keys = new SafeSet();
+    pathCacheKeysByFilename.set(filename, keys);
+  }
+  keys.add(cacheKey);
+}
+
+// Like in Node, deleting an entry from `require.cache` only makes the next
+// `require()` evaluate the module again, the way it was resolved before. To
+// also pick up changes to how it resolves, like a new `main` in its
+// `package.json`, hot reloaders can call this instead.
+Module._clearCache = function (filename) {
+  delete Module._cache[filename];
+  const keys = pathCacheKeysByFilename.get(filename);
+  if (keys !== undefined) {
+    pathCacheKeysByFilename.delete(filename);
+    for (const key of new SafeSetIterator(keys)) {
+      if (Module._pathCache[key] === filename) {
+        delete Module._pathCache[key];
+      }
+    }
+  }
+  ops.op_require_clear_cache(filename);
+};
+
 const CHAR_FORWARD_SLASH = 47;
 const TRAILING_SLASH_REGEX = /(?:^|\/)\.?\.$/;
 const encodedSepRegEx = /%2F|%2C/i;
@@ -599,7 +635,7 @@ Module._findPath = function (request, paths, isMain, parentPath) {
     }
 
     if (filename) {
-      Module._pathCache[cacheKey] = filename;
+      setPathCache(cacheKey, filename);
       return filename;
     }
   }
@@ -694,7 +730,9 @@ Module._load = function (request, parent, isMain) {
     return cachedModule.exports;
   }
 
-  const mod = loadNativeModule(filename, request);
+  const mod = nativeModuleCanBeRequiredByUsers(request)
+    ? loadNativeModule(filename, request)
+    : undefined;
   if (
     mod
   ) {
@@ -815,7 +853,7 @@ Module._resolveFilename = function (
   if (selfResolved) {
     const cacheKey = request + "\x00" +
       (paths.length === 1 ? paths[0] : ArrayPrototypeJoin(paths, "\x00"));
-    Module._pathCache[cacheKey] = selfResolved;
+    setPathCache(cacheKey, selfResolved);
     return selfResolved;
   }
 
@@ -1028,10 +1066,7 @@ Module._extensions[".json"] = function (module, filename) {
 
 // Native extension for .node
 Module._extensions[".node"] = function (module, filename) {
-  if (filename.endsWith("fsevents.node")) {
-    throw new Error("Using fsevents module is currently not supported");
-  }
-  module.exports = ops.op_napi_open(filename, nodeGlobalThis);
+  process.dlopen(module, filename);
 };
 
 function createRequireFromPath(filename) {
@@ -1139,7 +1174,8 @@ function loadNativeModule(_id, request) {
 }
 
 function nativeModuleCanBeRequiredByUsers(request) {
-  return !!nativeModuleExports[request];
+  return !!nativeModuleExports[request] &&
+    !SetPrototypeHas(schemelessBlockList, request);
 }
 
 function readPackageScope() {
@@ -8,6 +8,7 @@ const primordials = globalThis.__bootstrap.primordials;
 const { ObjectDefineProperty } = primordials;
 import { nodeGlobals, nodeGlobalThis } from "ext:deno_node/00_globals.js";
 import "ext:deno_node/01_require.js";
+import { setOptions } from "ext:deno_node/internal_binding/node_options.ts";
 
 let initialized = false;
 
@@ -15,11 +16,13 @@ function initialize(
   nodeGlobalThisName,
   usesLocalNodeModulesDir,
   argv0,
+  nodeOptions,
 ) {
   if (initialized) {
     throw Error("Node runtime already initialized");
   }
   initialized = true;
+  setOptions(nodeOptions);
   if (usesLocalNodeModulesDir) {
     requireImpl.setUsesLocalNodeModulesDir();
   }
@@ -47,6 +50,9 @@ function initialize(
   // but it's the only way to get `args` and `version` and this point.
   internals.__bootstrapNodeProcess(argv0, Deno.args, Deno.version);
   internals.__initWorkerThreads();
+  internals.__initCluster();
+  // modules from `--require` in NODE_OPTIONS
+  requireImpl.Module._preloadModules(nodeOptions.require);
   // `Deno[Deno.internal].requireImpl` will be unreachable after this line.
   delete internals.requireImpl;
 }
@@ -1,11 +1,16 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import type { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  type CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { getValidatedPath } from "ext:deno_node/internal/fs/utils.mjs";
 import * as pathModule from "ext:deno_node/path.ts";
 import { parseFileMode } from "ext:deno_node/internal/validators.mjs";
 import { Buffer } from "ext:deno_node/buffer.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function chmod(
   path: string | Buffer | URL,
   mode: string | number,
@@ -25,7 +30,9 @@ export function chmod(
     }
   }
 
-  Deno.chmod(pathModule.toNamespacedPath(path), mode).catch((error) => {
+  checkWritePermission(path, "node:fs.chmod()").then(() =>
+    Deno.chmod(pathModule.toNamespacedPath(path as string), mode as number)
+  ).catch((error) => {
     // Ignore NotSupportedError that occurs on windows
     // https://github.com/denoland/deno_std/issues/2995
     if (!(error instanceof Deno.errors.NotSupported)) {
@@ -57,6 +64,7 @@ export function chmodSync(path: string | URL, mode: string | number) {
     }
   }
 
+  ops.op_node_check_write(path, "node:fs.chmodSync()");
   try {
     Deno.chmodSync(pathModule.toNamespacedPath(path), mode);
   } catch (error) {
@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import {
   type CallbackWithError,
+  checkWritePermission,
   makeCallback,
 } from "ext:deno_node/_fs/_fs_common.ts";
 import {
@@ -12,6 +13,8 @@ import { validateInteger } from "ext:deno_node/internal/validators.mjs";
 import type { Buffer } from "ext:deno_node/buffer.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 /**
  * Asynchronously changes the owner and group
  * of a file.
@@ -27,7 +30,9 @@ export function chown(
   validateInteger(uid, "uid", -1, kMaxUserId);
   validateInteger(gid, "gid", -1, kMaxUserId);
 
-  Deno.chown(pathModule.toNamespacedPath(path), uid, gid).then(
+  checkWritePermission(path, "node:fs.chown()").then(() =>
+    Deno.chown(pathModule.toNamespacedPath(path as string), uid, gid)
+  ).then(
     () => callback(null),
     callback,
   );
@@ -52,5 +57,6 @@ export function chownSync(
   validateInteger(uid, "uid", -1, kMaxUserId);
   validateInteger(gid, "gid", -1, kMaxUserId);
 
+  ops.op_node_check_write(path, "node:fs.chownSync()");
   Deno.chownSync(pathModule.toNamespacedPath(path), uid, gid);
 }
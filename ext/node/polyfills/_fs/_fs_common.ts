@@ -214,6 +214,23 @@ export function getOpenOptions(
 
 export { isUint32 as isFd } from "ext:deno_node/internal/validators.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
+/** Checks that `path` can be written to by a callback-style API. A denied
+ * permission rejects the promise, so that it reaches the callback like the
+ * other errors of the API instead of being thrown. */
+export function checkWritePermission(
+  path: string,
+  apiName: string,
+): Promise<void> {
+  try {
+    ops.op_node_check_write(path, apiName);
+    return Promise.resolve();
+  } catch (err) {
+    return Promise.reject(err);
+  }
+}
+
 export function maybeCallback(cb: unknown) {
   validateFunction(cb, "cb");
 
@@ -1,6 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import type { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
-import { makeCallback } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  checkWritePermission,
+  makeCallback,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { Buffer } from "ext:deno_node/buffer.ts";
 import {
   getValidatedPath,
@@ -10,6 +13,8 @@ import { fs } from "ext:deno_node/internal_binding/constants.ts";
 import { codeMap } from "ext:deno_node/internal_binding/uv.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function copyFile(
   src: string | Buffer | URL,
   dest: string | Buffer | URL,
@@ -35,26 +40,27 @@ export function copyFile(
   const destStr = getValidatedPath(dest, "dest").toString();
   const modeNum = getValidMode(mode, "copyFile");
   const cb = makeCallback(callback);
-
-  if ((modeNum & fs.COPYFILE_EXCL) === fs.COPYFILE_EXCL) {
-    Deno.lstat(destStr).then(() => {
-      // deno-lint-ignore no-explicit-any
-      const e: any = new Error(
-        `EEXIST: file already exists, copyfile '${srcStr}' -> '${destStr}'`,
-      );
-      e.syscall = "copyfile";
-      e.errno = codeMap.get("EEXIST");
-      e.code = "EEXIST";
-      cb(e);
-    }, (e) => {
-      if (e instanceof Deno.errors.NotFound) {
-        Deno.copyFile(srcStr, destStr).then(() => cb(null), cb);
-      }
-      cb(e);
-    });
-  } else {
-    Deno.copyFile(srcStr, destStr).then(() => cb(null), cb);
-  }
+  checkWritePermission(destStr, "node:fs.copyFile()").then(() => {
+    if ((modeNum & fs.COPYFILE_EXCL) === fs.COPYFILE_EXCL) {
+      Deno.lstat(destStr).then(() => {
+        // deno-lint-ignore no-explicit-any
+        const e: any = new Error(
+          `EEXIST: file already exists, copyfile '${srcStr}' -> '${destStr}'`,
+        );
+        e.syscall = "copyfile";
+        e.errno = codeMap.get("EEXIST");
+        e.code = "EEXIST";
+        cb(e);
+      }, (e) => {
+        if (e instanceof Deno.errors.NotFound) {
+          Deno.copyFile(srcStr, destStr).then(() => cb(null), cb);
+        }
+        cb(e);
+      });
+    } else {
+      Deno.copyFile(srcStr, destStr).then(() => cb(null), cb);
+    }
+  }, cb);
 }
 
 export const copyFilePromise = promisify(copyFile) as (
@@ -71,6 +77,7 @@ export function copyFileSync(
   const srcStr = getValidatedPath(src, "src").toString();
   const destStr = getValidatedPath(dest, "dest").toString();
   const modeNum = getValidMode(mode, "copyFile");
+  ops.op_node_check_write(destStr, "node:fs.copyFileSync()");
 
   if ((modeNum & fs.COPYFILE_EXCL) === fs.COPYFILE_EXCL) {
     try {
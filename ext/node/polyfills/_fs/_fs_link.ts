@@ -1,8 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import type { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  type CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 /**
  * TODO: Also accept 'path' parameter as a Node polyfill Buffer type once these
  * are implemented. See https://github.com/denoland/deno/issues/3403
@@ -17,7 +22,9 @@ export function link(
     : existingPath;
   newPath = newPath instanceof URL ? fromFileUrl(newPath) : newPath;
 
-  Deno.link(existingPath, newPath).then(() => callback(null), callback);
+  checkWritePermission(newPath, "node:fs.link()")
+    .then(() => Deno.link(existingPath as string, newPath as string))
+    .then(() => callback(null), callback);
 }
 
 /**
@@ -42,5 +49,6 @@ export function linkSync(
     : existingPath;
   newPath = newPath instanceof URL ? fromFileUrl(newPath) : newPath;
 
+  ops.op_node_check_write(newPath, "node:fs.linkSync()");
   Deno.linkSync(existingPath, newPath);
 }
@@ -1,10 +1,15 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import type { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  type CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 import { denoErrorToNodeError } from "ext:deno_node/internal/errors.ts";
 import { getValidatedPath } from "ext:deno_node/internal/fs/utils.mjs";
 import { validateBoolean } from "ext:deno_node/internal/validators.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 /**
  * TODO: Also accept 'path' parameter as a Node polyfill Buffer type once these
  * are implemented. See https://github.com/denoland/deno/issues/3403
@@ -36,7 +41,8 @@ export function mkdir(
   }
   validateBoolean(recursive, "options.recursive");
 
-  Deno.mkdir(path, { recursive, mode })
+  checkWritePermission(path, "node:fs.mkdir()")
+    .then(() => Deno.mkdir(path, { recursive, mode }))
     .then(() => {
       if (typeof callback === "function") {
         callback(null);
@@ -69,6 +75,7 @@ export function mkdirSync(path: string | URL, options?: MkdirOptions) {
   }
   validateBoolean(recursive, "options.recursive");
 
+  ops.op_node_check_write(path, "node:fs.mkdirSync()");
   try {
     Deno.mkdirSync(path, { recursive, mode });
   } catch (err) {
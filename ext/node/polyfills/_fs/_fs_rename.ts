@@ -1,7 +1,10 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { checkWritePermission } from "ext:deno_node/_fs/_fs_common.ts";
 import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function rename(
   oldPath: string | URL,
   newPath: string | URL,
@@ -12,7 +15,10 @@ export function rename(
 
   if (!callback) throw new Error("No callback function supplied");
 
-  Deno.rename(oldPath, newPath).then((_) => callback(), callback);
+  checkWritePermission(oldPath, "node:fs.rename()")
+    .then(() => checkWritePermission(newPath as string, "node:fs.rename()"))
+    .then(() => Deno.rename(oldPath, newPath))
+    .then((_) => callback(), callback);
 }
 
 export const renamePromise = promisify(rename) as (
@@ -24,5 +30,7 @@ export function renameSync(oldPath: string | URL, newPath: string | URL) {
   oldPath = oldPath instanceof URL ? fromFileUrl(oldPath) : oldPath;
   newPath = newPath instanceof URL ? fromFileUrl(newPath) : newPath;
 
+  ops.op_node_check_write(oldPath, "node:fs.renameSync()");
+  ops.op_node_check_write(newPath, "node:fs.renameSync()");
   Deno.renameSync(oldPath, newPath);
 }
@@ -5,6 +5,10 @@ import {
 } from "ext:deno_node/internal/fs/utils.mjs";
 import { denoErrorToNodeError } from "ext:deno_node/internal/errors.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
+import { fromFileUrl } from "ext:deno_node/path.ts";
+import { checkWritePermission } from "ext:deno_node/_fs/_fs_common.ts";
+
+const { ops } = globalThis.__bootstrap.core;
 
 type rmOptions = {
   force?: boolean;
@@ -35,6 +39,7 @@ export function rm(
 
   if (!callback) throw new Error("No callback function supplied");
 
+  path = path instanceof URL ? fromFileUrl(path) : path;
   validateRmOptions(
     path,
     options,
@@ -43,7 +48,8 @@ export function rm(
       if (err) {
         return callback(err);
       }
-      Deno.remove(path, { recursive: options?.recursive })
+      checkWritePermission(path as string, "node:fs.rm()")
+        .then(() => Deno.remove(path, { recursive: options?.recursive }))
         .then((_) => callback(null), (err: unknown) => {
           if (options?.force && err instanceof Deno.errors.NotFound) {
             callback(null);
@@ -66,6 +72,8 @@ export const rmPromise = promisify(rm) as (
 
 export function rmSync(path: string | URL, options?: rmOptions) {
   options = validateRmOptionsSync(path, options, false);
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  ops.op_node_check_write(path, "node:fs.rmSync()");
   try {
     Deno.removeSync(path, { recursive: options?.recursive });
   } catch (err: unknown) {
@@ -13,6 +13,9 @@ import {
 } from "ext:deno_node/internal/errors.ts";
 import { Buffer } from "ext:deno_node/buffer.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
+import { checkWritePermission } from "ext:deno_node/_fs/_fs_common.ts";
+
+const { ops } = globalThis.__bootstrap.core;
 
 type rmdirOptions = {
   maxRetries?: number;
@@ -58,13 +61,15 @@ export function rmdir(
           return callback(err);
         }
 
-        Deno.remove(path, { recursive: options?.recursive })
+        checkWritePermission(path as string, "node:fs.rmdir()")
+          .then(() => Deno.remove(path, { recursive: options?.recursive }))
           .then((_) => callback(), callback);
       },
     );
   } else {
     validateRmdirOptions(options);
-    Deno.remove(path, { recursive: options?.recursive })
+    checkWritePermission(path as string, "node:fs.rmdir()")
+      .then(() => Deno.remove(path, { recursive: options?.recursive }))
       .then((_) => callback(), (err: unknown) => {
         callback(
           err instanceof Error
@@ -96,8 +101,10 @@ export function rmdirSync(path: string | Buffer | URL, options?: rmdirOptions) {
     validateRmdirOptions(options);
   }
 
+  path = toNamespacedPath(path as string);
+  ops.op_node_check_write(path, "node:fs.rmdirSync()");
   try {
-    Deno.removeSync(toNamespacedPath(path as string), {
+    Deno.removeSync(path, {
       recursive: options?.recursive,
     });
   } catch (err: unknown) {
@@ -0,0 +1,126 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { denoErrorToNodeError } from "ext:deno_node/internal/errors.ts";
+import { fromFileUrl } from "ext:deno_node/path.ts";
+import { promisify } from "ext:deno_node/internal/util.mjs";
+
+const core = globalThis.__bootstrap.core;
+const { ops } = core;
+
+export type statfsOptions = {
+  bigint?: boolean;
+};
+
+interface RawStatFs {
+  type: number;
+  bsize: number;
+  blocks: number;
+  bfree: number;
+  bavail: number;
+  files: number;
+  ffree: number;
+}
+
+/** The statistics of a mounted file system. On Windows only the sizes are
+ * known and the other fields are `0`. */
+export class StatFs<T extends number | bigint = number> {
+  /** The type of the file system. */
+  type: T;
+  /** The size of a block, in bytes. */
+  bsize: T;
+  /** The number of blocks. */
+  blocks: T;
+  /** The number of free blocks. */
+  bfree: T;
+  /** The number of free blocks available to unprivileged users. */
+  bavail: T;
+  /** The number of file nodes. */
+  files: T;
+  /** The number of free file nodes. */
+  ffree: T;
+
+  constructor(raw: RawStatFs, bigint: boolean) {
+    const convert = (value: number) => (bigint ? BigInt(value) : value) as T;
+    this.type = convert(raw.type);
+    this.bsize = convert(raw.bsize);
+    this.blocks = convert(raw.blocks);
+    this.bfree = convert(raw.bfree);
+    this.bavail = convert(raw.bavail);
+    this.files = convert(raw.files);
+    this.ffree = convert(raw.ffree);
+  }
+}
+
+export type statfsCallback = (err: Error | null, stats?: StatFs) => void;
+export type statfsCallbackBigInt = (
+  err: Error | null,
+  stats?: StatFs<bigint>,
+) => void;
+
+export function statfs(path: string | URL, callback: statfsCallback): void;
+export function statfs(
+  path: string | URL,
+  options: { bigint: false },
+  callback: statfsCallback,
+): void;
+export function statfs(
+  path: string | URL,
+  options: { bigint: true },
+  callback: statfsCallbackBigInt,
+): void;
+export function statfs(
+  path: string | URL,
+  optionsOrCallback: statfsCallback | statfsCallbackBigInt | statfsOptions,
+  maybeCallback?: statfsCallback | statfsCallbackBigInt,
+) {
+  const callback =
+    (typeof optionsOrCallback === "function"
+      ? optionsOrCallback
+      : maybeCallback) as (
+        ...args: [Error] | [null, StatFs | StatFs<bigint>]
+      ) => void;
+  const options = typeof optionsOrCallback === "object"
+    ? optionsOrCallback
+    : { bigint: false };
+
+  if (!callback) throw new Error("No callback function supplied");
+
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  core.opAsync("op_node_statfs", path).then(
+    (raw: RawStatFs) => callback(null, new StatFs(raw, !!options.bigint)),
+    (err: Error) => callback(denoErrorToNodeError(err, { syscall: "statfs" })),
+  );
+}
+
+export const statfsPromise = promisify(statfs) as (
+  & ((path: string | URL) => Promise<StatFs>)
+  & ((path: string | URL, options: { bigint: false }) => Promise<StatFs>)
+  & ((
+    path: string | URL,
+    options: { bigint: true },
+  ) => Promise<StatFs<bigint>>)
+);
+
+export function statfsSync(path: string | URL): StatFs;
+export function statfsSync(
+  path: string | URL,
+  options: { bigint: false },
+): StatFs;
+export function statfsSync(
+  path: string | URL,
+  options: { bigint: true },
+): StatFs<bigint>;
+export function statfsSync(
+  path: string | URL,
+  options: statfsOptions = { bigint: false },
+): StatFs | StatFs<bigint> {
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  try {
+    return new StatFs(ops.op_node_statfs_sync(path), !!options.bigint);
+  } catch (err) {
+    if (err instanceof Error) {
+      throw denoErrorToNodeError(err, { syscall: "statfs" });
+    } else {
+      throw err;
+    }
+  }
+}
@@ -1,8 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 type SymlinkType = "file" | "dir";
 
 export function symlink(
@@ -24,7 +29,9 @@ export function symlink(
 
   if (!callback) throw new Error("No callback function supplied");
 
-  Deno.symlink(target, path, { type }).then(() => callback(null), callback);
+  checkWritePermission(path, "node:fs.symlink()")
+    .then(() => Deno.symlink(target as string, path as string, { type }))
+    .then(() => callback(null), callback);
 }
 
 export const symlinkPromise = promisify(symlink) as (
@@ -42,5 +49,6 @@ export function symlinkSync(
   path = path instanceof URL ? fromFileUrl(path) : path;
   type = type || "file";
 
+  ops.op_node_check_write(path, "node:fs.symlinkSync()");
   Deno.symlinkSync(target, path, { type });
 }
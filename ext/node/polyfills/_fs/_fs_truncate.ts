@@ -1,8 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function truncate(
   path: string | URL,
   lenOrCallback: number | CallbackWithError,
@@ -18,7 +23,9 @@ export function truncate(
 
   if (!callback) throw new Error("No callback function supplied");
 
-  Deno.truncate(path, len).then(() => callback(null), callback);
+  checkWritePermission(path, "node:fs.truncate()")
+    .then(() => Deno.truncate(path as string, len))
+    .then(() => callback(null), callback);
 }
 
 export const truncatePromise = promisify(truncate) as (
@@ -29,5 +36,6 @@ export const truncatePromise = promisify(truncate) as (
 export function truncateSync(path: string | URL, len?: number) {
   path = path instanceof URL ? fromFileUrl(path) : path;
 
+  ops.op_node_check_write(path, "node:fs.truncateSync()");
   Deno.truncateSync(path, len);
 }
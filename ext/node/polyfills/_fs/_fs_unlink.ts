@@ -1,9 +1,16 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { checkWritePermission } from "ext:deno_node/_fs/_fs_common.ts";
+import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function unlink(path: string | URL, callback: (err?: Error) => void) {
   if (!callback) throw new Error("No callback function supplied");
-  Deno.remove(path).then((_) => callback(), callback);
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  checkWritePermission(path, "node:fs.unlink()")
+    .then(() => Deno.remove(path))
+    .then((_) => callback(), callback);
 }
 
 export const unlinkPromise = promisify(unlink) as (
@@ -11,5 +18,7 @@ export const unlinkPromise = promisify(unlink) as (
 ) => Promise<void>;
 
 export function unlinkSync(path: string | URL) {
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  ops.op_node_check_write(path, "node:fs.unlinkSync()");
   Deno.removeSync(path);
 }
@@ -1,9 +1,15 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import type { CallbackWithError } from "ext:deno_node/_fs/_fs_common.ts";
+import {
+  type CallbackWithError,
+  checkWritePermission,
+} from "ext:deno_node/_fs/_fs_common.ts";
 import { fromFileUrl } from "ext:deno_node/path.ts";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const core = globalThis.__bootstrap.core;
+const { ops } = core;
+
 function getValidTime(
   time: number | string | Date,
   name: string,
@@ -39,7 +45,9 @@ export function utimes(
   atime = getValidTime(atime, "atime");
   mtime = getValidTime(mtime, "mtime");
 
-  Deno.utime(path, atime, mtime).then(() => callback(null), callback);
+  checkWritePermission(path, "node:fs.utimes()")
+    .then(() => Deno.utime(path as string, atime, mtime))
+    .then(() => callback(null), callback);
 }
 
 export const utimesPromise = promisify(utimes) as (
@@ -57,5 +65,70 @@ export function utimesSync(
   atime = getValidTime(atime, "atime");
   mtime = getValidTime(mtime, "mtime");
 
+  ops.op_node_check_write(path, "node:fs.utimesSync()");
   Deno.utimeSync(path, atime, mtime);
 }
+
+/** Splits a time, in seconds or as a `Date`, into seconds and nanoseconds. */
+function toSecondsAndNanos(time: number | Date): [number, number] {
+  const seconds = time instanceof Date ? time.getTime() / 1e3 : time;
+  const wholeSeconds = Math.floor(seconds);
+  const nanos = Math.round((seconds - wholeSeconds) * 1e9);
+  return nanos === 1e9 ? [wholeSeconds + 1, 0] : [wholeSeconds, nanos];
+}
+
+export function lutimes(
+  path: string | URL,
+  atime: number | string | Date,
+  mtime: number | string | Date,
+  callback: CallbackWithError,
+) {
+  path = path instanceof URL ? fromFileUrl(path) : path;
+
+  if (!callback) {
+    throw new Deno.errors.InvalidData("No callback function supplied");
+  }
+
+  const [atimeSecs, atimeNanos] = toSecondsAndNanos(
+    getValidTime(atime, "atime"),
+  );
+  const [mtimeSecs, mtimeNanos] = toSecondsAndNanos(
+    getValidTime(mtime, "mtime"),
+  );
+
+  checkWritePermission(path, "node:fs.lutimes()")
+    .then(() =>
+      core.opAsync(
+        "op_fs_lutime_async",
+        path,
+        atimeSecs,
+        atimeNanos,
+        mtimeSecs,
+        mtimeNanos,
+      )
+    )
+    .then(() => callback(null), callback);
+}
+
+export const lutimesPromise = promisify(lutimes) as (
+  path: string | URL,
+  atime: number | string | Date,
+  mtime: number | string | Date,
+) => Promise<void>;
+
+export function lutimesSync(
+  path: string | URL,
+  atime: number | string | Date,
+  mtime: number | string | Date,
+) {
+  path = path instanceof URL ? fromFileUrl(path) : path;
+  const [atimeSecs, atimeNanos] = toSecondsAndNanos(
+    getValidTime(atime, "atime"),
+  );
+  const [mtimeSecs, mtimeNanos] = toSecondsAndNanos(
+    getValidTime(mtime, "mtime"),
+  );
+
+  ops.op_node_check_write(path, "node:fs.lutimesSync()");
+  ops.op_fs_lutime_sync(path, atimeSecs, atimeNanos, mtimeSecs, mtimeNanos);
+}
@@ -21,6 +21,8 @@ import {
 } from "ext:deno_node/internal/fs/utils.mjs";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 
+const { ops } = globalThis.__bootstrap.core;
+
 interface Writer {
   write(p: Uint8Array): Promise<number>;
 }
@@ -68,6 +70,9 @@ export function writeFile(
   let error: Error | null = null;
   (async () => {
     try {
+      if (!isRid) {
+        ops.op_node_check_write(pathOrRid, "node:fs.writeFile()");
+      }
       file = isRid
         ? new Deno.FsFile(pathOrRid as number)
         : await Deno.open(pathOrRid as string, openOptions);
@@ -129,6 +134,9 @@ export function writeFileSync(
   }
 
   const isRid = typeof pathOrRid === "number";
+  if (!isRid) {
+    ops.op_node_check_write(pathOrRid, "node:fs.writeFileSync()");
+  }
   let file;
 
   let error: Error | null = null;
@@ -11,13 +11,7 @@ import * as fs from "ext:deno_fs/30_fs.js";
 
 /** Returns the operating system CPU architecture for which the Deno binary was compiled */
 export function arch(): string {
-  if (core.build.arch == "x86_64") {
-    return "x64";
-  } else if (core.build.arch == "aarch64") {
-    return "arm64";
-  } else {
-    throw Error("unreachable");
-  }
+  return core.ops.op_node_os_arch();
 }
 
 /** https://nodejs.org/api/process.html#process_process_chdir_directory */
@@ -85,6 +79,7 @@ export const env: InstanceType<ObjectConstructor> & Record<string, string> =
       }
     },
     set(_target, prop, value) {
+      core.ops.op_node_check_env(String(prop));
       Deno.env.set(String(prop), String(value));
       return true; // success
     },
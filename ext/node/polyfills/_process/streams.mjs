@@ -8,10 +8,39 @@ import {
   cursorTo,
   moveCursor,
 } from "ext:deno_node/internal/readline/callbacks.mjs";
+import { getColorDepth, hasColors } from "ext:deno_node/internal/tty.ts";
 import { Duplex, Readable, Writable } from "ext:deno_node/stream.ts";
 import { isWindows } from "ext:deno_node/_util/os.ts";
 import { fs as fsConstants } from "ext:deno_node/internal_binding/constants.ts";
 import * as io from "ext:deno_io/12_io.js";
+import {
+  bindConsoleResize,
+  pollConsoleResize,
+} from "ext:runtime/40_tty.js";
+
+const core = globalThis.__bootstrap.core;
+
+// Emits "resize" on a tty stream whenever the console size changes. The
+// console is only watched while there are listeners.
+function emitResizeEvents(stream) {
+  let rid = null;
+  stream.on("newListener", (event) => {
+    if (event !== "resize" || rid !== null) return;
+    const current = rid = bindConsoleResize();
+    (async () => {
+      while (rid === current && !(await pollConsoleResize(current))) {
+        stream.emit("resize");
+      }
+    })();
+  });
+  stream.on("removeListener", (event) => {
+    if (event !== "resize" || stream.listenerCount("resize") > 0) return;
+    if (rid !== null) {
+      core.tryClose(rid);
+      rid = null;
+    }
+  });
+}
 
 // https://github.com/nodejs/node/blob/00738314828074243c9a52a228ab4c68b04259ef/lib/internal/bootstrap/switches/is_main_thread.js#L41
 export function createWritableStdioStream(writer, name) {
@@ -87,6 +116,11 @@ export function createWritableStdioStream(writer, name) {
     stream.clearScreenDown = function (callback) {
       return clearScreenDown(this, callback);
     };
+
+    stream.getColorDepth = getColorDepth;
+    stream.hasColors = hasColors;
+
+    emitResizeEvents(stream);
   }
 
   return stream;
@@ -221,9 +255,19 @@ export const initStdin = () => {
     },
   });
   stdin._isRawMode = false;
+  let restoreModeOnUnload = false;
   stdin.setRawMode = (enable) => {
     io.stdin?.setRaw?.(enable);
     stdin._isRawMode = enable;
+    // Like node, leave the terminal in its original mode when exiting
+    if (enable && !restoreModeOnUnload) {
+      restoreModeOnUnload = true;
+      globalThis.addEventListener("unload", () => {
+        if (stdin._isRawMode) {
+          io.stdin?.setRaw?.(false);
+        }
+      });
+    }
     return stdin;
   };
   Object.defineProperty(stdin, "isRaw", {
@@ -4,7 +4,9 @@ const { ops } = globalThis.__bootstrap.core;
 
 export type OSType = "windows" | "linux" | "darwin" | "freebsd";
 
-export const osType: OSType = ops.op_node_build_os();
+const platform = ops.op_node_os_platform();
+
+export const osType: OSType = platform === "win32" ? "windows" : platform;
 
 export const isWindows = osType === "windows";
 export const isLinux = osType === "linux";
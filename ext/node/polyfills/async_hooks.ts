@@ -5,118 +5,73 @@
 // https://github.com/cloudflare/workerd/blob/77fd0ed6ddba184414f0216508fc62b06e716cab/src/workerd/api/node/async-hooks.c++#L9
 
 import { validateFunction } from "ext:deno_node/internal/validators.mjs";
+import {
+  getAsyncContext,
+  setAsyncContext,
+  shareAsyncContext,
+} from "ext:deno_web/01_async_context.js";
 
 const { core } = globalThis.__bootstrap;
+const { ops } = core;
 
 function assert(cond: boolean) {
   if (!cond) throw new Error("Assertion failed");
 }
-const asyncContextStack: AsyncContextFrame[] = [];
+
+// The current frame is the async context of ext/web, so that timers,
+// microtasks and event listeners keep it. These are the frames that were
+// current before the ones that were entered, to restore them on exit.
+const asyncContextStack: (AsyncContextFrame | undefined)[] = [];
 
 function pushAsyncFrame(frame: AsyncContextFrame) {
-  asyncContextStack.push(frame);
+  const previous = setAsyncContext(frame.isRoot() ? undefined : frame);
+  asyncContextStack.push(previous as AsyncContextFrame | undefined);
 }
 
 function popAsyncFrame() {
   assert(asyncContextStack.length > 0);
-  asyncContextStack.pop();
+  setAsyncContext(asyncContextStack.pop());
 }
 
 let rootAsyncFrame: AsyncContextFrame | undefined = undefined;
-let promiseHooksSet = false;
-
-const asyncContext = Symbol("asyncContext");
-function isRejected(promise: Promise<unknown>) {
-  const [state] = core.getPromiseDetails(promise);
-  return state == 2;
-}
+let promiseHookSet = false;
 
-function setPromiseHooks() {
-  if (promiseHooksSet) {
+// The frames are carried across promise reactions by a native promise hook,
+// which runs for every promise once it is set, so it is only set once a
+// context other than the root one is entered.
+function setPromiseHook() {
+  if (promiseHookSet) {
     return;
   }
-  promiseHooksSet = true;
-
-  const init = (promise: Promise<unknown>) => {
-    const currentFrame = AsyncContextFrame.current();
-    if (!currentFrame.isRoot()) {
-      assert(AsyncContextFrame.tryGetContext(promise) == null);
-      AsyncContextFrame.attachContext(promise);
-    }
-  };
-  const before = (promise: Promise<unknown>) => {
-    const maybeFrame = AsyncContextFrame.tryGetContext(promise);
-    if (maybeFrame) {
-      pushAsyncFrame(maybeFrame);
-    } else {
-      pushAsyncFrame(AsyncContextFrame.getRootAsyncContext());
-    }
-  };
-  const after = (promise: Promise<unknown>) => {
-    popAsyncFrame();
-    if (!isRejected(promise)) {
-      // @ts-ignore promise async context
-      delete promise[asyncContext];
-    }
-  };
-  const resolve = (promise: Promise<unknown>) => {
-    const currentFrame = AsyncContextFrame.current();
-    if (
-      !currentFrame.isRoot() && isRejected(promise) &&
-      AsyncContextFrame.tryGetContext(promise) == null
-    ) {
-      AsyncContextFrame.attachContext(promise);
-    }
-  };
-
-  core.setPromiseHooks(init, before, after, resolve);
+  promiseHookSet = true;
+  shareAsyncContext();
+  ops.op_node_async_context_enable();
 }
 
 class AsyncContextFrame {
-  storage: StorageEntry[];
+  storage: Map<StorageKey, unknown>;
   constructor(
     maybeParent?: AsyncContextFrame | null,
     maybeStorageEntry?: StorageEntry | null,
     isRoot = false,
   ) {
-    this.storage = [];
-
-    setPromiseHooks();
-
-    const propagate = (parent: AsyncContextFrame) => {
-      parent.storage = parent.storage.filter((entry) => !entry.key.isDead());
-      parent.storage.forEach((entry) => this.storage.push(entry.clone()));
-
-      if (maybeStorageEntry) {
-        const existingEntry = this.storage.find((entry) =>
-          entry.key === maybeStorageEntry.key
-        );
-        if (existingEntry) {
-          existingEntry.value = maybeStorageEntry.value;
-        } else {
-          this.storage.push(maybeStorageEntry);
-        }
-      }
-    };
+    this.storage = new Map();
 
-    if (!isRoot) {
-      if (maybeParent) {
-        propagate(maybeParent);
-      } else {
-        propagate(AsyncContextFrame.current());
-      }
+    if (isRoot) {
+      return;
     }
-  }
 
-  static tryGetContext(promise: Promise<unknown>) {
-    // @ts-ignore promise async context
-    return promise[asyncContext];
-  }
+    setPromiseHook();
 
-  static attachContext(promise: Promise<unknown>) {
-    assert(!(asyncContext in promise));
-    // @ts-ignore promise async context
-    promise[asyncContext] = AsyncContextFrame.current();
+    const parent = maybeParent ?? AsyncContextFrame.current();
+    for (const [key, value] of parent.storage) {
+      if (!key.isDead()) {
+        this.storage.set(key, value);
+      }
+    }
+    if (maybeStorageEntry) {
+      this.storage.set(maybeStorageEntry.key, maybeStorageEntry.value);
+    }
   }
 
   static getRootAsyncContext() {
@@ -128,12 +83,9 @@ class AsyncContextFrame {
     return rootAsyncFrame;
   }
 
-  static current() {
-    if (asyncContextStack.length === 0) {
-      return AsyncContextFrame.getRootAsyncContext();
-    }
-
-    return asyncContextStack[asyncContextStack.length - 1];
+  static current(): AsyncContextFrame {
+    return (getAsyncContext() as AsyncContextFrame | undefined) ??
+      AsyncContextFrame.getRootAsyncContext();
   }
 
   static create(
@@ -163,12 +115,7 @@ class AsyncContextFrame {
 
   get(key: StorageKey) {
     assert(!key.isDead());
-    this.storage = this.storage.filter((entry) => !entry.key.isDead());
-    const entry = this.storage.find((entry) => entry.key === key);
-    if (entry) {
-      return entry.value;
-    }
-    return undefined;
+    return this.storage.get(key);
   }
 
   isRoot() {
@@ -239,6 +186,12 @@ class Scope {
     }
   }
 
+  // Replaces the frame of the current synchronous execution without
+  // requiring a matching `exit()` call; used by `enterWith()`.
+  static replace(frame: AsyncContextFrame) {
+    setAsyncContext(frame.isRoot() ? undefined : frame);
+  }
+
   static exit() {
     popAsyncFrame();
   }
@@ -251,10 +204,6 @@ class StorageEntry {
     this.key = key;
     this.value = value;
   }
-
-  clone() {
-    return new StorageEntry(this.key, this.value);
-  }
 }
 
 class StorageKey {
@@ -281,6 +230,17 @@ export class AsyncLocalStorage {
     fnReg.register(this, this.#key);
   }
 
+  static bind(fn: (...args: unknown[]) => unknown) {
+    return AsyncResource.bind(fn);
+  }
+
+  static snapshot() {
+    return AsyncLocalStorage.bind(
+      (cb: (...args: unknown[]) => unknown, ...args: unknown[]) =>
+        cb(...args),
+    );
+  }
+
   // deno-lint-ignore no-explicit-any
   run(store: any, callback: any, ...args: any[]): any {
     const frame = AsyncContextFrame.create(
@@ -299,7 +259,20 @@ export class AsyncLocalStorage {
 
   // deno-lint-ignore no-explicit-any
   exit(callback: (...args: unknown[]) => any, ...args: any[]): any {
-    return this.run(undefined, callback, args);
+    return this.run(undefined, callback, ...args);
+  }
+
+  // deno-lint-ignore no-explicit-any
+  enterWith(store: any) {
+    const frame = AsyncContextFrame.create(
+      null,
+      new StorageEntry(this.#key, store),
+    );
+    Scope.replace(frame);
+  }
+
+  disable() {
+    this.enterWith(undefined);
   }
 
   // deno-lint-ignore no-explicit-any
@@ -313,6 +286,14 @@ export function executionAsyncId() {
   return 1;
 }
 
+export function triggerAsyncId() {
+  return 0;
+}
+
+export function executionAsyncResource() {
+  return {};
+}
+
 class AsyncHook {
   enable() {
   }
@@ -331,6 +312,8 @@ export default {
   // Embedder API
   AsyncResource,
   executionAsyncId,
+  executionAsyncResource,
+  triggerAsyncId,
   createHook,
   AsyncLocalStorage,
 };
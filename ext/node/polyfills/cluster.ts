@@ -1,69 +1,846 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
+// The primary forks the workers with `child_process.fork()` and talks to them
+// over a channel of its own (see ext/node/ops/cluster.rs), as the IPC channel
+// of `fork()` isn't implemented. The servers of the workers don't listen
+// themselves: the primary listens for them and passes each connection it
+// accepts to one of the workers, along with its socket.
+
+import { EventEmitter } from "ext:deno_node/events.ts";
+import { fork as forkProcess } from "ext:deno_node/child_process.ts";
+import type { ChildProcess } from "ext:deno_node/internal/child_process.ts";
+import { nextTick } from "ext:deno_node/_next_tick.ts";
+import process from "ext:deno_node/process.ts";
+import { codeMap } from "ext:deno_node/internal_binding/uv.ts";
+import {
+  constants as TCPConstants,
+  TCP,
+} from "ext:deno_node/internal_binding/tcp_wrap.ts";
+import { TcpConn } from "ext:deno_net/01_net.js";
+
+const core = globalThis.__bootstrap.core;
+const { ops } = core;
+const promiseIdSymbol = Symbol.for("Deno.core.internalPromiseId");
+
+/** Leave the distribution of the connections to the operating system. Deno
+ * can't share a listening socket between processes, so this is the same as
+ * `SCHED_RR`. */
+export const SCHED_NONE = 1;
+/** Pass the connections to the workers in turn. */
+export const SCHED_RR = 2;
+/** Pass the connections from the same remote address to the same worker, as
+ * long as the set of workers doesn't change. This is specific to Deno. */
+export const SCHED_STICKY = 3;
+
+/** Tells the internal messages of the cluster apart from the ones of
+ * `worker.send()`. */
+const INTERNAL_CMD = "NODE_CLUSTER";
+
+interface ClusterSettings {
+  exec?: string;
+  execArgv?: string[];
+  args?: string[];
+  cwd?: string;
+  silent?: boolean;
+  stdio?: unknown[];
+}
+
+interface ServerQuery {
+  address: string | null;
+  port: number | null;
+  addressType: number | null;
+  fd?: number | null;
+  flags?: number;
+}
+
+// deno-lint-ignore no-explicit-any
+type Message = any;
+
+type ReceivedConn = [number, Deno.NetAddr, Deno.NetAddr];
+
+/** One end of the channel between the primary and a worker. */
+class Channel {
+  #rid: number;
+  #onMessage: (message: Message, conn: TcpConn | null) => void;
+  #onClose: () => void;
+  #promiseId: number | null = null;
+  #ref = true;
+  #closed = false;
+
+  constructor(
+    rid: number,
+    onMessage: (message: Message, conn: TcpConn | null) => void,
+    onClose: () => void,
+  ) {
+    this.#rid = rid;
+    this.#onMessage = onMessage;
+    this.#onClose = onClose;
+    this.#recv();
+  }
+
+  get closed() {
+    return this.#closed;
+  }
+
+  async send(message: Message, conn?: Deno.Conn) {
+    if (this.#closed) {
+      return;
+    }
+    await core.opAsync(
+      "op_node_cluster_send",
+      this.#rid,
+      JSON.stringify(message),
+      conn?.rid ?? null,
+    );
+  }
+
+  sendInternal(message: Message, conn?: Deno.Conn) {
+    return this.send({ cmd: INTERNAL_CMD, ...message }, conn);
+  }
+
+  async #recv() {
+    while (!this.#closed) {
+      const promise = core.opAsync("op_node_cluster_recv", this.#rid);
+      this.#promiseId = promise[promiseIdSymbol];
+      if (!this.#ref) {
+        core.unrefOp(this.#promiseId);
+      }
+      let received: { message: string; conn: ReceivedConn | null } | null;
+      try {
+        received = await promise;
+      } catch {
+        received = null;
+      }
+      this.#promiseId = null;
+      if (received === null) {
+        break;
+      }
+      let conn = null;
+      if (received.conn) {
+        const { 0: rid, 1: localAddr, 2: remoteAddr } = received.conn;
+        conn = new TcpConn(
+          rid,
+          { ...remoteAddr, transport: "tcp" },
+          { ...localAddr, transport: "tcp" },
+        );
+      }
+      this.#onMessage(JSON.parse(received.message), conn);
+    }
+    this.close();
+  }
+
+  ref() {
+    this.#ref = true;
+    if (this.#promiseId !== null) {
+      core.refOp(this.#promiseId);
+    }
+  }
+
+  unref() {
+    this.#ref = false;
+    if (this.#promiseId !== null) {
+      core.unrefOp(this.#promiseId);
+    }
+  }
+
+  close() {
+    if (this.#closed) {
+      return;
+    }
+    this.#closed = true;
+    try {
+      core.close(this.#rid);
+    } catch {
+      // already closed
+    }
+    this.#onClose();
+  }
+}
+
+function closeConn(conn: Deno.Conn) {
+  try {
+    conn.close();
+  } catch {
+    // the connection was already passed on
+  }
+}
+
+const kChannel = Symbol("kChannel");
 
 /** A Worker object contains all public information and method about a worker.
  * In the primary it can be obtained using cluster.workers. In a worker it can
  * be obtained using cluster.worker.
  */
-export class Worker {
-  constructor() {
-    notImplemented("cluster.Worker.prototype.constructor");
+export class Worker extends EventEmitter {
+  id: number;
+  process: ChildProcess | typeof process;
+  state: string;
+  exitedAfterDisconnect: boolean | undefined = undefined;
+  [kChannel]: Channel | null = null;
+
+  constructor(
+    options: {
+      id?: number;
+      process?: ChildProcess | typeof process;
+      state?: string;
+    } = {},
+  ) {
+    super();
+    this.id = options.id ?? 0;
+    this.process = options.process ?? process;
+    this.state = options.state ?? "none";
+  }
+
+  /** Sends a message to the primary, or from the primary to this worker. */
+  send(
+    message: Message,
+    handle?: unknown,
+    options?: unknown,
+    callback?: (error: Error | null) => void,
+  ): boolean {
+    if (typeof handle === "function") {
+      callback = handle as typeof callback;
+    } else if (typeof options === "function") {
+      callback = options as typeof callback;
+    }
+    whenConnected(this)
+      .then((channel) => channel.send(message))
+      .then(() => callback?.(null), (error) => callback?.(error));
+    return true;
+  }
+
+  isConnected(): boolean {
+    return this[kChannel] !== null && !this[kChannel].closed;
+  }
+
+  isDead(): boolean {
+    if (isPrimary) {
+      const child = this.process as ChildProcess;
+      return child.exitCode !== null || child.signalCode !== null;
+    }
+    return false;
+  }
+
+  /** Closes the servers of the worker, after which the worker exits once
+   * there is nothing else keeping it alive. */
+  disconnect(): this {
+    this.exitedAfterDisconnect = true;
+    if (isPrimary) {
+      this[kChannel]?.sendInternal({ act: "disconnect" });
+    } else {
+      closeWorkerChannel();
+    }
+    return this;
+  }
+
+  kill(signal = "SIGTERM") {
+    if (isPrimary) {
+      this.exitedAfterDisconnect = true;
+      (this.process as ChildProcess).kill(signal as Deno.Signal);
+    } else {
+      this.disconnect();
+      process.exit(0);
+    }
+  }
+
+  destroy(signal = "SIGTERM") {
+    this.kill(signal);
   }
 }
-/** Calls .disconnect() on each worker in cluster.workers. */
-export function disconnected() {
-  notImplemented("cluster.disconnected");
-}
-/** Spawn a new worker process. */
-export function fork() {
-  notImplemented("cluster.fork");
-}
+
 /** True if the process is a primary. This is determined by
  * the process.env.NODE_UNIQUE_ID. If process.env.NODE_UNIQUE_ID is undefined,
  * then isPrimary is true. */
-export const isPrimary = undefined;
+export let isPrimary = true;
 /** True if the process is not a primary (it is the negation of
  * cluster.isPrimary). */
-export const isWorker = undefined;
+export let isWorker = false;
 /** Deprecated alias for cluster.isPrimary. details. */
-export const isMaster = isPrimary;
-/** The scheduling policy, either cluster.SCHED_RR for round-robin or
- * cluster.SCHED_NONE to leave it to the operating system. This is a global
- * setting and effectively frozen once either the first worker is spawned, or
- * .setupPrimary() is called, whichever comes first. */
-export const schedulingPolicy = undefined;
+export let isMaster = true;
+/** The scheduling policy, either cluster.SCHED_RR for round-robin,
+ * cluster.SCHED_NONE to leave it to the operating system, or
+ * cluster.SCHED_STICKY to keep the clients on the same worker. This is a
+ * global setting and effectively frozen once either the first worker is
+ * spawned, or .setupPrimary() is called, whichever comes first. */
+export let schedulingPolicy = SCHED_RR;
 /** The settings object */
-export const settings = undefined;
-/** Deprecated alias for .setupPrimary(). */
-export function setupMaster() {
-  notImplemented("cluster.setupMaster");
-}
-/** setupPrimary is used to change the default 'fork' behavior. Once called,
- * the settings will be present in cluster.settings. */
-export function setupPrimary() {
-  notImplemented("cluster.setupPrimary");
-}
+export const settings: ClusterSettings = {};
 /** A reference to the current worker object. Not available in the primary
  * process. */
-export const worker = undefined;
+export let worker: Worker | undefined = undefined;
 /** A hash that stores the active worker objects, keyed by id field. Makes it
  * easy to loop through all the workers. It is only available in the primary
  * process. */
-export const workers = undefined;
+export const workers: Record<number, Worker> = {};
+
+const cluster = new EventEmitter() as EventEmitter & Record<string, unknown>;
+
+function schedulingPolicyFromEnv(): number {
+  switch (process.env.NODE_CLUSTER_SCHED_POLICY) {
+    case "none":
+      return SCHED_NONE;
+    case "sticky":
+      return SCHED_STICKY;
+    default:
+      return SCHED_RR;
+  }
+}
+
+// --- primary ---
+
+let setupDone = false;
+let ids = 0;
+let channelServer: { rid: number; path: string } | null = null;
+
+/** The servers that the primary listens on for the workers, keyed by their
+ * address. */
+interface SharedServer {
+  listener: Deno.Listener;
+  /** The workers that listen on the server, sorted by their ids. */
+  workers: Worker[];
+  next: number;
+}
+const sharedServers = new Map<string, SharedServer>();
+
+/** setupPrimary is used to change the default 'fork' behavior. Once called,
+ * the settings will be present in cluster.settings. */
+export function setupPrimary(options?: ClusterSettings) {
+  Object.assign(settings, {
+    args: process.argv.slice(2),
+    exec: process.argv[1],
+    execArgv: process.execArgv,
+    silent: false,
+    ...settings,
+    ...options,
+  });
+  cluster.settings = settings;
+  if (!setupDone) {
+    setupDone = true;
+    // frozen from now on
+    schedulingPolicy = cluster.schedulingPolicy as number;
+    if (schedulingPolicy === SCHED_NONE) {
+      schedulingPolicy = SCHED_RR;
+    }
+  }
+  nextTick(() => cluster.emit("setup", settings));
+}
 
-export default {
+/** Deprecated alias for .setupPrimary(). */
+export const setupMaster = setupPrimary;
+
+/** Spawn a new worker process. */
+export function fork(env?: Record<string, string>): Worker {
+  if (!isPrimary) {
+    throw new Error("cluster.fork() can only be called in the primary");
+  }
+  setupPrimary();
+  if (channelServer === null) {
+    const { 0: rid, 1: path } = ops.op_node_cluster_listen();
+    channelServer = { rid, path };
+    acceptWorkers(rid);
+  }
+
+  const id = ++ids;
+  const child = forkProcess(settings.exec!, settings.args, {
+    cwd: settings.cwd,
+    env: {
+      ...env,
+      NODE_UNIQUE_ID: `${id}`,
+      NODE_CLUSTER_CHANNEL: channelServer.path,
+    },
+    execArgv: settings.execArgv,
+    silent: settings.silent,
+    stdio: settings.stdio,
+  } as Parameters<typeof forkProcess>[2]);
+  const worker = new Worker({ id, process: child });
+  workers[id] = worker;
+
+  child.once("exit", (code: number | null, signal: string | null) => {
+    worker.state = "dead";
+    removeWorker(worker);
+    worker[kChannel]?.close();
+    delete workers[id];
+    if (Object.keys(workers).length === 0) {
+      // removes the directory of the channel, until the next fork
+      closeChannelServer();
+    }
+    worker.emit("exit", code, signal);
+    cluster.emit("exit", worker, code, signal);
+  });
+
+  nextTick(() => cluster.emit("fork", worker));
+  return worker;
+}
+
+/** Calls .disconnect() on each worker in cluster.workers. */
+export function disconnect(callback?: () => void) {
+  const pending = Object.values(workers).filter((worker) =>
+    worker.isConnected()
+  );
+  let remaining = pending.length;
+  if (remaining === 0) {
+    if (callback) nextTick(callback);
+    return;
+  }
+  for (const worker of pending) {
+    worker.once("disconnect", () => {
+      if (--remaining === 0) {
+        callback?.();
+      }
+    });
+    worker.disconnect();
+  }
+}
+
+function closeChannelServer() {
+  if (channelServer !== null) {
+    core.close(channelServer.rid);
+    channelServer = null;
+  }
+}
+
+async function acceptWorkers(rid: number) {
+  while (true) {
+    const promise = core.opAsync("op_node_cluster_accept", rid);
+    // the workers keep the primary alive, not the channel
+    core.unrefOp(promise[promiseIdSymbol]);
+    let channelRid;
+    try {
+      channelRid = await promise;
+    } catch {
+      return;
+    }
+    let worker: Worker | undefined;
+    const channel = new Channel(
+      channelRid,
+      (message) => {
+        if (worker === undefined) {
+          // the first message of a worker is who it is
+          if (message?.cmd !== INTERNAL_CMD || message.act !== "online") {
+            channel.close();
+            return;
+          }
+          worker = workers[message.id];
+          if (worker === undefined || worker[kChannel] !== null) {
+            worker = undefined;
+            channel.close();
+            return;
+          }
+          worker[kChannel] = channel;
+          worker.state = "online";
+          worker.emit("online");
+          cluster.emit("online", worker);
+          return;
+        }
+        onPrimaryMessage(worker, message);
+      },
+      () => {
+        if (worker === undefined) {
+          return;
+        }
+        removeWorker(worker);
+        if (worker.state !== "dead") {
+          worker.state = "disconnected";
+        }
+        worker.emit("disconnect");
+        cluster.emit("disconnect", worker);
+      },
+    );
+    channel.unref();
+  }
+}
+
+function onPrimaryMessage(worker: Worker, message: Message) {
+  if (message?.cmd !== INTERNAL_CMD) {
+    worker.emit("message", message);
+    cluster.emit("message", worker, message);
+    return;
+  }
+  switch (message.act) {
+    case "queryServer":
+      queryServer(worker, message);
+      break;
+    case "listening": {
+      const address = {
+        addressType: message.addressType,
+        address: message.address,
+        port: message.port,
+      };
+      worker.state = "listening";
+      worker.emit("listening", address);
+      cluster.emit("listening", worker, address);
+      break;
+    }
+    case "close":
+      removeWorker(worker, message.key);
+      break;
+  }
+}
+
+function serverKey(query: ServerQuery) {
+  return `${query.addressType}:${query.address}:${query.port}`;
+}
+
+function listenErrno(e: unknown): number {
+  if (e instanceof Deno.errors.AddrInUse) {
+    return codeMap.get("EADDRINUSE")!;
+  } else if (e instanceof Deno.errors.AddrNotAvailable) {
+    return codeMap.get("EADDRNOTAVAIL")!;
+  } else if (e instanceof Deno.errors.PermissionDenied) {
+    return codeMap.get("EACCES")!;
+  }
+  return codeMap.get("UNKNOWN")!;
+}
+
+function queryServer(worker: Worker, message: ServerQuery & { seq: number }) {
+  const key = serverKey(message);
+  let server = sharedServers.get(key);
+  if (server === undefined) {
+    let listener;
+    try {
+      listener = Deno.listen({
+        hostname: message.address ||
+          (message.addressType === 6 ? "::" : "0.0.0.0"),
+        port: message.port ?? 0,
+        transport: "tcp",
+      });
+    } catch (e) {
+      worker[kChannel]?.sendInternal({
+        act: "queryServerReply",
+        seq: message.seq,
+        errno: listenErrno(e),
+      });
+      return;
+    }
+    server = { listener, workers: [], next: 0 };
+    sharedServers.set(key, server);
+    distributeConnections(key, server);
+  }
+  if (!server.workers.includes(worker)) {
+    server.workers.push(worker);
+    server.workers.sort((a, b) => a.id - b.id);
+  }
+  worker[kChannel]?.sendInternal({
+    act: "queryServerReply",
+    seq: message.seq,
+    errno: 0,
+    key,
+    port: (server.listener.addr as Deno.NetAddr).port,
+  });
+}
+
+/** Removes a worker from the servers it listens on, or only from the one with
+ * the given key, closing the servers no worker listens on anymore. */
+function removeWorker(worker: Worker, key?: string) {
+  for (const [serverKey, server] of sharedServers) {
+    if (key !== undefined && key !== serverKey) {
+      continue;
+    }
+    const index = server.workers.indexOf(worker);
+    if (index === -1) {
+      continue;
+    }
+    server.workers.splice(index, 1);
+    if (server.workers.length === 0) {
+      sharedServers.delete(serverKey);
+      try {
+        server.listener.close();
+      } catch {
+        // already closed
+      }
+    }
+  }
+}
+
+/** FNV-1a, to pick the same worker for the same address every time. */
+function hashAddress(address: string): number {
+  let hash = 0x811c9dc5;
+  for (let i = 0; i < address.length; i++) {
+    hash ^= address.charCodeAt(i);
+    hash = Math.imul(hash, 0x01000193);
+  }
+  return hash >>> 0;
+}
+
+function pickWorker(
+  server: SharedServer,
+  remoteAddr: Deno.NetAddr,
+): Worker | undefined {
+  const { workers } = server;
+  if (workers.length === 0) {
+    return undefined;
+  }
+  if (schedulingPolicy === SCHED_STICKY) {
+    return workers[hashAddress(remoteAddr.hostname) % workers.length];
+  }
+  server.next %= workers.length;
+  return workers[server.next++];
+}
+
+async function distributeConnections(key: string, server: SharedServer) {
+  while (true) {
+    let conn;
+    try {
+      conn = await server.listener.accept();
+    } catch (e) {
+      if (sharedServers.get(key) !== server) {
+        // no worker listens on the server anymore
+        return;
+      }
+      if (e instanceof Deno.errors.BadResource) {
+        return;
+      }
+      continue;
+    }
+    const worker = pickWorker(server, conn.remoteAddr as Deno.NetAddr);
+    const channel = worker?.[kChannel];
+    if (!channel || channel.closed) {
+      closeConn(conn);
+      continue;
+    }
+    channel.sendInternal({ act: "newconn", key }, conn).catch(() =>
+      closeConn(conn)
+    );
+  }
+}
+
+// --- worker ---
+
+let workerChannel: Promise<Channel> | null = null;
+let querySeq = 0;
+const queryCallbacks = new Map<number, (reply: Message) => void>();
+const clusterListeners = new Map<string, ClusterListener>();
+
+function whenConnected(target: Worker): Promise<Channel> {
+  if (isPrimary) {
+    const channel = target[kChannel];
+    return channel
+      ? Promise.resolve(channel)
+      : Promise.reject(new Error("The worker isn't connected"));
+  }
+  return workerChannel!;
+}
+
+/** The listener of a server of a worker, which accepts the connections that
+ * the primary passes to the worker. */
+class ClusterListener {
+  #key: string;
+  #addr: Deno.NetAddr;
+  #queue: Deno.Conn[] = [];
+  #pending: {
+    resolve: (conn: Deno.Conn) => void;
+    reject: (error: Error) => void;
+  }[] = [];
+  #ref = true;
+  #closed = false;
+
+  constructor(key: string, addr: Deno.NetAddr) {
+    this.#key = key;
+    this.#addr = addr;
+  }
+
+  get addr() {
+    return this.#addr;
+  }
+
+  hasRef(): boolean {
+    return this.#ref && !this.#closed;
+  }
+
+  accept(): Promise<Deno.Conn> {
+    if (this.#closed) {
+      return Promise.reject(
+        new Deno.errors.BadResource("Listener has been closed"),
+      );
+    }
+    const conn = this.#queue.shift();
+    if (conn !== undefined) {
+      return Promise.resolve(conn);
+    }
+    return new Promise((resolve, reject) => {
+      this.#pending.push({ resolve, reject });
+    });
+  }
+
+  push(conn: Deno.Conn) {
+    const pending = this.#pending.shift();
+    if (pending !== undefined) {
+      pending.resolve(conn);
+    } else {
+      this.#queue.push(conn);
+    }
+  }
+
+  close() {
+    if (this.#closed) {
+      return;
+    }
+    this.#closed = true;
+    clusterListeners.delete(this.#key);
+    for (const { reject } of this.#pending.splice(0)) {
+      reject(new Deno.errors.BadResource("Listener has been closed"));
+    }
+    for (const conn of this.#queue.splice(0)) {
+      closeConn(conn);
+    }
+    workerChannel?.then((channel) =>
+      channel.sendInternal({ act: "close", key: this.#key })
+    ).catch(() => {});
+    updateWorkerRef();
+  }
+
+  ref() {
+    this.#ref = true;
+    updateWorkerRef();
+  }
+
+  unref() {
+    this.#ref = false;
+    updateWorkerRef();
+  }
+}
+
+/** The channel keeps a worker alive as long as it has ref'ed servers. */
+function updateWorkerRef() {
+  workerChannel?.then((channel) => {
+    for (const listener of clusterListeners.values()) {
+      if (listener.hasRef()) {
+        channel.ref();
+        return;
+      }
+    }
+    channel.unref();
+  });
+}
+
+function onWorkerMessage(message: Message, conn: TcpConn | null) {
+  if (message?.cmd !== INTERNAL_CMD) {
+    worker!.emit("message", message);
+    process.emit("message", message);
+    return;
+  }
+  switch (message.act) {
+    case "queryServerReply": {
+      const callback = queryCallbacks.get(message.seq);
+      queryCallbacks.delete(message.seq);
+      callback?.(message);
+      break;
+    }
+    case "newconn": {
+      const listener = clusterListeners.get(message.key);
+      if (listener === undefined) {
+        closeConn(conn!);
+      } else {
+        listener.push(conn!);
+      }
+      break;
+    }
+    case "disconnect":
+      worker!.disconnect();
+      break;
+  }
+}
+
+function closeWorkerChannel() {
+  for (const listener of [...clusterListeners.values()]) {
+    listener.close();
+  }
+  workerChannel?.then((channel) => channel.close());
+}
+
+/** Gets the handle of a server of a worker from the primary. */
+export function _getServer(
+  server: EventEmitter,
+  query: ServerQuery,
+  callback: (errno: number, handle: TCP | null) => void,
+) {
+  const seq = querySeq++;
+  queryCallbacks.set(seq, (reply) => {
+    if (reply.errno) {
+      callback(reply.errno, null);
+      return;
+    }
+    const listener = new ClusterListener(reply.key, {
+      transport: "tcp",
+      hostname: query.address ||
+        (query.addressType === 6 ? "::" : "0.0.0.0"),
+      port: reply.port,
+    });
+    clusterListeners.set(reply.key, listener);
+    updateWorkerRef();
+    const handle = new TCP(TCPConstants.SERVER);
+    handle.setListener(listener as unknown as Deno.Listener);
+    server.once("listening", () => {
+      workerChannel!.then((channel) =>
+        channel.sendInternal({ act: "listening", ...query, port: reply.port })
+      );
+    });
+    callback(0, handle);
+  });
+  workerChannel!.then((channel) =>
+    channel.sendInternal({ act: "queryServer", seq, ...query })
+  ).catch((error) => {
+    queryCallbacks.delete(seq);
+    server.emit("error", error);
+  });
+}
+
+Object.assign(cluster, {
   Worker,
-  disconnected,
+  disconnect,
   fork,
   isPrimary,
   isWorker,
   isMaster,
   schedulingPolicy,
+  SCHED_NONE,
+  SCHED_RR,
+  SCHED_STICKY,
   settings,
   setupMaster,
   setupPrimary,
   worker,
   workers,
+  _getServer,
+});
+
+globalThis.__bootstrap.internals.__initCluster = () => {
+  const id = process.env.NODE_UNIQUE_ID;
+  isPrimary = id === undefined;
+  isWorker = !isPrimary;
+  isMaster = isPrimary;
+  schedulingPolicy = schedulingPolicyFromEnv();
+  Object.assign(cluster, { isPrimary, isWorker, isMaster, schedulingPolicy });
+  if (isPrimary) {
+    return;
+  }
+
+  worker = new Worker({ id: Number(id), process, state: "online" });
+  cluster.worker = worker;
+  cluster.fork = undefined;
+  cluster.disconnect = undefined;
+  workerChannel = core.opAsync("op_node_cluster_connect").then(
+    (rid: number | null) => {
+      if (rid === null) {
+        throw new Error("The channel to the cluster primary is missing");
+      }
+      const channel = new Channel(rid, onWorkerMessage, () => {
+        closeWorkerChannel();
+        worker!.emit("disconnect");
+        // like Node, a worker exits once its primary goes away
+        if (!worker!.exitedAfterDisconnect) {
+          process.exit(0);
+        }
+      });
+      worker![kChannel] = channel;
+      channel.sendInternal({ act: "online", id: worker!.id });
+      updateWorkerRef();
+      return channel;
+    },
+  );
+  // the rejection is surfaced by whatever uses the channel
+  workerChannel.catch(() => {});
 };
+
+export default cluster;
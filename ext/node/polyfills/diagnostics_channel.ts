@@ -2,14 +2,23 @@
 import { ERR_INVALID_ARG_TYPE } from "ext:deno_node/internal/errors.ts";
 import { validateFunction } from "ext:deno_node/internal/validators.mjs";
 import { nextTick } from "ext:deno_node/process.ts";
+import type { AsyncLocalStorage } from "ext:deno_node/async_hooks.ts";
 
 type Subscriber = (message: unknown, name?: string) => void;
+type Store = AsyncLocalStorage;
+type StoreTransform = (message: unknown) => unknown;
+
+function defaultTransform(message: unknown) {
+  return message;
+}
 
 export class Channel {
   _subscribers: Subscriber[];
+  _stores: Map<Store, StoreTransform>;
   name: string;
   constructor(name: string) {
     this._subscribers = [];
+    this._stores = new Map();
     this.name = name;
   }
 
@@ -41,8 +50,47 @@ export class Channel {
     return true;
   }
 
+  bindStore(store: Store, transform: StoreTransform = defaultTransform) {
+    this._stores.set(store, transform);
+  }
+
+  unbindStore(store: Store) {
+    return this._stores.delete(store);
+  }
+
+  // Runs `fn` with the bound stores entered, after publishing `message`.
+  runStores<T>(
+    message: unknown,
+    fn: (...args: unknown[]) => T,
+    thisArg?: unknown,
+    ...args: unknown[]
+  ): T {
+    let run = () => {
+      this.publish(message);
+      return Reflect.apply(fn, thisArg, args);
+    };
+
+    for (const [store, transform] of this._stores) {
+      const next = run;
+      run = () => {
+        let context;
+        try {
+          context = transform(message);
+        } catch (err) {
+          nextTick(() => {
+            throw err;
+          });
+          return next();
+        }
+        return store.run(context, next);
+      };
+    }
+
+    return run();
+  }
+
   get hasSubscribers() {
-    return this._subscribers.length > 0;
+    return this._subscribers.length > 0 || this._stores.size > 0;
   }
 }
 
@@ -80,10 +128,198 @@ export function unsubscribe(name: string, subscription: Subscriber) {
   return c.unsubscribe(subscription);
 }
 
+const traceEvents = [
+  "start",
+  "end",
+  "asyncStart",
+  "asyncEnd",
+  "error",
+] as const;
+
+type TraceEvent = typeof traceEvents[number];
+type TracingChannelSubscribers = Partial<Record<TraceEvent, Subscriber>>;
+// deno-lint-ignore no-explicit-any
+type TraceContext = Record<string, any>;
+
+export class TracingChannel {
+  declare start: Channel;
+  declare end: Channel;
+  declare asyncStart: Channel;
+  declare asyncEnd: Channel;
+  declare error: Channel;
+
+  constructor(nameOrChannels: string | Record<TraceEvent, Channel>) {
+    if (typeof nameOrChannels === "string") {
+      for (const eventName of traceEvents) {
+        Object.defineProperty(this, eventName, {
+          value: channel(`tracing:${nameOrChannels}:${eventName}`),
+        });
+      }
+    } else if (typeof nameOrChannels === "object" && nameOrChannels !== null) {
+      for (const eventName of traceEvents) {
+        const value = nameOrChannels[eventName];
+        if (!(value instanceof Channel)) {
+          throw new ERR_INVALID_ARG_TYPE(
+            `nameOrChannels.${eventName}`,
+            ["Channel"],
+            value,
+          );
+        }
+        Object.defineProperty(this, eventName, { value });
+      }
+    } else {
+      throw new ERR_INVALID_ARG_TYPE(
+        "nameOrChannels",
+        ["string", "object", "Channel"],
+        nameOrChannels,
+      );
+    }
+  }
+
+  subscribe(handlers: TracingChannelSubscribers) {
+    for (const name of traceEvents) {
+      const handler = handlers[name];
+      if (handler) {
+        this[name].subscribe(handler);
+      }
+    }
+  }
+
+  unsubscribe(handlers: TracingChannelSubscribers) {
+    let done = true;
+    for (const name of traceEvents) {
+      const handler = handlers[name];
+      if (handler && !this[name].unsubscribe(handler)) {
+        done = false;
+      }
+    }
+    return done;
+  }
+
+  traceSync<T>(
+    fn: (...args: unknown[]) => T,
+    context: TraceContext = {},
+    thisArg?: unknown,
+    ...args: unknown[]
+  ): T {
+    const { start, end, error } = this;
+    return start.runStores(context, () => {
+      try {
+        const result = Reflect.apply(fn, thisArg, args);
+        context.result = result;
+        return result;
+      } catch (err) {
+        context.error = err;
+        error.publish(context);
+        throw err;
+      } finally {
+        end.publish(context);
+      }
+    });
+  }
+
+  tracePromise<T>(
+    fn: (...args: unknown[]) => Promise<T>,
+    context: TraceContext = {},
+    thisArg?: unknown,
+    ...args: unknown[]
+  ): Promise<T> {
+    const { start, end, asyncStart, asyncEnd, error } = this;
+
+    function reject(err: unknown) {
+      context.error = err;
+      error.publish(context);
+      asyncStart.publish(context);
+      asyncEnd.publish(context);
+      return Promise.reject(err);
+    }
+
+    function resolve(result: T) {
+      context.result = result;
+      asyncStart.publish(context);
+      asyncEnd.publish(context);
+      return result;
+    }
+
+    return start.runStores(context, () => {
+      try {
+        let promise = Reflect.apply(fn, thisArg, args);
+        // convert thenables to native promises
+        if (!(promise instanceof Promise)) {
+          promise = Promise.resolve(promise);
+        }
+        return promise.then(resolve, reject);
+      } catch (err) {
+        context.error = err;
+        error.publish(context);
+        throw err;
+      } finally {
+        end.publish(context);
+      }
+    });
+  }
+
+  traceCallback<T>(
+    fn: (...args: unknown[]) => T,
+    position = -1,
+    context: TraceContext = {},
+    thisArg?: unknown,
+    ...args: unknown[]
+  ): T {
+    const { start, end, asyncStart, asyncEnd, error } = this;
+
+    const callback = args.at(position);
+    validateFunction(callback, "callback");
+
+    function wrappedCallback(this: unknown, err: unknown, res: unknown) {
+      if (err) {
+        context.error = err;
+        error.publish(context);
+      } else {
+        context.result = res;
+      }
+
+      // running the stores here allows to recover the context manually
+      asyncStart.runStores(context, () => {
+        try {
+          return Reflect.apply(
+            callback as (...args: unknown[]) => unknown,
+            this,
+            arguments,
+          );
+        } finally {
+          asyncEnd.publish(context);
+        }
+      });
+    }
+
+    args.splice(position, 1, wrappedCallback);
+
+    return start.runStores(context, () => {
+      try {
+        return Reflect.apply(fn, thisArg, args);
+      } catch (err) {
+        context.error = err;
+        error.publish(context);
+        throw err;
+      } finally {
+        end.publish(context);
+      }
+    });
+  }
+}
+
+export function tracingChannel(
+  nameOrChannels: string | Record<TraceEvent, Channel>,
+) {
+  return new TracingChannel(nameOrChannels);
+}
+
 export default {
   channel,
   hasSubscribers,
   subscribe,
+  tracingChannel,
   unsubscribe,
   Channel,
 };
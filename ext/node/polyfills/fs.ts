@@ -69,6 +69,11 @@ import {
 import { rmdir, rmdirPromise, rmdirSync } from "ext:deno_node/_fs/_fs_rmdir.ts";
 import { rm, rmPromise, rmSync } from "ext:deno_node/_fs/_fs_rm.ts";
 import { stat, statPromise, statSync } from "ext:deno_node/_fs/_fs_stat.ts";
+import {
+  statfs,
+  statfsPromise,
+  statfsSync,
+} from "ext:deno_node/_fs/_fs_statfs.ts";
 import {
   symlink,
   symlinkPromise,
@@ -85,6 +90,9 @@ import {
   unlinkSync,
 } from "ext:deno_node/_fs/_fs_unlink.ts";
 import {
+  lutimes,
+  lutimesPromise,
+  lutimesSync,
   utimes,
   utimesPromise,
   utimesSync,
@@ -149,6 +157,7 @@ const promises = {
   symlink: symlinkPromise,
   lstat: lstatPromise,
   stat: statPromise,
+  statfs: statfsPromise,
   link: linkPromise,
   unlink: unlinkPromise,
   chmod: chmodPromise,
@@ -156,7 +165,7 @@ const promises = {
   // lchown: promisify(lchown),
   chown: chownPromise,
   utimes: utimesPromise,
-  // lutimes = promisify(lutimes),
+  lutimes: lutimesPromise,
   realpath: realpathPromise,
   mkdtemp: mkdtempPromise,
   writeFile: writeFilePromise,
@@ -200,6 +209,8 @@ export default {
   linkSync,
   lstat,
   lstatSync,
+  lutimes,
+  lutimesSync,
   mkdir,
   mkdirSync,
   mkdtemp,
@@ -244,6 +255,8 @@ export default {
   stat,
   Stats,
   statSync,
+  statfs,
+  statfsSync,
   symlink,
   symlinkSync,
   truncate,
@@ -301,6 +314,8 @@ export {
   linkSync,
   lstat,
   lstatSync,
+  lutimes,
+  lutimesSync,
   mkdir,
   mkdirSync,
   mkdtemp,
@@ -345,6 +360,8 @@ export {
   stat,
   Stats,
   statSync,
+  statfs,
+  statfsSync,
   symlink,
   symlinkSync,
   truncate,
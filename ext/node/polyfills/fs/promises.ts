@@ -15,6 +15,7 @@ export const readlink = fsPromises.readlink;
 export const symlink = fsPromises.symlink;
 export const lstat = fsPromises.lstat;
 export const stat = fsPromises.stat;
+export const statfs = fsPromises.statfs;
 export const link = fsPromises.link;
 export const unlink = fsPromises.unlink;
 export const chmod = fsPromises.chmod;
@@ -22,7 +23,7 @@ export const chmod = fsPromises.chmod;
 // export const lchown = fs.lchown;
 export const chown = fsPromises.chown;
 export const utimes = fsPromises.utimes;
-// export const lutimes = fs.lutimes;
+export const lutimes = fsPromises.lutimes;
 export const realpath = fsPromises.realpath;
 export const mkdtemp = fsPromises.mkdtemp;
 export const writeFile = fsPromises.writeFile;
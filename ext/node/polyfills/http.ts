@@ -12,7 +12,10 @@ import {
   Socket,
 } from "ext:deno_node/net.ts";
 import { Buffer } from "ext:deno_node/buffer.ts";
-import { ERR_SERVER_NOT_RUNNING } from "ext:deno_node/internal/errors.ts";
+import {
+  ERR_OUT_OF_RANGE,
+  ERR_SERVER_NOT_RUNNING,
+} from "ext:deno_node/internal/errors.ts";
 import { EventEmitter } from "ext:deno_node/events.ts";
 import { nextTick } from "ext:deno_node/_next_tick.ts";
 import {
@@ -42,13 +45,19 @@ import { notImplemented } from "ext:deno_node/_utils.ts";
 import {
   connResetException,
   ERR_HTTP_HEADERS_SENT,
+  ERR_HTTP_REQUEST_TIMEOUT,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_HTTP_TOKEN,
   ERR_INVALID_PROTOCOL,
   ERR_UNESCAPED_CHARACTERS,
 } from "ext:deno_node/internal/errors.ts";
 import { getTimerDuration } from "ext:deno_node/internal/timers.mjs";
-import { serve, upgradeHttpRaw } from "ext:deno_http/00_serve.js";
+import {
+  kConnectionTimeouts,
+  kRespondOnTimeout,
+  serve,
+  upgradeHttpRaw,
+} from "ext:deno_http/00_serve.js";
 import { createHttpClient } from "ext:deno_fetch/22_http_client.js";
 import { timerId } from "ext:deno_web/03_abort_signal.js";
 import { clearTimeout as webClearTimeout } from "ext:deno_web/02_timers.js";
@@ -1348,6 +1357,14 @@ export class ServerResponse extends NodeWritable {
   _implicitHeader() {
     this.writeHead(this.statusCode);
   }
+
+  /** hyper sends the "100 Continue" response by itself as soon as the
+   * request body is read, so there is nothing to write here. */
+  writeContinue(cb?: () => void) {
+    if (typeof cb === "function") {
+      nextTick(cb);
+    }
+  }
 }
 
 // TODO(@AaronO): optimize
@@ -1423,11 +1440,25 @@ type ServerHandler = (
   res: ServerResponse,
 ) => void;
 
-export function Server(handler?: ServerHandler): ServerImpl {
-  return new ServerImpl(handler);
+const continueExpression = /(?:^|\W)100-continue(?:$|\W)/i;
+
+/** Converts a timeout of `http.Server` to whole milliseconds, where 0
+ * disables it like in Node. */
+function connectionTimeout(msecs: number): number {
+  return Number.isFinite(msecs) && msecs > 0 ? Math.floor(msecs) : 0;
+}
+
+// deno-lint-ignore no-explicit-any
+type ServerOptions = Record<string, any>;
+
+export function Server(
+  opts?: ServerOptions | ServerHandler,
+  handler?: ServerHandler,
+): ServerImpl {
+  return new ServerImpl(opts, handler);
 }
 
-class ServerImpl extends EventEmitter {
+export class ServerImpl extends EventEmitter {
   #httpConnections: Set<Deno.HttpConn> = new Set();
   #listener?: Deno.Listener;
 
@@ -1438,14 +1469,66 @@ class ServerImpl extends EventEmitter {
   #ac?: AbortController;
   #servePromise: Deferred<void>;
   listening = false;
+  keepAliveTimeout = 5000;
+  headersTimeout: number;
+  requestTimeout: number;
 
-  constructor(handler?: ServerHandler) {
+  constructor(
+    opts?: ServerOptions | ServerHandler,
+    handler?: ServerHandler,
+  ) {
     super();
+    if (typeof opts === "function") {
+      handler = opts;
+      opts = {};
+    }
+    opts ??= {};
+
+    const { requestTimeout, headersTimeout, keepAliveTimeout } = opts;
+    if (requestTimeout !== undefined) {
+      validateInteger(requestTimeout, "requestTimeout", 0);
+      this.requestTimeout = requestTimeout;
+    } else {
+      this.requestTimeout = 300_000;
+    }
+    if (headersTimeout !== undefined) {
+      validateInteger(headersTimeout, "headersTimeout", 0);
+      this.headersTimeout = headersTimeout;
+    } else {
+      this.headersTimeout = Math.min(60_000, this.requestTimeout);
+    }
+    if (
+      this.requestTimeout > 0 && this.headersTimeout > 0 &&
+      this.headersTimeout > this.requestTimeout
+    ) {
+      throw new ERR_OUT_OF_RANGE(
+        "headersTimeout",
+        "<= requestTimeout",
+        headersTimeout,
+      );
+    }
+    if (keepAliveTimeout !== undefined) {
+      validateInteger(keepAliveTimeout, "keepAliveTimeout", 0);
+      this.keepAliveTimeout = keepAliveTimeout;
+    }
+
     this.#servePromise = deferred();
     this.#servePromise.then(() => this.emit("close"));
     if (handler !== undefined) {
       this.on("request", handler);
     }
+    // Like node, the clients that took too long to send a request only get a
+    // 408 response when there is no "clientError" listener to handle them.
+    this.on("newListener", (event) => {
+      if (event === "clientError") {
+        this.#server?.[kRespondOnTimeout](false);
+      }
+    });
+    this.on("removeListener", (event) => {
+      if (event === "clientError" && this.listenerCount("clientError") === 0) {
+        this.#server?.[kRespondOnTimeout](true);
+      }
+    });
   }
 
   listen(...args: unknown[]): this {
@@ -1492,7 +1575,7 @@ class ServerImpl extends EventEmitter {
       } else {
         return new Promise<Response>((resolve): void => {
           const res = new ServerResponse(resolve);
-          this.emit("request", req, res);
+          this.#emitRequest(req, res);
         });
       }
     };
@@ -1511,6 +1594,15 @@ class ServerImpl extends EventEmitter {
           this.#addr!.port = port;
           this.emit("listening");
         },
+        [kConnectionTimeouts]: {
+          keepAliveTimeout: connectionTimeout(this.keepAliveTimeout),
+          headersTimeout: connectionTimeout(this.headersTimeout),
+          requestTimeout: connectionTimeout(this.requestTimeout),
+          respondOnTimeout: this.listenerCount("clientError") === 0,
+          onClientTimeout: (remoteAddress: string, remotePort: number) =>
+            this.#emitClientTimeout(remoteAddress, remotePort),
+        },
+        ...this._additionalServeOptions(),
       },
     );
     if (this.#unref) {
@@ -1519,6 +1611,52 @@ class ServerImpl extends EventEmitter {
     this.#server.finished.then(() => this.#servePromise!.resolve());
   }
 
+  /** Overridden by the servers of `node:https` and `node:http2` to serve
+   * over TLS. */
+  _additionalServeOptions(): Record<string | symbol, unknown> {
+    return {};
+  }
+
+  #emitClientTimeout(remoteAddress: string, remotePort: number) {
+    if (this.listenerCount("clientError") === 0) {
+      return;
+    }
+    // The connection is closed by the time the listeners are told, so they
+    // get a socket that is already destroyed.
+    const socket = {
+      remoteAddress,
+      remotePort,
+      destroyed: true,
+      writable: false,
+      destroy() {},
+      end() {},
+      write() {
+        return false;
+      },
+    };
+    this.emit("clientError", new ERR_HTTP_REQUEST_TIMEOUT(), socket);
+  }
+
+  #emitRequest(req: IncomingMessageForServer, res: ServerResponse) {
+    const expect = req.headers["expect"];
+    if (expect === undefined) {
+      this.emit("request", req, res);
+    } else if (continueExpression.test(expect)) {
+      // Without a "checkContinue" listener, hyper answers with
+      // "100 Continue" once the request body is read.
+      if (this.listenerCount("checkContinue") > 0) {
+        this.emit("checkContinue", req, res);
+      } else {
+        this.emit("request", req, res);
+      }
+    } else if (this.listenerCount("checkExpectation") > 0) {
+      this.emit("checkExpectation", req, res);
+    } else {
+      res.writeHead(417);
+      res.end();
+    }
+  }
+
   setTimeout() {
     console.error("Not implemented: Server.setTimeout()");
   }
@@ -1573,8 +1711,11 @@ class ServerImpl extends EventEmitter {
 
 Server.prototype = ServerImpl.prototype;
 
-export function createServer(handler?: ServerHandler) {
-  return Server(handler);
+export function createServer(
+  opts?: ServerOptions | ServerHandler,
+  handler?: ServerHandler,
+) {
+  return Server(opts, handler);
 }
 
 /** Makes an HTTP request. */
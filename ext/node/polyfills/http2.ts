@@ -10,6 +10,7 @@ import { setStreamTimeout } from "ext:deno_node/internal/stream_base_commons.ts"
 import { FileHandle } from "ext:deno_node/fs/promises.ts";
 import { kStreamBaseField } from "ext:deno_node/internal_binding/stream_wrap.ts";
 import { addTrailers, serveHttpOnConnection } from "ext:deno_http/00_serve.js";
+import { ServerImpl as HttpsServerImpl } from "ext:deno_node/https.ts";
 import { type Deferred, deferred } from "ext:deno_node/_util/async.ts";
 import { nextTick } from "ext:deno_node/_next_tick.ts";
 import { TextEncoder } from "ext:deno_web/08_text_encoding.js";
@@ -542,7 +543,12 @@ export class Http2Server extends Server {
   }
 }
 
-export class Http2SecureServer extends Server {
+/**
+ * Serves HTTP/2 over TLS. With `allowHTTP1`, clients that don't negotiate
+ * "h2" through ALPN are served HTTP/1.1 by the same server, like in Node.
+ * Both kinds of requests are emitted as "request" events.
+ */
+export class Http2SecureServer extends HttpsServerImpl {
   #options: Record<string, unknown> = {};
   timeout = 0;
 
@@ -550,17 +556,12 @@ export class Http2SecureServer extends Server {
     options: Record<string, unknown>,
     requestListener: () => unknown,
   ) {
-    super(options, function () {
-      notImplemented("connectionListener");
-    });
-    this.#options = options;
-    if (typeof requestListener === "function") {
-      this.on("request", requestListener);
-    }
+    super(options, requestListener);
+    this.#options = options ?? {};
   }
 
-  close(_callback?: () => unknown) {
-    notImplemented("Http2SecureServer.close");
+  override _alpnProtocols(): string[] {
+    return this.#options.allowHTTP1 ? ["h2", "http/1.1"] : ["h2"];
   }
 
   setTimeout(msecs: number, callback?: () => unknown) {
@@ -587,11 +588,10 @@ export function createServer(
 }
 
 export function createSecureServer(
-  _options: Record<string, unknown>,
-  _onRequestHandler: () => unknown,
+  options: Record<string, unknown>,
+  onRequestHandler: () => unknown,
 ): Http2SecureServer {
-  notImplemented("http2.createSecureServer");
-  return new Http2SecureServer();
+  return new Http2SecureServer(options, onRequestHandler);
 }
 
 export function connect(
@@ -1,23 +1,75 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import { urlToHttpOptions } from "ext:deno_node/internal/url.ts";
 import {
   ClientRequest,
   IncomingMessageForClient as IncomingMessage,
+  IncomingMessageForServer,
   type RequestOptions,
+  ServerImpl as HttpServerImpl,
+  ServerResponse,
 } from "ext:deno_node/http.ts";
 import { Agent as HttpAgent } from "ext:deno_node/_http_agent.mjs";
 import { createHttpClient } from "ext:deno_fetch/22_http_client.js";
+import { kAlpnProtocols } from "ext:deno_http/00_serve.js";
 
-export class Server {
-  constructor() {
-    notImplemented("https.Server.prototype.constructor");
+type ServerHandler = (
+  req: IncomingMessageForServer,
+  res: ServerResponse,
+) => void;
+
+// deno-lint-ignore no-explicit-any
+type ServerOptions = Record<string, any>;
+
+export function Server(
+  opts?: ServerOptions | ServerHandler,
+  requestListener?: ServerHandler,
+): ServerImpl {
+  return new ServerImpl(opts, requestListener);
+}
+
+export class ServerImpl extends HttpServerImpl {
+  #options: ServerOptions;
+
+  constructor(
+    opts?: ServerOptions | ServerHandler,
+    requestListener?: ServerHandler,
+  ) {
+    if (typeof opts === "function") {
+      requestListener = opts;
+      opts = {};
+    }
+    super(opts, requestListener);
+    this.#options = opts ?? {};
+  }
+
+  /** The protocols offered through ALPN. Like in Node, only HTTP/1.1 unless
+   * the `ALPNProtocols` option says otherwise. */
+  _alpnProtocols(): string[] {
+    const protocols = this.#options.ALPNProtocols;
+    if (protocols === undefined) {
+      return ["http/1.1"];
+    }
+    return Array.from(protocols, (protocol) => protocol.toString());
+  }
+
+  override _additionalServeOptions() {
+    return {
+      key: this.#options.key?.toString(),
+      cert: this.#options.cert?.toString(),
+      [kAlpnProtocols]: this._alpnProtocols(),
+    };
   }
 }
-export function createServer() {
-  notImplemented("https.createServer");
+
+Server.prototype = ServerImpl.prototype;
+
+export function createServer(
+  opts?: ServerOptions | ServerHandler,
+  requestListener?: ServerHandler,
+) {
+  return new ServerImpl(opts, requestListener);
 }
 
 interface HttpsRequestOptions extends RequestOptions {
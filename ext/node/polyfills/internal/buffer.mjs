@@ -11,15 +11,19 @@ import {
   base64ToBytes,
   base64UrlToBytes,
   bytesToAscii,
+  bytesToBase64,
+  bytesToBase64Url,
+  bytesToHex,
+  bytesToLatin1,
   bytesToUtf16le,
   hexToBytes,
+  latin1ToBytes,
   utf16leToBytes,
 } from "ext:deno_node/internal_binding/_utils.ts";
 import { isAnyArrayBuffer, isArrayBufferView } from "ext:deno_node/internal/util/types.ts";
 import { normalizeEncoding } from "ext:deno_node/internal/util.mjs";
 import { validateBuffer } from "ext:deno_node/internal/validators.mjs";
 import { isUint8Array } from "ext:deno_node/internal/util/types.ts";
-import { forgivingBase64Encode, forgivingBase64UrlEncode } from "ext:deno_web/00_infra.js";
 import { atob, btoa } from "ext:deno_web/05_base64.js";
 import { Blob } from "ext:deno_web/09_file.js";
 
@@ -645,11 +649,7 @@ Buffer.prototype.base64Slice = function base64Slice(
   offset,
   length,
 ) {
-  if (offset === 0 && length === this.length) {
-    return forgivingBase64Encode(this);
-  } else {
-    return forgivingBase64Encode(this.slice(offset, length));
-  }
+  return _base64Slice(this, offset, length);
 };
 
 Buffer.prototype.base64Write = function base64Write(
@@ -665,9 +665,9 @@ Buffer.prototype.base64urlSlice = function base64urlSlice(
   length,
 ) {
   if (offset === 0 && length === this.length) {
-    return forgivingBase64UrlEncode(this);
+    return bytesToBase64Url(this);
   } else {
-    return forgivingBase64UrlEncode(this.slice(offset, length));
+    return bytesToBase64Url(this.slice(offset, length));
   }
 };
 
@@ -680,12 +680,7 @@ Buffer.prototype.base64urlWrite = function base64urlWrite(
 };
 
 Buffer.prototype.hexWrite = function hexWrite(string, offset, length) {
-  return blitBuffer(
-    hexToBytes(string, this.length - offset),
-    this,
-    offset,
-    length,
-  );
+  return blitBuffer(hexToBytes(string), this, offset, length);
 };
 
 Buffer.prototype.hexSlice = function hexSlice(string, offset, length) {
@@ -705,7 +700,7 @@ Buffer.prototype.latin1Write = function latin1Write(
   offset,
   length,
 ) {
-  return blitBuffer(asciiToBytes(string), this, offset, length);
+  return blitBuffer(latin1ToBytes(string), this, offset, length);
 };
 
 Buffer.prototype.ucs2Slice = function ucs2Slice(offset, length) {
@@ -823,9 +818,9 @@ function fromArrayBuffer(obj, byteOffset, length) {
 
 function _base64Slice(buf, start, end) {
   if (start === 0 && end === buf.length) {
-    return forgivingBase64Encode(buf);
+    return bytesToBase64(buf);
   } else {
-    return forgivingBase64Encode(buf.slice(start, end));
+    return bytesToBase64(buf.slice(start, end));
   }
 }
 
@@ -836,12 +831,7 @@ function _utf8Slice(buf, start, end) {
 }
 
 function _latin1Slice(buf, start, end) {
-  let ret = "";
-  end = Math.min(buf.length, end);
-  for (let i = start; i < end; ++i) {
-    ret += String.fromCharCode(buf[i]);
-  }
-  return ret;
+  return bytesToLatin1(buf.slice(start, end));
 }
 
 function _hexSlice(buf, start, end) {
@@ -852,11 +842,7 @@ function _hexSlice(buf, start, end) {
   if (!end || end < 0 || end > len) {
     end = len;
   }
-  let out = "";
-  for (let i = start; i < end; ++i) {
-    out += hexSliceLookupTable[buf[i]];
-  }
-  return out;
+  return bytesToHex(buf.slice(start, end));
 }
 
 Buffer.prototype.slice = function slice(start, end) {
@@ -1786,18 +1772,6 @@ function isInstance(obj, type) {
       obj.constructor.name != null && obj.constructor.name === type.name;
 }
 
-const hexSliceLookupTable = function () {
-  const alphabet = "0123456789abcdef";
-  const table = new Array(256);
-  for (let i = 0; i < 16; ++i) {
-    const i16 = i * 16;
-    for (let j = 0; j < 16; ++j) {
-      table[i16 + j] = alphabet[i] + alphabet[j];
-    }
-  }
-  return table;
-}();
-
 function defineBigIntMethod(fn) {
   return typeof BigInt === "undefined" ? BufferBigIntNotDefined : fn;
 }
@@ -2214,7 +2188,7 @@ export const encodingOps = {
     indexOf: (buf, val, byteOffset, dir) =>
       indexOfBuffer(
         buf,
-        asciiToBytes(val),
+        latin1ToBytes(val),
         byteOffset,
         encodingsMap.latin1,
         dir,
@@ -41,6 +41,8 @@ import { kEmptyObject } from "ext:deno_node/internal/util.mjs";
 import { getValidatedPath } from "ext:deno_node/internal/fs/utils.mjs";
 import process from "ext:deno_node/process.ts";
 
+const { ops } = globalThis.__bootstrap.core;
+
 export function mapValues<T, O>(
   record: Readonly<Record<string, T>>,
   transformer: (value: T) => O,
@@ -172,6 +174,7 @@ export class ChildProcess extends EventEmitter {
     this.spawnargs = [cmd, ...cmdArgs];
 
     const stringEnv = mapValues(env, (value) => value.toString());
+    ops.op_node_check_run(cmd, "node:child_process.spawn()");
     try {
       this.#process = new Deno.Command(cmd, {
         args: cmdArgs,
@@ -828,6 +831,7 @@ export function spawnSync(
   [command, args] = buildCommand(command, args ?? [], shell);
 
   const result: SpawnSyncResult = {};
+  ops.op_node_check_run(command, "node:child_process.spawnSync()");
   try {
     const output = new Deno.Command(command, {
       args,
@@ -1,5 +1,5 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { kKeyObject } from "ext:deno_node/internal/crypto/constants.ts";
+import { CryptoKey } from "ext:deno_crypto/00_crypto.js";
 
 export const kKeyType = Symbol("kKeyType");
 
@@ -10,7 +10,5 @@ export function isKeyObject(obj: unknown): boolean {
 }
 
 export function isCryptoKey(obj: unknown): boolean {
-  return (
-    obj != null && (obj as Record<symbol, unknown>)[kKeyObject] !== undefined
-  );
+  return obj instanceof CryptoKey;
 }
@@ -10,7 +10,7 @@ import { Buffer } from "ext:deno_node/buffer.ts";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import type { TransformOptions } from "ext:deno_node/_stream.d.ts";
 import { Transform } from "ext:deno_node/_stream.mjs";
-import { KeyObject } from "./keys.ts";
+import { isKeyObject, KeyObject, prepareSecretKey } from "./keys.ts";
 import type { BufferEncoding } from "ext:deno_node/_global.d.ts";
 import type {
   BinaryLike,
@@ -120,6 +120,14 @@ function toU8(input: string | Uint8Array): Uint8Array {
   return typeof input === "string" ? encode(input) : input;
 }
 
+/** Secret `KeyObject`s are used by their key material. */
+function prepareKey(key: CipherKey): Uint8Array {
+  if (isKeyObject(key)) {
+    return prepareSecretKey(key, undefined) as Uint8Array;
+  }
+  return toU8(key as string | Uint8Array);
+}
+
 export class Cipheriv extends Transform implements Cipher {
   /** CipherContext resource id */
   #context: number;
@@ -145,7 +153,11 @@ export class Cipheriv extends Transform implements Cipher {
       ...options,
     });
     this.#cache = new BlockModeCache(false);
-    this.#context = ops.op_node_create_cipheriv(cipher, toU8(key), toU8(iv));
+    this.#context = ops.op_node_create_cipheriv(
+      cipher,
+      prepareKey(key),
+      toU8(iv),
+    );
   }
 
   final(encoding: string = getDefaultEncoding()): Buffer | string {
@@ -261,7 +273,11 @@ export class Decipheriv extends Transform implements Cipher {
       ...options,
     });
     this.#cache = new BlockModeCache(true);
-    this.#context = ops.op_node_create_decipheriv(cipher, toU8(key), toU8(iv));
+    this.#context = ops.op_node_create_decipheriv(
+      cipher,
+      prepareKey(key),
+      toU8(iv),
+    );
   }
 
   final(encoding: string = getDefaultEncoding()): Buffer | string {
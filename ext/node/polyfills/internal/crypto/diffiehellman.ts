@@ -7,6 +7,8 @@ import {
   isArrayBufferView,
 } from "ext:deno_node/internal/util/types.ts";
 import {
+  ERR_CRYPTO_ECDH_INVALID_FORMAT,
+  ERR_CRYPTO_ECDH_INVALID_PUBLIC_KEY,
   ERR_CRYPTO_UNKNOWN_DH_GROUP,
   ERR_INVALID_ARG_TYPE,
   NodeError,
@@ -1163,32 +1165,81 @@ export class DiffieHellmanGroup {
   }
 }
 
+function getEllipticCurve(curve: string): EllipticCurve {
+  validateString(curve, "curve");
+
+  const c = ellipticCurves.find((x) => x.name == curve);
+  if (c == undefined) {
+    throw new Error("invalid curve");
+  }
+  return c;
+}
+
+/**
+ * Encodes a public key of the curve in the given format. Hybrid keys are
+ * uncompressed keys whose prefix also has the parity of the y coordinate.
+ */
+function encodePublicKey(
+  curve: EllipticCurve,
+  key: Uint8Array,
+  format: ECDHKeyFormat = "uncompressed",
+): Buffer {
+  if (
+    format !== "uncompressed" && format !== "compressed" && format !== "hybrid"
+  ) {
+    throw new ERR_CRYPTO_ECDH_INVALID_FORMAT(format);
+  }
+  if (key[0] === 0x06 || key[0] === 0x07) {
+    key = Buffer.from(key);
+    key[0] = 0x04;
+  }
+  const converted = ops.op_node_ecdh_convert_key(
+    curve.name,
+    key,
+    format === "compressed",
+  );
+  if (converted === null) {
+    throw new ERR_CRYPTO_ECDH_INVALID_PUBLIC_KEY();
+  }
+  const buf = Buffer.from(
+    converted.buffer,
+    converted.byteOffset,
+    converted.byteLength,
+  );
+  if (format === "hybrid") {
+    buf[0] = 0x06 | (buf[buf.length - 1] & 1);
+  }
+  return buf;
+}
+
+function encode(buf: Buffer, encoding?: string): Buffer | string {
+  if (encoding !== undefined && encoding !== "buffer") {
+    return buf.toString(encoding);
+  }
+  return buf;
+}
+
 export class ECDH {
   #curve: EllipticCurve; // the selected curve
   #privbuf: Buffer; // the private key
   #pubbuf: Buffer; // the public key
 
   constructor(curve: string) {
-    validateString(curve, "curve");
-
-    const c = ellipticCurves.find((x) => x.name == curve);
-    if (c == undefined) {
-      throw new Error("invalid curve");
-    }
-
-    this.#curve = c;
+    this.#curve = getEllipticCurve(curve);
     this.#pubbuf = Buffer.alloc(this.#curve.publicKeySize);
     this.#privbuf = Buffer.alloc(this.#curve.privateKeySize);
   }
 
   static convertKey(
-    _key: BinaryLike,
-    _curve: string,
-    _inputEncoding?: BinaryToTextEncoding,
-    _outputEncoding?: "latin1" | "hex" | "base64" | "base64url",
-    _format?: "uncompressed" | "compressed" | "hybrid",
+    key: BinaryLike,
+    curve: string,
+    inputEncoding?: BinaryToTextEncoding,
+    outputEncoding?: "latin1" | "hex" | "base64" | "base64url",
+    format?: "uncompressed" | "compressed" | "hybrid",
   ): Buffer | string {
-    notImplemented("crypto.ECDH.prototype.convertKey");
+    const c = getEllipticCurve(curve);
+    const buf = encodePublicKey(c, toBuf(key, inputEncoding), format);
+    return encode(buf, outputEncoding);
   }
 
   computeSecret(otherPublicKey: ArrayBufferView): Buffer;
@@ -1226,7 +1277,7 @@ export class ECDH {
   generateKeys(encoding: BinaryToTextEncoding, format?: ECDHKeyFormat): string;
   generateKeys(
     encoding?: BinaryToTextEncoding,
-    _format?: ECDHKeyFormat,
+    format?: ECDHKeyFormat,
   ): Buffer | string {
     ops.op_node_ecdh_generate_keys(
       this.#curve.name,
@@ -1234,10 +1285,7 @@ export class ECDH {
       this.#privbuf,
     );
 
-    if (encoding !== undefined) {
-      return this.#pubbuf.toString(encoding);
-    }
-    return this.#pubbuf;
+    return this.getPublicKey(encoding, format);
   }
 
   getPrivateKey(): Buffer;
@@ -1253,12 +1301,12 @@ export class ECDH {
   getPublicKey(encoding: BinaryToTextEncoding, format?: ECDHKeyFormat): string;
   getPublicKey(
     encoding?: BinaryToTextEncoding,
-    _format?: ECDHKeyFormat,
+    format?: ECDHKeyFormat,
   ): Buffer | string {
-    if (encoding !== undefined) {
-      return this.#pubbuf.toString(encoding);
-    }
-    return this.#pubbuf;
+    const buf = format === undefined || format === "uncompressed"
+      ? this.#pubbuf
+      : encodePublicKey(this.#curve, this.#pubbuf, format);
+    return encode(buf, encoding);
   }
 
   setPrivateKey(privateKey: ArrayBufferView): void;
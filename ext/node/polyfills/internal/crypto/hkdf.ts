@@ -7,17 +7,20 @@ import {
   validateString,
 } from "ext:deno_node/internal/validators.mjs";
 import {
-  ERR_CRYPTO_INVALID_DIGEST,
+  ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE,
+  ERR_CRYPTO_INVALID_KEYLEN,
   ERR_INVALID_ARG_TYPE,
   ERR_OUT_OF_RANGE,
   hideStackFrames,
 } from "ext:deno_node/internal/errors.ts";
 import {
+  getKdfDigest,
   toBuf,
   validateByteSource,
 } from "ext:deno_node/internal/crypto/util.ts";
 import {
   createSecretKey,
+  getKeyMaterial,
   isKeyObject,
   KeyObject,
 } from "ext:deno_node/internal/crypto/keys.ts";
@@ -33,7 +36,7 @@ const { ops } = core;
 
 const validateParameters = hideStackFrames((hash, key, salt, info, length) => {
   validateString(hash, "digest");
-  key = new Uint8Array(prepareKey(key));
+  key = getKeyMaterial(prepareKey(key));
   validateByteSource(salt, "salt");
   validateByteSource(info, "info");
 
@@ -50,8 +53,14 @@ const validateParameters = hideStackFrames((hash, key, salt, info, length) => {
     );
   }
 
+  // like OpenSSL, at most 255 blocks of the digest's size are derived
+  const digest = getKdfDigest(hash);
+  if (length > digest.size * 255) {
+    throw new ERR_CRYPTO_INVALID_KEYLEN();
+  }
+
   return {
-    hash,
+    hash: digest.name,
     key,
     salt,
     info,
@@ -61,6 +70,9 @@ const validateParameters = hideStackFrames((hash, key, salt, info, length) => {
 
 function prepareKey(key: BinaryLike | KeyObject) {
   if (isKeyObject(key)) {
+    if (key.type !== "secret") {
+      throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(key.type, "secret");
+    }
     return key;
   }
 
@@ -108,7 +120,7 @@ export function hkdf(
 
   core.opAsync("op_node_hkdf_async", hash, key, salt, info, length)
     .then((okm) => callback(null, okm.buffer))
-    .catch((err) => callback(new ERR_CRYPTO_INVALID_DIGEST(err), undefined));
+    .catch((err) => callback(err, undefined));
 }
 
 export function hkdfSync(
@@ -127,11 +139,7 @@ export function hkdfSync(
   ));
 
   const okm = new Uint8Array(length);
-  try {
-    ops.op_node_hkdf(hash, key, salt, info, okm);
-  } catch (e) {
-    throw new ERR_CRYPTO_INVALID_DIGEST(e);
-  }
+  ops.op_node_hkdf(hash, key, salt, info, okm);
 
   return okm.buffer;
 }
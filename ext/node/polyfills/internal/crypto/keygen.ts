@@ -6,6 +6,10 @@
 import { KeyObject } from "ext:deno_node/internal/crypto/keys.ts";
 import { kAesKeyLengths } from "ext:deno_node/internal/crypto/util.ts";
 import {
+  AsymmetricKeyDetails,
+  getNamedCurve,
+  PrivateKeyObject,
+  PublicKeyObject,
   SecretKeyObject,
   setOwnedKey,
 } from "ext:deno_node/internal/crypto/keys.ts";
@@ -546,9 +550,8 @@ export function generateKeyPair(
     privateKey: any,
   ) => void,
 ) {
-  createJob(kAsync, type, options).then(([privateKey, publicKey]) => {
-    privateKey = new KeyObject("private", setOwnedKey(privateKey));
-    publicKey = new KeyObject("public", setOwnedKey(publicKey));
+  createJob(kAsync, type, options).then((keys) => {
+    let [privateKey, publicKey] = createKeyPairObjects(type, options, keys);
 
     if (typeof options === "object" && options !== null) {
       const { publicKeyEncoding, privateKeyEncoding } = options as any;
@@ -747,10 +750,11 @@ export function generateKeyPairSync(
 ):
   | KeyPairKeyObjectResult
   | KeyPairSyncResult<string | Buffer, string | Buffer> {
-  let [privateKey, publicKey] = createJob(kSync, type, options);
-
-  privateKey = new KeyObject("private", setOwnedKey(privateKey));
-  publicKey = new KeyObject("public", setOwnedKey(publicKey));
+  let [privateKey, publicKey] = createKeyPairObjects(
+    type,
+    options,
+    createJob(kSync, type, options),
+  );
 
   if (typeof options === "object" && options !== null) {
     const { publicKeyEncoding, privateKeyEncoding } = options as any;
@@ -770,6 +774,30 @@ export function generateKeyPairSync(
 const kSync = 0;
 const kAsync = 1;
 
+function createKeyPairObjects(
+  type: KeyType,
+  options: any,
+  [privateKey, publicKey]: [Uint8Array, Uint8Array],
+): [any, any] {
+  let details: AsymmetricKeyDetails = {};
+  switch (type) {
+    case "rsa":
+    case "rsa-pss":
+      details = {
+        modulusLength: options.modulusLength,
+        publicExponent: BigInt(options.publicExponent ?? 0x10001),
+      };
+      break;
+    case "ec":
+      details = { namedCurve: getNamedCurve(options.namedCurve) };
+      break;
+  }
+  return [
+    new PrivateKeyObject(setOwnedKey(privateKey), type, details),
+    new PublicKeyObject(setOwnedKey(publicKey), type, details),
+  ];
+}
+
 function createJob(mode, type, options) {
   validateString(type, "type");
 
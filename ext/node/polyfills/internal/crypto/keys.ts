@@ -1,11 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // Copyright Joyent, Inc. and Node.js contributors. All rights reserved. MIT license.
 
+import { kHandle } from "ext:deno_node/internal/crypto/constants.ts";
 import {
-  kHandle,
-  kKeyObject,
-} from "ext:deno_node/internal/crypto/constants.ts";
-import {
+  ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS,
   ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE,
   ERR_INVALID_ARG_TYPE,
   ERR_INVALID_ARG_VALUE,
@@ -33,10 +31,17 @@ import {
   validateOneOf,
 } from "ext:deno_node/internal/validators.mjs";
 import {
+  forgivingBase64Encode as encodeToBase64,
   forgivingBase64UrlEncode as encodeToBase64Url,
 } from "ext:deno_web/00_infra.js";
+import {
+  _importKeyData as kImportKeyData,
+  exportKeyInner,
+} from "ext:deno_crypto/00_crypto.js";
 
-const getArrayBufferOrView = hideStackFrames(
+const { ops } = globalThis.__bootstrap.core;
+
+export const getArrayBufferOrView = hideStackFrames(
   (
     buffer,
     name,
@@ -131,9 +136,7 @@ export function isKeyObject(obj: unknown): obj is KeyObject {
   return isKeyObject_(obj);
 }
 
-export function isCryptoKey(
-  obj: unknown,
-): obj is { type: string; [kKeyObject]: KeyObject } {
+export function isCryptoKey(obj: unknown): obj is CryptoKey {
   return isCryptoKey_(obj);
 }
 
@@ -188,7 +191,26 @@ export class KeyObject {
       throw new ERR_INVALID_ARG_TYPE("key", "CryptoKey", key);
     }
 
-    notImplemented("crypto.KeyObject.prototype.from");
+    if (key.type === "secret") {
+      const material = new Uint8Array(exportKeyInner("raw", key));
+      return new SecretKeyObject(setOwnedKey(material));
+    }
+
+    const asymmetricKeyType = getAsymmetricKeyType(key.algorithm);
+    const details = getAsymmetricKeyDetails(key.algorithm);
+    const der = new Uint8Array(
+      exportKeyInner(key.type === "public" ? "spki" : "pkcs8", key),
+    );
+    const material = ops.op_node_key_object_from_der(
+      key.type,
+      asymmetricKeyType,
+      details.namedCurve,
+      der,
+    );
+    const handle = setOwnedKey(material);
+    return key.type === "public"
+      ? new PublicKeyObject(handle, asymmetricKeyType, details)
+      : new PrivateKeyObject(handle, asymmetricKeyType, details);
   }
 
   equals(otherKeyObject: KeyObject): boolean {
@@ -209,6 +231,171 @@ export class KeyObject {
   export(_options?: unknown): string | Buffer | JsonWebKey {
     notImplemented("crypto.KeyObject.prototype.asymmetricKeyType");
   }
+
+  /** Returns the key material in `format`, for `subtle.importKey()`. */
+  [kImportKeyData](_format: string): BufferSource | JsonWebKey {
+    notImplemented("crypto.subtle.importKey with a KeyObject");
+  }
+}
+
+const kNamedCurves: Record<string, string> = {
+  "P-256": "prime256v1",
+  "P-384": "secp384r1",
+};
+
+/** Returns the OpenSSL name of a curve, which node reports. */
+export function getNamedCurve(namedCurve: string): string {
+  return kNamedCurves[namedCurve] ?? namedCurve;
+}
+
+function getAsymmetricKeyType(algorithm: KeyAlgorithm): KeyType {
+  switch (algorithm.name) {
+    case "RSASSA-PKCS1-v1_5":
+    case "RSA-PSS":
+    case "RSA-OAEP":
+      return "rsa";
+    case "ECDSA":
+    case "ECDH":
+      return "ec";
+    case "Ed25519":
+      return "ed25519";
+    case "X25519":
+      return "x25519";
+    default:
+      throw new ERR_INVALID_ARG_VALUE("key.algorithm", algorithm.name);
+  }
+}
+
+function getAsymmetricKeyDetails(
+  algorithm: KeyAlgorithm,
+): AsymmetricKeyDetails {
+  if ("modulusLength" in algorithm) {
+    const { modulusLength, publicExponent } = algorithm as RsaKeyAlgorithm;
+    let exponent = 0n;
+    for (const byte of publicExponent) {
+      exponent = (exponent << 8n) | BigInt(byte);
+    }
+    return { modulusLength, publicExponent: exponent };
+  }
+  if ("namedCurve" in algorithm) {
+    const { namedCurve } = algorithm as EcKeyAlgorithm;
+    return { namedCurve: getNamedCurve(namedCurve) };
+  }
+  return {};
+}
+
+function toPem(label: string, der: Uint8Array): string {
+  const base64 = encodeToBase64(der);
+  let pem = `-----BEGIN ${label}-----\n`;
+  for (let i = 0; i < base64.length; i += 64) {
+    pem += base64.slice(i, i + 64) + "\n";
+  }
+  return pem + `-----END ${label}-----\n`;
+}
+
+/** Encodes the key as SPKI if it is public, or PKCS#8 if it is private. */
+function toDer(key: AsymmetricKeyObject): Uint8Array {
+  return ops.op_node_key_object_to_der(
+    key.type,
+    key.asymmetricKeyType,
+    key.asymmetricKeyDetails.namedCurve,
+    KEY_STORE.get(key[kHandle]),
+  );
+}
+
+function exportDer(
+  key: AsymmetricKeyObject,
+  options: KeyExportOptions<KeyFormat>,
+  type: "spki" | "pkcs8",
+  label: string,
+): string | Buffer {
+  validateObject(options, "options");
+  if (options.type !== type) {
+    notImplemented(
+      `crypto.KeyObject.prototype.export with type "${options.type}"`,
+    );
+  }
+  validateOneOf(options.format, "options.format", ["pem", "der"]);
+  const der = toDer(key);
+  return options.format === "pem" ? toPem(label, der) : Buffer.from(der);
+}
+
+class AsymmetricKeyObject extends KeyObject {
+  #asymmetricKeyType: KeyType;
+  #asymmetricKeyDetails: AsymmetricKeyDetails;
+
+  constructor(
+    type: KeyObjectType,
+    handle: unknown,
+    asymmetricKeyType: KeyType,
+    asymmetricKeyDetails: AsymmetricKeyDetails = {},
+  ) {
+    super(type, handle);
+    this.#asymmetricKeyType = asymmetricKeyType;
+    this.#asymmetricKeyDetails = asymmetricKeyDetails;
+  }
+
+  get asymmetricKeyType(): KeyType {
+    return this.#asymmetricKeyType;
+  }
+
+  get asymmetricKeyDetails(): AsymmetricKeyDetails {
+    return { ...this.#asymmetricKeyDetails };
+  }
+}
+
+export class PublicKeyObject extends AsymmetricKeyObject {
+  constructor(
+    handle: unknown,
+    asymmetricKeyType: KeyType,
+    asymmetricKeyDetails?: AsymmetricKeyDetails,
+  ) {
+    super("public", handle, asymmetricKeyType, asymmetricKeyDetails);
+  }
+
+  export(options: KeyExportOptions<KeyFormat>): string | Buffer {
+    return exportDer(this, options, "spki", "PUBLIC KEY");
+  }
+
+  [kImportKeyData](format: string): BufferSource {
+    switch (format) {
+      case "spki":
+        return toDer(this);
+      case "raw":
+        // EC, Ed25519 and X25519 public keys are kept in their raw form
+        if (["ec", "ed25519", "x25519"].includes(this.asymmetricKeyType)) {
+          return KEY_STORE.get(this[kHandle]).slice();
+        }
+    }
+    throw new ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS(format, "for a public key");
+  }
+}
+
+export class PrivateKeyObject extends AsymmetricKeyObject {
+  constructor(
+    handle: unknown,
+    asymmetricKeyType: KeyType,
+    asymmetricKeyDetails?: AsymmetricKeyDetails,
+  ) {
+    super("private", handle, asymmetricKeyType, asymmetricKeyDetails);
+  }
+
+  export(options: KeyExportOptions<KeyFormat>): string | Buffer {
+    if (options?.cipher !== undefined) {
+      notImplemented("crypto.KeyObject.prototype.export with a cipher");
+    }
+    return exportDer(this, options, "pkcs8", "PRIVATE KEY");
+  }
+
+  [kImportKeyData](format: string): BufferSource {
+    if (format !== "pkcs8") {
+      throw new ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS(
+        format,
+        "for a private key",
+      );
+    }
+    return toDer(this);
+  }
 }
 
 export interface JsonWebKeyInput {
@@ -256,12 +443,12 @@ export function prepareSecretKey(
       if (key.type !== "secret") {
         throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(key.type, "secret");
       }
-      return key[kHandle];
+      return getKeyMaterial(key);
     } else if (isCryptoKey(key)) {
       if (key.type !== "secret") {
         throw new ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE(key.type, "secret");
       }
-      return key[kKeyObject][kHandle];
+      return getKeyMaterial(KeyObject.from(key));
     }
   }
   if (
@@ -311,6 +498,16 @@ export class SecretKeyObject extends KeyObject {
     }
     return key.slice();
   }
+
+  [kImportKeyData](format: string): BufferSource | JsonWebKey {
+    switch (format) {
+      case "raw":
+        return this.export();
+      case "jwk":
+        return this.export({ format: "jwk" });
+    }
+    throw new ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS(format, "for a secret key");
+  }
 }
 
 export function setOwnedKey(key: Uint8Array): unknown {
@@ -345,6 +542,8 @@ export default {
   isCryptoKey,
   KeyObject,
   prepareSecretKey,
+  PrivateKeyObject,
+  PublicKeyObject,
   setOwnedKey,
   SecretKeyObject,
 };
@@ -1,6 +1,14 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import { Buffer } from "ext:deno_node/buffer.ts";
 import { HASH_DATA } from "ext:deno_node/internal/crypto/types.ts";
+import { hideStackFrames } from "ext:deno_node/internal/errors.ts";
+import { getArrayBufferOrView } from "ext:deno_node/internal/crypto/keys.ts";
+import { getKdfDigest } from "ext:deno_node/internal/crypto/util.ts";
+import {
+  validateFunction,
+  validateInt32,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
 
 const { core } = globalThis.__bootstrap;
 const { ops } = core;
@@ -26,9 +34,34 @@ export type Algorithms =
   | "sha384"
   | "sha512";
 
+const check = hideStackFrames(
+  (
+    password: HASH_DATA,
+    salt: HASH_DATA,
+    iterations: number,
+    keylen: number,
+    digest: string,
+  ) => {
+    validateString(digest, "digest");
+    password = getArrayBufferOrView(password, "password");
+    salt = getArrayBufferOrView(salt, "salt");
+    // OpenSSL uses a signed int for these, so they are limited to 31 bits
+    validateInt32(iterations, "iterations", 1);
+    validateInt32(keylen, "keylen", 0);
+
+    return {
+      password,
+      salt,
+      iterations,
+      keylen,
+      digest: getKdfDigest(digest).name as Algorithms,
+    };
+  },
+);
+
 /**
- * @param iterations Needs to be higher or equal than zero
- * @param keylen  Needs to be higher or equal than zero but less than max allocation size (2^30)
+ * @param iterations Needs to be higher than zero
+ * @param keylen  Needs to be higher or equal than zero
  * @param digest Algorithm to be used for encryption
  */
 export function pbkdf2Sync(
@@ -36,26 +69,25 @@ export function pbkdf2Sync(
   salt: HASH_DATA,
   iterations: number,
   keylen: number,
-  digest: Algorithms = "sha1",
+  digest: Algorithms,
 ): Buffer {
-  if (typeof iterations !== "number" || iterations < 0) {
-    throw new TypeError("Bad iterations");
-  }
-  if (typeof keylen !== "number" || keylen < 0 || keylen > MAX_ALLOC) {
-    throw new TypeError("Bad key length");
-  }
+  ({ password, salt, iterations, keylen, digest } = check(
+    password,
+    salt,
+    iterations,
+    keylen,
+    digest,
+  ));
 
   const DK = new Uint8Array(keylen);
-  if (!ops.op_node_pbkdf2(password, salt, iterations, digest, DK)) {
-    throw new Error("Invalid digest");
-  }
+  ops.op_node_pbkdf2(password, salt, iterations, digest, DK);
 
   return Buffer.from(DK);
 }
 
 /**
- * @param iterations Needs to be higher or equal than zero
- * @param keylen  Needs to be higher or equal than zero but less than max allocation size (2^30)
+ * @param iterations Needs to be higher than zero
+ * @param keylen  Needs to be higher or equal than zero
  * @param digest Algorithm to be used for encryption
  */
 export function pbkdf2(
@@ -63,15 +95,18 @@ export function pbkdf2(
   salt: HASH_DATA,
   iterations: number,
   keylen: number,
-  digest: Algorithms = "sha1",
+  digest: Algorithms,
   callback: (err: Error | null, derivedKey?: Buffer) => void,
 ) {
-  if (typeof iterations !== "number" || iterations < 0) {
-    throw new TypeError("Bad iterations");
-  }
-  if (typeof keylen !== "number" || keylen < 0 || keylen > MAX_ALLOC) {
-    throw new TypeError("Bad key length");
-  }
+  ({ password, salt, iterations, keylen, digest } = check(
+    password,
+    salt,
+    iterations,
+    keylen,
+    digest,
+  ));
+
+  validateFunction(callback, "callback");
 
   core.opAsync(
     "op_node_pbkdf2_async",
@@ -4,6 +4,7 @@
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { Buffer } from "ext:deno_node/buffer.ts";
 import {
+  ERR_CRYPTO_INVALID_DIGEST,
   ERR_INVALID_ARG_TYPE,
   hideStackFrames,
 } from "ext:deno_node/internal/errors.ts";
@@ -85,6 +86,8 @@ export const ellipticCurves: Array<EllipticCurve> = [
     publicKeySize: 57,
     sharedSecretSize: 28,
   }, // NIST P-224 EC
+  // The brainpool curves are left out: the arithmetic of the bp256 and bp384
+  // crates is only available behind their "wip-arithmetic-do-not-use" feature.
 ];
 
 // deno-fmt-ignore
@@ -148,6 +151,36 @@ export const validateByteSource = hideStackFrames((val, name) => {
   );
 });
 
+// The output sizes, in bytes, of the digests that `hkdf()` and `pbkdf2()`
+// support, by the names the ops know them by.
+const kdfDigestSizes = new Map([
+  ["md4", 16],
+  ["md5", 16],
+  ["ripemd160", 20],
+  ["sha1", 20],
+  ["sha224", 28],
+  ["sha256", 32],
+  ["sha384", 48],
+  ["sha512", 64],
+]);
+
+/**
+ * Returns the name of a digest as the key derivation ops take it, along with
+ * its output size, or throws `ERR_CRYPTO_INVALID_DIGEST` if it isn't
+ * supported. Names are case insensitive, like in OpenSSL.
+ */
+export function getKdfDigest(digest: string): { name: string; size: number } {
+  let name = digest.toLowerCase();
+  if (name === "rmd160" || name === "ripemd") {
+    name = "ripemd160";
+  }
+  const size = kdfDigestSizes.get(name);
+  if (size === undefined) {
+    throw new ERR_CRYPTO_INVALID_DIGEST(digest);
+  }
+  return { name, size };
+}
+
 /**
  * Returns an array of the names of the supported hash algorithms, such as 'sha1'.
  */
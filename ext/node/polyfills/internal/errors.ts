@@ -902,6 +902,12 @@ export class ERR_CRYPTO_INVALID_DIGEST extends NodeTypeError {
   }
 }
 
+export class ERR_CRYPTO_INVALID_KEYLEN extends NodeRangeError {
+  constructor() {
+    super("ERR_CRYPTO_INVALID_KEYLEN", "Invalid key length");
+  }
+}
+
 export class ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE extends NodeTypeError {
   constructor(x: string, y: string) {
     super(
@@ -1354,6 +1360,11 @@ export class ERR_HTTP_INVALID_STATUS_CODE extends NodeRangeError {
     super("ERR_HTTP_INVALID_STATUS_CODE", `Invalid status code: ${x}`);
   }
 }
+export class ERR_HTTP_REQUEST_TIMEOUT extends NodeError {
+  constructor() {
+    super("ERR_HTTP_REQUEST_TIMEOUT", "Request timeout");
+  }
+}
 export class ERR_HTTP_SOCKET_ENCODING extends NodeError {
   constructor() {
     super(
@@ -1522,6 +1533,11 @@ export class ERR_INVALID_REPL_INPUT extends NodeTypeError {
     super("ERR_INVALID_REPL_INPUT", `${x}`);
   }
 }
+export class ERR_INVALID_STATE extends NodeError {
+  constructor(x: string) {
+    super("ERR_INVALID_STATE", `Invalid state: ${x}`);
+  }
+}
 export class ERR_INVALID_SYNC_FORK_INPUT extends NodeTypeError {
   constructor(x: string) {
     super(
@@ -1687,6 +1703,14 @@ export class ERR_NO_ICU extends NodeTypeError {
     );
   }
 }
+export class ERR_NOT_BUILDING_SNAPSHOT extends NodeError {
+  constructor() {
+    super(
+      "ERR_NOT_BUILDING_SNAPSHOT",
+      "Operation cannot be invoked when not building startup snapshot",
+    );
+  }
+}
 export class ERR_QUICCLIENTSESSION_FAILED extends NodeError {
   constructor(x: string) {
     super(
@@ -2447,6 +2471,35 @@ export class ERR_PACKAGE_IMPORT_NOT_DEFINED extends NodeTypeError {
   }
 }
 
+export class ERR_PARSE_ARGS_INVALID_OPTION_VALUE extends NodeTypeError {
+  constructor(x: string) {
+    super("ERR_PARSE_ARGS_INVALID_OPTION_VALUE", x);
+  }
+}
+
+export class ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL extends NodeTypeError {
+  constructor(x: string) {
+    super(
+      "ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL",
+      `Unexpected argument '${x}'. This command does not take positional arguments`,
+    );
+  }
+}
+
+export class ERR_PARSE_ARGS_UNKNOWN_OPTION extends NodeTypeError {
+  constructor(option: string, allowPositionals: boolean) {
+    const suggestDashDash = allowPositionals
+      ? `. To specify a positional argument starting with a '-', place it at the end of the command after '--', as in '-- ${
+        JSON.stringify(option)
+      }`
+      : "";
+    super(
+      "ERR_PARSE_ARGS_UNKNOWN_OPTION",
+      `Unknown option '${option}'${suggestDashDash}`,
+    );
+  }
+}
+
 export class ERR_PACKAGE_PATH_NOT_EXPORTED extends NodeError {
   constructor(subpath: string, pkgPath: string, basePath?: string) {
     let msg: string;
@@ -2557,6 +2610,10 @@ codes.ERR_IPC_CHANNEL_CLOSED = ERR_IPC_CHANNEL_CLOSED;
 codes.ERR_INVALID_ARG_TYPE = ERR_INVALID_ARG_TYPE;
 codes.ERR_INVALID_ARG_VALUE = ERR_INVALID_ARG_VALUE;
 codes.ERR_OUT_OF_RANGE = ERR_OUT_OF_RANGE;
+codes.ERR_PARSE_ARGS_INVALID_OPTION_VALUE = ERR_PARSE_ARGS_INVALID_OPTION_VALUE;
+codes.ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL =
+  ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL;
+codes.ERR_PARSE_ARGS_UNKNOWN_OPTION = ERR_PARSE_ARGS_UNKNOWN_OPTION;
 codes.ERR_SOCKET_BAD_PORT = ERR_SOCKET_BAD_PORT;
 codes.ERR_BUFFER_OUT_OF_BOUNDS = ERR_BUFFER_OUT_OF_BOUNDS;
 codes.ERR_UNKNOWN_ENCODING = ERR_UNKNOWN_ENCODING;
@@ -2634,6 +2691,7 @@ export default {
   ERR_CRYPTO_INCOMPATIBLE_KEY,
   ERR_CRYPTO_INCOMPATIBLE_KEY_OPTIONS,
   ERR_CRYPTO_INVALID_DIGEST,
+  ERR_CRYPTO_INVALID_KEYLEN,
   ERR_CRYPTO_INVALID_KEY_OBJECT_TYPE,
   ERR_CRYPTO_INVALID_STATE,
   ERR_CRYPTO_PBKDF2_ERROR,
@@ -2701,6 +2759,7 @@ export default {
   ERR_HTTP_HEADERS_SENT,
   ERR_HTTP_INVALID_HEADER_VALUE,
   ERR_HTTP_INVALID_STATUS_CODE,
+  ERR_HTTP_REQUEST_TIMEOUT,
   ERR_HTTP_SOCKET_ENCODING,
   ERR_HTTP_TRAILER_INVALID,
   ERR_INCOMPATIBLE_OPTION_PAIR,
@@ -2742,6 +2801,7 @@ export default {
   ERR_INVALID_RETURN_PROPERTY,
   ERR_INVALID_RETURN_PROPERTY_VALUE,
   ERR_INVALID_RETURN_VALUE,
+  ERR_INVALID_STATE,
   ERR_INVALID_SYNC_FORK_INPUT,
   ERR_INVALID_THIS,
   ERR_INVALID_TUPLE,
@@ -2771,6 +2831,9 @@ export default {
   ERR_OUT_OF_RANGE,
   ERR_PACKAGE_IMPORT_NOT_DEFINED,
   ERR_PACKAGE_PATH_NOT_EXPORTED,
+  ERR_PARSE_ARGS_INVALID_OPTION_VALUE,
+  ERR_PARSE_ARGS_UNEXPECTED_POSITIONAL,
+  ERR_PARSE_ARGS_UNKNOWN_OPTION,
   ERR_QUICCLIENTSESSION_FAILED,
   ERR_QUICCLIENTSESSION_FAILED_SETSOCKET,
   ERR_QUICSESSION_DESTROYED,
@@ -48,6 +48,8 @@
 
 "use strict";
 
+const { ops } = globalThis.__bootstrap.core;
+
 /**
  * Creates an array containing the numeric code points of each Unicode
  * character in the string. While JavaScript uses UCS-2 internally,
@@ -102,3 +104,32 @@ export const ucs2 = {
   decode: ucs2decode,
   encode: ucs2encode,
 };
+
+/**
+ * The UTS #46 processing flags. They are all off by default, which is how
+ * the WHATWG URL standard processes host names.
+ */
+export interface DomainOptions {
+  /** Map deviation characters, like "ß", the way IDNA2003 did. */
+  transitionalProcessing?: boolean;
+  /** Reject code points that STD3 doesn't allow in host names. */
+  useStd3AsciiRules?: boolean;
+  /** Reject empty labels, and labels or domains too long for DNS. */
+  verifyDnsLength?: boolean;
+}
+
+/**
+ * Converts a domain to its ASCII form, throwing a `TypeError` if it isn't
+ * valid.
+ */
+export function toASCII(domain: string, options?: DomainOptions): string {
+  return ops.op_node_idna_domain_to_ascii(domain, options);
+}
+
+/**
+ * Converts a domain to its Unicode form, throwing a `TypeError` if it isn't
+ * valid.
+ */
+export function toUnicode(domain: string, options?: DomainOptions): string {
+  return ops.op_node_idna_domain_to_unicode(domain, options);
+}
@@ -19,6 +19,9 @@ export const kTimeout = Symbol("timeout");
 const kRefed = Symbol("refed");
 const createTimer = Symbol("createTimer");
 
+/** Timers that haven't fired or been cleared yet, keyed by timer id. */
+export const activeTimers = new Map();
+
 // Timer constructor function.
 export function Timeout(callback, after, args, isRepeat, isRefed) {
   if (typeof after === "number" && after > TIMEOUT_MAX) {
@@ -34,13 +37,19 @@ export function Timeout(callback, after, args, isRepeat, isRefed) {
 
 Timeout.prototype[createTimer] = function () {
   const callback = this._onTimeout;
-  const cb = (...args) => callback.bind(this)(...args);
+  const cb = (...args) => {
+    if (!this._isRepeat) {
+      activeTimers.delete(this[kTimerId]);
+    }
+    return callback.bind(this)(...args);
+  };
   const id = this._isRepeat
     ? setInterval_(cb, this._idleTimeout, ...this._timerArgs)
     : setTimeout_(cb, this._idleTimeout, ...this._timerArgs);
   if (!this[kRefed]) {
     Deno.unrefTimer(id);
   }
+  activeTimers.set(id, this);
   return id;
 };
 
@@ -57,6 +66,7 @@ Timeout.prototype[inspect.custom] = function (_, options) {
 
 Timeout.prototype.refresh = function () {
   clearTimeout_(this[kTimerId]);
+  activeTimers.delete(this[kTimerId]);
   this[kTimerId] = this[createTimer]();
   return this;
 };
@@ -117,6 +127,7 @@ export function setUnrefTimeout(callback, timeout, ...args) {
 }
 
 export default {
+  activeTimers,
   getTimerDuration,
   kTimerId,
   kTimeout,
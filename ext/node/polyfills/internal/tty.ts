@@ -0,0 +1,45 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+// Copyright Joyent, Inc. and Node.js contributors. All rights reserved. MIT license.
+
+import { validateInteger } from "ext:deno_node/internal/validators.mjs";
+
+const { ops } = globalThis.__bootstrap.core;
+
+type Env = Record<string, string | undefined>;
+
+function toEnvMap(env: Env): Record<string, string> {
+  const map: Record<string, string> = {};
+  for (const key in env) {
+    const value = env[key];
+    if (value !== undefined) {
+      map[key] = String(value);
+    }
+  }
+  return map;
+}
+
+/**
+ * Gets the number of bits per color the terminal supports: 1, 4, 8 or 24.
+ *
+ * The environment of the process is used when none is given, which requires
+ * env permission. Without it, the terminal is assumed to have no colors.
+ */
+export function getColorDepth(env?: Env): number {
+  return ops.op_node_tty_color_depth(
+    env === undefined ? undefined : toEnvMap(env),
+  );
+}
+
+export function hasColors(count?: number | Env, env?: Env): boolean {
+  if (
+    env === undefined &&
+    (count === undefined || (typeof count === "object" && count !== null))
+  ) {
+    env = count as Env | undefined;
+    count = 16;
+  } else {
+    validateInteger(count, "count", 2);
+  }
+
+  return (count as number) <= 2 ** getColorDepth(env);
+}
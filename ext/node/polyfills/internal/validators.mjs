@@ -279,6 +279,43 @@ const validateArray = hideStackFrames(
   },
 );
 
+/**
+ * @param {unknown} value
+ * @param {string} name
+ */
+function validateStringArray(value, name) {
+  validateArray(value, name);
+  for (let i = 0; i < value.length; i++) {
+    validateString(value[i], `${name}[${i}]`);
+  }
+}
+
+/**
+ * @param {unknown} value
+ * @param {string} name
+ */
+function validateBooleanArray(value, name) {
+  validateArray(value, name);
+  for (let i = 0; i < value.length; i++) {
+    validateBoolean(value[i], `${name}[${i}]`);
+  }
+}
+
+/**
+ * @param {unknown} value
+ * @param {string} name
+ * @param {unknown[]} union
+ */
+function validateUnion(value, name, union) {
+  if (!union.includes(value)) {
+    throw new codes.ERR_INVALID_ARG_TYPE(
+      name,
+      `('${union.join("|")}')`,
+      value,
+    );
+  }
+}
+
 export default {
   isInt32,
   isUint32,
@@ -286,6 +323,7 @@ export default {
   validateAbortSignal,
   validateArray,
   validateBoolean,
+  validateBooleanArray,
   validateBuffer,
   validateFunction,
   validateInt32,
@@ -295,7 +333,9 @@ export default {
   validateOneOf,
   validatePort,
   validateString,
+  validateStringArray,
   validateUint32,
+  validateUnion,
 };
 export {
   isInt32,
@@ -304,6 +344,7 @@ export {
   validateAbortSignal,
   validateArray,
   validateBoolean,
+  validateBooleanArray,
   validateBuffer,
   validateFunction,
   validateInt32,
@@ -313,5 +354,7 @@ export {
   validateOneOf,
   validatePort,
   validateString,
+  validateStringArray,
   validateUint32,
+  validateUnion,
 };
@@ -5,3 +5,7 @@ const { ops } = globalThis.__bootstrap.core;
 export function uvTranslateSysError(sysErrno: number): string {
   return ops.op_node_sys_to_uv_error(sysErrno);
 }
+
+export function uvTranslateUvError(code: string): number | undefined {
+  return ops.op_node_uv_to_sys_error(code) ?? undefined;
+}
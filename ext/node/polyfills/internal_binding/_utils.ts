@@ -1,8 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import {
-  forgivingBase64Decode,
-  forgivingBase64UrlEncode,
-} from "ext:deno_web/00_infra.js";
+
+const { ops } = globalThis.__bootstrap.core;
 
 export function asciiToBytes(str: string) {
   const byteArray = [];
@@ -12,47 +10,37 @@ export function asciiToBytes(str: string) {
   return new Uint8Array(byteArray);
 }
 
-export function base64ToBytes(str: string) {
-  str = base64clean(str);
-  str = str.replaceAll("-", "+").replaceAll("_", "/");
-  return forgivingBase64Decode(str);
+// Node decodes both alphabets for "base64" and "base64url" alike.
+export function base64ToBytes(str: string): Uint8Array {
+  return ops.op_node_buffer_base64_decode(str);
 }
 
-const INVALID_BASE64_RE = /[^+/0-9A-Za-z-_]/g;
-function base64clean(str: string) {
-  // Node takes equal signs as end of the Base64 encoding
-  str = str.split("=")[0];
-  // Node strips out invalid characters like \n and \t from the string, std/base64 does not
-  str = str.trim().replace(INVALID_BASE64_RE, "");
-  // Node converts strings with length < 2 to ''
-  if (str.length < 2) return "";
-  // Node allows for non-padded base64 strings (missing trailing ===), std/base64 does not
-  while (str.length % 4 !== 0) {
-    str = str + "=";
-  }
-  return str;
+export function base64UrlToBytes(str: string): Uint8Array {
+  return ops.op_node_buffer_base64_decode(str);
 }
 
-export function base64UrlToBytes(str: string) {
-  str = base64clean(str);
-  str = str.replaceAll("+", "-").replaceAll("/", "_");
-  return forgivingBase64UrlEncode(str);
+export function hexToBytes(str: string): Uint8Array {
+  return ops.op_node_buffer_hex_decode(str);
 }
 
-export function hexToBytes(str: string) {
-  const byteArray = new Uint8Array(Math.floor((str || "").length / 2));
-  let i;
-  for (i = 0; i < byteArray.length; i++) {
-    const a = Number.parseInt(str[i * 2], 16);
-    const b = Number.parseInt(str[i * 2 + 1], 16);
-    if (Number.isNaN(a) && Number.isNaN(b)) {
-      break;
-    }
-    byteArray[i] = (a << 4) | b;
-  }
-  return new Uint8Array(
-    i === byteArray.length ? byteArray : byteArray.slice(0, i),
-  );
+export function latin1ToBytes(str: string): Uint8Array {
+  return ops.op_node_buffer_latin1_decode(str);
+}
+
+export function bytesToBase64(bytes: Uint8Array): string {
+  return ops.op_node_buffer_base64_encode(bytes, false);
+}
+
+export function bytesToBase64Url(bytes: Uint8Array): string {
+  return ops.op_node_buffer_base64_encode(bytes, true);
+}
+
+export function bytesToHex(bytes: Uint8Array): string {
+  return ops.op_node_buffer_hex_encode(bytes);
+}
+
+export function bytesToLatin1(bytes: Uint8Array): string {
+  return ops.op_node_buffer_latin1_encode(bytes);
 }
 
 export function utf16leToBytes(str: string, units: number) {
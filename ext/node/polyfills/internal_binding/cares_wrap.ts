@@ -31,11 +31,18 @@ import {
   AsyncWrap,
   providerType,
 } from "ext:deno_node/internal_binding/async_wrap.ts";
-// deno-lint-ignore camelcase
-import { ares_strerror } from "ext:deno_node/internal_binding/ares.ts";
+import {
+  AI_ALL,
+  AI_V4MAPPED,
+  // deno-lint-ignore camelcase
+  ares_strerror,
+} from "ext:deno_node/internal_binding/ares.ts";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { isWindows } from "ext:deno_node/_util/os.ts";
 
+const core = globalThis.__bootstrap.core;
+const { ops } = core;
+
 interface LookupAddress {
   address: string;
   family: number;
@@ -63,57 +70,36 @@ export function getaddrinfo(
   req: GetAddrInfoReqWrap,
   hostname: string,
   family: number,
-  _hints: number,
+  hints: number,
   verbatim: boolean,
 ): number {
-  let addresses: string[] = [];
+  ops.op_node_check_net(hostname, "node:dns.lookup()");
 
-  // TODO(cmorten): use hints
+  // The resolver of the system is used, like getaddrinfo(3) in node, so the
+  // hosts file is honored. AI_ADDRCONFIG is not supported.
   // REF: https://nodejs.org/api/dns.html#dns_supported_getaddrinfo_flags
-
-  const recordTypes: ("A" | "AAAA")[] = [];
-
-  if (family === 0 || family === 4) {
-    recordTypes.push("A");
-  }
-  if (family === 0 || family === 6) {
-    recordTypes.push("AAAA");
-  }
-
-  (async () => {
-    await Promise.allSettled(
-      recordTypes.map((recordType) =>
-        Deno.resolveDns(hostname, recordType).then((records) => {
-          records.forEach((record) => addresses.push(record));
-        })
-      ),
-    );
-
-    const error = addresses.length ? 0 : codeMap.get("EAI_NODATA")!;
-
-    // TODO(cmorten): needs work
-    // REF: https://github.com/nodejs/node/blob/master/src/cares_wrap.cc#L1444
-    if (!verbatim) {
-      addresses.sort((a: string, b: string): number => {
-        if (isIPv4(a)) {
-          return -1;
-        } else if (isIPv4(b)) {
-          return 1;
-        }
-
-        return 0;
-      });
-    }
-
+  core.opAsync(
+    "op_node_getaddrinfo",
+    hostname,
+    family,
+    (hints & AI_V4MAPPED) !== 0,
+    (hints & AI_ALL) !== 0,
+    verbatim,
+  ).then(([error, addresses]: [number, string[]]) => {
     // TODO(@bartlomieju): Forces IPv4 as a workaround for Deno not
     // aligning with Node on implicit binding on Windows
     // REF: https://github.com/denoland/deno/issues/10762
     if (isWindows && hostname === "localhost") {
       addresses = addresses.filter((address) => isIPv4(address));
+      if (!error && !addresses.length) {
+        error = codeMap.get("EAI_NODATA")!;
+      }
     }
 
     req.oncomplete(error, addresses);
-  })();
+  }, () => {
+    req.oncomplete(codeMap.get("EAI_FAIL")!, []);
+  });
 
   return 0;
 }
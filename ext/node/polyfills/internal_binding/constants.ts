@@ -1,4 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+import { osType } from "ext:deno_node/_util/os.ts";
+
 let os: {
   dlopen: {
     RTLD_DEEPBIND?: number;
@@ -196,9 +198,7 @@ let os: {
   UV_UDP_REUSEADDR: number;
 };
 
-const core = globalThis.__bootstrap.core;
-const buildOs = core.ops.op_node_build_os();
-if (buildOs === "darwin") {
+if (osType === "darwin") {
   os = {
     UV_UDP_REUSEADDR: 4,
     dlopen: {
@@ -330,7 +330,7 @@ if (buildOs === "darwin") {
       PRIORITY_HIGHEST: -20,
     },
   };
-} else if (buildOs === "linux") {
+} else if (osType === "linux") {
   os = {
     UV_UDP_REUSEADDR: 4,
     dlopen: {
@@ -25,15 +25,30 @@
 // - https://github.com/nodejs/node/blob/master/src/node_options.cc
 // - https://github.com/nodejs/node/blob/master/src/node_options.h
 
-export function getOptions() {
-  // TODO(kt3k): Return option arguments as parsed object
-  return { options: new Map() };
+// deno-lint-ignore no-explicit-any
+const options = new Map<string, { value: any }>();
+
+/** The options from `NODE_OPTIONS`, as parsed by `NodeOptions` in Rust. */
+export interface NodeOptions {
+  maxOldSpaceSize: number | null;
+  require: string[];
+  noWarnings: boolean;
+  enableSourceMaps: boolean;
+}
 
-  // const { Deno } = globalThis as any;
-  // const args = parse(Deno?.args ?? []);
-  // const options = new Map(
-  //   Object.entries(args).map(([key, value]) => [key, { value }]),
-  // );
-  //
-  // return { options };
+export function setOptions(nodeOptions: NodeOptions) {
+  if (nodeOptions.maxOldSpaceSize !== null) {
+    options.set("--max-old-space-size", {
+      value: nodeOptions.maxOldSpaceSize,
+    });
+  }
+  options.set("--require", { value: nodeOptions.require });
+  options.set("--warnings", { value: !nodeOptions.noWarnings });
+  options.set("--enable-source-maps", {
+    value: nodeOptions.enableSourceMaps,
+  });
+}
+
+export function getOptions() {
+  return { options };
 }
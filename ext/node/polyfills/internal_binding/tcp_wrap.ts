@@ -24,6 +24,8 @@
 // - https://github.com/nodejs/node/blob/master/src/tcp_wrap.cc
 // - https://github.com/nodejs/node/blob/master/src/tcp_wrap.h
 
+const { ops } = globalThis.__bootstrap.core;
+
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { unreachable } from "ext:deno_node/_util/asserts.ts";
 import { ConnectionWrap } from "ext:deno_node/internal_binding/connection_wrap.ts";
@@ -96,6 +98,7 @@ export class TCP extends ConnectionWrap {
 
   #closed = false;
   #acceptBackoffDelay?: number;
+  #keepAlive?: [enable: boolean, initialDelay: number];
 
   /**
    * Creates a new TCP class instance.
@@ -203,10 +206,10 @@ export class TCP extends ConnectionWrap {
       transport: "tcp" as const,
     };
 
-    let listener;
+    let listener = this.#listener;
 
     try {
-      listener = Deno.listen(listenOptions);
+      listener ??= Deno.listen(listenOptions);
     } catch (e) {
       if (e instanceof Deno.errors.AddrInUse) {
         return codeMap.get("EADDRINUSE")!;
@@ -230,6 +233,16 @@ export class TCP extends ConnectionWrap {
     return 0;
   }
 
+  /**
+   * Sets the listener that `listen()` accepts connections from, instead of
+   * binding one. The workers of `node:cluster` get their connections from
+   * the primary this way.
+   * @param listener The listener to accept connections from.
+   */
+  setListener(listener: Deno.Listener) {
+    this.#listener = listener;
+  }
+
   override ref() {
     if (this.#listener) {
       this.#listener.ref();
@@ -304,8 +317,31 @@ export class TCP extends ConnectionWrap {
    * @param initialDelay
    * @return An error status code.
    */
-  setKeepAlive(_enable: boolean, _initialDelay: number): number {
-    // TODO(bnoordhuis) https://github.com/denoland/deno/pull/13103
+  setKeepAlive(enable: boolean, initialDelay: number): number {
+    // The handle exists before the connection does, so the setting is kept
+    // until it can be applied once connected.
+    this.#keepAlive = [enable, initialDelay];
+
+    if (!this[kStreamBaseField]) {
+      return 0;
+    }
+
+    return this.#applyKeepAlive();
+  }
+
+  #applyKeepAlive(): number {
+    const [enable, initialDelay] = this.#keepAlive!;
+
+    try {
+      ops.op_set_keepalive_with_delay(
+        (this[kStreamBaseField] as Deno.Conn).rid,
+        enable,
+        initialDelay,
+      );
+    } catch {
+      return codeMap.get("EINVAL")!;
+    }
+
     return 0;
   }
 
@@ -376,6 +412,10 @@ export class TCP extends ConnectionWrap {
         this.#port = req.localPort = localAddr.port;
         this[kStreamBaseField] = conn;
 
+        if (this.#keepAlive) {
+          this.#applyKeepAlive();
+        }
+
         try {
           this.afterConnect(req, 0);
         } catch {
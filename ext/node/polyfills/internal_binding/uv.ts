@@ -28,7 +28,10 @@
 
 import { unreachable } from "ext:deno_node/_util/asserts.ts";
 import { osType } from "ext:deno_node/_util/os.ts";
-import { uvTranslateSysError } from "ext:deno_node/internal_binding/_libuv_winerror.ts";
+import {
+  uvTranslateSysError,
+  uvTranslateUvError,
+} from "ext:deno_node/internal_binding/_libuv_winerror.ts";
 
 // In Node these values are coming from libuv:
 // Ref: https://github.com/libuv/libuv/blob/v1.x/include/uv/errno.h
@@ -428,6 +431,16 @@ export function mapSysErrnoToUvErrno(sysErrno: number): number {
   }
 }
 
+export function mapUvErrnoToSysErrno(uvErrno: number): number {
+  if (osType === "windows") {
+    const code = errorMap.get(uvErrno)?.[0];
+    const sysErrno = code === undefined ? undefined : uvTranslateUvError(code);
+    return sysErrno ?? -uvErrno;
+  } else {
+    return -uvErrno;
+  }
+}
+
 export const UV_EAI_MEMORY = codeMap.get("EAI_MEMORY")!;
 export const UV_EBADF = codeMap.get("EBADF")!;
 export const UV_EEXIST = codeMap.get("EEXIST");
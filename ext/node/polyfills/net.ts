@@ -66,6 +66,7 @@ import {
   writevGeneric,
 } from "ext:deno_node/internal/stream_base_commons.ts";
 import { kTimeout } from "ext:deno_node/internal/timers.mjs";
+import * as cluster from "ext:deno_node/cluster.ts";
 import { nextTick } from "ext:deno_node/_next_tick.ts";
 import {
   DTRACE_NET_SERVER_CONNECTION,
@@ -1651,16 +1652,11 @@ function _listenInCluster(
 ) {
   exclusive = !!exclusive;
 
-  // TODO(cmorten): here we deviate somewhat from the Node implementation which
-  // makes use of the https://nodejs.org/api/cluster.html module to run servers
-  // across a "cluster" of Node processes to take advantage of multi-core
-  // systems.
-  //
-  // Though Deno has has a Worker capability from which we could simulate this,
-  // for now we assert that we are _always_ on the primary process.
-  const isPrimary = true;
+  // The primary only listens on TCP ports for its workers, so the workers
+  // listen on pipes and handles themselves.
+  const isTCP = addressType === 4 || addressType === 6;
 
-  if (isPrimary || exclusive) {
+  if (cluster.isPrimary || exclusive || !isTCP) {
     // Will create a new handle
     // _listen2 sets up the listened handle, it is still named like this
     // to avoid breaking code that wraps this method
@@ -1668,6 +1664,25 @@ function _listenInCluster(
 
     return;
   }
+
+  const serverQuery = { address, port, addressType, fd, flags };
+
+  // Get the primary's server handle, and listen on it
+  cluster._getServer(server, serverQuery, listenOnPrimaryHandle);
+
+  function listenOnPrimaryHandle(err: number, handle: TCP | null) {
+    if (err) {
+      const ex = uvExceptionWithHostPort(err, "bind", address, port);
+
+      return server.emit("error", ex);
+    }
+
+    // Reuse primary's server handle
+    server._handle = handle;
+    // _listen2 sets up the listened handle, it is still named like this
+    // to avoid breaking code that wraps this method
+    server._listen2(address, port, addressType, backlog, fd, flags);
+  }
 }
 
 function _lookupAndListen(
@@ -26,6 +26,9 @@ import process from "ext:deno_node/process.ts";
 import { isWindows, osType } from "ext:deno_node/_util/os.ts";
 import { os } from "ext:deno_node/internal_binding/constants.ts";
 import { osUptime } from "ext:runtime/30_os.js";
+
+const { ops } = globalThis.__bootstrap.core;
+
 export const constants = os;
 
 const SEE_GITHUB_ISSUE = "See https://github.com/denoland/deno_std/issues/1436";
@@ -121,20 +124,10 @@ export function arch(): string {
 // deno-lint-ignore no-explicit-any
 (uptime as any)[Symbol.toPrimitive] = (): number => uptime();
 
+/** Returns an array of objects containing information about each logical CPU core. */
 export function cpus(): CPUCoreInfo[] {
-  return Array.from(Array(navigator.hardwareConcurrency)).map(() => {
-    return {
-      model: "",
-      speed: 0,
-      times: {
-        user: 0,
-        nice: 0,
-        sys: 0,
-        idle: 0,
-        irq: 0,
-      },
-    };
-  });
+  ops.op_node_check_sys("cpus", "node:os.cpus()");
+  return ops.op_cpus();
 }
 
 /**
@@ -152,6 +145,7 @@ export function endianness(): "BE" | "LE" {
 
 /** Return free memory amount */
 export function freemem(): number {
+  ops.op_node_check_sys("systemMemoryInfo", "node:os.freemem()");
   return Deno.systemMemoryInfo().free;
 }
 
@@ -161,6 +155,12 @@ export function getPriority(pid = 0): number {
   notImplemented(SEE_GITHUB_ISSUE);
 }
 
+/** Reads an environment variable, checking the env permission of it. */
+function getEnv(name: string): string | undefined {
+  ops.op_node_check_env(name);
+  return Deno.env.get(name);
+}
+
 /** Returns the string path of the current user's home directory. */
 export function homedir(): string | null {
   // Note: Node/libuv calls getpwuid() / GetUserProfileDirectory() when the
@@ -168,11 +168,11 @@ export function homedir(): string | null {
   // path. IMO, it's okay to punt on that for now.
   switch (osType) {
     case "windows":
-      return Deno.env.get("USERPROFILE") || null;
+      return getEnv("USERPROFILE") || null;
     case "linux":
     case "darwin":
     case "freebsd":
-      return Deno.env.get("HOME") || null;
+      return getEnv("HOME") || null;
     default:
       throw Error("unreachable");
   }
@@ -180,6 +180,7 @@ export function homedir(): string | null {
 
 /** Returns the host name of the operating system as a string. */
 export function hostname(): string {
+  ops.op_node_check_sys("hostname", "node:os.hostname()");
   return Deno.hostname();
 }
 
@@ -188,15 +189,22 @@ export function loadavg(): number[] {
   if (isWindows) {
     return [0, 0, 0];
   }
+  ops.op_node_check_sys("loadavg", "node:os.loadavg()");
   return Deno.loadavg();
 }
 
+/** Returns the machine type as a string, as reported by the kernel (ex. "x86_64" or "aarch64") */
+export function machine(): string {
+  return ops.op_node_os_machine();
+}
+
 /** Returns an object containing network interfaces that have been assigned a network address.
  * Each key on the returned object identifies a network interface. The associated value is an array of objects that each describe an assigned network address. */
 export function networkInterfaces(): NetworkInterfaces {
+  ops.op_node_check_sys("networkInterfaces", "node:os.networkInterfaces()");
   const interfaces: NetworkInterfaces = {};
   for (
-    const { name, address, netmask, family, mac, scopeid, cidr } of Deno
+    const { name, address, netmask, family, mac, scopeid, cidr, flags } of Deno
       .networkInterfaces()
   ) {
     const addresses = interfaces[name] ||= [];
@@ -205,8 +213,7 @@ export function networkInterfaces(): NetworkInterfaces {
       netmask,
       family,
       mac,
-      internal: (family === "IPv4" && isIPv4LoopbackAddr(address)) ||
-        (family === "IPv6" && isIPv6LoopbackAddr(address)),
+      internal: flags.loopback,
       cidr,
     };
     if (family === "IPv6") {
@@ -217,14 +224,6 @@ export function networkInterfaces(): NetworkInterfaces {
   return interfaces;
 }
 
-function isIPv4LoopbackAddr(addr: string) {
-  return addr.startsWith("127");
-}
-
-function isIPv6LoopbackAddr(addr: string) {
-  return addr === "::1" || addr === "fe80::1";
-}
-
 /** Returns the a string identifying the operating system platform. The value is set at compile time. Possible values are 'darwin', 'linux', and 'win32'. */
 export function platform(): string {
   return process.platform;
@@ -232,6 +231,7 @@ export function platform(): string {
 
 /** Returns the operating system as a string */
 export function release(): string {
+  ops.op_node_check_sys("osRelease", "node:os.release()");
   return Deno.osRelease();
 }
 
@@ -239,6 +239,7 @@ export function release(): string {
 export function version(): string {
   // TODO(kt3k): Temporarily uses Deno.osRelease().
   // Revisit this if this implementation is insufficient for any npm module
+  ops.op_node_check_sys("osRelease", "node:os.version()");
   return Deno.osRelease();
 }
 
@@ -267,24 +268,25 @@ export function tmpdir(): string | null {
      * Node removes a single trailing / or \, we remove all.
   */
   if (isWindows) {
-    const temp = Deno.env.get("TEMP") || Deno.env.get("TMP");
+    const temp = getEnv("TEMP") || getEnv("TMP");
     if (temp) {
       return temp.replace(/(?<!:)[/\\]*$/, "");
     }
-    const base = Deno.env.get("SYSTEMROOT") || Deno.env.get("WINDIR");
+    const base = getEnv("SYSTEMROOT") || getEnv("WINDIR");
     if (base) {
       return base + "\\temp";
     }
     return null;
   } else { // !isWindows
-    const temp = Deno.env.get("TMPDIR") || Deno.env.get("TMP") ||
-      Deno.env.get("TEMP") || "/tmp";
+    const temp = getEnv("TMPDIR") || getEnv("TMP") ||
+      getEnv("TEMP") || "/tmp";
     return temp.replace(/(?<!^)\/*$/, "");
   }
 }
 
 /** Return total physical memory amount */
 export function totalmem(): number {
+  ops.op_node_check_sys("systemMemoryInfo", "node:os.totalmem()");
   return Deno.systemMemoryInfo().total;
 }
 
@@ -306,6 +308,7 @@ export function type(): string {
 
 /** Returns the Operating System uptime in number of seconds. */
 export function uptime(): number {
+  ops.op_node_check_sys("osUptime", "node:os.uptime()");
   return osUptime();
 }
 
@@ -329,6 +332,7 @@ export default {
   homedir,
   hostname,
   loadavg,
+  machine,
   networkInterfaces,
   platform,
   release,
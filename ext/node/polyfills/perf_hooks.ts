@@ -1,14 +1,316 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
-import { notImplemented } from "ext:deno_node/_utils.ts";
 import {
+  addResourceTiming,
+  addSupportedEntryType,
+  illegalConstructorKey,
   performance as shimPerformance,
   PerformanceEntry,
+  PerformanceObserver,
+  PerformanceObserverEntryList,
+  queueEntry,
 } from "ext:deno_web/15_performance.js";
+import {
+  clearInterval,
+  setInterval,
+  unrefTimer,
+} from "ext:deno_web/02_timers.js";
+import {
+  validateFunction,
+  validateInteger,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_OUT_OF_RANGE,
+} from "ext:deno_node/internal/errors.ts";
+
+const core = globalThis.__bootstrap.core;
+const { ops } = core;
 
-// FIXME(bartlomieju)
-const PerformanceObserver = undefined;
 const constants = {};
 
+/** A performance entry produced by Node.js specific APIs, like `timerify`. */
+class PerformanceNodeEntry extends PerformanceEntry {
+  #detail: unknown;
+
+  constructor(
+    name: string,
+    entryType: string,
+    startTime: number,
+    duration: number,
+    detail: unknown,
+  ) {
+    super(name, entryType, startTime, duration, illegalConstructorKey);
+    this.#detail = detail;
+  }
+
+  get detail() {
+    return this.#detail;
+  }
+
+  toJSON() {
+    return {
+      ...super.toJSON(),
+      detail: this.#detail,
+    };
+  }
+}
+
+interface ConnectionTimingInfo {
+  domainLookupStartTime?: number;
+  domainLookupEndTime?: number;
+  connectionStartTime?: number;
+  connectionEndTime?: number;
+  secureConnectionStartTime?: number;
+  ALPNNegotiatedProtocol?: string;
+}
+
+interface ResourceTimingInfo {
+  startTime: number;
+  endTime: number;
+  redirectStartTime?: number;
+  redirectEndTime?: number;
+  postRedirectStartTime?: number;
+  finalServiceWorkerStartTime?: number;
+  finalNetworkRequestStartTime?: number;
+  finalNetworkResponseStartTime?: number;
+  encodedBodySize?: number;
+  decodedBodySize?: number;
+  finalConnectionTimingInfo?: ConnectionTimingInfo | null;
+}
+
+class PerformanceResourceTiming extends PerformanceEntry {
+  #initiatorType: string;
+  #cacheMode: string;
+  #timingInfo: ResourceTimingInfo;
+
+  constructor(
+    requestedUrl: string,
+    initiatorType: string,
+    timingInfo: ResourceTimingInfo,
+    cacheMode = "",
+  ) {
+    super(
+      requestedUrl,
+      "resource",
+      timingInfo.startTime,
+      timingInfo.endTime - timingInfo.startTime,
+      illegalConstructorKey,
+    );
+    this.#initiatorType = initiatorType;
+    this.#cacheMode = cacheMode;
+    this.#timingInfo = timingInfo;
+  }
+
+  get initiatorType() {
+    return this.#initiatorType;
+  }
+
+  get workerStart() {
+    return this.#timingInfo.finalServiceWorkerStartTime ?? 0;
+  }
+
+  get redirectStart() {
+    return this.#timingInfo.redirectStartTime ?? 0;
+  }
+
+  get redirectEnd() {
+    return this.#timingInfo.redirectEndTime ?? 0;
+  }
+
+  get fetchStart() {
+    return this.#timingInfo.postRedirectStartTime ?? this.startTime;
+  }
+
+  get domainLookupStart() {
+    return this.#timingInfo.finalConnectionTimingInfo
+      ?.domainLookupStartTime ?? this.fetchStart;
+  }
+
+  get domainLookupEnd() {
+    return this.#timingInfo.finalConnectionTimingInfo?.domainLookupEndTime ??
+      this.fetchStart;
+  }
+
+  get connectStart() {
+    return this.#timingInfo.finalConnectionTimingInfo?.connectionStartTime ??
+      this.fetchStart;
+  }
+
+  get connectEnd() {
+    return this.#timingInfo.finalConnectionTimingInfo?.connectionEndTime ??
+      this.fetchStart;
+  }
+
+  get secureConnectionStart() {
+    return this.#timingInfo.finalConnectionTimingInfo
+      ?.secureConnectionStartTime ?? 0;
+  }
+
+  get nextHopProtocol() {
+    return this.#timingInfo.finalConnectionTimingInfo
+      ?.ALPNNegotiatedProtocol ?? "";
+  }
+
+  get requestStart() {
+    return this.#timingInfo.finalNetworkRequestStartTime ?? 0;
+  }
+
+  get responseStart() {
+    return this.#timingInfo.finalNetworkResponseStartTime ?? 0;
+  }
+
+  get responseEnd() {
+    return this.#timingInfo.endTime;
+  }
+
+  get encodedBodySize() {
+    return this.#timingInfo.encodedBodySize ?? 0;
+  }
+
+  get decodedBodySize() {
+    return this.#timingInfo.decodedBodySize ?? 0;
+  }
+
+  get transferSize() {
+    if (this.#cacheMode === "local") {
+      return 0;
+    }
+    if (this.#cacheMode === "validated") {
+      return 300;
+    }
+    // 300 bytes is the approximated size of the response headers.
+    return this.encodedBodySize + 300;
+  }
+
+  toJSON() {
+    return {
+      ...super.toJSON(),
+      initiatorType: this.initiatorType,
+      nextHopProtocol: this.nextHopProtocol,
+      workerStart: this.workerStart,
+      redirectStart: this.redirectStart,
+      redirectEnd: this.redirectEnd,
+      fetchStart: this.fetchStart,
+      domainLookupStart: this.domainLookupStart,
+      domainLookupEnd: this.domainLookupEnd,
+      connectStart: this.connectStart,
+      connectEnd: this.connectEnd,
+      secureConnectionStart: this.secureConnectionStart,
+      requestStart: this.requestStart,
+      responseStart: this.responseStart,
+      responseEnd: this.responseEnd,
+      transferSize: this.transferSize,
+      encodedBodySize: this.encodedBodySize,
+      decodedBodySize: this.decodedBodySize,
+    };
+  }
+}
+
+// the entries of `timerify()` go to the same observers as the web ones
+addSupportedEntryType("function");
+
+function timerify<T extends (...args: unknown[]) => unknown>(
+  fn: T,
+  options: { histogram?: RecordableHistogram } = {},
+): T {
+  validateFunction(fn, "fn");
+  validateObject(options, "options");
+  const { histogram } = options;
+  if (
+    histogram !== undefined && !(histogram instanceof RecordableHistogram)
+  ) {
+    throw new ERR_INVALID_ARG_TYPE(
+      "options.histogram",
+      "RecordableHistogram",
+      histogram,
+    );
+  }
+
+  function timerified(this: unknown, ...args: unknown[]) {
+    const start = shimPerformance.now();
+    const result = new.target
+      ? Reflect.construct(fn, args, fn)
+      : Reflect.apply(fn, this, args);
+    const duration = shimPerformance.now() - start;
+    // nanoseconds, like Node.js
+    histogram?.record(Math.max(1, Math.round(duration * 1e6)));
+    // like in Node.js, they aren't buffered
+    queueEntry(
+      new PerformanceNodeEntry(fn.name, "function", start, duration, args),
+      false,
+    );
+    return result;
+  }
+
+  Object.defineProperties(timerified, {
+    length: {
+      configurable: false,
+      enumerable: true,
+      value: fn.length,
+    },
+    name: {
+      configurable: false,
+      enumerable: true,
+      value: `timerified ${fn.name}`,
+    },
+  });
+
+  return timerified as T;
+}
+
+function markResourceTiming(
+  timingInfo: ResourceTimingInfo,
+  requestedUrl: string,
+  initiatorType: string,
+  _global: unknown,
+  cacheMode: string,
+): PerformanceResourceTiming {
+  const entry = new PerformanceResourceTiming(
+    requestedUrl,
+    initiatorType,
+    timingInfo,
+    cacheMode,
+  );
+  addResourceTiming(entry);
+  return entry;
+}
+
+interface EventLoopUtilization {
+  idle: number;
+  active: number;
+  utilization: number;
+}
+
+const eluBuffer = new Float64Array(2);
+
+function eventLoopUtilization(
+  util1?: EventLoopUtilization,
+  util2?: EventLoopUtilization,
+): EventLoopUtilization {
+  ops.op_node_event_loop_utilization(eluBuffer);
+  const idle = eluBuffer[0];
+  const active = eluBuffer[1];
+
+  if (!util1) {
+    return {
+      idle,
+      active,
+      utilization: active / (idle + active),
+    };
+  }
+
+  const idleDelta = util2 ? util1.idle - util2.idle : idle - util1.idle;
+  const activeDelta = util2
+    ? util1.active - util2.active
+    : active - util1.active;
+  return {
+    idle: idleDelta,
+    active: activeDelta,
+    utilization: activeDelta / (idleDelta + activeDelta),
+  };
+}
+
 const performance:
   & Omit<
     Performance,
@@ -26,9 +328,9 @@ const performance:
     markResourceTiming: any;
   } = {
     clearMarks: (markName: string) => shimPerformance.clearMarks(markName),
-    eventLoopUtilization: () =>
-      notImplemented("eventLoopUtilization from performance"),
-    mark: (markName: string) => shimPerformance.mark(markName),
+    eventLoopUtilization,
+    mark: (markName: string, markOptions?: PerformanceMarkOptions) =>
+      shimPerformance.mark(markName, markOptions),
     measure: (
       measureName: string,
       startMark?: string | PerformanceMeasureOptions,
@@ -49,10 +351,13 @@ const performance:
     },
     nodeTiming: {},
     now: () => shimPerformance.now(),
-    timerify: () => notImplemented("timerify from performance"),
+    timerify,
     // deno-lint-ignore no-explicit-any
     timeOrigin: (shimPerformance as any).timeOrigin,
-    markResourceTiming: () => {},
+    markResourceTiming,
+    clearResourceTimings: () => shimPerformance.clearResourceTimings(),
+    setResourceTimingBufferSize: (maxSize: number) =>
+      shimPerformance.setResourceTimingBufferSize(maxSize),
     // @ts-ignore waiting on update in `deno`, but currently this is
     // a circular dependency
     toJSON: () => shimPerformance.toJSON(),
@@ -67,23 +372,175 @@ const performance:
     ) => shimPerformance.dispatchEvent(...args),
   };
 
-const monitorEventLoopDelay = () =>
-  notImplemented(
-    "monitorEventLoopDelay from performance",
-  );
+const statsBuffer = new Float64Array(6);
+
+// The resources of the histograms that weren't closed are closed once they
+// are garbage collected.
+const histogramRegistry = new FinalizationRegistry((rid: number) => {
+  core.tryClose(rid);
+});
+
+class Histogram {
+  #rid: number;
+
+  constructor() {
+    this.#rid = ops.op_node_histogram_new();
+    histogramRegistry.register(this, this.#rid, this);
+  }
+
+  /**
+   * Closes the resource of the histogram right away. This isn't part of the
+   * node API, where histograms are only released once garbage collected.
+   */
+  close() {
+    histogramRegistry.unregister(this);
+    core.tryClose(this.#rid);
+  }
+
+  #stats() {
+    ops.op_node_histogram_stats(this.#rid, statsBuffer);
+    return statsBuffer;
+  }
+
+  get count(): number {
+    return this.#stats()[0];
+  }
+
+  get min(): number {
+    return this.#stats()[1];
+  }
+
+  get max(): number {
+    return this.#stats()[2];
+  }
+
+  get mean(): number {
+    return this.#stats()[3];
+  }
+
+  get stddev(): number {
+    return this.#stats()[4];
+  }
+
+  get exceeds(): number {
+    return this.#stats()[5];
+  }
+
+  get percentiles(): Map<number, number> {
+    return new Map(ops.op_node_histogram_percentiles(this.#rid));
+  }
+
+  percentile(percentile: number): number {
+    if (
+      typeof percentile !== "number" || !(percentile > 0 && percentile <= 100)
+    ) {
+      throw new ERR_OUT_OF_RANGE("percentile", "> 0 && <= 100", percentile);
+    }
+    return ops.op_node_histogram_percentile(this.#rid, percentile);
+  }
+
+  reset() {
+    ops.op_node_histogram_reset(this.#rid);
+  }
+
+  protected record(value: number): boolean {
+    return ops.op_node_histogram_record(this.#rid, value);
+  }
+}
+
+class RecordableHistogram extends Histogram {
+  #prev: number | undefined = undefined;
+
+  override record(value: number | bigint) {
+    if (typeof value === "bigint") {
+      value = Number(value);
+    }
+    validateInteger(value, "val", 1);
+    super.record(value);
+  }
+
+  recordDelta() {
+    const now = shimPerformance.now();
+    if (this.#prev !== undefined) {
+      // nanoseconds, like `process.hrtime`
+      super.record(Math.round((now - this.#prev) * 1e6));
+    }
+    this.#prev = now;
+  }
+}
+
+class IntervalHistogram extends Histogram {
+  #resolution: number;
+  #timer: number | undefined = undefined;
+  #prev = 0;
+
+  constructor(resolution: number) {
+    super();
+    this.#resolution = resolution;
+  }
+
+  enable(): boolean {
+    if (this.#timer !== undefined) {
+      return false;
+    }
+    this.#prev = shimPerformance.now();
+    this.#timer = setInterval(() => {
+      const now = shimPerformance.now();
+      // The time between two runs of the timer is the resolution plus the
+      // delay caused by the event loop being busy, in nanoseconds.
+      super.record(Math.round((now - this.#prev) * 1e6));
+      this.#prev = now;
+    }, this.#resolution);
+    unrefTimer(this.#timer);
+    return true;
+  }
+
+  disable(): boolean {
+    if (this.#timer === undefined) {
+      return false;
+    }
+    clearInterval(this.#timer);
+    this.#timer = undefined;
+    return true;
+  }
+
+  override close() {
+    this.disable();
+    super.close();
+  }
+}
+
+function createHistogram(): RecordableHistogram {
+  return new RecordableHistogram();
+}
+
+function monitorEventLoopDelay(
+  options: { resolution?: number } = {},
+): IntervalHistogram {
+  validateObject(options, "options");
+  const { resolution = 10 } = options;
+  validateInteger(resolution, "options.resolution", 1);
+  return new IntervalHistogram(resolution);
+}
 
 export default {
   performance,
   PerformanceObserver,
+  PerformanceObserverEntryList,
   PerformanceEntry,
+  PerformanceResourceTiming,
   monitorEventLoopDelay,
+  createHistogram,
   constants,
 };
 
 export {
   constants,
+  createHistogram,
   monitorEventLoopDelay,
   performance,
   PerformanceEntry,
   PerformanceObserver,
+  PerformanceObserverEntryList,
+  PerformanceResourceTiming,
 };
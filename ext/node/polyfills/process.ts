@@ -5,7 +5,10 @@ const internals = globalThis.__bootstrap.internals;
 const { core } = globalThis.__bootstrap;
 import { notImplemented, warnNotImplemented } from "ext:deno_node/_utils.ts";
 import { EventEmitter } from "ext:deno_node/events.ts";
-import { validateString } from "ext:deno_node/internal/validators.mjs";
+import {
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
 import {
   ERR_INVALID_ARG_TYPE,
   ERR_UNKNOWN_SIGNAL,
@@ -35,6 +38,8 @@ import {
   runNextTicks,
 } from "ext:deno_node/_next_tick.ts";
 import { isWindows } from "ext:deno_node/_util/os.ts";
+import { nodeGlobalThis } from "ext:deno_node/00_globals.js";
+import { activeTimers } from "ext:deno_node/internal/timers.mjs";
 import * as io from "ext:deno_io/12_io.js";
 import { Command } from "ext:runtime/40_process.js";
 
@@ -240,6 +245,91 @@ memoryUsage.rss = function (): number {
   return memoryUsage().rss;
 };
 
+/** Maps Deno resource names to the name of the equivalent Node.js handle. */
+const resourceNames: Record<string, string> = {
+  child: "ProcessWrap",
+  childStdin: "PipeWrap",
+  childStdout: "PipeWrap",
+  childStderr: "PipeWrap",
+  fsEvents: "FSEventWrap",
+  fsFile: "FileHandle",
+  httpConn: "TCPSocketWrap",
+  signal: "SignalWrap",
+  tcpListener: "TCPServerWrap",
+  tcpStream: "TCPSocketWrap",
+  tlsListener: "TCPServerWrap",
+  tlsStream: "TLSWrap",
+  udpSocket: "UDPWrap",
+  unixDatagram: "UDPWrap",
+  unixListener: "PipeServerWrap",
+  unixStream: "PipeWrap",
+};
+
+/**
+ * Returns the resources that keep the event loop alive. This is an
+ * approximation based on the resource table and the timers created through
+ * `node:timers`: stdio and resources without a Node.js equivalent are
+ * omitted.
+ */
+function getActiveResources(): { type: string; resource: unknown }[] {
+  const resources = [];
+  for (const [rid, name] of Object.entries(core.resources())) {
+    const type = resourceNames[name as string];
+    // stdin, stdout and stderr
+    if (type === undefined || Number(rid) <= 2) {
+      continue;
+    }
+    resources.push({ type, resource: { rid: Number(rid), type } });
+  }
+  for (const timer of activeTimers.values()) {
+    if (timer.hasRef()) {
+      resources.push({ type: "Timeout", resource: timer });
+    }
+  }
+  return resources;
+}
+
+/** https://nodejs.org/api/process.html#processgetactiveresourcesinfo */
+export function getActiveResourcesInfo(): string[] {
+  return getActiveResources().map(({ type }) => type);
+}
+
+/** Approximation of Node's undocumented `process._getActiveHandles()`. */
+export function _getActiveHandles(): unknown[] {
+  return getActiveResources().map(({ resource }) => resource);
+}
+
+/** Approximation of Node's undocumented `process._getActiveRequests()`.
+ * Pending async operations aren't tracked, so this is always empty. */
+export function _getActiveRequests(): unknown[] {
+  return [];
+}
+
+/** https://nodejs.org/api/process.html#processdlopenmodule-filename-flags
+ *
+ * Loads a Node-API addon through the napi subsystem, which requires
+ * `--allow-ffi`. The `flags` are accepted for compatibility but unused. */
+export function dlopen(
+  module: { exports: unknown },
+  filename: string,
+  _flags?: number,
+) {
+  validateObject(module, "module");
+  validateString(filename, "filename");
+  if (filename.endsWith("fsevents.node")) {
+    throw new Error("Using fsevents module is currently not supported");
+  }
+  try {
+    module.exports = core.ops.op_napi_open(filename, nodeGlobalThis);
+  } catch (err) {
+    if (err instanceof TypeError) {
+      // deno-lint-ignore no-explicit-any
+      (err as any).code = "ERR_DLOPEN_FAILED";
+    }
+    throw err;
+  }
+}
+
 // Returns a negative error code than can be recognized by errnoException
 function _kill(pid: number, sig: number): number {
   let errCode;
@@ -508,7 +598,7 @@ class Process extends EventEmitter {
   /** https://nodejs.org/api/process.html#process_process_platform */
   get platform() {
     if (!platform) {
-      platform = isWindows ? "win32" : Deno.build.os;
+      platform = core.ops.op_node_os_platform();
     }
     return platform;
   }
@@ -579,6 +669,16 @@ class Process extends EventEmitter {
 
   memoryUsage = memoryUsage;
 
+  /** https://nodejs.org/api/process.html#processgetactiveresourcesinfo */
+  getActiveResourcesInfo = getActiveResourcesInfo;
+
+  _getActiveHandles = _getActiveHandles;
+
+  _getActiveRequests = _getActiveRequests;
+
+  /** https://nodejs.org/api/process.html#processdlopenmodule-filename-flags */
+  dlopen = dlopen;
+
   /** https://nodejs.org/api/process.html#process_process_stderr */
   stderr = stderr;
 
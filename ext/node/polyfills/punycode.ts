@@ -1,15 +1,18 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import { ucs2 } from "ext:deno_node/internal/idna.ts";
+import {
+  toASCII as domainToASCII,
+  ucs2,
+} from "ext:deno_node/internal/idna.ts";
 
 const { ops } = globalThis.__bootstrap.core;
 
 function toASCII(domain) {
-  return ops.op_node_idna_domain_to_ascii(domain);
+  return domainToASCII(domain);
 }
 
 function toUnicode(domain) {
-  return ops.op_node_idna_domain_to_unicode(domain);
+  return ops.op_node_idna_punycode_to_unicode(domain);
 }
 
 function decode(domain) {
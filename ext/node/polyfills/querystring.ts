@@ -1,6 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 import { Buffer } from "ext:deno_node/buffer.ts";
 import { encodeStr, hexTable } from "ext:deno_node/internal/querystring.ts";
+import { ERR_INVALID_URI } from "ext:deno_node/internal/errors.ts";
+
+const { ops } = globalThis.__bootstrap.core;
 
 /**
  * Alias of querystring.parse()
@@ -142,8 +145,10 @@ export function parse(
     return obj;
   }
 
-  const sepCodes = !sep ? [38] /* & */ : charCodes(String(sep));
-  const eqCodes = !eq ? [61] /* = */ : charCodes(String(eq));
+  sep = !sep ? "&" : String(sep);
+  eq = !eq ? "=" : String(eq);
+  const sepCodes = charCodes(sep);
+  const eqCodes = charCodes(eq);
   const sepLen = sepCodes.length;
   const eqLen = eqCodes.length;
 
@@ -164,6 +169,20 @@ export function parse(
   }
   const customDecode = decode !== unescape;
 
+  if (!customDecode) {
+    // fast path, the default decoding is done natively as well
+    const fields: string[] = ops.op_node_querystring_parse(
+      str,
+      sep,
+      eq,
+      pairs,
+    );
+    for (let i = 0; i < fields.length; i += 2) {
+      addKeyVal(obj, fields[i], fields[i + 1], false, false, decode);
+    }
+    return obj;
+  }
+
   let lastPos = 0;
   let sepIdx = 0;
   let eqIdx = 0;
@@ -382,6 +401,36 @@ export function stringify(
 ): string {
   sep ||= "&";
   eq ||= "=";
+
+  if (!options) {
+    // fast path, the default encoding is done natively
+    if (obj === null || typeof obj !== "object") {
+      return "";
+    }
+    const fields: string[] = [];
+    const keys = Object.keys(obj);
+    for (let i = 0; i < keys.length; ++i) {
+      const k = keys[i];
+      const v = obj[k];
+      if (Array.isArray(v)) {
+        for (let j = 0; j < v.length; ++j) {
+          fields.push(k, stringifyPrimitive(v[j]));
+        }
+      } else {
+        fields.push(k, stringifyPrimitive(v));
+      }
+    }
+    const result = ops.op_node_querystring_stringify(
+      fields,
+      String(sep),
+      String(eq),
+    );
+    if (result === null) {
+      throw new ERR_INVALID_URI();
+    }
+    return result;
+  }
+
   const encode = options ? options.encodeURIComponent : qsEscape;
   const convert = options ? encodeStringifiedCustom : encodeStringified;
 
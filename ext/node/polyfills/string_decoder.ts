@@ -21,20 +21,13 @@
 // USE OR OTHER DEALINGS IN THE SOFTWARE.
 
 import { Buffer } from "ext:deno_node/buffer.ts";
-import {
-  normalizeEncoding as castEncoding,
-  notImplemented,
-} from "ext:deno_node/_utils.ts";
+import { normalizeEncoding as castEncoding } from "ext:deno_node/_utils.ts";
 
-enum NotImplemented {
-  "ascii",
-  "latin1",
-  "utf16le",
-}
+const core = globalThis.__bootstrap.core;
+const ops = core.ops;
 
 function normalizeEncoding(enc?: string): string {
   const encoding = castEncoding(enc ?? null);
-  if (encoding && encoding in NotImplemented) notImplemented(encoding);
   if (!encoding && typeof enc === "string" && enc.toLowerCase() !== "raw") {
     throw new Error(`Unknown encoding: ${enc}`);
   }
@@ -96,56 +89,6 @@ function utf8CheckIncomplete(
   return 0;
 }
 
-/*
- * Validates as many continuation bytes for a multi-byte UTF-8 character as
- * needed or are available. If we see a non-continuation byte where we expect
- * one, we "replace" the validated continuation bytes we've seen so far with
- * a single UTF-8 replacement character ('\ufffd'), to match v8's UTF-8 decoding
- * behavior. The continuation byte check is included three times in the case
- * where all of the continuation bytes for a character exist in the same buffer.
- * It is also done this way as a slight performance increase instead of using a
- * loop.
- */
-function utf8CheckExtraBytes(
-  self: StringDecoderBase,
-  buf: Buffer,
-): string | undefined {
-  if ((buf[0] & 0xc0) !== 0x80) {
-    self.lastNeed = 0;
-    return "\ufffd";
-  }
-  if (self.lastNeed > 1 && buf.length > 1) {
-    if ((buf[1] & 0xc0) !== 0x80) {
-      self.lastNeed = 1;
-      return "\ufffd";
-    }
-    if (self.lastNeed > 2 && buf.length > 2) {
-      if ((buf[2] & 0xc0) !== 0x80) {
-        self.lastNeed = 2;
-        return "\ufffd";
-      }
-    }
-  }
-}
-
-/*
- * Attempts to complete a multi-byte UTF-8 character using bytes from a Buffer.
- */
-function utf8FillLastComplete(
-  this: StringDecoderBase,
-  buf: Buffer,
-): string | undefined {
-  const p = this.lastTotal - this.lastNeed;
-  const r = utf8CheckExtraBytes(this, buf);
-  if (r !== undefined) return r;
-  if (this.lastNeed <= buf.length) {
-    buf.copy(this.lastChar, p, 0, this.lastNeed);
-    return this.lastChar.toString(this.encoding, 0, this.lastTotal);
-  }
-  buf.copy(this.lastChar, p, 0, buf.length);
-  this.lastNeed -= buf.length;
-}
-
 /*
  * Attempts to complete a partial non-UTF-8 character using bytes from a Buffer
  */
@@ -175,18 +118,8 @@ function utf8Text(this: StringDecoderBase, buf: Buffer, i: number): string {
   return buf.toString("utf8", i, end);
 }
 
-/*
- * For UTF-8, a replacement character is added when ending on a partial
- * character.
- */
-function utf8End(this: Utf8Decoder, buf?: Buffer): string {
-  const r = buf && buf.length ? this.write(buf) : "";
-  if (this.lastNeed) return r + "\ufffd";
-  return r;
-}
-
 function utf8Write(
-  this: Utf8Decoder | Base64Decoder,
+  this: Base64Decoder,
   buf: Buffer | string,
 ): string {
   if (typeof buf === "string") {
@@ -280,14 +213,59 @@ class GenericDecoder extends StringDecoderBase {
   }
 }
 
-class Utf8Decoder extends StringDecoderBase {
-  public end = utf8End;
-  public fillLast = utf8FillLastComplete;
+// A decoder that is dropped before `end()` is called closes its resource once
+// it is garbage collected.
+const decoderRegistry = new FinalizationRegistry((rid: number) => {
+  core.tryClose(rid);
+});
+
+/*
+ * Decodes with a streaming decoder resource, the one `TextDecoder` uses, which
+ * keeps the bytes of a partial character around until the next write.
+ * Malformed sequences, including unpaired surrogates in UTF-16, are replaced
+ * with U+FFFD.
+ */
+class StreamingDecoder extends StringDecoderBase {
+  public end: (buf?: Buffer) => string;
+  public fillLast = undefined;
   public text = utf8Text;
-  public write = utf8Write;
+  public write: (buf: Buffer | string) => string;
+  #label: string;
+  #rid: number | null = null;
 
-  constructor(encoding?: string) {
+  constructor(encoding: string | undefined, label: string) {
     super(normalizeEncoding(encoding), 4);
+    this.#label = label;
+    // `StringDecoder` copies these onto itself, so they can't rely on `this`
+    this.write = (buf) => this.#write(buf);
+    this.end = (buf) => this.#end(buf);
+  }
+
+  #write(buf: Buffer | string): string {
+    if (typeof buf === "string") {
+      return buf;
+    }
+    if (buf.length === 0) return "";
+    if (this.#rid === null) {
+      this.#rid = ops.op_encoding_new_decoder(this.#label, false, true);
+      decoderRegistry.register(this, this.#rid, this);
+    }
+    const input = ArrayBuffer.isView(buf) ? buf : Buffer.from(buf);
+    return ops.op_encoding_decode_stream(input, this.#rid);
+  }
+
+  #end(buf?: Buffer): string {
+    const r = buf && buf.length ? this.#write(buf) : "";
+    if (this.#rid === null) return r;
+    // flushes a partial character as a replacement character and resets the
+    // decoder, so it can be reused
+    try {
+      return r + ops.op_encoding_decode(new Uint8Array(), this.#rid, false);
+    } finally {
+      decoderRegistry.unregister(this);
+      core.close(this.#rid);
+      this.#rid = null;
+    }
   }
 }
 
@@ -308,10 +286,13 @@ export class StringDecoder {
 
   constructor(encoding?: string) {
     const normalizedEncoding = normalizeEncoding(encoding);
-    let decoder: Utf8Decoder | Base64Decoder | GenericDecoder;
+    let decoder: StreamingDecoder | Base64Decoder | GenericDecoder;
     switch (normalizedEncoding) {
       case "utf8":
-        decoder = new Utf8Decoder(encoding);
+        decoder = new StreamingDecoder(encoding, "utf-8");
+        break;
+      case "utf16le":
+        decoder = new StreamingDecoder(encoding, "utf-16le");
         break;
       case "base64":
         decoder = new Base64Decoder(encoding);
@@ -0,0 +1,1205 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+// This module maps the `node:test` API onto `Deno.test()`. Top level tests
+// and suites are registered as Deno tests, while tests nested in suites or
+// created with `t.test()` run as test steps. Outside of `deno test` the
+// registration functions are no-ops, just like `Deno.test()`.
+
+import { notImplemented } from "ext:deno_node/_utils.ts";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+  ERR_INVALID_STATE,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateFunction,
+  validateInteger,
+  validateObject,
+} from "ext:deno_node/internal/validators.mjs";
+// the timers of the web implementation keep working with mocked timers
+import {
+  clearTimeout as webClearTimeout,
+  setTimeout as webSetTimeout,
+} from "ext:deno_web/02_timers.js";
+
+type Done = (err?: unknown) => void;
+type TestFn = (t: TestContext, done: Done) => unknown;
+type SuiteFn = (s: SuiteContext) => unknown;
+type HookFn = (t?: TestContext, done?: Done) => unknown;
+
+interface TestOptions {
+  concurrency?: number | boolean;
+  only?: boolean;
+  signal?: AbortSignal;
+  skip?: boolean | string;
+  todo?: boolean | string;
+  timeout?: number;
+}
+
+interface HookOptions {
+  signal?: AbortSignal;
+  timeout?: number;
+}
+
+interface Hooks {
+  before: HookFn[];
+  after: HookFn[];
+  beforeEach: HookFn[];
+  afterEach: HookFn[];
+}
+
+interface TestDefinition {
+  name: string;
+  options: TestOptions;
+  fn: TestFn;
+}
+
+class Suite {
+  name: string;
+  options: TestOptions;
+  fn: SuiteFn;
+  parent: Suite | null;
+  children: (Suite | TestDefinition)[] = [];
+  hooks: Hooks = { before: [], after: [], beforeEach: [], afterEach: [] };
+
+  constructor(
+    name: string,
+    options: TestOptions,
+    fn: SuiteFn,
+    parent: Suite | null,
+  ) {
+    this.name = name;
+    this.options = options;
+    this.fn = fn;
+    this.parent = parent;
+  }
+}
+
+// Node.js doesn't check tests for leaking async ops, resources or calls to
+// `process.exit()`, so neither do translated tests.
+const sanitizers = {
+  sanitizeOps: false,
+  sanitizeResources: false,
+  sanitizeExit: false,
+};
+
+const rootHooks: Hooks = {
+  before: [],
+  after: [],
+  beforeEach: [],
+  afterEach: [],
+};
+let rootBeforeDone = false;
+let rootAfterRegistered = false;
+
+/** The suite whose children are being collected, if any. */
+let currentSuite: Suite | null = null;
+/** The innermost test that is running, if any. */
+let currentTest: TestContext | null = null;
+
+function prepareArgs(
+  name: unknown,
+  options: unknown,
+  fn: unknown,
+  overrides: TestOptions = {},
+): TestDefinition {
+  if (typeof name === "function") {
+    fn = name;
+    options = {};
+    name = (fn as TestFn).name;
+  } else if (typeof name === "object" && name !== null) {
+    fn = options;
+    options = name;
+    name = "";
+  } else if (typeof options === "function") {
+    fn = options;
+    options = {};
+  }
+  if (options === undefined || options === null) {
+    options = {};
+  }
+  validateObject(options, "options");
+  if (fn === undefined) {
+    // tests without a body are todo
+    fn = () => {};
+    overrides = { todo: true, ...overrides };
+  }
+  validateFunction(fn, "fn");
+  const { timeout } = options as TestOptions;
+  if (timeout !== undefined && timeout !== Infinity) {
+    validateInteger(timeout, "options.timeout", 0);
+  }
+  return {
+    name: name ? String(name) : (fn as TestFn).name || "<anonymous>",
+    options: { ...(options as TestOptions), ...overrides },
+    fn: fn as TestFn,
+  };
+}
+
+function isIgnored(options: TestOptions): boolean {
+  return !!options.skip;
+}
+
+function withTimeout<T>(
+  promise: Promise<T>,
+  timeout: number | undefined,
+  signal: AbortSignal | undefined,
+): Promise<T> {
+  if (
+    (timeout === undefined || timeout === Infinity) && signal === undefined
+  ) {
+    return promise;
+  }
+  return new Promise((resolve, reject) => {
+    let timer: number | undefined;
+    const onAbort = () => {
+      cleanup();
+      reject(signal!.reason);
+    };
+    const cleanup = () => {
+      webClearTimeout(timer);
+      signal?.removeEventListener("abort", onAbort);
+    };
+    if (timeout !== undefined && timeout !== Infinity) {
+      timer = webSetTimeout(() => {
+        cleanup();
+        reject(new Error(`test timed out after ${timeout}ms`));
+      }, timeout);
+    }
+    if (signal !== undefined) {
+      if (signal.aborted) {
+        onAbort();
+        return;
+      }
+      signal.addEventListener("abort", onAbort);
+    }
+    promise.then((value) => {
+      cleanup();
+      resolve(value);
+    }, (err) => {
+      cleanup();
+      reject(err);
+    });
+  });
+}
+
+/** Calls a test or hook function, supporting both promises and callbacks. */
+function callWithDone(
+  fn: TestFn | HookFn,
+  // deno-lint-ignore no-explicit-any
+  thisArg: any,
+  t: TestContext | SuiteContext | undefined,
+): Promise<unknown> {
+  if (fn.length < 2) {
+    try {
+      return Promise.resolve(fn.call(thisArg, t as TestContext));
+    } catch (err) {
+      return Promise.reject(err);
+    }
+  }
+  return new Promise((resolve, reject) => {
+    const done: Done = (err) => err ? reject(err) : resolve(undefined);
+    try {
+      const result = fn.call(thisArg, t as TestContext, done);
+      if (typeof (result as Promise<unknown>)?.then === "function") {
+        reject(
+          new Error(
+            "passed a callback but also returned a Promise",
+          ),
+        );
+      }
+    } catch (err) {
+      reject(err);
+    }
+  });
+}
+
+async function runHooks(hooks: HookFn[], t?: TestContext) {
+  for (const hook of hooks) {
+    await callWithDone(hook, undefined, t);
+  }
+}
+
+async function runRootBefore() {
+  if (!rootBeforeDone) {
+    rootBeforeDone = true;
+    await runHooks(rootHooks.before);
+  }
+}
+
+/** Runs a test body with its context, its `beforeEach` and `afterEach`
+ * hooks and the cleanup of its mocks. */
+async function runTest(
+  definition: TestDefinition,
+  denoContext: Deno.TestContext,
+  parent: TestContext | null,
+  beforeEach: HookFn[],
+  afterEach: HookFn[],
+) {
+  const { name, options, fn } = definition;
+  const t = new TestContext(name, denoContext, parent, options.signal);
+  const previousTest = currentTest;
+  currentTest = t;
+  try {
+    await runHooks(beforeEach, t);
+    try {
+      await withTimeout(
+        callWithDone(fn, t, t),
+        options.timeout,
+        options.signal,
+      );
+    } catch (err) {
+      if (!options.todo && !t[kTodo]) {
+        throw err;
+      }
+      t.diagnostic(`TODO failed: ${(err as Error)?.message ?? err}`);
+    } finally {
+      await t[kFinish]();
+      await runHooks(afterEach, t);
+    }
+  } finally {
+    currentTest = previousTest;
+    t[kAbort]();
+    t.mock.reset();
+  }
+}
+
+function suiteBeforeEach(suite: Suite | null): HookFn[] {
+  const hooks = [];
+  for (let s = suite; s !== null; s = s.parent) {
+    hooks.unshift(...s.hooks.beforeEach);
+  }
+  return [...rootHooks.beforeEach, ...hooks];
+}
+
+function suiteAfterEach(suite: Suite | null): HookFn[] {
+  const hooks = [];
+  for (let s = suite; s !== null; s = s.parent) {
+    hooks.push(...s.hooks.afterEach);
+  }
+  return [...hooks, ...rootHooks.afterEach];
+}
+
+async function runSuite(suite: Suite, denoContext: Deno.TestContext) {
+  const previousSuite = currentSuite;
+  currentSuite = suite;
+  try {
+    await callWithDone(
+      suite.fn as unknown as TestFn,
+      undefined,
+      new SuiteContext(suite.name, suite.options.signal),
+    );
+  } finally {
+    currentSuite = previousSuite;
+  }
+
+  // `only` narrows down the tests of a suite to the marked ones
+  const hasOnly = suite.children.some((child) => child.options.only);
+
+  await runHooks(suite.hooks.before);
+  try {
+    for (const child of suite.children) {
+      await denoContext.step({
+        name: child.name,
+        ignore: isIgnored(child.options) || (hasOnly && !child.options.only),
+        ...sanitizers,
+        fn: (t) =>
+          child instanceof Suite ? runSuite(child, t) : runTest(
+            child,
+            t,
+            null,
+            suiteBeforeEach(suite),
+            suiteAfterEach(suite),
+          ),
+      });
+    }
+  } finally {
+    await runHooks(suite.hooks.after);
+  }
+}
+
+function registerRootAfter() {
+  if (rootAfterRegistered) {
+    return;
+  }
+  rootAfterRegistered = true;
+  // there is no way to run code after the last test, so `after()` hooks at
+  // the top level run when the test module is unloaded
+  globalThis.addEventListener("unload", () => {
+    for (const hook of rootHooks.after) {
+      hook();
+    }
+  });
+}
+
+function register(definition: Suite | TestDefinition) {
+  const { name, options } = definition;
+  Deno.test({
+    name,
+    ignore: isIgnored(options),
+    only: !!options.only,
+    ...sanitizers,
+    async fn(t) {
+      await runRootBefore();
+      if (definition instanceof Suite) {
+        await runSuite(definition, t);
+      } else {
+        await runTest(
+          definition,
+          t,
+          null,
+          rootHooks.beforeEach,
+          rootHooks.afterEach,
+        );
+      }
+    },
+  });
+}
+
+const kFinish = Symbol("kFinish");
+const kAbort = Symbol("kAbort");
+const kTodo = Symbol("kTodo");
+const kSuite = Symbol("kSuite");
+
+export class TestContext {
+  #name: string;
+  #denoContext: Deno.TestContext;
+  #parent: TestContext | null;
+  #abortController = new AbortController();
+  #hooks: Hooks = { before: [], after: [], beforeEach: [], afterEach: [] };
+  #beforeDone = false;
+  #mock: MockTracker | undefined;
+  [kTodo] = false;
+
+  constructor(
+    name: string,
+    denoContext: Deno.TestContext,
+    parent: TestContext | null,
+    signal?: AbortSignal,
+  ) {
+    this.#name = name;
+    this.#denoContext = denoContext;
+    this.#parent = parent;
+    if (signal !== undefined) {
+      signal.addEventListener("abort", () => this[kAbort]());
+    }
+  }
+
+  get name(): string {
+    return this.#name;
+  }
+
+  get signal(): AbortSignal {
+    return this.#abortController.signal;
+  }
+
+  get mock(): MockTracker {
+    this.#mock ??= new MockTracker();
+    return this.#mock;
+  }
+
+  diagnostic(message: string) {
+    console.log(`# ${message}`);
+  }
+
+  runOnly(_value: boolean) {
+    // only is handled when tests are registered
+  }
+
+  skip(message?: string) {
+    // a running test can't be marked as ignored, so just report it
+    this.diagnostic(message ? `SKIP ${message}` : "SKIP");
+  }
+
+  todo(message?: string) {
+    this[kTodo] = true;
+    this.diagnostic(message ? `TODO ${message}` : "TODO");
+  }
+
+  before(fn: HookFn, _options?: HookOptions) {
+    validateFunction(fn, "fn");
+    this.#hooks.before.push(fn);
+  }
+
+  after(fn: HookFn, _options?: HookOptions) {
+    validateFunction(fn, "fn");
+    this.#hooks.after.push(fn);
+  }
+
+  beforeEach(fn: HookFn, _options?: HookOptions) {
+    validateFunction(fn, "fn");
+    this.#hooks.beforeEach.push(fn);
+  }
+
+  afterEach(fn: HookFn, _options?: HookOptions) {
+    validateFunction(fn, "fn");
+    this.#hooks.afterEach.push(fn);
+  }
+
+  async test(
+    name?: string | TestOptions | TestFn,
+    options?: TestOptions | TestFn,
+    fn?: TestFn,
+  ): Promise<void> {
+    const definition = prepareArgs(name, options, fn);
+    if (!this.#beforeDone) {
+      this.#beforeDone = true;
+      await runHooks(this.#hooks.before, this);
+    }
+    const beforeEach = [];
+    const afterEach = [];
+    for (let t: TestContext | null = this; t !== null; t = t.#parent) {
+      beforeEach.unshift(...t.#hooks.beforeEach);
+      afterEach.push(...t.#hooks.afterEach);
+    }
+    await this.#denoContext.step({
+      name: definition.name,
+      ignore: isIgnored(definition.options),
+      ...sanitizers,
+      fn: (t) => runTest(definition, t, this, beforeEach, afterEach),
+    });
+  }
+
+  async [kSuite](suite: Suite) {
+    await this.#denoContext.step({
+      name: suite.name,
+      ignore: isIgnored(suite.options),
+      ...sanitizers,
+      fn: (t) => runSuite(suite, t),
+    });
+  }
+
+  async [kFinish]() {
+    await runHooks(this.#hooks.after, this);
+  }
+
+  [kAbort]() {
+    if (!this.#abortController.signal.aborted) {
+      this.#abortController.abort();
+    }
+  }
+}
+
+export class SuiteContext {
+  #name: string;
+  #signal: AbortSignal;
+
+  constructor(name: string, signal?: AbortSignal) {
+    this.#name = name;
+    this.#signal = signal ?? new AbortController().signal;
+  }
+
+  get name(): string {
+    return this.#name;
+  }
+
+  get signal(): AbortSignal {
+    return this.#signal;
+  }
+}
+
+export function test(
+  name?: string | TestOptions | TestFn,
+  options?: TestOptions | TestFn,
+  fn?: TestFn,
+): Promise<void> {
+  return runOrRegister(prepareArgs(name, options, fn));
+}
+
+function runOrRegister(definition: TestDefinition): Promise<void> {
+  if (currentSuite !== null) {
+    currentSuite.children.push(definition);
+  } else if (currentTest !== null) {
+    return currentTest.test(
+      definition.name,
+      definition.options,
+      definition.fn,
+    );
+  } else {
+    register(definition);
+  }
+  return Promise.resolve();
+}
+
+test.skip = function skip(
+  name?: string | TestOptions | TestFn,
+  options?: TestOptions | TestFn,
+  fn?: TestFn,
+) {
+  return runOrRegister(prepareArgs(name, options, fn, { skip: true }));
+};
+
+test.todo = function todo(
+  name?: string | TestOptions | TestFn,
+  options?: TestOptions | TestFn,
+  fn?: TestFn,
+) {
+  return runOrRegister(prepareArgs(name, options, fn, { todo: true }));
+};
+
+test.only = function only(
+  name?: string | TestOptions | TestFn,
+  options?: TestOptions | TestFn,
+  fn?: TestFn,
+) {
+  return runOrRegister(prepareArgs(name, options, fn, { only: true }));
+};
+
+export function describe(
+  name?: string | TestOptions | SuiteFn,
+  options?: TestOptions | SuiteFn,
+  fn?: SuiteFn,
+): Promise<void> {
+  return describeWith(name, options, fn);
+}
+
+function describeWith(
+  name: unknown,
+  options: unknown,
+  fn: unknown,
+  overrides?: TestOptions,
+): Promise<void> {
+  const { name: suiteName, options: suiteOptions, fn: suiteFn } = prepareArgs(
+    name,
+    options,
+    fn,
+    overrides,
+  );
+  const suite = new Suite(
+    suiteName,
+    suiteOptions,
+    suiteFn as unknown as SuiteFn,
+    currentSuite,
+  );
+  if (currentSuite !== null) {
+    currentSuite.children.push(suite);
+  } else if (currentTest !== null) {
+    return currentTest[kSuite](suite);
+  } else {
+    register(suite);
+  }
+  return Promise.resolve();
+}
+
+describe.skip = function skip(
+  name?: string | TestOptions | SuiteFn,
+  options?: TestOptions | SuiteFn,
+  fn?: SuiteFn,
+) {
+  return describeWith(name, options, fn, { skip: true });
+};
+
+describe.todo = function todo(
+  name?: string | TestOptions | SuiteFn,
+  options?: TestOptions | SuiteFn,
+  fn?: SuiteFn,
+) {
+  return describeWith(name, options, fn, { todo: true });
+};
+
+describe.only = function only(
+  name?: string | TestOptions | SuiteFn,
+  options?: TestOptions | SuiteFn,
+  fn?: SuiteFn,
+) {
+  return describeWith(name, options, fn, { only: true });
+};
+
+export const it = test;
+export const suite = describe;
+
+function addHook(kind: keyof Hooks, fn: HookFn, options?: HookOptions) {
+  validateFunction(fn, "fn");
+  if (options !== undefined) {
+    validateObject(options, "options");
+  }
+  if (currentSuite !== null) {
+    currentSuite.hooks[kind].push(fn);
+  } else if (currentTest !== null) {
+    currentTest[kind](fn, options);
+  } else {
+    rootHooks[kind].push(fn);
+    if (kind === "after") {
+      registerRootAfter();
+    }
+  }
+}
+
+export function before(fn: HookFn, options?: HookOptions) {
+  addHook("before", fn, options);
+}
+
+export function after(fn: HookFn, options?: HookOptions) {
+  addHook("after", fn, options);
+}
+
+export function beforeEach(fn: HookFn, options?: HookOptions) {
+  addHook("beforeEach", fn, options);
+}
+
+export function afterEach(fn: HookFn, options?: HookOptions) {
+  addHook("afterEach", fn, options);
+}
+
+export function run() {
+  notImplemented("test.run");
+}
+
+interface MockCall {
+  arguments: unknown[];
+  error: unknown;
+  result: unknown;
+  stack: Error;
+  target: unknown;
+  this: unknown;
+}
+
+class MockFunctionContext {
+  #calls: MockCall[] = [];
+  #implementation: ((...args: unknown[]) => unknown) | undefined;
+  #onceImplementations = new Map<number, (...args: unknown[]) => unknown>();
+  #times: number;
+  #restore: (() => void) | undefined;
+
+  constructor(
+    implementation: ((...args: unknown[]) => unknown) | undefined,
+    restore: (() => void) | undefined,
+    times: number,
+  ) {
+    this.#implementation = implementation;
+    this.#restore = restore;
+    this.#times = times;
+  }
+
+  get calls(): MockCall[] {
+    return [...this.#calls];
+  }
+
+  callCount(): number {
+    return this.#calls.length;
+  }
+
+  mockImplementation(implementation: (...args: unknown[]) => unknown) {
+    validateFunction(implementation, "implementation");
+    this.#implementation = implementation;
+  }
+
+  mockImplementationOnce(
+    implementation: (...args: unknown[]) => unknown,
+    onCall?: number,
+  ) {
+    validateFunction(implementation, "implementation");
+    const call = onCall ?? this.#calls.length;
+    validateInteger(call, "onCall", this.#calls.length);
+    this.#onceImplementations.set(call, implementation);
+  }
+
+  resetCalls() {
+    this.#calls = [];
+  }
+
+  restore() {
+    this.#restore?.();
+  }
+
+  /** Returns the implementation for the next call. */
+  nextImplementation(
+    original: ((...args: unknown[]) => unknown) | undefined,
+  ): ((...args: unknown[]) => unknown) | undefined {
+    const call = this.#calls.length;
+    const once = this.#onceImplementations.get(call);
+    if (once !== undefined) {
+      this.#onceImplementations.delete(call);
+      return once;
+    }
+    if (call >= this.#times) {
+      return original;
+    }
+    return this.#implementation ?? original;
+  }
+
+  recordCall(call: MockCall) {
+    this.#calls.push(call);
+  }
+}
+
+interface MockFunctionOptions {
+  times?: number;
+}
+
+interface MockMethodOptions extends MockFunctionOptions {
+  getter?: boolean;
+  setter?: boolean;
+}
+
+export class MockTracker {
+  #mocks: MockFunctionContext[] = [];
+  #timers: MockTimers | undefined;
+
+  get timers(): MockTimers {
+    this.#timers ??= new MockTimers();
+    return this.#timers;
+  }
+
+  fn(
+    original?:
+      | ((...args: unknown[]) => unknown)
+      | MockFunctionOptions,
+    implementation?:
+      | ((...args: unknown[]) => unknown)
+      | MockFunctionOptions,
+    options: MockFunctionOptions = {},
+  ) {
+    if (original !== null && typeof original === "object") {
+      options = original;
+      original = undefined;
+      implementation = undefined;
+    } else if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = undefined;
+    }
+    original ??= function () {};
+    validateFunction(original, "original");
+    if (implementation !== undefined) {
+      validateFunction(implementation, "implementation");
+    }
+    validateObject(options, "options");
+    const { times = Infinity } = options;
+    if (times !== Infinity) {
+      validateInteger(times, "options.times", 1);
+    }
+    const ctx = new MockFunctionContext(
+      implementation as (...args: unknown[]) => unknown,
+      undefined,
+      times,
+    );
+    return this.#setupMock(ctx, original as (...args: unknown[]) => unknown);
+  }
+
+  method(
+    // deno-lint-ignore no-explicit-any
+    object: any,
+    methodName: string | symbol,
+    implementation?:
+      | ((...args: unknown[]) => unknown)
+      | MockMethodOptions,
+    options: MockMethodOptions = {},
+  ) {
+    if (object === null || typeof object !== "object") {
+      if (typeof object !== "function") {
+        throw new ERR_INVALID_ARG_TYPE(
+          "object",
+          ["Object", "Function"],
+          object,
+        );
+      }
+    }
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = undefined;
+    }
+    validateObject(options, "options");
+    const { getter = false, setter = false, times = Infinity } = options;
+    if (getter && setter) {
+      throw new ERR_INVALID_ARG_VALUE(
+        "options.setter",
+        setter,
+        "cannot be used with 'options.getter'",
+      );
+    }
+
+    let descriptor: PropertyDescriptor | undefined;
+    for (
+      let target = object;
+      target !== null && descriptor === undefined;
+      target = Object.getPrototypeOf(target)
+    ) {
+      descriptor = Object.getOwnPropertyDescriptor(target, methodName);
+    }
+    const kind = getter ? "get" : setter ? "set" : "value";
+    const original = descriptor?.[kind];
+    if (typeof original !== "function") {
+      throw new ERR_INVALID_ARG_VALUE(
+        "methodName",
+        original,
+        "must be a method",
+      );
+    }
+    if (implementation !== undefined) {
+      validateFunction(implementation, "implementation");
+    }
+
+    const ownDescriptor = Object.getOwnPropertyDescriptor(object, methodName);
+    const restore = () => {
+      if (ownDescriptor === undefined) {
+        delete object[methodName];
+      } else {
+        Object.defineProperty(object, methodName, ownDescriptor);
+      }
+    };
+    const ctx = new MockFunctionContext(
+      implementation as (...args: unknown[]) => unknown,
+      restore,
+      times,
+    );
+    const mock = this.#setupMock(ctx, original);
+    Object.defineProperty(object, methodName, {
+      configurable: true,
+      enumerable: descriptor!.enumerable,
+      ...(kind === "value" ? { writable: true, value: mock } : {
+        get: kind === "get" ? mock : descriptor!.get,
+        set: kind === "set" ? mock : descriptor!.set,
+      }),
+    });
+    return mock;
+  }
+
+  getter(
+    // deno-lint-ignore no-explicit-any
+    object: any,
+    methodName: string | symbol,
+    implementation?: ((...args: unknown[]) => unknown) | MockMethodOptions,
+    options: MockMethodOptions = {},
+  ) {
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = undefined;
+    }
+    return this.method(object, methodName, implementation, {
+      ...options,
+      getter: true,
+    });
+  }
+
+  setter(
+    // deno-lint-ignore no-explicit-any
+    object: any,
+    methodName: string | symbol,
+    implementation?: ((...args: unknown[]) => unknown) | MockMethodOptions,
+    options: MockMethodOptions = {},
+  ) {
+    if (implementation !== null && typeof implementation === "object") {
+      options = implementation;
+      implementation = undefined;
+    }
+    return this.method(object, methodName, implementation, {
+      ...options,
+      setter: true,
+    });
+  }
+
+  reset() {
+    this.restoreAll();
+    this.#timers?.reset();
+    this.#mocks = [];
+  }
+
+  restoreAll() {
+    for (const ctx of this.#mocks) {
+      ctx.restore();
+    }
+  }
+
+  #setupMock(
+    ctx: MockFunctionContext,
+    original: (...args: unknown[]) => unknown,
+  ) {
+    this.#mocks.push(ctx);
+    // deno-lint-ignore no-explicit-any
+    const mock = function (this: unknown, ...args: unknown[]): any {
+      const implementation = ctx.nextImplementation(original);
+      const call: MockCall = {
+        arguments: args,
+        error: undefined,
+        result: undefined,
+        stack: new Error(),
+        target: new.target,
+        this: this,
+      };
+      try {
+        call.result = new.target
+          ? Reflect.construct(implementation!, args, new.target)
+          : Reflect.apply(implementation!, this, args);
+        return call.result;
+      } catch (err) {
+        call.error = err;
+        throw err;
+      } finally {
+        ctx.recordCall(call);
+      }
+    };
+    Object.defineProperty(mock, "mock", {
+      value: ctx,
+      enumerable: false,
+      configurable: false,
+    });
+    Object.defineProperty(mock, "name", { value: original.name });
+    Object.defineProperty(mock, "length", { value: original.length });
+    return mock as typeof mock & { mock: MockFunctionContext };
+  }
+}
+
+type TimerApi = "setTimeout" | "setInterval" | "setImmediate" | "Date";
+
+const TIMER_APIS: TimerApi[] = [
+  "setTimeout",
+  "setInterval",
+  "setImmediate",
+  "Date",
+];
+
+interface MockTimer {
+  id: number;
+  callback: (...args: unknown[]) => unknown;
+  args: unknown[];
+  runAt: number;
+  interval: number | undefined;
+}
+
+class MockTimeout {
+  #id: number;
+  #timers: MockTimers;
+
+  constructor(id: number, timers: MockTimers) {
+    this.#id = id;
+    this.#timers = timers;
+  }
+
+  ref() {
+    return this;
+  }
+
+  unref() {
+    return this;
+  }
+
+  hasRef() {
+    return true;
+  }
+
+  refresh() {
+    this.#timers[kRefresh](this.#id);
+    return this;
+  }
+
+  [Symbol.toPrimitive]() {
+    return this.#id;
+  }
+}
+
+const kRefresh = Symbol("kRefresh");
+
+export class MockTimers {
+  #enabled = false;
+  #now = 0;
+  #nextId = 1;
+  #timers = new Map<number, MockTimer>();
+  #restore: (() => void)[] = [];
+
+  enable(options?: TimerApi[] | { apis?: TimerApi[]; now?: number | Date }) {
+    if (this.#enabled) {
+      throw new ERR_INVALID_STATE("MockTimers is already enabled!");
+    }
+    let apis = TIMER_APIS;
+    let now: number | Date = 0;
+    if (Array.isArray(options)) {
+      apis = options;
+    } else if (options !== undefined) {
+      validateObject(options, "options");
+      apis = options.apis ?? TIMER_APIS;
+      now = options.now ?? 0;
+    }
+    for (const api of apis) {
+      if (!TIMER_APIS.includes(api)) {
+        throw new ERR_INVALID_ARG_VALUE(
+          "options.apis",
+          api,
+          `option ${api} is not supported`,
+        );
+      }
+    }
+    this.#now = now instanceof Date ? now.getTime() : now;
+    validateInteger(this.#now, "options.now", 0);
+    this.#enabled = true;
+
+    for (const api of apis) {
+      switch (api) {
+        case "setTimeout":
+          this.#replace("setTimeout", (callback, delay, ...args) =>
+            this.#add(callback, delay, args, false));
+          this.#replace("clearTimeout", (timer) => this.#clear(timer));
+          break;
+        case "setInterval":
+          this.#replace("setInterval", (callback, delay, ...args) =>
+            this.#add(callback, delay, args, true));
+          this.#replace("clearInterval", (timer) => this.#clear(timer));
+          break;
+        case "setImmediate":
+          this.#replace("setImmediate", (callback, ...args) =>
+            this.#add(callback, 0, args, false));
+          this.#replace("clearImmediate", (timer) => this.#clear(timer));
+          break;
+        case "Date":
+          this.#replace("Date", this.#createDate());
+          break;
+      }
+    }
+  }
+
+  tick(milliseconds = 1) {
+    this.#assertEnabled("tick");
+    validateInteger(milliseconds, "milliseconds", 0);
+    const target = this.#now + milliseconds;
+    for (;;) {
+      const timer = this.#nextTimer();
+      if (timer === undefined || timer.runAt > target) {
+        break;
+      }
+      this.#run(timer);
+    }
+    this.#now = target;
+  }
+
+  runAll() {
+    this.#assertEnabled("runAll");
+    let latest = this.#now;
+    for (const timer of this.#timers.values()) {
+      latest = Math.max(latest, timer.runAt);
+    }
+    this.tick(latest - this.#now);
+  }
+
+  setTime(milliseconds: number) {
+    this.#assertEnabled("setTime");
+    validateInteger(milliseconds, "milliseconds", 0);
+    this.#now = milliseconds;
+  }
+
+  reset() {
+    for (const restore of this.#restore.reverse()) {
+      restore();
+    }
+    this.#restore = [];
+    this.#timers.clear();
+    this.#enabled = false;
+    this.#now = 0;
+  }
+
+  [kRefresh](id: number) {
+    const timer = this.#timers.get(id);
+    if (timer !== undefined) {
+      const delay = timer.interval ?? 0;
+      timer.runAt = this.#now + delay;
+    }
+  }
+
+  #assertEnabled(method: string) {
+    if (!this.#enabled) {
+      throw new ERR_INVALID_STATE(
+        `You should enable MockTimers first by calling the .enable function before calling .${method}`,
+      );
+    }
+  }
+
+  // deno-lint-ignore no-explicit-any
+  #replace(name: string, value: any) {
+    // deno-lint-ignore no-explicit-any
+    const global = globalThis as any;
+    const descriptor = Object.getOwnPropertyDescriptor(global, name);
+    global[name] = value;
+    this.#restore.push(() => {
+      if (descriptor === undefined) {
+        delete global[name];
+      } else {
+        Object.defineProperty(global, name, descriptor);
+      }
+    });
+  }
+
+  #add(
+    callback: (...args: unknown[]) => unknown,
+    delay: unknown,
+    args: unknown[],
+    repeat: boolean,
+  ): MockTimeout {
+    validateFunction(callback, "callback");
+    let ms = Number(delay);
+    if (!(ms >= 1 && ms <= 2 ** 31 - 1)) {
+      ms = repeat || delay !== 0 ? 1 : 0;
+    }
+    const id = this.#nextId++;
+    this.#timers.set(id, {
+      id,
+      callback,
+      args,
+      runAt: this.#now + ms,
+      interval: repeat ? ms : undefined,
+    });
+    return new MockTimeout(id, this);
+  }
+
+  #clear(timer: unknown) {
+    if (timer !== undefined && timer !== null) {
+      this.#timers.delete(Number(timer));
+    }
+  }
+
+  #nextTimer(): MockTimer | undefined {
+    let next: MockTimer | undefined;
+    for (const timer of this.#timers.values()) {
+      // timers that are due at the same time run in creation order
+      if (next === undefined || timer.runAt < next.runAt) {
+        next = timer;
+      }
+    }
+    return next;
+  }
+
+  #run(timer: MockTimer) {
+    this.#now = timer.runAt;
+    if (timer.interval === undefined) {
+      this.#timers.delete(timer.id);
+    } else {
+      timer.runAt += timer.interval;
+    }
+    timer.callback(...timer.args);
+  }
+
+  #createDate() {
+    const OriginalDate = Date;
+    // deno-lint-ignore no-this-alias
+    const timers = this;
+    function MockDate(this: unknown, ...args: unknown[]) {
+      if (!new.target) {
+        return new OriginalDate(timers.#now).toString();
+      }
+      if (args.length === 0) {
+        return Reflect.construct(OriginalDate, [timers.#now], new.target);
+      }
+      return Reflect.construct(OriginalDate, args, new.target);
+    }
+    Object.setPrototypeOf(MockDate, OriginalDate);
+    MockDate.prototype = OriginalDate.prototype;
+    MockDate.now = () => timers.#now;
+    return MockDate;
+  }
+}
+
+export const mock = new MockTracker();
+
+test.test = test;
+test.describe = describe;
+test.it = it;
+test.suite = suite;
+test.before = before;
+test.after = after;
+test.beforeEach = beforeEach;
+test.afterEach = afterEach;
+test.mock = mock;
+test.run = run;
+
+export const skip = test.skip;
+export const todo = test.todo;
+export const only = test.only;
+
+export default test;
@@ -1,6 +1,10 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
-import { setUnrefTimeout, Timeout } from "ext:deno_node/internal/timers.mjs";
+import {
+  activeTimers,
+  setUnrefTimeout,
+  Timeout,
+} from "ext:deno_node/internal/timers.mjs";
 import { validateFunction } from "ext:deno_node/internal/validators.mjs";
 import { promisify } from "ext:deno_node/internal/util.mjs";
 export { setUnrefTimeout } from "ext:deno_node/internal/timers.mjs";
@@ -29,7 +33,9 @@ export function clearTimeout(timeout?: Timeout | number) {
   if (timeout == null) {
     return;
   }
-  clearTimeout_(+timeout);
+  const id = +timeout;
+  activeTimers.delete(id);
+  clearTimeout_(id);
 }
 export function setInterval(
   callback: (...args: unknown[]) => void,
@@ -43,7 +49,9 @@ export function clearInterval(timeout?: Timeout | number | string) {
   if (timeout == null) {
     return;
   }
-  clearInterval_(+timeout);
+  const id = +timeout;
+  activeTimers.delete(id);
+  clearInterval_(id);
 }
 // TODO(bartlomieju): implement the 'NodeJS.Immediate' versions of the timers.
 // https://github.com/DefinitelyTyped/DefinitelyTyped/blob/1163ead296d84e7a3c80d71e7c81ecbd1a130e9a/types/node/v12/globals.d.ts#L1120-L1131
@@ -1,7 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
+const { core } = globalThis.__bootstrap;
 import { notImplemented } from "ext:deno_node/_utils.ts";
+import { validateOneOf } from "ext:deno_node/internal/validators.mjs";
 import tlsCommon from "ext:deno_node/_tls_common.ts";
 import tlsWrap from "ext:deno_node/_tls_wrap.ts";
 
@@ -27,6 +29,16 @@ export function getCiphers() {
   return Object.keys(cipherMap).map((name) => name.toLowerCase());
 }
 
+/**
+ * Returns the PEM encoded certificates of the CA store that is shared with
+ * `fetch`, which includes certificates from `--cert`, `DENO_CERT` and
+ * `NODE_EXTRA_CA_CERTS` along with the bundled Mozilla roots.
+ */
+export function getCACertificates(type = "default"): string[] {
+  validateOneOf(type, "type", ["default", "bundled", "system", "extra"]);
+  return core.ops.op_tls_ca_certificates(type);
+}
+
 export const rootCertificates = undefined;
 export const DEFAULT_ECDH_CURVE = "auto";
 export const DEFAULT_MAX_VERSION = "TLSv1.3";
@@ -49,6 +61,7 @@ export default {
   createSecureContext: tlsCommon.createSecureContext,
   createSecurePair,
   createServer: tlsWrap.createServer,
+  getCACertificates,
   getCiphers,
   rootCertificates,
   DEFAULT_CIPHERS: tlsWrap.DEFAULT_CIPHERS,
@@ -1,6 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 import { Socket } from "ext:deno_node/net.ts";
+import {
+  clearLine,
+  clearScreenDown,
+  cursorTo,
+  moveCursor,
+} from "ext:deno_node/internal/readline/callbacks.mjs";
+import { getColorDepth, hasColors } from "ext:deno_node/internal/tty.ts";
 
 // Returns true when the given numeric fd is associated with a TTY and false otherwise.
 function isatty(fd: number) {
@@ -19,6 +26,38 @@ export class ReadStream extends Socket {
 }
 // TODO(kt3k): Implement tty.WriteStream class
 export class WriteStream extends Socket {
+  isTTY = true;
+
+  getColorDepth(env?: Record<string, string | undefined>): number {
+    return getColorDepth(env);
+  }
+
+  hasColors(
+    count?: number | Record<string, string | undefined>,
+    env?: Record<string, string | undefined>,
+  ): boolean {
+    return hasColors(count, env);
+  }
+
+  cursorTo(
+    x: number,
+    y?: number | (() => void),
+    callback?: () => void,
+  ): boolean {
+    return cursorTo(this, x, y, callback);
+  }
+
+  moveCursor(dx: number, dy: number, callback?: () => void): boolean {
+    return moveCursor(this, dx, dy, callback);
+  }
+
+  clearLine(dir: -1 | 0 | 1, callback?: () => void): boolean {
+    return clearLine(this, dir, callback);
+  }
+
+  clearScreenDown(callback?: () => void): boolean {
+    return clearScreenDown(this, callback);
+  }
 }
 
 export { isatty };
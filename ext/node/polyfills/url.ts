@@ -27,6 +27,7 @@ import {
   ERR_INVALID_FILE_URL_PATH,
   ERR_INVALID_URL,
   ERR_INVALID_URL_SCHEME,
+  ERR_MISSING_ARGS,
 } from "ext:deno_node/internal/errors.ts";
 import { validateString } from "ext:deno_node/internal/validators.mjs";
 import {
@@ -67,7 +68,7 @@ import {
   CHAR_ZERO_WIDTH_NOBREAK_SPACE,
 } from "ext:deno_node/path/_constants.ts";
 import * as path from "ext:deno_node/path.ts";
-import { toASCII, toUnicode } from "ext:deno_node/punycode.ts";
+import { toASCII, toUnicode } from "ext:deno_node/internal/idna.ts";
 import { isWindows, osType } from "ext:deno_node/_util/os.ts";
 import { encodeStr, hexTable } from "ext:deno_node/internal/querystring.ts";
 import querystring from "ext:deno_node/querystring.ts";
@@ -811,9 +812,8 @@ export class Url {
           // have non-ASCII characters, i.e. it doesn't matter if
           // you call it with a domain that already is ASCII-only.
 
-          // Use lenient mode (`true`) to try to support even non-compliant
-          // URLs.
-          this.hostname = toASCII(this.hostname);
+          // Invalid domains become empty, which is rejected below.
+          this.hostname = domainToASCII(this.hostname);
 
           // Prevent two potential routes of hostname spoofing.
           // 1. If this.hostname is empty, it must have become empty due to toASCII
@@ -1247,21 +1247,39 @@ export function resolveObject(source: string | Url, relative: string) {
 /**
  * The url.domainToASCII() takes an arbitrary domain and attempts to convert it into an IDN
  *
+ * Returns an empty string if the domain is invalid.
+ *
  * @param domain The domain to convert to an IDN
- * @see https://www.rfc-editor.org/rfc/rfc3490#section-4
+ * @see https://url.spec.whatwg.org/#concept-domain-to-ascii
  */
 export function domainToASCII(domain: string) {
-  return toASCII(domain);
+  if (arguments.length < 1) {
+    throw new ERR_MISSING_ARGS("domain");
+  }
+  try {
+    return toASCII(`${domain}`);
+  } catch {
+    return "";
+  }
 }
 
 /**
  * The url.domainToUnicode() takes an IDN and attempts to convert it into unicode
  *
+ * Returns an empty string if the domain is invalid.
+ *
  * @param domain The IDN to convert to Unicode
- * @see https://www.rfc-editor.org/rfc/rfc3490#section-4
+ * @see https://url.spec.whatwg.org/#concept-domain-to-unicode
  */
 export function domainToUnicode(domain: string) {
-  return toUnicode(domain);
+  if (arguments.length < 1) {
+    throw new ERR_MISSING_ARGS("domain");
+  }
+  try {
+    return toUnicode(`${domain}`);
+  } catch {
+    return "";
+  }
 }
 
 /**
@@ -2,6 +2,9 @@
 import { promisify } from "ext:deno_node/internal/util.mjs";
 import { callbackify } from "ext:deno_node/_util/_util_callbackify.ts";
 import { debuglog } from "ext:deno_node/internal/util/debuglog.ts";
+import {
+  parseArgs,
+} from "ext:deno_node/internal/util/parse_args/parse_args.mjs";
 import {
   format,
   formatWithOptions,
@@ -21,6 +24,7 @@ export {
   format,
   formatWithOptions,
   inspect,
+  parseArgs,
   promisify,
   stripVTControlCharacters,
   types,
@@ -283,6 +287,7 @@ export default {
   callbackify,
   promisify,
   inherits,
+  parseArgs,
   types,
   stripVTControlCharacters,
   TextDecoder,
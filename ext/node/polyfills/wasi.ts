@@ -1,11 +1,1050 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+// Copyright Joyent and Node contributors. All rights reserved. MIT license.
 
-class Context {
-  constructor() {
-    throw new Error("Context is currently not supported");
+// This module implements WASI snapshot preview1 on top of the Deno APIs, so
+// file system access is subject to the usual permission checks. Host paths
+// are only reachable through the directories listed in `preopens`.
+
+import * as io from "ext:deno_io/12_io.js";
+import {
+  ERR_INVALID_ARG_TYPE,
+  ERR_INVALID_ARG_VALUE,
+  ERR_WASI_ALREADY_STARTED,
+} from "ext:deno_node/internal/errors.ts";
+import {
+  validateArray,
+  validateBoolean,
+  validateFunction,
+  validateInt32,
+  validateObject,
+  validateString,
+} from "ext:deno_node/internal/validators.mjs";
+import {
+  basename,
+  dirname,
+  isAbsolute,
+  join,
+  relative,
+  resolve,
+  sep,
+} from "ext:deno_node/path.ts";
+
+const ERRNO_SUCCESS = 0;
+const ERRNO_2BIG = 1;
+const ERRNO_ACCES = 2;
+const ERRNO_BADF = 8;
+const ERRNO_EXIST = 20;
+const ERRNO_INTR = 27;
+const ERRNO_INVAL = 28;
+const ERRNO_IO = 29;
+const ERRNO_ISDIR = 31;
+const ERRNO_NOENT = 44;
+const ERRNO_NOSYS = 52;
+const ERRNO_NOTDIR = 54;
+const ERRNO_NOTEMPTY = 55;
+const ERRNO_NOTSUP = 58;
+const ERRNO_SPIPE = 70;
+const ERRNO_NOTCAPABLE = 76;
+
+const CLOCKID_REALTIME = 0;
+const CLOCKID_MONOTONIC = 1;
+const CLOCKID_PROCESS_CPUTIME_ID = 2;
+const CLOCKID_THREAD_CPUTIME_ID = 3;
+
+const FILETYPE_UNKNOWN = 0;
+const FILETYPE_CHARACTER_DEVICE = 2;
+const FILETYPE_DIRECTORY = 3;
+const FILETYPE_REGULAR_FILE = 4;
+const FILETYPE_SYMBOLIC_LINK = 7;
+
+const FDFLAGS_APPEND = 0x0001;
+
+const OFLAGS_CREAT = 0x0001;
+const OFLAGS_DIRECTORY = 0x0002;
+const OFLAGS_EXCL = 0x0004;
+const OFLAGS_TRUNC = 0x0008;
+
+const RIGHTS_FD_READ = 0x0000000000000002n;
+const RIGHTS_FD_WRITE = 0x0000000000000040n;
+const RIGHTS_ALL = 0x000000001fffffffn;
+
+const LOOKUPFLAGS_SYMLINK_FOLLOW = 0x0001;
+
+const WHENCE_SET = 0;
+const WHENCE_CUR = 1;
+const WHENCE_END = 2;
+
+const FSTFLAGS_ATIM = 0x0001;
+const FSTFLAGS_ATIM_NOW = 0x0002;
+const FSTFLAGS_MTIM = 0x0004;
+const FSTFLAGS_MTIM_NOW = 0x0008;
+
+const PREOPENTYPE_DIR = 0;
+
+interface FileDescriptor {
+  /** Deno resource id, if the descriptor is backed by an open resource. */
+  rid?: number;
+  type: number;
+  flags: number;
+  /** Path on the host. */
+  path?: string;
+  /** Path inside the sandbox, only set for preopened directories. */
+  vpath?: string;
+  /** The preopened directory this descriptor was opened from. */
+  root?: string;
+  /** Directory entries, cached while iterating with `fd_readdir`. */
+  entries?: Deno.DirEntry[];
+}
+
+class ExitStatus {
+  code: number;
+
+  constructor(code: number) {
+    this.code = code;
+  }
+}
+
+function errnoFromError(err: unknown): number {
+  if (err instanceof Deno.errors.NotFound) {
+    return ERRNO_NOENT;
+  }
+  if (err instanceof Deno.errors.PermissionDenied) {
+    return ERRNO_ACCES;
+  }
+  if (err instanceof Deno.errors.AlreadyExists) {
+    return ERRNO_EXIST;
+  }
+  if (err instanceof Deno.errors.BadResource) {
+    return ERRNO_BADF;
+  }
+  if (err instanceof Deno.errors.InvalidData) {
+    return ERRNO_INVAL;
+  }
+  if (err instanceof Deno.errors.Interrupted) {
+    return ERRNO_INTR;
+  }
+  if (err instanceof Deno.errors.NotSupported) {
+    return ERRNO_NOTSUP;
+  }
+  const message = (err as Error)?.message ?? "";
+  if (message.includes("os error 39") || message.includes("os error 66")) {
+    return ERRNO_NOTEMPTY;
+  }
+  if (message.includes("os error 20")) {
+    return ERRNO_NOTDIR;
+  }
+  if (message.includes("os error 21")) {
+    return ERRNO_ISDIR;
+  }
+  return ERRNO_IO;
+}
+
+function fileTypeOf(info: Deno.FileInfo): number {
+  if (info.isFile) {
+    return FILETYPE_REGULAR_FILE;
+  }
+  if (info.isDirectory) {
+    return FILETYPE_DIRECTORY;
+  }
+  if (info.isSymlink) {
+    return FILETYPE_SYMBOLIC_LINK;
+  }
+  return FILETYPE_UNKNOWN;
+}
+
+function toNanoseconds(date: Date | null): bigint {
+  return date ? BigInt(date.getTime()) * 1_000_000n : 0n;
+}
+
+function writeFilestat(view: DataView, ptr: number, info: Deno.FileInfo) {
+  view.setBigUint64(ptr, BigInt(info.dev ?? 0), true);
+  view.setBigUint64(ptr + 8, BigInt(info.ino ?? 0), true);
+  view.setUint8(ptr + 16, fileTypeOf(info));
+  view.setBigUint64(ptr + 24, BigInt(info.nlink ?? 1), true);
+  view.setBigUint64(ptr + 32, BigInt(info.size), true);
+  view.setBigUint64(ptr + 40, toNanoseconds(info.atime), true);
+  view.setBigUint64(ptr + 48, toNanoseconds(info.mtime), true);
+  view.setBigUint64(ptr + 56, toNanoseconds(info.birthtime), true);
+}
+
+/** The path with its symlinks resolved. The components that don't exist
+ * yet, like the file that is about to be created, are kept as they are. The
+ * last component is only resolved if `followLast` is set, so that a link
+ * itself can be removed or read. */
+function realPath(path: string, followLast: boolean): string {
+  if (followLast) {
+    try {
+      return Deno.realPathSync(path);
+    } catch (err) {
+      if (!(err instanceof Deno.errors.NotFound)) {
+        throw err;
+      }
+    }
+    let info;
+    try {
+      info = Deno.lstatSync(path);
+    } catch (err) {
+      if (!(err instanceof Deno.errors.NotFound)) {
+        throw err;
+      }
+    }
+    if (info?.isSymlink) {
+      // a dangling link still leads to where a file would be created
+      return realPath(resolve(dirname(path), Deno.readLinkSync(path)), true);
+    }
+  }
+  const parent = dirname(path);
+  if (parent === path) {
+    return path;
+  }
+  return join(realPath(parent, true), basename(path));
+}
+
+/** Whether `path` is `root` or inside of it, comparing whole components so
+ * that names like "..foo" are allowed. */
+function isWithin(root: string, path: string): boolean {
+  const rel = relative(root, path);
+  return !isAbsolute(rel) && rel.split(sep)[0] !== "..";
+}
+
+const textEncoder = new TextEncoder();
+const textDecoder = new TextDecoder();
+
+type WASISyscall = (...args: (number | bigint)[]) => number;
+
+type WASIOptions = {
+  version?: "unstable" | "preview1";
+  args?: string[];
+  env?: Record<string, string>;
+  preopens?: Record<string, string>;
+  returnOnExit?: boolean;
+  stdin?: number;
+  stdout?: number;
+  stderr?: number;
+};
+
+export class WASI {
+  wasiImport: Record<string, WASISyscall>;
+
+  #version: string;
+  #args: string[];
+  #env: string[];
+  #returnOnExit: boolean;
+  #fds: (FileDescriptor | undefined)[];
+  #memory: WebAssembly.Memory | null = null;
+  #started = false;
+
+  constructor(options: WASIOptions = {}) {
+    validateObject(options, "options");
+
+    const { version = "preview1" } = options;
+    validateString(version, "options.version");
+    if (version !== "unstable" && version !== "preview1") {
+      throw new ERR_INVALID_ARG_VALUE("options.version", version);
+    }
+    this.#version = version;
+
+    const { args = [] } = options;
+    validateArray(args, "options.args");
+    this.#args = args.map(String);
+
+    const { env = {} } = options;
+    validateObject(env, "options.env");
+    this.#env = Object.entries(env).map(([key, value]) => `${key}=${value}`);
+
+    const { returnOnExit = true } = options;
+    validateBoolean(returnOnExit, "options.returnOnExit");
+    this.#returnOnExit = returnOnExit;
+
+    const { stdin = 0, stdout = 1, stderr = 2 } = options;
+    validateInt32(stdin, "options.stdin", 0);
+    validateInt32(stdout, "options.stdout", 0);
+    validateInt32(stderr, "options.stderr", 0);
+    this.#fds = [
+      { rid: stdin, type: FILETYPE_CHARACTER_DEVICE, flags: 0 },
+      { rid: stdout, type: FILETYPE_CHARACTER_DEVICE, flags: 0 },
+      { rid: stderr, type: FILETYPE_CHARACTER_DEVICE, flags: 0 },
+    ];
+
+    const { preopens = {} } = options;
+    validateObject(preopens, "options.preopens");
+    for (const [vpath, path] of Object.entries(preopens)) {
+      const root = resolve(String(path));
+      this.#fds.push({
+        type: FILETYPE_DIRECTORY,
+        flags: 0,
+        path: root,
+        vpath,
+        root,
+      });
+    }
+
+    this.wasiImport = this.#createImports();
+  }
+
+  getImportObject() {
+    const name = this.#version === "unstable"
+      ? "wasi_unstable"
+      : "wasi_snapshot_preview1";
+    return { [name]: this.wasiImport };
+  }
+
+  /** Runs the `_start()` export of a WASI command. Returns the exit code. */
+  start(instance: WebAssembly.Instance): number {
+    const { _start, _initialize } = this.#setup(instance);
+    validateFunction(_start, "instance.exports._start");
+    if (_initialize !== undefined) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports._initialize",
+        "undefined",
+        _initialize,
+      );
+    }
+    try {
+      (_start as () => void)();
+    } catch (err) {
+      if (err instanceof ExitStatus) {
+        return err.code;
+      }
+      throw err;
+    }
+    return 0;
+  }
+
+  /** Runs the `_initialize()` export of a WASI reactor, if there is one. */
+  initialize(instance: WebAssembly.Instance) {
+    const { _start, _initialize } = this.#setup(instance);
+    if (_start !== undefined) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports._start",
+        "undefined",
+        _start,
+      );
+    }
+    if (_initialize !== undefined) {
+      validateFunction(_initialize, "instance.exports._initialize");
+      (_initialize as () => void)();
+    }
+  }
+
+  #setup(instance: WebAssembly.Instance) {
+    validateObject(instance, "instance");
+    const { exports } = instance;
+    validateObject(exports, "instance.exports");
+    if (!(exports.memory instanceof WebAssembly.Memory)) {
+      throw new ERR_INVALID_ARG_TYPE(
+        "instance.exports.memory",
+        "WebAssembly.Memory",
+        exports.memory,
+      );
+    }
+    if (this.#started) {
+      throw new ERR_WASI_ALREADY_STARTED();
+    }
+    this.#started = true;
+    this.#memory = exports.memory;
+    return exports;
+  }
+
+  #view(): DataView {
+    return new DataView(this.#memory!.buffer);
+  }
+
+  #bytes(ptr: number, len: number): Uint8Array {
+    return new Uint8Array(this.#memory!.buffer, ptr, len);
+  }
+
+  #string(ptr: number, len: number): string {
+    return textDecoder.decode(this.#bytes(ptr, len));
+  }
+
+  #iovecs(iovsPtr: number, iovsLen: number): Uint8Array[] {
+    const view = this.#view();
+    const iovs = [];
+    for (let i = 0; i < iovsLen; i++) {
+      const ptr = view.getUint32(iovsPtr + i * 8, true);
+      const len = view.getUint32(iovsPtr + i * 8 + 4, true);
+      iovs.push(this.#bytes(ptr, len));
+    }
+    return iovs;
+  }
+
+  /** Resolves a path relative to a directory descriptor, making sure it
+   * doesn't escape the preopened directory it belongs to, including through
+   * symlinks. `followLast` is whether the operation follows a symlink in the
+   * last component. */
+  #resolvePath(
+    fd: number,
+    pathPtr: number,
+    pathLen: number,
+    followLast = true,
+  ): string {
+    const entry = this.#fds[fd];
+    if (!entry) {
+      throw ERRNO_BADF;
+    }
+    if (entry.type !== FILETYPE_DIRECTORY || !entry.path || !entry.root) {
+      throw ERRNO_NOTDIR;
+    }
+    const path = resolve(entry.path, this.#string(pathPtr, pathLen));
+    this.#checkWithinRoot(entry.root, realPath(path, followLast));
+    return path;
+  }
+
+  #checkWithinRoot(root: string, path: string) {
+    if (!isWithin(Deno.realPathSync(root), path)) {
+      throw ERRNO_NOTCAPABLE;
+    }
+  }
+
+  #entry(fd: number): FileDescriptor {
+    const entry = this.#fds[fd];
+    if (!entry) {
+      throw ERRNO_BADF;
+    }
+    return entry;
+  }
+
+  #writeBuffers(
+    data: Uint8Array[],
+    bufPtr: number,
+    countPtr: number,
+  ) {
+    const view = this.#view();
+    let bufOffset = bufPtr;
+    for (let i = 0; i < data.length; i++) {
+      view.setUint32(countPtr + i * 4, bufOffset, true);
+      this.#bytes(bufOffset, data[i].length).set(data[i]);
+      bufOffset += data[i].length;
+    }
+  }
+
+  #createImports(): Record<string, WASISyscall> {
+    // deno-lint-ignore no-explicit-any
+    const syscalls: Record<string, (...args: any[]) => number | void> = {
+      args_get: (argvPtr: number, argvBufPtr: number) => {
+        const args = this.#args.map((arg) => textEncoder.encode(`${arg}\0`));
+        this.#writeBuffers(args, argvBufPtr, argvPtr);
+      },
+
+      args_sizes_get: (argcPtr: number, argvBufSizePtr: number) => {
+        const view = this.#view();
+        view.setUint32(argcPtr, this.#args.length, true);
+        const size = this.#args.reduce(
+          (size, arg) => size + textEncoder.encode(arg).length + 1,
+          0,
+        );
+        view.setUint32(argvBufSizePtr, size, true);
+      },
+
+      environ_get: (environPtr: number, environBufPtr: number) => {
+        const env = this.#env.map((pair) => textEncoder.encode(`${pair}\0`));
+        this.#writeBuffers(env, environBufPtr, environPtr);
+      },
+
+      environ_sizes_get: (environcPtr: number, environBufSizePtr: number) => {
+        const view = this.#view();
+        view.setUint32(environcPtr, this.#env.length, true);
+        const size = this.#env.reduce(
+          (size, pair) => size + textEncoder.encode(pair).length + 1,
+          0,
+        );
+        view.setUint32(environBufSizePtr, size, true);
+      },
+
+      clock_res_get: (id: number, resolutionPtr: number) => {
+        const view = this.#view();
+        switch (id) {
+          case CLOCKID_REALTIME:
+            view.setBigUint64(resolutionPtr, 1_000_000n, true);
+            break;
+          case CLOCKID_MONOTONIC:
+          case CLOCKID_PROCESS_CPUTIME_ID:
+          case CLOCKID_THREAD_CPUTIME_ID:
+            view.setBigUint64(resolutionPtr, 1_000n, true);
+            break;
+          default:
+            return ERRNO_INVAL;
+        }
+      },
+
+      clock_time_get: (id: number, _precision: bigint, timePtr: number) => {
+        const view = this.#view();
+        switch (id) {
+          case CLOCKID_REALTIME:
+            view.setBigUint64(timePtr, BigInt(Date.now()) * 1_000_000n, true);
+            break;
+          case CLOCKID_MONOTONIC:
+          case CLOCKID_PROCESS_CPUTIME_ID:
+          case CLOCKID_THREAD_CPUTIME_ID:
+            view.setBigUint64(
+              timePtr,
+              BigInt(Math.trunc(performance.now() * 1e6)),
+              true,
+            );
+            break;
+          default:
+            return ERRNO_INVAL;
+        }
+      },
+
+      fd_advise: () => ERRNO_NOSYS,
+
+      fd_allocate: (fd: number, offset: bigint, len: bigint) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const { size } = Deno.fstatSync(entry.rid);
+        const end = Number(offset + len);
+        if (end > size) {
+          Deno.ftruncateSync(entry.rid, end);
+        }
+      },
+
+      fd_close: (fd: number) => {
+        const entry = this.#entry(fd);
+        if (entry.rid !== undefined && fd > 2) {
+          Deno.close(entry.rid);
+        }
+        delete this.#fds[fd];
+      },
+
+      fd_datasync: (fd: number) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        Deno.fdatasyncSync(entry.rid);
+      },
+
+      fd_fdstat_get: (fd: number, statPtr: number) => {
+        const entry = this.#entry(fd);
+        const view = this.#view();
+        view.setUint8(statPtr, entry.type);
+        view.setUint16(statPtr + 2, entry.flags, true);
+        view.setBigUint64(statPtr + 8, RIGHTS_ALL, true);
+        view.setBigUint64(statPtr + 16, RIGHTS_ALL, true);
+      },
+
+      fd_fdstat_set_flags: () => ERRNO_NOSYS,
+
+      fd_fdstat_set_rights: () => ERRNO_NOSYS,
+
+      fd_filestat_get: (fd: number, bufPtr: number) => {
+        const entry = this.#entry(fd);
+        let info;
+        if (entry.rid !== undefined) {
+          info = Deno.fstatSync(entry.rid);
+        } else if (entry.path !== undefined) {
+          info = Deno.statSync(entry.path);
+        } else {
+          return ERRNO_BADF;
+        }
+        writeFilestat(this.#view(), bufPtr, info);
+        if (entry.type === FILETYPE_CHARACTER_DEVICE) {
+          this.#view().setUint8(bufPtr + 16, FILETYPE_CHARACTER_DEVICE);
+        }
+      },
+
+      fd_filestat_set_size: (fd: number, size: bigint) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        Deno.ftruncateSync(entry.rid, Number(size));
+      },
+
+      fd_filestat_set_times: (
+        fd: number,
+        atim: bigint,
+        mtim: bigint,
+        flags: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const info = Deno.fstatSync(entry.rid);
+        const [atime, mtime] = resolveTimes(info, atim, mtim, flags);
+        Deno.futimeSync(entry.rid, atime, mtime);
+      },
+
+      fd_pread: (
+        fd: number,
+        iovsPtr: number,
+        iovsLen: number,
+        offset: bigint,
+        nreadPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const position = Deno.seekSync(entry.rid, 0, Deno.SeekMode.Current);
+        Deno.seekSync(entry.rid, Number(offset), Deno.SeekMode.Start);
+        let nread = 0;
+        try {
+          nread = this.#readInto(entry.rid, iovsPtr, iovsLen);
+        } finally {
+          Deno.seekSync(entry.rid, position, Deno.SeekMode.Start);
+        }
+        this.#view().setUint32(nreadPtr, nread, true);
+      },
+
+      fd_prestat_get: (fd: number, prestatPtr: number) => {
+        const entry = this.#fds[fd];
+        if (!entry || entry.vpath === undefined) {
+          return ERRNO_BADF;
+        }
+        const view = this.#view();
+        view.setUint8(prestatPtr, PREOPENTYPE_DIR);
+        view.setUint32(
+          prestatPtr + 4,
+          textEncoder.encode(entry.vpath).length,
+          true,
+        );
+      },
+
+      fd_prestat_dir_name: (fd: number, pathPtr: number, pathLen: number) => {
+        const entry = this.#fds[fd];
+        if (!entry || entry.vpath === undefined) {
+          return ERRNO_BADF;
+        }
+        const name = textEncoder.encode(entry.vpath);
+        this.#bytes(pathPtr, pathLen).set(name.subarray(0, pathLen));
+      },
+
+      fd_pwrite: (
+        fd: number,
+        iovsPtr: number,
+        iovsLen: number,
+        offset: bigint,
+        nwrittenPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const position = Deno.seekSync(entry.rid, 0, Deno.SeekMode.Current);
+        Deno.seekSync(entry.rid, Number(offset), Deno.SeekMode.Start);
+        let nwritten = 0;
+        try {
+          nwritten = this.#writeFrom(entry.rid, iovsPtr, iovsLen);
+        } finally {
+          Deno.seekSync(entry.rid, position, Deno.SeekMode.Start);
+        }
+        this.#view().setUint32(nwrittenPtr, nwritten, true);
+      },
+
+      fd_read: (
+        fd: number,
+        iovsPtr: number,
+        iovsLen: number,
+        nreadPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const nread = this.#readInto(entry.rid, iovsPtr, iovsLen);
+        this.#view().setUint32(nreadPtr, nread, true);
+      },
+
+      fd_readdir: (
+        fd: number,
+        bufPtr: number,
+        bufLen: number,
+        cookie: bigint,
+        bufUsedPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.type !== FILETYPE_DIRECTORY || entry.path === undefined) {
+          return ERRNO_NOTDIR;
+        }
+        if (cookie === 0n || entry.entries === undefined) {
+          entry.entries = [...Deno.readDirSync(entry.path)];
+        }
+        const view = this.#view();
+        let used = 0;
+        for (let i = Number(cookie); i < entry.entries.length; i++) {
+          const dirEntry = entry.entries[i];
+          const name = textEncoder.encode(dirEntry.name);
+          const header = new DataView(new ArrayBuffer(24));
+          header.setBigUint64(0, BigInt(i + 1), true);
+          header.setBigUint64(8, 0n, true);
+          header.setUint32(16, name.length, true);
+          header.setUint8(
+            20,
+            dirEntry.isDirectory
+              ? FILETYPE_DIRECTORY
+              : dirEntry.isSymlink
+              ? FILETYPE_SYMBOLIC_LINK
+              : dirEntry.isFile
+              ? FILETYPE_REGULAR_FILE
+              : FILETYPE_UNKNOWN,
+          );
+          const record = new Uint8Array(24 + name.length);
+          record.set(new Uint8Array(header.buffer));
+          record.set(name, 24);
+          // the last entry may be truncated, which signals the guest that
+          // the buffer was filled
+          const len = Math.min(record.length, bufLen - used);
+          this.#bytes(bufPtr + used, len).set(record.subarray(0, len));
+          used += len;
+          if (used === bufLen) {
+            break;
+          }
+        }
+        view.setUint32(bufUsedPtr, used, true);
+      },
+
+      fd_renumber: (fd: number, to: number) => {
+        const entry = this.#entry(fd);
+        const target = this.#entry(to);
+        if (target.rid !== undefined && to > 2) {
+          Deno.close(target.rid);
+        }
+        this.#fds[to] = entry;
+        delete this.#fds[fd];
+      },
+
+      fd_seek: (
+        fd: number,
+        offset: bigint,
+        whence: number,
+        newOffsetPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        if (entry.type === FILETYPE_CHARACTER_DEVICE) {
+          return ERRNO_SPIPE;
+        }
+        let mode;
+        switch (whence) {
+          case WHENCE_SET:
+            mode = Deno.SeekMode.Start;
+            break;
+          case WHENCE_CUR:
+            mode = Deno.SeekMode.Current;
+            break;
+          case WHENCE_END:
+            mode = Deno.SeekMode.End;
+            break;
+          default:
+            return ERRNO_INVAL;
+        }
+        const position = Deno.seekSync(entry.rid, Number(offset), mode);
+        this.#view().setBigUint64(newOffsetPtr, BigInt(position), true);
+      },
+
+      fd_sync: (fd: number) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        Deno.fsyncSync(entry.rid);
+      },
+
+      fd_tell: (fd: number, offsetPtr: number) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        if (entry.type === FILETYPE_CHARACTER_DEVICE) {
+          return ERRNO_SPIPE;
+        }
+        const position = Deno.seekSync(entry.rid, 0, Deno.SeekMode.Current);
+        this.#view().setBigUint64(offsetPtr, BigInt(position), true);
+      },
+
+      fd_write: (
+        fd: number,
+        iovsPtr: number,
+        iovsLen: number,
+        nwrittenPtr: number,
+      ) => {
+        const entry = this.#entry(fd);
+        if (entry.rid === undefined) {
+          return ERRNO_BADF;
+        }
+        const nwritten = this.#writeFrom(entry.rid, iovsPtr, iovsLen);
+        this.#view().setUint32(nwrittenPtr, nwritten, true);
+      },
+
+      path_create_directory: (fd: number, pathPtr: number, pathLen: number) => {
+        Deno.mkdirSync(this.#resolvePath(fd, pathPtr, pathLen, false));
+      },
+
+      path_filestat_get: (
+        fd: number,
+        flags: number,
+        pathPtr: number,
+        pathLen: number,
+        bufPtr: number,
+      ) => {
+        const follow = (flags & LOOKUPFLAGS_SYMLINK_FOLLOW) !== 0;
+        const path = this.#resolvePath(fd, pathPtr, pathLen, follow);
+        const info = follow ? Deno.statSync(path) : Deno.lstatSync(path);
+        writeFilestat(this.#view(), bufPtr, info);
+      },
+
+      path_filestat_set_times: (
+        fd: number,
+        _flags: number,
+        pathPtr: number,
+        pathLen: number,
+        atim: bigint,
+        mtim: bigint,
+        fstFlags: number,
+      ) => {
+        const path = this.#resolvePath(fd, pathPtr, pathLen);
+        const info = Deno.statSync(path);
+        const [atime, mtime] = resolveTimes(info, atim, mtim, fstFlags);
+        Deno.utimeSync(path, atime, mtime);
+      },
+
+      path_link: (
+        oldFd: number,
+        _oldFlags: number,
+        oldPathPtr: number,
+        oldPathLen: number,
+        newFd: number,
+        newPathPtr: number,
+        newPathLen: number,
+      ) => {
+        Deno.linkSync(
+          this.#resolvePath(oldFd, oldPathPtr, oldPathLen),
+          this.#resolvePath(newFd, newPathPtr, newPathLen, false),
+        );
+      },
+
+      path_open: (
+        fd: number,
+        _dirflags: number,
+        pathPtr: number,
+        pathLen: number,
+        oflags: number,
+        rightsBase: bigint,
+        _rightsInheriting: bigint,
+        fdflags: number,
+        openedFdPtr: number,
+      ) => {
+        const root = this.#entry(fd).root;
+        const path = this.#resolvePath(fd, pathPtr, pathLen);
+
+        let info;
+        try {
+          info = Deno.statSync(path);
+        } catch (err) {
+          if (!(err instanceof Deno.errors.NotFound)) {
+            throw err;
+          }
+        }
+
+        if ((oflags & OFLAGS_DIRECTORY) !== 0 || info?.isDirectory) {
+          if (info === undefined) {
+            return ERRNO_NOENT;
+          }
+          if (!info.isDirectory) {
+            return ERRNO_NOTDIR;
+          }
+          const opened = this.#fds.push({
+            type: FILETYPE_DIRECTORY,
+            flags: fdflags,
+            path,
+            root,
+          }) - 1;
+          this.#view().setUint32(openedFdPtr, opened, true);
+          return;
+        }
+
+        const append = (fdflags & FDFLAGS_APPEND) !== 0;
+        const write = (rightsBase & RIGHTS_FD_WRITE) !== 0n ||
+          (oflags & (OFLAGS_CREAT | OFLAGS_TRUNC)) !== 0 || append;
+        const file = Deno.openSync(path, {
+          read: (rightsBase & RIGHTS_FD_READ) !== 0n || !write,
+          write: write && !append,
+          append,
+          create: (oflags & OFLAGS_CREAT) !== 0,
+          createNew: (oflags & OFLAGS_EXCL) !== 0,
+          truncate: (oflags & OFLAGS_TRUNC) !== 0,
+        });
+        const opened = this.#fds.push({
+          rid: file.rid,
+          type: FILETYPE_REGULAR_FILE,
+          flags: fdflags,
+          path,
+          root,
+        }) - 1;
+        this.#view().setUint32(openedFdPtr, opened, true);
+      },
+
+      path_readlink: (
+        fd: number,
+        pathPtr: number,
+        pathLen: number,
+        bufPtr: number,
+        bufLen: number,
+        bufUsedPtr: number,
+      ) => {
+        const path = this.#resolvePath(fd, pathPtr, pathLen, false);
+        const target = textEncoder.encode(Deno.readLinkSync(path));
+        const len = Math.min(target.length, bufLen);
+        this.#bytes(bufPtr, len).set(target.subarray(0, len));
+        this.#view().setUint32(bufUsedPtr, len, true);
+      },
+
+      path_remove_directory: (fd: number, pathPtr: number, pathLen: number) => {
+        const path = this.#resolvePath(fd, pathPtr, pathLen, false);
+        if (!Deno.lstatSync(path).isDirectory) {
+          return ERRNO_NOTDIR;
+        }
+        Deno.removeSync(path);
+      },
+
+      path_rename: (
+        fd: number,
+        oldPathPtr: number,
+        oldPathLen: number,
+        newFd: number,
+        newPathPtr: number,
+        newPathLen: number,
+      ) => {
+        Deno.renameSync(
+          this.#resolvePath(fd, oldPathPtr, oldPathLen, false),
+          this.#resolvePath(newFd, newPathPtr, newPathLen, false),
+        );
+      },
+
+      path_symlink: (
+        oldPathPtr: number,
+        oldPathLen: number,
+        fd: number,
+        newPathPtr: number,
+        newPathLen: number,
+      ) => {
+        const target = this.#string(oldPathPtr, oldPathLen);
+        const path = this.#resolvePath(fd, newPathPtr, newPathLen, false);
+        // the link must not point outside of the preopened directory
+        this.#checkWithinRoot(
+          this.#entry(fd).root!,
+          realPath(resolve(dirname(path), target), true),
+        );
+        Deno.symlinkSync(target, path);
+      },
+
+      path_unlink_file: (fd: number, pathPtr: number, pathLen: number) => {
+        const path = this.#resolvePath(fd, pathPtr, pathLen, false);
+        if (Deno.lstatSync(path).isDirectory) {
+          return ERRNO_ISDIR;
+        }
+        Deno.removeSync(path);
+      },
+
+      poll_oneoff: () => ERRNO_NOSYS,
+
+      proc_exit: (code: number) => {
+        if (this.#returnOnExit) {
+          throw new ExitStatus(code);
+        }
+        Deno.exit(code);
+      },
+
+      proc_raise: () => ERRNO_NOSYS,
+
+      sched_yield: () => {},
+
+      random_get: (bufPtr: number, bufLen: number) => {
+        // getRandomValues() is limited to 65536 bytes per call
+        for (let offset = 0; offset < bufLen; offset += 65536) {
+          crypto.getRandomValues(
+            this.#bytes(bufPtr + offset, Math.min(65536, bufLen - offset)),
+          );
+        }
+      },
+
+      sock_accept: () => ERRNO_NOSYS,
+      sock_recv: () => ERRNO_NOSYS,
+      sock_send: () => ERRNO_NOSYS,
+      sock_shutdown: () => ERRNO_NOSYS,
+    };
+
+    const imports: Record<string, WASISyscall> = {};
+    for (const [name, syscall] of Object.entries(syscalls)) {
+      imports[name] = (...args) => {
+        if (this.#memory === null) {
+          return ERRNO_INVAL;
+        }
+        try {
+          return syscall(...args) ?? ERRNO_SUCCESS;
+        } catch (err) {
+          if (err instanceof ExitStatus) {
+            throw err;
+          }
+          if (typeof err === "number") {
+            return err;
+          }
+          return errnoFromError(err);
+        }
+      };
+    }
+    return imports;
+  }
+
+  #readInto(rid: number, iovsPtr: number, iovsLen: number): number {
+    let nread = 0;
+    for (const iov of this.#iovecs(iovsPtr, iovsLen)) {
+      const n = io.readSync(rid, iov);
+      if (n === null || n === 0) {
+        break;
+      }
+      nread += n;
+      if (n < iov.length) {
+        break;
+      }
+    }
+    return nread;
+  }
+
+  #writeFrom(rid: number, iovsPtr: number, iovsLen: number): number {
+    let nwritten = 0;
+    for (const iov of this.#iovecs(iovsPtr, iovsLen)) {
+      let offset = 0;
+      while (offset < iov.length) {
+        offset += io.writeSync(rid, iov.subarray(offset));
+      }
+      nwritten += iov.length;
+    }
+    if (nwritten > 2 ** 32 - 1) {
+      throw ERRNO_2BIG;
+    }
+    return nwritten;
   }
 }
 
-export const WASI = Context;
+function resolveTimes(
+  info: Deno.FileInfo,
+  atim: bigint,
+  mtim: bigint,
+  flags: number,
+): [Date, Date] {
+  const now = new Date();
+  let atime = info.atime ?? now;
+  let mtime = info.mtime ?? now;
+  if ((flags & FSTFLAGS_ATIM) !== 0) {
+    atime = new Date(Number(atim / 1_000_000n));
+  } else if ((flags & FSTFLAGS_ATIM_NOW) !== 0) {
+    atime = now;
+  }
+  if ((flags & FSTFLAGS_MTIM) !== 0) {
+    mtime = new Date(Number(mtim / 1_000_000n));
+  } else if ((flags & FSTFLAGS_MTIM_NOW) !== 0) {
+    mtime = now;
+  }
+  return [atime, mtime];
+}
 
 export default { WASI };
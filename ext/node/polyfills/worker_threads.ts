@@ -5,7 +5,11 @@ import { resolve, toFileUrl } from "ext:deno_node/path.ts";
 import { notImplemented } from "ext:deno_node/_utils.ts";
 import { EventEmitter, once } from "ext:deno_node/events.ts";
 import { BroadcastChannel } from "ext:deno_broadcast_channel/01_broadcast_channel.js";
-import { MessageChannel, MessagePort } from "ext:deno_web/13_message_port.js";
+import {
+  MessageChannel,
+  MessagePort,
+  refMessagePort,
+} from "ext:deno_web/13_message_port.js";
 
 let environmentData = new Map();
 let threads = 0;
@@ -76,10 +80,10 @@ class _Worker extends EventEmitter {
       "messageerror",
       (event) => this.emit("messageerror", event.data),
     );
-    handle.addEventListener(
-      "message",
-      (event) => this.emit("message", event.data),
-    );
+    handle.addEventListener("message", (event) => {
+      webMessagePortsToNodeMessagePorts(event);
+      this.emit("message", event.data);
+    });
     handle.postMessage({
       environmentData,
       threadId: (this.threadId = ++threads),
@@ -151,6 +155,8 @@ globalThis.__bootstrap.internals.__initWorkerThreads = () => {
     const listeners = new WeakMap<(...args: any[]) => void, (ev: any) => any>();
 
     parentPort = self as ParentPort;
+    // added before any listener, so that they receive node ports
+    parentPort.addEventListener("message", webMessagePortsToNodeMessagePorts);
 
     const initPromise = once(
       parentPort,
@@ -216,6 +222,132 @@ globalThis.__bootstrap.internals.__initWorkerThreads = () => {
   }
 };
 
+// Node's MessagePort is a NodeEventTarget, whose listeners are called with the
+// message instead of the event. The ports are the ones of `ext:deno_web`, so
+// that they can be transferred to web workers, and the node methods are added
+// to the ports that are created or received by this module.
+const kNodeListeners = Symbol("kNodeListeners");
+
+type NodeListener = (...args: unknown[]) => void;
+type NodeMessagePort = MessagePort & {
+  [kNodeListeners]?: Map<string, Map<NodeListener, (ev: Event) => void>>;
+};
+
+function getNodeListeners(port: NodeMessagePort, name: string) {
+  const listenersByName = port[kNodeListeners] ??= new Map();
+  let listeners = listenersByName.get(name);
+  if (!listeners) {
+    listeners = new Map();
+    listenersByName.set(name, listeners);
+  }
+  return listeners;
+}
+
+function addNodeListener(
+  port: NodeMessagePort,
+  name: string,
+  listener: NodeListener,
+  isOnce: boolean,
+) {
+  const listeners = getNodeListeners(port, name);
+  if (listeners.has(listener)) {
+    return;
+  }
+  const wrapped = (ev: Event) => {
+    if (isOnce) {
+      listeners.delete(listener);
+    }
+    if (name === "message" || name === "messageerror") {
+      listener((ev as MessageEvent).data);
+    } else {
+      listener();
+    }
+  };
+  listeners.set(listener, wrapped);
+  port.addEventListener(name, wrapped, { once: isOnce });
+  if (name === "message") {
+    port.start();
+  }
+}
+
+function portOn(
+  this: NodeMessagePort,
+  name: string,
+  listener: NodeListener,
+) {
+  addNodeListener(this, name, listener, false);
+  return this;
+}
+
+function portOnce(
+  this: NodeMessagePort,
+  name: string,
+  listener: NodeListener,
+) {
+  addNodeListener(this, name, listener, true);
+  return this;
+}
+
+function portOff(
+  this: NodeMessagePort,
+  name: string,
+  listener: NodeListener,
+) {
+  const listeners = getNodeListeners(this, name);
+  const wrapped = listeners.get(listener);
+  if (wrapped) {
+    listeners.delete(listener);
+    this.removeEventListener(name, wrapped);
+  }
+  return this;
+}
+
+function portRef(this: MessagePort) {
+  refMessagePort(this, true);
+}
+
+function portUnref(this: MessagePort) {
+  refMessagePort(this, false);
+}
+
+const nodeMessagePortMethods = {
+  on: portOn,
+  addListener: portOn,
+  once: portOnce,
+  off: portOff,
+  removeListener: portOff,
+  ref: portRef,
+  unref: portUnref,
+};
+
+function webMessagePortToNodeMessagePort(port: MessagePort) {
+  for (const [name, value] of Object.entries(nodeMessagePortMethods)) {
+    Object.defineProperty(port, name, {
+      value,
+      configurable: true,
+      writable: true,
+    });
+  }
+  return port;
+}
+
+function webMessagePortsToNodeMessagePorts(event: Event) {
+  for (const port of (event as MessageEvent).ports) {
+    webMessagePortToNodeMessagePort(port);
+  }
+}
+
+class NodeMessageChannel {
+  readonly port1: MessagePort;
+  readonly port2: MessagePort;
+
+  constructor() {
+    const { port1, port2 } = new MessageChannel();
+    this.port1 = webMessagePortToNodeMessagePort(port1);
+    this.port2 = webMessagePortToNodeMessagePort(port2);
+  }
+}
+
 export function getEnvironmentData(key: unknown) {
   return environmentData.get(key);
 }
@@ -241,8 +373,8 @@ export function receiveMessageOnPort() {
 export {
   _Worker as Worker,
   BroadcastChannel,
-  MessageChannel,
   MessagePort,
+  NodeMessageChannel as MessageChannel,
   parentPort,
   threadId,
   workerData,
@@ -253,7 +385,7 @@ const defaultExport = {
   moveMessagePortToContext,
   receiveMessageOnPort,
   MessagePort,
-  MessageChannel,
+  MessageChannel: NodeMessageChannel,
   BroadcastChannel,
   Worker: _Worker,
   getEnvironmentData,
@@ -18,6 +18,7 @@ use deno_semver::npm::NpmPackageNvReference;
 use deno_semver::npm::NpmPackageReqReference;
 
 use crate::errors;
+use crate::package_json::BrowserReplacement;
 use crate::AllowAllNodePermissions;
 use crate::NodePermissions;
 use crate::NpmResolverRc;
@@ -27,6 +28,10 @@ use crate::PathClean;
 pub static DEFAULT_CONDITIONS: &[&str] = &["deno", "node", "import"];
 pub static REQUIRE_CONDITIONS: &[&str] = &["require", "node"];
 
+/// What modules mapped to `false` in the "browser" field resolve to.
+pub const EMPTY_MODULE_SPECIFIER: &str =
+  "data:application/javascript,export%20default%20%7B%7D%3B";
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum NodeModuleKind {
   Esm,
@@ -111,11 +116,38 @@ pub type NodeResolverRc = deno_fs::sync::MaybeArc<NodeResolver>;
 pub struct NodeResolver {
   fs: FileSystemRc,
   npm_resolver: NpmResolverRc,
+  /// User provided conditions (ex. `--conditions=development`) that are
+  /// matched in addition to the default ones.
+  conditions: Vec<String>,
+  /// Whether the "browser" field of packages is honored, which is opted
+  /// into with the `browser` condition.
+  browser_field: bool,
 }
 
 impl NodeResolver {
-  pub fn new(fs: FileSystemRc, npm_resolver: NpmResolverRc) -> Self {
-    Self { fs, npm_resolver }
+  pub fn new(
+    fs: FileSystemRc,
+    npm_resolver: NpmResolverRc,
+    conditions: Vec<String>,
+  ) -> Self {
+    let browser_field = conditions.iter().any(|c| c == "browser");
+    Self {
+      fs,
+      npm_resolver,
+      conditions,
+      browser_field,
+    }
+  }
+
+  /// Returns the given default conditions followed by the user provided ones.
+  pub fn conditions(&self, defaults: &[&'static str]) -> Vec<&str> {
+    let mut conditions: Vec<&str> = defaults.to_vec();
+    for condition in &self.conditions {
+      if !conditions.contains(&condition.as_str()) {
+        conditions.push(condition);
+      }
+    }
+    conditions
   }
 
   pub fn in_npm_package(&self, specifier: &ModuleSpecifier) -> bool {
@@ -134,7 +166,30 @@ impl NodeResolver {
     // Note: if we are here, then the referrer is an esm module
     // TODO(bartlomieju): skipped "policy" part as we don't plan to support it
 
-    if crate::is_builtin_node_module(specifier) {
+    if let Some(replacement) = self.browser_replacement_for_module(
+      specifier,
+      referrer,
+      mode,
+      permissions,
+    )? {
+      let conditions = self.conditions(DEFAULT_CONDITIONS);
+      return match self.resolve_browser_replacement(
+        replacement,
+        referrer,
+        &conditions,
+        permissions,
+      )? {
+        Some(url) if url.as_str() == EMPTY_MODULE_SPECIFIER => {
+          Ok(Some(NodeResolution::Esm(url)))
+        }
+        Some(url) => Ok(Some(self.url_to_node_resolution(url)?)),
+        None => Ok(None),
+      };
+    }
+
+    if crate::is_builtin_node_module(specifier)
+      && !crate::requires_node_scheme(specifier)
+    {
       return Ok(Some(NodeResolution::BuiltIn(specifier.to_string())));
     }
 
@@ -168,7 +223,7 @@ impl NodeResolver {
     let url = self.module_resolve(
       specifier,
       referrer,
-      DEFAULT_CONDITIONS,
+      &self.conditions(DEFAULT_CONDITIONS),
       mode,
       permissions,
     )?;
@@ -176,6 +231,9 @@ impl NodeResolver {
       Some(url) => url,
       None => return Ok(None),
     };
+    if url.as_str() == EMPTY_MODULE_SPECIFIER {
+      return Ok(Some(NodeResolution::Esm(url)));
+    }
     let url = match mode {
       NodeResolutionMode::Execution => url,
       NodeResolutionMode::Types => {
@@ -217,6 +275,13 @@ impl NodeResolver {
         declaration_path.map(|declaration_path| {
           ModuleSpecifier::from_file_path(declaration_path).unwrap()
         })
+      } else if self.browser_field {
+        return self.browser_resolve_path(
+          to_file_path(&resolved_specifier),
+          referrer,
+          conditions,
+          permissions,
+        );
       } else {
         Some(resolved_specifier)
       }
@@ -253,6 +318,81 @@ impl NodeResolver {
     })
   }
 
+  /// Looks up a bare specifier in the "browser" field of the referrer's
+  /// package, which also allows replacing built-in modules.
+  fn browser_replacement_for_module(
+    &self,
+    specifier: &str,
+    referrer: &ModuleSpecifier,
+    mode: NodeResolutionMode,
+    permissions: &dyn NodePermissions,
+  ) -> Result<Option<BrowserReplacement>, AnyError> {
+    if !self.browser_field || mode.is_types() || referrer.scheme() != "file" {
+      return Ok(None);
+    }
+    let package_json =
+      match self.get_closest_package_json(referrer, permissions) {
+        Ok(package_json) => package_json,
+        Err(_) => return Ok(None),
+      };
+    Ok(package_json.browser_replacement_for_module(specifier))
+  }
+
+  /// Applies the "browser" field of the package that contains `path`.
+  fn browser_resolve_path(
+    &self,
+    path: PathBuf,
+    referrer: &ModuleSpecifier,
+    conditions: &[&str],
+    permissions: &dyn NodePermissions,
+  ) -> Result<Option<ModuleSpecifier>, AnyError> {
+    let specifier = ModuleSpecifier::from_file_path(&path).unwrap();
+    let replacement = self
+      .get_closest_package_json(&specifier, permissions)
+      .ok()
+      .and_then(|package_json| {
+        package_json.browser_replacement_for_path(&path)
+      });
+    match replacement {
+      Some(replacement) => self.resolve_browser_replacement(
+        replacement,
+        referrer,
+        conditions,
+        permissions,
+      ),
+      None => Ok(Some(self.finalize_resolution(specifier, referrer)?)),
+    }
+  }
+
+  fn resolve_browser_replacement(
+    &self,
+    replacement: BrowserReplacement,
+    referrer: &ModuleSpecifier,
+    conditions: &[&str],
+    permissions: &dyn NodePermissions,
+  ) -> Result<Option<ModuleSpecifier>, AnyError> {
+    let url = match replacement {
+      BrowserReplacement::Empty => {
+        return Ok(Some(ModuleSpecifier::parse(EMPTY_MODULE_SPECIFIER)?));
+      }
+      BrowserReplacement::Path(path) => {
+        ModuleSpecifier::from_file_path(path).unwrap()
+      }
+      BrowserReplacement::Package(specifier) => match self.package_resolve(
+        &specifier,
+        referrer,
+        NodeModuleKind::Esm,
+        conditions,
+        NodeResolutionMode::Execution,
+        permissions,
+      )? {
+        Some(path) => ModuleSpecifier::from_file_path(path).unwrap(),
+        None => return Ok(None),
+      },
+    };
+    Ok(Some(self.finalize_resolution(url, referrer)?))
+  }
+
   fn finalize_resolution(
     &self,
     resolved: ModuleSpecifier,
@@ -335,7 +475,7 @@ impl NodeResolver {
           .unwrap_or_else(|| ".".to_string()),
         &package_folder,
         node_module_kind,
-        DEFAULT_CONDITIONS,
+        &self.conditions(DEFAULT_CONDITIONS),
         mode,
         permissions,
       )
@@ -575,35 +715,14 @@ impl NodeResolver {
             return Ok(resolved);
           }
         } else {
-          let mut best_match = "";
-          let mut best_match_subpath = None;
-          for key in imports.keys() {
-            let pattern_index = key.find('*');
-            if let Some(pattern_index) = pattern_index {
-              let key_sub = &key[0..=pattern_index];
-              if name.starts_with(key_sub) {
-                let pattern_trailer = &key[pattern_index + 1..];
-                if name.len() > key.len()
-                  && name.ends_with(&pattern_trailer)
-                  && pattern_key_compare(best_match, key) == 1
-                  && key.rfind('*') == Some(pattern_index)
-                {
-                  best_match = key;
-                  best_match_subpath = Some(
-                    name[pattern_index..=(name.len() - pattern_trailer.len())]
-                      .to_string(),
-                  );
-                }
-              }
-            }
-          }
-
-          if !best_match.is_empty() {
+          if let Some((best_match, best_match_subpath)) =
+            find_best_pattern_match(name, imports.keys())
+          {
             let target = imports.get(best_match).unwrap().to_owned();
             let maybe_resolved = self.resolve_package_target(
               package_json_path.as_ref().unwrap(),
               target,
-              best_match_subpath.unwrap(),
+              best_match_subpath,
               best_match.to_string(),
               referrer,
               referrer_kind,
@@ -661,7 +780,7 @@ impl NodeResolver {
         if !is_url {
           let export_target = if pattern {
             pattern_re
-              .replace(&target, |_caps: &regex::Captures| subpath.clone())
+              .replace_all(&target, |_caps: &regex::Captures| subpath.clone())
               .to_string()
           } else {
             format!("{target}{subpath}")
@@ -729,7 +848,7 @@ impl NodeResolver {
     if pattern {
       let resolved_path_str = resolved_path.to_string_lossy();
       let replaced = pattern_re
-        .replace(&resolved_path_str, |_caps: &regex::Captures| {
+        .replace_all(&resolved_path_str, |_caps: &regex::Captures| {
           subpath.clone()
         });
       return Ok(PathBuf::from(replaced.to_string()));
@@ -904,46 +1023,16 @@ impl NodeResolver {
       return Ok(resolved.unwrap());
     }
 
-    let mut best_match = "";
-    let mut best_match_subpath = None;
-    for key in package_exports.keys() {
-      let pattern_index = key.find('*');
-      if let Some(pattern_index) = pattern_index {
-        let key_sub = &key[0..pattern_index];
-        if package_subpath.starts_with(key_sub) {
-          // When this reaches EOL, this can throw at the top of the whole function:
-          //
-          // if (StringPrototypeEndsWith(packageSubpath, '/'))
-          //   throwInvalidSubpath(packageSubpath)
-          //
-          // To match "imports" and the spec.
-          if package_subpath.ends_with('/') {
-            // TODO(bartlomieju):
-            // emitTrailingSlashPatternDeprecation();
-          }
-          let pattern_trailer = &key[pattern_index + 1..];
-          if package_subpath.len() >= key.len()
-            && package_subpath.ends_with(&pattern_trailer)
-            && pattern_key_compare(best_match, key) == 1
-            && key.rfind('*') == Some(pattern_index)
-          {
-            best_match = key;
-            best_match_subpath = Some(
-              package_subpath[pattern_index
-                ..(package_subpath.len() - pattern_trailer.len())]
-                .to_string(),
-            );
-          }
-        }
-      }
-    }
-
-    if !best_match.is_empty() {
+    // TODO(bartlomieju): emitTrailingSlashPatternDeprecation() when the
+    // subpath ends with a slash
+    if let Some((best_match, best_match_subpath)) =
+      find_best_pattern_match(&package_subpath, package_exports.keys())
+    {
       let target = package_exports.get(best_match).unwrap().to_owned();
       let maybe_resolved = self.resolve_package_target(
         package_json_path,
         target,
-        best_match_subpath.unwrap(),
+        best_match_subpath,
         best_match.to_string(),
         referrer,
         referrer_kind,
@@ -1045,6 +1134,13 @@ impl NodeResolver {
     }
 
     let file_path = package_json.path.parent().unwrap().join(&package_subpath);
+    if self.browser_field && !mode.is_types() {
+      if let Some(BrowserReplacement::Path(path)) =
+        package_json.browser_replacement_for_path(&file_path.clean())
+      {
+        return Ok(Some(path));
+      }
+    }
 
     if mode.is_types() {
       let maybe_declaration_path =
@@ -1140,6 +1236,15 @@ impl NodeResolver {
         }
       }
     } else {
+      if self.browser_field {
+        if let Some(main) = package_json.browser_main() {
+          for guess in [main.clone(), with_known_extension(&main, "js")] {
+            if self.fs.is_file(&guess) {
+              return Ok(Some(guess));
+            }
+          }
+        }
+      }
       package_json.main(referrer_kind)
     };
 
@@ -1502,6 +1607,37 @@ fn pattern_key_compare(a: &str, b: &str) -> i32 {
   0
 }
 
+/// Finds the `*` pattern key of an "exports" or "imports" map that best
+/// matches the provided name. Returns the key along with the part of the
+/// name that the `*` stands for.
+fn find_best_pattern_match<'a>(
+  name: &str,
+  keys: impl Iterator<Item = &'a String>,
+) -> Option<(&'a str, String)> {
+  let mut best_match: Option<(&'a str, String)> = None;
+  for key in keys {
+    let Some(pattern_index) = key.find('*') else {
+      continue;
+    };
+    if key.rfind('*') != Some(pattern_index) {
+      continue;
+    }
+    let key_prefix = &key[..pattern_index];
+    let pattern_trailer = &key[pattern_index + 1..];
+    let best_key = best_match.as_ref().map(|(key, _)| *key).unwrap_or("");
+    if name != key_prefix
+      && name.starts_with(key_prefix)
+      && name.len() >= key.len()
+      && name.ends_with(pattern_trailer)
+      && pattern_key_compare(best_key, key) == 1
+    {
+      let subpath = &name[pattern_index..name.len() - pattern_trailer.len()];
+      best_match = Some((key, subpath.to_string()));
+    }
+  }
+  best_match
+}
+
 #[cfg(test)]
 mod tests {
   use deno_core::serde_json::json;
@@ -1643,4 +1779,174 @@ mod tests {
       assert_eq!(actual.to_string_lossy(), *expected);
     }
   }
+
+  #[test]
+  fn test_find_best_pattern_match() {
+    let keys = [
+      "#exact".to_string(),
+      "#internal/*".to_string(),
+      "#internal/special/*".to_string(),
+      "#styles/*.css".to_string(),
+      "#multiple/*/*".to_string(),
+    ];
+    let find = |name: &str| find_best_pattern_match(name, keys.iter());
+
+    assert_eq!(find("#exact"), None);
+    assert_eq!(find("#internal/a/b"), Some(("#internal/*", "a/b".into())));
+    assert_eq!(
+      find("#internal/special/a"),
+      Some(("#internal/special/*", "a".into()))
+    );
+    assert_eq!(
+      find("#styles/main.css"),
+      Some(("#styles/*.css", "main".into()))
+    );
+    // the pattern needs to match at least one character
+    assert_eq!(find("#internal/"), None);
+    assert_eq!(find("#styles/.css"), None);
+    assert_eq!(find("#styles/main.js"), None);
+    // only a single `*` is allowed in a key
+    assert_eq!(find("#multiple/a/b"), None);
+  }
+
+  /// Resolves every path to a single package, without any npm packages
+  /// that it depends on.
+  #[derive(Debug)]
+  struct TestNpmResolver(PathBuf);
+
+  impl crate::NpmResolver for TestNpmResolver {
+    fn resolve_package_folder_from_package(
+      &self,
+      specifier: &str,
+      _referrer: &ModuleSpecifier,
+      _mode: NodeResolutionMode,
+    ) -> Result<PathBuf, AnyError> {
+      Err(generic_error(format!(
+        "Could not find package '{specifier}'"
+      )))
+    }
+
+    fn resolve_package_folder_from_path(
+      &self,
+      _path: &Path,
+    ) -> Result<PathBuf, AnyError> {
+      Ok(self.0.clone())
+    }
+
+    fn resolve_package_folder_from_deno_module(
+      &self,
+      pkg_nv: &NpmPackageNv,
+    ) -> Result<PathBuf, AnyError> {
+      Err(generic_error(format!("Could not find package '{pkg_nv}'")))
+    }
+
+    fn resolve_pkg_id_from_pkg_req(
+      &self,
+      req: &deno_semver::npm::NpmPackageReq,
+    ) -> Result<
+      deno_npm::NpmPackageId,
+      deno_npm::resolution::PackageReqNotFoundError,
+    > {
+      Err(deno_npm::resolution::PackageReqNotFoundError(req.clone()))
+    }
+
+    fn resolve_nv_ref_from_pkg_req_ref(
+      &self,
+      req_ref: &NpmPackageReqReference,
+    ) -> Result<
+      NpmPackageNvReference,
+      deno_npm::resolution::PackageReqNotFoundError,
+    > {
+      Err(deno_npm::resolution::PackageReqNotFoundError(
+        req_ref.req.clone(),
+      ))
+    }
+
+    fn in_npm_package(&self, _specifier: &ModuleSpecifier) -> bool {
+      true
+    }
+
+    fn ensure_read_permission(
+      &self,
+      _permissions: &dyn NodePermissions,
+      _path: &Path,
+    ) -> Result<(), AnyError> {
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn test_package_imports_resolve() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let root = temp_dir.path().to_path_buf();
+    std::fs::write(
+      root.join("package.json"),
+      json!({
+        "name": "package",
+        "imports": {
+          "#exact": "./exact.js",
+          "#internal/*": "./src/internal/*.js",
+          "#internal/special/*": "./special/*.js",
+          "#internal/private/*": null,
+          "#conditional/*": {
+            "require": "./cjs/*.cjs",
+            "deno": "./deno/*.mjs",
+            "default": "./esm/*.mjs"
+          },
+          "#fallback/*": ["invalid:*", "./fallback/*.js"]
+        }
+      })
+      .to_string(),
+    )
+    .unwrap();
+    let resolver = NodeResolver::new(
+      deno_fs::sync::MaybeArc::new(deno_fs::RealFs),
+      deno_fs::sync::MaybeArc::new(TestNpmResolver(root.clone())),
+      Vec::new(),
+    );
+    let referrer =
+      ModuleSpecifier::from_file_path(root.join("index.js")).unwrap();
+    let resolve = |name: &str, conditions: &[&str]| {
+      resolver.package_imports_resolve(
+        name,
+        &referrer,
+        NodeModuleKind::Esm,
+        conditions,
+        NodeResolutionMode::Execution,
+        &AllowAllNodePermissions,
+      )
+    };
+
+    assert_eq!(
+      resolve("#exact", DEFAULT_CONDITIONS).unwrap(),
+      root.join("exact.js")
+    );
+    assert_eq!(
+      resolve("#internal/a/b", DEFAULT_CONDITIONS).unwrap(),
+      root.join("src/internal/a/b.js")
+    );
+    assert_eq!(
+      resolve("#internal/special/a", DEFAULT_CONDITIONS).unwrap(),
+      root.join("special/a.js")
+    );
+    assert_eq!(
+      resolve("#conditional/a", DEFAULT_CONDITIONS).unwrap(),
+      root.join("deno/a.mjs")
+    );
+    assert_eq!(
+      resolve("#conditional/a", REQUIRE_CONDITIONS).unwrap(),
+      root.join("cjs/a.cjs")
+    );
+    assert_eq!(
+      resolve("#conditional/a", &["browser"]).unwrap(),
+      root.join("esm/a.mjs")
+    );
+    assert_eq!(
+      resolve("#fallback/a", DEFAULT_CONDITIONS).unwrap(),
+      root.join("fallback/a.js")
+    );
+    assert!(resolve("#internal/private/a", DEFAULT_CONDITIONS).is_err());
+    assert!(resolve("#internal/", DEFAULT_CONDITIONS).is_err());
+    assert!(resolve("#missing", DEFAULT_CONDITIONS).is_err());
+  }
 }
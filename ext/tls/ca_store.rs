@@ -0,0 +1,183 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use crate::rustls;
+use crate::webpki_roots;
+
+use rustls::Certificate;
+use rustls::RootCertStore;
+use std::io::BufRead;
+
+/// The certificate authorities trusted by `fetch`, `Deno.connectTls` and
+/// `node:tls` alike.
+///
+/// Unlike a bare `RootCertStore` this keeps the certificates that were loaded
+/// from the platform or from PEM files, so they can be handed back to user
+/// code through `tls.getCACertificates()`.
+#[derive(Clone)]
+pub struct CaStore {
+  root_cert_store: RootCertStore,
+  /// Whether the bundled Mozilla roots were added.
+  has_bundled: bool,
+  system: Vec<Certificate>,
+  extra: Vec<Certificate>,
+}
+
+/// The subsets of a [`CaStore`] understood by `tls.getCACertificates()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaCertificateKind {
+  /// Every certificate in the store.
+  Default,
+  /// The bundled Mozilla roots.
+  Bundled,
+  /// Certificates loaded from the platform's certificate store.
+  System,
+  /// Certificates loaded from `--cert`, `DENO_CERT` or
+  /// `NODE_EXTRA_CA_CERTS`.
+  Extra,
+}
+
+impl CaStore {
+  pub fn empty() -> Self {
+    Self {
+      root_cert_store: RootCertStore::empty(),
+      has_bundled: false,
+      system: Vec::new(),
+      extra: Vec::new(),
+    }
+  }
+
+  /// Adds the Mozilla roots bundled through `webpki-roots`.
+  pub fn add_bundled(&mut self) {
+    self.root_cert_store.add_server_trust_anchors(
+      webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+          ta.subject,
+          ta.spki,
+          ta.name_constraints,
+        )
+      }),
+    );
+    self.has_bundled = true;
+  }
+
+  /// Adds the certificates from the platform's certificate store.
+  pub fn add_system(&mut self) -> Result<(), std::io::Error> {
+    for root in rustls_native_certs::load_native_certs()? {
+      let cert = Certificate(root.0);
+      if self.root_cert_store.add(&cert).is_ok() {
+        self.system.push(cert);
+      }
+    }
+    Ok(())
+  }
+
+  /// Adds every certificate found in a PEM encoded bundle. Certificates that
+  /// can't be parsed are skipped.
+  pub fn add_extra_pem(
+    &mut self,
+    reader: &mut dyn BufRead,
+  ) -> Result<(), std::io::Error> {
+    for der in rustls_pemfile::certs(reader)? {
+      let cert = Certificate(der);
+      if self.root_cert_store.add(&cert).is_ok() {
+        self.extra.push(cert);
+      }
+    }
+    Ok(())
+  }
+
+  pub fn root_cert_store(&self) -> &RootCertStore {
+    &self.root_cert_store
+  }
+
+  pub fn into_root_cert_store(self) -> RootCertStore {
+    self.root_cert_store
+  }
+
+  /// Returns the certificates of the given kind, PEM encoded.
+  pub fn to_pem(&self, kind: CaCertificateKind) -> Vec<String> {
+    let bundled = if self.has_bundled {
+      bundled_certs()
+    } else {
+      Vec::new()
+    };
+    let certs: Box<dyn Iterator<Item = &[u8]>> = match kind {
+      CaCertificateKind::Default => Box::new(
+        bundled
+          .into_iter()
+          .chain(self.system.iter().map(|cert| cert.0.as_slice()))
+          .chain(self.extra.iter().map(|cert| cert.0.as_slice())),
+      ),
+      CaCertificateKind::Bundled => Box::new(bundled.into_iter()),
+      CaCertificateKind::System => {
+        Box::new(self.system.iter().map(|cert| cert.0.as_slice()))
+      }
+      CaCertificateKind::Extra => {
+        Box::new(self.extra.iter().map(|cert| cert.0.as_slice()))
+      }
+    };
+    certs.map(encode_pem).collect()
+  }
+}
+
+/// The certificates of the bundled Mozilla roots.
+///
+/// `webpki-roots` only has the trust anchors that are derived from them, so
+/// the full certificates come from `webpki-root-certs`. Only the ones whose
+/// trust anchor is bundled are returned, as the two crates may be generated
+/// from different releases of the Mozilla roots.
+fn bundled_certs() -> Vec<&'static [u8]> {
+  webpki_root_certs::TLS_SERVER_ROOT_CERTS
+    .iter()
+    .map(|cert| cert.as_ref())
+    .filter(|der| {
+      let Ok(anchor) = webpki::TrustAnchor::try_from_cert_der(der) else {
+        return false;
+      };
+      webpki_roots::TLS_SERVER_ROOTS
+        .0
+        .iter()
+        .any(|ta| ta.subject == anchor.subject && ta.spki == anchor.spki)
+    })
+    .collect()
+}
+
+fn encode_pem(der: &[u8]) -> String {
+  let encoded = base64::encode(der);
+  let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+  for line in encoded.as_bytes().chunks(64) {
+    // base64 output is always ASCII
+    pem.push_str(std::str::from_utf8(line).unwrap());
+    pem.push('\n');
+  }
+  pem.push_str("-----END CERTIFICATE-----\n");
+  pem
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encode_pem_wraps_lines() {
+    let pem = encode_pem(&[0u8; 60]);
+    let lines = pem.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "-----BEGIN CERTIFICATE-----");
+    assert_eq!(lines[1].len(), 64);
+    assert_eq!(lines[2], "AAAAAAAAAAAAAAAA");
+    assert_eq!(lines[3], "-----END CERTIFICATE-----");
+  }
+
+  #[test]
+  fn bundled_roots_are_listed() {
+    let mut store = CaStore::empty();
+    assert!(store.to_pem(CaCertificateKind::Bundled).is_empty());
+    store.add_bundled();
+    let bundled = store.to_pem(CaCertificateKind::Bundled);
+    assert!(!bundled.is_empty());
+    assert!(bundled.len() <= webpki_roots::TLS_SERVER_ROOTS.0.len());
+    assert_eq!(store.to_pem(CaCertificateKind::Default), bundled);
+    assert!(store.to_pem(CaCertificateKind::System).is_empty());
+  }
+}
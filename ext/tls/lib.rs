@@ -6,6 +6,11 @@ pub use rustls_pemfile;
 pub use webpki;
 pub use webpki_roots;
 
+mod ca_store;
+
+pub use ca_store::CaCertificateKind;
+pub use ca_store::CaStore;
+
 use deno_core::anyhow::anyhow;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
@@ -37,6 +42,12 @@ use std::time::SystemTime;
 /// and takes a bit of time to initialize.
 pub trait RootCertStoreProvider: Send + Sync {
   fn get_or_try_init(&self) -> Result<&RootCertStore, AnyError>;
+
+  /// Returns the CA store the root cert store was built from, for providers
+  /// that keep track of it.
+  fn get_ca_store(&self) -> Result<Option<&CaStore>, AnyError> {
+    Ok(None)
+  }
 }
 
 // This extension has no runtime apis, it only exports some shared native functions.
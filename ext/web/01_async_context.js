@@ -0,0 +1,49 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+// @ts-check
+/// <reference path="../../core/internal.d.ts" />
+
+// The async context is the state that follows the logical flow of a program
+// from the code that schedules a callback to the callback, like the stores of
+// `AsyncLocalStorage` in Node. It is opaque here: it is set by whoever
+// implements the contexts, and it is snapshotted when a timer, a microtask or
+// an event listener is scheduled and restored while the callback runs.
+//
+// It is kept in the first slot of an array rather than in a variable, so that
+// native code, like the promise hook of node:async_hooks, can share it once
+// it needs to, without an op on every get and set.
+
+const core = globalThis.Deno.core;
+const ops = core.ops;
+
+const asyncContext = [undefined];
+let shared = false;
+
+/** The current async context, or `undefined` if none was entered. */
+function getAsyncContext() {
+  return asyncContext[0];
+}
+
+/**
+ * Makes `context` the current async context, and returns the previous one,
+ * which the caller is expected to restore once it is done.
+ *
+ * @param {unknown} context
+ * @returns {unknown}
+ */
+function setAsyncContext(context) {
+  const previous = asyncContext[0];
+  asyncContext[0] = context;
+  return previous;
+}
+
+/** Lets native code get and set the async context from now on. */
+function shareAsyncContext() {
+  if (shared) {
+    return;
+  }
+  shared = true;
+  ops.op_async_context_share(asyncContext);
+}
+
+export { getAsyncContext, setAsyncContext, shareAsyncContext };
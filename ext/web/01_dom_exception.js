@@ -7,14 +7,17 @@
 /// <reference path="../web/internal.d.ts" />
 /// <reference path="../web/lib.deno_web.d.ts" />
 
+const core = globalThis.Deno.core;
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayPrototypePush,
   ArrayPrototypeSlice,
   Error,
   ErrorPrototype,
   ObjectDefineProperty,
   ObjectCreate,
   ObjectEntries,
+  ObjectGetOwnPropertyNames,
   ObjectPrototypeIsPrototypeOf,
   ObjectSetPrototypeOf,
   Symbol,
@@ -85,6 +88,21 @@ const nameToCodeMapping = ObjectCreate(null, {
   DataCloneError: { value: DATA_CLONE_ERR },
 });
 
+// The names of the error names table that have no legacy code.
+const namesWithoutCode = [
+  "EncodingError",
+  "NotReadableError",
+  "UnknownError",
+  "ConstraintError",
+  "DataError",
+  "TransactionInactiveError",
+  "ReadOnlyError",
+  "VersionError",
+  "OperationError",
+  "NotAllowedError",
+  "OptOutError",
+];
+
 // Defined in WebIDL 4.3.
 // https://webidl.spec.whatwg.org/#idl-DOMException
 class DOMException {
@@ -199,4 +217,18 @@ for (let i = 0; i < entries.length; ++i) {
   ObjectDefineProperty(DOMException.prototype, key, desc);
 }
 
+// Ops throw a `DOMException` by returning an error of class
+// `DOMException${name}`, see `ext/web/dom_exception.rs`.
+const errorNames = ObjectGetOwnPropertyNames(nameToCodeMapping);
+for (let i = 0; i < namesWithoutCode.length; ++i) {
+  ArrayPrototypePush(errorNames, namesWithoutCode[i]);
+}
+for (let i = 0; i < errorNames.length; ++i) {
+  const name = errorNames[i];
+  core.registerErrorBuilder(
+    `DOMException${name}`,
+    (msg) => new DOMException(msg, name),
+  );
+}
+
 export default DOMException;
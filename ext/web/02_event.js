@@ -9,6 +9,10 @@ const core = globalThis.Deno.core;
 const ops = core.ops;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import DOMException from "ext:deno_web/01_dom_exception.js";
+import {
+  getAsyncContext,
+  setAsyncContext,
+} from "ext:deno_web/01_async_context.js";
 import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
@@ -784,16 +788,24 @@ function innerInvokeEventListeners(
       setInPassiveListener(eventImpl, true);
     }
 
-    if (typeof listener.callback === "object") {
-      if (typeof listener.callback.handleEvent === "function") {
-        listener.callback.handleEvent(eventImpl);
+    // Events that are dispatched by the runtime, outside of any async
+    // context, are handled in the context the listener was added in.
+    const asyncContext = getAsyncContext() ?? listener.asyncContext;
+    const previousAsyncContext = setAsyncContext(asyncContext);
+    try {
+      if (typeof listener.callback === "object") {
+        if (typeof listener.callback.handleEvent === "function") {
+          listener.callback.handleEvent(eventImpl);
+        }
+      } else {
+        FunctionPrototypeCall(
+          listener.callback,
+          eventImpl.currentTarget,
+          eventImpl,
+        );
       }
-    } else {
-      FunctionPrototypeCall(
-        listener.callback,
-        eventImpl.currentTarget,
-        eventImpl,
-      );
+    } finally {
+      setAsyncContext(previousAsyncContext);
     }
 
     setInPassiveListener(eventImpl, false);
@@ -1019,7 +1031,11 @@ class EventTarget {
       }
     }
 
-    ArrayPrototypePush(listeners[type], { callback, options });
+    ArrayPrototypePush(listeners[type], {
+      callback,
+      options,
+      asyncContext: getAsyncContext(),
+    });
   }
 
   removeEventListener(
@@ -1340,6 +1356,52 @@ class ProgressEvent extends Event {
   [SymbolToStringTag] = "ProgressEvent";
 }
 
+class TaskPriorityChangeEvent extends Event {
+  #previousPriority = null;
+
+  constructor(type, eventInitDict = {}) {
+    const prefix = "Failed to construct 'TaskPriorityChangeEvent'";
+    webidl.requiredArguments(arguments.length, 2, prefix);
+    super(type, eventInitDict);
+    this.#previousPriority = webidl.converters.TaskPriority(
+      eventInitDict.previousPriority,
+      prefix,
+      "Argument 2",
+    );
+  }
+
+  get previousPriority() {
+    return this.#previousPriority;
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect) {
+    return inspect(createFilteredInspectProxy({
+      object: this,
+      evaluate: ObjectPrototypeIsPrototypeOf(
+        TaskPriorityChangeEvent.prototype,
+        this,
+      ),
+      keys: [
+        ...new SafeArrayIterator(EVENT_PROPS),
+        "previousPriority",
+      ],
+    }));
+  }
+
+  // TODO(lucacasonato): remove when this interface is spec aligned
+  [SymbolToStringTag] = "TaskPriorityChangeEvent";
+}
+
+ReflectDefineProperty(TaskPriorityChangeEvent.prototype, "previousPriority", {
+  enumerable: true,
+});
+
+webidl.converters.TaskPriority = webidl.createEnumConverter("TaskPriority", [
+  "user-blocking",
+  "user-visible",
+  "background",
+]);
+
 class PromiseRejectionEvent extends Event {
   #promise = null;
   #reason = null;
@@ -1556,4 +1618,5 @@ export {
   setEventTargetData,
   setIsTrusted,
   setTarget,
+  TaskPriorityChangeEvent,
 };
@@ -6,8 +6,10 @@ const core = globalThis.Deno.core;
 const ops = core.ops;
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayPrototypeIndexOf,
   ArrayPrototypePush,
   ArrayPrototypeShift,
+  ArrayPrototypeSplice,
   FunctionPrototypeCall,
   MapPrototypeDelete,
   MapPrototypeGet,
@@ -16,18 +18,34 @@ const {
   Uint8Array,
   Uint32Array,
   NumberPOSITIVE_INFINITY,
+  ObjectPrototypeIsPrototypeOf,
+  Promise,
   PromisePrototypeThen,
+  PromiseReject,
   SafeArrayIterator,
   SafeMap,
+  Symbol,
   SymbolFor,
   TypedArrayPrototypeGetBuffer,
   TypeError,
   indirectEval,
+  queueMicrotask: coreQueueMicrotask,
 } = primordials;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { reportException } from "ext:deno_web/02_event.js";
 import { assert } from "ext:deno_web/00_infra.js";
-const { op_sleep, op_void_async_deferred } = core.ensureFastOps();
+import {
+  getAsyncContext,
+  setAsyncContext,
+} from "ext:deno_web/01_async_context.js";
+import {
+  add,
+  remove,
+  signalPriority,
+  TaskSignalPrototype,
+} from "ext:deno_web/03_abort_signal.js";
+const { op_sleep, op_sleep_interval, op_void_async_deferred } = core
+  .ensureFastOps();
 
 const hrU8 = new Uint8Array(8);
 const hr = new Uint32Array(TypedArrayPrototypeGetBuffer(hrU8));
@@ -133,6 +151,8 @@ function initializeTimer(
   if (timeout < 0) timeout = 0;
   if (timerNestingLevel > 5 && timeout < 4 && respectNesting) timeout = 4;
 
+  const asyncContext = getAsyncContext();
+
   // 9. Let task be a task that runs the following steps:
   const task = {
     action: () => {
@@ -147,18 +167,23 @@ function initializeTimer(
 
       // 2.
       // 3.
-      if (typeof callback === "function") {
-        try {
-          FunctionPrototypeCall(
-            callback,
-            globalThis,
-            ...new SafeArrayIterator(args),
-          );
-        } catch (error) {
-          reportException(error);
+      const previousAsyncContext = setAsyncContext(asyncContext);
+      try {
+        if (typeof callback === "function") {
+          try {
+            FunctionPrototypeCall(
+              callback,
+              globalThis,
+              ...new SafeArrayIterator(args),
+            );
+          } catch (error) {
+            reportException(error);
+          }
+        } else {
+          indirectEval(callback);
         }
-      } else {
-        indirectEval(callback);
+      } finally {
+        setAsyncContext(previousAsyncContext);
       }
 
       if (repeat) {
@@ -191,6 +216,7 @@ function initializeTimer(
     () => ArrayPrototypePush(timerTasks, task),
     timeout,
     timerInfo,
+    repeat,
   );
 
   return id;
@@ -218,8 +244,10 @@ const scheduledTimers = { head: null, tail: null };
  * cancelled.
  * @param {number} millis
  * @param {{ cancelRid: number, isRef: boolean, promiseId: number }} timerInfo
+ * @param {boolean} repeat Whether this is a period of an interval, whose
+ * deadlines are kept by the timer handle so they don't drift.
  */
-function runAfterTimeout(cb, millis, timerInfo) {
+function runAfterTimeout(cb, millis, timerInfo, repeat = false) {
   const cancelRid = timerInfo.cancelRid;
   let sleepPromise;
   // If this timeout is scheduled for 0ms it means we want it to run at the
@@ -228,6 +256,8 @@ function runAfterTimeout(cb, millis, timerInfo) {
   // in this case, because the timer will take closer to 0ms instead of >1ms.
   if (millis === 0) {
     sleepPromise = op_void_async_deferred();
+  } else if (repeat) {
+    sleepPromise = op_sleep_interval(millis, cancelRid);
   } else {
     sleepPromise = op_sleep(millis, cancelRid);
   }
@@ -370,6 +400,21 @@ function clearInterval(id = 0) {
   clearTimeout(id);
 }
 
+function queueMicrotask(callback) {
+  if (typeof callback !== "function") {
+    return coreQueueMicrotask(callback);
+  }
+  const asyncContext = getAsyncContext();
+  return coreQueueMicrotask(() => {
+    const previousAsyncContext = setAsyncContext(asyncContext);
+    try {
+      callback();
+    } finally {
+      setAsyncContext(previousAsyncContext);
+    }
+  });
+}
+
 function refTimer(id) {
   const timerInfo = MapPrototypeGet(activeTimers, id);
   if (timerInfo === undefined || timerInfo.isRef) {
@@ -388,12 +433,228 @@ function unrefTimer(id) {
   core.unrefOp(timerInfo.promiseId);
 }
 
+// ---------------------------------------------------------------------------
+
+const taskPriorities = ["user-blocking", "user-visible", "background"];
+
+/**
+ * @typedef SchedulerTask
+ * @property {string | null} priority The fixed priority of the task.
+ * @property {TaskSignal | null} prioritySignal The signal whose priority is
+ * the one of the task, if it doesn't have a fixed priority.
+ * @property {AbortSignal | null} signal
+ * @property {boolean} isContinuation Whether the task resumes a
+ * `scheduler.yield()`.
+ * @property {() => void} run
+ */
+
+/**
+ * The scheduler tasks whose delay has passed, in the order they were queued.
+ * The priority of a task can change while it waits, so the next task is only
+ * picked when it's its turn to run.
+ *
+ * @type {SchedulerTask[]}
+ */
+const schedulerTasks = [];
+
+/**
+ * The scheduler task whose callback is running, which `scheduler.yield()`
+ * inherits the priority and signal from.
+ *
+ * @type {SchedulerTask | null}
+ */
+let currentSchedulerTask = null;
+
+/** @param {SchedulerTask} task */
+function schedulerTaskRank(task) {
+  const priority = task.priority ?? task.prioritySignal[signalPriority];
+  // continuations run before the other tasks of the same priority
+  return ArrayPrototypeIndexOf(taskPriorities, priority) * 2 +
+    (task.isContinuation ? 0 : 1);
+}
+
+function runNextSchedulerTask() {
+  if (schedulerTasks.length === 0) {
+    // the task was aborted after it was queued
+    return;
+  }
+  let next = 0;
+  let nextRank = schedulerTaskRank(schedulerTasks[0]);
+  for (let i = 1; i < schedulerTasks.length; i++) {
+    const rank = schedulerTaskRank(schedulerTasks[i]);
+    if (rank < nextRank) {
+      next = i;
+      nextRank = rank;
+    }
+  }
+  const task = ArrayPrototypeSplice(schedulerTasks, next, 1)[0];
+  currentSchedulerTask = task;
+  try {
+    task.run();
+  } finally {
+    currentSchedulerTask = null;
+  }
+}
+
+/**
+ * Queues `task` after `delay` milliseconds on the timer task source, like a
+ * timer. The returned promise is settled by `steps` when the task runs, or
+ * rejected when its signal is aborted before.
+ *
+ * @param {SchedulerTask} task
+ * @param {number} delay
+ * @param {(resolve: Function, reject: Function) => void} steps
+ * @returns {Promise<any>}
+ */
+function queueSchedulerTask(task, delay, steps) {
+  const signal = task.signal;
+  if (signal?.aborted) {
+    return PromiseReject(signal.reason);
+  }
+  return new Promise((resolve, reject) => {
+    const timerInfo = {
+      cancelRid: ops.op_timer_handle(),
+      isRef: true,
+      promiseId: -1,
+    };
+    let aborted = false;
+    const abortAlgorithm = () => {
+      aborted = true;
+      core.tryClose(timerInfo.cancelRid);
+      const index = ArrayPrototypeIndexOf(schedulerTasks, task);
+      if (index !== -1) {
+        ArrayPrototypeSplice(schedulerTasks, index, 1);
+      }
+      reject(signal.reason);
+    };
+    signal?.[add](abortAlgorithm);
+
+    const asyncContext = getAsyncContext();
+    task.run = () => {
+      signal?.[remove](abortAlgorithm);
+      const previousAsyncContext = setAsyncContext(asyncContext);
+      try {
+        steps(resolve, reject);
+      } finally {
+        setAsyncContext(previousAsyncContext);
+      }
+    };
+    runAfterTimeout(() => {
+      core.tryClose(timerInfo.cancelRid);
+      if (aborted) {
+        return;
+      }
+      ArrayPrototypePush(schedulerTasks, task);
+      ArrayPrototypePush(timerTasks, {
+        action: runNextSchedulerTask,
+        nestingLevel: 0,
+      });
+    }, delay, timerInfo);
+  });
+}
+
+let postTaskOptionsConverter;
+
+// This is lazy loaded because there is a circular dependency with AbortSignal.
+function lazyPostTaskOptionsConverter() {
+  postTaskOptionsConverter ??= webidl.createDictionaryConverter(
+    "SchedulerPostTaskOptions",
+    [
+      {
+        key: "signal",
+        converter: webidl.converters.AbortSignal,
+      },
+      {
+        key: "priority",
+        converter: webidl.converters.TaskPriority,
+      },
+      {
+        key: "delay",
+        converter: webidl.converters["unsigned long long"],
+        defaultValue: 0,
+      },
+    ],
+  );
+  return postTaskOptionsConverter;
+}
+
+const illegalConstructorKey = Symbol("illegalConstructorKey");
+
+class Scheduler {
+  constructor(key = null) {
+    if (key !== illegalConstructorKey) {
+      webidl.illegalConstructor();
+    }
+    this[webidl.brand] = webidl.brand;
+  }
+
+  postTask(callback, options = {}) {
+    webidl.assertBranded(this, SchedulerPrototype);
+    const prefix = "Failed to execute 'postTask' on 'Scheduler'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    callback = webidl.converters.Function(callback, prefix, "Argument 1");
+    options = lazyPostTaskOptionsConverter()(options, prefix, "Argument 2");
+
+    const signal = options.signal ?? null;
+    let prioritySignal = null;
+    if (
+      options.priority === undefined && signal !== null &&
+      ObjectPrototypeIsPrototypeOf(TaskSignalPrototype, signal)
+    ) {
+      prioritySignal = signal;
+    }
+    /** @type {SchedulerTask} */
+    const task = {
+      priority: prioritySignal === null
+        ? options.priority ?? "user-visible"
+        : null,
+      prioritySignal,
+      signal,
+      isContinuation: false,
+      run: null,
+    };
+    return queueSchedulerTask(task, options.delay, (resolve, reject) => {
+      try {
+        resolve(FunctionPrototypeCall(callback, undefined));
+      } catch (error) {
+        reject(error);
+      }
+    });
+  }
+
+  /**
+   * The continuation inherits the priority and signal of the task that is
+   * running, which are only known before the task awaits anything.
+   */
+  yield() {
+    webidl.assertBranded(this, SchedulerPrototype);
+    const inherited = currentSchedulerTask;
+    /** @type {SchedulerTask} */
+    const task = {
+      priority: inherited === null ? "user-visible" : inherited.priority,
+      prioritySignal: inherited?.prioritySignal ?? null,
+      signal: inherited?.signal ?? null,
+      isContinuation: true,
+      run: null,
+    };
+    return queueSchedulerTask(task, 0, (resolve) => resolve(undefined));
+  }
+}
+
+webidl.configurePrototype(Scheduler);
+const SchedulerPrototype = Scheduler.prototype;
+
+const scheduler = new Scheduler(illegalConstructorKey);
+
 export {
   clearInterval,
   clearTimeout,
   handleTimerMacrotask,
   opNow,
+  queueMicrotask,
   refTimer,
+  Scheduler,
+  scheduler,
   setInterval,
   setTimeout,
   setTimeoutUnclamped,
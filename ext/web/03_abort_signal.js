@@ -10,6 +10,7 @@ import {
   EventTarget,
   listenerCount,
   setIsTrusted,
+  TaskPriorityChangeEvent,
 } from "ext:deno_web/02_event.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
@@ -30,6 +31,8 @@ const abortReason = Symbol("[[abortReason]]");
 const abortAlgos = Symbol("[[abortAlgos]]");
 const signal = Symbol("[[signal]]");
 const timerId = Symbol("[[timerId]]");
+const signalPriority = Symbol("[[priority]]");
+const priorityChanging = Symbol("[[priorityChanging]]");
 
 const illegalConstructorKey = Symbol("illegalConstructorKey");
 
@@ -174,6 +177,76 @@ class AbortController {
 webidl.configurePrototype(AbortController);
 const AbortControllerPrototype = AbortController.prototype;
 
+class TaskSignal extends AbortSignal {
+  constructor(key = null, priority = "user-visible") {
+    super(key);
+    this[signalPriority] = priority;
+    this[priorityChanging] = false;
+  }
+
+  get priority() {
+    webidl.assertBranded(this, TaskSignalPrototype);
+    return this[signalPriority];
+  }
+}
+defineEventHandler(TaskSignal.prototype, "prioritychange");
+
+webidl.configurePrototype(TaskSignal);
+const TaskSignalPrototype = TaskSignal.prototype;
+
+webidl.converters.TaskControllerInit = webidl.createDictionaryConverter(
+  "TaskControllerInit",
+  [
+    {
+      key: "priority",
+      converter: webidl.converters.TaskPriority,
+      defaultValue: "user-visible",
+    },
+  ],
+);
+
+class TaskController extends AbortController {
+  constructor(init = {}) {
+    super();
+    const prefix = "Failed to construct 'TaskController'";
+    init = webidl.converters.TaskControllerInit(init, prefix, "Argument 1");
+    this[signal] = new TaskSignal(illegalConstructorKey, init.priority);
+  }
+
+  setPriority(priority) {
+    webidl.assertBranded(this, TaskControllerPrototype);
+    const prefix = "Failed to execute 'setPriority' on 'TaskController'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    priority = webidl.converters.TaskPriority(priority, prefix, "Argument 1");
+
+    const taskSignal = this[signal];
+    if (taskSignal[priorityChanging]) {
+      throw new DOMException(
+        "The priority is already being changed.",
+        "NotAllowedError",
+      );
+    }
+    if (taskSignal[signalPriority] === priority) {
+      return;
+    }
+    taskSignal[priorityChanging] = true;
+    const previousPriority = taskSignal[signalPriority];
+    taskSignal[signalPriority] = priority;
+    const event = new TaskPriorityChangeEvent("prioritychange", {
+      previousPriority,
+    });
+    setIsTrusted(event, true);
+    try {
+      taskSignal.dispatchEvent(event);
+    } finally {
+      taskSignal[priorityChanging] = false;
+    }
+  }
+}
+
+webidl.configurePrototype(TaskController);
+const TaskControllerPrototype = TaskController.prototype;
+
 webidl.converters["AbortSignal"] = webidl.createInterfaceConverter(
   "AbortSignal",
   AbortSignal.prototype,
@@ -203,5 +276,9 @@ export {
   newSignal,
   remove,
   signalAbort,
+  signalPriority,
+  TaskController,
+  TaskSignal,
+  TaskSignalPrototype,
   timerId,
 };
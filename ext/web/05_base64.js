@@ -13,7 +13,11 @@ import DOMException from "ext:deno_web/01_dom_exception.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ObjectPrototypeIsPrototypeOf,
+  SyntaxError,
+  TypedArrayPrototypeGetSymbolToStringTag,
+  TypeError,
   TypeErrorPrototype,
+  Uint32Array,
 } = primordials;
 
 /**
@@ -58,4 +62,136 @@ function btoa(data) {
   }
 }
 
-export { atob, btoa };
+// The base64 methods of `Uint8Array`, from the TC39 proposal:
+// https://tc39.es/proposal-arraybuffer-base64/spec/
+
+function validateUint8Array(value) {
+  if (TypedArrayPrototypeGetSymbolToStringTag(value) !== "Uint8Array") {
+    throw new TypeError("Receiver is not a Uint8Array");
+  }
+}
+
+function getOptionsObject(options) {
+  if (options === undefined) {
+    return { __proto__: null };
+  }
+  if (
+    (typeof options !== "object" || options === null) &&
+    typeof options !== "function"
+  ) {
+    throw new TypeError("Options must be an object");
+  }
+  return options;
+}
+
+function getAlphabet(options) {
+  const alphabet = options.alphabet;
+  if (alphabet === undefined) {
+    return "base64";
+  }
+  if (alphabet !== "base64" && alphabet !== "base64url") {
+    throw new TypeError('alphabet must be "base64" or "base64url"');
+  }
+  return alphabet;
+}
+
+function getLastChunkHandling(options) {
+  const lastChunkHandling = options.lastChunkHandling;
+  if (lastChunkHandling === undefined) {
+    return "loose";
+  }
+  if (
+    lastChunkHandling !== "loose" && lastChunkHandling !== "strict" &&
+    lastChunkHandling !== "stop-before-partial"
+  ) {
+    throw new TypeError(
+      'lastChunkHandling must be "loose", "strict" or "stop-before-partial"',
+    );
+  }
+  return lastChunkHandling;
+}
+
+/**
+ * Decoding errors are type errors on the Rust side, and syntax errors for
+ * the proposal.
+ */
+function toSyntaxError(e) {
+  if (ObjectPrototypeIsPrototypeOf(TypeErrorPrototype, e)) {
+    return new SyntaxError(e.message);
+  }
+  return e;
+}
+
+/**
+ * @param {string} string
+ * @param {{ alphabet?: string, lastChunkHandling?: string }} [options]
+ * @returns {Uint8Array}
+ */
+function fromBase64(string, options = undefined) {
+  if (typeof string !== "string") {
+    throw new TypeError("Argument must be a string");
+  }
+  options = getOptionsObject(options);
+  const alphabet = getAlphabet(options);
+  const lastChunkHandling = getLastChunkHandling(options);
+  try {
+    return ops.op_base64_decode_with_options(
+      string,
+      alphabet === "base64url",
+      lastChunkHandling,
+    );
+  } catch (e) {
+    throw toSyntaxError(e);
+  }
+}
+
+/**
+ * @param {{ alphabet?: string, omitPadding?: boolean }} [options]
+ * @returns {string}
+ */
+function toBase64(options = undefined) {
+  validateUint8Array(this);
+  options = getOptionsObject(options);
+  const alphabet = getAlphabet(options);
+  const omitPadding = !!options.omitPadding;
+  return ops.op_base64_encode_with_options(
+    this,
+    alphabet === "base64url",
+    omitPadding,
+  );
+}
+
+const decodeIntoBuf = new Uint32Array(2);
+
+/**
+ * Decodes as much of `string` as fits into the array. With
+ * `lastChunkHandling: "stop-before-partial"`, the characters that aren't
+ * read can be prepended to the next chunk of a stream.
+ *
+ * @param {string} string
+ * @param {{ alphabet?: string, lastChunkHandling?: string }} [options]
+ * @returns {{ read: number, written: number }}
+ */
+function setFromBase64(string, options = undefined) {
+  validateUint8Array(this);
+  if (typeof string !== "string") {
+    throw new TypeError("Argument must be a string");
+  }
+  options = getOptionsObject(options);
+  const alphabet = getAlphabet(options);
+  const lastChunkHandling = getLastChunkHandling(options);
+  try {
+    ops.op_base64_decode_into(
+      string,
+      alphabet === "base64url",
+      lastChunkHandling,
+      this,
+      decodeIntoBuf,
+    );
+  } catch (e) {
+    throw toSyntaxError(e);
+  }
+  return { read: decodeIntoBuf[0], written: decodeIntoBuf[1] };
+}
+
+export { atob, btoa, fromBase64, setFromBase64, toBase64 };
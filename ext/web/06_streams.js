@@ -81,6 +81,7 @@ const {
 } = primordials;
 import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
 import { assert, AssertionError } from "ext:deno_web/00_infra.js";
+import DOMException from "ext:deno_web/01_dom_exception.js";
 
 /** @template T */
 class Deferred {
@@ -713,16 +714,55 @@ const _original = Symbol("[[original]]");
  * @returns {ReadableStream<Uint8Array>}
  */
 function readableStreamForRid(rid, autoClose = true) {
+  return createReadableStreamForRid(rid, null, autoClose);
+}
+
+/**
+ * Like `readableStreamForRid`, but the resource is only opened by calling
+ * `open` once the stream is first read from, or once the resource is taken
+ * from the stream. A stream that is never read doesn't hold on to a resource.
+ *
+ * @param {() => number} open Opens the resource to read from.
+ * @param {boolean=} autoClose If the resource should be auto-closed when the stream closes. Defaults to true.
+ * @returns {ReadableStream<Uint8Array>}
+ */
+function readableStreamForLazyRid(open, autoClose = true) {
+  return createReadableStreamForRid(null, open, autoClose);
+}
+
+/**
+ * @param {number | null} rid
+ * @param {(() => number) | null} open
+ * @param {boolean} autoClose
+ * @returns {ReadableStream<Uint8Array>}
+ */
+function createReadableStreamForRid(rid, open, autoClose) {
   const stream = webidl.createBranded(ReadableStream);
-  stream[_resourceBacking] = { rid, autoClose };
+
+  const getRid = () => {
+    if (rid === null) {
+      rid = open();
+      if (autoClose) {
+        RESOURCE_REGISTRY.register(stream, rid, stream);
+      }
+    }
+    return rid;
+  };
+  stream[_resourceBacking] = {
+    get rid() {
+      return getRid();
+    },
+    autoClose,
+  };
 
   const tryClose = () => {
-    if (!autoClose) return;
+    // a resource that was never opened has nothing to close
+    if (!autoClose || rid === null) return;
     RESOURCE_REGISTRY.unregister(stream);
     core.tryClose(rid);
   };
 
-  if (autoClose) {
+  if (autoClose && rid !== null) {
     RESOURCE_REGISTRY.register(stream, rid, stream);
   }
 
@@ -731,6 +771,7 @@ function readableStreamForRid(rid, autoClose = true) {
     async pull(controller) {
       const v = controller.byobRequest.view;
       try {
+        const rid = getRid();
         if (controller[_readAll] === true) {
           // fast path for tee'd streams consuming body
           const chunk = await core.readAll(rid);
@@ -776,8 +817,8 @@ const _isUnref = Symbol("isUnref");
  * Create a new ReadableStream object that is backed by a Resource that
  * implements `Resource::read_return`. This readable stream supports being
  * refed and unrefed by calling `readableStreamForRidUnrefableRef` and
- * `readableStreamForRidUnrefableUnref` on it. Unrefable streams are not
- * FastStream compatible.
+ * `readableStreamForRidUnrefableUnref` on it. Like the streams returned by
+ * `readableStreamForRid`, unrefable streams are FastStream compatible.
  *
  * @param {number} rid The resource ID to read from.
  * @returns {ReadableStream<Uint8Array>}
@@ -786,6 +827,7 @@ function readableStreamForRidUnrefable(rid) {
   const stream = webidl.createBranded(ReadableStream);
   stream[promiseIdSymbol] = undefined;
   stream[_isUnref] = false;
+  stream[_resourceBacking] = { rid, autoClose: true };
   stream[_resourceBackingUnrefable] = { rid, autoClose: true };
   const underlyingSource = {
     type: "bytes",
@@ -948,12 +990,18 @@ function writableStreamForRid(rid, autoClose = true) {
   }
 
   const underlyingSink = {
-    async write(chunk, controller) {
+    start(controller) {
+      // Aborting must not wait for a write that the other end isn't
+      // consuming, so the resource is closed right away, which cancels any
+      // pending write.
+      controller[_signal][add](tryClose);
+    },
+    async write(chunk) {
       try {
         await core.writeAll(rid, chunk);
       } catch (e) {
-        controller.error(e);
         tryClose();
+        throw e;
       }
     },
     close() {
@@ -978,6 +1026,207 @@ function getWritableStreamResourceBacking(stream) {
   return stream[_resourceBacking];
 }
 
+/**
+ * Posts a message about a stream that is proxied through a port.
+ * @param {MessagePort} port
+ * @param {"chunk" | "pull" | "close" | "error"} type
+ * @param {any} value
+ */
+function packAndPostMessage(port, type, value) {
+  port.postMessage({ type, value });
+}
+
+/**
+ * @param {MessagePort} port
+ * @param {any} error
+ */
+function crossRealmTransformSendError(port, error) {
+  try {
+    packAndPostMessage(port, "error", error);
+  } catch {
+    // the error can't be cloned either, so the other end isn't told
+  }
+}
+
+/**
+ * Like `packAndPostMessage()`, but a value that can't be cloned errors the
+ * other end of the stream before the error is thrown.
+ * @param {MessagePort} port
+ * @param {"chunk" | "pull" | "close" | "error"} type
+ * @param {any} value
+ */
+function packAndPostMessageHandlingError(port, type, value) {
+  try {
+    packAndPostMessage(port, type, value);
+  } catch (error) {
+    crossRealmTransformSendError(port, error);
+    throw error;
+  }
+}
+
+/**
+ * Creates the readable end of a stream whose chunks are written to the
+ * entangled port, as the receiving side of a transferred `ReadableStream`.
+ * @param {MessagePort} port
+ * @returns {ReadableStream}
+ */
+function readableStreamFromPort(port) {
+  /** @type {ReadableStreamDefaultController} */
+  let controller;
+  port.addEventListener("message", (event) => {
+    const { type, value } = event.data;
+    switch (type) {
+      case "chunk":
+        readableStreamDefaultControllerEnqueue(controller, value);
+        break;
+      case "close":
+        readableStreamDefaultControllerClose(controller);
+        port.close();
+        break;
+      case "error":
+        readableStreamDefaultControllerError(controller, value);
+        port.close();
+        break;
+    }
+  });
+  port.addEventListener("messageerror", () => {
+    const error = new DOMException(
+      "Failed to deserialize a chunk of a transferred stream",
+      "DataCloneError",
+    );
+    crossRealmTransformSendError(port, error);
+    readableStreamDefaultControllerError(controller, error);
+    port.close();
+  });
+  port.start();
+
+  const stream = createReadableStream(
+    () => undefined,
+    () => {
+      packAndPostMessage(port, "pull", undefined);
+      return resolvePromiseWith(undefined);
+    },
+    (reason) => {
+      try {
+        packAndPostMessageHandlingError(port, "error", reason);
+      } catch (error) {
+        return PromiseReject(error);
+      } finally {
+        port.close();
+      }
+      return resolvePromiseWith(undefined);
+    },
+    0,
+  );
+  controller = stream[_controller];
+  return stream;
+}
+
+/**
+ * Creates the writable end of a stream whose chunks are read from the
+ * entangled port, as the receiving side of a transferred `WritableStream`.
+ * A chunk is only posted once the other end asked for one.
+ * @param {MessagePort} port
+ * @returns {WritableStream}
+ */
+function writableStreamFromPort(port) {
+  /** @type {WritableStreamDefaultController} */
+  let controller;
+  /** @type {Deferred<void> | undefined} */
+  let backpressurePromise = new Deferred();
+  port.addEventListener("message", (event) => {
+    const { type, value } = event.data;
+    switch (type) {
+      case "pull":
+        if (backpressurePromise !== undefined) {
+          backpressurePromise.resolve(undefined);
+          backpressurePromise = undefined;
+        }
+        break;
+      case "error":
+        writableStreamDefaultControllerErrorIfNeeded(controller, value);
+        if (backpressurePromise !== undefined) {
+          backpressurePromise.resolve(undefined);
+          backpressurePromise = undefined;
+        }
+        break;
+    }
+  });
+  port.addEventListener("messageerror", () => {
+    const error = new DOMException(
+      "Failed to deserialize a message of a transferred stream",
+      "DataCloneError",
+    );
+    crossRealmTransformSendError(port, error);
+    port.close();
+  });
+  port.start();
+
+  const stream = createWritableStream(
+    () => undefined,
+    (chunk) => {
+      if (backpressurePromise === undefined) {
+        backpressurePromise = new Deferred();
+        backpressurePromise.resolve(undefined);
+      }
+      return transformPromiseWith(backpressurePromise.promise, () => {
+        backpressurePromise = new Deferred();
+        try {
+          packAndPostMessageHandlingError(port, "chunk", chunk);
+        } catch (error) {
+          port.close();
+          throw error;
+        }
+      });
+    },
+    () => {
+      packAndPostMessage(port, "close", undefined);
+      port.close();
+      return resolvePromiseWith(undefined);
+    },
+    (reason) => {
+      try {
+        packAndPostMessageHandlingError(port, "error", reason);
+      } catch (error) {
+        return PromiseReject(error);
+      } finally {
+        port.close();
+      }
+      return resolvePromiseWith(undefined);
+    },
+    1,
+    () => 1,
+  );
+  controller = stream[_controller];
+  return stream;
+}
+
+/**
+ * Pipes a stream that is being transferred into a port, whose entangled port
+ * is transferred in its place. The stream stays locked from then on.
+ * @param {ReadableStream} stream
+ * @param {MessagePort} port
+ */
+function transferReadableStream(stream, port) {
+  assert(!isReadableStreamLocked(stream));
+  const writable = writableStreamFromPort(port);
+  const promise = readableStreamPipeTo(stream, writable, false, false, false);
+  setPromiseIsHandledToTrue(promise);
+}
+
+/**
+ * Pipes a port into a stream that is being transferred, whose entangled port
+ * is transferred in its place. The stream stays locked from then on.
+ * @param {WritableStream} stream
+ * @param {MessagePort} port
+ */
+function transferWritableStream(stream, port) {
+  assert(!isWritableStreamLocked(stream));
+  const readable = readableStreamFromPort(port);
+  const promise = readableStreamPipeTo(readable, stream, false, false, false);
+  setPromiseIsHandledToTrue(promise);
+}
+
 /*
  * @param {ReadableStream} stream
  */
@@ -2454,6 +2703,23 @@ function readableStreamPipeTo(
   );
   assert(!isReadableStreamLocked(source));
   assert(!isWritableStreamLocked(dest));
+  if (
+    signal === undefined &&
+    getReadableStreamResourceBacking(source) &&
+    getWritableStreamResourceBacking(dest) &&
+    source[_state] === "readable" && source[_disturbed] === false &&
+    dest[_state] === "writable" &&
+    writableStreamHasOperationMarkedInFlight(dest) === false &&
+    dest[_controller][_queue].length === 0
+  ) {
+    return readableStreamPipeToResource(
+      source,
+      dest,
+      preventClose,
+      preventAbort,
+      preventCancel,
+    );
+  }
   // We use acquireReadableStreamDefaultReader even in case of ReadableByteStreamController
   // as the spec allows us, and the only reason to use BYOBReader is to do some smart things
   // with it, but the spec does not specify what things, so to simplify we stick to DefaultReader.
@@ -2712,6 +2978,68 @@ function readableStreamPipeTo(
   }
 }
 
+/**
+ * Pipes a resource backed stream into a resource backed stream in a single
+ * op, so that the chunks never pass through JavaScript. Both streams stay
+ * locked until the source is exhausted.
+ *
+ * @param {ReadableStream<Uint8Array>} source
+ * @param {WritableStream<Uint8Array>} dest
+ * @param {boolean} preventClose
+ * @param {boolean} preventAbort
+ * @param {boolean} preventCancel
+ * @returns {Promise<void>}
+ */
+async function readableStreamPipeToResource(
+  source,
+  dest,
+  preventClose,
+  preventAbort,
+  preventCancel,
+) {
+  const reader = acquireReadableStreamDefaultReader(source);
+  const writer = acquireWritableStreamDefaultWriter(dest);
+  source[_disturbed] = true;
+  try {
+    try {
+      const promise = core.opAsync(
+        "op_pipe_resources",
+        getReadableStreamResourceBacking(source).rid,
+        getWritableStreamResourceBacking(dest).rid,
+      );
+      if (readableStreamIsUnrefable(source)) {
+        const promiseId = source[promiseIdSymbol] = promise[promiseIdSymbol];
+        if (source[_isUnref]) core.unrefOp(promiseId);
+      }
+      await promise;
+    } catch (err) {
+      const actions = [];
+      if (preventAbort === false && dest[_state] === "writable") {
+        ArrayPrototypePush(actions, writableStreamAbort(dest, err));
+      }
+      if (preventCancel === false && source[_state] === "readable") {
+        ArrayPrototypePush(actions, readableStreamCancel(source, err));
+      }
+      await SafePromiseAll(actions);
+      throw err;
+    }
+    // the source was read to the end, cancelling it closes the stream and
+    // releases the resource
+    if (source[_state] === "readable") {
+      await readableStreamCancel(source, undefined);
+    }
+    if (preventClose === false) {
+      await writableStreamDefaultWriterCloseWithErrorPropagation(writer);
+    }
+  } finally {
+    if (readableStreamIsUnrefable(source)) {
+      source[promiseIdSymbol] = undefined;
+    }
+    writableStreamDefaultWriterRelease(writer);
+    readableStreamDefaultReaderRelease(reader);
+  }
+}
+
 /**
  * @param {ReadableStreamGenericReader<any> | ReadableStreamBYOBReader} reader
  * @param {any} reason
@@ -6388,6 +6716,8 @@ export {
   getReadableStreamResourceBacking,
   getWritableStreamResourceBacking,
   isReadableStreamDisturbed,
+  isReadableStreamLocked,
+  isWritableStreamLocked,
   ReadableByteStreamController,
   ReadableStream,
   ReadableStreamBYOBReader,
@@ -6397,12 +6727,16 @@ export {
   ReadableStreamDefaultController,
   ReadableStreamDefaultReader,
   readableStreamDisturb,
+  readableStreamForLazyRid,
   readableStreamForRid,
   readableStreamForRidUnrefable,
   readableStreamForRidUnrefableRef,
   readableStreamForRidUnrefableUnref,
+  readableStreamFromPort,
   ReadableStreamPrototype,
   readableStreamThrowIfErrored,
+  transferReadableStream,
+  transferWritableStream,
   TransformStream,
   TransformStreamDefaultController,
   WritableStream,
@@ -6410,4 +6744,6 @@ export {
   WritableStreamDefaultController,
   WritableStreamDefaultWriter,
   writableStreamForRid,
+  writableStreamFromPort,
+  WritableStreamPrototype,
 };
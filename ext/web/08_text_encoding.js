@@ -182,7 +182,10 @@ class TextDecoder {
           this.#ignoreBOM,
         );
       }
-      return ops.op_encoding_decode(input, this.#rid, stream);
+      if (stream) {
+        return ops.op_encoding_decode_stream(input, this.#rid);
+      }
+      return ops.op_encoding_decode(input, this.#rid, false);
     } finally {
       if (!stream && this.#rid !== null) {
         core.close(this.#rid);
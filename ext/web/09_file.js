@@ -13,7 +13,7 @@
 const core = globalThis.Deno.core;
 const ops = core.ops;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
-import { ReadableStream } from "ext:deno_web/06_streams.js";
+import { readableStreamForLazyRid } from "ext:deno_web/06_streams.js";
 import { URL } from "ext:deno_url/00_url.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
@@ -357,23 +357,15 @@ class Blob {
    */
   stream() {
     webidl.assertBranded(this, BlobPrototype);
-    const partIterator = toIterator(this[_parts]);
-    const stream = new ReadableStream({
-      type: "bytes",
-      /** @param {ReadableByteStreamController} controller */
-      async pull(controller) {
-        while (true) {
-          const { value, done } = await AsyncGeneratorPrototypeNext(
-            partIterator,
-          );
-          if (done) return controller.close();
-          if (TypedArrayPrototypeGetByteLength(value) > 0) {
-            return controller.enqueue(value);
-          }
-        }
-      },
-    });
-    return stream;
+    // the parts are read from a resource, so that the stream is read in
+    // chunks and can be passed on as is, like as the body of a response.
+    // It is only opened once the stream is used, as bodies made from a blob
+    // get a stream that is often never read. The blob is kept by the stream
+    // until then, so that its parts aren't removed.
+    const blob = this;
+    return readableStreamForLazyRid(() =>
+      ops.op_blob_open_reader(getParts(blob))
+    );
   }
 
   /**
@@ -553,7 +545,8 @@ const registry = new SafeFinalizationRegistry((uuid) => {
   ops.op_blob_remove_part(uuid);
 });
 
-// TODO(lucacasonato): get a better stream from Rust in BlobReference#stream
+/** The most bytes of a blob part that are read at once. */
+const READ_CHUNK_SIZE = 1024 * 1024;
 
 /**
  * An opaque reference to a blob part in Rust. This could be backed by a file,
@@ -601,20 +594,17 @@ class BlobReference {
   }
 
   /**
-   * Read the entire contents of the reference blob.
+   * Read the entire contents of the reference blob, in chunks so that parts
+   * that are backed by a file aren't loaded into memory at once.
    * @returns {AsyncGenerator<Uint8Array>}
    */
   async *stream() {
-    yield core.opAsync("op_blob_read_part", this._id);
-
-    // let position = 0;
-    // const end = this.size;
-    // while (position !== end) {
-    //   const size = MathMin(end - position, 65536);
-    //   const chunk = this.slice(position, position + size);
-    //   position += chunk.size;
-    //   yield core.opAsync("op_blob_read_part", chunk._id);
-    // }
+    let position = 0;
+    do {
+      const size = MathMin(this.size - position, READ_CHUNK_SIZE);
+      yield core.opAsync("op_blob_read_part", this._id, position, size);
+      position += size;
+    } while (position < this.size);
   }
 }
 
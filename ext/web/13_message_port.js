@@ -11,20 +11,44 @@ const { InterruptedPrototype, ops } = core;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import {
   defineEventHandler,
+  Event,
   EventTarget,
   MessageEvent,
   setEventTargetData,
 } from "ext:deno_web/02_event.js";
 import DOMException from "ext:deno_web/01_dom_exception.js";
+import {
+  isReadableStreamLocked,
+  isWritableStreamLocked,
+  readableStreamFromPort,
+  ReadableStreamPrototype,
+  transferReadableStream,
+  transferWritableStream,
+  writableStreamFromPort,
+  WritableStreamPrototype,
+} from "ext:deno_web/06_streams.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayBufferPrototype,
   ArrayBufferPrototypeGetByteLength,
   ArrayPrototypeFilter,
   ArrayPrototypeIncludes,
+  ArrayIsArray,
   ArrayPrototypePush,
+  Map,
+  MapPrototype,
+  MapPrototypeForEach,
+  MapPrototypeSet,
+  ObjectGetPrototypeOf,
+  ObjectKeys,
+  ObjectPrototype,
   ObjectPrototypeIsPrototypeOf,
   ObjectSetPrototypeOf,
+  SafeMap,
+  Set,
+  SetPrototype,
+  SetPrototypeAdd,
+  SetPrototypeForEach,
   Symbol,
   SymbolFor,
   SymbolIterator,
@@ -68,6 +92,9 @@ const MessageChannelPrototype = MessageChannel.prototype;
 
 const _id = Symbol("id");
 const _enabled = Symbol("enabled");
+const _refed = Symbol("refed");
+const _recvPromiseId = Symbol("recvPromiseId");
+const promiseIdSymbol = SymbolFor("Deno.core.internalPromiseId");
 
 /**
  * @param {number} id
@@ -79,6 +106,8 @@ function createMessagePort(id) {
   port[webidl.brand] = webidl.brand;
   setEventTargetData(port);
   port[_id] = id;
+  port[_refed] = true;
+  port[_recvPromiseId] = null;
   return port;
 }
 
@@ -87,6 +116,10 @@ class MessagePort extends EventTarget {
   [_id] = null;
   /** @type {boolean} */
   [_enabled] = false;
+  /** @type {boolean} */
+  [_refed] = true;
+  /** @type {number | null} */
+  [_recvPromiseId] = null;
 
   constructor() {
     super();
@@ -139,13 +172,20 @@ class MessagePort extends EventTarget {
         if (this[_id] === null) break;
         let data;
         try {
-          data = await core.opAsync(
+          const promise = core.opAsync(
             "op_message_port_recv_message",
             this[_id],
           );
+          this[_recvPromiseId] = promise[promiseIdSymbol];
+          if (!this[_refed]) {
+            core.unrefOp(this[_recvPromiseId]);
+          }
+          data = await promise;
         } catch (err) {
           if (ObjectPrototypeIsPrototypeOf(InterruptedPrototype, err)) break;
           throw err;
+        } finally {
+          this[_recvPromiseId] = null;
         }
         if (data === null) break;
         let message, transferables;
@@ -168,6 +208,8 @@ class MessagePort extends EventTarget {
         this.dispatchEvent(event);
       }
       this[_enabled] = false;
+      // the port was closed, either on this side or on the entangled one
+      this.dispatchEvent(new Event("close"));
     })();
   }
 
@@ -188,6 +230,25 @@ defineEventHandler(MessagePort.prototype, "messageerror");
 webidl.configurePrototype(MessagePort);
 const MessagePortPrototype = MessagePort.prototype;
 
+/**
+ * Sets whether a started port keeps the event loop alive while it waits for
+ * messages, like `ref()` and `unref()` of ports in Node.
+ * @param {MessagePort} port
+ * @param {boolean} ref
+ */
+function refMessagePort(port, ref) {
+  webidl.assertBranded(port, MessagePortPrototype);
+  port[_refed] = ref;
+  const promiseId = port[_recvPromiseId];
+  if (promiseId !== null) {
+    if (ref) {
+      core.refOp(promiseId);
+    } else {
+      core.unrefOp(promiseId);
+    }
+  }
+}
+
 /**
  * @returns {[number, number]}
  */
@@ -221,6 +282,20 @@ function deserializeJsMessageData(messageData) {
         ArrayPrototypePush(arrayBufferIdsInTransferables, index);
         break;
       }
+      case "readableStream": {
+        const port = createMessagePort(transferable.data);
+        const stream = readableStreamFromPort(port);
+        ArrayPrototypePush(transferables, stream);
+        ArrayPrototypePush(hostObjects, stream);
+        break;
+      }
+      case "writableStream": {
+        const port = createMessagePort(transferable.data);
+        const stream = writableStreamFromPort(port);
+        ArrayPrototypePush(transferables, stream);
+        ArrayPrototypePush(hostObjects, stream);
+        break;
+      }
       default:
         throw new TypeError("Unreachable");
     }
@@ -239,6 +314,65 @@ function deserializeJsMessageData(messageData) {
   return [data, transferables];
 }
 
+/**
+ * Streams aren't host objects, so the serializer can't transfer them. This
+ * copies the objects, arrays, maps and sets of a message with the transferred
+ * streams swapped for placeholders which are host objects, and that are
+ * deserialized as the streams on the other end.
+ * @param {any} value
+ * @param {Map<ReadableStream | WritableStream, object>} placeholders
+ * @param {Map<object, object>} copies
+ * @returns {any}
+ */
+function replaceStreams(value, placeholders, copies) {
+  if (typeof value !== "object" || value === null) {
+    return value;
+  }
+  const placeholder = placeholders.get(value);
+  if (placeholder !== undefined) {
+    return placeholder;
+  }
+  const existingCopy = copies.get(value);
+  if (existingCopy !== undefined) {
+    return existingCopy;
+  }
+
+  const proto = ObjectGetPrototypeOf(value);
+  let copy;
+  if (ArrayIsArray(value) || proto === ObjectPrototype || proto === null) {
+    copy = ArrayIsArray(value) ? [] : {};
+    copies.set(value, copy);
+    const keys = ObjectKeys(value);
+    for (let i = 0; i < keys.length; ++i) {
+      copy[keys[i]] = replaceStreams(value[keys[i]], placeholders, copies);
+    }
+    if (ArrayIsArray(value)) {
+      copy.length = value.length;
+    }
+  } else if (proto === MapPrototype) {
+    copy = new Map();
+    copies.set(value, copy);
+    MapPrototypeForEach(value, (v, k) => {
+      MapPrototypeSet(
+        copy,
+        replaceStreams(k, placeholders, copies),
+        replaceStreams(v, placeholders, copies),
+      );
+    });
+  } else if (proto === SetPrototype) {
+    copy = new Set();
+    copies.set(value, copy);
+    SetPrototypeForEach(value, (v) => {
+      SetPrototypeAdd(copy, replaceStreams(v, placeholders, copies));
+    });
+  } else {
+    // other objects are serialized as they are
+    copy = value;
+    copies.set(value, copy);
+  }
+  return copy;
+}
+
 /**
  * @param {any} data
  * @param {object[]} transferables
@@ -246,12 +380,15 @@ function deserializeJsMessageData(messageData) {
  */
 function serializeJsMessageData(data, transferables) {
   const transferredArrayBuffers = [];
+  const hostObjects = [];
+  /** @type {Map<ReadableStream | WritableStream, object>} */
+  const streamPlaceholders = new SafeMap();
   for (let i = 0, j = 0; i < transferables.length; i++) {
-    const ab = transferables[i];
-    if (ObjectPrototypeIsPrototypeOf(ArrayBufferPrototype, ab)) {
+    const transferable = transferables[i];
+    if (ObjectPrototypeIsPrototypeOf(ArrayBufferPrototype, transferable)) {
       if (
-        ArrayBufferPrototypeGetByteLength(ab) === 0 &&
-        ops.op_arraybuffer_was_detached(ab)
+        ArrayBufferPrototypeGetByteLength(transferable) === 0 &&
+        ops.op_arraybuffer_was_detached(transferable)
       ) {
         throw new DOMException(
           `ArrayBuffer at index ${j} is already detached`,
@@ -259,15 +396,37 @@ function serializeJsMessageData(data, transferables) {
         );
       }
       j++;
-      ArrayPrototypePush(transferredArrayBuffers, ab);
+      ArrayPrototypePush(transferredArrayBuffers, transferable);
+    } else if (
+      ObjectPrototypeIsPrototypeOf(MessagePortPrototype, transferable)
+    ) {
+      ArrayPrototypePush(hostObjects, transferable);
+    } else if (
+      ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, transferable) ||
+      ObjectPrototypeIsPrototypeOf(WritableStreamPrototype, transferable)
+    ) {
+      const locked =
+        ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, transferable)
+          ? isReadableStreamLocked(transferable)
+          : isWritableStreamLocked(transferable);
+      if (locked) {
+        throw new DOMException(
+          `Stream at index ${i} is locked`,
+          "DataCloneError",
+        );
+      }
+      const placeholder = core.createHostObject();
+      streamPlaceholders.set(transferable, placeholder);
+      ArrayPrototypePush(hostObjects, placeholder);
     }
   }
 
+  if (streamPlaceholders.size > 0) {
+    data = replaceStreams(data, streamPlaceholders, new SafeMap());
+  }
+
   const serializedData = core.serialize(data, {
-    hostObjects: ArrayPrototypeFilter(
-      transferables,
-      (a) => ObjectPrototypeIsPrototypeOf(MessagePortPrototype, a),
-    ),
+    hostObjects,
     transferredArrayBuffers,
   }, (err) => {
     throw new DOMException(err, "DataCloneError");
@@ -301,6 +460,26 @@ function serializeJsMessageData(data, transferables) {
         data: transferredArrayBuffers[arrayBufferI],
       });
       arrayBufferI++;
+    } else if (
+      ObjectPrototypeIsPrototypeOf(ReadableStreamPrototype, transferable)
+    ) {
+      const { 0: portId, 1: transferredPortId } =
+        opCreateEntangledMessagePort();
+      transferReadableStream(transferable, createMessagePort(portId));
+      ArrayPrototypePush(serializedTransferables, {
+        kind: "readableStream",
+        data: transferredPortId,
+      });
+    } else if (
+      ObjectPrototypeIsPrototypeOf(WritableStreamPrototype, transferable)
+    ) {
+      const { 0: portId, 1: transferredPortId } =
+        opCreateEntangledMessagePort();
+      transferWritableStream(transferable, createMessagePort(portId));
+      ArrayPrototypePush(serializedTransferables, {
+        kind: "writableStream",
+        data: transferredPortId,
+      });
     } else {
       throw new DOMException("Value not transferable", "DataCloneError");
     }
@@ -343,6 +522,7 @@ export {
   MessageChannel,
   MessagePort,
   MessagePortPrototype,
+  refMessagePort,
   serializeJsMessageData,
   structuredClone,
 };
@@ -9,6 +9,8 @@ const core = globalThis.Deno.core;
 const ops = core.ops;
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  RangeError,
+  TypeError,
   TypedArrayPrototypeGetByteLength,
 } = primordials;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
@@ -20,18 +22,111 @@ webidl.converters.CompressionFormat = webidl.createEnumConverter(
     "deflate",
     "deflate-raw",
     "gzip",
+    "br",
+    "zstd",
   ],
 );
 
+webidl.converters.CompressionFlushMode = webidl.createEnumConverter(
+  "CompressionFlushMode",
+  [
+    "none",
+    "sync",
+    "full",
+  ],
+);
+
+webidl.converters.CompressionOptions = webidl.createDictionaryConverter(
+  "CompressionOptions",
+  [
+    {
+      key: "level",
+      converter: (V, prefix, context, opts) =>
+        webidl.converters.octet(V, prefix, context, {
+          ...opts,
+          enforceRange: true,
+        }),
+    },
+    {
+      key: "dictionary",
+      converter: webidl.converters.BufferSource,
+    },
+    {
+      key: "flush",
+      converter: webidl.converters.CompressionFlushMode,
+    },
+  ],
+);
+
+webidl.converters.DecompressionOptions = webidl.createDictionaryConverter(
+  "DecompressionOptions",
+  [
+    {
+      key: "dictionary",
+      converter: webidl.converters.BufferSource,
+    },
+  ],
+);
+
+/** The highest compression levels of the formats that don't stop at 9. */
+const MAX_LEVELS = {
+  __proto__: null,
+  br: 11,
+  zstd: 22,
+};
+
+/**
+ * Creates the resource for a stream. The level, dictionary and flush mode
+ * are a non-standard extension that needs `--unstable`, which is checked by
+ * the op.
+ * @param {string} format
+ * @param {boolean} isDecoder
+ * @param {CompressionOptions} options
+ * @param {string} prefix
+ * @returns {number}
+ */
+function createCompressionResource(format, isDecoder, options, prefix) {
+  const { level, dictionary, flush } = options;
+  if (level === undefined && dictionary === undefined && flush === undefined) {
+    return ops.op_compression_new(format, isDecoder);
+  }
+  const maxLevel = MAX_LEVELS[format] ?? 9;
+  if (level !== undefined && level > maxLevel) {
+    throw new RangeError(
+      `${prefix}: The compression level must be between 0 and ${maxLevel}.`,
+    );
+  }
+  if (
+    dictionary !== undefined && format !== "deflate" &&
+    format !== "deflate-raw"
+  ) {
+    throw new TypeError(
+      `${prefix}: A dictionary is not supported for the "${format}" format.`,
+    );
+  }
+  return ops.op_compression_new_with_options(
+    format,
+    isDecoder,
+    level ?? null,
+    dictionary ?? null,
+    flush ?? null,
+  );
+}
+
 class CompressionStream {
   #transform;
 
-  constructor(format) {
+  constructor(format, options = {}) {
     const prefix = "Failed to construct 'CompressionStream'";
     webidl.requiredArguments(arguments.length, 1, prefix);
     format = webidl.converters.CompressionFormat(format, prefix, "Argument 1");
+    options = webidl.converters.CompressionOptions(
+      options,
+      prefix,
+      "Argument 2",
+    );
 
-    const rid = ops.op_compression_new(format, false);
+    const rid = createCompressionResource(format, false, options, prefix);
 
     this.#transform = new TransformStream({
       transform(chunk, controller) {
@@ -68,12 +163,17 @@ const CompressionStreamPrototype = CompressionStream.prototype;
 class DecompressionStream {
   #transform;
 
-  constructor(format) {
+  constructor(format, options = {}) {
     const prefix = "Failed to construct 'DecompressionStream'";
     webidl.requiredArguments(arguments.length, 1, prefix);
     format = webidl.converters.CompressionFormat(format, prefix, "Argument 1");
+    options = webidl.converters.DecompressionOptions(
+      options,
+      prefix,
+      "Argument 2",
+    );
 
-    const rid = ops.op_compression_new(format, true);
+    const rid = createCompressionResource(format, true, options, prefix);
 
     this.#transform = new TransformStream({
       transform(chunk, controller) {
@@ -4,12 +4,23 @@ const primordials = globalThis.__bootstrap.primordials;
 const {
   ArrayPrototypeFilter,
   ArrayPrototypeFind,
+  ArrayPrototypeIncludes,
   ArrayPrototypePush,
   ArrayPrototypeReverse,
   ArrayPrototypeSlice,
+  ArrayPrototypeSort,
+  FunctionPrototypeCall,
+  ObjectFreeze,
   ObjectKeys,
   ObjectPrototypeIsPrototypeOf,
   ReflectHas,
+  SafeArrayIterator,
+  SafeSet,
+  SafeSetIterator,
+  SetPrototypeAdd,
+  SetPrototypeClear,
+  SetPrototypeDelete,
+  SetPrototypeHas,
   Symbol,
   SymbolFor,
   TypeError,
@@ -17,14 +28,23 @@ const {
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { structuredClone } from "ext:deno_web/02_structured_clone.js";
 import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
-import { EventTarget } from "ext:deno_web/02_event.js";
-import { opNow } from "ext:deno_web/02_timers.js";
+import {
+  Event,
+  EventTarget,
+  reportException,
+} from "ext:deno_web/02_event.js";
+import { opNow, setTimeout, unrefTimer } from "ext:deno_web/02_timers.js";
 import DOMException from "ext:deno_web/01_dom_exception.js";
 
 const illegalConstructorKey = Symbol("illegalConstructorKey");
 const customInspect = SymbolFor("Deno.customInspect");
 let performanceEntries = [];
 let timeOrigin;
+let supportedEntryTypes = ObjectFreeze(["mark", "measure", "resource"]);
+const registeredObservers = new SafeSet();
+let isObserverTaskQueued = false;
+let resourceTimingBufferSize = 250;
+let resourceTimingCount = 0;
 
 webidl.converters["PerformanceMarkOptions"] = webidl
   .createDictionaryConverter(
@@ -41,6 +61,27 @@ webidl.converters["PerformanceMarkOptions"] = webidl
     ],
   );
 
+webidl.converters["PerformanceObserverInit"] = webidl
+  .createDictionaryConverter(
+    "PerformanceObserverInit",
+    [
+      {
+        key: "entryTypes",
+        converter: webidl.createSequenceConverter(
+          webidl.converters.DOMString,
+        ),
+      },
+      {
+        key: "type",
+        converter: webidl.converters.DOMString,
+      },
+      {
+        key: "buffered",
+        converter: webidl.converters.boolean,
+      },
+    ],
+  );
+
 webidl.converters["DOMString or DOMHighResTimeStamp"] = (
   V,
   prefix,
@@ -127,9 +168,10 @@ function convertMarkToTimestamp(mark) {
 function filterByNameType(
   name,
   type,
+  entries = performanceEntries,
 ) {
   return ArrayPrototypeFilter(
-    performanceEntries,
+    entries,
     (entry) =>
       (name ? entry.name === name : true) &&
       (type ? entry.entryType === type : true),
@@ -138,6 +180,72 @@ function filterByNameType(
 
 const now = opNow;
 
+/** Adds an entry to the performance entry buffer, and queues it to the
+ * observers of its type. */
+function queueEntry(entry, addToBuffer = true) {
+  if (addToBuffer) {
+    ArrayPrototypePush(performanceEntries, entry);
+  }
+  for (const observer of new SafeSetIterator(registeredObservers)) {
+    if (SetPrototypeHas(observer[_entryTypes], entry[_entryType])) {
+      ArrayPrototypePush(observer[_buffer], entry);
+      queueObserverTask();
+    }
+  }
+}
+
+/** Lets node:perf_hooks report the entries of its own APIs, like the
+ * "function" ones of `timerify()`, to the same observers. */
+function addSupportedEntryType(type) {
+  if (ArrayPrototypeIncludes(supportedEntryTypes, type)) {
+    return;
+  }
+  const types = ArrayPrototypeSlice(supportedEntryTypes);
+  ArrayPrototypePush(types, type);
+  ArrayPrototypeSort(types);
+  supportedEntryTypes = ObjectFreeze(types);
+}
+
+function queueObserverTask() {
+  if (isObserverTaskQueued) {
+    return;
+  }
+  isObserverTaskQueued = true;
+  // observers don't keep the process alive
+  const id = setTimeout(() => {
+    isObserverTaskQueued = false;
+    // the observers that are registered while the callbacks run are only
+    // called on the next task
+    const observers = [];
+    for (const observer of new SafeSetIterator(registeredObservers)) {
+      ArrayPrototypePush(observers, observer);
+    }
+    for (let i = 0; i < observers.length; ++i) {
+      const observer = observers[i];
+      const entries = observer[_buffer];
+      if (entries.length === 0) {
+        continue;
+      }
+      observer[_buffer] = [];
+      const entryList = new PerformanceObserverEntryList(
+        entries,
+        illegalConstructorKey,
+      );
+      try {
+        FunctionPrototypeCall(
+          observer[_callback],
+          observer,
+          entryList,
+          observer,
+        );
+      } catch (err) {
+        reportException(err);
+      }
+    }
+  }, 0);
+  unrefTimer(id);
+}
+
 const _name = Symbol("[[name]]");
 const _entryType = Symbol("[[entryType]]");
 const _startTime = Symbol("[[startTime]]");
@@ -340,6 +448,323 @@ class PerformanceMeasure extends PerformanceEntry {
 }
 webidl.configurePrototype(PerformanceMeasure);
 const PerformanceMeasurePrototype = PerformanceMeasure.prototype;
+
+const _initiatorType = Symbol("[[initiatorType]]");
+const _responseStart = Symbol("[[responseStart]]");
+const _responseStatus = Symbol("[[responseStatus]]");
+/** The timing of a fetch. Deno doesn't know about the connection of a
+ * request, so the timestamps in between `fetchStart` and `responseStart` are
+ * `0`, and the entry is reported when the response headers are received. */
+class PerformanceResourceTiming extends PerformanceEntry {
+  [_initiatorType] = "";
+  [_responseStart] = 0;
+  [_responseStatus] = 0;
+
+  get entryType() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return "resource";
+  }
+
+  get initiatorType() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return this[_initiatorType];
+  }
+
+  get nextHopProtocol() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return "";
+  }
+
+  get fetchStart() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return this[_startTime];
+  }
+
+  get responseStart() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return this[_responseStart];
+  }
+
+  get responseEnd() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return this[_startTime] + this[_duration];
+  }
+
+  get responseStatus() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return this[_responseStatus];
+  }
+
+  constructor(
+    name = null,
+    initiatorType = null,
+    startTime = null,
+    responseStart = null,
+    responseStatus = null,
+    key = undefined,
+  ) {
+    if (key !== illegalConstructorKey) {
+      webidl.illegalConstructor();
+    }
+
+    super(name, "resource", startTime, responseStart - startTime, key);
+    this[webidl.brand] = webidl.brand;
+    this[_initiatorType] = initiatorType;
+    this[_responseStart] = responseStart;
+    this[_responseStatus] = responseStatus;
+  }
+
+  toJSON() {
+    webidl.assertBranded(this, PerformanceResourceTimingPrototype);
+    return {
+      name: this.name,
+      entryType: this.entryType,
+      startTime: this.startTime,
+      duration: this.duration,
+      initiatorType: this.initiatorType,
+      nextHopProtocol: this.nextHopProtocol,
+      fetchStart: this.fetchStart,
+      responseStart: this.responseStart,
+      responseEnd: this.responseEnd,
+      responseStatus: this.responseStatus,
+    };
+  }
+
+  [customInspect](inspect) {
+    return inspect(createFilteredInspectProxy({
+      object: this,
+      evaluate: ObjectPrototypeIsPrototypeOf(
+        PerformanceResourceTimingPrototype,
+        this,
+      ),
+      keys: [
+        "name",
+        "entryType",
+        "startTime",
+        "duration",
+        "initiatorType",
+        "fetchStart",
+        "responseStart",
+        "responseEnd",
+        "responseStatus",
+      ],
+    }));
+  }
+}
+webidl.configurePrototype(PerformanceResourceTiming);
+const PerformanceResourceTimingPrototype = PerformanceResourceTiming.prototype;
+
+/** Records the timing of a fetch, from when `fetch()` was called until the
+ * response headers were received. */
+function markResourceTiming(url, startTime, responseStatus) {
+  addResourceTiming(
+    new PerformanceResourceTiming(
+      url,
+      "fetch",
+      startTime,
+      now(),
+      responseStatus,
+      illegalConstructorKey,
+    ),
+  );
+}
+
+/** Adds a resource timing entry to the buffer, unless it is full, and queues
+ * it to the observers. */
+function addResourceTiming(entry) {
+  if (resourceTimingCount >= resourceTimingBufferSize) {
+    // give the listeners a chance to make some room
+    performance.dispatchEvent(new Event("resourcetimingbufferfull"));
+  }
+  const addToBuffer = resourceTimingCount < resourceTimingBufferSize;
+  if (addToBuffer) {
+    resourceTimingCount++;
+  }
+  queueEntry(entry, addToBuffer);
+}
+
+const _entries = Symbol("[[entries]]");
+class PerformanceObserverEntryList {
+  [_entries] = [];
+
+  constructor(entries = null, key = undefined) {
+    if (key !== illegalConstructorKey) {
+      webidl.illegalConstructor();
+    }
+    this[webidl.brand] = webidl.brand;
+    this[_entries] = entries;
+  }
+
+  getEntries() {
+    webidl.assertBranded(this, PerformanceObserverEntryListPrototype);
+    return filterByNameType(undefined, undefined, this[_entries]);
+  }
+
+  getEntriesByType(type) {
+    webidl.assertBranded(this, PerformanceObserverEntryListPrototype);
+    const prefix =
+      "Failed to execute 'getEntriesByType' on 'PerformanceObserverEntryList'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+
+    type = webidl.converters.DOMString(type, prefix, "Argument 1");
+
+    return filterByNameType(undefined, type, this[_entries]);
+  }
+
+  getEntriesByName(name, type = undefined) {
+    webidl.assertBranded(this, PerformanceObserverEntryListPrototype);
+    const prefix =
+      "Failed to execute 'getEntriesByName' on 'PerformanceObserverEntryList'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+
+    name = webidl.converters.DOMString(name, prefix, "Argument 1");
+
+    if (type !== undefined) {
+      type = webidl.converters.DOMString(type, prefix, "Argument 2");
+    }
+
+    return filterByNameType(name, type, this[_entries]);
+  }
+
+  [customInspect](inspect) {
+    return inspect(createFilteredInspectProxy({
+      object: this,
+      evaluate: ObjectPrototypeIsPrototypeOf(
+        PerformanceObserverEntryListPrototype,
+        this,
+      ),
+      keys: [],
+    }));
+  }
+}
+webidl.configurePrototype(PerformanceObserverEntryList);
+const PerformanceObserverEntryListPrototype =
+  PerformanceObserverEntryList.prototype;
+
+const _callback = Symbol("[[callback]]");
+const _buffer = Symbol("[[buffer]]");
+const _entryTypes = Symbol("[[entryTypes]]");
+const _observerType = Symbol("[[observerType]]");
+class PerformanceObserver {
+  [_callback] = null;
+  [_buffer] = [];
+  [_entryTypes] = new SafeSet();
+  /** Either "undefined", "single" or "multiple", depending on how `observe()`
+   * was first called. */
+  [_observerType] = "undefined";
+
+  static get supportedEntryTypes() {
+    return supportedEntryTypes;
+  }
+
+  constructor(callback) {
+    const prefix = "Failed to construct 'PerformanceObserver'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    callback = webidl.converters.Function(callback, prefix, "Argument 1");
+    this[webidl.brand] = webidl.brand;
+    this[_callback] = callback;
+  }
+
+  observe(options = {}) {
+    webidl.assertBranded(this, PerformanceObserverPrototype);
+    const prefix = "Failed to execute 'observe' on 'PerformanceObserver'";
+    options = webidl.converters.PerformanceObserverInit(
+      options,
+      prefix,
+      "Argument 1",
+    );
+
+    const { entryTypes, type, buffered } = options;
+    if (entryTypes === undefined && type === undefined) {
+      throw new TypeError(
+        `${prefix}: either 'entryTypes' or 'type' must be specified.`,
+      );
+    }
+    if (
+      entryTypes !== undefined && (type !== undefined || buffered !== undefined)
+    ) {
+      throw new TypeError(
+        `${prefix}: 'entryTypes' can't be specified with 'type' or 'buffered'.`,
+      );
+    }
+    if (this[_observerType] === "undefined") {
+      this[_observerType] = entryTypes !== undefined ? "multiple" : "single";
+    }
+    if (this[_observerType] === "single" && entryTypes !== undefined) {
+      throw new DOMException(
+        `${prefix}: 'entryTypes' can't be specified after 'type'.`,
+        "InvalidModificationError",
+      );
+    }
+    if (this[_observerType] === "multiple" && type !== undefined) {
+      throw new DOMException(
+        `${prefix}: 'type' can't be specified after 'entryTypes'.`,
+        "InvalidModificationError",
+      );
+    }
+
+    if (this[_observerType] === "multiple") {
+      const types = ArrayPrototypeFilter(
+        entryTypes,
+        (type) => ArrayPrototypeIncludes(supportedEntryTypes, type),
+      );
+      if (types.length === 0) {
+        return;
+      }
+      SetPrototypeClear(this[_entryTypes]);
+      for (const type of new SafeArrayIterator(types)) {
+        SetPrototypeAdd(this[_entryTypes], type);
+      }
+    } else {
+      if (!ArrayPrototypeIncludes(supportedEntryTypes, type)) {
+        return;
+      }
+      SetPrototypeAdd(this[_entryTypes], type);
+      if (buffered) {
+        const entries = filterByNameType(undefined, type);
+        if (entries.length > 0) {
+          for (const entry of new SafeArrayIterator(entries)) {
+            ArrayPrototypePush(this[_buffer], entry);
+          }
+          ArrayPrototypeSort(
+            this[_buffer],
+            (a, b) => a[_startTime] - b[_startTime],
+          );
+          queueObserverTask();
+        }
+      }
+    }
+    SetPrototypeAdd(registeredObservers, this);
+  }
+
+  disconnect() {
+    webidl.assertBranded(this, PerformanceObserverPrototype);
+    SetPrototypeDelete(registeredObservers, this);
+    this[_buffer] = [];
+    SetPrototypeClear(this[_entryTypes]);
+    this[_observerType] = "undefined";
+  }
+
+  takeRecords() {
+    webidl.assertBranded(this, PerformanceObserverPrototype);
+    const entries = this[_buffer];
+    this[_buffer] = [];
+    return entries;
+  }
+
+  [customInspect](inspect) {
+    return inspect(createFilteredInspectProxy({
+      object: this,
+      evaluate: ObjectPrototypeIsPrototypeOf(
+        PerformanceObserverPrototype,
+        this,
+      ),
+      keys: [],
+    }));
+  }
+}
+webidl.configurePrototype(PerformanceObserver);
+const PerformanceObserverPrototype = PerformanceObserver.prototype;
 class Performance extends EventTarget {
   constructor(key = null) {
     if (key != illegalConstructorKey) {
@@ -398,6 +823,27 @@ class Performance extends EventTarget {
     }
   }
 
+  clearResourceTimings() {
+    webidl.assertBranded(this, PerformancePrototype);
+    performanceEntries = ArrayPrototypeFilter(
+      performanceEntries,
+      (entry) => entry.entryType !== "resource",
+    );
+    resourceTimingCount = 0;
+  }
+
+  setResourceTimingBufferSize(maxSize) {
+    webidl.assertBranded(this, PerformancePrototype);
+    const prefix =
+      "Failed to execute 'setResourceTimingBufferSize' on 'Performance'";
+    webidl.requiredArguments(arguments.length, 1, prefix);
+    resourceTimingBufferSize = webidl.converters["unsigned long"](
+      maxSize,
+      prefix,
+      "Argument 1",
+    );
+  }
+
   getEntries() {
     webidl.assertBranded(this, PerformancePrototype);
     return filterByNameType();
@@ -450,8 +896,7 @@ class Performance extends EventTarget {
     // same name as a read only attribute in the PerformanceTiming interface,
     // throw a SyntaxError. - not implemented
     const entry = new PerformanceMark(markName, markOptions);
-    // 3.1.1.7 Queue entry - not implemented
-    ArrayPrototypePush(performanceEntries, entry);
+    queueEntry(entry);
     return entry;
   }
 
@@ -553,7 +998,7 @@ class Performance extends EventTarget {
         : null,
       illegalConstructorKey,
     );
-    ArrayPrototypePush(performanceEntries, entry);
+    queueEntry(entry);
     return entry;
   }
 
@@ -588,10 +1033,18 @@ webidl.converters["Performance"] = webidl.createInterfaceConverter(
 const performance = new Performance(illegalConstructorKey);
 
 export {
+  addResourceTiming,
+  addSupportedEntryType,
+  illegalConstructorKey,
+  markResourceTiming,
   Performance,
   performance,
   PerformanceEntry,
   PerformanceMark,
   PerformanceMeasure,
+  PerformanceObserver,
+  PerformanceObserverEntryList,
+  PerformanceResourceTiming,
+  queueEntry,
   setTimeOrigin,
 };
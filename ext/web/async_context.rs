@@ -0,0 +1,54 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Native access to the current async context of `01_async_context.js`, for
+//! code that follows it too, like the promise hook of node:async_hooks that
+//! carries it across promise reactions without calling into JS for every
+//! promise. The context stays in JS: it is only shared once such code asks
+//! for it with `shareAsyncContext()`.
+
+use deno_core::op;
+use deno_core::serde_v8;
+use deno_core::v8;
+
+/// The array whose first element is the current async context.
+struct AsyncContext(v8::Global<v8::Array>);
+
+/// The current async context, or `undefined` if none was entered or the
+/// context wasn't shared with native code.
+pub fn get_async_context<'s>(
+  scope: &mut v8::HandleScope<'s>,
+) -> v8::Local<'s, v8::Value> {
+  let holder = scope
+    .get_slot::<AsyncContext>()
+    .map(|context| context.0.clone());
+  holder
+    .and_then(|holder| v8::Local::new(scope, holder).get_index(scope, 0))
+    .unwrap_or_else(|| v8::undefined(scope).into())
+}
+
+/// Makes `context` the current async context, and returns the previous one.
+/// Does nothing if the context wasn't shared with native code.
+pub fn set_async_context<'s>(
+  scope: &mut v8::HandleScope<'s>,
+  context: v8::Local<v8::Value>,
+) -> v8::Local<'s, v8::Value> {
+  let previous = get_async_context(scope);
+  let holder = scope
+    .get_slot::<AsyncContext>()
+    .map(|context| context.0.clone());
+  if let Some(holder) = holder {
+    v8::Local::new(scope, holder).set_index(scope, 0, context);
+  }
+  previous
+}
+
+#[op(v8)]
+pub fn op_async_context_share<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  holder: serde_v8::Value<'a>,
+) {
+  let holder = v8::Local::<v8::Array>::try_from(holder.v8_value)
+    .expect("the async context is kept in an array");
+  let holder = v8::Global::new(scope, holder);
+  scope.set_slot(AsyncContext(holder));
+}
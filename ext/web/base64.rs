@@ -0,0 +1,311 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The base64 methods of `Uint8Array`, which follow the TC39 proposal:
+//! https://tc39.es/proposal-arraybuffer-base64/spec/
+//!
+//! `op_base64_decode_into` decodes as much of its input as fits in the
+//! buffer and reports how much it read, so that large payloads can be decoded
+//! chunk by chunk.
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::ZeroCopyBuf;
+
+const INVALID: u8 = 0xff;
+
+const fn decode_table(url: bool) -> [u8; 256] {
+  let mut table = [INVALID; 256];
+  let mut i = 0;
+  while i < 26 {
+    table[b'A' as usize + i] = i as u8;
+    table[b'a' as usize + i] = 26 + i as u8;
+    i += 1;
+  }
+  let mut i = 0;
+  while i < 10 {
+    table[b'0' as usize + i] = 52 + i as u8;
+    i += 1;
+  }
+  if url {
+    table[b'-' as usize] = 62;
+    table[b'_' as usize] = 63;
+  } else {
+    table[b'+' as usize] = 62;
+    table[b'/' as usize] = 63;
+  }
+  table
+}
+
+static STANDARD_TABLE: [u8; 256] = decode_table(false);
+static URL_TABLE: [u8; 256] = decode_table(true);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LastChunkHandling {
+  Loose,
+  Strict,
+  StopBeforePartial,
+}
+
+impl LastChunkHandling {
+  fn parse(value: &str) -> Result<Self, AnyError> {
+    match value {
+      "loose" => Ok(Self::Loose),
+      "strict" => Ok(Self::Strict),
+      "stop-before-partial" => Ok(Self::StopBeforePartial),
+      _ => Err(type_error(format!("Invalid lastChunkHandling: {value}"))),
+    }
+  }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Decoded {
+  /// How many characters of the input were decoded.
+  read: usize,
+  /// How many bytes were written to the output.
+  written: usize,
+}
+
+fn syntax_error(message: &str) -> AnyError {
+  type_error(format!("Failed to decode base64: {message}"))
+}
+
+fn skip_ascii_whitespace(input: &[u8], mut index: usize) -> usize {
+  while index < input.len()
+    && matches!(input[index], b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+  {
+    index += 1;
+  }
+  index
+}
+
+/// Decodes the values of a chunk of 2 to 4 characters into `output`, and
+/// returns how many bytes it wrote.
+fn decode_chunk(
+  chunk: &[u8],
+  throw_on_extra_bits: bool,
+  output: &mut [u8],
+) -> Result<usize, AnyError> {
+  let mut bits = 0u32;
+  for (i, value) in chunk.iter().enumerate() {
+    bits |= (*value as u32) << (18 - 6 * i);
+  }
+  let bytes = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+  let len = chunk.len() - 1;
+  if throw_on_extra_bits && len < 3 && bytes[len] != 0 {
+    return Err(syntax_error("the last chunk has extra bits"));
+  }
+  output[..len].copy_from_slice(&bytes[..len]);
+  Ok(len)
+}
+
+/// Decodes `input` into `output`, up to its length. This is the FromBase64
+/// operation of the proposal, except that the bytes are written to `output`
+/// as they are decoded: on error, the ones of the chunks before it have been
+/// written already.
+fn decode_into(
+  input: &[u8],
+  url: bool,
+  last_chunk_handling: LastChunkHandling,
+  output: &mut [u8],
+) -> Result<Decoded, AnyError> {
+  let max_length = output.len();
+  let mut decoded = Decoded::default();
+  if max_length == 0 {
+    return Ok(decoded);
+  }
+  let table = if url { &URL_TABLE } else { &STANDARD_TABLE };
+  let mut chunk = [0u8; 4];
+  let mut chunk_length = 0;
+  let mut index = 0;
+  let length = input.len();
+  loop {
+    index = skip_ascii_whitespace(input, index);
+    if index == length {
+      if chunk_length > 0 {
+        match last_chunk_handling {
+          LastChunkHandling::StopBeforePartial => return Ok(decoded),
+          LastChunkHandling::Loose if chunk_length > 1 => {
+            decoded.written += decode_chunk(
+              &chunk[..chunk_length],
+              false,
+              &mut output[decoded.written..],
+            )?;
+          }
+          _ => return Err(syntax_error("the last chunk is incomplete")),
+        }
+      }
+      decoded.read = length;
+      return Ok(decoded);
+    }
+
+    let char = input[index];
+    index += 1;
+    if char == b'=' {
+      if chunk_length < 2 {
+        return Err(syntax_error("unexpected padding"));
+      }
+      index = skip_ascii_whitespace(input, index);
+      if chunk_length == 2 {
+        if index == length {
+          if last_chunk_handling == LastChunkHandling::StopBeforePartial {
+            return Ok(decoded);
+          }
+          return Err(syntax_error("the padding is incomplete"));
+        }
+        if input[index] == b'=' {
+          index = skip_ascii_whitespace(input, index + 1);
+        }
+      }
+      if index < length {
+        return Err(syntax_error("unexpected data after the padding"));
+      }
+      decoded.written += decode_chunk(
+        &chunk[..chunk_length],
+        last_chunk_handling == LastChunkHandling::Strict,
+        &mut output[decoded.written..],
+      )?;
+      decoded.read = length;
+      return Ok(decoded);
+    }
+
+    let value = table[char as usize];
+    if value == INVALID {
+      return Err(syntax_error("invalid character"));
+    }
+    // a partial chunk wouldn't fit, so it is left for the next call
+    let remaining = max_length - decoded.written;
+    if (remaining == 1 && chunk_length == 2)
+      || (remaining == 2 && chunk_length == 3)
+    {
+      return Ok(decoded);
+    }
+    chunk[chunk_length] = value;
+    chunk_length += 1;
+    if chunk_length == 4 {
+      decoded.written +=
+        decode_chunk(&chunk, false, &mut output[decoded.written..])?;
+      chunk_length = 0;
+      decoded.read = index;
+      if decoded.written == max_length {
+        return Ok(decoded);
+      }
+    }
+  }
+}
+
+#[op]
+pub fn op_base64_decode_with_options(
+  input: String,
+  url: bool,
+  last_chunk_handling: String,
+) -> Result<ZeroCopyBuf, AnyError> {
+  let last_chunk_handling = LastChunkHandling::parse(&last_chunk_handling)?;
+  let mut output = vec![0; input.len() / 4 * 3 + 2];
+  let decoded =
+    decode_into(input.as_bytes(), url, last_chunk_handling, &mut output)?;
+  output.truncate(decoded.written);
+  Ok(output.into())
+}
+
+/// Writes how many characters were read and how many bytes were written to
+/// `out_buf`.
+#[op]
+pub fn op_base64_decode_into(
+  input: String,
+  url: bool,
+  last_chunk_handling: String,
+  buffer: &mut [u8],
+  out_buf: &mut [u32],
+) -> Result<(), AnyError> {
+  let last_chunk_handling = LastChunkHandling::parse(&last_chunk_handling)?;
+  let decoded =
+    decode_into(input.as_bytes(), url, last_chunk_handling, buffer)?;
+  out_buf[0] = decoded.read as u32;
+  out_buf[1] = decoded.written as u32;
+  Ok(())
+}
+
+#[op]
+pub fn op_base64_encode_with_options(
+  input: &[u8],
+  url: bool,
+  omit_padding: bool,
+) -> String {
+  match (url, omit_padding) {
+    (false, false) => base64_simd::STANDARD.encode_to_string(input),
+    (false, true) => base64_simd::STANDARD_NO_PAD.encode_to_string(input),
+    (true, false) => base64_simd::URL_SAFE.encode_to_string(input),
+    (true, true) => base64_simd::URL_SAFE_NO_PAD.encode_to_string(input),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode(
+    input: &str,
+    url: bool,
+    last_chunk_handling: &str,
+    max_length: usize,
+  ) -> Result<(Decoded, Vec<u8>), AnyError> {
+    let mut output = vec![0; max_length];
+    let decoded = decode_into(
+      input.as_bytes(),
+      url,
+      LastChunkHandling::parse(last_chunk_handling).unwrap(),
+      &mut output,
+    )?;
+    output.truncate(decoded.written);
+    Ok((decoded, output))
+  }
+
+  #[test]
+  fn decode_complete() {
+    let (decoded, bytes) = decode("Zm9v YmFy\n", false, "loose", 10).unwrap();
+    assert_eq!((decoded.read, decoded.written), (10, 6));
+    assert_eq!(bytes, b"foobar");
+    let (_, bytes) = decode("Zm9vYg==", false, "strict", 10).unwrap();
+    assert_eq!(bytes, b"foob");
+    let (_, bytes) = decode("_-8", true, "loose", 10).unwrap();
+    assert_eq!(bytes, [0xff, 0xef]);
+    assert!(decode("_-8", false, "loose", 10).is_err());
+    assert!(decode("+/8", true, "loose", 10).is_err());
+  }
+
+  #[test]
+  fn decode_last_chunk() {
+    let (_, bytes) = decode("Zm9vYg", false, "loose", 10).unwrap();
+    assert_eq!(bytes, b"foob");
+    assert!(decode("Zm9vYg", false, "strict", 10).is_err());
+    assert!(decode("Zm9vY", false, "loose", 10).is_err());
+    // extra bits are only rejected when strict
+    assert!(decode("Zm9vYh==", false, "strict", 10).is_err());
+    assert!(decode("Zm9vYh==", false, "loose", 10).is_ok());
+    assert!(decode("Zm9vYg=", false, "loose", 10).is_err());
+    assert!(decode("Zm9vYg==Zg==", false, "loose", 10).is_err());
+
+    let (decoded, bytes) =
+      decode("Zm9vYg", false, "stop-before-partial", 10).unwrap();
+    assert_eq!((decoded.read, decoded.written), (4, 3));
+    assert_eq!(bytes, b"foo");
+    let (decoded, _) =
+      decode("Zm9vYg=", false, "stop-before-partial", 10).unwrap();
+    assert_eq!((decoded.read, decoded.written), (4, 3));
+  }
+
+  #[test]
+  fn decode_into_small_buffer() {
+    let (decoded, bytes) = decode("Zm9vYmFy", false, "loose", 4).unwrap();
+    assert_eq!((decoded.read, decoded.written), (4, 3));
+    assert_eq!(bytes, b"foo");
+    let (decoded, bytes) = decode("Zm9vYmFy", false, "loose", 0).unwrap();
+    assert_eq!(decoded, Decoded::default());
+    assert!(bytes.is_empty());
+    // a final partial chunk that fits is decoded
+    let (decoded, bytes) = decode("Zm9vYg==", false, "loose", 4).unwrap();
+    assert_eq!((decoded.read, decoded.written), (8, 4));
+    assert_eq!(bytes, b"foob");
+  }
+}
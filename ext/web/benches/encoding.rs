@@ -30,15 +30,20 @@ fn setup() -> Vec<Extension> {
     deno_web::deno_web::init_ops_and_esm::<Permissions>(
       BlobStore::default(),
       None,
+      false,
     ),
     Extension::builder("bench_setup")
       .esm(vec![ExtensionFileSource {
         specifier: "ext:bench_setup/setup",
         code: ExtensionFileSourceCode::IncludedInBinary(
           r#"
-        import { TextDecoder } from "ext:deno_web/08_text_encoding.js";
+        import { TextDecoder, TextEncoder } from "ext:deno_web/08_text_encoding.js";
         globalThis.TextDecoder = TextDecoder;
+        globalThis.TextEncoder = TextEncoder;
         globalThis.hello12k = Deno.core.encode("hello world\n".repeat(1e3));
+        globalThis.helloTwoByte12k = "hello wörld €\n".repeat(1e3);
+        globalThis.latin1_12k = new Uint8Array(12 * 1024).fill(0xe9);
+        globalThis.buffer24k = new Uint8Array(24 * 1024);
         "#,
         ),
       }])
@@ -54,5 +59,22 @@ fn bench_encode_12kb(b: &mut Bencher) {
   bench_js_sync(b, r#"new TextDecoder().decode(hello12k);"#, setup);
 }
 
-benchmark_group!(benches, bench_encode_12kb);
+fn bench_encode_into_two_byte_12kb(b: &mut Bencher) {
+  bench_js_sync(
+    b,
+    r#"new TextEncoder().encodeInto(helloTwoByte12k, buffer24k);"#,
+    setup,
+  );
+}
+
+fn bench_decode_latin1_12kb(b: &mut Bencher) {
+  bench_js_sync(b, r#"new TextDecoder("latin1").decode(latin1_12k);"#, setup);
+}
+
+benchmark_group!(
+  benches,
+  bench_encode_12kb,
+  bench_encode_into_two_byte_12kb,
+  bench_decode_latin1_12kb
+);
 bench_or_profile!(benches);
@@ -25,7 +25,11 @@ fn setup() -> Vec<Extension> {
     deno_webidl::deno_webidl::init_ops_and_esm(),
     deno_url::deno_url::init_ops_and_esm(),
     deno_console::deno_console::init_ops_and_esm(),
-    deno_web::deno_web::init_ops_and_esm::<Permissions>(BlobStore::default(), None),
+    deno_web::deno_web::init_ops_and_esm::<Permissions>(
+      BlobStore::default(),
+      None,
+      false,
+    ),
     Extension::builder("bench_setup")
     .esm(vec![
       ExtensionFileSource {
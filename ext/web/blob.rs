@@ -1,34 +1,175 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::SeekFrom;
+use std::io::Write;
 use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
+use deno_core::futures::future::BoxFuture;
+use deno_core::futures::future::Shared;
+use deno_core::futures::FutureExt;
 use deno_core::op;
 use deno_core::parking_lot::Mutex;
 use deno_core::url::Url;
+use deno_core::AsyncRefCell;
+use deno_core::AsyncResult;
+use deno_core::BufView;
 use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
+use deno_core::ResourceId;
 use deno_core::ZeroCopyBuf;
 use serde::Deserialize;
 use serde::Serialize;
+use tempfile::TempPath;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 use uuid::Uuid;
 
+use crate::DomExceptionQuotaExceededError;
 use crate::Location;
 
 pub type PartMap = HashMap<Uuid, Arc<dyn BlobPart + Send + Sync>>;
 
-#[derive(Clone, Default, Debug)]
+/// How much memory the parts of a [`BlobStore`] may take up by default,
+/// before new parts are written to temporary files instead.
+pub const DEFAULT_BLOB_MEMORY_LIMIT: usize = 256 * 1024 * 1024;
+
+/// Bounds on what a [`BlobStore`] holds, so that a long-running process that
+/// leaks blobs or object URLs fails instead of growing without end. `None`
+/// means there is no bound.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlobStoreLimits {
+  /// The largest size, in bytes, of a part created by the store.
+  pub max_part_size: Option<usize>,
+  /// How many bytes the parts created by the store may take up in total, in
+  /// memory and in temporary files.
+  pub max_total_size: Option<usize>,
+  /// How many object URLs may exist at the same time.
+  pub max_object_urls: Option<usize>,
+}
+
+/// A snapshot of what a [`BlobStore`] holds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobStoreMetrics {
+  /// The sizes of the parts that are registered with the store, including
+  /// slices of other parts.
+  pub part_sizes: Vec<usize>,
+  /// How many bytes the parts created by the store take up, in memory and in
+  /// temporary files. Slices don't count, as they share the bytes of the
+  /// part they are sliced from.
+  pub total_size: usize,
+  /// How many of those bytes are in memory.
+  pub memory_usage: usize,
+  pub object_url_count: usize,
+}
+
+#[derive(Clone, Debug)]
 pub struct BlobStore {
   parts: Arc<Mutex<PartMap>>,
   object_urls: Arc<Mutex<HashMap<Url, Arc<Blob>>>>,
+  storage: Arc<PartStorage>,
+  limits: BlobStoreLimits,
+}
+
+impl Default for BlobStore {
+  fn default() -> Self {
+    Self::new(Some(DEFAULT_BLOB_MEMORY_LIMIT))
+  }
 }
 
 impl BlobStore {
+  /// `memory_limit` is how many bytes the parts created by
+  /// [`BlobStore::create_part`] may take up in memory. Parts that would go
+  /// over it are written to temporary files, which are removed once they
+  /// are no longer used. `None` keeps every part in memory.
+  pub fn new(memory_limit: Option<usize>) -> Self {
+    Self {
+      parts: Default::default(),
+      object_urls: Default::default(),
+      storage: Arc::new(PartStorage {
+        memory_limit,
+        memory_usage: AtomicUsize::new(0),
+        total_size: AtomicUsize::new(0),
+      }),
+      limits: BlobStoreLimits::default(),
+    }
+  }
+
+  pub fn with_limits(mut self, limits: BlobStoreLimits) -> Self {
+    self.limits = limits;
+    self
+  }
+
+  pub fn metrics(&self) -> BlobStoreMetrics {
+    let part_sizes =
+      self.parts.lock().values().map(|part| part.size()).collect();
+    BlobStoreMetrics {
+      part_sizes,
+      total_size: self.storage.total_size.load(Ordering::SeqCst),
+      memory_usage: self.storage.memory_usage.load(Ordering::SeqCst),
+      object_url_count: self.object_urls.lock().len(),
+    }
+  }
+
+  /// Creates a part with the given bytes, in memory or in a temporary file
+  /// depending on the memory limit of the store.
+  pub fn create_part(
+    &self,
+    data: Vec<u8>,
+  ) -> Result<Arc<dyn BlobPart + Send + Sync>, AnyError> {
+    let storage = &self.storage;
+    let len = data.len();
+    if let Some(max_part_size) = self.limits.max_part_size {
+      if len > max_part_size {
+        return Err(
+          DomExceptionQuotaExceededError::new(&format!(
+            "Blob part of {len} bytes exceeds the limit of {max_part_size} bytes"
+          ))
+          .into(),
+        );
+      }
+    }
+    let total_size = storage.total_size.fetch_add(len, Ordering::SeqCst);
+    if let Some(max_total_size) = self.limits.max_total_size {
+      if total_size + len > max_total_size {
+        storage.total_size.fetch_sub(len, Ordering::SeqCst);
+        return Err(
+          DomExceptionQuotaExceededError::new(&format!(
+            "Blob parts exceed the limit of {max_total_size} bytes"
+          ))
+          .into(),
+        );
+      }
+    }
+
+    let memory_usage = storage.memory_usage.fetch_add(len, Ordering::SeqCst);
+    match storage.memory_limit {
+      Some(limit) if memory_usage + len > limit && len > 0 => {
+        storage.memory_usage.fetch_sub(len, Ordering::SeqCst);
+        Ok(Arc::new(FileBlobPart {
+          file: spill_to_temp_file(data),
+          size: len,
+          storage: storage.clone(),
+        }))
+      }
+      _ => Ok(Arc::new(InMemoryBlobPart {
+        data,
+        storage: Some(storage.clone()),
+      })),
+    }
+  }
+
   pub fn insert_part(&self, part: Arc<dyn BlobPart + Send + Sync>) -> Uuid {
     let id = Uuid::new_v4();
     let mut parts = self.parts.lock();
@@ -75,12 +216,68 @@ impl BlobStore {
     url
   }
 
+  /// Fails if another object URL would go over the limit of the store.
+  fn check_object_url_limit(&self) -> Result<(), AnyError> {
+    match self.limits.max_object_urls {
+      Some(max) if self.object_urls.lock().len() >= max => Err(
+        DomExceptionQuotaExceededError::new(&format!(
+          "Exceeded the limit of {max} object URLs"
+        ))
+        .into(),
+      ),
+      _ => Ok(()),
+    }
+  }
+
   pub fn remove_object_url(&self, url: &Url) {
     let mut blob_store = self.object_urls.lock();
     blob_store.remove(url);
   }
 }
 
+/// Keeps track of the memory and the space taken up by the parts of a
+/// [`BlobStore`].
+#[derive(Debug)]
+struct PartStorage {
+  memory_limit: Option<usize>,
+  memory_usage: AtomicUsize,
+  total_size: AtomicUsize,
+}
+
+/// The temporary file of a [`FileBlobPart`], once it has been written.
+type TempFile =
+  Shared<BoxFuture<'static, Result<Arc<TempPath>, Arc<std::io::Error>>>>;
+
+/// Writes the bytes to a temporary file that only the current user has
+/// access to. The file is written on a blocking thread when there is a
+/// runtime, so that creating a part doesn't block the op thread.
+fn spill_to_temp_file(data: Vec<u8>) -> TempFile {
+  let write = move || -> std::io::Result<Arc<TempPath>> {
+    let mut file = tempfile::Builder::new().prefix("deno_blob_").tempfile()?;
+    file.write_all(&data)?;
+    Ok(Arc::new(file.into_temp_path()))
+  };
+  match tokio::runtime::Handle::try_current() {
+    Ok(handle) => {
+      let task = handle.spawn_blocking(write);
+      async move {
+        match task.await {
+          Ok(result) => result.map_err(Arc::new),
+          Err(err) => Err(Arc::new(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            err,
+          ))),
+        }
+      }
+      .boxed()
+      .shared()
+    }
+    Err(_) => deno_core::futures::future::ready(write().map_err(Arc::new))
+      .boxed()
+      .shared(),
+  }
+}
+
 #[derive(Debug)]
 pub struct Blob {
   pub media_type: String,
@@ -95,8 +292,8 @@ impl Blob {
     let mut bytes = Vec::with_capacity(size);
 
     for part in &self.parts {
-      let chunk = part.read().await?;
-      bytes.extend_from_slice(chunk);
+      let chunk = part.read(0, part.size()).await?;
+      bytes.extend_from_slice(&chunk);
     }
 
     assert_eq!(bytes.len(), size);
@@ -115,28 +312,101 @@ impl Blob {
 
 #[async_trait]
 pub trait BlobPart: Debug {
-  // TODO(lucacsonato): this should be a stream!
-  async fn read(&self) -> Result<&[u8], AnyError>;
+  /// Reads `len` bytes of the part, starting at `start`. The range must be
+  /// within the size of the part.
+  async fn read(&self, start: usize, len: usize)
+    -> Result<Cow<[u8]>, AnyError>;
   fn size(&self) -> usize;
 }
 
 #[derive(Debug)]
-pub struct InMemoryBlobPart(Vec<u8>);
+pub struct InMemoryBlobPart {
+  data: Vec<u8>,
+  /// The storage whose memory usage this part counts towards.
+  storage: Option<Arc<PartStorage>>,
+}
 
 impl From<Vec<u8>> for InMemoryBlobPart {
   fn from(vec: Vec<u8>) -> Self {
-    Self(vec)
+    Self {
+      data: vec,
+      storage: None,
+    }
+  }
+}
+
+impl Drop for InMemoryBlobPart {
+  fn drop(&mut self) {
+    if let Some(storage) = &self.storage {
+      let len = self.data.len();
+      storage.memory_usage.fetch_sub(len, Ordering::SeqCst);
+      storage.total_size.fetch_sub(len, Ordering::SeqCst);
+    }
   }
 }
 
 #[async_trait]
 impl BlobPart for InMemoryBlobPart {
-  async fn read(&self) -> Result<&[u8], AnyError> {
-    Ok(&self.0)
+  async fn read(
+    &self,
+    start: usize,
+    len: usize,
+  ) -> Result<Cow<[u8]>, AnyError> {
+    Ok(Cow::Borrowed(&self.data[start..start + len]))
+  }
+
+  fn size(&self) -> usize {
+    self.data.len()
+  }
+}
+
+/// A part that is kept in a temporary file, which is removed once the part
+/// and its pending reads are dropped.
+pub struct FileBlobPart {
+  file: TempFile,
+  size: usize,
+  /// The storage whose space this part counts towards.
+  storage: Arc<PartStorage>,
+}
+
+impl Debug for FileBlobPart {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("FileBlobPart")
+      .field("size", &self.size)
+      .finish_non_exhaustive()
+  }
+}
+
+impl Drop for FileBlobPart {
+  fn drop(&mut self) {
+    self
+      .storage
+      .total_size
+      .fetch_sub(self.size, Ordering::SeqCst);
+  }
+}
+
+#[async_trait]
+impl BlobPart for FileBlobPart {
+  async fn read(
+    &self,
+    start: usize,
+    len: usize,
+  ) -> Result<Cow<[u8]>, AnyError> {
+    let path = self
+      .file
+      .clone()
+      .await
+      .map_err(|err| std::io::Error::new(err.kind(), err.to_string()))?;
+    let mut file = tokio::fs::File::open(&**path).await?;
+    file.seek(SeekFrom::Start(start as u64)).await?;
+    let mut buf = vec![0; len];
+    file.read_exact(&mut buf).await?;
+    Ok(Cow::Owned(buf))
   }
 
   fn size(&self) -> usize {
-    self.0.len()
+    self.size
   }
 }
 
@@ -149,9 +419,12 @@ pub struct SlicedBlobPart {
 
 #[async_trait]
 impl BlobPart for SlicedBlobPart {
-  async fn read(&self) -> Result<&[u8], AnyError> {
-    let original = self.part.read().await?;
-    Ok(&original[self.start..self.start + self.len])
+  async fn read(
+    &self,
+    start: usize,
+    len: usize,
+  ) -> Result<Cow<[u8]>, AnyError> {
+    self.part.read(self.start + start, len).await
   }
 
   fn size(&self) -> usize {
@@ -160,10 +433,13 @@ impl BlobPart for SlicedBlobPart {
 }
 
 #[op]
-pub fn op_blob_create_part(state: &mut OpState, data: ZeroCopyBuf) -> Uuid {
+pub fn op_blob_create_part(
+  state: &mut OpState,
+  data: ZeroCopyBuf,
+) -> Result<Uuid, AnyError> {
   let blob_store = state.borrow::<BlobStore>();
-  let part = InMemoryBlobPart(data.to_vec());
-  blob_store.insert_part(Arc::new(part))
+  let part = blob_store.create_part(data.to_vec())?;
+  Ok(blob_store.insert_part(part))
 }
 
 #[derive(Deserialize)]
@@ -199,10 +475,14 @@ pub fn op_blob_slice_part(
   Ok(id)
 }
 
+/// Reads a range of a part, so that parts that are backed by a file can be
+/// streamed without loading them into memory.
 #[op]
 pub async fn op_blob_read_part(
   state: Rc<RefCell<OpState>>,
   id: Uuid,
+  start: usize,
+  len: usize,
 ) -> Result<ZeroCopyBuf, AnyError> {
   let part = {
     let state = state.borrow();
@@ -210,8 +490,80 @@ pub async fn op_blob_read_part(
     blob_store.get_part(&id)
   }
   .ok_or_else(|| type_error("Blob part not found"))?;
-  let buf = part.read().await?;
-  Ok(ZeroCopyBuf::from(buf.to_vec()))
+  if start + len > part.size() {
+    return Err(type_error(
+      "start + len can not be larger than blob part size",
+    ));
+  }
+  let buf = part.read(start, len).await?;
+  Ok(ZeroCopyBuf::from(buf.into_owned()))
+}
+
+/// Reads the parts of a blob one after the other, a chunk at a time, so that
+/// the blob can be streamed from a resource.
+pub struct BlobReader {
+  parts: Vec<Arc<dyn BlobPart + Send + Sync>>,
+  /// The index of the part that is being read, and the position in it.
+  position: AsyncRefCell<(usize, usize)>,
+}
+
+impl BlobReader {
+  pub fn new(parts: Vec<Arc<dyn BlobPart + Send + Sync>>) -> Self {
+    Self {
+      parts,
+      position: AsyncRefCell::new((0, 0)),
+    }
+  }
+
+  async fn read(self: Rc<Self>, limit: usize) -> Result<BufView, AnyError> {
+    let mut position = RcRef::map(&self, |r| &r.position).borrow_mut().await;
+    let (index, start) = &mut *position;
+    while let Some(part) = self.parts.get(*index) {
+      let len = (part.size() - *start).min(limit);
+      if len == 0 {
+        *index += 1;
+        *start = 0;
+        continue;
+      }
+      let buf = part.read(*start, len).await?;
+      *start += len;
+      return Ok(BufView::from(buf.into_owned()));
+    }
+    Ok(BufView::empty())
+  }
+}
+
+impl Resource for BlobReader {
+  fn name(&self) -> Cow<str> {
+    "blobReader".into()
+  }
+
+  fn read(self: Rc<Self>, limit: usize) -> AsyncResult<BufView> {
+    Box::pin(BlobReader::read(self, limit))
+  }
+
+  fn size_hint(&self) -> (u64, Option<u64>) {
+    let size = self.parts.iter().map(|part| part.size() as u64).sum();
+    (size, Some(size))
+  }
+}
+
+/// Opens a [`BlobReader`] for the given parts, which are read in order.
+#[op]
+pub fn op_blob_open_reader(
+  state: &mut OpState,
+  part_ids: Vec<Uuid>,
+) -> Result<ResourceId, AnyError> {
+  let blob_store = state.borrow::<BlobStore>();
+  let parts = part_ids
+    .iter()
+    .map(|id| {
+      blob_store
+        .get_part(id)
+        .ok_or_else(|| type_error("Blob part not found"))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+  Ok(state.resource_table.add(BlobReader::new(parts)))
 }
 
 #[op]
@@ -228,6 +580,7 @@ pub fn op_blob_create_object_url(
 ) -> Result<String, AnyError> {
   let mut parts = Vec::with_capacity(part_ids.len());
   let blob_store = state.borrow::<BlobStore>();
+  blob_store.check_object_url_limit()?;
   for part_id in part_ids {
     let part = blob_store
       .get_part(&part_id)
@@ -257,6 +610,13 @@ pub fn op_blob_revoke_object_url(
   Ok(())
 }
 
+/// What the blob store of this isolate holds, to find leaked blobs and
+/// object URLs.
+#[op]
+pub fn op_blob_store_metrics(state: &mut OpState) -> BlobStoreMetrics {
+  state.borrow::<BlobStore>().metrics()
+}
+
 #[derive(Serialize)]
 pub struct ReturnBlob {
   pub media_type: String,
@@ -299,3 +659,129 @@ pub fn op_blob_from_object_url(
     Ok(None)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[tokio::test]
+  async fn parts_over_memory_limit_are_files() {
+    let blob_store = BlobStore::new(Some(4));
+    let in_memory = blob_store.create_part(b"abc".to_vec()).unwrap();
+    let in_file = blob_store.create_part(b"defgh".to_vec()).unwrap();
+    assert_eq!(blob_store.storage.memory_usage.load(Ordering::SeqCst), 3);
+    assert_eq!(blob_store.storage.total_size.load(Ordering::SeqCst), 8);
+
+    assert_eq!(&*in_file.read(1, 3).await.unwrap(), b"efg");
+    let sliced = SlicedBlobPart {
+      part: in_file.clone(),
+      start: 2,
+      len: 3,
+    };
+    assert_eq!(&*sliced.read(1, 2).await.unwrap(), b"gh");
+    let blob = Blob {
+      media_type: String::new(),
+      parts: vec![in_memory.clone(), in_file.clone()],
+    };
+    assert_eq!(blob.read_all().await.unwrap(), b"abcdefgh");
+
+    // the memory of dropped parts is given back
+    drop((blob, in_memory));
+    assert_eq!(blob_store.storage.memory_usage.load(Ordering::SeqCst), 0);
+    drop((sliced, in_file));
+    assert_eq!(blob_store.storage.total_size.load(Ordering::SeqCst), 0);
+  }
+
+  #[tokio::test]
+  async fn temp_files() {
+    let file = spill_to_temp_file(b"abc".to_vec());
+    let path = file.clone().await.unwrap().to_path_buf();
+    assert_eq!(std::fs::read(&path).unwrap(), b"abc");
+    // only the current user has access to the file
+    #[cfg(unix)]
+    {
+      use std::os::unix::fs::PermissionsExt;
+      let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+      assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // the file is removed along with its part
+    drop(file);
+    assert!(!path.exists());
+  }
+
+  #[test]
+  fn limits() {
+    let blob_store = BlobStore::new(Some(4)).with_limits(BlobStoreLimits {
+      max_part_size: Some(6),
+      max_total_size: Some(10),
+      max_object_urls: Some(1),
+    });
+    let err = blob_store.create_part(vec![0; 7]).unwrap_err();
+    assert!(err.is::<DomExceptionQuotaExceededError>());
+
+    let in_memory = blob_store.create_part(vec![0; 4]).unwrap();
+    let in_file = blob_store.create_part(vec![0; 6]).unwrap();
+    let err = blob_store.create_part(vec![0; 1]).unwrap_err();
+    assert!(err.is::<DomExceptionQuotaExceededError>());
+    let id = blob_store.insert_part(in_file.clone());
+    let sliced = SlicedBlobPart {
+      part: in_file.clone(),
+      start: 1,
+      len: 2,
+    };
+    blob_store.insert_part(Arc::new(sliced));
+    let blob = Blob {
+      media_type: String::new(),
+      parts: vec![in_memory.clone()],
+    };
+    let url = blob_store.insert_object_url(blob, None);
+    assert!(blob_store.check_object_url_limit().is_err());
+
+    let mut metrics = blob_store.metrics();
+    metrics.part_sizes.sort();
+    assert_eq!(
+      metrics,
+      BlobStoreMetrics {
+        part_sizes: vec![2, 6],
+        total_size: 10,
+        memory_usage: 4,
+        object_url_count: 1,
+      }
+    );
+
+    // the space of parts that are no longer used is given back
+    blob_store.remove_object_url(&url);
+    drop(in_memory);
+    assert!(blob_store.check_object_url_limit().is_ok());
+    assert_eq!(blob_store.metrics().total_size, 6);
+    blob_store.create_part(vec![0; 4]).unwrap();
+    blob_store.remove_part(&id);
+    drop(in_file);
+    // the slice still holds on to the file backed part
+    assert_eq!(blob_store.metrics().total_size, 6);
+  }
+
+  #[tokio::test]
+  async fn blob_reader() {
+    let part = |data: &[u8]| {
+      Arc::new(InMemoryBlobPart::from(data.to_vec()))
+        as Arc<dyn BlobPart + Send + Sync>
+    };
+    let reader = Rc::new(BlobReader::new(vec![
+      part(b"abcde"),
+      part(b""),
+      part(b"fg"),
+    ]));
+    assert_eq!(reader.size_hint(), (7, Some(7)));
+    let mut chunks = Vec::new();
+    loop {
+      let chunk = BlobReader::read(reader.clone(), 3).await.unwrap();
+      if chunk.is_empty() {
+        break;
+      }
+      chunks.push(chunk.to_vec());
+    }
+    assert_eq!(chunks, [b"abc".to_vec(), b"de".to_vec(), b"fg".to_vec()]);
+  }
+}
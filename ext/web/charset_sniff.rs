@@ -0,0 +1,350 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Determines the encoding of legacy content that may not be UTF-8, like the
+//! encoding sniffing algorithm of HTML does:
+//! https://html.spec.whatwg.org/multipage/parsing.html#encoding-sniffing-algorithm
+//!
+//! The encoding is the first of: the one of the BOM, the charset of the
+//! transport layer (the `Content-Type` header), the one that a `<meta>` tag
+//! declares in the first 1024 bytes, and a guess made from the bytes.
+//!
+//! It is only a building block for embedders and tools that decode legacy
+//! pages: `Body.text()` of `fetch()` keeps decoding UTF-8 whatever the
+//! content is, as the Fetch standard requires, so it isn't exposed to JS.
+
+use encoding_rs::Encoding;
+use encoding_rs::UTF_16BE;
+use encoding_rs::UTF_16LE;
+use encoding_rs::UTF_8;
+use encoding_rs::WINDOWS_1252;
+use encoding_rs::X_USER_DEFINED;
+
+/// How many bytes are scanned for a `<meta>` tag.
+const PRESCAN_LEN: usize = 1024;
+
+pub fn sniff_encoding(
+  data: &[u8],
+  transport_charset: Option<&str>,
+) -> &'static Encoding {
+  if let Some((encoding, _)) = Encoding::for_bom(data) {
+    return encoding;
+  }
+  if let Some(encoding) = transport_charset
+    .and_then(|charset| Encoding::for_label(charset.trim().as_bytes()))
+  {
+    return encoding;
+  }
+  if let Some(encoding) = prescan(&data[..data.len().min(PRESCAN_LEN)]) {
+    return encoding;
+  }
+  // Without a declaration, content that is valid UTF-8 and not only ASCII is
+  // very unlikely to be in another encoding. Otherwise the default of HTML
+  // for an unknown locale is used.
+  if !data.is_ascii() && std::str::from_utf8(data).is_ok() {
+    UTF_8
+  } else {
+    WINDOWS_1252
+  }
+}
+
+fn is_whitespace(byte: u8) -> bool {
+  matches!(byte, b'\t' | b'\n' | b'\x0c' | b'\r' | b' ')
+}
+
+fn starts_with_ignore_case(data: &[u8], prefix: &[u8]) -> bool {
+  data.len() >= prefix.len()
+    && data[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+/// https://html.spec.whatwg.org/multipage/parsing.html#prescan-a-byte-stream-to-determine-its-encoding
+fn prescan(data: &[u8]) -> Option<&'static Encoding> {
+  let mut position = 0;
+  while position < data.len() {
+    let rest = &data[position..];
+    if rest.starts_with(b"<!--") {
+      // the "-->" can share its dashes with the "<!--"
+      let end = find(&data[position + 2..], b"-->")?;
+      position += 2 + end + 3;
+      continue;
+    }
+    if starts_with_ignore_case(rest, b"<meta")
+      && rest
+        .get(5)
+        .map_or(false, |c| is_whitespace(*c) || *c == b'/')
+    {
+      position += 5;
+      if let Some(encoding) = prescan_meta(data, &mut position) {
+        return Some(encoding);
+      }
+      continue;
+    }
+    let is_tag = match rest {
+      [b'<', c, ..] if c.is_ascii_alphabetic() => true,
+      [b'<', b'/', c, ..] if c.is_ascii_alphabetic() => true,
+      _ => false,
+    };
+    if is_tag {
+      while position < data.len()
+        && !is_whitespace(data[position])
+        && data[position] != b'>'
+      {
+        position += 1;
+      }
+      while get_attribute(data, &mut position).is_some() {}
+    } else if rest.starts_with(b"<!")
+      || rest.starts_with(b"</")
+      || rest.starts_with(b"<?")
+    {
+      position += find(rest, b">")?;
+    }
+    position += 1;
+  }
+  None
+}
+
+/// The part of the prescan for the attributes of a `<meta>` tag.
+fn prescan_meta(
+  data: &[u8],
+  position: &mut usize,
+) -> Option<&'static Encoding> {
+  let mut names = Vec::new();
+  let mut got_pragma = false;
+  let mut need_pragma = None;
+  let mut charset: Option<&'static Encoding> = None;
+  while let Some((name, value)) = get_attribute(data, position) {
+    if names.contains(&name) {
+      continue;
+    }
+    match name.as_slice() {
+      b"http-equiv" => {
+        if value.eq_ignore_ascii_case(b"content-type") {
+          got_pragma = true;
+        }
+      }
+      b"content" => {
+        if charset.is_none() {
+          if let Some(encoding) = extract_charset_from_meta(&value)
+            .and_then(|label| Encoding::for_label(&label))
+          {
+            charset = Some(encoding);
+            need_pragma = Some(true);
+          }
+        }
+      }
+      b"charset" => {
+        if charset.is_none() {
+          charset = Encoding::for_label(&value);
+          need_pragma = Some(false);
+        }
+      }
+      _ => {}
+    }
+    names.push(name);
+  }
+  match need_pragma {
+    None => return None,
+    Some(true) if !got_pragma => return None,
+    _ => {}
+  }
+  match charset? {
+    encoding if encoding == UTF_16BE || encoding == UTF_16LE => Some(UTF_8),
+    encoding if encoding == X_USER_DEFINED => Some(WINDOWS_1252),
+    encoding => Some(encoding),
+  }
+}
+
+/// https://html.spec.whatwg.org/multipage/parsing.html#concept-get-attributes-when-sniffing
+///
+/// The name and the value are lowercased.
+fn get_attribute(
+  data: &[u8],
+  position: &mut usize,
+) -> Option<(Vec<u8>, Vec<u8>)> {
+  let byte = |position: usize| data.get(position).copied();
+  while byte(*position).map_or(false, |c| is_whitespace(c) || c == b'/') {
+    *position += 1;
+  }
+  if byte(*position)? == b'>' {
+    return None;
+  }
+
+  let mut name = Vec::new();
+  loop {
+    let c = byte(*position)?;
+    match c {
+      b'=' if !name.is_empty() => {
+        *position += 1;
+        break;
+      }
+      c if is_whitespace(c) => {
+        while byte(*position).map_or(false, is_whitespace) {
+          *position += 1;
+        }
+        if byte(*position)? != b'=' {
+          return Some((name, Vec::new()));
+        }
+        *position += 1;
+        break;
+      }
+      b'/' | b'>' => return Some((name, Vec::new())),
+      c => name.push(c.to_ascii_lowercase()),
+    }
+    *position += 1;
+  }
+
+  while byte(*position).map_or(false, is_whitespace) {
+    *position += 1;
+  }
+  let mut value = Vec::new();
+  let c = byte(*position)?;
+  if c == b'"' || c == b'\'' {
+    *position += 1;
+    loop {
+      let d = byte(*position)?;
+      *position += 1;
+      if d == c {
+        return Some((name, value));
+      }
+      value.push(d.to_ascii_lowercase());
+    }
+  }
+  if c == b'>' {
+    return Some((name, value));
+  }
+  loop {
+    let c = byte(*position)?;
+    if is_whitespace(c) || c == b'>' {
+      return Some((name, value));
+    }
+    value.push(c.to_ascii_lowercase());
+    *position += 1;
+  }
+}
+
+/// https://html.spec.whatwg.org/multipage/urls-and-fetching.html#algorithm-for-extracting-a-character-encoding-from-a-meta-element
+fn extract_charset_from_meta(content: &[u8]) -> Option<Vec<u8>> {
+  let mut position = 0;
+  loop {
+    position += find_ignore_case(&content[position..], b"charset")? + 7;
+    while content.get(position).map_or(false, |c| is_whitespace(*c)) {
+      position += 1;
+    }
+    if content.get(position) == Some(&b'=') {
+      position += 1;
+      break;
+    }
+  }
+  while content.get(position).map_or(false, |c| is_whitespace(*c)) {
+    position += 1;
+  }
+  let rest = &content[position..];
+  match rest.first()? {
+    quote @ (b'"' | b'\'') => {
+      let end = rest[1..].iter().position(|c| c == quote)?;
+      Some(rest[1..end + 1].to_vec())
+    }
+    _ => {
+      let end = rest
+        .iter()
+        .position(|c| is_whitespace(*c) || *c == b';')
+        .unwrap_or(rest.len());
+      Some(rest[..end].to_vec())
+    }
+  }
+}
+
+fn find(data: &[u8], needle: &[u8]) -> Option<usize> {
+  data
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
+
+fn find_ignore_case(data: &[u8], needle: &[u8]) -> Option<usize> {
+  data
+    .windows(needle.len())
+    .position(|window| window.eq_ignore_ascii_case(needle))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sniff(data: &[u8], charset: Option<&str>) -> &'static str {
+    sniff_encoding(data, charset).name()
+  }
+
+  #[test]
+  fn sniff_bom_and_transport() {
+    assert_eq!(sniff(b"\xef\xbb\xbfabc", Some("latin1")), "UTF-8");
+    assert_eq!(sniff(b"\xff\xfea\x00", None), "UTF-16LE");
+    assert_eq!(sniff(b"\xfe\xff\x00a", None), "UTF-16BE");
+    assert_eq!(sniff(b"abc", Some(" Shift_JIS ")), "Shift_JIS");
+    // an invalid charset is ignored
+    assert_eq!(sniff(b"caf\xc3\xa9", Some("unknown")), "UTF-8");
+  }
+
+  #[test]
+  fn sniff_meta() {
+    assert_eq!(
+      sniff(b"<html><head><meta charset=\"euc-kr\">", None),
+      "EUC-KR"
+    );
+    assert_eq!(sniff(b"<META CHARSET='koi8-r'/>", None), "KOI8-R");
+    assert_eq!(
+      sniff(
+        b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=iso-8859-2\">",
+        None
+      ),
+      "ISO-8859-2"
+    );
+    // the content is only used with the pragma
+    assert_eq!(
+      sniff(b"<meta content=\"text/html; charset=iso-8859-2\">", None),
+      "windows-1252"
+    );
+    // UTF-16 can't be declared by a document that is ASCII-compatible
+    assert_eq!(sniff(b"<meta charset=utf-16le>", None), "UTF-8");
+    assert_eq!(
+      sniff(b"<meta charset=x-user-defined>", None),
+      "windows-1252"
+    );
+  }
+
+  #[test]
+  fn sniff_meta_skips_comments_and_attributes() {
+    assert_eq!(
+      sniff(
+        b"<!-- <meta charset=euc-kr> --><div title='<meta charset=gbk>'><meta charset=big5>",
+        None
+      ),
+      "Big5"
+    );
+    assert_eq!(sniff(b"<!--><meta charset=gbk>", None), "GBK");
+    // only the start of the content is scanned
+    let mut data = vec![b' '; PRESCAN_LEN];
+    data.extend_from_slice(b"<meta charset=gbk>");
+    assert_eq!(sniff(&data, None), "windows-1252");
+  }
+
+  #[test]
+  fn sniff_heuristic() {
+    assert_eq!(sniff("<p>café</p>".as_bytes(), None), "UTF-8");
+    assert_eq!(sniff(b"<p>caf\xe9</p>", None), "windows-1252");
+    assert_eq!(sniff(b"<p>cafe</p>", None), "windows-1252");
+  }
+
+  #[test]
+  fn extract_charset() {
+    let extract = |content: &[u8]| {
+      extract_charset_from_meta(content).map(|c| String::from_utf8(c).unwrap())
+    };
+    assert_eq!(extract(b"text/html; charset=gbk"), Some("gbk".to_string()));
+    assert_eq!(
+      extract(b"text/html; charset = \"big5\""),
+      Some("big5".to_string())
+    );
+    assert_eq!(extract(b"charsetx; charset=a;b"), Some("a".to_string()));
+    assert_eq!(extract(b"text/html; charset='gbk"), None);
+    assert_eq!(extract(b"text/html"), None);
+  }
+}
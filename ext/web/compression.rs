@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op;
 use deno_core::OpState;
@@ -12,17 +13,61 @@ use flate2::write::GzDecoder;
 use flate2::write::GzEncoder;
 use flate2::write::ZlibDecoder;
 use flate2::write::ZlibEncoder;
+use flate2::Compress;
 use flate2::Compression;
+use flate2::Crc;
+use flate2::Decompress;
+use flate2::FlushCompress;
+use flate2::FlushDecompress;
+use flate2::Status;
 use std::borrow::Cow;
 use std::cell::RefCell;
 use std::io::Write;
 use std::rc::Rc;
 
-#[derive(Debug)]
-struct CompressionResource(RefCell<Inner>);
+use crate::Unstable;
 
-/// https://wicg.github.io/compression/#supported-formats
-#[derive(Debug)]
+struct CompressionResource(RefCell<Inner>, FlushMode);
+
+/// How the output of an encoder is flushed after every chunk that is written
+/// to it, which is a non-standard extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlushMode {
+  /// Output is only produced once the internal buffers of the encoder are
+  /// full, which gives the best compression.
+  None,
+  /// Everything that was written is output, so that it can be decompressed
+  /// right away. This is the default.
+  Sync,
+  /// Like `Sync`, but the compression state is also reset, so that the
+  /// decompression can start again from the next chunk. Only the "deflate",
+  /// "deflate-raw" and "gzip" formats distinguish it from `Sync`.
+  Full,
+}
+
+impl FlushMode {
+  fn parse(flush: Option<&str>) -> Result<Self, AnyError> {
+    match flush {
+      None | Some("sync") => Ok(Self::Sync),
+      Some("none") => Ok(Self::None),
+      Some("full") => Ok(Self::Full),
+      Some(flush) => Err(type_error(format!("Invalid flush mode \"{flush}\""))),
+    }
+  }
+}
+
+impl From<FlushMode> for FlushCompress {
+  fn from(flush: FlushMode) -> Self {
+    match flush {
+      FlushMode::None => FlushCompress::None,
+      FlushMode::Sync => FlushCompress::Sync,
+      FlushMode::Full => FlushCompress::Full,
+    }
+  }
+}
+
+/// https://wicg.github.io/compression/#supported-formats, plus the
+/// non-standard "br" and "zstd" formats.
 enum Inner {
   DeflateDecoder(ZlibDecoder<Vec<u8>>),
   DeflateEncoder(ZlibEncoder<Vec<u8>>),
@@ -30,6 +75,12 @@ enum Inner {
   DeflateRawEncoder(DeflateEncoder<Vec<u8>>),
   GzDecoder(GzDecoder<Vec<u8>>),
   GzEncoder(GzEncoder<Vec<u8>>),
+  BrotliDecoder(brotli::DecompressorWriter<SharedBuf>, SharedBuf),
+  BrotliEncoder(brotli::CompressorWriter<SharedBuf>, SharedBuf),
+  ZstdDecoder(zstd::stream::write::Decoder<'static, Vec<u8>>),
+  ZstdEncoder(zstd::stream::write::Encoder<'static, Vec<u8>>),
+  DictionaryDecoder(DictionaryDecoder),
+  FlateEncoder(FlateEncoder),
 }
 
 impl Resource for CompressionResource {
@@ -38,30 +89,147 @@ impl Resource for CompressionResource {
   }
 }
 
-#[op]
-pub fn op_compression_new(
-  state: &mut OpState,
+/// A writer whose contents can be taken while it's owned by an encoder or a
+/// decoder, for the brotli ones that only give it back once they're done.
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuf {
+  fn take(&self) -> Vec<u8> {
+    std::mem::take(&mut self.0.borrow_mut())
+  }
+}
+
+impl Write for SharedBuf {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.borrow_mut().extend_from_slice(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+/// The size of the internal buffers of the brotli encoder and decoder.
+const BROTLI_BUFFER_SIZE: usize = 64 * 1024;
+
+/// `level` is the compression level the user asked for, whose range depends
+/// on the format, or `None` for the default one.
+fn new_inner(
   format: &str,
   is_decoder: bool,
-) -> ResourceId {
+  level: Option<u32>,
+) -> Result<Inner, AnyError> {
   let w = Vec::new();
-  let inner = match (format, is_decoder) {
+  let flate_level = level.map(Compression::new).unwrap_or_default();
+  Ok(match (format, is_decoder) {
     ("deflate", true) => Inner::DeflateDecoder(ZlibDecoder::new(w)),
     ("deflate", false) => {
-      Inner::DeflateEncoder(ZlibEncoder::new(w, Compression::default()))
+      Inner::DeflateEncoder(ZlibEncoder::new(w, flate_level))
     }
     ("deflate-raw", true) => Inner::DeflateRawDecoder(DeflateDecoder::new(w)),
     ("deflate-raw", false) => {
-      Inner::DeflateRawEncoder(DeflateEncoder::new(w, Compression::default()))
+      Inner::DeflateRawEncoder(DeflateEncoder::new(w, flate_level))
     }
     ("gzip", true) => Inner::GzDecoder(GzDecoder::new(w)),
-    ("gzip", false) => {
-      Inner::GzEncoder(GzEncoder::new(w, Compression::default()))
+    ("gzip", false) => Inner::GzEncoder(GzEncoder::new(w, flate_level)),
+    ("br", true) => {
+      let out = SharedBuf::default();
+      let decoder =
+        brotli::DecompressorWriter::new(out.clone(), BROTLI_BUFFER_SIZE);
+      Inner::BrotliDecoder(decoder, out)
+    }
+    ("br", false) => {
+      let out = SharedBuf::default();
+      // the same quality and window size as the compression of responses
+      // in `Deno.serve()`
+      let quality = level.unwrap_or(6).min(11);
+      let encoder = brotli::CompressorWriter::new(
+        out.clone(),
+        BROTLI_BUFFER_SIZE,
+        quality,
+        22,
+      );
+      Inner::BrotliEncoder(encoder, out)
+    }
+    ("zstd", true) => Inner::ZstdDecoder(zstd::stream::write::Decoder::new(w)?),
+    ("zstd", false) => {
+      // 0 picks the default level of zstd
+      let level = level.unwrap_or(0) as i32;
+      Inner::ZstdEncoder(zstd::stream::write::Encoder::new(w, level)?)
     }
     _ => unreachable!(),
+  })
+}
+
+#[op]
+pub fn op_compression_new(
+  state: &mut OpState,
+  format: &str,
+  is_decoder: bool,
+) -> Result<ResourceId, AnyError> {
+  let inner = new_inner(format, is_decoder, None)?;
+  let resource = CompressionResource(RefCell::new(inner), FlushMode::Sync);
+  Ok(state.resource_table.add(resource))
+}
+
+/// Creates a stream with a compression level, a preset dictionary or a flush
+/// mode, which is a non-standard extension.
+#[op]
+pub fn op_compression_new_with_options(
+  state: &mut OpState,
+  format: &str,
+  is_decoder: bool,
+  level: Option<u32>,
+  dictionary: Option<ZeroCopyBuf>,
+  flush: Option<String>,
+) -> Result<ResourceId, AnyError> {
+  if !state.borrow::<Unstable>().0 {
+    let api_name = if is_decoder {
+      "DecompressionStream options"
+    } else {
+      "CompressionStream options"
+    };
+    eprintln!(
+      "Unstable API '{api_name}'. The --unstable flag must be provided."
+    );
+    std::process::exit(70);
+  }
+
+  let flush = FlushMode::parse(flush.as_deref())?;
+  let flate_format = match format {
+    "deflate" => Some(FlateFormat::Zlib),
+    "deflate-raw" => Some(FlateFormat::Raw),
+    "gzip" => Some(FlateFormat::Gzip),
+    _ => None,
+  };
+  if dictionary.is_some()
+    && !matches!(flate_format, Some(FlateFormat::Zlib | FlateFormat::Raw))
+  {
+    return Err(type_error(format!(
+      "A dictionary is not supported for the \"{format}\" format"
+    )));
+  }
+
+  let inner = match (flate_format, is_decoder, dictionary) {
+    (Some(flate_format), false, dictionary) => {
+      let level = level.map(Compression::new).unwrap_or_default();
+      Inner::FlateEncoder(FlateEncoder::new(
+        flate_format,
+        level,
+        dictionary.as_deref(),
+        flush,
+      )?)
+    }
+    (Some(flate_format), true, Some(dictionary)) => {
+      let zlib = flate_format == FlateFormat::Zlib;
+      Inner::DictionaryDecoder(DictionaryDecoder::new(&dictionary, zlib)?)
+    }
+    _ => new_inner(format, is_decoder, level)?,
   };
-  let resource = CompressionResource(RefCell::new(inner));
-  state.resource_table.add(resource)
+  let resource = CompressionResource(RefCell::new(inner), flush);
+  Ok(state.resource_table.add(resource))
 }
 
 #[op]
@@ -71,6 +239,7 @@ pub fn op_compression_write(
   input: &[u8],
 ) -> Result<ZeroCopyBuf, AnyError> {
   let resource = state.resource_table.get::<CompressionResource>(rid)?;
+  let flush = resource.1 != FlushMode::None;
   let mut inner = resource.0.borrow_mut();
   let out: Vec<u8> = match &mut *inner {
     Inner::DeflateDecoder(d) => {
@@ -103,6 +272,32 @@ pub fn op_compression_write(
       d.flush()?;
       d.get_mut().drain(..)
     }
+    Inner::BrotliDecoder(d, out) => {
+      d.write_all(input)?;
+      d.flush()?;
+      return Ok(out.take().into());
+    }
+    Inner::BrotliEncoder(d, out) => {
+      d.write_all(input)?;
+      if flush {
+        d.flush()?;
+      }
+      return Ok(out.take().into());
+    }
+    Inner::ZstdDecoder(d) => {
+      d.write_all(input)?;
+      d.flush()?;
+      d.get_mut().drain(..)
+    }
+    Inner::ZstdEncoder(d) => {
+      d.write_all(input)?;
+      if flush {
+        d.flush()?;
+      }
+      d.get_mut().drain(..)
+    }
+    Inner::DictionaryDecoder(d) => return Ok(d.write(input)?.into()),
+    Inner::FlateEncoder(d) => return Ok(d.write(input)?.into()),
   }
   .collect();
   Ok(out.into())
@@ -123,6 +318,322 @@ pub fn op_compression_finish(
     Inner::DeflateRawEncoder(d) => d.finish()?,
     Inner::GzDecoder(d) => d.finish()?,
     Inner::GzEncoder(d) => d.finish()?,
+    Inner::BrotliDecoder(d, out) => {
+      d.into_inner()
+        .map_err(|_| type_error("Unexpected end of compressed data"))?;
+      out.take()
+    }
+    Inner::BrotliEncoder(d, out) => {
+      // taking the writer back writes the end of the stream
+      d.into_inner();
+      out.take()
+    }
+    Inner::ZstdDecoder(mut d) => {
+      d.flush()?;
+      d.into_inner()
+    }
+    Inner::ZstdEncoder(d) => d.finish()?,
+    Inner::DictionaryDecoder(d) => d.finish()?,
+    Inner::FlateEncoder(d) => d.finish()?,
   };
   Ok(out.into())
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FlateFormat {
+  /// "deflate"
+  Zlib,
+  /// "deflate-raw"
+  Raw,
+  /// "gzip"
+  Gzip,
+}
+
+enum Checksum {
+  None,
+  Adler32(u32),
+  Crc32(Crc),
+}
+
+/// A "deflate", "deflate-raw" or "gzip" encoder that drives `Compress`
+/// itself, since the writers of `flate2` always do a sync flush.
+///
+/// The `miniz_oxide` backend of `flate2` can't be given a dictionary, so
+/// a dictionary is compressed up front and that output is dropped. This
+/// leaves the dictionary in the window and the stream at a block boundary,
+/// which is the same state zlib's `deflateSetDictionary()` leads to.
+struct FlateEncoder {
+  compress: Compress,
+  /// The zlib or gzip header, until it's been written out.
+  header: Vec<u8>,
+  /// The checksum of the uncompressed data, for the trailer.
+  checksum: Checksum,
+  flush: FlushMode,
+}
+
+impl FlateEncoder {
+  fn new(
+    format: FlateFormat,
+    level: Compression,
+    dictionary: Option<&[u8]>,
+    flush: FlushMode,
+  ) -> Result<Self, AnyError> {
+    let mut compress = Compress::new(level, false);
+    if let Some(dictionary) = dictionary {
+      compress_all(
+        &mut compress,
+        dictionary,
+        FlushCompress::Sync,
+        &mut Vec::new(),
+      )?;
+    }
+    let (header, checksum) = match format {
+      FlateFormat::Zlib => {
+        (zlib_header(level, dictionary), Checksum::Adler32(1))
+      }
+      FlateFormat::Raw => (Vec::new(), Checksum::None),
+      FlateFormat::Gzip => (gzip_header(level), Checksum::Crc32(Crc::new())),
+    };
+    Ok(Self {
+      compress,
+      header,
+      checksum,
+      flush,
+    })
+  }
+
+  fn write(&mut self, input: &[u8]) -> Result<Vec<u8>, AnyError> {
+    let mut out = std::mem::take(&mut self.header);
+    match &mut self.checksum {
+      Checksum::None => {}
+      Checksum::Adler32(adler) => *adler = adler32(*adler, input),
+      Checksum::Crc32(crc) => crc.update(input),
+    }
+    compress_all(&mut self.compress, input, self.flush.into(), &mut out)?;
+    Ok(out)
+  }
+
+  fn finish(mut self) -> Result<Vec<u8>, AnyError> {
+    let mut out = std::mem::take(&mut self.header);
+    compress_all(&mut self.compress, &[], FlushCompress::Finish, &mut out)?;
+    match self.checksum {
+      Checksum::None => {}
+      Checksum::Adler32(adler) => out.extend_from_slice(&adler.to_be_bytes()),
+      Checksum::Crc32(crc) => {
+        out.extend_from_slice(&crc.sum().to_le_bytes());
+        out.extend_from_slice(&crc.amount().to_le_bytes());
+      }
+    }
+    Ok(out)
+  }
+}
+
+/// A "deflate" or "deflate-raw" decoder with a preset dictionary, primed the
+/// same way as [`FlateEncoder`].
+#[derive(Debug)]
+struct DictionaryDecoder {
+  decompress: Decompress,
+  dictionary_id: u32,
+  /// The zlib header, while it's being read.
+  header: Option<Vec<u8>>,
+  /// The zlib trailer, once the compressed data has ended.
+  trailer: Vec<u8>,
+  /// The checksum of the uncompressed data, for the "deflate" format.
+  adler: Option<u32>,
+  ended: bool,
+}
+
+impl DictionaryDecoder {
+  fn new(dictionary: &[u8], zlib: bool) -> Result<Self, AnyError> {
+    let mut primer = Vec::new();
+    compress_all(
+      &mut Compress::new(Compression::default(), false),
+      dictionary,
+      FlushCompress::Sync,
+      &mut primer,
+    )?;
+    let mut decompress = Decompress::new(false);
+    decompress_all(&mut decompress, &primer, &mut Vec::new())?;
+    Ok(Self {
+      decompress,
+      dictionary_id: adler32(1, dictionary),
+      header: zlib.then(Vec::new),
+      trailer: Vec::new(),
+      adler: zlib.then_some(1),
+      ended: false,
+    })
+  }
+
+  fn write(&mut self, mut input: &[u8]) -> Result<Vec<u8>, AnyError> {
+    while let Some(header) = &mut self.header {
+      if header.len() == zlib_header_len(header) {
+        check_zlib_header(header, self.dictionary_id)?;
+        self.header = None;
+      } else if let Some((byte, rest)) = input.split_first() {
+        header.push(*byte);
+        input = rest;
+      } else {
+        return Ok(Vec::new());
+      }
+    }
+
+    let mut out = Vec::new();
+    if !self.ended {
+      let (rest, ended) =
+        decompress_all(&mut self.decompress, input, &mut out)?;
+      input = rest;
+      self.ended = ended;
+      if let Some(adler) = &mut self.adler {
+        *adler = adler32(*adler, &out);
+      }
+    }
+    if self.adler.is_some() {
+      self.trailer.extend_from_slice(input);
+      if self.trailer.len() > 4 {
+        return Err(type_error("Junk found after end of compressed data"));
+      }
+    } else if !input.is_empty() {
+      return Err(type_error("Junk found after end of compressed data"));
+    }
+    Ok(out)
+  }
+
+  fn finish(self) -> Result<Vec<u8>, AnyError> {
+    if self.header.is_some() || !self.ended {
+      return Err(type_error("Unexpected end of compressed data"));
+    }
+    if let Some(adler) = self.adler {
+      if self.trailer.len() < 4 {
+        return Err(type_error("Unexpected end of compressed data"));
+      }
+      if self.trailer[..] != adler.to_be_bytes() {
+        return Err(type_error("Incorrect data check"));
+      }
+    }
+    Ok(Vec::new())
+  }
+}
+
+fn compress_all(
+  compress: &mut Compress,
+  mut input: &[u8],
+  flush: FlushCompress,
+  out: &mut Vec<u8>,
+) -> Result<(), AnyError> {
+  loop {
+    out.reserve(input.len() + 1024);
+    let total_in = compress.total_in();
+    let status = compress.compress_vec(input, out, flush)?;
+    input = &input[(compress.total_in() - total_in) as usize..];
+    let done = match flush {
+      FlushCompress::Finish => status == Status::StreamEnd,
+      _ => input.is_empty() && out.len() < out.capacity(),
+    };
+    if done {
+      return Ok(());
+    }
+  }
+}
+
+/// Returns the input that's left after the end of the compressed data and
+/// whether the end was reached.
+fn decompress_all<'a>(
+  decompress: &mut Decompress,
+  mut input: &'a [u8],
+  out: &mut Vec<u8>,
+) -> Result<(&'a [u8], bool), AnyError> {
+  loop {
+    out.reserve(input.len() * 2 + 1024);
+    let total_in = decompress.total_in();
+    let total_out = decompress.total_out();
+    let status =
+      decompress.decompress_vec(input, out, FlushDecompress::None)?;
+    input = &input[(decompress.total_in() - total_in) as usize..];
+    if status == Status::StreamEnd {
+      return Ok((input, true));
+    }
+    let made_progress =
+      decompress.total_in() != total_in || decompress.total_out() != total_out;
+    if !made_progress || (input.is_empty() && out.len() < out.capacity()) {
+      return Ok((input, false));
+    }
+  }
+}
+
+fn zlib_header(level: Compression, dictionary: Option<&[u8]>) -> Vec<u8> {
+  // deflate with a 32K window
+  let cmf = 0x78u8;
+  let flevel = match level.level() {
+    0 | 1 => 0,
+    2..=5 => 1,
+    6 => 2,
+    _ => 3,
+  };
+  let mut flg = flevel << 6;
+  if dictionary.is_some() {
+    // FDICT
+    flg |= 0x20;
+  }
+  let check = (u16::from(cmf) << 8 | u16::from(flg)) % 31;
+  if check != 0 {
+    flg += (31 - check) as u8;
+  }
+  let mut header = vec![cmf, flg];
+  if let Some(dictionary) = dictionary {
+    header.extend_from_slice(&adler32(1, dictionary).to_be_bytes());
+  }
+  header
+}
+
+/// The same header as the one of `flate2::write::GzEncoder`.
+fn gzip_header(level: Compression) -> Vec<u8> {
+  let xfl = match level.level() {
+    9 => 2,
+    1 => 4,
+    _ => 0,
+  };
+  // deflate, no flags, no modification time, unknown OS
+  vec![0x1f, 0x8b, 8, 0, 0, 0, 0, 0, xfl, 0xff]
+}
+
+fn zlib_header_len(header: &[u8]) -> usize {
+  if header.len() >= 2 && header[1] & 0x20 != 0 {
+    6
+  } else {
+    2
+  }
+}
+
+fn check_zlib_header(
+  header: &[u8],
+  dictionary_id: u32,
+) -> Result<(), AnyError> {
+  let (cmf, flg) = (header[0], header[1]);
+  if cmf & 0x0f != 8 || (u16::from(cmf) << 8 | u16::from(flg)) % 31 != 0 {
+    return Err(type_error("Invalid zlib header"));
+  }
+  if flg & 0x20 != 0 {
+    let id = u32::from_be_bytes([header[2], header[3], header[4], header[5]]);
+    if id != dictionary_id {
+      return Err(type_error("Incorrect dictionary"));
+    }
+  }
+  Ok(())
+}
+
+fn adler32(adler: u32, data: &[u8]) -> u32 {
+  const MOD: u32 = 65521;
+  // the most bytes that can be added up before `b` could overflow
+  const NMAX: usize = 5552;
+  let mut a = adler & 0xffff;
+  let mut b = adler >> 16;
+  for chunk in data.chunks(NMAX) {
+    for byte in chunk {
+      a += u32::from(*byte);
+      b += a;
+    }
+    a %= MOD;
+    b %= MOD;
+  }
+  (b << 16) | a
+}
@@ -0,0 +1,102 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Errors that make ops throw a `DOMException` with one of the names of the
+//! WebIDL error names table, so that ops in any extension can raise
+//! spec-correct exceptions.
+//!
+//! The class name of each error is the name prefixed by `DOMException`, for
+//! which `01_dom_exception.js` registers an error builder.
+//! See <https://webidl.spec.whatwg.org/#dfn-error-names-table>
+
+use deno_core::error::AnyError;
+use std::fmt;
+
+macro_rules! dom_exceptions {
+  ($($ty:ident => $name:literal,)*) => {
+    $(
+      #[derive(Debug)]
+      pub struct $ty {
+        pub msg: String,
+      }
+
+      impl $ty {
+        pub fn new(msg: &str) -> Self {
+          $ty {
+            msg: msg.to_string(),
+          }
+        }
+      }
+
+      impl fmt::Display for $ty {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+          f.pad(&self.msg)
+        }
+      }
+
+      impl std::error::Error for $ty {}
+    )*
+
+    pub fn get_error_class_name(e: &AnyError) -> Option<&'static str> {
+      $(
+        if e.is::<$ty>() {
+          return Some(concat!("DOMException", $name));
+        }
+      )*
+      None
+    }
+  };
+}
+
+dom_exceptions! {
+  DomExceptionIndexSizeError => "IndexSizeError",
+  DomExceptionHierarchyRequestError => "HierarchyRequestError",
+  DomExceptionWrongDocumentError => "WrongDocumentError",
+  DomExceptionInvalidCharacterError => "InvalidCharacterError",
+  DomExceptionNoModificationAllowedError => "NoModificationAllowedError",
+  DomExceptionNotFoundError => "NotFoundError",
+  DomExceptionNotSupportedError => "NotSupportedError",
+  DomExceptionInUseAttributeError => "InUseAttributeError",
+  DomExceptionInvalidStateError => "InvalidStateError",
+  DomExceptionSyntaxError => "SyntaxError",
+  DomExceptionInvalidModificationError => "InvalidModificationError",
+  DomExceptionNamespaceError => "NamespaceError",
+  DomExceptionInvalidAccessError => "InvalidAccessError",
+  DomExceptionTypeMismatchError => "TypeMismatchError",
+  DomExceptionSecurityError => "SecurityError",
+  DomExceptionNetworkError => "NetworkError",
+  DomExceptionAbortError => "AbortError",
+  DomExceptionUrlMismatchError => "URLMismatchError",
+  DomExceptionQuotaExceededError => "QuotaExceededError",
+  DomExceptionTimeoutError => "TimeoutError",
+  DomExceptionInvalidNodeTypeError => "InvalidNodeTypeError",
+  DomExceptionDataCloneError => "DataCloneError",
+  DomExceptionEncodingError => "EncodingError",
+  DomExceptionNotReadableError => "NotReadableError",
+  DomExceptionUnknownError => "UnknownError",
+  DomExceptionConstraintError => "ConstraintError",
+  DomExceptionDataError => "DataError",
+  DomExceptionTransactionInactiveError => "TransactionInactiveError",
+  DomExceptionReadOnlyError => "ReadOnlyError",
+  DomExceptionVersionError => "VersionError",
+  DomExceptionOperationError => "OperationError",
+  DomExceptionNotAllowedError => "NotAllowedError",
+  DomExceptionOptOutError => "OptOutError",
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn error_class_names() {
+    let e: AnyError = DomExceptionDataCloneError::new("x").into();
+    assert_eq!(get_error_class_name(&e), Some("DOMExceptionDataCloneError"));
+    let e: AnyError = DomExceptionUrlMismatchError::new("x").into();
+    assert_eq!(
+      get_error_class_name(&e),
+      Some("DOMExceptionURLMismatchError")
+    );
+    let e: AnyError = deno_core::error::type_error("x");
+    assert_eq!(get_error_class_name(&e), None);
+  }
+}
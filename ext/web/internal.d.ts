@@ -103,6 +103,12 @@ declare module "ext:deno_web/13_message_port.js" {
   } | {
     kind: "arrayBuffer";
     data: number;
+  } | {
+    kind: "readableStream";
+    data: number;
+  } | {
+    kind: "writableStream";
+    data: number;
   };
   interface MessageData {
     data: Uint8Array;
@@ -202,6 +202,46 @@ declare function atob(s: string): string;
  */
 declare function btoa(s: string): string;
 
+/** @category Encoding API */
+declare interface Uint8ArrayFromBase64Options {
+  alphabet?: "base64" | "base64url";
+  lastChunkHandling?: "loose" | "strict" | "stop-before-partial";
+}
+
+/** @category Encoding API */
+declare interface Uint8ArrayToBase64Options {
+  alphabet?: "base64" | "base64url";
+  omitPadding?: boolean;
+}
+
+/** @category Encoding API */
+declare interface Uint8ArrayConstructor {
+  /** Decodes a base64 string into a new `Uint8Array`.
+   *
+   * ```
+   * Uint8Array.fromBase64("aGVsbG8="); // Uint8Array(5) [ 104, 101, ... ]
+   * ```
+   */
+  fromBase64(
+    string: string,
+    options?: Uint8ArrayFromBase64Options,
+  ): Uint8Array;
+}
+
+/** @category Encoding API */
+declare interface Uint8Array {
+  /** Encodes the bytes of the array as a base64 string. */
+  toBase64(options?: Uint8ArrayToBase64Options): string;
+  /** Decodes as much of a base64 string as fits into the array, and returns
+   * how many characters were read and how many bytes were written. With
+   * `lastChunkHandling: "stop-before-partial"`, the characters that weren't
+   * read can be prepended to the next chunk of a stream. */
+  setFromBase64(
+    string: string,
+    options?: Uint8ArrayFromBase64Options,
+  ): { read: number; written: number };
+}
+
 /** @category Encoding API */
 declare interface TextDecoderOptions {
   fatal?: boolean;
@@ -350,6 +390,120 @@ declare var AbortSignal: {
   timeout(milliseconds: number): AbortSignal;
 };
 
+/** The priority of a task that is posted with `scheduler.postTask()`.
+ *
+ * @category Web APIs
+ */
+type TaskPriority = "user-blocking" | "user-visible" | "background";
+
+/** @category Web APIs */
+interface TaskControllerInit {
+  priority?: TaskPriority;
+}
+
+/** A controller of the priority of tasks, which can also abort them.
+ *
+ * @category Web APIs
+ */
+declare class TaskController extends AbortController {
+  constructor(init?: TaskControllerInit);
+  /** Returns the TaskSignal object associated with this object. */
+  readonly signal: TaskSignal;
+  /** Changes the priority of the tasks associated with the signal, and fires
+   * a `prioritychange` event at it. */
+  setPriority(priority: TaskPriority): void;
+}
+
+/** @category Web APIs */
+interface TaskSignalEventMap extends AbortSignalEventMap {
+  prioritychange: TaskPriorityChangeEvent;
+}
+
+/** An abort signal that also carries the priority of the tasks that are
+ * posted with it.
+ *
+ * @category Web APIs
+ */
+interface TaskSignal extends AbortSignal {
+  readonly priority: TaskPriority;
+  onprioritychange:
+    | ((this: TaskSignal, ev: TaskPriorityChangeEvent) => any)
+    | null;
+  addEventListener<K extends keyof TaskSignalEventMap>(
+    type: K,
+    listener: (this: TaskSignal, ev: TaskSignalEventMap[K]) => any,
+    options?: boolean | AddEventListenerOptions,
+  ): void;
+  addEventListener(
+    type: string,
+    listener: EventListenerOrEventListenerObject,
+    options?: boolean | AddEventListenerOptions,
+  ): void;
+  removeEventListener<K extends keyof TaskSignalEventMap>(
+    type: K,
+    listener: (this: TaskSignal, ev: TaskSignalEventMap[K]) => any,
+    options?: boolean | EventListenerOptions,
+  ): void;
+  removeEventListener(
+    type: string,
+    listener: EventListenerOrEventListenerObject,
+    options?: boolean | EventListenerOptions,
+  ): void;
+}
+
+/** @category Web APIs */
+declare var TaskSignal: {
+  prototype: TaskSignal;
+  new (): never;
+};
+
+/** @category Web APIs */
+interface TaskPriorityChangeEventInit extends EventInit {
+  previousPriority: TaskPriority;
+}
+
+/** @category Web APIs */
+declare class TaskPriorityChangeEvent extends Event {
+  constructor(type: string, eventInitDict: TaskPriorityChangeEventInit);
+  readonly previousPriority: TaskPriority;
+}
+
+/** @category Web APIs */
+interface SchedulerPostTaskOptions {
+  /** The signal that aborts the task, and gives it its priority if it is a
+   * `TaskSignal` and `priority` isn't set. */
+  signal?: AbortSignal;
+  priority?: TaskPriority;
+  /** How many milliseconds to wait before queueing the task. */
+  delay?: number;
+}
+
+/** Schedules tasks by priority, for code that cooperatively yields to more
+ * important work.
+ *
+ * @category Web APIs
+ */
+interface Scheduler {
+  /** Runs `callback` in a task of the given priority, which is
+   * `"user-visible"` by default, and resolves with what it returns. */
+  postTask<T>(
+    callback: () => T | PromiseLike<T>,
+    options?: SchedulerPostTaskOptions,
+  ): Promise<T>;
+  /** Resolves in a task of the priority of the task that is running, which
+   * runs before the other tasks of that priority. */
+  yield(): Promise<void>;
+}
+
+/** @category Web APIs */
+declare var Scheduler: {
+  prototype: Scheduler;
+  new (): never;
+};
+
+/** @category Web APIs */
+declare var scheduler: Scheduler;
+
 /** @category Web File API */
 interface FileReaderEventMap {
   "abort": ProgressEvent<FileReader>;
@@ -962,6 +1116,44 @@ declare function structuredClone(
   options?: StructuredSerializeOptions,
 ): any;
 
+/**
+ * **UNSTABLE**: New API, yet to be vetted. This is a non-standard extension
+ * that requires the `--unstable` flag.
+ *
+ * @category Compression Streams API
+ */
+declare interface CompressionOptions {
+  /** The compression level, from `0` (none) to `9` (best), or up to `11`
+   * for "br" and `22` for "zstd". */
+  level?: number;
+  /** A preset dictionary for the "deflate" and "deflate-raw" formats. */
+  dictionary?: BufferSource;
+  /** How the output is flushed after every chunk that is written.
+   *
+   * - `"sync"`: Everything written so far is output, so it can be
+   *   decompressed right away, e.g. for server-sent events over gzip.
+   * - `"full"`: Like `"sync"`, but the compression state is also reset, so
+   *   that decompression can start from any chunk. This only makes a
+   *   difference for the "deflate", "deflate-raw" and "gzip" formats.
+   * - `"none"`: Output is only produced once enough data was written, which
+   *   gives the best compression.
+   *
+   * @default {"sync"}
+   */
+  flush?: "none" | "sync" | "full";
+}
+
+/**
+ * **UNSTABLE**: New API, yet to be vetted. This is a non-standard extension
+ * that requires the `--unstable` flag.
+ *
+ * @category Compression Streams API
+ */
+declare interface DecompressionOptions {
+  /** The preset dictionary the data was compressed with. */
+  dictionary?: BufferSource;
+}
+
 /**
  * An API for compressing a stream of data.
  *
@@ -977,12 +1169,13 @@ declare function structuredClone(
 declare class CompressionStream {
   /**
    * Creates a new `CompressionStream` object which compresses a stream of
-   * data.
+   * data. The supported formats are "deflate", "deflate-raw" and "gzip", as
+   * well as the non-standard "br" and "zstd".
    *
    * Throws a `TypeError` if the format passed to the constructor is not
    * supported.
    */
-  constructor(format: string);
+  constructor(format: string, options?: CompressionOptions);
 
   readonly readable: ReadableStream<Uint8Array>;
   readonly writable: WritableStream<Uint8Array>;
@@ -1006,12 +1199,13 @@ declare class CompressionStream {
 declare class DecompressionStream {
   /**
    * Creates a new `DecompressionStream` object which decompresses a stream of
-   * data.
+   * data. The supported formats are "deflate", "deflate-raw" and "gzip", as
+   * well as the non-standard "br" and "zstd".
    *
    * Throws a `TypeError` if the format passed to the constructor is not
    * supported.
    */
-  constructor(format: string);
+  constructor(format: string, options?: DecompressionOptions);
 
   readonly readable: ReadableStream<Uint8Array>;
   readonly writable: WritableStream<Uint8Array>;
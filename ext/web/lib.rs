@@ -1,9 +1,15 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+mod async_context;
+mod base64;
 mod blob;
+mod charset_sniff;
 mod compression;
+mod dom_exception;
 mod hr_timer_lock;
 mod message_port;
+mod single_byte;
+mod stream_resource;
 mod timers;
 
 use deno_core::error::range_error;
@@ -27,21 +33,31 @@ use encoding_rs::DecoderResult;
 use encoding_rs::Encoding;
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::fmt;
 use std::path::PathBuf;
 use std::usize;
 
+pub use crate::async_context::get_async_context;
+use crate::async_context::op_async_context_share;
+pub use crate::async_context::set_async_context;
+
 use crate::blob::op_blob_create_object_url;
 use crate::blob::op_blob_create_part;
 use crate::blob::op_blob_from_object_url;
+use crate::blob::op_blob_open_reader;
 use crate::blob::op_blob_read_part;
 use crate::blob::op_blob_remove_part;
 use crate::blob::op_blob_revoke_object_url;
 use crate::blob::op_blob_slice_part;
+use crate::blob::op_blob_store_metrics;
 pub use crate::blob::Blob;
 pub use crate::blob::BlobPart;
+pub use crate::blob::BlobReader;
 pub use crate::blob::BlobStore;
+pub use crate::blob::BlobStoreLimits;
+pub use crate::blob::BlobStoreMetrics;
+pub use crate::blob::FileBlobPart;
 pub use crate::blob::InMemoryBlobPart;
+pub use crate::blob::DEFAULT_BLOB_MEMORY_LIMIT;
 
 pub use crate::message_port::create_entangled_message_port;
 use crate::message_port::op_message_port_create_entangled;
@@ -50,48 +66,104 @@ use crate::message_port::op_message_port_recv_message;
 pub use crate::message_port::JsMessageData;
 pub use crate::message_port::MessagePort;
 
+pub use crate::charset_sniff::sniff_encoding;
+
+pub use crate::dom_exception::get_error_class_name;
+pub use crate::dom_exception::DomExceptionAbortError;
+pub use crate::dom_exception::DomExceptionConstraintError;
+pub use crate::dom_exception::DomExceptionDataCloneError;
+pub use crate::dom_exception::DomExceptionDataError;
+pub use crate::dom_exception::DomExceptionEncodingError;
+pub use crate::dom_exception::DomExceptionHierarchyRequestError;
+pub use crate::dom_exception::DomExceptionInUseAttributeError;
+pub use crate::dom_exception::DomExceptionIndexSizeError;
+pub use crate::dom_exception::DomExceptionInvalidAccessError;
+pub use crate::dom_exception::DomExceptionInvalidCharacterError;
+pub use crate::dom_exception::DomExceptionInvalidModificationError;
+pub use crate::dom_exception::DomExceptionInvalidNodeTypeError;
+pub use crate::dom_exception::DomExceptionInvalidStateError;
+pub use crate::dom_exception::DomExceptionNamespaceError;
+pub use crate::dom_exception::DomExceptionNetworkError;
+pub use crate::dom_exception::DomExceptionNoModificationAllowedError;
+pub use crate::dom_exception::DomExceptionNotAllowedError;
+pub use crate::dom_exception::DomExceptionNotFoundError;
+pub use crate::dom_exception::DomExceptionNotReadableError;
+pub use crate::dom_exception::DomExceptionNotSupportedError;
+pub use crate::dom_exception::DomExceptionOperationError;
+pub use crate::dom_exception::DomExceptionOptOutError;
+pub use crate::dom_exception::DomExceptionQuotaExceededError;
+pub use crate::dom_exception::DomExceptionReadOnlyError;
+pub use crate::dom_exception::DomExceptionSecurityError;
+pub use crate::dom_exception::DomExceptionSyntaxError;
+pub use crate::dom_exception::DomExceptionTimeoutError;
+pub use crate::dom_exception::DomExceptionTransactionInactiveError;
+pub use crate::dom_exception::DomExceptionTypeMismatchError;
+pub use crate::dom_exception::DomExceptionUnknownError;
+pub use crate::dom_exception::DomExceptionUrlMismatchError;
+pub use crate::dom_exception::DomExceptionVersionError;
+pub use crate::dom_exception::DomExceptionWrongDocumentError;
+
+use crate::single_byte::SingleByteDecoder;
+
+use crate::stream_resource::op_pipe_resources;
+
 use crate::timers::op_now;
 use crate::timers::op_sleep;
+use crate::timers::op_sleep_interval;
 use crate::timers::op_timer_handle;
 use crate::timers::StartTime;
+pub use crate::timers::TimerCoalescing;
 pub use crate::timers::TimersPermission;
 
+struct Unstable(bool); // --unstable
+
 deno_core::extension!(deno_web,
   deps = [ deno_webidl, deno_console, deno_url ],
   parameters = [P: TimersPermission],
   ops = [
+    op_async_context_share,
     op_base64_decode,
     op_base64_encode,
     op_base64_atob,
     op_base64_btoa,
+    base64::op_base64_decode_with_options,
+    base64::op_base64_decode_into,
+    base64::op_base64_encode_with_options,
     op_encoding_normalize_label,
     op_encoding_decode_single,
     op_encoding_decode_utf8,
     op_encoding_new_decoder,
     op_encoding_decode,
+    op_encoding_decode_stream,
     op_encoding_encode_into,
     op_encode_binary_string,
     op_blob_create_part,
     op_blob_slice_part,
     op_blob_read_part,
+    op_blob_open_reader,
     op_blob_remove_part,
     op_blob_create_object_url,
     op_blob_revoke_object_url,
     op_blob_from_object_url,
+    op_blob_store_metrics,
     op_message_port_create_entangled,
     op_message_port_post_message,
     op_message_port_recv_message,
     compression::op_compression_new,
+    compression::op_compression_new_with_options,
     compression::op_compression_write,
     compression::op_compression_finish,
+    op_pipe_resources,
     op_now<P>,
     op_timer_handle,
     op_cancel_handle,
     op_sleep,
+    op_sleep_interval,
     op_transfer_arraybuffer,
   ],
   esm = [
     "00_infra.js",
+    "01_async_context.js",
     "01_dom_exception.js",
     "01_mimesniff.js",
     "02_event.js",
@@ -112,13 +184,16 @@ deno_core::extension!(deno_web,
   options = {
     blob_store: BlobStore,
     maybe_location: Option<Url>,
+    unstable: bool,
   },
   state = |state, options| {
     state.put(options.blob_store);
+    state.put(Unstable(options.unstable));
     if let Some(location) = options.maybe_location {
       state.put(Location(location));
     }
     state.put(StartTime::now());
+    state.put(SingleByteDecoder::default());
   }
 );
 
@@ -209,19 +284,35 @@ fn op_encoding_decode_utf8<'a>(
   }
 }
 
-#[op]
-fn op_encoding_decode_single(
+/// Single-byte encodings are decoded with a table by `SingleByteDecoder`,
+/// and the others with `encoding_rs`.
+#[op(v8)]
+fn op_encoding_decode_single<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  state: &mut OpState,
   data: &[u8],
   label: String,
   fatal: bool,
   ignore_bom: bool,
-) -> Result<U16String, AnyError> {
+) -> Result<serde_v8::Value<'a>, AnyError> {
   let encoding = Encoding::for_label(label.as_bytes()).ok_or_else(|| {
     range_error(format!(
       "The encoding label provided ('{label}') is invalid."
     ))
   })?;
 
+  let single_byte_decoder = state.borrow_mut::<SingleByteDecoder>();
+  if let Some(text) =
+    single_byte_decoder.decode(encoding, data, fatal, |output| {
+      v8::String::new_from_two_byte(scope, output, v8::NewStringType::Normal)
+    })?
+  {
+    return match text {
+      Some(text) => Ok(serde_v8::from_v8(scope, text.into())?),
+      None => Err(type_error("buffer exceeds maximum length")),
+    };
+  }
+
   let mut decoder = if ignore_bom {
     encoding.new_decoder_without_bom_handling()
   } else {
@@ -233,32 +324,14 @@ fn op_encoding_decode_single(
     .ok_or_else(|| range_error("Value too large to decode."))?;
 
   let mut output = vec![0; max_buffer_length];
-
-  if fatal {
-    let (result, _, written) =
-      decoder.decode_to_utf16_without_replacement(data, &mut output, true);
-    match result {
-      DecoderResult::InputEmpty => {
-        output.truncate(written);
-        Ok(output.into())
-      }
-      DecoderResult::OutputFull => {
-        Err(range_error("Provided buffer too small."))
-      }
-      DecoderResult::Malformed(_, _) => {
-        Err(type_error("The encoded data is not valid."))
-      }
-    }
-  } else {
-    let (result, _, written, _) =
-      decoder.decode_to_utf16(data, &mut output, true);
-    match result {
-      CoderResult::InputEmpty => {
-        output.truncate(written);
-        Ok(output.into())
-      }
-      CoderResult::OutputFull => Err(range_error("Provided buffer too small.")),
-    }
+  let written = decode_to_utf16(&mut decoder, fatal, data, &mut output, true)?;
+  match v8::String::new_from_two_byte(
+    scope,
+    &output[..written],
+    v8::NewStringType::Normal,
+  ) {
+    Some(text) => Ok(serde_v8::from_v8(scope, text.into())?),
+    None => Err(type_error("buffer exceeds maximum length")),
   }
 }
 
@@ -284,6 +357,7 @@ fn op_encoding_new_decoder(
   let rid = state.resource_table.add(TextDecoderResource {
     decoder: RefCell::new(decoder),
     fatal,
+    output: RefCell::new(Vec::new()),
   });
 
   Ok(rid)
@@ -306,15 +380,68 @@ fn op_encoding_decode(
     .ok_or_else(|| range_error("Value too large to decode."))?;
 
   let mut output = vec![0; max_buffer_length];
+  let written =
+    decode_to_utf16(&mut decoder, fatal, data, &mut output, !stream)?;
+  output.truncate(written);
+  Ok(output.into())
+}
 
+/// Decodes a chunk of a stream into the buffer of the decoder, which is
+/// reused across chunks, and creates the string straight from the decoded
+/// part of it. This saves an allocation and a copy per chunk compared to
+/// `op_encoding_decode`, which matters for `TextDecoderStream` on large
+/// inputs.
+#[op(v8)]
+fn op_encoding_decode_stream<'a>(
+  scope: &mut v8::HandleScope<'a>,
+  state: &mut OpState,
+  data: &[u8],
+  rid: ResourceId,
+) -> Result<serde_v8::Value<'a>, AnyError> {
+  let resource = state.resource_table.get::<TextDecoderResource>(rid)?;
+
+  let mut decoder = resource.decoder.borrow_mut();
+  let mut output = resource.output.borrow_mut();
+
+  let max_buffer_length = decoder
+    .max_utf16_buffer_length(data.len())
+    .ok_or_else(|| range_error("Value too large to decode."))?;
+  if output.len() < max_buffer_length {
+    output.resize(max_buffer_length, 0);
+  }
+
+  let written = decode_to_utf16(
+    &mut decoder,
+    resource.fatal,
+    data,
+    &mut output[..max_buffer_length],
+    false,
+  )?;
+
+  match v8::String::new_from_two_byte(
+    scope,
+    &output[..written],
+    v8::NewStringType::Normal,
+  ) {
+    Some(text) => Ok(serde_v8::from_v8(scope, text.into())?),
+    None => Err(type_error("buffer exceeds maximum length")),
+  }
+}
+
+/// Returns the number of code units written to `output`, which must be big
+/// enough for the whole of `data`.
+fn decode_to_utf16(
+  decoder: &mut Decoder,
+  fatal: bool,
+  data: &[u8],
+  output: &mut [u16],
+  last: bool,
+) -> Result<usize, AnyError> {
   if fatal {
     let (result, _, written) =
-      decoder.decode_to_utf16_without_replacement(data, &mut output, !stream);
+      decoder.decode_to_utf16_without_replacement(data, output, last);
     match result {
-      DecoderResult::InputEmpty => {
-        output.truncate(written);
-        Ok(output.into())
-      }
+      DecoderResult::InputEmpty => Ok(written),
       DecoderResult::OutputFull => {
         Err(range_error("Provided buffer too small."))
       }
@@ -323,13 +450,9 @@ fn op_encoding_decode(
       }
     }
   } else {
-    let (result, _, written, _) =
-      decoder.decode_to_utf16(data, &mut output, !stream);
+    let (result, _, written, _) = decoder.decode_to_utf16(data, output, last);
     match result {
-      CoderResult::InputEmpty => {
-        output.truncate(written);
-        Ok(output.into())
-      }
+      CoderResult::InputEmpty => Ok(written),
       CoderResult::OutputFull => Err(range_error("Provided buffer too small.")),
     }
   }
@@ -338,6 +461,8 @@ fn op_encoding_decode(
 struct TextDecoderResource {
   decoder: RefCell<Decoder>,
   fatal: bool,
+  /// The buffer `op_encoding_decode_stream` decodes into.
+  output: RefCell<Vec<u16>>,
 }
 
 impl Resource for TextDecoderResource {
@@ -355,6 +480,13 @@ fn op_encoding_encode_into_fallback(
 ) -> Result<(), AnyError> {
   let s = v8::Local::<v8::String>::try_from(input.v8_value)?;
 
+  if !s.is_onebyte() {
+    let (read, written) = encode_utf16_into(scope, s, buffer);
+    out_buf[0] = read as u32;
+    out_buf[1] = written as u32;
+    return Ok(());
+  }
+
   let mut nchars = 0;
   out_buf[1] = s.write_utf8(
     scope,
@@ -367,6 +499,27 @@ fn op_encoding_encode_into_fallback(
   Ok(())
 }
 
+/// Encodes a two-byte string into `buffer`, which `write_utf8()` does one
+/// code point at a time. Its UTF-16 code units are copied out of V8 instead,
+/// and converted to UTF-8 with SIMD by encoding_rs. Returns how many code
+/// units were read and how many bytes were written.
+fn encode_utf16_into(
+  scope: &mut v8::HandleScope,
+  s: v8::Local<v8::String>,
+  buffer: &mut [u8],
+) -> (usize, usize) {
+  // every code unit is at least one byte in UTF-8, so the ones that don't
+  // fit in `buffer` aren't copied
+  let length = s.length();
+  let mut utf16 = vec![0u16; length.min(buffer.len())];
+  s.write(scope, &mut utf16, 0, v8::WriteOptions::NO_NULL_TERMINATION);
+  // a surrogate pair that was cut in half would be encoded as U+FFFD
+  if utf16.len() < length && matches!(utf16.last(), Some(0xD800..=0xDBFF)) {
+    utf16.pop();
+  }
+  encoding_rs::mem::convert_utf16_to_utf8_partial(&utf16, buffer)
+}
+
 #[op(fast, slow = op_encoding_encode_into_fallback)]
 fn op_encoding_encode_into(
   input: Cow<'_, str>,
@@ -438,53 +591,4 @@ pub fn get_declaration() -> PathBuf {
   PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib.deno_web.d.ts")
 }
 
-#[derive(Debug)]
-pub struct DomExceptionQuotaExceededError {
-  pub msg: String,
-}
-
-impl DomExceptionQuotaExceededError {
-  pub fn new(msg: &str) -> Self {
-    DomExceptionQuotaExceededError {
-      msg: msg.to_string(),
-    }
-  }
-}
-
-#[derive(Debug)]
-pub struct DomExceptionInvalidCharacterError {
-  pub msg: String,
-}
-
-impl DomExceptionInvalidCharacterError {
-  pub fn new(msg: &str) -> Self {
-    DomExceptionInvalidCharacterError {
-      msg: msg.to_string(),
-    }
-  }
-}
-
-impl fmt::Display for DomExceptionQuotaExceededError {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    f.pad(&self.msg)
-  }
-}
-impl fmt::Display for DomExceptionInvalidCharacterError {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    f.pad(&self.msg)
-  }
-}
-
-impl std::error::Error for DomExceptionQuotaExceededError {}
-
-impl std::error::Error for DomExceptionInvalidCharacterError {}
-
-pub fn get_error_class_name(e: &AnyError) -> Option<&'static str> {
-  e.downcast_ref::<DomExceptionQuotaExceededError>()
-    .map(|_| "DOMExceptionQuotaExceededError")
-    .or_else(|| {
-      e.downcast_ref::<DomExceptionInvalidCharacterError>()
-        .map(|_| "DOMExceptionInvalidCharacterError")
-    })
-}
 pub struct Location(pub Url);
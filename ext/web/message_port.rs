@@ -24,6 +24,9 @@ use tokio::sync::mpsc::UnboundedSender;
 enum Transferable {
   MessagePort(MessagePort),
   ArrayBuffer(u32),
+  /// A stream is transferred as the port its chunks are proxied through.
+  ReadableStream(MessagePort),
+  WritableStream(MessagePort),
 }
 
 type MessagePortMessage = (DetachedBuffer, Vec<Transferable>);
@@ -136,6 +139,29 @@ pub enum JsTransferable {
   #[serde(rename_all = "camelCase")]
   MessagePort(ResourceId),
   ArrayBuffer(u32),
+  ReadableStream(ResourceId),
+  WritableStream(ResourceId),
+}
+
+fn take_message_port(
+  state: &mut OpState,
+  rid: ResourceId,
+) -> Result<MessagePort, AnyError> {
+  let resource = state
+    .resource_table
+    .take::<MessagePortResource>(rid)
+    .map_err(|_| type_error("Invalid message port transfer"))?;
+  resource.cancel.cancel();
+  let resource = Rc::try_unwrap(resource)
+    .map_err(|_| type_error("Message port is not ready for transfer"))?;
+  Ok(resource.port)
+}
+
+fn add_message_port(state: &mut OpState, port: MessagePort) -> ResourceId {
+  state.resource_table.add(MessagePortResource {
+    port,
+    cancel: CancelHandle::new(),
+  })
 }
 
 fn deserialize_js_transferables(
@@ -146,18 +172,20 @@ fn deserialize_js_transferables(
   for js_transferable in js_transferables {
     match js_transferable {
       JsTransferable::MessagePort(id) => {
-        let resource = state
-          .resource_table
-          .take::<MessagePortResource>(id)
-          .map_err(|_| type_error("Invalid message port transfer"))?;
-        resource.cancel.cancel();
-        let resource = Rc::try_unwrap(resource)
-          .map_err(|_| type_error("Message port is not ready for transfer"))?;
-        transferables.push(Transferable::MessagePort(resource.port));
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::MessagePort(port));
       }
       JsTransferable::ArrayBuffer(id) => {
         transferables.push(Transferable::ArrayBuffer(id));
       }
+      JsTransferable::ReadableStream(id) => {
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::ReadableStream(port));
+      }
+      JsTransferable::WritableStream(id) => {
+        let port = take_message_port(state, id)?;
+        transferables.push(Transferable::WritableStream(port));
+      }
     }
   }
   Ok(transferables)
@@ -171,15 +199,20 @@ fn serialize_transferables(
   for transferable in transferables {
     match transferable {
       Transferable::MessagePort(port) => {
-        let rid = state.resource_table.add(MessagePortResource {
-          port,
-          cancel: CancelHandle::new(),
-        });
+        let rid = add_message_port(state, port);
         js_transferables.push(JsTransferable::MessagePort(rid));
       }
       Transferable::ArrayBuffer(id) => {
         js_transferables.push(JsTransferable::ArrayBuffer(id));
       }
+      Transferable::ReadableStream(port) => {
+        let rid = add_message_port(state, port);
+        js_transferables.push(JsTransferable::ReadableStream(rid));
+      }
+      Transferable::WritableStream(port) => {
+        let rid = add_message_port(state, port);
+        js_transferables.push(JsTransferable::WritableStream(rid));
+      }
     }
   }
   js_transferables
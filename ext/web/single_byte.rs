@@ -0,0 +1,121 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Single pass decoding of the single-byte encodings, like windows-1252 (which
+//! `latin1` is a label of). Each byte of these maps to one UTF-16 code unit,
+//! so they are decoded with a lookup table instead of the state machine of
+//! `encoding_rs`, into a buffer that is reused across calls.
+
+use std::collections::HashMap;
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use encoding_rs::Encoding;
+
+/// What the bytes that an encoding doesn't map are decoded to.
+const REPLACEMENT: u16 = 0xfffd;
+
+/// The decoded buffer is only kept for the next calls up to this many code
+/// units, so that decoding a large file once doesn't hold on to its memory.
+const MAX_RETAINED_LEN: usize = 64 * 1024;
+
+#[derive(Default)]
+pub struct SingleByteDecoder {
+  /// The code unit of every byte, by encoding name.
+  tables: HashMap<&'static str, [u16; 256]>,
+  output: Vec<u16>,
+}
+
+impl SingleByteDecoder {
+  /// Decodes `data` and passes the code units to `f`. Returns `Ok(None)` if
+  /// `encoding` isn't a single-byte encoding.
+  pub fn decode<R>(
+    &mut self,
+    encoding: &'static Encoding,
+    data: &[u8],
+    fatal: bool,
+    f: impl FnOnce(&[u16]) -> R,
+  ) -> Result<Option<R>, AnyError> {
+    if !encoding.is_single_byte() {
+      return Ok(None);
+    }
+    let table = self
+      .tables
+      .entry(encoding.name())
+      .or_insert_with(|| build_table(encoding));
+
+    self.output.clear();
+    self
+      .output
+      .extend(data.iter().map(|byte| table[*byte as usize]));
+    let result = if fatal && self.output.contains(&REPLACEMENT) {
+      Err(type_error("The encoded data is not valid."))
+    } else {
+      Ok(Some(f(&self.output)))
+    };
+    if self.output.capacity() > MAX_RETAINED_LEN {
+      self.output = Vec::new();
+    }
+    result
+  }
+}
+
+fn build_table(encoding: &'static Encoding) -> [u16; 256] {
+  let bytes = (0..=255).collect::<Vec<u8>>();
+  let mut decoder = encoding.new_decoder_without_bom_handling();
+  let mut table = [0; 256];
+  let (_, read, written, _) = decoder.decode_to_utf16(&bytes, &mut table, true);
+  debug_assert_eq!((read, written), (256, 256));
+  table
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode(
+    label: &str,
+    data: &[u8],
+    fatal: bool,
+  ) -> Result<Option<String>, AnyError> {
+    let encoding = Encoding::for_label(label.as_bytes()).unwrap();
+    let mut decoder = SingleByteDecoder::default();
+    decoder.decode(encoding, data, fatal, |output| {
+      String::from_utf16(output).unwrap()
+    })
+  }
+
+  #[test]
+  fn decode_single_byte() {
+    assert_eq!(
+      decode("latin1", b"caf\xe9 \x80\x9f", false).unwrap(),
+      Some("café €Ÿ".to_string())
+    );
+    assert_eq!(
+      decode("iso-8859-7", b"\xe1\xe2", true).unwrap(),
+      Some("αβ".to_string())
+    );
+    assert_eq!(decode("utf-8", b"abc", false).unwrap(), None);
+    assert_eq!(decode("shift_jis", b"abc", false).unwrap(), None);
+  }
+
+  #[test]
+  fn decode_single_byte_unmapped() {
+    // 0xaa isn't mapped by windows-1253
+    assert_eq!(
+      decode("windows-1253", b"a\xaab", false).unwrap(),
+      Some("a\u{fffd}b".to_string())
+    );
+    assert!(decode("windows-1253", b"a\xaab", true).is_err());
+  }
+
+  #[test]
+  fn decode_single_byte_every_encoding() {
+    // the table agrees with encoding_rs for every byte
+    let bytes = (0..=255).collect::<Vec<u8>>();
+    for label in ["windows-1252", "koi8-r", "ibm866", "x-user-defined"] {
+      let encoding = Encoding::for_label(label.as_bytes()).unwrap();
+      let (expected, _) = encoding.decode_without_bom_handling(&bytes);
+      assert_eq!(decode(label, &bytes, false).unwrap().unwrap(), expected);
+    }
+  }
+}
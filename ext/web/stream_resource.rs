@@ -0,0 +1,39 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use deno_core::error::AnyError;
+use deno_core::op;
+use deno_core::OpState;
+use deno_core::ResourceId;
+
+/// The maximum size of a single chunk that is piped between resources.
+const PIPE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Pipes the contents of one resource into another until the source is
+/// exhausted, without handing the chunks to JavaScript.
+///
+/// A chunk is only read once the previous one was fully written, so a slow
+/// destination applies backpressure to the source.
+#[op]
+pub async fn op_pipe_resources(
+  state: Rc<RefCell<OpState>>,
+  src_rid: ResourceId,
+  dst_rid: ResourceId,
+) -> Result<(), AnyError> {
+  let (src, dst) = {
+    let state = state.borrow();
+    (
+      state.resource_table.get_any(src_rid)?,
+      state.resource_table.get_any(dst_rid)?,
+    )
+  };
+  loop {
+    let chunk = src.clone().read(PIPE_CHUNK_SIZE).await?;
+    if chunk.is_empty() {
+      return Ok(());
+    }
+    dst.clone().write_all(chunk).await?;
+  }
+}
@@ -11,6 +11,7 @@ use deno_core::OpState;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use std::borrow::Cow;
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::time::Duration;
@@ -23,6 +24,13 @@ pub trait TimersPermission {
 
 pub type StartTime = Instant;
 
+/// When put in the `OpState`, the wakeups of repeating timers whose period is
+/// at least this long are rounded up to a multiple of it since the
+/// [`StartTime`], so that intervals that are due within the same window share
+/// a single wakeup of the event loop.
+#[derive(Clone, Copy, Debug)]
+pub struct TimerCoalescing(pub Duration);
+
 // Returns a milliseconds and nanoseconds subsec
 // since the start time of the deno runtime.
 // If the High precision flag is not set, the
@@ -54,7 +62,13 @@ where
   buf[1] = subsec_nanos;
 }
 
-pub struct TimerHandle(Rc<CancelHandle>);
+pub struct TimerHandle {
+  cancel: Rc<CancelHandle>,
+  /// The deadline of the last period of a repeating timer. The next period is
+  /// measured from it rather than from when the callback ran, so that the
+  /// timer doesn't drift.
+  deadline: Cell<Option<Instant>>,
+}
 
 impl Resource for TimerHandle {
   fn name(&self) -> Cow<str> {
@@ -62,17 +76,18 @@ impl Resource for TimerHandle {
   }
 
   fn close(self: Rc<Self>) {
-    self.0.cancel();
+    self.cancel.cancel();
   }
 }
 
 /// Creates a [`TimerHandle`] resource that can be used to cancel invocations of
-/// [`op_sleep`].
+/// [`op_sleep`] and [`op_sleep_interval`].
 #[op]
 pub fn op_timer_handle(state: &mut OpState) -> ResourceId {
-  state
-    .resource_table
-    .add(TimerHandle(CancelHandle::new_rc()))
+  state.resource_table.add(TimerHandle {
+    cancel: CancelHandle::new_rc(),
+    deadline: Cell::new(None),
+  })
 }
 
 /// Waits asynchronously until either `millis` milliseconds have passed or the
@@ -101,9 +116,132 @@ pub async fn op_sleep(
   };
 
   let res = tokio::time::sleep(Duration::from_millis(millis))
-    .or_cancel(handle.0.clone())
+    .or_cancel(handle.cancel.clone())
     .await;
 
   // We release the high-res timer lock here, either by being cancelled or resolving.
   Ok(res.is_ok())
 }
+
+/// Waits asynchronously until the next period of the repeating timer given by
+/// `rid` is due, or until it has been canceled.
+///
+/// Unlike [`op_sleep`], the period is counted from the previous deadline of the
+/// timer, and periods that were missed because the event loop was busy are
+/// skipped rather than fired back to back. If a [`TimerCoalescing`] window is
+/// set, the wakeup is delayed to the end of the window it falls in.
+///
+/// If the timer is canceled, this returns `false`. Otherwise, it returns `true`.
+#[op(deferred)]
+pub async fn op_sleep_interval(
+  state: Rc<RefCell<OpState>>,
+  millis: u64,
+  rid: ResourceId,
+) -> Result<bool, AnyError> {
+  let (handle, start_time, coalescing) = {
+    let state = state.borrow();
+    let handle = state.resource_table.get::<TimerHandle>(rid)?;
+    let start_time = *state.borrow::<StartTime>();
+    let coalescing = state.try_borrow::<TimerCoalescing>().map(|c| c.0);
+    (handle, start_time, coalescing)
+  };
+
+  let period = Duration::from_millis(millis);
+  let deadline =
+    next_interval_deadline(handle.deadline.get(), period, Instant::now());
+  handle.deadline.set(Some(deadline));
+  let wakeup = match coalescing {
+    Some(window) if !window.is_zero() && period >= window => {
+      coalesce_deadline(deadline, start_time, window)
+    }
+    _ => deadline,
+  };
+
+  // See `op_sleep` for why short timers take the high-res timer lock.
+  let _hr_timer_lock = if millis <= 100 {
+    Some(hr_timer_lock())
+  } else {
+    None
+  };
+
+  let res = tokio::time::sleep_until(wakeup.into())
+    .or_cancel(handle.cancel.clone())
+    .await;
+
+  Ok(res.is_ok())
+}
+
+/// Returns the deadline of the period of a repeating timer that follows the
+/// one ending at `previous`. If that deadline already passed by `now`, the
+/// missed periods are skipped and the timer starts over from `now`.
+fn next_interval_deadline(
+  previous: Option<Instant>,
+  period: Duration,
+  now: Instant,
+) -> Instant {
+  match previous {
+    Some(previous) if previous + period > now => previous + period,
+    _ => now + period,
+  }
+}
+
+/// Rounds `deadline` up to the next multiple of `window` since `start_time`.
+fn coalesce_deadline(
+  deadline: Instant,
+  start_time: Instant,
+  window: Duration,
+) -> Instant {
+  let since_start = deadline.saturating_duration_since(start_time).as_nanos();
+  let window = window.as_nanos();
+  let rounded = (since_start + window - 1) / window * window;
+  start_time + Duration::from_nanos(rounded.min(u64::MAX as u128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn first_interval_deadline_is_one_period_away() {
+    let now = Instant::now();
+    let period = Duration::from_millis(10);
+    assert_eq!(next_interval_deadline(None, period, now), now + period);
+  }
+
+  #[test]
+  fn interval_deadline_does_not_drift() {
+    let start = Instant::now();
+    let period = Duration::from_millis(10);
+    // the callback of the previous period ran late, but within the period
+    let now = start + Duration::from_millis(13);
+    assert_eq!(
+      next_interval_deadline(Some(start + period), period, now),
+      start + Duration::from_millis(20)
+    );
+  }
+
+  #[test]
+  fn interval_deadline_skips_missed_periods() {
+    let start = Instant::now();
+    let period = Duration::from_millis(10);
+    let now = start + Duration::from_millis(45);
+    assert_eq!(
+      next_interval_deadline(Some(start + period), period, now),
+      now + period
+    );
+  }
+
+  #[test]
+  fn coalesced_deadline_rounds_up_to_window() {
+    let start = Instant::now();
+    let window = Duration::from_millis(16);
+    assert_eq!(
+      coalesce_deadline(start + Duration::from_millis(17), start, window),
+      start + Duration::from_millis(32)
+    );
+    assert_eq!(
+      coalesce_deadline(start + Duration::from_millis(32), start, window),
+      start + Duration::from_millis(32)
+    );
+  }
+}
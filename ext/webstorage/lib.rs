@@ -2,7 +2,6 @@
 
 // NOTE to all: use **cached** prepared statements when interfacing with SQLite.
 
-use std::fmt;
 use std::path::PathBuf;
 
 use deno_core::error::AnyError;
@@ -12,6 +11,7 @@ use rusqlite::params;
 use rusqlite::Connection;
 use rusqlite::OptionalExtension;
 
+pub use deno_web::DomExceptionNotSupportedError;
 pub use rusqlite;
 
 #[derive(Clone)]
@@ -230,27 +230,7 @@ pub fn op_webstorage_iterate_keys(
   Ok(keys)
 }
 
-#[derive(Debug)]
-pub struct DomExceptionNotSupportedError {
-  pub msg: String,
-}
-
-impl DomExceptionNotSupportedError {
-  pub fn new(msg: &str) -> Self {
-    DomExceptionNotSupportedError {
-      msg: msg.to_string(),
-    }
-  }
-}
-
-impl fmt::Display for DomExceptionNotSupportedError {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    f.pad(&self.msg)
-  }
-}
-
-impl std::error::Error for DomExceptionNotSupportedError {}
-
+#[deprecated(note = "use deno_web::get_error_class_name")]
 pub fn get_not_supported_error_class_name(
   e: &AnyError,
 ) -> Option<&'static str> {
@@ -122,6 +122,13 @@ mod startup_snapshot {
   }
 
   impl deno_node::NodePermissions for Permissions {
+    fn check_net(
+      &mut self,
+      _host: (&str, Option<u16>),
+      _api_name: &str,
+    ) -> Result<(), deno_core::error::AnyError> {
+      unreachable!("snapshotting!")
+    }
     fn check_net_url(
       &mut self,
       _url: &deno_core::url::Url,
@@ -132,6 +139,33 @@ mod startup_snapshot {
     fn check_read(&self, _p: &Path) -> Result<(), deno_core::error::AnyError> {
       unreachable!("snapshotting!")
     }
+    fn check_write(
+      &mut self,
+      _p: &Path,
+      _api_name: &str,
+    ) -> Result<(), deno_core::error::AnyError> {
+      unreachable!("snapshotting!")
+    }
+    fn check_env(
+      &mut self,
+      _var: &str,
+    ) -> Result<(), deno_core::error::AnyError> {
+      unreachable!("snapshotting!")
+    }
+    fn check_run(
+      &mut self,
+      _cmd: &str,
+      _api_name: &str,
+    ) -> Result<(), deno_core::error::AnyError> {
+      unreachable!("snapshotting!")
+    }
+    fn check_sys(
+      &mut self,
+      _kind: &str,
+      _api_name: &str,
+    ) -> Result<(), deno_core::error::AnyError> {
+      unreachable!("snapshotting!")
+    }
   }
 
   impl deno_net::NetPermissions for Permissions {
@@ -300,6 +334,7 @@ mod startup_snapshot {
       deno_web::deno_web::init_ops_and_esm::<Permissions>(
         deno_web::BlobStore::default(),
         Default::default(),
+        false, // No --unstable.
       ),
       deno_fetch::deno_fetch::init_ops_and_esm::<Permissions>(
         Default::default(),
@@ -313,7 +348,7 @@ mod startup_snapshot {
       deno_webstorage::deno_webstorage::init_ops_and_esm(None),
       deno_crypto::deno_crypto::init_ops_and_esm(None),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
-        deno_broadcast_channel::InMemoryBroadcastChannel::default(),
+        deno_broadcast_channel::RuntimeBroadcastChannel::default(),
         false, // No --unstable.
       ),
       deno_ffi::deno_ffi::init_ops_and_esm::<Permissions>(false),
@@ -333,7 +368,11 @@ mod startup_snapshot {
       runtime::init_ops_and_esm(),
       // FIXME(bartlomieju): these extensions are specified last, because they
       // depend on `runtime`, even though it should be other way around
-      deno_node::deno_node::init_ops_and_esm::<Permissions>(None, fs),
+      deno_node::deno_node::init_ops_and_esm::<Permissions>(
+        None,
+        fs,
+        Vec::new(),
+      ),
       runtime_main::init_ops_and_esm(),
     ];
 
@@ -29,6 +29,8 @@ use std::net::SocketAddr;
 use std::pin::pin;
 use std::process;
 use std::rc::Rc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::thread;
 use uuid::Uuid;
 
@@ -39,6 +41,7 @@ pub struct InspectorServer {
   register_inspector_tx: UnboundedSender<InspectorInfo>,
   shutdown_server_tx: Option<oneshot::Sender<()>>,
   thread_handle: Option<thread::JoinHandle<()>>,
+  print_frontend_url: AtomicBool,
 }
 
 impl InspectorServer {
@@ -62,9 +65,17 @@ impl InspectorServer {
       register_inspector_tx,
       shutdown_server_tx: Some(shutdown_server_tx),
       thread_handle: Some(thread_handle),
+      print_frontend_url: AtomicBool::new(false),
     }
   }
 
+  /// Also print the URL that opens DevTools connected to the inspectors
+  /// registered from now on, so it can be opened without going through
+  /// chrome://inspect.
+  pub fn set_print_frontend_url(&self, print: bool) {
+    self.print_frontend_url.store(print, Ordering::Relaxed);
+  }
+
   pub fn register_inspector(
     &self,
     module_url: String,
@@ -75,13 +86,14 @@ impl InspectorServer {
     let mut inspector = inspector_rc.borrow_mut();
     let session_sender = inspector.get_session_sender();
     let deregister_rx = inspector.add_deregister_handler();
-    let info = InspectorInfo::new(
+    let mut info = InspectorInfo::new(
       self.host,
       session_sender,
       deregister_rx,
       module_url,
       wait_for_session,
     );
+    info.print_frontend_url = self.print_frontend_url.load(Ordering::Relaxed);
     self.register_inspector_tx.unbounded_send(info).unwrap();
   }
 }
@@ -227,6 +239,9 @@ async fn server(
         info.get_websocket_debugger_url()
       );
       eprintln!("Visit chrome://inspect to connect to the debugger.");
+      if info.print_frontend_url {
+        eprintln!("Or open {} in Chrome.", info.get_frontend_url());
+      }
       if info.wait_for_session {
         eprintln!("Deno is waiting for debugger to connect.");
       }
@@ -360,6 +375,7 @@ pub struct InspectorInfo {
   pub deregister_rx: oneshot::Receiver<()>,
   pub url: String,
   pub wait_for_session: bool,
+  pub print_frontend_url: bool,
 }
 
 impl InspectorInfo {
@@ -378,6 +394,7 @@ impl InspectorInfo {
       deregister_rx,
       url,
       wait_for_session,
+      print_frontend_url: false,
     }
   }
 
@@ -7,10 +7,14 @@ const {
   ArrayPrototypeFilter,
   Error,
   ObjectPrototypeIsPrototypeOf,
+  queueMicrotask,
+  SafeFinalizationRegistry,
   String,
   StringPrototypeStartsWith,
+  Symbol,
   SymbolIterator,
   SymbolToStringTag,
+  TypeError,
 } = primordials;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import { URL } from "ext:deno_url/00_url.js";
@@ -63,6 +67,24 @@ function hostRecvMessage(id) {
   return core.opAsync("op_host_recv_message", id);
 }
 
+// Passed in the options of the `Worker` constructor by `WorkerPool` with the
+// id of an already running worker.
+const _pooledWorkerId = Symbol("[[pooledWorkerId]]");
+
+function resolveWorkerSpecifier(specifier, workerType) {
+  if (
+    StringPrototypeStartsWith(specifier, "./") ||
+    StringPrototypeStartsWith(specifier, "../") ||
+    StringPrototypeStartsWith(specifier, "/") || workerType === "classic"
+  ) {
+    const baseUrl = getLocationHref();
+    if (baseUrl != null) {
+      return new URL(specifier, baseUrl).href;
+    }
+  }
+  return specifier;
+}
+
 class Worker extends EventTarget {
   #id = 0;
   #name = "";
@@ -84,17 +106,7 @@ class Worker extends EventTarget {
     } = options;
 
     const workerType = webidl.converters["WorkerType"](type);
-
-    if (
-      StringPrototypeStartsWith(specifier, "./") ||
-      StringPrototypeStartsWith(specifier, "../") ||
-      StringPrototypeStartsWith(specifier, "/") || workerType === "classic"
-    ) {
-      const baseUrl = getLocationHref();
-      if (baseUrl != null) {
-        specifier = new URL(specifier, baseUrl).href;
-      }
-    }
+    specifier = resolveWorkerSpecifier(specifier, workerType);
 
     this.#name = name;
     let hasSourceCode, sourceCode;
@@ -106,7 +118,7 @@ class Worker extends EventTarget {
       sourceCode = "";
     }
 
-    const id = createWorker(
+    const id = options[_pooledWorkerId] ?? createWorker(
       specifier,
       hasSourceCode,
       sourceCode,
@@ -242,6 +254,68 @@ class Worker extends EventTarget {
   [SymbolToStringTag] = "Worker";
 }
 
+// The idle workers of a pool that is garbage collected without being closed
+// are terminated, as nothing could hand them out anymore.
+const workerPoolRegistry = new SafeFinalizationRegistry((id) => {
+  ops.op_worker_pool_close(id);
+});
+
+class WorkerPool {
+  #id = 0;
+  #specifier = "";
+  #name = "";
+  #closed = false;
+
+  constructor(specifier, options = {}) {
+    specifier = resolveWorkerSpecifier(String(specifier), "module");
+    const {
+      deno,
+      name,
+      size = 1,
+    } = options;
+
+    this.#specifier = specifier;
+    this.#name = name;
+    this.#id = ops.op_create_worker_pool({
+      name,
+      permissions: serializePermissions(deno?.permissions),
+      specifier,
+      size: webidl.converters["unsigned long"](
+        size,
+        "Failed to construct 'WorkerPool'",
+        "size",
+        { enforceRange: true },
+      ),
+    });
+    workerPoolRegistry.register(this, this.#id, this);
+  }
+
+  acquire() {
+    if (this.#closed) {
+      throw new TypeError("Worker pool is closed.");
+    }
+    const id = ops.op_worker_pool_acquire(this.#id);
+    // Replace the worker once the caller had the chance to post its first
+    // message, so spawning doesn't delay it.
+    queueMicrotask(() => ops.op_worker_pool_refill(this.#id));
+    return new Worker(this.#specifier, {
+      name: this.#name,
+      type: "module",
+      [_pooledWorkerId]: id,
+    });
+  }
+
+  close() {
+    if (!this.#closed) {
+      this.#closed = true;
+      workerPoolRegistry.unregister(this);
+      ops.op_worker_pool_close(this.#id);
+    }
+  }
+
+  [SymbolToStringTag] = "WorkerPool";
+}
+
 defineEventHandler(Worker.prototype, "error");
 defineEventHandler(Worker.prototype, "message");
 defineEventHandler(Worker.prototype, "messageerror");
@@ -251,4 +325,4 @@ webidl.converters["WorkerType"] = webidl.createEnumConverter("WorkerType", [
   "module",
 ]);
 
-export { Worker };
+export { Worker, WorkerPool };
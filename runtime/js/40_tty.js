@@ -3,6 +3,7 @@ const core = globalThis.Deno.core;
 const ops = core.ops;
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  SymbolFor,
   Uint32Array,
   Uint8Array,
 } = primordials;
@@ -20,4 +21,18 @@ function isatty(rid) {
   return !!isattyBuffer[0];
 }
 
-export { consoleSize, isatty };
+function bindConsoleResize() {
+  return ops.op_console_resize_bind();
+}
+
+/**
+ * Resolves when the console is resized, or with `true` once the resource is
+ * closed. Like signal listeners, this doesn't keep the event loop alive.
+ */
+function pollConsoleResize(rid) {
+  const promise = core.opAsync("op_console_resize_poll", rid);
+  core.unrefOp(promise[SymbolFor("Deno.core.internalPromiseId")]);
+  return promise;
+}
+
+export { bindConsoleResize, consoleSize, isatty, pollConsoleResize };
@@ -23,6 +23,8 @@ import * as tty from "ext:runtime/40_tty.js";
 // TODO(bartlomieju): this is funky we have two `http` imports
 import * as httpRuntime from "ext:runtime/40_http.js";
 import * as kv from "ext:deno_kv/01_db.ts";
+import * as workers from "ext:runtime/11_workers.js";
+import * as broadcastChannel from "ext:deno_broadcast_channel/01_broadcast_channel.js";
 
 const denoNs = {
   metrics: core.metrics,
@@ -176,6 +178,8 @@ const denoNsUnstable = {
   Kv: kv.Kv,
   KvU64: kv.KvU64,
   KvListIterator: kv.KvListIterator,
+  WorkerPool: workers.WorkerPool,
+  broadcastChannelStats: broadcastChannel.broadcastChannelStats,
 };
 
 export { denoNs, denoNsUnstable };
@@ -69,6 +69,13 @@ const windowOrWorkerGlobalScope = {
   PerformanceEntry: util.nonEnumerable(performance.PerformanceEntry),
   PerformanceMark: util.nonEnumerable(performance.PerformanceMark),
   PerformanceMeasure: util.nonEnumerable(performance.PerformanceMeasure),
+  PerformanceObserver: util.nonEnumerable(performance.PerformanceObserver),
+  PerformanceObserverEntryList: util.nonEnumerable(
+    performance.PerformanceObserverEntryList,
+  ),
+  PerformanceResourceTiming: util.nonEnumerable(
+    performance.PerformanceResourceTiming,
+  ),
   PromiseRejectionEvent: util.nonEnumerable(event.PromiseRejectionEvent),
   ProgressEvent: util.nonEnumerable(event.ProgressEvent),
   ReadableStream: util.nonEnumerable(streams.ReadableStream),
@@ -77,6 +84,10 @@ const windowOrWorkerGlobalScope = {
   ),
   Request: util.nonEnumerable(request.Request),
   Response: util.nonEnumerable(response.Response),
+  Scheduler: util.nonEnumerable(timers.Scheduler),
+  TaskController: util.nonEnumerable(abortSignal.TaskController),
+  TaskPriorityChangeEvent: util.nonEnumerable(event.TaskPriorityChangeEvent),
+  TaskSignal: util.nonEnumerable(abortSignal.TaskSignal),
   TextDecoder: util.nonEnumerable(encoding.TextDecoder),
   TextEncoder: util.nonEnumerable(encoding.TextEncoder),
   TextDecoderStream: util.nonEnumerable(encoding.TextDecoderStream),
@@ -130,7 +141,9 @@ const windowOrWorkerGlobalScope = {
   SubtleCrypto: util.nonEnumerable(crypto.SubtleCrypto),
   fetch: util.writable(fetch.fetch),
   performance: util.writable(performance.performance),
+  queueMicrotask: util.writable(timers.queueMicrotask),
   reportError: util.writable(event.reportError),
+  scheduler: util.writable(timers.scheduler),
   setInterval: util.writable(timers.setInterval),
   setTimeout: util.writable(timers.setTimeout),
   structuredClone: util.writable(messagePort.structuredClone),
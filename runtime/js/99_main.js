@@ -35,6 +35,8 @@ const {
   Symbol,
   SymbolIterator,
   TypeError,
+  Uint8Array,
+  Uint8ArrayPrototype,
   WeakMapPrototypeDelete,
   WeakMapPrototypeGet,
   WeakMapPrototypeSet,
@@ -45,6 +47,7 @@ import * as location from "ext:deno_web/12_location.js";
 import * as version from "ext:runtime/01_version.ts";
 import * as os from "ext:runtime/30_os.js";
 import * as timers from "ext:deno_web/02_timers.js";
+import * as base64 from "ext:deno_web/05_base64.js";
 import {
   getDefaultInspectOptions,
   getNoColor,
@@ -251,48 +254,6 @@ core.registerErrorClass("BadResource", errors.BadResource);
 core.registerErrorClass("Http", errors.Http);
 core.registerErrorClass("Busy", errors.Busy);
 core.registerErrorClass("NotSupported", errors.NotSupported);
-core.registerErrorBuilder(
-  "DOMExceptionOperationError",
-  function DOMExceptionOperationError(msg) {
-    return new DOMException(msg, "OperationError");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionQuotaExceededError",
-  function DOMExceptionQuotaExceededError(msg) {
-    return new DOMException(msg, "QuotaExceededError");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionNotSupportedError",
-  function DOMExceptionNotSupportedError(msg) {
-    return new DOMException(msg, "NotSupported");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionNetworkError",
-  function DOMExceptionNetworkError(msg) {
-    return new DOMException(msg, "NetworkError");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionAbortError",
-  function DOMExceptionAbortError(msg) {
-    return new DOMException(msg, "AbortError");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionInvalidCharacterError",
-  function DOMExceptionInvalidCharacterError(msg) {
-    return new DOMException(msg, "InvalidCharacterError");
-  },
-);
-core.registerErrorBuilder(
-  "DOMExceptionDataError",
-  function DOMExceptionDataError(msg) {
-    return new DOMException(msg, "DataError");
-  },
-);
 
 function runtimeStart(
   denoVersion,
@@ -405,6 +366,14 @@ function promiseRejectMacrotaskCallback() {
 let hasBootstrapped = false;
 // Set up global properties shared by main and worker runtime.
 ObjectDefineProperties(globalThis, windowOrWorkerGlobalScope);
+// The base64 methods of `Uint8Array`, which V8 doesn't implement yet.
+ObjectDefineProperties(Uint8Array, {
+  fromBase64: util.nonEnumerable(base64.fromBase64),
+});
+ObjectDefineProperties(Uint8ArrayPrototype, {
+  setFromBase64: util.nonEnumerable(base64.setFromBase64),
+  toBase64: util.nonEnumerable(base64.toBase64),
+});
 // FIXME(bartlomieju): temporarily add whole `Deno.core` to
 // `Deno[Deno.internal]` namespace. It should be removed and only necessary
 // methods should be left there.
@@ -2,6 +2,7 @@
 
 use super::utils::into_string;
 use crate::permissions::PermissionsContainer;
+use crate::resource_limits::resource_limits;
 use crate::worker::ExitCode;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
@@ -19,6 +20,7 @@ mod sys_info;
 deno_core::ops!(
   deno_ops,
   [
+    op_cpus,
     op_env,
     op_exec_path,
     op_exit,
@@ -163,6 +165,17 @@ fn op_loadavg(state: &mut OpState) -> Result<(f64, f64, f64), AnyError> {
   Ok(sys_info::loadavg())
 }
 
+#[op]
+fn op_cpus(state: &mut OpState) -> Result<Vec<sys_info::CpuInfo>, AnyError> {
+  state
+    .borrow_mut::<PermissionsContainer>()
+    .check_sys("cpus", "node:os.cpus()")?;
+  // only report the CPUs that a cgroup lets the process use
+  let mut cpus = sys_info::cpu_info()?;
+  cpus.truncate(resource_limits().cpu_count);
+  Ok(cpus)
+}
+
 #[op]
 fn op_hostname(state: &mut OpState) -> Result<String, AnyError> {
   state
@@ -186,7 +199,15 @@ fn op_network_interfaces(
   state
     .borrow_mut::<PermissionsContainer>()
     .check_sys("networkInterfaces", "Deno.networkInterfaces()")?;
-  Ok(netif::up()?.map(NetworkInterface::from).collect())
+  let details = sys_info::interface_details();
+  let mut interfaces = netif::up()?
+    .map(|ifa| NetworkInterface::new(ifa, &details))
+    .collect::<Vec<_>>();
+  // the order in which the OS lists the addresses isn't stable
+  interfaces.sort_by(|a, b| {
+    (&a.name, a.family, &a.address).cmp(&(&b.name, b.family, &b.address))
+  });
+  Ok(interfaces)
 }
 
 #[derive(serde::Serialize)]
@@ -198,10 +219,15 @@ struct NetworkInterface {
   scopeid: Option<u32>,
   cidr: String,
   mac: String,
+  flags: sys_info::InterfaceFlags,
+  mtu: Option<u32>,
 }
 
-impl From<netif::Interface> for NetworkInterface {
-  fn from(ifa: netif::Interface) -> Self {
+impl NetworkInterface {
+  fn new(
+    ifa: netif::Interface,
+    details: &HashMap<String, sys_info::InterfaceDetails>,
+  ) -> Self {
     let family = match ifa.address() {
       std::net::IpAddr::V4(_) => "IPv4",
       std::net::IpAddr::V6(_) => "IPv6",
@@ -218,6 +244,18 @@ impl From<netif::Interface> for NetworkInterface {
     let [b0, b1, b2, b3, b4, b5] = ifa.mac();
     let mac = format!("{b0:02x}:{b1:02x}:{b2:02x}:{b3:02x}:{b4:02x}:{b5:02x}");
 
+    // netif only lists the interfaces that are up
+    let details = details.get(&name).copied().unwrap_or_else(|| {
+      sys_info::InterfaceDetails {
+        flags: sys_info::InterfaceFlags {
+          up: true,
+          loopback: ifa.address().is_loopback(),
+          multicast: false,
+        },
+        mtu: None,
+      }
+    });
+
     Self {
       family,
       name,
@@ -226,6 +264,8 @@ impl From<netif::Interface> for NetworkInterface {
       scopeid,
       cidr,
       mac,
+      flags: details.flags,
+      mtu: details.mtu,
     }
   }
 }
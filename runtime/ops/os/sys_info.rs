@@ -392,3 +392,449 @@ pub fn os_uptime() -> u64 {
 
   uptime
 }
+
+#[derive(serde::Serialize)]
+pub struct CpuTimes {
+  pub user: u64,
+  pub nice: u64,
+  pub sys: u64,
+  pub idle: u64,
+  pub irq: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct CpuInfo {
+  pub model: String,
+  /// The clock speed in MHz.
+  pub speed: u64,
+  /// Time spent in each mode since boot, in milliseconds.
+  pub times: CpuTimes,
+}
+
+pub fn cpu_info() -> std::io::Result<Vec<CpuInfo>> {
+  #[cfg(target_os = "linux")]
+  {
+    // SAFETY: `sysconf` returns a system constant.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks <= 0 {
+      return Err(std::io::Error::last_os_error());
+    }
+    let multiplier = 1000 / ticks as u64;
+
+    #[allow(clippy::disallowed_methods)]
+    let stat = std::fs::read_to_string("/proc/stat")?;
+    #[allow(clippy::disallowed_methods)]
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+
+    // "model name" is missing on some architectures (e.g. ARM)
+    let models = cpuinfo
+      .lines()
+      .filter(|line| line.starts_with("model name"))
+      .filter_map(|line| line.split_once(':'))
+      .map(|(_, model)| model.trim().to_string())
+      .collect::<Vec<_>>();
+    let speeds = cpuinfo
+      .lines()
+      .filter(|line| line.starts_with("cpu MHz"))
+      .filter_map(|line| line.split_once(':'))
+      .map(|(_, speed)| speed.trim().parse::<f64>().unwrap_or(0.0) as u64)
+      .collect::<Vec<_>>();
+
+    let mut cpus = Vec::new();
+    for line in stat.lines() {
+      // per-core lines look like "cpu0 user nice system idle iowait irq ..."
+      let mut fields = line.split_whitespace();
+      let Some(Ok(index)) = fields
+        .next()
+        .and_then(|name| name.strip_prefix("cpu"))
+        .map(|id| id.parse::<usize>())
+      else {
+        continue;
+      };
+      let values = fields
+        .map(|value| value.parse::<u64>().unwrap_or(0) * multiplier)
+        .collect::<Vec<_>>();
+      if values.len() < 6 {
+        continue;
+      }
+
+      #[allow(clippy::disallowed_methods)]
+      let speed = std::fs::read_to_string(format!(
+        "/sys/devices/system/cpu/cpu{index}/cpufreq/scaling_cur_freq"
+      ))
+      .ok()
+      .and_then(|khz| khz.trim().parse::<u64>().ok())
+      .map(|khz| khz / 1000)
+      .or_else(|| speeds.get(index).copied())
+      .unwrap_or(0);
+
+      cpus.push(CpuInfo {
+        model: models
+          .get(index)
+          .cloned()
+          .unwrap_or_else(|| "unknown".to_string()),
+        speed,
+        times: CpuTimes {
+          user: values[0],
+          nice: values[1],
+          sys: values[2],
+          idle: values[3],
+          irq: values[5],
+        },
+      });
+    }
+    Ok(cpus)
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    fn sysctl_by_name<T>(name: &str, mut value: T) -> Option<T> {
+      let name = std::ffi::CString::new(name).ok()?;
+      let mut size = std::mem::size_of::<T>();
+      // SAFETY: `value` is a valid pointer to `size` bytes.
+      let res = unsafe {
+        libc::sysctlbyname(
+          name.as_ptr(),
+          &mut value as *mut T as *mut libc::c_void,
+          &mut size,
+          std::ptr::null_mut(),
+          0,
+        )
+      };
+      (res == 0).then_some(value)
+    }
+
+    // SAFETY: `sysconf` returns a system constant.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks <= 0 {
+      return Err(std::io::Error::last_os_error());
+    }
+    let multiplier = 1000 / ticks as u64;
+
+    let model = match sysctl_by_name("machdep.cpu.brand_string", [0u8; 256]) {
+      Some(buf) => {
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        String::from_utf8_lossy(&buf[..len]).to_string()
+      }
+      None => String::from("unknown"),
+    };
+    // not available on Apple silicon
+    let speed =
+      sysctl_by_name("hw.cpufrequency", 0u64).unwrap_or(0) / 1_000_000;
+
+    let mut num_cpus: libc::natural_t = 0;
+    let mut info: libc::processor_info_array_t = std::ptr::null_mut();
+    let mut info_count: libc::mach_msg_type_number_t = 0;
+    // SAFETY: all out pointers are valid. We assume that `mach_host_self`
+    // always returns a valid value.
+    let res = unsafe {
+      libc::host_processor_info(
+        libc::mach_host_self(),
+        libc::PROCESSOR_CPU_LOAD_INFO,
+        &mut num_cpus,
+        &mut info,
+        &mut info_count,
+      )
+    };
+    if res != libc::KERN_SUCCESS {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        format!("host_processor_info() failed with {res}"),
+      ));
+    }
+
+    let mut cpus = Vec::with_capacity(num_cpus as usize);
+    for i in 0..num_cpus as usize {
+      let offset = i * libc::CPU_STATE_MAX as usize;
+      let ticks = |state: libc::c_int| {
+        // SAFETY: `info` holds CPU_STATE_MAX ticks for each of the
+        // `num_cpus` processors.
+        let ticks = unsafe { *info.add(offset + state as usize) };
+        ticks as u32 as u64 * multiplier
+      };
+      cpus.push(CpuInfo {
+        model: model.clone(),
+        speed,
+        times: CpuTimes {
+          user: ticks(libc::CPU_STATE_USER),
+          nice: ticks(libc::CPU_STATE_NICE),
+          sys: ticks(libc::CPU_STATE_SYSTEM),
+          idle: ticks(libc::CPU_STATE_IDLE),
+          irq: 0,
+        },
+      });
+    }
+
+    // SAFETY: `info` was allocated by the kernel in host_processor_info().
+    unsafe {
+      libc::vm_deallocate(
+        libc::mach_task_self(),
+        info as libc::vm_address_t,
+        info_count as usize * std::mem::size_of::<libc::integer_t>(),
+      );
+    }
+    Ok(cpus)
+  }
+  #[cfg(target_family = "windows")]
+  {
+    use ntapi::ntexapi::NtQuerySystemInformation;
+    use ntapi::ntexapi::SystemProcessorPerformanceInformation;
+    use ntapi::ntexapi::SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION;
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::shared::minwindef::DWORD;
+    use winapi::shared::ntdef::NT_SUCCESS;
+    use winapi::um::winreg::RegGetValueW;
+    use winapi::um::winreg::HKEY_LOCAL_MACHINE;
+    use winapi::um::winreg::RRF_RT_REG_DWORD;
+    use winapi::um::winreg::RRF_RT_REG_SZ;
+
+    fn wide(s: &str) -> Vec<u16> {
+      std::ffi::OsStr::new(s)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+    }
+
+    let num_cpus = std::thread::available_parallelism()
+      .map(|n| n.get())
+      .unwrap_or(1);
+    let mut perf_info =
+      Vec::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>::with_capacity(num_cpus);
+    let mut returned: u32 = 0;
+    // SAFETY: `perf_info` has room for `num_cpus` entries.
+    let status = unsafe {
+      NtQuerySystemInformation(
+        SystemProcessorPerformanceInformation,
+        perf_info.as_mut_ptr() as *mut _,
+        (num_cpus
+          * std::mem::size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>())
+          as u32,
+        &mut returned,
+      )
+    };
+    if !NT_SUCCESS(status) {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        format!("NtQuerySystemInformation() failed with {status:#x}"),
+      ));
+    }
+    let count = returned as usize
+      / std::mem::size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>();
+    // SAFETY: NtQuerySystemInformation() initialized `count` entries.
+    unsafe { perf_info.set_len(count.min(num_cpus)) };
+
+    let mut cpus = Vec::with_capacity(perf_info.len());
+    for (index, info) in perf_info.iter().enumerate() {
+      let key = wide(&format!(
+        "HARDWARE\\DESCRIPTION\\System\\CentralProcessor\\{index}"
+      ));
+
+      let mut speed: DWORD = 0;
+      let mut size = std::mem::size_of::<DWORD>() as DWORD;
+      // SAFETY: `speed` is a valid pointer to a DWORD.
+      unsafe {
+        RegGetValueW(
+          HKEY_LOCAL_MACHINE,
+          key.as_ptr(),
+          wide("~MHz").as_ptr(),
+          RRF_RT_REG_DWORD,
+          std::ptr::null_mut(),
+          &mut speed as *mut DWORD as *mut _,
+          &mut size,
+        )
+      };
+
+      let mut model = [0u16; 256];
+      let mut size = (model.len() * 2) as DWORD;
+      // SAFETY: `model` is a valid pointer to `size` bytes.
+      let res = unsafe {
+        RegGetValueW(
+          HKEY_LOCAL_MACHINE,
+          key.as_ptr(),
+          wide("ProcessorNameString").as_ptr(),
+          RRF_RT_REG_SZ,
+          std::ptr::null_mut(),
+          model.as_mut_ptr() as *mut _,
+          &mut size,
+        )
+      };
+      let model = if res == 0 {
+        let len = model.iter().take_while(|&&c| c != 0).count();
+        String::from_utf16_lossy(&model[..len]).trim().to_string()
+      } else {
+        String::from("unknown")
+      };
+
+      // SAFETY: reading the QuadPart of LARGE_INTEGERs.
+      let (idle, kernel, user, interrupt) = unsafe {
+        (
+          *info.IdleTime.QuadPart() as u64,
+          *info.KernelTime.QuadPart() as u64,
+          *info.UserTime.QuadPart() as u64,
+          *info.InterruptTime.QuadPart() as u64,
+        )
+      };
+      // times are reported in 100ns units, and kernel time includes idle time
+      cpus.push(CpuInfo {
+        model,
+        speed: speed as u64,
+        times: CpuTimes {
+          user: user / 10_000,
+          nice: 0,
+          sys: kernel.saturating_sub(idle) / 10_000,
+          idle: idle / 10_000,
+          irq: interrupt / 10_000,
+        },
+      });
+    }
+    Ok(cpus)
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_vendor = "apple",
+    target_family = "windows"
+  )))]
+  {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::Unsupported,
+      "The CPU information is not available on this platform",
+    ))
+  }
+}
+
+#[derive(Debug, Default, Clone, Copy, serde::Serialize)]
+pub struct InterfaceFlags {
+  pub up: bool,
+  pub loopback: bool,
+  pub multicast: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InterfaceDetails {
+  pub flags: InterfaceFlags,
+  /// The maximum transmission unit, in bytes.
+  pub mtu: Option<u32>,
+}
+
+/// The flags and MTU of the network interfaces, by name.
+pub fn interface_details() -> std::collections::HashMap<String, InterfaceDetails>
+{
+  let mut details = std::collections::HashMap::new();
+  #[cfg(target_family = "unix")]
+  {
+    use std::ffi::CStr;
+
+    let mut ifaddrs = std::ptr::null_mut();
+    // SAFETY: `ifaddrs` is a valid pointer to a pointer.
+    if unsafe { libc::getifaddrs(&mut ifaddrs) } != 0 {
+      return details;
+    }
+    let mut ifa = ifaddrs;
+    while !ifa.is_null() {
+      // SAFETY: `ifa` is an entry of the list that getifaddrs() returned.
+      let (name, flags) = unsafe {
+        (
+          CStr::from_ptr((*ifa).ifa_name)
+            .to_string_lossy()
+            .into_owned(),
+          (*ifa).ifa_flags,
+        )
+      };
+      let entry: &mut InterfaceDetails = details.entry(name).or_default();
+      entry.flags = InterfaceFlags {
+        up: flags & libc::IFF_UP as libc::c_uint != 0,
+        loopback: flags & libc::IFF_LOOPBACK as libc::c_uint != 0,
+        multicast: flags & libc::IFF_MULTICAST as libc::c_uint != 0,
+      };
+      #[cfg(target_vendor = "apple")]
+      // SAFETY: the data of AF_LINK entries is a `libc::if_data` struct.
+      unsafe {
+        let addr = (*ifa).ifa_addr;
+        if !addr.is_null()
+          && (*addr).sa_family as libc::c_int == libc::AF_LINK
+          && !(*ifa).ifa_data.is_null()
+        {
+          let data = (*ifa).ifa_data as *const libc::if_data;
+          entry.mtu = Some((*data).ifi_mtu);
+        }
+      }
+      // SAFETY: `ifa` is an entry of the list that getifaddrs() returned.
+      ifa = unsafe { (*ifa).ifa_next };
+    }
+    // SAFETY: `ifaddrs` was returned by getifaddrs().
+    unsafe { libc::freeifaddrs(ifaddrs) };
+
+    #[cfg(target_os = "linux")]
+    for (name, entry) in details.iter_mut() {
+      entry.mtu = std::fs::read_to_string(format!("/sys/class/net/{name}/mtu"))
+        .ok()
+        .and_then(|mtu| mtu.trim().parse().ok());
+    }
+  }
+  #[cfg(target_family = "windows")]
+  {
+    use winapi::shared::ifdef::IfOperStatusUp;
+    use winapi::shared::ipifcons::IF_TYPE_SOFTWARE_LOOPBACK;
+    use winapi::shared::winerror::ERROR_BUFFER_OVERFLOW;
+    use winapi::shared::winerror::ERROR_SUCCESS;
+    use winapi::shared::ws2def::AF_UNSPEC;
+    use winapi::um::iphlpapi::GetAdaptersAddresses;
+    use winapi::um::iptypes::GAA_FLAG_SKIP_ANYCAST;
+    use winapi::um::iptypes::GAA_FLAG_SKIP_DNS_SERVER;
+    use winapi::um::iptypes::GAA_FLAG_SKIP_MULTICAST;
+    use winapi::um::iptypes::IP_ADAPTER_ADDRESSES;
+    use winapi::um::iptypes::IP_ADAPTER_NO_MULTICAST;
+
+    // the recommended initial size, which avoids calling it twice
+    let mut size: u32 = 15 * 1024;
+    let mut buffer: Vec<u64>;
+    loop {
+      buffer = vec![0; size as usize / 8 + 1];
+      // SAFETY: `buffer` has room for `size` bytes, and is aligned for
+      // IP_ADAPTER_ADDRESSES.
+      let res = unsafe {
+        GetAdaptersAddresses(
+          AF_UNSPEC as u32,
+          GAA_FLAG_SKIP_ANYCAST
+            | GAA_FLAG_SKIP_MULTICAST
+            | GAA_FLAG_SKIP_DNS_SERVER,
+          std::ptr::null_mut(),
+          buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES,
+          &mut size,
+        )
+      };
+      match res {
+        ERROR_SUCCESS => break,
+        ERROR_BUFFER_OVERFLOW => continue,
+        _ => return details,
+      }
+    }
+
+    let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES;
+    while !adapter.is_null() {
+      // SAFETY: `adapter` is an entry of the list that
+      // GetAdaptersAddresses() wrote to `buffer`.
+      let (name, entry) = unsafe {
+        let adapter = &*adapter;
+        let friendly_name = adapter.FriendlyName;
+        let len = (0..).take_while(|&i| *friendly_name.add(i) != 0).count();
+        let name = String::from_utf16_lossy(std::slice::from_raw_parts(
+          friendly_name,
+          len,
+        ));
+        let entry = InterfaceDetails {
+          flags: InterfaceFlags {
+            up: adapter.OperStatus == IfOperStatusUp,
+            loopback: adapter.IfType == IF_TYPE_SOFTWARE_LOOPBACK,
+            multicast: adapter.Flags & IP_ADAPTER_NO_MULTICAST == 0,
+          },
+          mtu: Some(adapter.Mtu),
+        };
+        (name, entry)
+      };
+      details.insert(name, entry);
+      // SAFETY: see above.
+      adapter = unsafe { (*adapter).Next };
+    }
+  }
+  details
+}
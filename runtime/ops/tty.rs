@@ -1,23 +1,35 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::io::Error;
 use std::rc::Rc;
 
 use deno_core::error::resource_unavailable;
 use deno_core::error::AnyError;
 use deno_core::op;
+#[cfg(unix)]
+use deno_core::AsyncRefCell;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
 use deno_core::OpState;
+use deno_core::RcRef;
 use deno_core::Resource;
+use deno_core::ResourceId;
 use deno_io::fs::FileResource;
 
-#[cfg(unix)]
-use deno_core::ResourceId;
 #[cfg(unix)]
 use nix::sys::termios;
-#[cfg(unix)]
-use std::cell::RefCell;
+#[cfg(windows)]
+use std::cell::Cell;
 #[cfg(unix)]
 use std::collections::HashMap;
+#[cfg(unix)]
+use tokio::signal::unix::signal;
+#[cfg(unix)]
+use tokio::signal::unix::Signal;
+#[cfg(unix)]
+use tokio::signal::unix::SignalKind;
 
 #[cfg(unix)]
 #[derive(Default, Clone)]
@@ -74,7 +86,13 @@ fn get_fd_from_resource(
 
 deno_core::extension!(
   deno_tty,
-  ops = [op_stdin_set_raw, op_isatty, op_console_size],
+  ops = [
+    op_set_raw,
+    op_isatty,
+    op_console_size,
+    op_console_resize_bind,
+    op_console_resize_poll,
+  ],
   state = |state| {
     #[cfg(unix)]
     state.put(TtyModeStore::default());
@@ -102,13 +120,12 @@ fn mode_raw_input_off(original_mode: DWORD) -> DWORD {
 }
 
 #[op(fast)]
-fn op_stdin_set_raw(
+fn op_set_raw(
   state: &mut OpState,
+  rid: u32,
   is_raw: bool,
   cbreak: bool,
 ) -> Result<(), AnyError> {
-  let rid = 0; // stdin is always rid=0
-
   // From https://github.com/kkawakam/rustyline/blob/master/src/tty/windows.rs
   // and https://github.com/kkawakam/rustyline/blob/master/src/tty/unix.rs
   // and https://github.com/crossterm-rs/crossterm/blob/e35d4d2c1cc4c919e36d242e014af75f6127ab50/src/terminal/sys/windows.rs
@@ -236,25 +253,21 @@ fn op_console_size(
   state: &mut OpState,
   result: &mut [u32],
 ) -> Result<(), AnyError> {
-  fn check_console_size(
-    state: &mut OpState,
-    result: &mut [u32],
-    rid: u32,
-  ) -> Result<(), AnyError> {
-    FileResource::with_resource(state, rid, move |resource| {
-      let fd = get_fd_from_resource(resource)?;
-      let size = console_size_from_fd(fd)?;
-      result[0] = size.cols;
-      result[1] = size.rows;
-      Ok(())
-    })
-  }
+  let size = stdio_console_size(state)?;
+  result[0] = size.cols;
+  result[1] = size.rows;
+  Ok(())
+}
 
-  let mut last_result = Ok(());
+fn stdio_console_size(state: &mut OpState) -> Result<ConsoleSize, AnyError> {
+  let mut last_result = Err(resource_unavailable());
   // Since stdio might be piped we try to get the size of the console for all
   // of them and return the first one that succeeds.
   for rid in [0, 1, 2] {
-    last_result = check_console_size(state, result, rid);
+    last_result = FileResource::with_resource(state, rid, move |resource| {
+      let fd = get_fd_from_resource(resource)?;
+      Ok(console_size_from_fd(fd)?)
+    });
     if last_result.is_ok() {
       return last_result;
     }
@@ -263,6 +276,77 @@ fn op_console_size(
   last_result
 }
 
+/// The resource for the stream of console size changes.
+struct ConsoleResizeResource {
+  #[cfg(unix)]
+  signal: AsyncRefCell<Signal>,
+  #[cfg(windows)]
+  last_size: Cell<Option<ConsoleSize>>,
+  cancel: CancelHandle,
+}
+
+impl Resource for ConsoleResizeResource {
+  fn name(&self) -> Cow<str> {
+    "consoleResize".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
+
+#[op]
+fn op_console_resize_bind(state: &mut OpState) -> Result<ResourceId, AnyError> {
+  let resource = ConsoleResizeResource {
+    #[cfg(unix)]
+    signal: AsyncRefCell::new(signal(SignalKind::window_change())?),
+    #[cfg(windows)]
+    last_size: Cell::new(stdio_console_size(state).ok()),
+    cancel: Default::default(),
+  };
+  Ok(state.resource_table.add(resource))
+}
+
+/// Waits for the size of the console to change. Resolves to `true` once the
+/// resource is closed, like `op_signal_poll`.
+#[op]
+async fn op_console_resize_poll(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<bool, AnyError> {
+  let resource = state
+    .borrow_mut()
+    .resource_table
+    .get::<ConsoleResizeResource>(rid)?;
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+
+  #[cfg(unix)]
+  {
+    let mut signal = RcRef::map(&resource, |r| &r.signal).borrow_mut().await;
+    match signal.recv().or_cancel(cancel).await {
+      Ok(result) => Ok(result.is_none()),
+      Err(_) => Ok(true),
+    }
+  }
+  // There is no SIGWINCH on Windows and resize events are only reported to
+  // console input readers, so the size is polled instead.
+  #[cfg(windows)]
+  {
+    const POLL_INTERVAL: std::time::Duration =
+      std::time::Duration::from_millis(250);
+    loop {
+      let sleep = tokio::time::sleep(POLL_INTERVAL);
+      if sleep.or_cancel(cancel.clone()).await.is_err() {
+        return Ok(true);
+      }
+      let size = stdio_console_size(&mut state.borrow_mut()).ok();
+      if size != resource.last_size.replace(size) {
+        return Ok(false);
+      }
+    }
+  }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct ConsoleSize {
   pub cols: u32,
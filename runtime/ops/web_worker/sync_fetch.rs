@@ -9,7 +9,7 @@ use deno_core::url::Url;
 use deno_core::OpState;
 use deno_fetch::data_url::DataUrl;
 use deno_web::BlobStore;
-use deno_websocket::DomExceptionNetworkError;
+use deno_web::DomExceptionNetworkError;
 use hyper::body::Bytes;
 use serde::Deserialize;
 use serde::Serialize;
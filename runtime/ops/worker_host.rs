@@ -3,6 +3,7 @@
 use crate::ops::TestingFeaturesEnabled;
 use crate::permissions::create_child_permissions;
 use crate::permissions::ChildPermissionsArg;
+use crate::permissions::Permissions;
 use crate::permissions::PermissionsContainer;
 use crate::web_worker::run_web_worker;
 use crate::web_worker::SendableWebWorkerHandle;
@@ -12,6 +13,8 @@ use crate::web_worker::WebWorkerType;
 use crate::web_worker::WorkerControlEvent;
 use crate::web_worker::WorkerId;
 use crate::worker::FormatJsErrorFn;
+use deno_core::error::bad_resource_id;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::future::LocalFutureObj;
 use deno_core::op;
@@ -24,6 +27,7 @@ use deno_web::JsMessageData;
 use log::debug;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -87,6 +91,28 @@ impl Drop for WorkerThread {
 
 pub type WorkersTable = HashMap<WorkerId, WorkerThread>;
 
+/// Module workers that are spawned ahead of time for `Deno.WorkerPool`, so
+/// that they have evaluated their module by the time they are needed.
+struct WorkerPool {
+  specifier: String,
+  name: String,
+  /// The permissions given in `deno.permissions`, which are derived from the
+  /// current permissions of the host whenever a worker is spawned, so that
+  /// permissions the host revoked aren't handed on. The workers share the
+  /// permissions of the host otherwise.
+  permissions: Option<ChildPermissionsArg>,
+  size: usize,
+  /// The workers that weren't handed out yet, in the workers table, with the
+  /// permissions they were spawned with if they were given `permissions`.
+  idle: VecDeque<(WorkerId, Option<Permissions>)>,
+}
+
+#[derive(Default)]
+struct WorkerPoolsTable {
+  next_id: u32,
+  pools: HashMap<u32, WorkerPool>,
+}
+
 deno_core::extension!(
   deno_worker_host,
   ops = [
@@ -95,6 +121,10 @@ deno_core::extension!(
     op_host_post_message,
     op_host_recv_ctrl,
     op_host_recv_message,
+    op_create_worker_pool,
+    op_worker_pool_acquire,
+    op_worker_pool_refill,
+    op_worker_pool_close,
   ],
   options = {
     create_web_worker_cb: Arc<CreateWebWorkerCb>,
@@ -105,6 +135,7 @@ deno_core::extension!(
   state = |state, options| {
     state.put::<WorkersTable>(WorkersTable::default());
     state.put::<WorkerId>(WorkerId::default());
+    state.put::<WorkerPoolsTable>(WorkerPoolsTable::default());
 
     let create_web_worker_cb_holder =
       CreateWebWorkerCbHolder(options.create_web_worker_cb);
@@ -138,18 +169,16 @@ fn op_create_worker(
   state: &mut OpState,
   args: CreateWorkerArgs,
 ) -> Result<WorkerId, AnyError> {
-  let specifier = args.specifier.clone();
   let maybe_source_code = if args.has_source_code {
     Some(args.source_code.clone())
   } else {
     None
   };
-  let args_name = args.name;
   let worker_type = args.worker_type;
   if let WebWorkerType::Classic = worker_type {
     if let TestingFeaturesEnabled(false) = state.borrow() {
       return Err(
-        deno_webstorage::DomExceptionNotSupportedError::new(
+        deno_web::DomExceptionNotSupportedError::new(
           "Classic workers are not supported.",
         )
         .into(),
@@ -160,17 +189,44 @@ fn op_create_worker(
   if args.permissions.is_some() {
     super::check_unstable(state, "Worker.deno.permissions");
   }
+  let worker_permissions = resolve_worker_permissions(state, args.permissions)?;
+  create_worker(
+    state,
+    &args.specifier,
+    maybe_source_code,
+    args.name.unwrap_or_default(),
+    worker_type,
+    worker_permissions,
+  )
+}
+
+/// Create the permissions of a worker from the ones it was given in
+/// `deno.permissions`, or inherit the ones of the host if there are none.
+fn resolve_worker_permissions(
+  state: &mut OpState,
+  maybe_permissions: Option<ChildPermissionsArg>,
+) -> Result<PermissionsContainer, AnyError> {
   let parent_permissions = state.borrow_mut::<PermissionsContainer>();
-  let worker_permissions = if let Some(child_permissions_arg) = args.permissions
-  {
+  if let Some(child_permissions_arg) = maybe_permissions {
     let mut parent_permissions = parent_permissions.0.lock();
     let perms =
       create_child_permissions(&mut parent_permissions, child_permissions_arg)?;
-    PermissionsContainer::new(perms)
+    Ok(PermissionsContainer::new(perms))
   } else {
-    parent_permissions.clone()
-  };
-  let parent_permissions = parent_permissions.clone();
+    Ok(parent_permissions.clone())
+  }
+}
+
+/// Spawn the thread of a new worker and add it to the workers table.
+fn create_worker(
+  state: &mut OpState,
+  specifier: &str,
+  maybe_source_code: Option<String>,
+  worker_name: String,
+  worker_type: WebWorkerType,
+  worker_permissions: PermissionsContainer,
+) -> Result<WorkerId, AnyError> {
+  let parent_permissions = state.borrow::<PermissionsContainer>().clone();
   let worker_id = state.take::<WorkerId>();
   let create_web_worker_cb = state.take::<CreateWebWorkerCbHolder>();
   state.put::<CreateWebWorkerCbHolder>(create_web_worker_cb.clone());
@@ -182,8 +238,7 @@ fn op_create_worker(
   state.put::<FormatJsErrorFnHolder>(format_js_error_fn.clone());
   state.put::<WorkerId>(worker_id.next().unwrap());
 
-  let module_specifier = deno_core::resolve_url(&specifier)?;
-  let worker_name = args_name.unwrap_or_default();
+  let module_specifier = deno_core::resolve_url(specifier)?;
 
   let (handle_sender, handle_receiver) = std::sync::mpsc::sync_channel::<
     Result<SendableWebWorkerHandle, AnyError>,
@@ -246,6 +301,160 @@ fn op_create_worker(
   Ok(worker_id)
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWorkerPoolArgs {
+  name: Option<String>,
+  permissions: Option<ChildPermissionsArg>,
+  specifier: String,
+  size: usize,
+}
+
+/// Create a pool of module workers and spawn its workers
+#[op]
+fn op_create_worker_pool(
+  state: &mut OpState,
+  args: CreateWorkerPoolArgs,
+) -> Result<u32, AnyError> {
+  super::check_unstable(state, "Deno.WorkerPool");
+  if args.size == 0 {
+    return Err(type_error("The size of a worker pool must be at least 1."));
+  }
+  let mut pool = WorkerPool {
+    specifier: args.specifier,
+    name: args.name.unwrap_or_default(),
+    permissions: args.permissions,
+    size: args.size,
+    idle: VecDeque::with_capacity(args.size),
+  };
+  fill_worker_pool(state, &mut pool)?;
+
+  let pools = state.borrow_mut::<WorkerPoolsTable>();
+  let id = pools.next_id;
+  pools.next_id += 1;
+  pools.pools.insert(id, pool);
+  Ok(id)
+}
+
+/// Spawn workers until the pool has as many idle workers as its size.
+fn fill_worker_pool(
+  state: &mut OpState,
+  pool: &mut WorkerPool,
+) -> Result<(), AnyError> {
+  while pool.idle.len() < pool.size {
+    let worker_permissions =
+      resolve_worker_permissions(state, pool.permissions.clone())?;
+    let spawned_with = pool
+      .permissions
+      .as_ref()
+      .map(|_| worker_permissions.0.lock().clone());
+    let worker_id = create_worker(
+      state,
+      &pool.specifier,
+      None,
+      pool.name.clone(),
+      WebWorkerType::Module,
+      worker_permissions,
+    )?;
+    pool.idle.push_back((worker_id, spawned_with));
+  }
+  Ok(())
+}
+
+/// Hand out an idle worker of a pool. The pool is refilled separately by
+/// `op_worker_pool_refill`, so that spawning the replacement doesn't delay
+/// the caller.
+#[op]
+fn op_worker_pool_acquire(
+  state: &mut OpState,
+  id: u32,
+) -> Result<WorkerId, AnyError> {
+  let pools = state.borrow::<WorkerPoolsTable>();
+  let permissions_arg = match pools.pools.get(&id) {
+    Some(pool) => pool.permissions.clone(),
+    None => return Err(bad_resource_id()),
+  };
+  // The host might have revoked some of its permissions since the idle
+  // workers were spawned.
+  let permissions = match permissions_arg {
+    Some(permissions_arg) => {
+      let permissions =
+        resolve_worker_permissions(state, Some(permissions_arg))?;
+      let permissions = permissions.0.lock().clone();
+      Some(permissions)
+    }
+    None => None,
+  };
+  let mut pool = state
+    .borrow_mut::<WorkerPoolsTable>()
+    .pools
+    .remove(&id)
+    .unwrap();
+  let mut acquired = None;
+  while let Some((worker_id, spawned_with)) = pool.idle.pop_front() {
+    let workers = state.borrow_mut::<WorkersTable>();
+    let is_terminated = match workers.get(&worker_id) {
+      Some(worker_thread) => worker_thread.worker_handle.is_terminated(),
+      None => continue,
+    };
+    if !is_terminated && spawned_with == permissions {
+      acquired = Some(worker_id);
+      break;
+    }
+    // Workers that closed themselves while idle, or that have permissions
+    // the host doesn't have anymore, can't be used.
+    workers.remove(&worker_id).unwrap().terminate();
+  }
+  let result = match acquired {
+    Some(worker_id) => Ok(worker_id),
+    // None of the workers were usable, so this one is started cold.
+    None => fill_worker_pool(state, &mut pool)
+      .map(|_| pool.idle.pop_front().unwrap().0),
+  };
+  state
+    .borrow_mut::<WorkerPoolsTable>()
+    .pools
+    .insert(id, pool);
+  result
+}
+
+/// Replace the workers that were handed out by a pool.
+#[op]
+fn op_worker_pool_refill(state: &mut OpState, id: u32) -> Result<(), AnyError> {
+  let pools = state.borrow_mut::<WorkerPoolsTable>();
+  let mut pool = match pools.pools.remove(&id) {
+    Some(pool) => pool,
+    // The pool might have been closed in the meantime.
+    None => return Ok(()),
+  };
+  let result = fill_worker_pool(state, &mut pool);
+  state
+    .borrow_mut::<WorkerPoolsTable>()
+    .pools
+    .insert(id, pool);
+  result
+}
+
+/// Terminate the idle workers of a pool. The workers that were handed out
+/// keep running.
+#[op]
+fn op_worker_pool_close(state: &mut OpState, id: u32) {
+  let pools = state.borrow_mut::<WorkerPoolsTable>();
+  let pool = match pools.pools.remove(&id) {
+    Some(pool) => pool,
+    None => {
+      debug!("tried to close non-existent worker pool {}", id);
+      return;
+    }
+  };
+  let workers = state.borrow_mut::<WorkersTable>();
+  for (worker_id, _) in pool.idle {
+    if let Some(worker_thread) = workers.remove(&worker_id) {
+      worker_thread.terminate();
+    }
+  }
+}
+
 #[op]
 fn op_host_terminate_worker(state: &mut OpState, id: WorkerId) {
   if let Some(worker_thread) = state.borrow_mut::<WorkersTable>().remove(&id) {
@@ -19,6 +19,7 @@ use deno_core::OpState;
 use log;
 use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
@@ -233,9 +234,63 @@ pub struct UnaryPermission<T: Eq + Hash> {
   pub global_state: PermissionState,
   pub granted_list: HashSet<T>,
   pub denied_list: HashSet<T>,
+  /// Whether the `--deny-*` flag was passed without values, which denies all
+  /// access whatever else was granted.
+  pub flag_denied_global: bool,
+  /// The descriptors denied by the `--deny-*` flag. They take precedence over
+  /// the granted ones and are never prompted for.
+  pub flag_denied_list: HashSet<T>,
   pub prompt: bool,
 }
 
+impl<T: Eq + Hash> UnaryPermission<T> {
+  /// Denies everything that `denied` grants, which is the `--deny-*` flag
+  /// parsed the way the matching `--allow-*` flag would be.
+  fn with_flag_denials(self, denied: Self) -> Self {
+    Self {
+      flag_denied_global: denied.global_state == PermissionState::Granted,
+      flag_denied_list: denied.granted_list,
+      ..self
+    }
+  }
+
+  /// Gets whether the `--deny-*` flag denies the access. `matches` tells if a
+  /// denied descriptor covers the accessed one, and is `None` when access to
+  /// everything is checked, which any denial rules out.
+  fn is_flag_denied(&self, matches: Option<impl Fn(&T) -> bool>) -> bool {
+    if self.flag_denied_global {
+      return true;
+    }
+    if self.flag_denied_list.is_empty() {
+      return false;
+    }
+    match matches {
+      None => true,
+      Some(matches) => self.flag_denied_list.iter().any(matches),
+    }
+  }
+
+  /// Runs `f` as if the `--deny-*` flag denied no descriptors, for checks
+  /// whose outcome is narrowed down by the same denials afterwards.
+  fn without_flag_denied_list<R>(
+    &mut self,
+    f: impl FnOnce(&mut Self) -> R,
+  ) -> R {
+    let flag_denied_list = std::mem::take(&mut self.flag_denied_list);
+    let result = f(self);
+    self.flag_denied_list = flag_denied_list;
+    result
+  }
+
+  fn inherit_flag_denials(&mut self, parent: &Self)
+  where
+    T: Clone,
+  {
+    self.flag_denied_global = parent.flag_denied_global;
+    self.flag_denied_list = parent.flag_denied_list.clone();
+  }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
 pub struct ReadDescriptor(pub PathBuf);
 
@@ -307,6 +362,40 @@ impl FromStr for RunDescriptor {
   }
 }
 
+impl RunDescriptor {
+  /// The executable that the command runs, with its symlinks resolved, so
+  /// that it is recognized whichever way it is named. Names are looked up in
+  /// the `PATH`.
+  fn resolve_executable(&self) -> Option<PathBuf> {
+    let path = match self {
+      RunDescriptor::Name(name) => find_in_path(name)?,
+      RunDescriptor::Path(path) => path.clone(),
+    };
+    std::fs::canonicalize(path).ok()
+  }
+}
+
+fn find_in_path(name: &str) -> Option<PathBuf> {
+  let paths = std::env::var_os("PATH")?;
+  #[cfg(windows)]
+  let extensions = std::env::var("PATHEXT")
+    .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+  for dir in std::env::split_paths(&paths) {
+    let path = dir.join(name);
+    if path.is_file() {
+      return Some(path);
+    }
+    #[cfg(windows)]
+    for extension in extensions.split(';') {
+      let path = dir.join(format!("{name}{extension}"));
+      if path.is_file() {
+        return Some(path);
+      }
+    }
+  }
+  None
+}
+
 impl ToString for RunDescriptor {
   fn to_string(&self) -> String {
     match self {
@@ -322,7 +411,7 @@ pub struct SysDescriptor(pub String);
 pub fn parse_sys_kind(kind: &str) -> Result<&str, AnyError> {
   match kind {
     "hostname" | "osRelease" | "osUptime" | "loadavg" | "networkInterfaces"
-    | "systemMemoryInfo" | "uid" | "gid" => Ok(kind),
+    | "systemMemoryInfo" | "uid" | "gid" | "cpus" => Ok(kind),
     _ => Err(type_error(format!("unknown system info kind \"{kind}\""))),
   }
 }
@@ -332,6 +421,13 @@ pub struct FfiDescriptor(pub PathBuf);
 
 impl UnaryPermission<ReadDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
+    if self.is_flag_denied(path.map(|path| {
+      move |desc: &ReadDescriptor| {
+        resolve_from_cwd(path).unwrap().starts_with(&desc.0)
+      }
+    })) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Granted {
       return PermissionState::Granted;
     }
@@ -519,6 +615,8 @@ impl Default for UnaryPermission<ReadDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -526,6 +624,13 @@ impl Default for UnaryPermission<ReadDescriptor> {
 
 impl UnaryPermission<WriteDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
+    if self.is_flag_denied(path.map(|path| {
+      move |desc: &WriteDescriptor| {
+        resolve_from_cwd(path).unwrap().starts_with(&desc.0)
+      }
+    })) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Granted {
       return PermissionState::Granted;
     }
@@ -713,6 +818,8 @@ impl Default for UnaryPermission<WriteDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -723,6 +830,16 @@ impl UnaryPermission<NetDescriptor> {
     &self,
     host: Option<&(T, Option<u16>)>,
   ) -> PermissionState {
+    // a denied host without a port denies all of its ports, and a host
+    // accessed on any port is denied by the denial of one of them
+    if self.is_flag_denied(host.map(|host| {
+      move |desc: &NetDescriptor| {
+        desc.0 == host.0.as_ref()
+          && (desc.1.is_none() || host.1.is_none() || desc.1 == host.1)
+      }
+    })) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Denied
       && match host.as_ref() {
         None => true,
@@ -923,6 +1040,8 @@ impl Default for UnaryPermission<NetDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -931,6 +1050,13 @@ impl Default for UnaryPermission<NetDescriptor> {
 impl UnaryPermission<EnvDescriptor> {
   pub fn query(&self, env: Option<&str>) -> PermissionState {
     let env = env.map(EnvVarName::new);
+    if self.is_flag_denied(
+      env
+        .as_ref()
+        .map(|env| move |desc: &EnvDescriptor| desc.0 == *env),
+    ) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Denied
       && match env.as_ref() {
         None => true,
@@ -1064,6 +1190,8 @@ impl Default for UnaryPermission<EnvDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -1071,6 +1199,11 @@ impl Default for UnaryPermission<EnvDescriptor> {
 
 impl UnaryPermission<SysDescriptor> {
   pub fn query(&self, kind: Option<&str>) -> PermissionState {
+    if self.is_flag_denied(
+      kind.map(|kind| move |desc: &SysDescriptor| desc.0 == kind),
+    ) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Denied
       && match kind {
         None => true,
@@ -1203,6 +1336,8 @@ impl Default for UnaryPermission<SysDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -1210,6 +1345,22 @@ impl Default for UnaryPermission<SysDescriptor> {
 
 impl UnaryPermission<RunDescriptor> {
   pub fn query(&self, cmd: Option<&str>) -> PermissionState {
+    // a command is also denied when it runs the same executable as a denied
+    // one, like a path to a denied name or a symlink to a denied path
+    if self.is_flag_denied(cmd.map(|cmd| {
+      let cmd = RunDescriptor::from_str(cmd).unwrap();
+      let executable = if self.flag_denied_list.is_empty() {
+        None
+      } else {
+        cmd.resolve_executable()
+      };
+      move |desc: &RunDescriptor| {
+        *desc == cmd
+          || (executable.is_some() && desc.resolve_executable() == executable)
+      }
+    })) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Denied
       && match cmd {
         None => true,
@@ -1363,6 +1514,8 @@ impl Default for UnaryPermission<RunDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
@@ -1371,6 +1524,13 @@ impl Default for UnaryPermission<RunDescriptor> {
 impl UnaryPermission<FfiDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
     let path = path.map(|p| resolve_from_cwd(p).unwrap());
+    if self.is_flag_denied(
+      path
+        .as_ref()
+        .map(|path| move |desc: &FfiDescriptor| path.starts_with(&desc.0)),
+    ) {
+      return PermissionState::Denied;
+    }
     if self.global_state == PermissionState::Denied
       && match path.as_ref() {
         None => true,
@@ -1533,11 +1693,77 @@ impl Default for UnaryPermission<FfiDescriptor> {
       global_state: Default::default(),
       granted_list: Default::default(),
       denied_list: Default::default(),
+      flag_denied_global: false,
+      flag_denied_list: Default::default(),
       prompt: false,
     }
   }
 }
 
+/// The symbols that may be bound from the libraries listed in the
+/// `--allow-ffi-symbols` file, on top of the ffi permission to load them.
+/// Once the file is given, only the symbols of the listed libraries may be
+/// bound.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FfiSymbolPermission {
+  /// Maps a canonicalized library, or directory of libraries, to its allowed
+  /// symbols. It is `None` when symbols are not restricted.
+  pub allowlist: Option<HashMap<PathBuf, HashSet<String>>>,
+  pub prompt: bool,
+}
+
+impl FfiSymbolPermission {
+  /// Queries a symbol of the library at `path`, which must be canonicalized.
+  pub fn query(&self, path: &Path, symbol: &str) -> PermissionState {
+    let Some(allowlist) = &self.allowlist else {
+      return PermissionState::Granted;
+    };
+    match allowlist_entry(allowlist, path) {
+      Some(library) if allowlist[library].contains(symbol) => {
+        PermissionState::Granted
+      }
+      _ => PermissionState::Prompt,
+    }
+  }
+
+  pub fn check(&mut self, path: &Path, symbol: &str) -> Result<(), AnyError> {
+    let display_path = path.to_path_buf();
+    let resolved_path = canonicalize_ffi_path(path)?;
+    if self.query(&resolved_path, symbol) == PermissionState::Granted {
+      return Ok(());
+    }
+    let access = format!(
+      "ffi access to symbol \"{}\" of \"{}\"",
+      symbol,
+      display_path.display()
+    );
+    if self.prompt {
+      match permission_prompt(&access, "ffi", Some("Deno.dlopen()"), false) {
+        PromptResponse::Allow | PromptResponse::AllowAll => {
+          // the allowlist exists, otherwise the symbol would have been
+          // granted
+          let allowlist = self.allowlist.as_mut().unwrap();
+          let library = allowlist_entry(allowlist, &resolved_path)
+            .unwrap_or(&resolved_path)
+            .clone();
+          allowlist
+            .entry(library)
+            .or_default()
+            .insert(symbol.to_string());
+          return Ok(());
+        }
+        PromptResponse::Deny => {}
+      }
+    }
+    Err(custom_error(
+      "PermissionDenied",
+      format!(
+        "Requires {access}, add it to the file given to the --allow-ffi-symbols flag"
+      ),
+    ))
+  }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Permissions {
   pub read: UnaryPermission<ReadDescriptor>,
@@ -1547,6 +1773,7 @@ pub struct Permissions {
   pub sys: UnaryPermission<SysDescriptor>,
   pub run: UnaryPermission<RunDescriptor>,
   pub ffi: UnaryPermission<FfiDescriptor>,
+  pub ffi_symbols: FfiSymbolPermission,
   pub hrtime: UnitPermission,
 }
 
@@ -1560,6 +1787,7 @@ impl Default for Permissions {
       sys: Permissions::new_sys(&None, false).unwrap(),
       run: Permissions::new_run(&None, false).unwrap(),
       ffi: Permissions::new_ffi(&None, false).unwrap(),
+      ffi_symbols: Default::default(),
       hrtime: Permissions::new_hrtime(false),
     }
   }
@@ -1571,10 +1799,20 @@ pub struct PermissionsOptions {
   pub allow_hrtime: bool,
   pub allow_net: Option<Vec<String>>,
   pub allow_ffi: Option<Vec<PathBuf>>,
+  /// A JSON file mapping library paths, relative to the file, to the only
+  /// symbols that may be bound from them.
+  pub allow_ffi_symbols: Option<PathBuf>,
   pub allow_read: Option<Vec<PathBuf>>,
   pub allow_run: Option<Vec<String>>,
   pub allow_sys: Option<Vec<String>>,
   pub allow_write: Option<Vec<PathBuf>>,
+  pub deny_env: Option<Vec<String>>,
+  pub deny_net: Option<Vec<String>>,
+  pub deny_ffi: Option<Vec<PathBuf>>,
+  pub deny_read: Option<Vec<PathBuf>>,
+  pub deny_run: Option<Vec<String>>,
+  pub deny_sys: Option<Vec<String>>,
+  pub deny_write: Option<Vec<PathBuf>>,
   pub prompt: bool,
 }
 
@@ -1709,6 +1947,16 @@ impl Permissions {
     })
   }
 
+  pub fn new_ffi_symbols(
+    allowlist_path: &Option<PathBuf>,
+    prompt: bool,
+  ) -> Result<FfiSymbolPermission, AnyError> {
+    Ok(FfiSymbolPermission {
+      allowlist: resolve_ffi_symbol_allowlist(allowlist_path)?,
+      prompt,
+    })
+  }
+
   pub fn new_hrtime(state: bool) -> UnitPermission {
     unit_permission_from_flag_bool(
       state,
@@ -1720,13 +1968,24 @@ impl Permissions {
 
   pub fn from_options(opts: &PermissionsOptions) -> Result<Self, AnyError> {
     Ok(Self {
-      read: Permissions::new_read(&opts.allow_read, opts.prompt)?,
-      write: Permissions::new_write(&opts.allow_write, opts.prompt)?,
-      net: Permissions::new_net(&opts.allow_net, opts.prompt)?,
-      env: Permissions::new_env(&opts.allow_env, opts.prompt)?,
-      sys: Permissions::new_sys(&opts.allow_sys, opts.prompt)?,
-      run: Permissions::new_run(&opts.allow_run, opts.prompt)?,
-      ffi: Permissions::new_ffi(&opts.allow_ffi, opts.prompt)?,
+      read: Permissions::new_read(&opts.allow_read, opts.prompt)?
+        .with_flag_denials(Permissions::new_read(&opts.deny_read, false)?),
+      write: Permissions::new_write(&opts.allow_write, opts.prompt)?
+        .with_flag_denials(Permissions::new_write(&opts.deny_write, false)?),
+      net: Permissions::new_net(&opts.allow_net, opts.prompt)?
+        .with_flag_denials(Permissions::new_net(&opts.deny_net, false)?),
+      env: Permissions::new_env(&opts.allow_env, opts.prompt)?
+        .with_flag_denials(Permissions::new_env(&opts.deny_env, false)?),
+      sys: Permissions::new_sys(&opts.allow_sys, opts.prompt)?
+        .with_flag_denials(Permissions::new_sys(&opts.deny_sys, false)?),
+      run: Permissions::new_run(&opts.allow_run, opts.prompt)?
+        .with_flag_denials(Permissions::new_run(&opts.deny_run, false)?),
+      ffi: Permissions::new_ffi(&opts.allow_ffi, opts.prompt)?
+        .with_flag_denials(Permissions::new_ffi(&opts.deny_ffi, false)?),
+      ffi_symbols: Permissions::new_ffi_symbols(
+        &opts.allow_ffi_symbols,
+        opts.prompt,
+      )?,
       hrtime: Permissions::new_hrtime(opts.allow_hrtime),
     })
   }
@@ -1740,6 +1999,7 @@ impl Permissions {
       sys: Permissions::new_sys(&Some(vec![]), false).unwrap(),
       run: Permissions::new_run(&Some(vec![]), false).unwrap(),
       ffi: Permissions::new_ffi(&Some(vec![]), false).unwrap(),
+      ffi_symbols: Default::default(),
       hrtime: Permissions::new_hrtime(true),
     }
   }
@@ -1873,6 +2133,15 @@ impl PermissionsContainer {
 }
 
 impl deno_node::NodePermissions for PermissionsContainer {
+  #[inline(always)]
+  fn check_net(
+    &mut self,
+    host: (&str, Option<u16>),
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    self.0.lock().net.check(&host, Some(api_name))
+  }
+
   #[inline(always)]
   fn check_net_url(
     &mut self,
@@ -1886,6 +2155,30 @@ impl deno_node::NodePermissions for PermissionsContainer {
   fn check_read(&self, path: &Path) -> Result<(), AnyError> {
     self.0.lock().read.check(path, None)
   }
+
+  #[inline(always)]
+  fn check_write(
+    &mut self,
+    path: &Path,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    self.0.lock().write.check(path, Some(api_name))
+  }
+
+  #[inline(always)]
+  fn check_env(&mut self, var: &str) -> Result<(), AnyError> {
+    self.0.lock().env.check(var)
+  }
+
+  #[inline(always)]
+  fn check_run(&mut self, cmd: &str, api_name: &str) -> Result<(), AnyError> {
+    self.0.lock().run.check(cmd, Some(api_name))
+  }
+
+  #[inline(always)]
+  fn check_sys(&mut self, kind: &str, api_name: &str) -> Result<(), AnyError> {
+    self.0.lock().sys.check(kind, Some(api_name))
+  }
 }
 
 impl deno_net::NetPermissions for PermissionsContainer {
@@ -2018,6 +2311,15 @@ impl deno_ffi::FfiPermissions for PermissionsContainer {
   fn check(&mut self, path: Option<&Path>) -> Result<(), AnyError> {
     self.0.lock().ffi.check(path)
   }
+
+  #[inline(always)]
+  fn check_symbol(
+    &mut self,
+    path: &Path,
+    symbol: &str,
+  ) -> Result<(), AnyError> {
+    self.0.lock().ffi_symbols.check(path, symbol)
+  }
 }
 
 impl deno_kv::sqlite::SqliteDbHandlerPermissions for PermissionsContainer {
@@ -2112,6 +2414,64 @@ pub fn resolve_ffi_allowlist(
   }
 }
 
+/// The most specific entry of the allowlist for the library, so that a
+/// library can be restricted further than the directory containing it.
+fn allowlist_entry<'a>(
+  allowlist: &'a HashMap<PathBuf, HashSet<String>>,
+  path: &Path,
+) -> Option<&'a PathBuf> {
+  allowlist
+    .keys()
+    .filter(|library| path.starts_with(library))
+    .max_by_key(|library| library.components().count())
+}
+
+/// Resolves the path of a library from the CWD and follows its symlinks, so
+/// that it can't be reached through a path other than the listed one. A path
+/// that doesn't exist is only normalized.
+fn canonicalize_ffi_path(path: &Path) -> Result<PathBuf, AnyError> {
+  let path = resolve_from_cwd(path)?;
+  Ok(std::fs::canonicalize(&path).unwrap_or(path))
+}
+
+/// Reads the `--allow-ffi-symbols` file, which is a JSON object mapping
+/// library paths, relative to the file, to arrays of symbol names.
+pub fn resolve_ffi_symbol_allowlist(
+  allowlist_path: &Option<PathBuf>,
+) -> Result<Option<HashMap<PathBuf, HashSet<String>>>, AnyError> {
+  let Some(allowlist_path) = allowlist_path else {
+    return Ok(None);
+  };
+  let allowlist_path = resolve_from_cwd(allowlist_path)?;
+  let text = std::fs::read_to_string(&allowlist_path).map_err(|err| {
+    AnyError::msg(format!(
+      "Unable to read ffi symbol allowlist \"{}\": {}",
+      allowlist_path.display(),
+      err
+    ))
+  })?;
+  let allowlist: HashMap<PathBuf, HashSet<String>> =
+    serde_json::from_str(&text).map_err(|err| {
+      AnyError::msg(format!(
+        "Invalid ffi symbol allowlist \"{}\": {}",
+        allowlist_path.display(),
+        err
+      ))
+    })?;
+  let base = allowlist_path.parent().unwrap();
+  allowlist
+    .into_iter()
+    .map(|(library, symbols)| {
+      if library.as_os_str().is_empty() {
+        Err(AnyError::msg("Empty path is not allowed"))
+      } else {
+        Ok((canonicalize_ffi_path(&base.join(library))?, symbols))
+      }
+    })
+    .collect::<Result<_, _>>()
+    .map(Some)
+}
+
 /// Arbitrary helper. Resolves the path from CWD, and also gets a path that
 /// can be displayed without leaking the CWD when not allowed.
 #[inline]
@@ -2128,7 +2488,7 @@ fn escalation_error() -> AnyError {
   )
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ChildUnitPermissionArg {
   Inherit,
   Granted,
@@ -2180,7 +2540,7 @@ impl<'de> Deserialize<'de> for ChildUnitPermissionArg {
   }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ChildUnaryPermissionArg {
   Inherit,
   Granted,
@@ -2248,7 +2608,7 @@ impl<'de> Deserialize<'de> for ChildUnaryPermissionArg {
 }
 
 /// Directly deserializable from JS worker and test permission options.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ChildPermissionsArg {
   env: ChildUnaryPermissionArg,
   hrtime: ChildUnitPermissionArg,
@@ -2390,7 +2750,11 @@ pub fn create_child_permissions(
       worker_perms.env = main_perms.env.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.env.check_all().is_err() {
+      if main_perms
+        .env
+        .without_flag_denied_list(|perm| perm.check_all())
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.env.global_state = PermissionState::Granted;
@@ -2410,6 +2774,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.env.denied_list = main_perms.env.denied_list.clone();
+  worker_perms.env.inherit_flag_denials(&main_perms.env);
   if main_perms.env.global_state == PermissionState::Denied {
     worker_perms.env.global_state = PermissionState::Denied;
   }
@@ -2419,7 +2784,11 @@ pub fn create_child_permissions(
       worker_perms.sys = main_perms.sys.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.sys.check_all().is_err() {
+      if main_perms
+        .sys
+        .without_flag_denied_list(|perm| perm.check_all())
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.sys.global_state = PermissionState::Granted;
@@ -2439,6 +2808,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.sys.denied_list = main_perms.sys.denied_list.clone();
+  worker_perms.sys.inherit_flag_denials(&main_perms.sys);
   if main_perms.sys.global_state == PermissionState::Denied {
     worker_perms.sys.global_state = PermissionState::Denied;
   }
@@ -2464,7 +2834,11 @@ pub fn create_child_permissions(
       worker_perms.net = main_perms.net.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.net.check_all().is_err() {
+      if main_perms
+        .net
+        .without_flag_denied_list(|perm| perm.check_all())
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.net.global_state = PermissionState::Granted;
@@ -2484,6 +2858,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.net.denied_list = main_perms.net.denied_list.clone();
+  worker_perms.net.inherit_flag_denials(&main_perms.net);
   if main_perms.net.global_state == PermissionState::Denied {
     worker_perms.net.global_state = PermissionState::Denied;
   }
@@ -2493,7 +2868,11 @@ pub fn create_child_permissions(
       worker_perms.ffi = main_perms.ffi.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.ffi.check_all().is_err() {
+      if main_perms
+        .ffi
+        .without_flag_denied_list(|perm| perm.check_all())
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.ffi.global_state = PermissionState::Granted;
@@ -2516,16 +2895,23 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.ffi.denied_list = main_perms.ffi.denied_list.clone();
+  worker_perms.ffi.inherit_flag_denials(&main_perms.ffi);
   if main_perms.ffi.global_state == PermissionState::Denied {
     worker_perms.ffi.global_state = PermissionState::Denied;
   }
   worker_perms.ffi.prompt = main_perms.ffi.prompt;
+  // the symbol allowlist only ever restricts, so it is always inherited
+  worker_perms.ffi_symbols = main_perms.ffi_symbols.clone();
   match child_permissions_arg.read {
     ChildUnaryPermissionArg::Inherit => {
       worker_perms.read = main_perms.read.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.read.check_all(None).is_err() {
+      if main_perms
+        .read
+        .without_flag_denied_list(|perm| perm.check_all(None))
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.read.global_state = PermissionState::Granted;
@@ -2548,6 +2934,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.read.denied_list = main_perms.read.denied_list.clone();
+  worker_perms.read.inherit_flag_denials(&main_perms.read);
   if main_perms.read.global_state == PermissionState::Denied {
     worker_perms.read.global_state = PermissionState::Denied;
   }
@@ -2557,7 +2944,11 @@ pub fn create_child_permissions(
       worker_perms.run = main_perms.run.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.run.check_all(None).is_err() {
+      if main_perms
+        .run
+        .without_flag_denied_list(|perm| perm.check_all(None))
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.run.global_state = PermissionState::Granted;
@@ -2577,6 +2968,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.run.denied_list = main_perms.run.denied_list.clone();
+  worker_perms.run.inherit_flag_denials(&main_perms.run);
   if main_perms.run.global_state == PermissionState::Denied {
     worker_perms.run.global_state = PermissionState::Denied;
   }
@@ -2586,7 +2978,11 @@ pub fn create_child_permissions(
       worker_perms.write = main_perms.write.clone();
     }
     ChildUnaryPermissionArg::Granted => {
-      if main_perms.write.check_all(None).is_err() {
+      if main_perms
+        .write
+        .without_flag_denied_list(|perm| perm.check_all(None))
+        .is_err()
+      {
         return Err(escalation_error());
       }
       worker_perms.write.global_state = PermissionState::Granted;
@@ -2609,6 +3005,7 @@ pub fn create_child_permissions(
     }
   }
   worker_perms.write.denied_list = main_perms.write.denied_list.clone();
+  worker_perms.write.inherit_flag_denials(&main_perms.write);
   if main_perms.write.global_state == PermissionState::Denied {
     worker_perms.write.global_state = PermissionState::Denied;
   }
@@ -2729,6 +3126,56 @@ mod tests {
     assert!(perms.ffi.check(Some(Path::new("/a/b"))).is_err());
   }
 
+  #[test]
+  fn check_ffi_symbols() {
+    set_prompter(Box::new(TestPrompter));
+    let temp_dir = test_util::TempDir::new();
+    temp_dir.write(
+      "symbols.json",
+      r#"{ "lib": ["a", "b"], "lib/libfoo.so": ["a"] }"#,
+    );
+    temp_dir.create_dir_all("lib");
+    temp_dir.write("lib/libfoo.so", "");
+    temp_dir.write("libc.so", "");
+    let mut perms = Permissions::new_ffi_symbols(
+      &Some(temp_dir.path().join("symbols.json")),
+      false,
+    )
+    .unwrap();
+    let lib = temp_dir.path().join("lib");
+
+    // the most specific entry applies
+    assert!(perms.check(&lib.join("libbar.so"), "a").is_ok());
+    assert!(perms.check(&lib.join("libbar.so"), "b").is_ok());
+    assert!(perms.check(&lib.join("libbar.so"), "c").is_err());
+    assert!(perms.check(&lib.join("libfoo.so"), "a").is_ok());
+    assert!(perms.check(&lib.join("libfoo.so"), "b").is_err());
+
+    // libraries that aren't listed are denied
+    assert!(perms.check(&temp_dir.path().join("libc.so"), "a").is_err());
+
+    // libraries are compared after following symlinks
+    #[cfg(unix)]
+    {
+      temp_dir.symlink_file("lib/libfoo.so", "libfoo.so");
+      assert!(perms.check(&temp_dir.path().join("libfoo.so"), "a").is_ok());
+      assert!(perms
+        .check(&temp_dir.path().join("libfoo.so"), "b")
+        .is_err());
+      temp_dir.symlink_file("libc.so", "lib/libc.so");
+      assert!(perms.check(&lib.join("libc.so"), "a").is_err());
+    }
+
+    // a symbol that was allowed from the prompt is remembered
+    let prompt_value = PERMISSION_PROMPT_STUB_VALUE_SETTER.lock();
+    perms.prompt = true;
+    prompt_value.set(true);
+    assert!(perms.check(&lib.join("libfoo.so"), "b").is_ok());
+    prompt_value.set(false);
+    assert!(perms.check(&lib.join("libfoo.so"), "b").is_ok());
+    assert!(perms.check(&lib.join("libfoo.so"), "c").is_err());
+  }
+
   #[test]
   fn test_check_net_with_values() {
     set_prompter(Box::new(TestPrompter));
@@ -3012,6 +3459,7 @@ mod tests {
         global_state: PermissionState::Prompt,
         ..Permissions::new_run(&Some(svec!["deno"]), false).unwrap()
       },
+      ffi_symbols: Default::default(),
       hrtime: UnitPermission {
         state: PermissionState::Prompt,
         ..Permissions::new_hrtime(false)
@@ -3151,6 +3599,7 @@ mod tests {
         global_state: PermissionState::Prompt,
         ..Permissions::new_run(&Some(svec!["deno"]), false).unwrap()
       },
+      ffi_symbols: Default::default(),
       hrtime: UnitPermission {
         state: PermissionState::Denied,
         ..Permissions::new_hrtime(false)
@@ -3188,6 +3637,7 @@ mod tests {
       sys: Permissions::new_sys(&None, true).unwrap(),
       run: Permissions::new_run(&None, true).unwrap(),
       ffi: Permissions::new_ffi(&None, true).unwrap(),
+      ffi_symbols: Default::default(),
       hrtime: Permissions::new_hrtime(false),
     };
 
@@ -3252,6 +3702,7 @@ mod tests {
       sys: Permissions::new_sys(&None, true).unwrap(),
       run: Permissions::new_run(&None, true).unwrap(),
       ffi: Permissions::new_ffi(&None, true).unwrap(),
+      ffi_symbols: Default::default(),
       hrtime: Permissions::new_hrtime(false),
     };
 
@@ -3590,6 +4041,130 @@ mod tests {
     assert_eq!(worker_perms.write.denied_list, main_perms.write.denied_list);
   }
 
+  #[test]
+  fn test_check_flag_denied() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_env: Some(vec![]),
+      allow_net: Some(vec![]),
+      allow_read: Some(vec![]),
+      allow_run: Some(svec!["deno", "curl"]),
+      deny_env: Some(svec!["SECRET"]),
+      deny_net: Some(svec!["metadata.internal", "127.0.0.1:8000"]),
+      deny_read: Some(vec![PathBuf::from("/etc")]),
+      deny_run: Some(svec!["curl"]),
+      deny_write: Some(vec![]),
+      prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+
+    assert!(perms.read.check(Path::new("/etc/passwd"), None).is_err());
+    assert!(perms.read.check(Path::new("/etcetera"), None).is_ok());
+    assert!(perms.read.check(Path::new("/home"), None).is_ok());
+    assert!(perms.read.check_all(None).is_err());
+    // the flag denials can't be prompted or revoked away
+    assert_eq!(
+      perms.read.request(Some(Path::new("/etc"))),
+      PermissionState::Denied
+    );
+    assert_eq!(
+      perms.read.revoke(Some(Path::new("/etc"))),
+      PermissionState::Denied
+    );
+    assert_eq!(perms.read.query(None), PermissionState::Denied);
+
+    assert!(perms.write.check(Path::new("/tmp/foo"), None).is_err());
+    assert_eq!(perms.write.request(None), PermissionState::Denied);
+
+    assert!(perms
+      .net
+      .check(&("metadata.internal", Some(80)), None)
+      .is_err());
+    assert!(perms.net.check(&("127.0.0.1", Some(8000)), None).is_err());
+    assert!(perms.net.check(&("127.0.0.1", Some(8001)), None).is_ok());
+    assert!(perms
+      .net
+      .check_url(&Url::parse("http://metadata.internal/").unwrap(), None)
+      .is_err());
+    assert!(perms
+      .net
+      .check_url(&Url::parse("https://deno.land/").unwrap(), None)
+      .is_ok());
+    assert_eq!(
+      perms.net.query(Some(&("127.0.0.1", None))),
+      PermissionState::Denied
+    );
+
+    assert!(perms.env.check("SECRET").is_err());
+    assert!(perms.env.check("HOME").is_ok());
+    assert!(perms.env.check_all().is_err());
+
+    assert!(perms.run.check("curl", None).is_err());
+    assert!(perms.run.check("deno", None).is_ok());
+  }
+
+  #[test]
+  fn test_check_flag_denied_run_executable() {
+    set_prompter(Box::new(TestPrompter));
+    let temp_dir = test_util::TempDir::new();
+    temp_dir.create_dir_all("bin");
+    temp_dir.write("bin/tool", "");
+    temp_dir.write("bin/other", "");
+    let tool = temp_dir.path().join("bin/tool");
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_run: Some(vec![]),
+      deny_run: Some(vec![tool.to_string_lossy().to_string()]),
+      ..Default::default()
+    })
+    .unwrap();
+
+    assert!(perms.run.check(&tool.to_string_lossy(), None).is_err());
+    let relative = temp_dir.path().join("bin/../bin/tool");
+    assert!(perms.run.check(&relative.to_string_lossy(), None).is_err());
+    let other = temp_dir.path().join("bin/other");
+    assert!(perms.run.check(&other.to_string_lossy(), None).is_ok());
+    #[cfg(unix)]
+    {
+      temp_dir.symlink_file("bin/tool", "link");
+      let link = temp_dir.path().join("link");
+      assert!(perms.run.check(&link.to_string_lossy(), None).is_err());
+    }
+  }
+
+  #[test]
+  fn test_create_child_permissions_with_flag_denials() {
+    set_prompter(Box::new(TestPrompter));
+    let mut main_perms = Permissions::from_options(&PermissionsOptions {
+      allow_read: Some(vec![]),
+      deny_read: Some(vec![PathBuf::from("/etc")]),
+      ..Default::default()
+    })
+    .unwrap();
+    let mut worker_perms = create_child_permissions(
+      &mut main_perms.clone(),
+      ChildPermissionsArg {
+        read: ChildUnaryPermissionArg::Granted,
+        ..ChildPermissionsArg::none()
+      },
+    )
+    .unwrap();
+    assert_eq!(worker_perms.read, main_perms.read);
+    assert!(worker_perms.read.check(Path::new("/home"), None).is_ok());
+    assert!(worker_perms
+      .read
+      .check(Path::new("/etc/hosts"), None)
+      .is_err());
+    assert!(create_child_permissions(
+      &mut main_perms,
+      ChildPermissionsArg {
+        read: ChildUnaryPermissionArg::GrantedList(svec!["/etc"]),
+        ..ChildPermissionsArg::none()
+      }
+    )
+    .is_err());
+  }
+
   #[test]
   fn test_handle_empty_value() {
     set_prompter(Box::new(TestPrompter));
@@ -0,0 +1,295 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! The CPUs and memory that the process may use. On Linux, the limits of the
+//! cgroup (v1 or v2) of the process are taken into account, so that the
+//! defaults which are derived from them don't over-subscribe a container
+//! that is limited to, say, half a CPU and 256MB of memory.
+
+use deno_core::v8;
+use once_cell::sync::Lazy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+  /// How many CPUs can be used at the same time, rounded up.
+  pub cpu_count: usize,
+  /// How much memory can be used, in bytes, if it is limited.
+  pub memory_limit: Option<u64>,
+}
+
+static RESOURCE_LIMITS: Lazy<ResourceLimits> = Lazy::new(|| {
+  let available_parallelism = std::thread::available_parallelism()
+    .map(|p| p.get())
+    .unwrap_or(1);
+  #[cfg(target_os = "linux")]
+  {
+    let cgroup = linux::CgroupLimits::read();
+    ResourceLimits {
+      cpu_count: match cgroup.cpu_quota {
+        Some(quota) => available_parallelism.min(cpu_count_for_quota(quota)),
+        None => available_parallelism,
+      },
+      memory_limit: cgroup.memory_limit,
+    }
+  }
+  #[cfg(not(target_os = "linux"))]
+  {
+    ResourceLimits {
+      cpu_count: available_parallelism,
+      memory_limit: None,
+    }
+  }
+});
+
+/// The limits of the current process, which are detected once.
+pub fn resource_limits() -> ResourceLimits {
+  *RESOURCE_LIMITS
+}
+
+/// The number of threads of the blocking thread pool of tokio, which runs
+/// the synchronous fs ops and the CPU bound tasks.
+pub fn max_blocking_threads() -> usize {
+  // we don't ever want to have more than a couple dozen threads
+  (resource_limits().cpu_count * 8).clamp(8, 32)
+}
+
+/// The parameters of the main isolate, whose heap limits are derived from
+/// the memory limit like V8 does it from the physical memory. The limits that
+/// are given with `--v8-flags` still take precedence.
+pub fn v8_create_params() -> Option<v8::CreateParams> {
+  let memory_limit = resource_limits().memory_limit?;
+  Some(
+    v8::CreateParams::default().heap_limits_from_system_memory(memory_limit, 0),
+  )
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn cpu_count_for_quota(quota: f64) -> usize {
+  (quota.ceil() as usize).max(1)
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+  use std::path::Path;
+
+  const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+  #[derive(Debug, Default, PartialEq)]
+  pub struct CgroupLimits {
+    /// The number of CPUs that the cgroup may use, like `0.5`.
+    pub cpu_quota: Option<f64>,
+    pub memory_limit: Option<u64>,
+  }
+
+  /// The cgroups of the process, as `/proc/self/cgroup` lists them.
+  #[derive(Debug, Default, PartialEq)]
+  pub struct CgroupPaths {
+    pub v2: Option<String>,
+    pub cpu_v1: Option<String>,
+    pub memory_v1: Option<String>,
+  }
+
+  impl CgroupPaths {
+    pub fn parse(text: &str) -> Self {
+      let mut paths = Self::default();
+      for line in text.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) =
+          (parts.next(), parts.next(), parts.next())
+        else {
+          continue;
+        };
+        if controllers.is_empty() {
+          paths.v2 = Some(path.to_string());
+          continue;
+        }
+        for controller in controllers.split(',') {
+          match controller {
+            "cpu" => paths.cpu_v1 = Some(path.to_string()),
+            "memory" => paths.memory_v1 = Some(path.to_string()),
+            _ => {}
+          }
+        }
+      }
+      paths
+    }
+  }
+
+  impl CgroupLimits {
+    pub fn read() -> Self {
+      let Ok(text) = std::fs::read_to_string("/proc/self/cgroup") else {
+        return Self::default();
+      };
+      let paths = CgroupPaths::parse(&text);
+      let root = Path::new(CGROUP_ROOT);
+      let mut limits = Self::default();
+      if let Some(path) = &paths.v2 {
+        limits.cpu_quota = read_min(root, path, |dir| {
+          parse_cpu_max(&read_file(dir, "cpu.max")?)
+        });
+        limits.memory_limit = read_min(root, path, |dir| {
+          parse_memory_max(&read_file(dir, "memory.max")?)
+        });
+      }
+      if let (None, Some(path)) = (limits.cpu_quota, &paths.cpu_v1) {
+        limits.cpu_quota = ["cpu,cpuacct", "cpu"].iter().find_map(|name| {
+          read_min(&root.join(name), path, |dir| {
+            parse_cfs_quota(
+              &read_file(dir, "cpu.cfs_quota_us")?,
+              &read_file(dir, "cpu.cfs_period_us")?,
+            )
+          })
+        });
+      }
+      if let (None, Some(path)) = (limits.memory_limit, &paths.memory_v1) {
+        limits.memory_limit = read_min(&root.join("memory"), path, |dir| {
+          parse_memory_max(&read_file(dir, "memory.limit_in_bytes")?)
+        });
+      }
+      limits
+    }
+  }
+
+  fn read_file(dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(name)).ok()
+  }
+
+  /// Reads a limit from the cgroup at `path` and its ancestors, and returns
+  /// the lowest one. The cgroup of the process might not be visible in a
+  /// container, in which case the root of the hierarchy is the one of the
+  /// container.
+  fn read_min<T: PartialOrd>(
+    root: &Path,
+    path: &str,
+    read: impl Fn(&Path) -> Option<T>,
+  ) -> Option<T> {
+    let mut dir = root.join(path.trim_start_matches('/'));
+    if !dir.starts_with(root) || !dir.is_dir() {
+      dir = root.to_path_buf();
+    }
+    let mut min: Option<T> = None;
+    loop {
+      if let Some(value) = read(&dir) {
+        if min.as_ref().map(|min| value < *min).unwrap_or(true) {
+          min = Some(value);
+        }
+      }
+      if dir == root || !dir.pop() {
+        return min;
+      }
+    }
+  }
+
+  /// Parses the `cpu.max` file of cgroup v2, like `50000 100000`.
+  pub fn parse_cpu_max(text: &str) -> Option<f64> {
+    let mut parts = text.split_whitespace();
+    let quota = parts.next()?;
+    let period = parts.next().unwrap_or("100000");
+    if quota == "max" {
+      return None;
+    }
+    parse_cfs_quota(quota, period)
+  }
+
+  /// Parses the `cpu.cfs_quota_us` and `cpu.cfs_period_us` files of cgroup
+  /// v1, where a quota of `-1` means that there is no limit.
+  pub fn parse_cfs_quota(quota: &str, period: &str) -> Option<f64> {
+    let quota = quota.trim().parse::<i64>().ok()?;
+    let period = period.trim().parse::<i64>().ok()?;
+    if quota <= 0 || period <= 0 {
+      return None;
+    }
+    Some(quota as f64 / period as f64)
+  }
+
+  /// Parses the `memory.max` file of cgroup v2, or `memory.limit_in_bytes`
+  /// of cgroup v1, which is a huge number when there is no limit.
+  pub fn parse_memory_max(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if text == "max" {
+      return None;
+    }
+    let limit = text.parse::<u64>().ok()?;
+    // cgroup v1 reports a page-aligned i64::MAX without a limit
+    if limit >= (i64::MAX as u64) & !0xfff {
+      return None;
+    }
+    Some(limit)
+  }
+
+  #[cfg(test)]
+  mod tests {
+    use super::*;
+
+    #[test]
+    fn cgroup_paths() {
+      assert_eq!(
+        CgroupPaths::parse("0::/user.slice/session-1.scope\n"),
+        CgroupPaths {
+          v2: Some("/user.slice/session-1.scope".to_string()),
+          ..Default::default()
+        }
+      );
+      assert_eq!(
+        CgroupPaths::parse(
+          "12:memory:/docker/abc\n4:cpu,cpuacct:/docker/abc\n1:name=systemd:/docker/abc\n"
+        ),
+        CgroupPaths {
+          v2: None,
+          cpu_v1: Some("/docker/abc".to_string()),
+          memory_v1: Some("/docker/abc".to_string()),
+        }
+      );
+    }
+
+    #[test]
+    fn cpu_limits() {
+      assert_eq!(parse_cpu_max("max 100000\n"), None);
+      assert_eq!(parse_cpu_max("50000 100000\n"), Some(0.5));
+      assert_eq!(parse_cpu_max("200000 100000\n"), Some(2.0));
+      assert_eq!(parse_cfs_quota("-1\n", "100000\n"), None);
+      assert_eq!(parse_cfs_quota("150000\n", "100000\n"), Some(1.5));
+    }
+
+    #[test]
+    fn memory_limits() {
+      assert_eq!(parse_memory_max("max\n"), None);
+      assert_eq!(parse_memory_max("268435456\n"), Some(268435456));
+      assert_eq!(parse_memory_max("9223372036854771712\n"), None);
+    }
+
+    #[test]
+    fn read_min_of_ancestors() {
+      let temp_dir = std::env::temp_dir()
+        .join(format!("deno_cgroup_test_{}", std::process::id()));
+      let leaf = temp_dir.join("a/b");
+      std::fs::create_dir_all(&leaf).unwrap();
+      std::fs::write(temp_dir.join("a/memory.max"), "1000\n").unwrap();
+      std::fs::write(leaf.join("memory.max"), "max\n").unwrap();
+      let read_memory_max =
+        |dir: &Path| parse_memory_max(&read_file(dir, "memory.max")?);
+      assert_eq!(read_min(&temp_dir, "/a/b", read_memory_max), Some(1000));
+      // a cgroup that isn't visible falls back to the root
+      std::fs::write(temp_dir.join("memory.max"), "2000\n").unwrap();
+      assert_eq!(
+        read_min(&temp_dir, "/docker/abc", read_memory_max),
+        Some(2000)
+      );
+      std::fs::remove_dir_all(&temp_dir).unwrap();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn cpu_count() {
+    assert_eq!(cpu_count_for_quota(0.5), 1);
+    assert_eq!(cpu_count_for_quota(1.0), 1);
+    assert_eq!(cpu_count_for_quota(2.5), 3);
+    let limits = resource_limits();
+    assert!(limits.cpu_count >= 1);
+    assert!((8..=32).contains(&max_blocking_threads()));
+  }
+}
@@ -4,6 +4,8 @@ use std::str::FromStr;
 
 use deno_core::task::MaskFutureAsSend;
 
+use crate::resource_limits;
+
 /// Default configuration for tokio. In the future, this method may have different defaults
 /// depending on the platform and/or CPU layout.
 const fn tokio_configuration() -> (u32, u32, usize) {
@@ -39,9 +41,9 @@ pub fn create_basic_runtime() -> tokio::runtime::Runtime {
     // This limits the number of threads for blocking operations (like for
     // synchronous fs ops) or CPU bound tasks like when we run dprint in
     // parallel for deno fmt.
-    // The default value is 512, which is an unhelpfully large thread pool. We
-    // don't ever want to have more than a couple dozen threads.
-    .max_blocking_threads(32)
+    // The default value is 512, which is an unhelpfully large thread pool,
+    // so it is sized after the CPUs that the process may use.
+    .max_blocking_threads(resource_limits::max_blocking_threads())
     .build()
     .unwrap()
 }
@@ -7,7 +7,7 @@ use crate::tokio_util::create_and_run_current_thread;
 use crate::worker::init_runtime_module_map;
 use crate::worker::FormatJsErrorFn;
 use crate::BootstrapOptions;
-use deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_broadcast_channel::RuntimeBroadcastChannel;
 use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
 use deno_core::ascii_str;
@@ -233,6 +233,11 @@ pub struct WebWorkerHandle {
 }
 
 impl WebWorkerHandle {
+  /// Check if this worker is terminated or being terminated
+  pub fn is_terminated(&self) -> bool {
+    self.has_terminated.load(Ordering::SeqCst)
+  }
+
   /// Get the WorkerEvent with lock
   /// Return error if more than one listener tries to get event
   pub async fn get_control_event(
@@ -336,6 +341,9 @@ pub struct WebWorkerOptions {
   pub fs: Arc<dyn FileSystem>,
   pub module_loader: Rc<dyn ModuleLoader>,
   pub npm_resolver: Option<Arc<dyn deno_node::NpmResolver>>,
+  /// Custom conditions used by `node:module` when resolving the "exports"
+  /// and "imports" of npm packages.
+  pub node_conditions: Vec<String>,
   pub create_web_worker_cb: Arc<ops::worker_host::CreateWebWorkerCb>,
   pub preload_module_cb: Arc<ops::worker_host::WorkerEventCb>,
   pub pre_execute_module_cb: Arc<ops::worker_host::WorkerEventCb>,
@@ -345,7 +353,7 @@ pub struct WebWorkerOptions {
   pub maybe_inspector_server: Option<Arc<InspectorServer>>,
   pub get_error_class_fn: Option<GetErrorClassFn>,
   pub blob_store: BlobStore,
-  pub broadcast_channel: InMemoryBroadcastChannel,
+  pub broadcast_channel: RuntimeBroadcastChannel,
   pub shared_array_buffer_store: Option<SharedArrayBufferStore>,
   pub compiled_wasm_module_store: Option<CompiledWasmModuleStore>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
@@ -405,6 +413,7 @@ impl WebWorker {
       deno_web::deno_web::init_ops::<PermissionsContainer>(
         options.blob_store.clone(),
         Some(main_module.clone()),
+        unstable,
       ),
       deno_fetch::deno_fetch::init_ops::<PermissionsContainer>(
         deno_fetch::Options {
@@ -450,6 +459,7 @@ impl WebWorker {
       deno_node::deno_node::init_ops::<PermissionsContainer>(
         options.npm_resolver,
         options.fs,
+        options.node_conditions,
       ),
       // Runtime ops that are always initialized for WebWorkers
       ops::web_worker::deno_web_worker::init_ops(),
@@ -8,7 +8,7 @@ use std::sync::Arc;
 use std::task::Context;
 use std::task::Poll;
 
-use deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_broadcast_channel::RuntimeBroadcastChannel;
 use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
 use deno_core::ascii_str;
@@ -111,6 +111,9 @@ pub struct WorkerOptions {
   /// executed tries to load modules.
   pub module_loader: Rc<dyn ModuleLoader>,
   pub npm_resolver: Option<Arc<dyn deno_node::NpmResolver>>,
+  /// Custom conditions used by `node:module` when resolving the "exports"
+  /// and "imports" of npm packages.
+  pub node_conditions: Vec<String>,
   // Callbacks invoked when creating new instance of WebWorker
   pub create_web_worker_cb: Arc<ops::worker_host::CreateWebWorkerCb>,
   pub web_worker_preload_module_cb: Arc<ops::worker_host::WorkerEventCb>,
@@ -134,7 +137,7 @@ pub struct WorkerOptions {
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub origin_storage_dir: Option<std::path::PathBuf>,
   pub blob_store: BlobStore,
-  pub broadcast_channel: InMemoryBroadcastChannel,
+  pub broadcast_channel: RuntimeBroadcastChannel,
 
   /// The store to use for transferring SharedArrayBuffers between isolates.
   /// If multiple isolates should have the possibility of sharing
@@ -182,6 +185,7 @@ impl Default for WorkerOptions {
       source_map_getter: Default::default(),
       root_cert_store_provider: Default::default(),
       npm_resolver: Default::default(),
+      node_conditions: Default::default(),
       blob_store: Default::default(),
       extensions: Default::default(),
       startup_snapshot: Default::default(),
@@ -241,6 +245,7 @@ impl MainWorker {
       deno_web::deno_web::init_ops::<PermissionsContainer>(
         options.blob_store.clone(),
         options.bootstrap.location.clone(),
+        unstable,
       ),
       deno_fetch::deno_fetch::init_ops::<PermissionsContainer>(
         deno_fetch::Options {
@@ -290,6 +295,7 @@ impl MainWorker {
       deno_node::deno_node::init_ops::<PermissionsContainer>(
         options.npm_resolver,
         options.fs,
+        options.node_conditions,
       ),
       // Ops from this crate
       ops::runtime::deno_runtime::init_ops(main_module.clone()),
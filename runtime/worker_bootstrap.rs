@@ -2,9 +2,9 @@
 
 use deno_core::v8;
 use deno_core::ModuleSpecifier;
-use std::thread;
 
 use crate::colors;
+use crate::resource_limits::resource_limits;
 
 /// The log level to use when printing diagnostic log messages, warnings,
 /// or errors in the worker.
@@ -59,9 +59,7 @@ pub struct BootstrapOptions {
 
 impl Default for BootstrapOptions {
   fn default() -> Self {
-    let cpu_count = thread::available_parallelism()
-      .map(|p| p.get())
-      .unwrap_or(1);
+    let cpu_count = resource_limits().cpu_count;
 
     let runtime_version = env!("CARGO_PKG_VERSION").into();
     let user_agent = format!("Deno/{runtime_version}");
@@ -234,3 +234,43 @@ fn event_loop_integration() {
   assert_eq!(stdout, expected);
   assert_eq!(stderr, "");
 }
+
+#[test]
+fn symbol_allowlist() {
+  build();
+
+  let temp_dir = test_util::TempDir::new();
+  temp_dir.write(
+    "ffi_symbols.json",
+    format!("{{ {:?}: [\"add_u32\"] }}", test_util::target_dir()),
+  );
+  let output = deno_cmd()
+    .arg("run")
+    .arg("--allow-ffi")
+    .arg(format!(
+      "--allow-ffi-symbols={}",
+      temp_dir.path().join("ffi_symbols.json").display()
+    ))
+    .arg("--allow-read")
+    .arg("--unstable")
+    .arg("--quiet")
+    .arg("tests/symbol_allowlist_test.js")
+    .env("NO_COLOR", "1")
+    .output()
+    .unwrap();
+  let stdout = std::str::from_utf8(&output.stdout).unwrap();
+  let stderr = std::str::from_utf8(&output.stderr).unwrap();
+  if !output.status.success() {
+    println!("stdout {stdout}");
+    println!("stderr {stderr}");
+  }
+  println!("{:?}", output.status);
+  assert!(output.status.success());
+  let expected = "\
+    3\n\
+    true\n\
+    true\n\
+    true\n";
+  assert_eq!(stdout, expected);
+  assert_eq!(stderr, "");
+}
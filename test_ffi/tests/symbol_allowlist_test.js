@@ -0,0 +1,29 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+const targetDir = Deno.execPath().replace(/[^\/\\]+$/, "");
+const [libPrefix, libSuffix] = {
+  darwin: ["lib", "dylib"],
+  linux: ["lib", "so"],
+  windows: ["", "dll"],
+}[Deno.build.os];
+const libPath = `${targetDir}/${libPrefix}test_ffi.${libSuffix}`;
+
+const dylib = Deno.dlopen(libPath, {
+  add_u32: { parameters: ["u32", "u32"], result: "u32" },
+});
+console.log(dylib.symbols.add_u32(1, 2));
+dylib.close();
+
+for (
+  const symbols of [
+    { add_i32: { parameters: ["i32", "i32"], result: "i32" } },
+    { add: { name: "add_i32", parameters: ["i32", "i32"], result: "i32" } },
+    { static_u32: { type: "u32" } },
+  ]
+) {
+  try {
+    Deno.dlopen(libPath, symbols);
+  } catch (e) {
+    console.log(e instanceof Deno.errors.PermissionDenied);
+  }
+}
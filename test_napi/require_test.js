@@ -0,0 +1,29 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+import { assert, assertEquals, libPrefix, libSuffix } from "./common.js";
+import { createRequire } from "node:module";
+import process from "node:process";
+
+const targetDir = Deno.execPath().replace(/[^\/\\]+$/, "");
+const libPath = `${targetDir}/${libPrefix}test_napi.${libSuffix}`;
+
+Deno.test("napi require .node addon", function () {
+  const dir = Deno.makeTempDirSync();
+  try {
+    const addonPath = `${dir}/test_napi.node`;
+    Deno.copyFileSync(libPath, addonPath);
+    const require = createRequire(`${dir}/index.js`);
+    const addon = require("./test_napi.node");
+    assertEquals(addon.test_utf8("🦕"), "🦕");
+    // the module is cached like any other
+    assert(require("./test_napi") === addon);
+  } finally {
+    Deno.removeSync(dir, { recursive: true });
+  }
+});
+
+Deno.test("napi process.dlopen", function () {
+  const module = { exports: {} };
+  process.dlopen(module, libPath);
+  assertEquals(module.exports.test_utf16("🦕"), "🦕");
+});